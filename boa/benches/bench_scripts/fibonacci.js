@@ -0,0 +1,8 @@
+function fib(n) {
+  if (n <= 1) {
+    return 1;
+  }
+  return fib(n - 1) + fib(n - 2);
+}
+
+fib(15);
@@ -0,0 +1,8 @@
+let data = [];
+for (let i = 0; i < 100; i++) {
+  data.push({ id: i, name: "entry " + i, tags: ["a", "b", "c"], active: true, parent: null });
+}
+
+let text = JSON.stringify(data);
+let copy = JSON.parse(text);
+JSON.stringify(copy).length;
@@ -0,0 +1,11 @@
+//! The scripts of the representative workloads, shared by the benchmarks of each stage of the
+//! engine.
+
+/// The name and the source of each workload.
+pub(crate) static WORKLOADS: &[(&str, &str)] = &[
+    ("Fibonacci", include_str!("fibonacci.js")),
+    ("String building", include_str!("string_building.js")),
+    ("Object churn", include_str!("object_churn.js")),
+    ("JSON round trip", include_str!("json_round_trip.js")),
+    ("RegExp matching", include_str!("regexp.js")),
+];
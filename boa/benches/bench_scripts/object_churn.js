@@ -0,0 +1,15 @@
+function Point(x, y) {
+  this.x = x;
+  this.y = y;
+}
+
+let total = 0;
+for (let i = 0; i < 500; i++) {
+  let point = new Point(i, i * 2);
+  let bag = { a: i, b: "b", c: [i, i + 1] };
+  bag.d = point;
+  delete bag.b;
+  total += bag.d.x + bag.d.y + bag.c[1];
+}
+
+total;
@@ -0,0 +1,18 @@
+let lines = [];
+for (let i = 0; i < 100; i++) {
+  lines.push("user" + i + "@example" + (i % 7) + ".com, id: " + i * 31);
+}
+
+let email = /^([a-z0-9]+)@([a-z0-9]+)\.com/;
+let id = /id: ([0-9]+)$/;
+let matched = 0;
+for (let i = 0; i < lines.length; i++) {
+  let line = lines[i];
+  if (email.test(line)) {
+    matched += 1;
+  }
+  matched += line.match(id)[1].length;
+  lines[i] = line.replace(email, "$2");
+}
+
+matched;
@@ -0,0 +1,9 @@
+let parts = [];
+let text = "";
+for (let i = 0; i < 500; i++) {
+  let word = "item" + i;
+  text += word.toUpperCase() + ",";
+  parts.push(word.slice(0, 4));
+}
+
+text.length + parts.join("-").length;
@@ -1,7 +1,15 @@
 //! Benchmarks of the whole execution engine in Boa.
 
-use boa::{exec, realm::Realm};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+mod bench_scripts;
+
+use bench_scripts::WORKLOADS;
+use boa::{
+    exec, parse, realm::Realm, syntax::optimizer::OptimizerOptions, Executable, Interpreter,
+};
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::Measurement, BatchSize,
+    BenchmarkGroup, BenchmarkId, Criterion, Throughput,
+};
 
 #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
 #[cfg_attr(
@@ -65,11 +73,185 @@ fn fibonacci(c: &mut Criterion) {
     });
 }
 
+/// Builds a string with `s += chunk`, as many times as given.
+///
+/// The throughput is reported in appended chunks, so it stays the same for every number of chunks
+/// if appending to a string takes constant time.
+fn string_concatenation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("String concatenation (Execution)");
+    for &chunks in &[1_000, 2_000, 4_000, 8_000] {
+        let src = format!(
+            r#"
+let s = "";
+for (let i = 0; i < {}; i++) {{
+    s += "chunk";
+}}
+
+s.length
+"#,
+            chunks
+        );
+
+        group.throughput(Throughput::Elements(chunks));
+        group.bench_with_input(BenchmarkId::from_parameter(chunks), &src, |b, src| {
+            b.iter(|| exec(black_box(src)))
+        });
+    }
+    group.finish();
+}
+
+/// Benchmarks a script, parsed beforehand, so that only the interpreter is measured.
+///
+/// Each run gets a new realm, whose creation isn't measured either.
+fn bench_execution<M: Measurement>(group: &mut BenchmarkGroup<'_, M>, name: &str, src: &str) {
+    let script = parse(src.as_bytes(), OptimizerOptions::empty()).expect("failed to parse");
+
+    group.bench_function(name, move |b| {
+        b.iter_batched(
+            || Interpreter::new(Realm::create()),
+            |mut engine| {
+                let result = script.run(&mut engine);
+                // The engine is returned so that dropping it isn't measured.
+                (engine, result)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Runs the workloads.
+fn workloads_execution(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Workloads (Execution)");
+    for &(name, src) in WORKLOADS {
+        bench_execution(&mut group, name, src);
+    }
+    group.finish();
+}
+
+/// The micro-benchmarks of the property accesses, each reading a kind of binding or property in
+/// a loop, from a function.
+static PROPERTY_ACCESS: &[(&str, &str)] = &[
+    (
+        "Global variable",
+        r#"
+var total = 1;
+function read() {
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        sum += total;
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+    (
+        "Global builtin",
+        r#"
+function read() {
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        sum += Math.PI;
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+    (
+        "Local variable",
+        r#"
+function read() {
+    var a = 1, b = 2, c = 3, d = 4, e = 5, f = 6, g = 7, h = 8, k = 9;
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        sum += a + k;
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+    (
+        "Closure variable",
+        r#"
+function read() {
+    let total = 1;
+    return function () {
+        let sum = 0;
+        for (let i = 0; i < 1000; i++) {
+            sum += total;
+        }
+        return sum;
+    };
+}
+read()();
+"#,
+    ),
+    (
+        "Own property",
+        r#"
+function read() {
+    let point = { x: 1, y: 2 };
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        sum += point.x + point.y;
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+    (
+        "Computed property",
+        r#"
+function read() {
+    let point = { x: 1, y: 2 };
+    let keys = ["x", "y"];
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        sum += point[keys[i % 2]];
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+    (
+        "Prototype property",
+        r#"
+function read() {
+    let point = { x: 1, y: 2 };
+    let sum = 0;
+    for (let i = 0; i < 1000; i++) {
+        if (point.hasOwnProperty) {
+            sum++;
+        }
+    }
+    return sum;
+}
+read();
+"#,
+    ),
+];
+
+/// Runs the property access micro-benchmarks.
+fn property_access(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Property access (Execution)");
+    for &(name, src) in PROPERTY_ACCESS {
+        bench_execution(&mut group, name, src);
+    }
+    group.finish();
+}
+
 criterion_group!(
     execution,
     create_realm,
     symbol_creation,
     for_loop_execution,
-    fibonacci
+    fibonacci,
+    string_concatenation,
+    workloads_execution,
+    property_access
 );
 criterion_main!(execution);
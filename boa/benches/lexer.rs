@@ -1,5 +1,8 @@
 //! Benchmarks of the lexing process in Boa.
 
+mod bench_scripts;
+
+use bench_scripts::WORKLOADS;
 use boa::syntax::lexer::Lexer;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -17,7 +20,7 @@ static EXPRESSION: &str = r#"
 fn expression_lexer(c: &mut Criterion) {
     c.bench_function("Expression (Lexer)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(EXPRESSION));
+            let mut lexer = Lexer::new(black_box(EXPRESSION.as_bytes()));
 
             lexer.lex()
         })
@@ -29,7 +32,7 @@ static HELLO_WORLD: &str = "let foo = 'hello world!'; foo;";
 fn hello_world_lexer(c: &mut Criterion) {
     c.bench_function("Hello World (Lexer)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(HELLO_WORLD));
+            let mut lexer = Lexer::new(black_box(HELLO_WORLD.as_bytes()));
             // return the value into the blackbox so its not optimized away
             // https://gist.github.com/jasonwilliams/5325da61a794d8211dcab846d466c4fd
             lexer.lex()
@@ -52,12 +55,32 @@ for (let a = 10; a < 100; a++) {
 fn for_loop_lexer(c: &mut Criterion) {
     c.bench_function("For loop (Lexer)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(FOR_LOOP));
+            let mut lexer = Lexer::new(black_box(FOR_LOOP.as_bytes()));
 
             lexer.lex()
         })
     });
 }
 
-criterion_group!(lexer, expression_lexer, hello_world_lexer, for_loop_lexer);
+fn workloads_lexer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Workloads (Lexer)");
+    for &(name, src) in WORKLOADS {
+        group.bench_function(name, move |b| {
+            b.iter(|| {
+                let mut lexer = Lexer::new(black_box(src.as_bytes()));
+
+                lexer.lex()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    lexer,
+    expression_lexer,
+    hello_world_lexer,
+    for_loop_lexer,
+    workloads_lexer
+);
 criterion_main!(lexer);
@@ -1,5 +1,8 @@
 //! Benchmarks of the parsing process in Boa.
 
+mod bench_scripts;
+
+use bench_scripts::WORKLOADS;
 use boa::syntax::{lexer::Lexer, parser::Parser};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
@@ -19,7 +22,7 @@ fn expression_parser(c: &mut Criterion) {
 
     c.bench_function("Expression (Parser)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(EXPRESSION));
+            let mut lexer = Lexer::new(black_box(EXPRESSION.as_bytes()));
             lexer.lex().expect("failed to lex");
 
             Parser::new(&black_box(lexer.tokens)).parse_all()
@@ -34,7 +37,7 @@ fn hello_world_parser(c: &mut Criterion) {
 
     c.bench_function("Hello World (Parser)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(HELLO_WORLD));
+            let mut lexer = Lexer::new(black_box(HELLO_WORLD.as_bytes()));
             lexer.lex().expect("failed to lex");
 
             Parser::new(&black_box(lexer.tokens)).parse_all()
@@ -59,7 +62,33 @@ fn for_loop_parser(c: &mut Criterion) {
 
     c.bench_function("For loop (Parser)", move |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(FOR_LOOP));
+            let mut lexer = Lexer::new(black_box(FOR_LOOP.as_bytes()));
+            lexer.lex().expect("failed to lex");
+
+            Parser::new(&black_box(lexer.tokens)).parse_all()
+        })
+    });
+}
+
+static LONG_FILE_CHUNK: &str = r#"
+function compute(a, b) {
+    let result = a * b + (a - b) / 2;
+    if (result > 100 && a !== b) {
+        result = result % 7 ? -result : result;
+    } else {
+        while (result < 10) result = result + a[b] + obj.field;
+    }
+    return result;
+}
+"#;
+
+fn long_file_parser(c: &mut Criterion) {
+    // We include the lexing in the benchmarks, since they will get together soon, anyways.
+    let long_file = LONG_FILE_CHUNK.repeat(500);
+
+    c.bench_function("Long file (Parser)", move |b| {
+        b.iter(|| {
+            let mut lexer = Lexer::new(black_box(long_file.as_bytes()));
             lexer.lex().expect("failed to lex");
 
             Parser::new(&black_box(lexer.tokens)).parse_all()
@@ -67,10 +96,27 @@ fn for_loop_parser(c: &mut Criterion) {
     });
 }
 
+fn workloads_parser(c: &mut Criterion) {
+    // The tokens are lexed beforehand, so that only the parsing is measured.
+    let mut group = c.benchmark_group("Workloads (Parser)");
+    for &(name, src) in WORKLOADS {
+        let mut lexer = Lexer::new(src.as_bytes());
+        lexer.lex().expect("failed to lex");
+        let tokens = lexer.tokens;
+
+        group.bench_function(name, move |b| {
+            b.iter(|| Parser::new(black_box(&tokens)).parse_all())
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     parser,
     expression_parser,
     hello_world_parser,
-    for_loop_parser
+    for_loop_parser,
+    long_file_parser,
+    workloads_parser
 );
 criterion_main!(parser);
@@ -0,0 +1,147 @@
+//! This module implements the array iterators, returned by the `values`, `keys` and `entries`
+//! methods of arrays.
+//!
+//! An array iterator reads the `length` of the iterated object at each step, so it sees the
+//! elements appended during the iteration. It works on any array-like object.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-array-iterator-objects
+
+use super::Array;
+use crate::{
+    builtins::{
+        function::make_builtin_fn,
+        intrinsics::{self, Intrinsic},
+        iterable::create_iter_result_object,
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+
+/// The internal slot holding the object iterated by an array iterator, which is `undefined`
+/// once the iterator is done.
+const ITERATED_ARRAY_LIKE: &str = "IteratedArrayLike";
+
+/// The internal slot holding the index of the next element produced by an array iterator.
+const ARRAY_LIKE_NEXT_INDEX: &str = "ArrayLikeNextIndex";
+
+/// The internal slot holding the kind of the values produced by an array iterator.
+const ARRAY_LIKE_ITERATION_KIND: &str = "ArrayLikeIterationKind";
+
+/// The kind of the values produced by an array iterator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ArrayIterationKind {
+    /// The indices of the elements, produced by `keys`.
+    Key,
+    /// The elements, produced by `values`.
+    Value,
+    /// Arrays with the index and the element, produced by `entries`.
+    KeyAndValue,
+}
+
+impl ArrayIterationKind {
+    /// Gets the name of the kind, as it is stored in the internal slot of an iterator.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Key => "key",
+            Self::Value => "value",
+            Self::KeyAndValue => "key+value",
+        }
+    }
+
+    /// Gets the kind from its name.
+    fn from_name(name: &str) -> Self {
+        match name {
+            "key" => Self::Key,
+            "value" => Self::Value,
+            _ => Self::KeyAndValue,
+        }
+    }
+}
+
+/// `ArrayIterator` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArrayIterator;
+
+impl ArrayIterator {
+    /// Creates a new iterator over an array-like object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createarrayiterator
+    pub(crate) fn create_array_iterator(
+        ctx: &Interpreter,
+        array: Value,
+        kind: ArrayIterationKind,
+    ) -> Value {
+        let prototype = ctx.realm().intrinsic(Intrinsic::ArrayIteratorPrototype);
+        let iterator = Value::new_object_from_prototype(prototype, ObjectKind::Ordinary);
+        iterator.set_internal_slot(ITERATED_ARRAY_LIKE, array);
+        iterator.set_internal_slot(ARRAY_LIKE_NEXT_INDEX, Value::from(0));
+        iterator.set_internal_slot(ARRAY_LIKE_ITERATION_KIND, Value::from(kind.as_str()));
+        iterator
+    }
+
+    /// `%ArrayIteratorPrototype%.next( )`
+    ///
+    /// Returns the next value of the iteration, or a result which is `done` once all the
+    /// elements of the array have been produced.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-%arrayiteratorprototype%.next
+    pub(crate) fn next(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let is_array_iterator = match this.as_object() {
+            Some(obj) => obj.internal_slots.contains_key(ITERATED_ARRAY_LIKE),
+            None => false,
+        };
+        if !is_array_iterator {
+            return ctx.throw_type_error("'this' is not an array iterator");
+        }
+
+        let array = this.get_internal_slot(ITERATED_ARRAY_LIKE);
+        if array.is_undefined() {
+            return Ok(create_iter_result_object(ctx, Value::undefined(), true));
+        }
+
+        let index = ctx.to_number(&this.get_internal_slot(ARRAY_LIKE_NEXT_INDEX))? as usize;
+        let len = Array::length_of_array_like(&array, ctx)?;
+        if index >= len {
+            this.set_internal_slot(ITERATED_ARRAY_LIKE, Value::undefined());
+            return Ok(create_iter_result_object(ctx, Value::undefined(), true));
+        }
+        this.set_internal_slot(ARRAY_LIKE_NEXT_INDEX, Value::from(index + 1));
+
+        let kind = this
+            .get_internal_slot(ARRAY_LIKE_ITERATION_KIND)
+            .to_string();
+        let result = match ArrayIterationKind::from_name(&kind) {
+            ArrayIterationKind::Key => Value::from(index),
+            ArrayIterationKind::Value => ctx.get_field_value(&array, index)?,
+            ArrayIterationKind::KeyAndValue => {
+                let entry = Array::new_array(ctx)?;
+                let element = ctx.get_field_value(&array, index)?;
+                Array::construct_array(&entry, &[Value::from(index), element])?
+            }
+        };
+        Ok(create_iter_result_object(ctx, result, false))
+    }
+
+    /// Creates `%ArrayIteratorPrototype%`, and makes `Array.prototype.values` the
+    /// `[Symbol.iterator]` method of arrays.
+    pub(crate) fn init(global: &Value, iterator_prototype: &Value, iterator_symbol: &Value) {
+        let prototype = Value::new_object(Some(global));
+        prototype.set_internal_slot(INSTANCE_PROTOTYPE, iterator_prototype.clone());
+        make_builtin_fn(Self::next, "next", &prototype, 0);
+        intrinsics::set(global, Intrinsic::ArrayIteratorPrototype, prototype);
+
+        let array_prototype = intrinsics::get(global, Intrinsic::ArrayPrototype);
+        let values = array_prototype.get_field("values");
+        array_prototype.set_field(iterator_symbol.clone(), values);
+    }
+}
@@ -9,24 +9,27 @@
 //! [spec]: https://tc39.es/ecma262/#sec-array-objects
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
 
+pub(crate) mod array_iterator;
 #[cfg(test)]
 mod tests;
 
+use self::array_iterator::{ArrayIterationKind, ArrayIterator};
 use super::function::{make_builtin_fn, make_constructor_fn};
 use crate::{
     builtins::{
-        object::{ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
+        intrinsics::Intrinsic,
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
         property::Property,
         value::{same_value_zero, ResultValue, Value, ValueData},
     },
     exec::Interpreter,
     BoaProfiler,
 };
-use std::{
-    borrow::Borrow,
-    cmp::{max, min},
-    ops::Deref,
-};
+use std::{borrow::Borrow, cmp::min, iter, ops::Deref};
+
+/// The largest integer which can be represented exactly by a number, 2<sup>53</sup> - 1, which
+/// is the maximum length of an array-like object.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
 
 /// JavaScript `Array` built-in implementation.
 #[derive(Debug, Clone, Copy)]
@@ -45,14 +48,14 @@ impl Array {
         array.set_kind(ObjectKind::Array);
         array.borrow().set_internal_slot(
             INSTANCE_PROTOTYPE,
-            interpreter
-                .realm()
-                .environment
-                .get_binding_value("Array")
-                .borrow()
-                .get_field(PROTOTYPE),
+            interpreter.realm().intrinsic(Intrinsic::ArrayPrototype),
         );
-        array.borrow().set_field("length", Value::from(0));
+        let length = Property::new()
+            .value(Value::from(0))
+            .writable(true)
+            .configurable(false)
+            .enumerable(false);
+        array.borrow().set_property("length".to_string(), length);
         Ok(array)
     }
 
@@ -87,21 +90,90 @@ impl Array {
     /// Utility function which takes an existing array object and puts additional
     /// values on the end, correctly rewriting the length
     pub(crate) fn add_to_array_object(array_ptr: &Value, add_values: &[Value]) -> ResultValue {
-        let orig_length = i32::from(&array_ptr.get_field("length"));
+        let orig_length = Self::dense_length(array_ptr)
+            .map(|length| length as usize)
+            .unwrap_or_else(|| usize::from(&array_ptr.get_field("length")));
 
+        // The indices are numbers, so the values of arrays are stored as elements, which also
+        // updates their length.
         for (n, value) in add_values.iter().enumerate() {
-            let new_index = orig_length.wrapping_add(n as i32);
-            array_ptr.set_field(new_index.to_string(), value);
+            array_ptr.set_field(orig_length.wrapping_add(n), value);
         }
 
-        array_ptr.set_field(
-            "length",
-            Value::from(orig_length.wrapping_add(add_values.len() as i32)),
-        );
+        let new_length = orig_length.wrapping_add(add_values.len());
+        if Self::dense_length(array_ptr) != Some(new_length as u32) {
+            array_ptr.set_field("length", Value::from(new_length));
+        }
 
         Ok(array_ptr.clone())
     }
 
+    /// Gets the `length` of an array from its properties, without looking it up by key.
+    ///
+    /// Returns `None` for values which aren't arrays.
+    fn dense_length(array_ptr: &Value) -> Option<u32> {
+        array_ptr
+            .as_object()
+            .and_then(|object| object.properties.array_length())
+    }
+
+    /// Converts the value assigned to the `length` of an array into an array length.
+    ///
+    /// It throws a `RangeError` if the value isn't an integer between 0 and 2<sup>32</sup> - 1.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraysetlength
+    #[allow(clippy::float_cmp)]
+    pub(crate) fn to_array_length(value: &Value, ctx: &mut Interpreter) -> Result<u32, Value> {
+        let length = ctx.to_uint32(value)?;
+        if f64::from(length) != ctx.to_number(value)? {
+            ctx.throw_range_error("invalid array length")?;
+        }
+        Ok(length)
+    }
+
+    /// Gets the length of an array-like object, as an integer between 0 and 2<sup>53</sup> - 1.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-lengthofarraylike
+    fn length_of_array_like(obj: &Value, ctx: &mut Interpreter) -> Result<usize, Value> {
+        let length = ctx.get_field_value(obj, "length")?;
+        ctx.to_length(&length)
+    }
+
+    /// Converts an optional relative index argument into an index between 0 and the length,
+    /// counting from the end if it is negative.
+    ///
+    /// A missing or `undefined` argument gives the default index.
+    fn relative_index(
+        value: Option<&Value>,
+        default: usize,
+        len: usize,
+        ctx: &mut Interpreter,
+    ) -> Result<usize, Value> {
+        let relative = match value {
+            Some(value) if !value.is_undefined() => ctx.to_integer_or_infinity(value)?,
+            _ => return Ok(default),
+        };
+        let index = if relative < 0.0 {
+            (len as f64 + relative).max(0.0)
+        } else {
+            relative.min(len as f64)
+        };
+        Ok(index as usize)
+    }
+
+    /// Returns true if the value is an array object.
+    fn is_array_value(value: &Value) -> bool {
+        value
+            .as_object()
+            .is_some_and(|obj| obj.kind == ObjectKind::Array)
+    }
+
     /// Create a new array
     pub(crate) fn make_array(
         this: &mut Value,
@@ -112,7 +184,7 @@ impl Array {
         // between indices and values): this creates an Object with no prototype
 
         // Set Prototype
-        let prototype = ctx.realm.global_obj.get_field("Array").get_field(PROTOTYPE);
+        let prototype = ctx.realm.intrinsic(Intrinsic::ArrayPrototype);
 
         this.set_internal_slot(INSTANCE_PROTOTYPE, prototype);
         // This value is used by console.log and other routines to match Object type
@@ -120,18 +192,15 @@ impl Array {
         this.set_kind(ObjectKind::Array);
 
         // add our arguments in
-        let mut length = args.len() as i32;
+        let mut length = args.len() as u32;
         match args.len() {
-            1 if args[0].is_integer() => {
-                length = i32::from(&args[0]);
+            1 if args[0].is_number() => {
+                length = Self::to_array_length(&args[0], ctx)?;
                 // TODO: It should not create an array of undefineds, but an empty array ("holy" array in V8) with length `n`.
                 for n in 0..length {
                     this.set_field(n.to_string(), Value::undefined());
                 }
             }
-            1 if args[0].is_double() => {
-                return ctx.throw_range_error("invalid array length");
-            }
             _ => {
                 for (n, value) in args.iter().enumerate() {
                     this.set_field(n.to_string(), value.clone());
@@ -141,7 +210,7 @@ impl Array {
 
         // finally create length property
         let length = Property::new()
-            .value(Value::from(length))
+            .value(Value::from(f64::from(length)))
             .writable(true)
             .configurable(false)
             .enumerable(false);
@@ -201,29 +270,52 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.concat
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/concat
-    pub(crate) fn concat(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-        if args.is_empty() {
-            // If concat is called with no arguments, it returns the original array
-            return Ok(this.clone());
+    pub(crate) fn concat(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let new_array = Self::new_array(ctx)?;
+        let mut n = 0;
+        for item in iter::once(&*this).chain(args) {
+            if Self::is_concat_spreadable(item, ctx)? {
+                let len = Self::length_of_array_like(item, ctx)?;
+                if (n + len) as f64 > MAX_SAFE_INTEGER {
+                    return ctx
+                        .throw_type_error("Array.prototype.concat: the new length is too large");
+                }
+                for k in 0..len {
+                    let key = k.to_string();
+                    if item.has_field(&key) {
+                        let element = ctx.get_field_value(item, key)?;
+                        new_array.set_field((n + k).to_string(), element);
+                    }
+                }
+                n += len;
+            } else {
+                new_array.set_field(n.to_string(), item.clone());
+                n += 1;
+            }
         }
+        new_array.set_field("length", Value::from(n));
+        Ok(new_array)
+    }
 
-        // Make a new array (using this object as the prototype basis for the new
-        // one)
-        let mut new_values: Vec<Value> = Vec::new();
-
-        let this_length = i32::from(&this.get_field("length"));
-        for n in 0..this_length {
-            new_values.push(this.get_field(n.to_string()));
+    /// Checks if the elements of a value are spread by `concat`, which is given by its
+    /// `@@isConcatSpreadable` property if it is defined, and by whether it is an array otherwise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-isconcatspreadable
+    fn is_concat_spreadable(value: &Value, ctx: &mut Interpreter) -> Result<bool, Value> {
+        if !value.is_object() {
+            return Ok(false);
         }
-
-        for concat_array in args {
-            let concat_length = i32::from(&concat_array.get_field("length"));
-            for n in 0..concat_length {
-                new_values.push(concat_array.get_field(n.to_string()));
+        let is_concat_spreadable = ctx.well_known_symbol("isConcatSpreadable");
+        if is_concat_spreadable.is_symbol() {
+            let spreadable = ctx.get_field_value(value, is_concat_spreadable)?;
+            if !spreadable.is_undefined() {
+                return Ok(spreadable.is_true());
             }
         }
-
-        Self::construct_array(this, &new_values)
+        Ok(Self::is_array_value(value))
     }
 
     /// `Array.prototype.push( ...items )`
@@ -240,7 +332,10 @@ impl Array {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/push
     pub(crate) fn push(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
         let new_array = Self::add_to_array_object(this, args)?;
-        Ok(new_array.get_field("length"))
+        match Self::dense_length(&new_array) {
+            Some(length) => Ok(Value::from(length as usize)),
+            None => Ok(new_array.get_field("length")),
+        }
     }
 
     /// `Array.prototype.pop()`
@@ -253,13 +348,13 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.pop
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/pop
-    pub(crate) fn pop(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        let curr_length = i32::from(&this.get_field("length"));
+    pub(crate) fn pop(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let curr_length = i32::from(&ctx.get_field_value(this, "length")?);
         if curr_length < 1 {
             return Ok(Value::undefined());
         }
         let pop_index = curr_length.wrapping_sub(1);
-        let pop_value: Value = this.get_field(pop_index.to_string());
+        let pop_value: Value = ctx.get_field_value(this, pop_index)?;
         this.remove_property(&pop_index.to_string());
         this.set_field("length", Value::from(pop_index));
         Ok(pop_value)
@@ -287,10 +382,10 @@ impl Array {
         let callback_arg = args.get(0).expect("Could not get `callbackFn` argument.");
         let mut this_arg = args.get(1).cloned().unwrap_or_else(Value::undefined);
 
-        let length = i32::from(&this.get_field("length"));
+        let length = i32::from(&interpreter.get_field_value(this, "length")?);
 
         for i in 0..length {
-            let element = this.get_field(i.to_string());
+            let element = interpreter.get_field_value(this, i)?;
             let arguments = [element, Value::from(i), this.clone()];
 
             interpreter.call(callback_arg, &mut this_arg, &arguments)?;
@@ -312,16 +407,20 @@ impl Array {
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.join
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/join
     pub(crate) fn join(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let separator = if args.is_empty() {
-            String::from(",")
-        } else {
-            ctx.to_string(args.get(0).expect("Could not get argument"))?
+        let len = Self::length_of_array_like(this, ctx)?;
+        let separator = match args.first() {
+            Some(separator) if !separator.is_undefined() => ctx.to_string(separator)?,
+            _ => String::from(","),
         };
 
-        let mut elem_strs: Vec<String> = Vec::new();
-        let length = i32::from(&this.get_field("length"));
-        for n in 0..length {
-            let elem_str: String = ctx.to_string(&this.get_field(n.to_string()))?;
+        let mut elem_strs: Vec<String> = Vec::with_capacity(len);
+        for n in 0..len {
+            let element = ctx.get_field_value(this, n)?;
+            let elem_str = if element.is_null_or_undefined() {
+                String::new()
+            } else {
+                ctx.to_string(&element)?
+            };
             elem_strs.push(elem_str);
         }
 
@@ -349,14 +448,12 @@ impl Array {
         let method_name = "join";
         let mut arguments = vec![Value::from(",")];
         // 2.
-        let mut method = this.get_field(method_name);
+        let mut method = ctx.get_field_value(this, method_name)?;
         // 3.
         if !method.is_function() {
             method = ctx
                 .realm
-                .global_obj
-                .get_field("Object")
-                .get_field(PROTOTYPE)
+                .intrinsic(Intrinsic::ObjectPrototype)
                 .get_field("toString");
 
             arguments = Vec::new();
@@ -365,7 +462,7 @@ impl Array {
         let join = ctx.call(&method, this, &arguments)?;
 
         let string = if let ValueData::String(ref s) = join.data() {
-            Value::from(s.as_str())
+            Value::from(s.clone())
         } else {
             Value::from("")
         };
@@ -385,8 +482,8 @@ impl Array {
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.reverse
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/reverse
     #[allow(clippy::else_if_without_else)]
-    pub(crate) fn reverse(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        let len = i32::from(&this.get_field("length"));
+    pub(crate) fn reverse(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = i32::from(&ctx.get_field_value(this, "length")?);
         let middle: i32 = len.wrapping_div(2);
 
         for lower in 0..middle {
@@ -395,8 +492,8 @@ impl Array {
             let upper_exists = this.has_field(&upper.to_string());
             let lower_exists = this.has_field(&lower.to_string());
 
-            let upper_value = this.get_field(upper.to_string());
-            let lower_value = this.get_field(lower.to_string());
+            let upper_value = ctx.get_field_value(this, upper)?;
+            let lower_value = ctx.get_field_value(this, lower)?;
 
             if upper_exists && lower_exists {
                 this.set_field(upper.to_string(), lower_value);
@@ -423,22 +520,21 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.shift
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/shift
-    pub(crate) fn shift(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        let len = i32::from(&this.get_field("length"));
+    pub(crate) fn shift(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = i32::from(&ctx.get_field_value(this, "length")?);
 
         if len == 0 {
             this.set_field("length", Value::from(0));
             // Since length is 0, this will be an Undefined value
-            return Ok(this.get_field(0.to_string()));
+            return ctx.get_field_value(this, 0);
         }
 
-        let first: Value = this.get_field(0.to_string());
+        let first: Value = ctx.get_field_value(this, 0)?;
 
         for k in 1..len {
-            let from = k.to_string();
             let to = (k.wrapping_sub(1)).to_string();
 
-            let from_value = this.get_field(from);
+            let from_value = ctx.get_field_value(this, k)?;
             if from_value.is_undefined() {
                 this.remove_property(&to);
             } else {
@@ -465,16 +561,15 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.unshift
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/unshift
-    pub(crate) fn unshift(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-        let len = i32::from(&this.get_field("length"));
+    pub(crate) fn unshift(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = i32::from(&ctx.get_field_value(this, "length")?);
         let arg_c: i32 = args.len() as i32;
 
         if arg_c > 0 {
             for k in (1..=len).rev() {
-                let from = (k.wrapping_sub(1)).to_string();
                 let to = (k.wrapping_add(arg_c).wrapping_sub(1)).to_string();
 
-                let from_value = this.get_field(from);
+                let from_value = ctx.get_field_value(this, k.wrapping_sub(1))?;
                 if from_value.is_undefined() {
                     this.remove_property(&to);
                 } else {
@@ -526,10 +621,10 @@ impl Array {
             Value::undefined()
         };
         let mut i = 0;
-        let max_len = i32::from(&this.get_field("length"));
+        let max_len = i32::from(&interpreter.get_field_value(this, "length")?);
         let mut len = max_len;
         while i < len {
-            let element = this.get_field(i.to_string());
+            let element = interpreter.get_field_value(this, i)?;
             let arguments = [element, Value::from(i), this.clone()];
             let result = interpreter
                 .call(callback, &mut this_arg, &arguments)?
@@ -537,7 +632,10 @@ impl Array {
             if !result {
                 return Ok(Value::from(false));
             }
-            len = min(max_len, i32::from(&this.get_field("length")));
+            len = min(
+                max_len,
+                i32::from(&interpreter.get_field_value(this, "length")?),
+            );
             i += 1;
         }
         Ok(Value::from(true))
@@ -568,20 +666,21 @@ impl Array {
         let callback = args.get(0).cloned().unwrap_or_else(Value::undefined);
         let mut this_val = args.get(1).cloned().unwrap_or_else(Value::undefined);
 
-        let length = i32::from(&this.get_field("length"));
+        let length = i32::from(&interpreter.get_field_value(this, "length")?);
 
         let new = Self::new_array(interpreter)?;
 
-        let values: Vec<Value> = (0..length)
-            .map(|idx| {
-                let element = this.get_field(idx.to_string());
-                let args = [element, Value::from(idx), new.clone()];
+        let mut values = Vec::new();
+        for idx in 0..length {
+            let element = interpreter.get_field_value(this, idx)?;
+            let args = [element, Value::from(idx), new.clone()];
 
+            values.push(
                 interpreter
                     .call(&callback, &mut this_val, &args)
-                    .unwrap_or_else(|_| Value::undefined())
-            })
-            .collect();
+                    .unwrap_or_else(|_| Value::undefined()),
+            );
+        }
 
         Self::construct_array(&new, &values)
     }
@@ -605,36 +704,32 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.indexof
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/indexOf
-    pub(crate) fn index_of(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-        // If no arguments, return -1. Not described in spec, but is what chrome does.
-        if args.is_empty() {
+    pub(crate) fn index_of(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        if len == 0 {
             return Ok(Value::from(-1));
         }
 
-        let search_element = args[0].clone();
-        let len = i32::from(&this.get_field("length"));
-
-        let mut idx = match args.get(1) {
-            Some(from_idx_ptr) => {
-                let from_idx = i32::from(from_idx_ptr);
-
-                if from_idx < 0 {
-                    len + from_idx
-                } else {
-                    from_idx
-                }
+        let search_element = args.first().cloned().unwrap_or_else(Value::undefined);
+        let from_index = ctx.to_integer_or_infinity(args.get(1).unwrap_or(&Value::undefined()))?;
+        let start = if from_index >= 0.0 {
+            if from_index >= len as f64 {
+                return Ok(Value::from(-1));
             }
-            None => 0,
+            from_index as usize
+        } else {
+            (len as f64 + from_index).max(0.0) as usize
         };
 
-        while idx < len {
-            let check_element = this.get_field(idx.to_string()).clone();
-
-            if check_element.strict_equals(&search_element) {
+        for idx in start..len {
+            let key = idx.to_string();
+            if this.has_field(&key)
+                && ctx
+                    .get_field_value(this, key)?
+                    .strict_equals(&search_element)
+            {
                 return Ok(Value::from(idx));
             }
-
-            idx += 1;
         }
 
         Ok(Value::from(-1))
@@ -661,37 +756,36 @@ impl Array {
     pub(crate) fn last_index_of(
         this: &mut Value,
         args: &[Value],
-        _: &mut Interpreter,
+        ctx: &mut Interpreter,
     ) -> ResultValue {
-        // If no arguments, return -1. Not described in spec, but is what chrome does.
-        if args.is_empty() {
+        let len = Self::length_of_array_like(this, ctx)?;
+        if len == 0 {
             return Ok(Value::from(-1));
         }
 
-        let search_element = args[0].clone();
-        let len = i32::from(&this.get_field("length"));
-
-        let mut idx = match args.get(1) {
-            Some(from_idx_ptr) => {
-                let from_idx = i32::from(from_idx_ptr);
-
-                if from_idx >= 0 {
-                    min(from_idx, len - 1)
-                } else {
-                    len + from_idx
-                }
-            }
-            None => len - 1,
+        let search_element = args.first().cloned().unwrap_or_else(Value::undefined);
+        let from_index = match args.get(1) {
+            Some(from_index) => ctx.to_integer_or_infinity(from_index)?,
+            None => len as f64 - 1.0,
         };
+        let last = if from_index >= 0.0 {
+            from_index.min(len as f64 - 1.0)
+        } else {
+            len as f64 + from_index
+        };
+        if last < 0.0 {
+            return Ok(Value::from(-1));
+        }
 
-        while idx >= 0 {
-            let check_element = this.get_field(idx.to_string()).clone();
-
-            if check_element.strict_equals(&search_element) {
+        for idx in (0..=last as usize).rev() {
+            let key = idx.to_string();
+            if this.has_field(&key)
+                && ctx
+                    .get_field_value(this, key)?
+                    .strict_equals(&search_element)
+            {
                 return Ok(Value::from(idx));
             }
-
-            idx -= 1;
         }
 
         Ok(Value::from(-1))
@@ -725,9 +819,9 @@ impl Array {
         } else {
             Value::undefined()
         };
-        let len = i32::from(&this.get_field("length"));
+        let len = i32::from(&interpreter.get_field_value(this, "length")?);
         for i in 0..len {
-            let element = this.get_field(i.to_string());
+            let element = interpreter.get_field_value(this, i)?;
             let arguments = [element.clone(), Value::from(i), this.clone()];
             let result = interpreter.call(callback, &mut this_arg, &arguments)?;
             if result.is_true() {
@@ -764,10 +858,10 @@ impl Array {
 
         let mut this_arg = args.get(1).cloned().unwrap_or_else(Value::undefined);
 
-        let length = i32::from(&this.get_field("length"));
+        let length = i32::from(&interpreter.get_field_value(this, "length")?);
 
         for i in 0..length {
-            let element = this.get_field(i.to_string());
+            let element = interpreter.get_field_value(this, i)?;
             let arguments = [element, Value::from(i), this.clone()];
 
             let result = interpreter.call(predicate_arg, &mut this_arg, &arguments)?;
@@ -791,35 +885,62 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.fill
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/fill
-    pub(crate) fn fill(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-        let len: i32 = i32::from(&this.get_field("length"));
-        let default_value = Value::undefined();
-        let value = args.get(0).unwrap_or(&default_value);
-        let relative_start = args.get(1).unwrap_or(&default_value).to_number() as i32;
-        let relative_end_val = args.get(2).unwrap_or(&default_value);
-        let relative_end = if relative_end_val.is_undefined() {
-            len
-        } else {
-            relative_end_val.to_number() as i32
-        };
-        let start = if relative_start < 0 {
-            max(len + relative_start, 0)
-        } else {
-            min(relative_start, len)
-        };
-        let fin = if relative_end < 0 {
-            max(len + relative_end, 0)
-        } else {
-            min(relative_end, len)
-        };
+    pub(crate) fn fill(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let value = args.first().cloned().unwrap_or_else(Value::undefined);
+        let start = Self::relative_index(args.get(1), 0, len, ctx)?;
+        let end = Self::relative_index(args.get(2), len, len, ctx)?;
 
-        for i in start..fin {
+        for i in start..end {
             this.set_field(i.to_string(), value.clone());
         }
 
         Ok(this.clone())
     }
 
+    /// `Array.prototype.copyWithin( target, start [ , end ] )`
+    ///
+    /// The method copies the elements from start index (default 0) to an end index (default
+    /// array length) to the target index, within the same array, and returns the array. The
+    /// copied elements may overlap with the target.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.copywithin
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/copyWithin
+    pub(crate) fn copy_within(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let target = Self::relative_index(args.first(), 0, len, ctx)?;
+        let start = Self::relative_index(args.get(1), 0, len, ctx)?;
+        let end = Self::relative_index(args.get(2), len, len, ctx)?;
+        let count = min(end.saturating_sub(start), len - target);
+
+        // The elements are copied backwards when the target overlaps the end of the source.
+        let indices: Box<dyn Iterator<Item = usize>> = if start < target && target < start + count {
+            Box::new((0..count).rev())
+        } else {
+            Box::new(0..count)
+        };
+        for i in indices {
+            let from = (start + i).to_string();
+            let to = (target + i).to_string();
+            if this.has_field(&from) {
+                let value = ctx.get_field_value(this, from)?;
+                this.set_field(to, value);
+            } else {
+                this.remove_property(&to);
+            }
+        }
+
+        Ok(this.clone())
+    }
+
     /// `Array.prototype.includes( valueToFind [, fromIndex] )`
     ///
     /// Determines whether an array includes a certain value among its entries, returning `true` or `false` as appropriate.
@@ -833,14 +954,14 @@ impl Array {
     pub(crate) fn includes_value(
         this: &mut Value,
         args: &[Value],
-        _: &mut Interpreter,
+        ctx: &mut Interpreter,
     ) -> ResultValue {
         let search_element = args.get(0).cloned().unwrap_or_else(Value::undefined);
 
-        let length = i32::from(&this.get_field("length"));
+        let length = i32::from(&ctx.get_field_value(this, "length")?);
 
         for idx in 0..length {
-            let check_element = this.get_field(idx.to_string()).clone();
+            let check_element = ctx.get_field_value(this, idx)?;
 
             if same_value_zero(&check_element, &search_element) {
                 return Ok(Value::from(true));
@@ -850,6 +971,283 @@ impl Array {
         Ok(Value::from(false))
     }
 
+    /// `Array.prototype.at( index )`
+    ///
+    /// Returns the element at the given index, counting from the end of the array if the index
+    /// is negative, or `undefined` if there is no element at this index.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.at
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/at
+    pub(crate) fn at(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let relative = ctx.to_integer_or_infinity(args.first().unwrap_or(&Value::undefined()))?;
+        let index = if relative < 0.0 {
+            len as f64 + relative
+        } else {
+            relative
+        };
+        if index < 0.0 || index >= len as f64 {
+            return Ok(Value::undefined());
+        }
+        ctx.get_field_value(this, index as usize)
+    }
+
+    /// `Array.prototype.flat( [ depth ] )`
+    ///
+    /// Creates a new array with the elements of the array, and of the arrays it contains
+    /// flattened into it, recursively up to the given depth (default 1). The holes of the
+    /// flattened arrays are skipped.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.flat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/flat
+    pub(crate) fn flat(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let depth = match args.first() {
+            Some(depth) if !depth.is_undefined() => ctx.to_integer_or_infinity(depth)?.max(0.0),
+            _ => 1.0,
+        };
+
+        let mut values = Vec::new();
+        Self::flatten_into(&mut values, this, depth, None, ctx)?;
+
+        let new_array = Self::new_array(ctx)?;
+        Self::construct_array(&new_array, &values)
+    }
+
+    /// `Array.prototype.flatMap( callback [ , thisArg ] )`
+    ///
+    /// Creates a new array with the results of the callback function called on each element of
+    /// the array, flattening the arrays it returns one level deep.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.flatmap
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/flatMap
+    pub(crate) fn flat_map(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let callback = args.first().cloned().unwrap_or_else(Value::undefined);
+        if !callback.is_function() {
+            return ctx.throw_type_error("Array.prototype.flatMap: callback is not a function");
+        }
+        let this_arg = args.get(1).cloned().unwrap_or_else(Value::undefined);
+
+        let mut values = Vec::new();
+        Self::flatten_into(&mut values, this, 1.0, Some((&callback, &this_arg)), ctx)?;
+
+        let new_array = Self::new_array(ctx)?;
+        Self::construct_array(&new_array, &values)
+    }
+
+    /// Appends the elements of an array-like object to a list, flattening the arrays among them
+    /// up to the given depth, after mapping them with the callback of `flatMap`, if any.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-flattenintoarray
+    fn flatten_into(
+        values: &mut Vec<Value>,
+        source: &Value,
+        depth: f64,
+        mapper: Option<(&Value, &Value)>,
+        ctx: &mut Interpreter,
+    ) -> Result<(), Value> {
+        let len = Self::length_of_array_like(source, ctx)?;
+        for i in 0..len {
+            let key = i.to_string();
+            if !source.has_field(&key) {
+                continue;
+            }
+            let mut element = ctx.get_field_value(source, key)?;
+            if let Some((callback, this_arg)) = mapper {
+                let arguments = [element, Value::from(i), source.clone()];
+                element = ctx.call(callback, &mut this_arg.clone(), &arguments)?;
+            }
+
+            if depth > 0.0 && Self::is_array_value(&element) {
+                Self::flatten_into(values, &element, depth - 1.0, None, ctx)?;
+            } else {
+                values.push(element);
+            }
+        }
+        Ok(())
+    }
+
+    /// `Array.prototype.toReversed()`
+    ///
+    /// Returns a new array with the elements of the array in reverse order, like `reverse`
+    /// without modifying the array. The holes of the array become `undefined`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.toreversed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/toReversed
+    pub(crate) fn to_reversed(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let values = (0..len)
+            .rev()
+            .map(|i| ctx.get_field_value(this, i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let new_array = Self::new_array(ctx)?;
+        Self::construct_array(&new_array, &values)
+    }
+
+    /// `Array.prototype.toSpliced( start, skipCount, ...items )`
+    ///
+    /// Returns a new array with `skipCount` elements removed from the start index and replaced
+    /// by the given items, like `splice` without modifying the array. The holes of the array
+    /// become `undefined`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.tospliced
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/toSpliced
+    pub(crate) fn to_spliced(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let start = Self::relative_index(args.first(), 0, len, ctx)?;
+        let skip_count = match args.len() {
+            0 => 0,
+            1 => len - start,
+            _ => {
+                let skip_count = ctx.to_integer_or_infinity(&args[1])?;
+                skip_count.max(0.0).min((len - start) as f64) as usize
+            }
+        };
+        let items = args.get(2..).unwrap_or_default();
+
+        if (len + items.len() - skip_count) as f64 > MAX_SAFE_INTEGER {
+            return ctx.throw_type_error("Array.prototype.toSpliced: the new length is too large");
+        }
+
+        let mut values = Vec::with_capacity(len + items.len() - skip_count);
+        for i in 0..start {
+            values.push(ctx.get_field_value(this, i)?);
+        }
+        values.extend(items.iter().cloned());
+        for i in start + skip_count..len {
+            values.push(ctx.get_field_value(this, i)?);
+        }
+
+        let new_array = Self::new_array(ctx)?;
+        Self::construct_array(&new_array, &values)
+    }
+
+    /// `Array.prototype.with( index, value )`
+    ///
+    /// Returns a new array with the element at the given index replaced by the value, like an
+    /// assignment to the index without modifying the array. A negative index counts from the
+    /// end of the array, and an index out of the array throws a `RangeError`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.with
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/with
+    pub(crate) fn with(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let relative = ctx.to_integer_or_infinity(args.first().unwrap_or(&Value::undefined()))?;
+        let index = if relative < 0.0 {
+            len as f64 + relative
+        } else {
+            relative
+        };
+        if index < 0.0 || index >= len as f64 {
+            return ctx.throw_range_error("Array.prototype.with: index out of range");
+        }
+        let index = index as usize;
+        let value = args.get(1).cloned().unwrap_or_else(Value::undefined);
+
+        let values = (0..len)
+            .map(|i| {
+                if i == index {
+                    Ok(value.clone())
+                } else {
+                    ctx.get_field_value(this, i)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let new_array = Self::new_array(ctx)?;
+        Self::construct_array(&new_array, &values)
+    }
+
+    /// `Array.prototype.values( )`
+    ///
+    /// Returns an iterator over the elements of the array. This is also the
+    /// `[Symbol.iterator]` method of arrays.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.values
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/values
+    pub(crate) fn values(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let array = ctx.to_object(this)?;
+        Ok(ArrayIterator::create_array_iterator(
+            ctx,
+            array,
+            ArrayIterationKind::Value,
+        ))
+    }
+
+    /// `Array.prototype.keys( )`
+    ///
+    /// Returns an iterator over the indices of the array.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.keys
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/keys
+    pub(crate) fn keys(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let array = ctx.to_object(this)?;
+        Ok(ArrayIterator::create_array_iterator(
+            ctx,
+            array,
+            ArrayIterationKind::Key,
+        ))
+    }
+
+    /// `Array.prototype.entries( )`
+    ///
+    /// Returns an iterator over the entries of the array, which are arrays holding the index
+    /// and the value of each element.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.entries
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/entries
+    pub(crate) fn entries(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let array = ctx.to_object(this)?;
+        Ok(ArrayIterator::create_array_iterator(
+            ctx,
+            array,
+            ArrayIterationKind::KeyAndValue,
+        ))
+    }
+
     /// `Array.prototype.slice( [begin[, end]] )`
     ///
     /// The slice method takes two arguments, start and end, and returns an array containing the
@@ -864,42 +1262,92 @@ impl Array {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.slice
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/slice
-    pub(crate) fn slice(
-        this: &mut Value,
-        args: &[Value],
-        interpreter: &mut Interpreter,
-    ) -> ResultValue {
-        let new_array = Self::new_array(interpreter)?;
-        let len = i32::from(&this.get_field("length"));
+    pub(crate) fn slice(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let from = Self::relative_index(args.first(), 0, len, ctx)?;
+        let to = Self::relative_index(args.get(1), len, len, ctx)?;
+
+        let new_array = Self::new_array(ctx)?;
+        let count = to.saturating_sub(from);
+        for i in 0..count {
+            let key = (from + i).to_string();
+            if this.has_field(&key) {
+                let element = ctx.get_field_value(this, key)?;
+                new_array.set_field(i.to_string(), element);
+            }
+        }
+        new_array.set_field("length", Value::from(count));
+        Ok(new_array)
+    }
 
-        let start = match args.get(0) {
-            Some(v) => i32::from(v),
-            None => 0,
-        };
-        let end = match args.get(1) {
-            Some(v) => i32::from(v),
-            None => len,
+    /// `Array.prototype.splice( start, deleteCount, ...items )`
+    ///
+    /// The splice method removes `deleteCount` elements of the array from the start index and
+    /// replaces them with the given items, returning an array containing the removed elements.
+    /// If `deleteCount` is omitted, all the elements from the start index are removed. The holes
+    /// of the array are kept in both arrays.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array.prototype.splice
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array/splice
+    pub(crate) fn splice(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let len = Self::length_of_array_like(this, ctx)?;
+        let start = Self::relative_index(args.first(), 0, len, ctx)?;
+        let delete_count = match args.len() {
+            0 => 0,
+            1 => len - start,
+            _ => {
+                let delete_count = ctx.to_integer_or_infinity(&args[1])?;
+                delete_count.max(0.0).min((len - start) as f64) as usize
+            }
         };
+        let items = args.get(2..).unwrap_or_default();
+        let new_len = len + items.len() - delete_count;
+        if new_len as f64 > MAX_SAFE_INTEGER {
+            return ctx.throw_type_error("Array.prototype.splice: the new length is too large");
+        }
 
-        let from = if start < 0 {
-            max(len.wrapping_add(start), 0)
-        } else {
-            min(start, len)
-        };
-        let to = if end < 0 {
-            max(len.wrapping_add(end), 0)
+        let removed = Self::new_array(ctx)?;
+        for k in 0..delete_count {
+            let from = (start + k).to_string();
+            if this.has_field(&from) {
+                let element = ctx.get_field_value(this, from)?;
+                removed.set_field(k.to_string(), element);
+            }
+        }
+        removed.set_field("length", Value::from(delete_count));
+
+        // Move the elements after the removed ones, starting from the side they are moved to so
+        // that none of them is overwritten before being moved.
+        let tail = start + delete_count..len;
+        let moves: Box<dyn Iterator<Item = usize>> = if items.len() < delete_count {
+            Box::new(tail)
         } else {
-            min(end, len)
+            Box::new(tail.rev())
         };
+        for from in moves {
+            let to = (from - delete_count + items.len()).to_string();
+            let from = from.to_string();
+            if this.has_field(&from) {
+                let value = ctx.get_field_value(this, from)?;
+                this.set_field(to, value);
+            } else {
+                this.remove_property(&to);
+            }
+        }
+        for k in (new_len..len).rev() {
+            this.remove_property(&k.to_string());
+        }
 
-        let span = max(to.wrapping_sub(from), 0);
-        let mut new_array_len: i32 = 0;
-        for i in from..from.wrapping_add(span) {
-            new_array.set_field(new_array_len.to_string(), this.get_field(i.to_string()));
-            new_array_len = new_array_len.wrapping_add(1);
+        for (k, item) in items.iter().enumerate() {
+            this.set_field((start + k).to_string(), item.clone());
         }
-        new_array.set_field("length", Value::from(new_array_len));
-        Ok(new_array)
+        this.set_field("length", Value::from(new_len));
+
+        Ok(removed)
     }
 
     /// `Array.prototype.filter( callback, [ thisArg ] )`
@@ -927,27 +1375,24 @@ impl Array {
         let callback = args.get(0).cloned().unwrap_or_else(Value::undefined);
         let mut this_val = args.get(1).cloned().unwrap_or_else(Value::undefined);
 
-        let length = i32::from(&this.get_field("length"));
+        let length = i32::from(&interpreter.get_field_value(this, "length")?);
 
         let new = Self::new_array(interpreter)?;
 
-        let values = (0..length)
-            .filter_map(|idx| {
-                let element = this.get_field(idx.to_string());
+        let mut values = Vec::new();
+        for idx in 0..length {
+            let element = interpreter.get_field_value(this, idx)?;
 
-                let args = [element.clone(), Value::from(idx), new.clone()];
+            let args = [element.clone(), Value::from(idx), new.clone()];
 
-                let callback_result = interpreter
-                    .call(&callback, &mut this_val, &args)
-                    .unwrap_or_else(|_| Value::undefined());
+            let callback_result = interpreter
+                .call(&callback, &mut this_val, &args)
+                .unwrap_or_else(|_| Value::undefined());
 
-                if callback_result.is_true() {
-                    Some(element)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Value>>();
+            if callback_result.is_true() {
+                values.push(element);
+            }
+        }
 
         Self::construct_array(&new, &values)
     }
@@ -984,10 +1429,10 @@ impl Array {
             Value::undefined()
         };
         let mut i = 0;
-        let max_len = i32::from(&this.get_field("length"));
+        let max_len = i32::from(&interpreter.get_field_value(this, "length")?);
         let mut len = max_len;
         while i < len {
-            let element = this.get_field(i.to_string());
+            let element = interpreter.get_field_value(this, i)?;
             let arguments = [element, Value::from(i), this.clone()];
             let result = interpreter
                 .call(callback, &mut this_arg, &arguments)?
@@ -996,7 +1441,10 @@ impl Array {
                 return Ok(Value::from(true));
             }
             // the length of the array must be updated because the callback can mutate it.
-            len = min(max_len, i32::from(&this.get_field("length")));
+            len = min(
+                max_len,
+                i32::from(&interpreter.get_field_value(this, "length")?),
+            );
             i += 1;
         }
         Ok(Value::from(false))
@@ -1005,7 +1453,7 @@ impl Array {
     /// Create a new `Array` object.
     pub(crate) fn create(global: &Value) -> Value {
         // Create prototype
-        let prototype = Value::new_object(None);
+        let prototype = Value::new_object(Some(global));
         let length = Property::default().value(Value::from(0));
 
         prototype.set_property_slice("length", length);
@@ -1029,7 +1477,18 @@ impl Array {
         make_builtin_fn(Self::find, "find", &prototype, 1);
         make_builtin_fn(Self::find_index, "findIndex", &prototype, 1);
         make_builtin_fn(Self::slice, "slice", &prototype, 2);
+        make_builtin_fn(Self::splice, "splice", &prototype, 2);
         make_builtin_fn(Self::some, "some", &prototype, 2);
+        make_builtin_fn(Self::at, "at", &prototype, 1);
+        make_builtin_fn(Self::copy_within, "copyWithin", &prototype, 2);
+        make_builtin_fn(Self::flat, "flat", &prototype, 0);
+        make_builtin_fn(Self::flat_map, "flatMap", &prototype, 1);
+        make_builtin_fn(Self::to_reversed, "toReversed", &prototype, 0);
+        make_builtin_fn(Self::to_spliced, "toSpliced", &prototype, 2);
+        make_builtin_fn(Self::with, "with", &prototype, 2);
+        make_builtin_fn(Self::values, "values", &prototype, 0);
+        make_builtin_fn(Self::keys, "keys", &prototype, 0);
+        make_builtin_fn(Self::entries, "entries", &prototype, 0);
 
         let array = make_constructor_fn("Array", 1, Self::make_array, global, prototype, true);
 
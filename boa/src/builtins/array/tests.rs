@@ -40,28 +40,75 @@ fn is_array() {
     );
 }
 
+#[test]
+fn prototype_inherits_from_object() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "[] instanceof Object"), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getPrototypeOf(Array.prototype) === Object.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "[].hasOwnProperty('length')"), "true");
+    assert_eq!(forward(&mut engine, "[1].hasOwnProperty('1')"), "false");
+}
+
 #[test]
 fn concat() {
-    //TODO: array display formatter
-    // let realm = Realm::create();
-    // let mut engine = Interpreter::new(realm);
-    // let init = r#"
-    // var empty = new Array();
-    // var one = new Array(1);
-    // "#;
-    // eprintln!("{}", forward(&mut engine, init));
-    // // Empty ++ Empty
-    // let ee = forward(&mut engine, "empty.concat(empty)");
-    // assert_eq!(ee, String::from("[]"));
-    // // Empty ++ NonEmpty
-    // let en = forward(&mut engine, "empty.concat(one)");
-    // assert_eq!(en, String::from("[a]"));
-    // // NonEmpty ++ Empty
-    // let ne = forward(&mut engine, "one.concat(empty)");
-    // assert_eq!(ne, String::from("a.b.c"));
-    // // NonEmpty ++ NonEmpty
-    // let nn = forward(&mut engine, "one.concat(one)");
-    // assert_eq!(nn, String::from("a.b.c"));
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var empty = [];
+        var one = [1];
+        var many = [1, 2, 3];
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "empty.concat(empty).length"), "0");
+    assert_eq!(forward(&mut engine, "empty.concat(one).join()"), "1");
+    assert_eq!(
+        forward(&mut engine, "many.concat(one, 4, [[5]]).join()"),
+        "1,2,3,1,4,5"
+    );
+    assert_eq!(
+        forward(&mut engine, "many.concat(one, 4, [[5]]).length"),
+        "6"
+    );
+    // the array is not modified
+    assert_eq!(forward(&mut engine, "many.concat(one) !== many"), "true");
+    assert_eq!(forward(&mut engine, "many.length"), "3");
+
+    forward(&mut engine, "var holes = [1, 2, 3]; delete holes[1];");
+    assert_eq!(forward(&mut engine, "1 in empty.concat(holes)"), "false");
+    assert_eq!(forward(&mut engine, "empty.concat(holes).length"), "3");
+}
+
+#[test]
+fn concat_spreadable() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var arrayLike = { length: 2, 0: "a", 1: "b" };
+        arrayLike[Symbol.isConcatSpreadable] = true;
+        var array = [1, 2];
+        array[Symbol.isConcatSpreadable] = false;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "[0].concat(arrayLike).join()"),
+        "0,a,b"
+    );
+    assert_eq!(forward(&mut engine, "[0].concat(array).length"), "2");
+    assert_eq!(
+        forward(&mut engine, "[0].concat(array)[1] === array"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "[0].concat({ length: 2 }).length"),
+        "2"
+    );
 }
 
 #[test]
@@ -207,6 +254,27 @@ fn push() {
     assert_eq!(forward(&mut engine, "arr[3]"), "4");
 }
 
+#[test]
+fn push_many() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var arr = [];
+        for (var i = 0; i < 10000; i++) {
+            arr.push(i);
+        }
+        var like = { length: 1 };
+        like.push = Array.prototype.push;
+        like.push(5, 6);
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "arr.length"), "10000");
+    assert_eq!(forward(&mut engine, "arr[9999]"), "9999");
+    assert_eq!(forward(&mut engine, "like.length"), "3");
+    assert_eq!(forward(&mut engine, "like[2]"), "6");
+}
+
 #[test]
 fn pop() {
     let realm = Realm::create();
@@ -495,10 +563,7 @@ fn fill() {
         String::from("4,4,4")
     );
 
-    assert_eq!(
-        forward(&mut engine, "a.fill().join()"),
-        String::from("undefined,undefined,undefined")
-    );
+    assert_eq!(forward(&mut engine, "a.fill().join()"), String::from(",,"));
 
     // test object reference
     forward(&mut engine, "a = (new Array(3)).fill({});");
@@ -811,3 +876,427 @@ fn call_array_constructor_with_one_argument() {
     let result = forward(&mut engine, "one.length");
     assert_eq!(result, "1");
 }
+
+#[test]
+fn indexed_writes_update_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [];
+        arr[0] = "a";
+        arr[1] = "b";
+        let holey = [1, 2];
+        holey[5] = 6;
+        let huge = [];
+        huge[4294967294] = 1;
+        huge[4294967295] = 2;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr.length"), "2");
+    assert_eq!(forward(&mut engine, "arr[1]"), "b");
+    assert_eq!(forward(&mut engine, "holey.length"), "6");
+    assert_eq!(forward(&mut engine, "holey[3]"), "undefined");
+    assert_eq!(forward(&mut engine, "holey[5]"), "6");
+    assert_eq!(forward(&mut engine, "huge.length"), "4294967295");
+    assert_eq!(forward(&mut engine, "huge[4294967295]"), "2");
+}
+
+#[test]
+fn deleted_elements_leave_holes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3, 4];
+        delete arr[1];
+        arr[4] = 5;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr[1]"), "undefined");
+    assert_eq!(forward(&mut engine, "arr[2]"), "3");
+    assert_eq!(forward(&mut engine, "arr['3']"), "4");
+    assert_eq!(forward(&mut engine, "arr[4]"), "5");
+    assert_eq!(forward(&mut engine, "arr.length"), "5");
+}
+
+#[test]
+fn read_only_elements() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3];
+        Object.defineProperty(arr, "1", { value: 20, writable: false });
+        arr[1] = 30;
+        arr[2] = 40;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr[1]"), "20");
+    assert_eq!(forward(&mut engine, "arr[2]"), "40");
+    assert_eq!(forward(&mut engine, "arr.length"), "3");
+}
+
+#[test]
+fn length_truncates_elements() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3, 4, 5];
+        arr.length = 2;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr.length"), "2");
+    assert_eq!(forward(&mut engine, "arr[2]"), "undefined");
+    assert_eq!(forward(&mut engine, "4 in arr"), "false");
+    forward(&mut engine, "arr.length = '4'");
+    assert_eq!(forward(&mut engine, "arr.length"), "4");
+    assert_eq!(forward(&mut engine, "arr[3]"), "undefined");
+}
+
+#[test]
+fn invalid_length_throws() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3];
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { arr.length = -1 } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { arr.length = 1.5 } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { new Array(-1) } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(arr, 'length', { value: 2 ** 32 }) } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "arr.length"), "3");
+}
+
+#[test]
+fn non_writable_length() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3];
+        Object.defineProperty(arr, "length", { value: 1, writable: false });
+        arr[5] = 6;
+        arr.length = 5;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr.length"), "1");
+    assert_eq!(forward(&mut engine, "arr[1]"), "undefined");
+    assert_eq!(forward(&mut engine, "arr[5]"), "undefined");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(arr, '1', { value: 2 }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn non_configurable_elements_stop_truncation() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let arr = [1, 2, 3, 4];
+        Object.defineProperty(arr, "1", { value: 2, configurable: false });
+        arr.length = 0;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "arr.length"), "2");
+    assert_eq!(forward(&mut engine, "arr[1]"), "2");
+    assert_eq!(forward(&mut engine, "arr[2]"), "undefined");
+}
+
+#[test]
+fn at() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3];");
+    assert_eq!(forward(&mut engine, "a.at(0)"), "1");
+    assert_eq!(forward(&mut engine, "a.at(-1)"), "3");
+    assert_eq!(forward(&mut engine, "a.at('1.7')"), "2");
+    assert_eq!(forward(&mut engine, "a.at(3)"), "undefined");
+    assert_eq!(forward(&mut engine, "a.at(-4)"), "undefined");
+    assert_eq!(forward(&mut engine, "a.at()"), "1");
+}
+
+#[test]
+fn flat() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        "var a = [1, [2, [3, [4]]], 0, 5]; delete a[2];",
+    );
+    assert_eq!(forward(&mut engine, "a.flat().length"), "4");
+    assert_eq!(forward(&mut engine, "a.flat().join()"), "1,2,3,4,5");
+    assert_eq!(forward(&mut engine, "a.flat()[2].length"), "2");
+    assert_eq!(forward(&mut engine, "a.flat(0).length"), "3");
+    assert_eq!(forward(&mut engine, "a.flat(-1).length"), "3");
+    assert_eq!(forward(&mut engine, "a.flat(2).length"), "5");
+    assert_eq!(forward(&mut engine, "a.flat(1 / 0).length"), "5");
+    assert_eq!(forward(&mut engine, "a.flat(1 / 0)[3]"), "4");
+    // the array is not modified
+    assert_eq!(forward(&mut engine, "a.length"), "4");
+}
+
+#[test]
+fn flat_map() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3];");
+    assert_eq!(
+        forward(&mut engine, "a.flatMap(x => [x, x * 2]).join()"),
+        "1,2,2,4,3,6"
+    );
+    assert_eq!(forward(&mut engine, "a.flatMap(x => [[x]]).length"), "3");
+    assert_eq!(
+        forward(&mut engine, "a.flatMap((x, i) => i).join()"),
+        "0,1,2"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { a.flatMap(1) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn copy_within() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3, 4, 5];");
+    assert_eq!(
+        forward(&mut engine, "a.copyWithin(0, 3).join()"),
+        "4,5,3,4,5"
+    );
+    // make sure the array is modified
+    assert_eq!(forward(&mut engine, "a.join()"), "4,5,3,4,5");
+
+    forward(&mut engine, "a = [1, 2, 3, 4, 5];");
+    assert_eq!(
+        forward(&mut engine, "a.copyWithin(1, 0).join()"),
+        "1,1,2,3,4"
+    );
+
+    forward(&mut engine, "a = [1, 2, 3, 4, 5];");
+    assert_eq!(
+        forward(&mut engine, "a.copyWithin(-2, -4, -3).join()"),
+        "1,2,3,2,5"
+    );
+
+    forward(&mut engine, "a = [1, 2, 3]; delete a[1];");
+    assert_eq!(forward(&mut engine, "a.copyWithin(0, 1).length"), "3");
+    assert_eq!(forward(&mut engine, "0 in a"), "false");
+    assert_eq!(forward(&mut engine, "a[1]"), "3");
+}
+
+#[test]
+fn to_reversed() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3]; delete a[1];");
+    assert_eq!(forward(&mut engine, "a.toReversed()[0]"), "3");
+    assert_eq!(forward(&mut engine, "a.toReversed()[2]"), "1");
+    assert_eq!(forward(&mut engine, "1 in a.toReversed()"), "true");
+    // the array is not modified
+    assert_eq!(forward(&mut engine, "a[0]"), "1");
+}
+
+#[test]
+fn to_spliced() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3, 4];");
+    assert_eq!(forward(&mut engine, "a.toSpliced(1, 2).join()"), "1,4");
+    assert_eq!(
+        forward(&mut engine, "a.toSpliced(1, 1, 'a', 'b').join()"),
+        "1,a,b,3,4"
+    );
+    assert_eq!(forward(&mut engine, "a.toSpliced(-1).join()"), "1,2,3");
+    assert_eq!(forward(&mut engine, "a.toSpliced().join()"), "1,2,3,4");
+    assert_eq!(
+        forward(&mut engine, "a.toSpliced(2, -1, 0).join()"),
+        "1,2,0,3,4"
+    );
+    // the array is not modified
+    assert_eq!(forward(&mut engine, "a.join()"), "1,2,3,4");
+}
+
+#[test]
+fn with() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3];");
+    assert_eq!(forward(&mut engine, "a.with(1, 5).join()"), "1,5,3");
+    assert_eq!(forward(&mut engine, "a.with(-1, 5).join()"), "1,2,5");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { a.with(3, 5) } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { a.with(-4, 5) } catch (e) { e instanceof RangeError }"
+        ),
+        "true"
+    );
+    // the array is not modified
+    assert_eq!(forward(&mut engine, "a.join()"), "1,2,3");
+}
+
+#[test]
+fn splice() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3, 4, 5];");
+    assert_eq!(forward(&mut engine, "a.splice(1, 2).join()"), "2,3");
+    assert_eq!(forward(&mut engine, "a.join()"), "1,4,5");
+
+    forward(&mut engine, "a = [1, 2, 3, 4, 5];");
+    assert_eq!(forward(&mut engine, "a.splice(1, 1, 'a', 'b').join()"), "2");
+    assert_eq!(forward(&mut engine, "a.join()"), "1,a,b,3,4,5");
+
+    forward(&mut engine, "a = [1, 2, 3, 4, 5];");
+    assert_eq!(forward(&mut engine, "a.splice(-2).join()"), "4,5");
+    assert_eq!(forward(&mut engine, "a.join()"), "1,2,3");
+
+    forward(&mut engine, "a = [1, 2, 3];");
+    assert_eq!(forward(&mut engine, "a.splice().length"), "0");
+    assert_eq!(forward(&mut engine, "a.splice(1, -1, 0).length"), "0");
+    assert_eq!(forward(&mut engine, "a.join()"), "1,0,2,3");
+
+    forward(&mut engine, "a = [1, 2, 3, 4]; delete a[1]; delete a[3];");
+    forward(&mut engine, "var removed = a.splice(0, 2, 'x');");
+    assert_eq!(forward(&mut engine, "removed.length"), "2");
+    assert_eq!(forward(&mut engine, "1 in removed"), "false");
+    assert_eq!(forward(&mut engine, "a.length"), "3");
+    assert_eq!(forward(&mut engine, "a[1]"), "3");
+    assert_eq!(forward(&mut engine, "2 in a"), "false");
+}
+
+#[test]
+fn slice_holes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, 2, 3, 4]; delete a[2];");
+    assert_eq!(forward(&mut engine, "a.slice(1).length"), "3");
+    assert_eq!(forward(&mut engine, "1 in a.slice(1)"), "false");
+    assert_eq!(forward(&mut engine, "a.slice('1', -1)[0]"), "2");
+    assert_eq!(forward(&mut engine, "a.slice(3, 1).length"), "0");
+}
+
+#[test]
+fn index_of_from_index() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        "var a = [1, 2, 1, undefined]; delete a[3]; a.length = 5;",
+    );
+    assert_eq!(forward(&mut engine, "a.indexOf(1, 1.5)"), "2");
+    assert_eq!(forward(&mut engine, "a.indexOf(1, '-10')"), "0");
+    assert_eq!(forward(&mut engine, "a.indexOf(1, 1 / 0)"), "-1");
+    assert_eq!(forward(&mut engine, "a.indexOf(undefined)"), "-1");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(1, 1.5)"), "0");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(1, -4)"), "0");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(1, -10)"), "-1");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(1, 1 / 0)"), "2");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(1, undefined)"), "0");
+    assert_eq!(forward(&mut engine, "a.lastIndexOf(undefined)"), "-1");
+}
+
+#[test]
+fn join_separator() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = [1, null, undefined, 2];");
+    assert_eq!(forward(&mut engine, "a.join()"), "1,,,2");
+    assert_eq!(forward(&mut engine, "a.join(undefined)"), "1,,,2");
+    assert_eq!(forward(&mut engine, "a.join(null)"), "1nullnullnull2");
+    assert_eq!(forward(&mut engine, "[1, 2].join(0)"), "102");
+    assert_eq!(
+        forward(&mut engine, "[1, 2].join({ toString: () => '-' })"),
+        "1-2"
+    );
+}
+
+#[test]
+fn iterators() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = ['a', 'b'];");
+    assert_eq!(
+        forward(&mut engine, "a[Symbol.iterator] === a.values"),
+        "true"
+    );
+    forward(&mut engine, "var it = a.values();");
+    assert_eq!(forward(&mut engine, "it[Symbol.iterator]() === it"), "true");
+    assert_eq!(forward(&mut engine, "it.next().value"), "a");
+    // the length is read at each step
+    forward(&mut engine, "a.push('c');");
+    assert_eq!(forward(&mut engine, "it.next().value"), "b");
+    assert_eq!(forward(&mut engine, "it.next().value"), "c");
+    assert_eq!(forward(&mut engine, "it.next().done"), "true");
+    forward(&mut engine, "a.push('d');");
+    assert_eq!(forward(&mut engine, "it.next().done"), "true");
+
+    forward(&mut engine, "var keys = a.keys();");
+    assert_eq!(forward(&mut engine, "keys.next().value"), "0");
+    assert_eq!(forward(&mut engine, "keys.next().value"), "1");
+    forward(&mut engine, "var entries = a.entries(); entries.next();");
+    assert_eq!(forward(&mut engine, "entries.next().value.join()"), "1,b");
+
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var other = { next: it.next }; try { other.next() } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn getters_of_elements() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var a = [1, 2];
+        Object.defineProperty(a, 0, { get: function () { return 9; } });
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "a[0]"), "9");
+    assert_eq!(forward(&mut engine, "a.join()"), "9,2");
+    assert_eq!(forward(&mut engine, "a.indexOf(9)"), "0");
+    assert_eq!(forward(&mut engine, "a.includes(9)"), "true");
+    assert_eq!(forward(&mut engine, "a.map(x => x + 1).join()"), "10,3");
+    assert_eq!(forward(&mut engine, "a.slice(0, 1)[0]"), "9");
+    assert_eq!(forward(&mut engine, "a.values().next().value"), "9");
+    assert_eq!(forward(&mut engine, "JSON.stringify(a)"), "[9,2]");
+}
@@ -19,34 +19,115 @@ mod tests;
 use crate::{
     builtins::{
         function::make_builtin_fn,
-        object::InternalState,
-        value::{display_obj, ResultValue, Value},
+        intrinsics::Intrinsic,
+        object::{enumerable_keys, InternalState, ObjectKind},
+        value::{display::log_string_from, display_obj, ResultValue, Value},
     },
     exec::Interpreter,
+    host::HostOperation,
     BoaProfiler,
 };
 use rustc_hash::FxHashMap;
-use std::time::SystemTime;
+use std::{fmt::Debug, mem};
 
 /// This is the internal console object state.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ConsoleState {
     count_map: FxHashMap<String, u32>,
     timer_map: FxHashMap<String, u128>,
     groups: Vec<String>,
+    writer: Box<dyn ConsoleWriter>,
+    /// The messages logged and not yet written, until the host policy allows them.
+    pending: Vec<LogMessage>,
+}
+
+impl Default for ConsoleState {
+    fn default() -> Self {
+        Self {
+            count_map: FxHashMap::default(),
+            timer_map: FxHashMap::default(),
+            groups: Vec::new(),
+            writer: Box::new(StdWriter),
+            pending: Vec::new(),
+        }
+    }
 }
 
 impl InternalState for ConsoleState {}
 
 /// This represents the different types of log messages.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LogMessage {
     Log(String),
+    Debug(String),
     Info(String),
     Warn(String),
     Error(String),
 }
 
+impl LogMessage {
+    /// Gets the text of the message.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Log(msg)
+            | Self::Debug(msg)
+            | Self::Info(msg)
+            | Self::Warn(msg)
+            | Self::Error(msg) => msg,
+        }
+    }
+
+    /// Replaces the text of the message, keeping its type.
+    fn map<F>(self, f: F) -> Self
+    where
+        F: FnOnce(String) -> String,
+    {
+        match self {
+            Self::Log(msg) => Self::Log(f(msg)),
+            Self::Debug(msg) => Self::Debug(f(msg)),
+            Self::Info(msg) => Self::Info(f(msg)),
+            Self::Warn(msg) => Self::Warn(f(msg)),
+            Self::Error(msg) => Self::Error(f(msg)),
+        }
+    }
+}
+
+/// The destination of the messages printed with the `console` object.
+///
+/// Embedders can capture the output of the scripts by implementing this trait, and setting it
+/// with `Interpreter::set_console_writer`.
+pub trait ConsoleWriter: Debug {
+    /// Writes a message, which has already been formatted and indented.
+    fn write(&mut self, msg: LogMessage);
+}
+
+/// The default console writer, which prints the errors and warnings to the standard error, and
+/// the other messages to the standard output.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdWriter;
+
+impl ConsoleWriter for StdWriter {
+    fn write(&mut self, msg: LogMessage) {
+        match msg {
+            LogMessage::Error(msg) | LogMessage::Warn(msg) => eprintln!("{}", msg),
+            LogMessage::Log(msg) | LogMessage::Debug(msg) | LogMessage::Info(msg) => {
+                println!("{}", msg)
+            }
+        }
+    }
+}
+
+impl Interpreter {
+    /// Sets where the messages printed with the `console` object are written.
+    pub fn set_console_writer<W>(&mut self, writer: W)
+    where
+        W: ConsoleWriter + 'static,
+    {
+        let console = self.realm().intrinsic(Intrinsic::Console);
+        with_state(&console, self, |state| state.writer = Box::new(writer));
+    }
+}
+
 /// Helper function that returns the argument at a specified index.
 fn get_arg_at_index<'a, T>(args: &'a [Value], index: usize) -> Option<T>
 where
@@ -55,79 +136,160 @@ where
     args.get(index).map(|s| T::from(s))
 }
 
-/// Helper function for logging messages.
-pub fn logger(msg: LogMessage, console_state: &ConsoleState) {
-    let indent = 2 * console_state.groups.len();
-
-    match msg {
-        LogMessage::Error(msg) => {
-            eprintln!("{:>width$}", msg, width = indent);
+/// Helper function that runs `f` with the state of the console.
+///
+/// The `console` functions can be called with any `this`, for example when one of them is
+/// registered as a global function, so the state of the global `console` object is used when
+/// `this` doesn't have one.
+fn with_state<R, F>(this: &Value, ctx: &Interpreter, f: F) -> R
+where
+    F: FnOnce(&mut ConsoleState) -> R,
+{
+    fn has_state(value: &Value) -> bool {
+        match value.get_internal_state() {
+            Some(state) => state.downcast_ref::<ConsoleState>().is_some(),
+            None => false,
         }
-        LogMessage::Log(msg) | LogMessage::Info(msg) | LogMessage::Warn(msg) => {
-            println!("{:>width$}", msg, width = indent);
+    }
+
+    if has_state(this) {
+        return this.with_internal_state_mut(f);
+    }
+    let console = ctx.realm().intrinsic(Intrinsic::Console);
+    if has_state(&console) {
+        console.with_internal_state_mut(f)
+    } else {
+        f(&mut ConsoleState::default())
+    }
+}
+
+/// Helper function that runs `f` with the state of the console, then writes the messages it
+/// logged, once the host policy allows each of them.
+fn log_with_state<R, F>(this: &Value, ctx: &mut Interpreter, f: F) -> Result<R, Value>
+where
+    F: FnOnce(&mut ConsoleState) -> R,
+{
+    let (result, messages) = with_state(this, ctx, |state| {
+        let result = f(state);
+        (result, mem::take(&mut state.pending))
+    });
+    for msg in messages {
+        if ctx.check_host_operation(&HostOperation::ConsoleOutput(&msg))? {
+            with_state(this, ctx, |state| state.writer.write(msg));
         }
     }
+    Ok(result)
+}
+
+/// Helper function that returns the label passed as the first argument, or `"default"`.
+fn get_label(args: &[Value], ctx: &mut Interpreter) -> Result<String, Value> {
+    match args.first() {
+        Some(value) if !value.is_undefined() => ctx.to_string(value),
+        _ => Ok("default".to_owned()),
+    }
+}
+
+/// Helper function for logging messages.
+///
+/// Every line of the message is indented by the groups that are open. The message is written
+/// once the function which logged it returns, by `log_with_state`.
+pub fn logger(msg: LogMessage, console_state: &mut ConsoleState) {
+    let indent = "  ".repeat(console_state.groups.len());
+    let msg = if indent.is_empty() {
+        msg
+    } else {
+        msg.map(|msg| {
+            msg.split('\n')
+                .map(|line| format!("{}{}", indent, line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    };
+
+    console_state.pending.push(msg);
 }
 
 /// This represents the `console` formatter.
+///
+/// If the first value is a string, its `%s`, `%d`, `%i`, `%f`, `%o`, `%O` and `%c` specifiers
+/// are replaced by the values that follow it. The values left are appended, separated by spaces.
+///
+/// More information:
+///  - [WHATWG `console` specification][spec]
+///
+/// [spec]: https://console.spec.whatwg.org/#formatter
 pub fn formatter(data: &[Value], ctx: &mut Interpreter) -> Result<String, Value> {
-    let target = ctx.to_string(&data.get(0).cloned().unwrap_or_default())?;
-    match data.len() {
-        0 => Ok(String::new()),
-        1 => Ok(target),
-        _ => {
-            let mut formatted = String::new();
-            let mut arg_index = 1;
-            let mut chars = target.chars();
-            while let Some(c) = chars.next() {
-                if c == '%' {
-                    let fmt = chars.next().unwrap_or('%');
-                    match fmt {
-                        /* integer */
-                        'd' | 'i' => {
-                            let arg = get_arg_at_index::<i32>(data, arg_index).unwrap_or_default();
-                            formatted.push_str(&format!("{}", arg));
-                            arg_index += 1;
-                        }
-                        /* float */
-                        'f' => {
-                            let arg = get_arg_at_index::<f64>(data, arg_index).unwrap_or_default();
-                            formatted.push_str(&format!("{number:.prec$}", number = arg, prec = 6));
-                            arg_index += 1
-                        }
-                        /* object, FIXME: how to render this properly? */
-                        'o' | 'O' => {
-                            let arg = data.get(arg_index).cloned().unwrap_or_default();
-                            formatted.push_str(&format!("{}", arg));
-                            arg_index += 1
-                        }
-                        /* string */
-                        's' => {
-                            let arg =
-                                ctx.to_string(&data.get(arg_index).cloned().unwrap_or_default())?;
-                            formatted.push_str(&arg);
-                            arg_index += 1
-                        }
-                        '%' => formatted.push('%'),
-                        /* TODO: %c is not implemented */
-                        c => {
-                            formatted.push('%');
-                            formatted.push(c);
-                        }
-                    }
-                } else {
-                    formatted.push(c);
-                };
-            }
+    let target = match data.first() {
+        None => return Ok(String::new()),
+        Some(target) if target.is_string() && data.len() == 1 => return ctx.to_string(target),
+        Some(target) if target.is_string() => ctx.to_string(target)?,
+        Some(_) => {
+            return Ok(data
+                .iter()
+                .map(|value| log_string_from(value, false))
+                .collect::<Vec<_>>()
+                .join(" "))
+        }
+    };
 
-            /* unformatted data */
-            for rest in data.iter().skip(arg_index) {
-                formatted.push_str(&format!(" {}", rest))
+    let mut formatted = String::new();
+    let mut arg_index = 1;
+    let mut chars = target.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            formatted.push(c);
+            continue;
+        }
+
+        let fmt = chars.next().unwrap_or('%');
+        let arg = match fmt {
+            'd' | 'i' | 'f' | 'o' | 'O' | 's' | 'c' => data.get(arg_index),
+            _ => None,
+        };
+        match (fmt, arg) {
+            /* integer */
+            ('d', Some(arg)) | ('i', Some(arg)) => {
+                let arg = ctx.to_number(arg)?;
+                formatted.push_str(&Value::from(arg.trunc()).to_string());
+            }
+            /* float */
+            ('f', Some(arg)) => {
+                let arg = ctx.to_number(arg)?;
+                formatted.push_str(&format!("{number:.prec$}", number = arg, prec = 6));
+            }
+            /* object */
+            ('o', Some(arg)) | ('O', Some(arg)) => {
+                formatted.push_str(&log_string_from(arg, false));
             }
+            /* string */
+            ('s', Some(arg)) => {
+                formatted.push_str(&ctx.to_string(arg)?);
+            }
+            /* CSS styles, which can't be rendered */
+            ('c', Some(_)) => {}
+            ('%', _) => formatted.push('%'),
+            /* unknown specifiers, and specifiers without a value, are rendered verbatim */
+            (c, _) => {
+                formatted.push('%');
+                formatted.push(c);
+            }
+        }
+        if arg.is_some() {
+            arg_index += 1;
+        }
+    }
 
-            Ok(formatted)
+    /* unformatted data */
+    for rest in data.iter().skip(arg_index) {
+        formatted.push(' ');
+        if rest.is_string() {
+            formatted.push_str(&rest.to_string());
+        } else {
+            formatted.push_str(&log_string_from(rest, false));
         }
     }
+
+    Ok(formatted)
 }
 
 /// `console.assert(condition, ...data)`
@@ -156,10 +318,8 @@ pub fn assert(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Result
             args[0] = Value::from(concat);
         }
 
-        this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-            logger(LogMessage::Error(formatter(&args, ctx)?), state);
-            Ok(())
-        })?;
+        let msg = formatter(&args, ctx)?;
+        log_with_state(this, ctx, |state| logger(LogMessage::Error(msg), state))?;
     }
 
     Ok(Value::undefined())
@@ -175,8 +335,8 @@ pub fn assert(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Result
 ///
 /// [spec]: https://console.spec.whatwg.org/#clear
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/clear
-pub fn clear(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+pub fn clear(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    with_state(this, ctx, |state| {
         state.groups.clear();
     });
 
@@ -194,10 +354,8 @@ pub fn clear(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue
 /// [spec]: https://console.spec.whatwg.org/#debug
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/debug
 pub fn debug(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-        logger(LogMessage::Log(formatter(args, ctx)?), state);
-        Ok(())
-    })?;
+    let msg = formatter(args, ctx)?;
+    log_with_state(this, ctx, |state| logger(LogMessage::Debug(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -212,10 +370,8 @@ pub fn debug(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 /// [spec]: https://console.spec.whatwg.org/#error
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/error
 pub fn error(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-        logger(LogMessage::Error(formatter(args, ctx)?), state);
-        Ok(())
-    })?;
+    let msg = formatter(args, ctx)?;
+    log_with_state(this, ctx, |state| logger(LogMessage::Error(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -230,10 +386,8 @@ pub fn error(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 /// [spec]: https://console.spec.whatwg.org/#info
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/info
 pub fn info(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-        logger(LogMessage::Info(formatter(args, ctx)?), state);
-        Ok(())
-    })?;
+    let msg = formatter(args, ctx)?;
+    log_with_state(this, ctx, |state| logger(LogMessage::Info(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -248,10 +402,8 @@ pub fn info(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
 /// [spec]: https://console.spec.whatwg.org/#log
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
 pub fn log(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-        logger(LogMessage::Log(formatter(args, ctx)?), state);
-        Ok(())
-    })?;
+    let msg = formatter(args, ctx)?;
+    log_with_state(this, ctx, |state| logger(LogMessage::Log(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -267,18 +419,16 @@ pub fn log(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultVal
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/trace
 pub fn trace(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     if !args.is_empty() {
-        this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-            logger(LogMessage::Log(formatter(args, ctx)?), state);
-            Ok(())
-        })?;
+        let msg = formatter(args, ctx)?;
+        log_with_state(this, ctx, |state| {
+            logger(LogMessage::Log(msg), state);
 
-        /* TODO: get and print stack trace */
-        this.with_internal_state_ref(|state| {
+            /* TODO: get and print stack trace */
             logger(
                 LogMessage::Log("Not implemented: <stack trace>".to_string()),
                 state,
             )
-        });
+        })?;
     }
 
     Ok(Value::undefined())
@@ -295,10 +445,8 @@ pub fn trace(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 /// [spec]: https://console.spec.whatwg.org/#warn
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/warn
 pub fn warn(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_ref::<_, Result<(), Value>, _>(|state| {
-        logger(LogMessage::Warn(formatter(args, ctx)?), state);
-        Ok(())
-    })?;
+    let msg = formatter(args, ctx)?;
+    log_with_state(this, ctx, |state| logger(LogMessage::Warn(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -313,18 +461,15 @@ pub fn warn(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
 /// [spec]: https://console.spec.whatwg.org/#count
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/count
 pub fn count(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let label = match args.get(0) {
-        Some(value) => ctx.to_string(value)?,
-        None => "default".to_owned(),
-    };
+    let label = get_label(args, ctx)?;
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+    log_with_state(this, ctx, |state| {
         let msg = format!("count {}:", &label);
         let c = state.count_map.entry(label).or_insert(0);
         *c += 1;
 
         logger(LogMessage::Info(format!("{} {}", msg, c)), state);
-    });
+    })?;
 
     Ok(Value::undefined())
 }
@@ -340,26 +485,20 @@ pub fn count(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 /// [spec]: https://console.spec.whatwg.org/#countreset
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/countReset
 pub fn count_reset(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let label = match args.get(0) {
-        Some(value) => ctx.to_string(value)?,
-        None => "default".to_owned(),
-    };
+    let label = get_label(args, ctx)?;
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+    log_with_state(this, ctx, |state| {
         state.count_map.remove(&label);
 
         logger(LogMessage::Warn(format!("countReset {}", label)), state);
-    });
+    })?;
 
     Ok(Value::undefined())
 }
 
-/// Returns current system time in ms.
-fn system_time_in_ms() -> u128 {
-    let now = SystemTime::now();
-    now.duration_since(SystemTime::UNIX_EPOCH)
-        .expect("negative duration")
-        .as_millis()
+/// Returns the current time in ms, from the clock of the interpreter.
+fn time_in_ms(ctx: &mut Interpreter) -> u128 {
+    ctx.now() as u128
 }
 
 /// `console.time(label)`
@@ -373,22 +512,19 @@ fn system_time_in_ms() -> u128 {
 /// [spec]: https://console.spec.whatwg.org/#time
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/time
 pub fn time(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let label = match args.get(0) {
-        Some(value) => ctx.to_string(value)?,
-        None => "default".to_owned(),
-    };
+    let label = get_label(args, ctx)?;
+    let time = time_in_ms(ctx);
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+    log_with_state(this, ctx, |state| {
         if state.timer_map.get(&label).is_some() {
             logger(
                 LogMessage::Warn(format!("Timer '{}' already exist", label)),
                 state,
             );
         } else {
-            let time = system_time_in_ms();
             state.timer_map.insert(label, time);
         }
-    });
+    })?;
 
     Ok(Value::undefined())
 }
@@ -404,17 +540,17 @@ pub fn time(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultVa
 /// [spec]: https://console.spec.whatwg.org/#timelog
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/timeLog
 pub fn time_log(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let label = match args.get(0) {
-        Some(value) => ctx.to_string(value)?,
-        None => "default".to_owned(),
-    };
+    let label = get_label(args, ctx)?;
+    let data = formatter(args.get(1..).unwrap_or_default(), ctx)?;
+    let time = time_in_ms(ctx);
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+    log_with_state(this, ctx, |state| {
         if let Some(t) = state.timer_map.get(&label) {
-            let time = system_time_in_ms();
-            let mut concat = format!("{}: {} ms", label, time - t);
-            for msg in args.iter().skip(1) {
-                concat = concat + " " + &msg.to_string();
+            // A clock set by the embedder may go backwards.
+            let mut concat = format!("{}: {} ms", label, time.saturating_sub(*t));
+            if !data.is_empty() {
+                concat.push(' ');
+                concat.push_str(&data);
             }
             logger(LogMessage::Log(concat), state);
         } else {
@@ -423,7 +559,7 @@ pub fn time_log(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Resu
                 state,
             );
         }
-    });
+    })?;
 
     Ok(Value::undefined())
 }
@@ -439,16 +575,17 @@ pub fn time_log(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Resu
 /// [spec]: https://console.spec.whatwg.org/#timeend
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/timeEnd
 pub fn time_end(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let label = match args.get(0) {
-        Some(value) => ctx.to_string(value)?,
-        None => "default".to_owned(),
-    };
+    let label = get_label(args, ctx)?;
+    let time = time_in_ms(ctx);
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+    log_with_state(this, ctx, |state| {
         if let Some(t) = state.timer_map.remove(&label) {
-            let time = system_time_in_ms();
             logger(
-                LogMessage::Info(format!("{}: {} ms - timer removed", label, time - t)),
+                LogMessage::Info(format!(
+                    "{}: {} ms - timer removed",
+                    label,
+                    time.saturating_sub(t)
+                )),
                 state,
             );
         } else {
@@ -457,7 +594,7 @@ pub fn time_end(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Resu
                 state,
             );
         }
-    });
+    })?;
 
     Ok(Value::undefined())
 }
@@ -475,10 +612,12 @@ pub fn time_end(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Resu
 pub fn group(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let group_label = formatter(args, ctx)?;
 
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
-        logger(LogMessage::Info(format!("group: {}", &group_label)), state);
+    log_with_state(this, ctx, |state| {
+        if !group_label.is_empty() {
+            logger(LogMessage::Info(group_label.clone()), state);
+        }
         state.groups.push(group_label);
-    });
+    })?;
 
     Ok(Value::undefined())
 }
@@ -493,8 +632,8 @@ pub fn group(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultV
 ///
 /// [spec]: https://console.spec.whatwg.org/#groupend
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/groupEnd
-pub fn group_end(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+pub fn group_end(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    with_state(this, ctx, |state| {
         state.groups.pop();
     });
 
@@ -511,15 +650,147 @@ pub fn group_end(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultVa
 ///
 /// [spec]: https://console.spec.whatwg.org/#dir
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/dir
-pub fn dir(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    this.with_internal_state_mut(|state: &mut ConsoleState| {
+pub fn dir(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    log_with_state(this, ctx, |state| {
         let undefined = Value::undefined();
         logger(
             LogMessage::Info(display_obj(args.get(0).unwrap_or(&undefined), true)),
             state,
         );
-    });
+    })?;
+
+    Ok(Value::undefined())
+}
+
+/// Helper function that renders a value in a cell of a table.
+fn table_cell(value: &Value) -> String {
+    if value.is_string() {
+        format!("'{}'", value)
+    } else if value.is_function() {
+        "[Function]".to_owned()
+    } else if let Some(obj) = value.as_object() {
+        if obj.kind == ObjectKind::Array {
+            "[Array]".to_owned()
+        } else {
+            "[Object]".to_owned()
+        }
+    } else {
+        log_string_from(value, false)
+    }
+}
+
+/// Helper function that draws a table with the given header and rows.
+fn render_table(header: &[String], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain(Some(header[column].chars().count()))
+                .max()
+                .unwrap_or_default()
+                + 2
+        })
+        .collect();
+    let separator = |left: &str, middle: &str, right: &str| {
+        let lines: Vec<String> = widths.iter().map(|&width| "─".repeat(width)).collect();
+        format!("{}{}{}", left, lines.join(middle), right)
+    };
+    let row = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| format!("{:^width$}", cell, width = width))
+            .collect();
+        format!("│{}│", cells.join("│"))
+    };
+
+    let mut lines = vec![
+        separator("┌", "┬", "┐"),
+        row(header),
+        separator("├", "┼", "┤"),
+    ];
+    lines.extend(rows.iter().map(|cells| row(cells)));
+    lines.push(separator("└", "┴", "┘"));
+    lines.join("\n")
+}
+
+/// `console.table(tabularData, properties)`
+///
+/// Prints the properties of an object, or the elements of an array, as a table. Each property
+/// of the rows that are objects gets a column, unless the columns are given by `properties`.
+///
+/// More information:
+///  - [MDN documentation][mdn]
+///  - [WHATWG `console` specification][spec]
+///
+/// [spec]: https://console.spec.whatwg.org/#table
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/table
+pub fn table(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let data = args.first().cloned().unwrap_or_default();
+    if !data.is_object() {
+        return log(this, args, ctx);
+    }
+
+    let properties = match args.get(1) {
+        Some(properties) if properties.is_object() => Some(
+            enumerable_keys(properties)
+                .iter()
+                .map(|key| ctx.to_string(&properties.get_field(key.as_str())))
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        _ => None,
+    };
+
+    let mut columns = properties.clone().unwrap_or_default();
+    let mut has_values = false;
+    let mut rows = Vec::new();
+    for index in enumerable_keys(&data) {
+        let row = data.get_field(index.as_str());
+        let keys = if row.is_object() {
+            enumerable_keys(&row)
+        } else {
+            has_values = true;
+            Vec::new()
+        };
+        if properties.is_none() {
+            for key in &keys {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+        rows.push((index, row, keys));
+    }
+
+    let mut header = vec!["(index)".to_owned()];
+    header.extend(columns.iter().cloned());
+    if has_values {
+        header.push("Values".to_owned());
+    }
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|(index, row, keys)| {
+            let mut cells = vec![index];
+            cells.extend(columns.iter().map(|column| {
+                if keys.contains(column) {
+                    table_cell(&row.get_field(column.as_str()))
+                } else {
+                    String::new()
+                }
+            }));
+            if has_values {
+                cells.push(if row.is_object() {
+                    String::new()
+                } else {
+                    table_cell(&row)
+                });
+            }
+            cells
+        })
+        .collect();
 
+    let msg = render_table(&header, &rows);
+    log_with_state(this, ctx, |state| logger(LogMessage::Log(msg), state))?;
     Ok(Value::undefined())
 }
 
@@ -546,6 +817,7 @@ pub fn create(global: &Value) -> Value {
     make_builtin_fn(time_end, "timeEnd", &console, 0);
     make_builtin_fn(dir, "dir", &console, 0);
     make_builtin_fn(dir, "dirxml", &console, 0);
+    make_builtin_fn(table, "table", &console, 0);
 
     console.set_internal_state(ConsoleState::default());
 
@@ -1,8 +1,35 @@
 use crate::{
-    builtins::{console::formatter, value::Value},
+    builtins::{
+        console::{formatter, ConsoleWriter, LogMessage},
+        value::Value,
+    },
     exec::Interpreter,
+    forward,
+    host::{HostDecision, HostOperation, HostPolicy},
     realm::Realm,
 };
+use std::{cell::RefCell, rc::Rc};
+
+/// A console writer which keeps the messages, so they can be checked.
+#[derive(Debug, Clone, Default)]
+struct CapturingWriter(Rc<RefCell<Vec<LogMessage>>>);
+
+impl ConsoleWriter for CapturingWriter {
+    fn write(&mut self, msg: LogMessage) {
+        self.0.borrow_mut().push(msg);
+    }
+}
+
+/// Runs the script, and returns the messages it printed.
+fn capture(src: &str) -> Vec<LogMessage> {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let writer = CapturingWriter::default();
+    engine.set_console_writer(writer.clone());
+    forward(&mut engine, src);
+    let messages = writer.0.borrow().clone();
+    messages
+}
 
 #[test]
 fn formatter_no_args_is_empty_string() {
@@ -80,3 +107,227 @@ fn formatter_float_format_works() {
     let res = formatter(&val, &mut engine).unwrap();
     assert_eq!(res, "3.141500");
 }
+
+#[test]
+fn formatter_converts_integers() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let val = [
+        Value::string("%d %i %d"),
+        Value::string("42"),
+        Value::rational(-3.9),
+        Value::string("foo"),
+    ];
+    let res = formatter(&val, &mut engine).unwrap();
+    assert_eq!(res, "42 -3 NaN");
+}
+
+#[test]
+fn formatter_specifiers_without_values_render_verbatim() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let val = [Value::string("%s and %d"), Value::string("one")];
+    let res = formatter(&val, &mut engine).unwrap();
+    assert_eq!(res, "one and %d");
+}
+
+#[test]
+fn formatter_skips_styles() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let val = [
+        Value::string("%cstyled"),
+        Value::string("color: red"),
+        Value::integer(1),
+    ];
+    let res = formatter(&val, &mut engine).unwrap();
+    assert_eq!(res, "styled 1");
+}
+
+#[test]
+fn formatter_without_format_string_joins_values() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let val = [
+        Value::integer(1),
+        Value::string("two"),
+        Value::boolean(true),
+    ];
+    let res = formatter(&val, &mut engine).unwrap();
+    assert_eq!(res, "1 two true");
+}
+
+#[test]
+fn log_levels() {
+    let messages = capture(
+        r#"
+        console.log("log");
+        console.debug("debug");
+        console.info("info");
+        console.warn("warn");
+        console.error("error %s", "message");
+        console.assert(true, "not printed");
+        console.assert(false, "printed");
+        "#,
+    );
+    assert_eq!(
+        messages,
+        vec![
+            LogMessage::Log("log".to_owned()),
+            LogMessage::Debug("debug".to_owned()),
+            LogMessage::Info("info".to_owned()),
+            LogMessage::Warn("warn".to_owned()),
+            LogMessage::Error("error message".to_owned()),
+            LogMessage::Error("Assertion failed: printed".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn groups_indent_messages() {
+    let messages = capture(
+        r#"
+        console.group("outer");
+        console.log("one");
+        console.group();
+        console.log("two\nlines");
+        console.groupEnd();
+        console.groupEnd();
+        console.log("three");
+        "#,
+    );
+    let texts: Vec<&str> = messages.iter().map(LogMessage::text).collect();
+    assert_eq!(texts, vec!["outer", "  one", "    two\n    lines", "three"]);
+}
+
+#[test]
+fn counters() {
+    let messages = capture(
+        r#"
+        console.count();
+        console.count("label");
+        console.count();
+        console.countReset();
+        console.count();
+        "#,
+    );
+    let texts: Vec<&str> = messages.iter().map(LogMessage::text).collect();
+    assert_eq!(
+        texts,
+        vec![
+            "count default: 1",
+            "count label: 1",
+            "count default: 2",
+            "countReset default",
+            "count default: 1",
+        ]
+    );
+}
+
+#[test]
+fn table_of_objects() {
+    let messages = capture(
+        r#"
+        console.table([{ a: 1, b: "x" }, { b: "y", c: true }, 3]);
+        "#,
+    );
+    let texts: Vec<&str> = messages.iter().map(LogMessage::text).collect();
+    assert_eq!(
+        texts,
+        vec![[
+            "┌─────────┬───┬─────┬──────┬────────┐",
+            "│ (index) │ a │  b  │  c   │ Values │",
+            "├─────────┼───┼─────┼──────┼────────┤",
+            "│    0    │ 1 │ 'x' │      │        │",
+            "│    1    │   │ 'y' │ true │        │",
+            "│    2    │   │     │      │   3    │",
+            "└─────────┴───┴─────┴──────┴────────┘",
+        ]
+        .join("\n")]
+    );
+}
+
+#[test]
+fn table_with_columns() {
+    let messages = capture(
+        r#"
+        console.table({ first: { a: 1, b: 2 }, second: { a: 3 } }, ["b"]);
+        "#,
+    );
+    let texts: Vec<&str> = messages.iter().map(LogMessage::text).collect();
+    assert_eq!(
+        texts,
+        vec![[
+            "┌─────────┬───┐",
+            "│ (index) │ b │",
+            "├─────────┼───┤",
+            "│  first  │ 2 │",
+            "│ second  │   │",
+            "└─────────┴───┘",
+        ]
+        .join("\n")]
+    );
+}
+
+#[test]
+fn console_functions_can_be_called_without_console() {
+    let messages = capture(
+        r#"
+        var log = console.log;
+        log("detached");
+        "#,
+    );
+    assert_eq!(messages, vec![LogMessage::Log("detached".to_owned())]);
+}
+
+/// A host policy which records the messages written to the console, skips the debug messages
+/// and denies the errors.
+#[derive(Debug, Clone, Default)]
+struct AuditingPolicy(Rc<RefCell<Vec<String>>>);
+
+impl HostPolicy for AuditingPolicy {
+    fn check(&mut self, operation: &HostOperation<'_>) -> HostDecision {
+        match operation {
+            HostOperation::ConsoleOutput(msg) => {
+                self.0.borrow_mut().push(msg.text().to_owned());
+                match msg {
+                    LogMessage::Debug(_) => HostDecision::Skip,
+                    LogMessage::Error(_) => HostDecision::Deny("no errors".to_owned()),
+                    _ => HostDecision::Allow,
+                }
+            }
+            _ => HostDecision::Allow,
+        }
+    }
+}
+
+#[test]
+fn host_policy() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let writer = CapturingWriter::default();
+    engine.set_console_writer(writer.clone());
+    let policy = AuditingPolicy::default();
+    engine.set_host_policy(Box::new(policy.clone()));
+
+    assert_eq!(
+        forward(
+            &mut engine,
+            r#"
+            console.log("allowed");
+            console.debug("skipped");
+            try { console.error("denied"); } catch (e) { e.message }
+            "#
+        ),
+        "no errors"
+    );
+    assert_eq!(*policy.0.borrow(), vec!["allowed", "skipped", "denied"]);
+    assert_eq!(
+        *writer.0.borrow(),
+        vec![LogMessage::Log("allowed".to_owned())]
+    );
+}
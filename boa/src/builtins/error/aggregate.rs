@@ -0,0 +1,49 @@
+//! This module implements the global `AggregateError` object.
+//!
+//! The `AggregateError` object represents several errors wrapped in a single one, like the
+//! rejections of the promises given to `Promise.any`. Its `errors` property is an array of the
+//! wrapped errors, which can be `AggregateError` objects themselves.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-aggregate-error-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/AggregateError
+
+use super::{install_property, Error, JsNativeErrorKind};
+use crate::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+    profiler::BoaProfiler,
+};
+
+/// JavaScript `AggregateError` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AggregateError;
+
+impl AggregateError {
+    /// Create a new error object, from the iterable of its errors and its message.
+    pub(crate) fn make_error(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let error = Error::construct(this, args, JsNativeErrorKind::Aggregate, ctx)?;
+        let errors = args.first().cloned().unwrap_or_else(Value::undefined);
+        let errors = ctx.iterable_to_list(&errors)?;
+        install_property(&error, "errors", ctx.create_array(&errors));
+        Ok(error)
+    }
+
+    /// Create a new `AggregateError` object.
+    pub(crate) fn create(global: &Value) -> Value {
+        Error::create_native_error(global, JsNativeErrorKind::Aggregate, 2, Self::make_error)
+    }
+
+    /// Initialise the global object with the `AggregateError` object.
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("aggregateerror", "init");
+        global.set_field("AggregateError", Self::create(global));
+    }
+}
@@ -1,8 +1,11 @@
-//! This module implements the global `Error` object.
+//! This module implements the global `Error` object, and the native error types.
 //!
 //! Error objects are thrown when runtime errors occur.
 //! The Error object can also be used as a base object for user-defined exceptions.
 //!
+//! The errors thrown by the engine and the builtins are built with `JsNativeError`, so that they
+//! are the same objects as the ones created by the constructors.
+//!
 //! More information:
 //!  - [MDN documentation][mdn]
 //!  - [ECMAScript reference][spec]
@@ -12,23 +15,206 @@
 
 use crate::{
     builtins::{
-        function::{make_builtin_fn, make_constructor_fn},
-        object::ObjectKind,
+        function::{make_builtin_fn, make_constructor_fn, NativeFunctionData},
+        intrinsics::{self, Intrinsic},
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
+        property::Property,
         value::{ResultValue, Value},
     },
     exec::Interpreter,
     profiler::BoaProfiler,
 };
 
+pub(crate) mod aggregate;
 // mod eval;
 pub(crate) mod range;
-// mod reference;
-// mod syntax;
+pub(crate) mod reference;
+pub(crate) mod syntax;
 pub(crate) mod r#type;
 // mod uri;
 
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::aggregate::AggregateError;
 pub(crate) use self::r#type::TypeError;
 pub(crate) use self::range::RangeError;
+pub(crate) use self::reference::ReferenceError;
+pub(crate) use self::syntax::SyntaxError;
+
+/// The kinds of the native errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsNativeErrorKind {
+    /// An `Error`.
+    Error,
+    /// An `AggregateError`, which holds a list of errors.
+    Aggregate,
+    /// A `RangeError`.
+    Range,
+    /// A `ReferenceError`.
+    Reference,
+    /// A `SyntaxError`.
+    Syntax,
+    /// A `TypeError`.
+    Type,
+}
+
+impl JsNativeErrorKind {
+    /// Gets the name of the constructor of the errors of this kind.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Error => "Error",
+            Self::Aggregate => "AggregateError",
+            Self::Range => "RangeError",
+            Self::Reference => "ReferenceError",
+            Self::Syntax => "SyntaxError",
+            Self::Type => "TypeError",
+        }
+    }
+
+    /// Gets the prototype of the errors of this kind.
+    pub(crate) fn prototype(self, ctx: &Interpreter) -> Value {
+        let prototype = match self {
+            Self::Error => Intrinsic::ErrorPrototype,
+            Self::Aggregate => Intrinsic::AggregateErrorPrototype,
+            Self::Range => Intrinsic::RangeErrorPrototype,
+            Self::Reference => Intrinsic::ReferenceErrorPrototype,
+            Self::Syntax => Intrinsic::SyntaxErrorPrototype,
+            Self::Type => Intrinsic::TypeErrorPrototype,
+        };
+        ctx.realm().intrinsic(prototype)
+    }
+}
+
+/// A native error, which is turned into an error object when it is thrown.
+///
+/// ```ignore
+/// JsNativeError::typ()
+///     .with_message("the callback is not a function")
+///     .with_cause(cause)
+///     .throw(ctx)
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsNativeError {
+    kind: JsNativeErrorKind,
+    message: Option<String>,
+    cause: Option<Value>,
+    errors: Vec<Value>,
+}
+
+impl JsNativeError {
+    /// Creates a native error of the given kind, without a message.
+    pub fn new(kind: JsNativeErrorKind) -> Self {
+        Self {
+            kind,
+            message: None,
+            cause: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Creates an `Error`.
+    pub fn error() -> Self {
+        Self::new(JsNativeErrorKind::Error)
+    }
+
+    /// Creates an `AggregateError` holding the given errors.
+    pub fn aggregate(errors: Vec<Value>) -> Self {
+        Self {
+            errors,
+            ..Self::new(JsNativeErrorKind::Aggregate)
+        }
+    }
+
+    /// Creates a `RangeError`.
+    pub fn range() -> Self {
+        Self::new(JsNativeErrorKind::Range)
+    }
+
+    /// Creates a `ReferenceError`.
+    pub fn reference() -> Self {
+        Self::new(JsNativeErrorKind::Reference)
+    }
+
+    /// Creates a `SyntaxError`.
+    pub fn syntax() -> Self {
+        Self::new(JsNativeErrorKind::Syntax)
+    }
+
+    /// Creates a `TypeError`.
+    pub fn typ() -> Self {
+        Self::new(JsNativeErrorKind::Type)
+    }
+
+    /// Sets the message of the error.
+    pub fn with_message<M>(mut self, message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the cause of the error, which is usually the error which led to it.
+    pub fn with_cause(mut self, cause: Value) -> Self {
+        self.cause = Some(cause);
+        self
+    }
+
+    /// Gets the kind of the error.
+    pub fn kind(&self) -> JsNativeErrorKind {
+        self.kind
+    }
+
+    /// Gets the message of the error, if it has one.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Creates the error object.
+    pub fn to_value(&self, ctx: &Interpreter) -> Value {
+        let error = Value::new_object_from_prototype(self.kind.prototype(ctx), ObjectKind::Error);
+        if let Some(ref message) = self.message {
+            install_property(&error, "message", Value::from(message.as_str()));
+        }
+        if let Some(ref cause) = self.cause {
+            install_property(&error, "cause", cause.clone());
+        }
+        if self.kind == JsNativeErrorKind::Aggregate {
+            install_property(&error, "errors", ctx.create_array(&self.errors));
+        }
+        error
+    }
+
+    /// Throws the error object.
+    ///
+    /// The error is returned in the `Err` variant, so it can be propagated with `?`.
+    pub fn throw(&self, ctx: &Interpreter) -> ResultValue {
+        Err(self.to_value(ctx))
+    }
+}
+
+/// Defines an own property of an error object, which is writable, configurable and not
+/// enumerable, like the properties of the prototypes.
+fn install_property(error: &Value, name: &str, value: Value) {
+    error.set_property_slice(
+        name,
+        Property::default()
+            .value(value)
+            .writable(true)
+            .enumerable(false)
+            .configurable(true),
+    );
+}
+
+/// Checks if a value is an error object, created by one of the error constructors or thrown by
+/// the engine.
+pub(crate) fn is_error(value: &Value) -> bool {
+    match value.as_object() {
+        Some(object) => object.kind == ObjectKind::Error,
+        None => false,
+    }
+}
 
 /// Built-in `Error` object.
 #[derive(Debug, Clone, Copy)]
@@ -36,27 +222,72 @@ pub(crate) struct Error;
 
 impl Error {
     /// Create a new error object.
-    pub(crate) fn make_error(this: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-        if !args.is_empty() {
-            this.set_field(
-                "message",
-                Value::from(
-                    args.get(0)
-                        .expect("failed getting error message")
-                        .to_string(),
-                ),
-            );
+    pub(crate) fn make_error(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        Self::construct(this, args, JsNativeErrorKind::Error, ctx)
+    }
+
+    /// Creates an error object of the given kind, from the arguments of its constructor.
+    ///
+    /// When the constructor is called without `new`, `this` isn't a new object inheriting from
+    /// the prototype of the errors, so the error is created here.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-nativeerror
+    pub(crate) fn construct(
+        this: &mut Value,
+        args: &[Value],
+        kind: JsNativeErrorKind,
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let prototype = kind.prototype(ctx);
+        let is_new_instance = !is_error(this)
+            && this.is_object()
+            && this
+                .get_internal_slot(INSTANCE_PROTOTYPE)
+                .strict_equals(&prototype);
+        let error = if is_new_instance {
+            this.set_kind(ObjectKind::Error);
+            this.clone()
+        } else {
+            Value::new_object_from_prototype(prototype, ObjectKind::Error)
+        };
+
+        // The errors of an `AggregateError` come before its message.
+        let args = if kind == JsNativeErrorKind::Aggregate {
+            args.get(1..).unwrap_or_default()
+        } else {
+            args
+        };
+        match args.first() {
+            Some(message) if !message.is_undefined() => {
+                let message = ctx.to_string(message)?;
+                install_property(&error, "message", Value::from(message));
+            }
+            _ => {}
+        }
+        // The cause is only installed if the options have one, even if it is `undefined`.
+        if let Some(options) = args.get(1) {
+            if options.is_object() && options.has_field("cause") {
+                let cause = ctx.get_field_value(options, Value::from("cause"))?;
+                install_property(&error, "cause", cause);
+            }
         }
-        // This value is used by console.log and other routines to match Object type
-        // to its Javascript Identifier (global constructor method name)
-        this.set_kind(ObjectKind::Error);
-        Err(this.clone())
+        Ok(error)
     }
 
     /// `Error.prototype.toString()`
     ///
     /// The toString() method returns a string representing the specified Error object.
     ///
+    /// The name defaults to `"Error"` and the message to an empty string, and either is left out
+    /// if it is empty.
+    ///
     /// More information:
     ///  - [MDN documentation][mdn]
     ///  - [ECMAScript reference][spec]
@@ -64,20 +295,78 @@ impl Error {
     /// [spec]: https://tc39.es/ecma262/#sec-error.prototype.tostring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Error/toString
     #[allow(clippy::wrong_self_convention)]
-    pub(crate) fn to_string(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        let name = this.get_field("name");
-        let message = this.get_field("message");
-        Ok(Value::from(format!("{}: {}", name, message)))
+    pub(crate) fn to_string(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if !this.is_object() {
+            return JsNativeError::typ()
+                .with_message("Error.prototype.toString: 'this' is not an object")
+                .throw(ctx);
+        }
+        let name = ctx.get_field_value(this, Value::from("name"))?;
+        let name = if name.is_undefined() {
+            "Error".to_owned()
+        } else {
+            ctx.to_string(&name)?
+        };
+        let message = ctx.get_field_value(this, Value::from("message"))?;
+        let message = if message.is_undefined() {
+            String::new()
+        } else {
+            ctx.to_string(&message)?
+        };
+
+        Ok(Value::from(if name.is_empty() {
+            message
+        } else if message.is_empty() {
+            name
+        } else {
+            format!("{}: {}", name, message)
+        }))
+    }
+
+    /// `Error.isError( value )`
+    ///
+    /// Checks if a value is an error object, even if it doesn't inherit from `Error.prototype`.
+    ///
+    /// More information:
+    ///  - [Proposal][proposal]
+    ///
+    /// [proposal]: https://tc39.es/proposal-is-error/
+    pub(crate) fn is_error(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+        Ok(Value::from(
+            matches!(args.first(), Some(value) if is_error(value)),
+        ))
     }
 
     /// Create a new `Error` object.
     pub(crate) fn create(global: &Value) -> Value {
         let prototype = Value::new_object(Some(global));
-        prototype.set_field("message", Value::from(""));
+        install_property(&prototype, "name", Value::from("Error"));
+        install_property(&prototype, "message", Value::from(""));
 
         make_builtin_fn(Self::to_string, "toString", &prototype, 0);
 
-        make_constructor_fn("Error", 1, Self::make_error, global, prototype, true)
+        let error = make_constructor_fn("Error", 1, Self::make_error, global, prototype, true);
+        make_builtin_fn(Self::is_error, "isError", &error, 1);
+        error
+    }
+
+    /// Creates the constructor of a native error type, whose prototype inherits from
+    /// `Error.prototype`, so it runs after the `Error` object is initialised.
+    pub(crate) fn create_native_error(
+        global: &Value,
+        kind: JsNativeErrorKind,
+        length: i32,
+        constructor: NativeFunctionData,
+    ) -> Value {
+        let prototype = Value::new_object(Some(global));
+        prototype.set_internal_slot(
+            INSTANCE_PROTOTYPE,
+            intrinsics::get(global, Intrinsic::ErrorPrototype),
+        );
+        install_property(&prototype, "name", Value::from(kind.name()));
+        install_property(&prototype, "message", Value::from(""));
+
+        make_constructor_fn(kind.name(), length, constructor, global, prototype, true)
     }
 
     /// Initialise the global object with the `Error` object.
@@ -0,0 +1,44 @@
+//! This module implements the global `ReferenceError` object.
+//!
+//! Indicates an error that occurs when de-referencing an invalid reference, like a variable used
+//! before its declaration.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard-referenceerror
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/ReferenceError
+
+use super::{Error, JsNativeErrorKind};
+use crate::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+    profiler::BoaProfiler,
+};
+
+/// JavaScript `ReferenceError` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReferenceError;
+
+impl ReferenceError {
+    /// Create a new error object.
+    pub(crate) fn make_error(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        Error::construct(this, args, JsNativeErrorKind::Reference, ctx)
+    }
+
+    /// Create a new `ReferenceError` object.
+    pub(crate) fn create(global: &Value) -> Value {
+        Error::create_native_error(global, JsNativeErrorKind::Reference, 1, Self::make_error)
+    }
+
+    /// Initialise the global object with the `ReferenceError` object.
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("referenceerror", "init");
+        global.set_field("ReferenceError", Self::create(global));
+    }
+}
@@ -0,0 +1,44 @@
+//! This module implements the global `SyntaxError` object.
+//!
+//! The `SyntaxError` object represents an error when trying to interpret syntactically invalid
+//! code, such as the pattern or the flags of a regular expression.
+//!
+//! More information:
+//!  - [MDN documentation][mdn]
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-native-error-types-used-in-this-standard-syntaxerror
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/SyntaxError
+
+use super::{Error, JsNativeErrorKind};
+use crate::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+    profiler::BoaProfiler,
+};
+
+/// JavaScript `SyntaxError` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SyntaxError;
+
+impl SyntaxError {
+    /// Create a new error object.
+    pub(crate) fn make_error(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        Error::construct(this, args, JsNativeErrorKind::Syntax, ctx)
+    }
+
+    /// Create a new `SyntaxError` object.
+    pub(crate) fn create(global: &Value) -> Value {
+        Error::create_native_error(global, JsNativeErrorKind::Syntax, 1, Self::make_error)
+    }
+
+    /// Initialise the global object with the `SyntaxError` object.
+    pub(crate) fn init(global: &Value) {
+        let _timer = BoaProfiler::global().start_event("syntaxerror", "init");
+        global.set_field("SyntaxError", Self::create(global));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn to_string() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        function describe(object) {
+            object.toString = Error.prototype.toString;
+            return object.toString();
+        }
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "new Error('x').toString()"),
+        "Error: x"
+    );
+    assert_eq!(forward(&mut engine, "new Error().toString()"), "Error");
+    assert_eq!(
+        forward(&mut engine, "new RangeError('r').toString()"),
+        "RangeError: r"
+    );
+    assert_eq!(
+        forward(&mut engine, "describe({ name: '', message: 'm' })"),
+        "m"
+    );
+    assert_eq!(
+        forward(&mut engine, "describe({ name: 'N', message: '' })"),
+        "N"
+    );
+    assert_eq!(forward(&mut engine, "describe({})"), "Error");
+}
+
+#[test]
+fn constructors() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var plain = Error('y');
+        var typed = TypeError('t');
+        var constructed = new SyntaxError('s');
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "plain instanceof Error"), "true");
+    assert_eq!(forward(&mut engine, "plain.message"), "y");
+    assert_eq!(forward(&mut engine, "typed instanceof TypeError"), "true");
+    assert_eq!(forward(&mut engine, "constructed instanceof Error"), "true");
+    assert_eq!(forward(&mut engine, "constructed.name"), "SyntaxError");
+    assert_eq!(
+        forward(&mut engine, "new Error().hasOwnProperty('message')"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "Object.keys(new Error('x')).length"),
+        "0"
+    );
+}
+
+#[test]
+fn cause() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var inner = new Error('inner');
+        var outer = new Error('outer', { cause: inner });
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "outer.cause === inner"), "true");
+    assert_eq!(forward(&mut engine, "'cause' in inner"), "false");
+    assert_eq!(
+        forward(&mut engine, "'cause' in new Error('x', {})"),
+        "false"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Error('x', { cause: undefined }).hasOwnProperty('cause')"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn is_error() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "Error.isError(new Error())"), "true");
+    assert_eq!(forward(&mut engine, "Error.isError(TypeError())"), "true");
+    assert_eq!(
+        forward(&mut engine, "Error.isError({ name: 'Error', message: '' })"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "Error.isError(Object.create(Error.prototype))"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "try { null.x } catch (e) { Error.isError(e) }"),
+        "true"
+    );
+}
+
+#[test]
+fn aggregate_error() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var nested = new AggregateError(
+            [new AggregateError([new Error('a')], 'inner'), 1],
+            'outer'
+        );
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "AggregateError.length"), "2");
+    assert_eq!(forward(&mut engine, "nested instanceof Error"), "true");
+    assert_eq!(
+        forward(&mut engine, "nested.toString()"),
+        "AggregateError: outer"
+    );
+    assert_eq!(forward(&mut engine, "nested.errors.length"), "2");
+    assert_eq!(forward(&mut engine, "nested.errors[1]"), "1");
+    assert_eq!(
+        forward(&mut engine, "nested.errors[0].errors[0].message"),
+        "a"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { new AggregateError(1) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn getters_of_errors() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var fake = {
+            get name() { return 'Fake'; },
+            get message() { return 'from a getter'; },
+        };
+        fake.toString = Error.prototype.toString;
+        var error = new Error('message', { get cause() { return 'cause'; } });
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "fake.toString()"),
+        "Fake: from a getter"
+    );
+    assert_eq!(forward(&mut engine, "error.cause"), "cause");
+}
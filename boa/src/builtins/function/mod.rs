@@ -14,22 +14,37 @@
 use crate::{
     builtins::{
         array::Array,
+        intrinsics::{self, Intrinsic},
         object::{Object, ObjectInternalMethods, ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE},
         property::Property,
-        value::{ResultValue, Value},
+        value::{ResultValue, Value, ValueData},
     },
     environment::function_environment_record::BindingStatus,
     environment::lexical_environment::{new_function_environment, Environment},
     exec::{Executable, Interpreter},
-    syntax::ast::node::{FormalParameter, StatementList},
+    interner::Sym,
+    syntax::{
+        analyzer,
+        ast::node::{FormalParameter, StatementList},
+    },
     BoaProfiler,
 };
 use gc::{unsafe_empty_trace, Finalize, Trace};
-use std::fmt::{self, Debug};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug},
+    rc::Rc,
+};
 
 /// _fn(this, arguments, ctx) -> ResultValue_ - The signature of a built-in function
 pub type NativeFunctionData = fn(&mut Value, &[Value], &mut Interpreter) -> ResultValue;
 
+/// _fn(this, arguments, ctx) -> ResultValue_ - The signature of a built-in function which
+/// captures some state, like the functions registered by embedders.
+///
+/// The captured state isn't traced by the garbage collector, so it must not hold any `Value`.
+pub type NativeClosure = dyn Fn(&mut Value, &[Value], &mut Interpreter) -> ResultValue;
+
 /// Sets the ConstructorKind
 #[derive(Debug, Copy, Clone)]
 pub enum ConstructorKind {
@@ -50,13 +65,14 @@ pub enum ThisMode {
 #[derive(Clone, Finalize)]
 pub enum FunctionBody {
     BuiltIn(NativeFunctionData),
+    Closure(Rc<NativeClosure>),
     Ordinary(StatementList),
 }
 
 impl Debug for FunctionBody {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::BuiltIn(_) => write!(f, "native code"),
+            Self::BuiltIn(_) | Self::Closure(_) => write!(f, "native code"),
             Self::Ordinary(statements) => write!(f, "{:?}", statements),
         }
     }
@@ -65,7 +81,7 @@ impl Debug for FunctionBody {
 /// `Trace` implementation for `FunctionBody`.
 ///
 /// This is indeed safe, but we need to mark this as an empty trace because neither
-// `NativeFunctionData` nor Node hold any GC'd objects, but Gc doesn't know that. So we need to
+// `NativeFunctionData`, `NativeClosure` nor Node hold any GC'd objects, but Gc doesn't know that. So we need to
 /// signal it manually. `rust-gc` does not have a `Trace` implementation for `fn(_, _, _)`.
 ///
 /// <https://github.com/Manishearth/rust-gc/blob/master/gc/src/trace.rs>
@@ -73,6 +89,44 @@ unsafe impl Trace for FunctionBody {
     unsafe_empty_trace!();
 }
 
+/// A call in tail position, left by a `return` statement to the caller of its function.
+///
+/// The caller runs it once the frame of the function has been popped, in the same frame of the
+/// Rust stack, so that tail recursion runs in constant stack space.
+#[derive(Debug, Clone)]
+pub(crate) struct TailCall {
+    /// The called function.
+    func: Value,
+    /// The `this` value of the call.
+    this: Value,
+    /// The arguments of the call.
+    args: Vec<Value>,
+}
+
+impl TailCall {
+    /// Creates a new tail call.
+    pub(crate) fn new(func: Value, this: Value, args: Vec<Value>) -> Self {
+        Self { func, this, args }
+    }
+
+    /// Runs the call.
+    ///
+    /// The body of an ordinary function is run directly, in the frame of the caller. The other
+    /// functions are called as usual.
+    pub(crate) fn run(mut self, interpreter: &mut Interpreter) -> ResultValue {
+        if let ValueData::Object(ref obj) = *self.func.data() {
+            let obj = (**obj).borrow();
+            if let Some(ref func) = obj.func {
+                if let (true, FunctionBody::Ordinary(ref body)) = (func.callable, &func.body) {
+                    interpreter.replace_profile_frame(&self.func);
+                    return func.run_body(body, &self.func, &self.this, &self.args, interpreter);
+                }
+            }
+        }
+        interpreter.call(&self.func, &mut self.this, &self.args)
+    }
+}
+
 /// Boa representation of a Function Object.
 ///
 /// <https://tc39.es/ecma262/#sec-ecmascript-function-objects>
@@ -90,6 +144,12 @@ pub struct Function {
     constructable: bool,
     /// Is it callable.
     callable: bool,
+    /// The names of the `var` and function declarations of the body, which are bound when the
+    /// function is called.
+    var_names: Box<[Sym]>,
+    /// Whether the scope analysis found that this is a simple function, whose calls reuse the
+    /// environments of the finished calls and don't create an `arguments` object.
+    simple: bool,
 }
 
 impl Function {
@@ -104,16 +164,31 @@ impl Function {
     where
         P: Into<Box<[FormalParameter]>>,
     {
+        let params: Box<[FormalParameter]> = parameter_list.into();
+        let var_names = match body {
+            FunctionBody::Ordinary(ref body) => {
+                analyzer::var_declared_names(body.statements()).into()
+            }
+            _ => Box::default(),
+        };
+
         Self {
             body,
             environment: scope,
-            params: parameter_list.into(),
+            params,
             this_mode,
             constructable,
             callable,
+            var_names,
+            simple: false,
         }
     }
 
+    /// Sets whether this is a simple function, as found by the scope analysis.
+    pub(crate) fn set_simple(&mut self, simple: bool) {
+        self.simple = simple;
+    }
+
     /// This will create an ordinary function object
     ///
     /// <https://tc39.es/ecma262/#sec-ordinaryfunctioncreate>
@@ -154,6 +229,22 @@ impl Function {
         )
     }
 
+    /// This will create a built-in function object from a closure
+    pub fn closure<P>(parameter_list: P, body: Rc<NativeClosure>) -> Self
+    where
+        P: Into<Box<[FormalParameter]>>,
+    {
+        let _timer = BoaProfiler::global().start_event("function::closure", "function");
+        Self::new(
+            parameter_list.into(),
+            None,
+            FunctionBody::Closure(body),
+            ThisMode::NonLexical,
+            false,
+            true,
+        )
+    }
+
     /// This will handle calls for both ordinary and built-in functions
     ///
     /// <https://tc39.es/ecma262/#sec-prepareforordinarycall>
@@ -169,58 +260,26 @@ impl Function {
         if self.callable {
             match self.body {
                 FunctionBody::BuiltIn(func) => func(this_obj, args_list, interpreter),
-                FunctionBody::Ordinary(ref body) => {
-                    // Create a new Function environment who's parent is set to the scope of the function declaration (self.environment)
-                    // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
-                    let local_env = new_function_environment(
-                        this.clone(),
-                        None,
-                        Some(self.environment.as_ref().unwrap().clone()),
-                        BindingStatus::Uninitialized,
-                    );
-
-                    // Add argument bindings to the function environment
-                    for i in 0..self.params.len() {
-                        let param = self.params.get(i).expect("Could not get param");
-                        // Rest Parameters
-                        if param.is_rest_param() {
-                            self.add_rest_param(param, i, args_list, interpreter, &local_env);
-                            break;
-                        }
-
-                        let value = args_list.get(i).expect("Could not get value");
-                        self.add_arguments_to_environment(param, value.clone(), &local_env);
-                    }
-
-                    // Add arguments object
-                    let arguments_obj = create_unmapped_arguments_object(args_list);
-                    local_env
-                        .borrow_mut()
-                        .create_mutable_binding("arguments".to_string(), false);
-                    local_env
-                        .borrow_mut()
-                        .initialize_binding("arguments", arguments_obj);
-
-                    interpreter.realm.environment.push(local_env);
-
-                    // Call body should be set before reaching here
-                    let result = body.run(interpreter);
-
-                    // local_env gets dropped here, its no longer needed
-                    interpreter.realm.environment.pop();
-                    result
-                }
+                FunctionBody::Closure(ref func) => func(this_obj, args_list, interpreter),
+                FunctionBody::Ordinary(ref body) => interpreter.run_frame(|interpreter| {
+                    let result = self.run_body(body, this, this_obj, args_list, interpreter);
+                    interpreter.run_tail_calls(result)
+                }),
             }
         } else {
             panic!("TypeError: class constructors must be invoked with 'new'");
         }
     }
 
+    /// This will handle `new` calls, where `new_target` is the constructor called by `new`, given
+    /// to the function as `new.target`.
+    ///
     /// <https://tc39.es/ecma262/#sec-ecmascript-function-objects-construct-argumentslist-newtarget>
     pub fn construct(
         &self,
         this: &mut Value, // represents a pointer to this function object wrapped in a GC (not a `this` JS object)
         args_list: &[Value],
+        new_target: &Value,
         interpreter: &mut Interpreter,
         this_obj: &mut Value,
     ) -> ResultValue {
@@ -230,44 +289,42 @@ impl Function {
                     func(this_obj, args_list, interpreter)?;
                     Ok(this_obj.clone())
                 }
+                FunctionBody::Closure(ref func) => {
+                    func(this_obj, args_list, interpreter)?;
+                    Ok(this_obj.clone())
+                }
                 FunctionBody::Ordinary(ref body) => {
                     // Create a new Function environment who's parent is set to the scope of the function declaration (self.environment)
                     // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
-                    let local_env = new_function_environment(
-                        this.clone(),
-                        Some(this_obj.clone()),
-                        Some(self.environment.as_ref().unwrap().clone()),
+                    let local_env = self.new_environment(
+                        this,
+                        this_obj.clone(),
                         BindingStatus::Initialized,
+                        new_target.clone(),
+                        interpreter,
                     );
 
-                    // Add argument bindings to the function environment
-                    for (i, param) in self.params.iter().enumerate() {
-                        // Rest Parameters
-                        if param.is_rest_param() {
-                            self.add_rest_param(param, i, args_list, interpreter, &local_env);
-                            break;
-                        }
-
-                        let value = args_list.get(i).expect("Could not get value");
-                        self.add_arguments_to_environment(param, value.clone(), &local_env);
+                    let caller = interpreter
+                        .realm
+                        .environment
+                        .push_function(local_env.clone());
+                    if let Err(err) = self.instantiate(this, args_list, interpreter) {
+                        self.pop_environment(local_env, caller, interpreter);
+                        return Err(err);
                     }
 
-                    // Add arguments object
-                    let arguments_obj = create_unmapped_arguments_object(args_list);
-                    local_env
-                        .borrow_mut()
-                        .create_mutable_binding("arguments".to_string(), false);
-                    local_env
-                        .borrow_mut()
-                        .initialize_binding("arguments", arguments_obj);
-
-                    interpreter.realm.environment.push(local_env);
-
                     // Call body should be set before reaching here
-                    let _ = body.run(interpreter);
+                    let _ = interpreter.run_frame(|interpreter| {
+                        let result = body.run(interpreter);
+                        interpreter.run_tail_calls(result)
+                    });
+                    if self.has_parameter_expressions() {
+                        interpreter.realm.environment.pop();
+                    }
 
                     // local_env gets dropped here, its no longer needed
                     let binding = interpreter.realm.environment.get_this_binding();
+                    self.pop_environment(local_env, caller, interpreter);
                     Ok(binding)
                 }
             }
@@ -277,46 +334,211 @@ impl Function {
         }
     }
 
-    // Adds the final rest parameters to the Environment as an array
-    fn add_rest_param(
+    /// Runs the body of an ordinary function, in a new function environment.
+    ///
+    /// `this` is the function object, and `this_obj` the `this` value of the call, which the arrow
+    /// functions take from their scope instead.
+    fn run_body(
         &self,
-        param: &FormalParameter,
-        index: usize,
+        body: &StatementList,
+        this: &Value,
+        this_obj: &Value,
         args_list: &[Value],
         interpreter: &mut Interpreter,
-        local_env: &Environment,
-    ) {
-        // Create array of values
-        let array = Array::new_array(interpreter).unwrap();
-        Array::add_to_array_object(&array, &args_list[index..]).unwrap();
-
-        // Create binding
-        local_env
-            .borrow_mut()
-            .create_mutable_binding(param.name().to_owned(), false);
-
-        // Set Binding to value
-        local_env
-            .borrow_mut()
-            .initialize_binding(param.name(), array);
+    ) -> ResultValue {
+        // Functions that define their `this` box primitives, and replace `null` and `undefined`
+        // with the global object.
+        // <https://tc39.es/ecma262/#sec-ordinarycallbindthis>
+        let (binding_status, this_obj) = match self.this_mode {
+            ThisMode::Lexical => (BindingStatus::Lexical, this_obj.clone()),
+            ThisMode::NonLexical if this_obj.is_null_or_undefined() => (
+                BindingStatus::Initialized,
+                interpreter.realm().global_obj.clone(),
+            ),
+            ThisMode::NonLexical => (BindingStatus::Initialized, interpreter.to_object(this_obj)?),
+        };
+        // Create a new Function environment who's parent is set to the scope of the function declaration (self.environment)
+        // <https://tc39.es/ecma262/#sec-prepareforordinarycall>
+        let local_env = self.new_environment(
+            this,
+            this_obj,
+            binding_status,
+            Value::undefined(),
+            interpreter,
+        );
+
+        let caller = interpreter
+            .realm
+            .environment
+            .push_function(local_env.clone());
+
+        // Call body should be set before reaching here
+        let result = self
+            .instantiate(this, args_list, interpreter)
+            .and_then(|()| {
+                let result = body.run(interpreter);
+                if self.has_parameter_expressions() {
+                    interpreter.realm.environment.pop();
+                }
+                result
+            });
+
+        // local_env gets dropped here, its no longer needed
+        self.pop_environment(local_env, caller, interpreter);
+        result
     }
 
-    // Adds an argument to the environment
-    fn add_arguments_to_environment(
+    /// Creates the environment of a call, whose outer environment is the scope of the function.
+    ///
+    /// <https://tc39.es/ecma262/#sec-newfunctionenvironment>
+    fn new_environment(
         &self,
-        param: &FormalParameter,
-        value: Value,
-        local_env: &Environment,
+        this: &Value,
+        this_obj: Value,
+        binding_status: BindingStatus,
+        new_target: Value,
+        interpreter: &mut Interpreter,
+    ) -> Environment {
+        let outer = Some(self.environment.as_ref().unwrap().clone());
+        if self.simple {
+            interpreter
+                .realm
+                .environment
+                .new_simple_function_environment(
+                    this.clone(),
+                    Some(this_obj),
+                    outer,
+                    binding_status,
+                    new_target,
+                )
+        } else {
+            new_function_environment(
+                this.clone(),
+                Some(this_obj),
+                outer,
+                binding_status,
+                new_target,
+            )
+        }
+    }
+
+    /// Pops the environment of a finished call, restoring the environments of the caller, and
+    /// gives it back for the next calls to reuse if this is a simple function.
+    fn pop_environment(
+        &self,
+        local_env: Environment,
+        caller: Option<VecDeque<Environment>>,
+        interpreter: &mut Interpreter,
     ) {
-        // Create binding
-        local_env
-            .borrow_mut()
-            .create_mutable_binding(param.name().to_owned(), false);
-
-        // Set Binding to value
-        local_env
-            .borrow_mut()
-            .initialize_binding(param.name(), value);
+        let environment = &mut interpreter.realm.environment;
+        environment.pop_function(caller);
+        if self.simple {
+            environment.release_function_environment(local_env);
+        }
+    }
+
+    /// Binds the parameters and the `arguments` object of a call in the current environment,
+    /// which is the environment of the function, evaluating the default values of the missing
+    /// arguments from left to right, then binds the `var` declarations of the body to
+    /// `undefined`, in the order of the slots given by the scope analysis.
+    ///
+    /// When some parameters have default values, the `var` declarations of the body are bound in
+    /// a new environment, so that the closures created by the default values don't see them, and
+    /// those redeclaring a parameter start with its value. This environment is pushed last, and
+    /// must be popped once the body has run.
+    ///
+    /// <https://tc39.es/ecma262/#sec-functiondeclarationinstantiation>
+    fn instantiate(
+        &self,
+        this: &Value,
+        args_list: &[Value],
+        interpreter: &mut Interpreter,
+    ) -> Result<(), Value> {
+        let env = interpreter
+            .realm
+            .environment
+            .get_current_environment_ref()
+            .clone();
+
+        // The parameters are in their temporal dead zone until they are initialized, so that a
+        // default value can only refer to the previous parameters.
+        for param in self.params.iter() {
+            if !env.borrow().has_binding(param.name()) {
+                env.borrow_mut().create_mutable_binding(param.name(), false);
+            }
+        }
+
+        // The arguments object only holds the given arguments, and is hidden by a parameter
+        // with the same name. The simple functions don't use it.
+        let arguments = Sym::new("arguments");
+        if !self.simple && !env.borrow().has_binding(arguments) {
+            let arguments_obj = create_unmapped_arguments_object(args_list);
+            let mut env = env.borrow_mut();
+            env.create_mutable_binding(arguments, false);
+            env.initialize_binding(arguments, arguments_obj);
+        }
+
+        for (i, param) in self.params.iter().enumerate() {
+            let value = if param.is_rest_param() {
+                let array = Array::new_array(interpreter)?;
+                Array::add_to_array_object(&array, args_list.get(i..).unwrap_or_default())?;
+                array
+            } else {
+                match (args_list.get(i), param.init()) {
+                    (Some(value), _) if !value.is_undefined() => value.clone(),
+                    (_, Some(init)) => init.run(interpreter)?,
+                    (_, None) => Value::undefined(),
+                }
+            };
+
+            // The last of the parameters with the same name gives its value.
+            let mut env = env.borrow_mut();
+            if env.is_initialized(param.name()) {
+                env.set_mutable_binding(param.name(), value, false);
+            } else {
+                env.initialize_binding(param.name(), value);
+            }
+        }
+
+        if self.has_parameter_expressions() {
+            // The `this` binding stays in the environment of the parameters.
+            let var_env = new_function_environment(
+                this.clone(),
+                None,
+                None,
+                BindingStatus::Lexical,
+                Value::undefined(),
+            );
+            for &name in self.var_names.iter() {
+                if var_env.borrow().has_binding(name) {
+                    continue;
+                }
+                let value = if env.borrow().has_binding(name) {
+                    env.borrow().get_binding_value(name, false)
+                } else {
+                    Value::undefined()
+                };
+                let mut var_env = var_env.borrow_mut();
+                var_env.create_mutable_binding(name, false);
+                var_env.initialize_binding(name, value);
+            }
+            interpreter.realm.environment.push(var_env);
+        } else {
+            let mut env = env.borrow_mut();
+            for &name in self.var_names.iter() {
+                if !env.has_binding(name) {
+                    env.create_mutable_binding(name, false);
+                    env.initialize_binding(name, Value::undefined());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if some parameters have a default value.
+    fn has_parameter_expressions(&self) -> bool {
+        self.params.iter().any(|param| param.init().is_some())
     }
 
     /// Returns true if the function object is callable.
@@ -328,6 +550,11 @@ impl Function {
     pub fn is_constructable(&self) -> bool {
         self.constructable
     }
+
+    /// Returns true if the function takes `this` from its scope, like the arrow functions.
+    pub fn has_lexical_this(&self) -> bool {
+        matches!(self.this_mode, ThisMode::Lexical)
+    }
 }
 
 impl Debug for Function {
@@ -399,7 +626,7 @@ pub fn make_constructor_fn(
     constructor_fn.constructable = constructable;
 
     // Get reference to Function.prototype
-    let func_prototype = global.get_field("Function").get_field(PROTOTYPE);
+    let func_prototype = intrinsics::get(global, Intrinsic::FunctionPrototype);
 
     // Create the function object and point its instance prototype to Function.prototype
     let mut constructor_obj = Object::function();
@@ -439,20 +666,85 @@ where
     let name_copy: String = name.into();
     let label = format!("{}{}", String::from("make_builtin_fn: "), &name_copy);
     let _timer = BoaProfiler::global().start_event(&label, "init");
-    let func = Function::builtin(Vec::new(), function);
+    let new_func_obj = builtin_function(function, &name_copy, length);
+
+    parent.set_field(Value::from(name_copy), new_func_obj);
+}
+
+/// Creates a builtin method keyed by a symbol, like the `[Symbol.iterator]` methods of the
+/// iterable objects.
+pub fn make_builtin_symbol_fn(
+    function: NativeFunctionData,
+    symbol: &Value,
+    name: &str,
+    parent: &Value,
+    length: i32,
+) {
+    let _timer = BoaProfiler::global().start_event(&format!("make_builtin_fn: {}", name), "init");
+    let new_func_obj = builtin_function(function, name, length);
 
+    parent.set_field(symbol.clone(), new_func_obj);
+}
+
+/// Creates an accessor property of a prototype, whose getter and setter are builtin functions.
+///
+/// The property is configurable and not enumerable, like the accessors of the standard
+/// prototypes.
+pub fn make_builtin_accessor<N>(
+    getter: NativeFunctionData,
+    setter: Option<NativeFunctionData>,
+    name: N,
+    parent: &Value,
+) where
+    N: Into<String>,
+{
+    let name: String = name.into();
+    let mut property = Property::default()
+        .get(builtin_function(getter, &format!("get {}", name), 0))
+        .set(Value::undefined())
+        .enumerable(false)
+        .configurable(true);
+    if let Some(setter) = setter {
+        property = property.set(builtin_function(setter, &format!("set {}", name), 1));
+    }
+    parent.set_property(name, property);
+}
+
+/// Creates a builtin function object with its `length` and `name`.
+fn builtin_function(function: NativeFunctionData, name: &str, length: i32) -> Value {
     let mut new_func = Object::function();
-    new_func.set_func(func);
+    new_func.set_func(Function::builtin(Vec::new(), function));
 
     let new_func_obj = Value::from(new_func);
     new_func_obj.set_field("length", length);
+    new_func_obj.set_field("name", name);
+    new_func_obj
+}
 
-    parent.set_field(Value::from(name_copy), new_func_obj);
+/// `%ThrowTypeError%`
+///
+/// The function shared by the poisoned accessors of the strict mode functions and of their
+/// `arguments` objects, which throws a `TypeError` whenever it is called.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-%throwtypeerror%
+fn throw_type_error(_: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    ctx.throw_type_error(
+        "'caller', 'callee', and 'arguments' properties may not be accessed on strict mode \
+         functions or the arguments objects for calls to them",
+    )
 }
 
-/// Initialise the `Function` object on the global object.
+/// Initialise the `Function` object on the global object, and `%ThrowTypeError%`.
 #[inline]
 pub fn init(global: &Value) {
     let _timer = BoaProfiler::global().start_event("function", "init");
-    global.set_field("Function", create(global));
+    let function = create(global);
+    global.set_field("Function", function.clone());
+
+    let throw_type_error = builtin_function(throw_type_error, "", 0);
+    throw_type_error.set_internal_slot(INSTANCE_PROTOTYPE, function.get_field(PROTOTYPE));
+    intrinsics::set(global, Intrinsic::ThrowTypeError, throw_type_error);
 }
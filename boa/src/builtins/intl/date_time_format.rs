@@ -0,0 +1,294 @@
+//! This module implements the `Intl.DateTimeFormat` object.
+//!
+//! An `Intl.DateTimeFormat` object formats times, given in milliseconds since the epoch, with the
+//! numeric date and time patterns of a locale. The times are always formatted in UTC.
+//!
+//! More information:
+//!  - [ECMAScript Internationalization API reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma402/#datetimeformat-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat
+
+use super::{get_string_option, locale::Locale};
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn},
+        object::InternalState,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+
+/// The styles accepted by the `dateStyle` and `timeStyle` options.
+const STYLES: &[&str] = &["full", "long", "medium", "short"];
+
+/// The number of milliseconds in a day.
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// The largest time, in milliseconds from the epoch, that can be formatted.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-time-values-and-time-range
+const MAX_TIME: f64 = 8.64e15;
+
+/// The internal representation of an `Intl.DateTimeFormat` object.
+///
+/// The styles only select whether the date and the time are formatted: both are formatted with the
+/// numeric patterns of the locale, whatever the style.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DateTimeFormat {
+    /// The locale the times are formatted for.
+    locale: &'static Locale,
+    /// The style of the date, if it is formatted.
+    date_style: Option<&'static str>,
+    /// The style of the time, if it is formatted.
+    time_style: Option<&'static str>,
+}
+
+impl InternalState for DateTimeFormat {}
+
+/// A time, broken down into its fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct DateTime {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+}
+
+impl DateTime {
+    /// Breaks down a time, in milliseconds from the epoch, in UTC.
+    fn from_time(time: i64) -> Self {
+        let days = time.div_euclid(MS_PER_DAY);
+        let ms = time.rem_euclid(MS_PER_DAY);
+
+        // Converts the days to a date of the proleptic Gregorian calendar, with the algorithm of
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self {
+            year,
+            month,
+            day,
+            hour: ms / 3_600_000,
+            minute: ms / 60_000 % 60,
+            second: ms / 1000 % 60,
+        }
+    }
+
+    /// Formats the time with a CLDR date or time pattern.
+    ///
+    /// Only the numeric fields are supported: `y`, `M`, `d`, `H`, `h`, `m`, `s`, and the period `a`.
+    /// Doubling a numeric field pads it to two digits.
+    fn format(&self, pattern: &str) -> String {
+        let mut result = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(field) = chars.next() {
+            let mut width = 1;
+            while chars.peek() == Some(&field) {
+                chars.next();
+                width += 1;
+            }
+            let value = match field {
+                'y' => self.year,
+                'M' => self.month,
+                'd' => self.day,
+                'H' => self.hour,
+                'h' => match self.hour % 12 {
+                    0 => 12,
+                    hour => hour,
+                },
+                'm' => self.minute,
+                's' => self.second,
+                'a' => {
+                    result.push_str(if self.hour < 12 { "AM" } else { "PM" });
+                    continue;
+                }
+                _ => {
+                    result.push_str(&field.to_string().repeat(width));
+                    continue;
+                }
+            };
+            result.push_str(&format!("{:01$}", value, width));
+        }
+        result
+    }
+}
+
+impl DateTimeFormat {
+    /// Creates a date and time format from the `locales` and `options` arguments of the
+    /// constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-initializedatetimeformat
+    pub(crate) fn new(
+        locales: &Value,
+        options: &Value,
+        ctx: &mut Interpreter,
+    ) -> Result<Self, Value> {
+        let locale = Locale::from_value(locales, ctx)?;
+        let date_style = get_string_option(options, "dateStyle", STYLES, ctx)?;
+        let time_style = get_string_option(options, "timeStyle", STYLES, ctx)?;
+        Ok(Self {
+            locale,
+            date_style,
+            time_style,
+        })
+    }
+
+    /// Formats a time, in milliseconds from the epoch.
+    ///
+    /// The date is formatted when no style is given.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-formatdatetime
+    pub(crate) fn format(&self, time: i64) -> String {
+        let date_time = DateTime::from_time(time);
+        let date = date_time.format(self.locale.date);
+        let time = date_time.format(self.locale.time);
+        match (self.date_style, self.time_style) {
+            (Some(_), Some(_)) => format!("{}{}{}", date, self.locale.date_time_separator, time),
+            (None, Some(_)) => time,
+            _ => date,
+        }
+    }
+
+    /// `Intl.DateTimeFormat( [ locales [ , options ] ] )`
+    ///
+    /// Creates a new object which formats dates and times according to a locale.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat
+    pub(crate) fn construct_date_time_format(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let locales = args.first().cloned().unwrap_or_else(Value::undefined);
+        let options = args.get(1).cloned().unwrap_or_else(Value::undefined);
+        let date_time_format = Self::new(&locales, &options, ctx)?;
+        this.set_internal_state(date_time_format);
+        Ok(this.clone())
+    }
+
+    /// `Intl.DateTimeFormat.prototype.format( [ date ] )`
+    ///
+    /// Formats a time, in milliseconds from the epoch, according to the locale and the options of
+    /// the object. The current time is formatted when no time is given.
+    ///
+    /// The specification defines `format` as a getter returning a bound function, but it is a
+    /// method here.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/format
+    pub(crate) fn format_date_time(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let date_time_format = Self::this_date_time_format(this, "format", ctx)?;
+        let time = match args.first() {
+            Some(time) if !time.is_undefined() => ctx.to_number(time)?,
+            _ => ctx.now(),
+        };
+        if !time.is_finite() || time.abs() > MAX_TIME {
+            return ctx
+                .throw_range_error("Intl.DateTimeFormat.prototype.format: invalid time value");
+        }
+        Ok(Value::from(date_time_format.format(time.trunc() as i64)))
+    }
+
+    /// `Intl.DateTimeFormat.prototype.resolvedOptions()`
+    ///
+    /// Returns an object with the locale and the options used by the object.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.datetimeformat.prototype.resolvedoptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/resolvedOptions
+    pub(crate) fn resolved_options(
+        this: &mut Value,
+        _: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let date_time_format = Self::this_date_time_format(this, "resolvedOptions", ctx)?;
+        let global = ctx.realm().global_obj.clone();
+        let options = Value::new_object(Some(&global));
+        options.set_field("locale", date_time_format.locale.tag);
+        options.set_field("calendar", "gregory");
+        options.set_field("numberingSystem", "latn");
+        options.set_field("timeZone", "UTC");
+        if let Some(date_style) = date_time_format.date_style {
+            options.set_field("dateStyle", date_style);
+        }
+        if let Some(time_style) = date_time_format.time_style {
+            options.set_field("timeStyle", time_style);
+        }
+        Ok(options)
+    }
+
+    /// Gets the date and time format of `this`, and throws a `TypeError` if it isn't an
+    /// `Intl.DateTimeFormat`.
+    fn this_date_time_format(
+        this: &Value,
+        method: &str,
+        ctx: &mut Interpreter,
+    ) -> Result<Self, Value> {
+        let date_time_format = this
+            .get_internal_state()
+            .and_then(|state| state.downcast_ref::<Self>().copied());
+        if let Some(date_time_format) = date_time_format {
+            return Ok(date_time_format);
+        }
+        ctx.throw_type_error(format!(
+            "Intl.DateTimeFormat.prototype.{}: 'this' is not an Intl.DateTimeFormat",
+            method
+        ))?;
+        unreachable!();
+    }
+
+    /// Create a new `Intl.DateTimeFormat` object.
+    pub(crate) fn create(global: &Value) -> Value {
+        let prototype = Value::new_object(Some(global));
+
+        make_builtin_fn(Self::format_date_time, "format", &prototype, 1);
+        make_builtin_fn(Self::resolved_options, "resolvedOptions", &prototype, 0);
+
+        make_constructor_fn(
+            "DateTimeFormat",
+            0,
+            Self::construct_date_time_format,
+            global,
+            prototype,
+            true,
+        )
+    }
+}
@@ -0,0 +1,153 @@
+//! The locale data used by the `Intl` objects.
+//!
+//! Only a small subset of the [CLDR][cldr] is bundled: the symbols and the patterns of the numbers
+//! and the dates, for a few common locales.
+//!
+//! [cldr]: http://cldr.unicode.org/
+
+use crate::{
+    builtins::value::{Value, ValueData},
+    exec::Interpreter,
+};
+
+/// The data of a locale.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Locale {
+    /// The language tag of the locale.
+    pub(crate) tag: &'static str,
+    /// The decimal separator.
+    pub(crate) decimal: &'static str,
+    /// The separator of the groups of digits.
+    pub(crate) group: &'static str,
+    /// The number of digits the integer part needs to have above the first group, to be grouped.
+    pub(crate) min_grouping_digits: usize,
+    /// The pattern of the percentages, where `{}` is replaced by the number.
+    pub(crate) percent: &'static str,
+    /// The pattern of the dates.
+    pub(crate) date: &'static str,
+    /// The pattern of the times.
+    pub(crate) time: &'static str,
+    /// The separator between a date and a time.
+    pub(crate) date_time_separator: &'static str,
+}
+
+/// The bundled locales. The first one is the default locale.
+pub(crate) static LOCALES: &[Locale] = &[
+    Locale {
+        tag: "en-US",
+        decimal: ".",
+        group: ",",
+        min_grouping_digits: 1,
+        percent: "{}%",
+        date: "M/d/y",
+        time: "h:mm:ss\u{202f}a",
+        date_time_separator: ", ",
+    },
+    Locale {
+        tag: "en-GB",
+        decimal: ".",
+        group: ",",
+        min_grouping_digits: 1,
+        percent: "{}%",
+        date: "dd/MM/y",
+        time: "HH:mm:ss",
+        date_time_separator: ", ",
+    },
+    Locale {
+        tag: "de-DE",
+        decimal: ",",
+        group: ".",
+        min_grouping_digits: 1,
+        percent: "{}\u{a0}%",
+        date: "d.M.y",
+        time: "HH:mm:ss",
+        date_time_separator: ", ",
+    },
+    Locale {
+        tag: "es-ES",
+        decimal: ",",
+        group: ".",
+        min_grouping_digits: 2,
+        percent: "{}\u{a0}%",
+        date: "d/M/y",
+        time: "H:mm:ss",
+        date_time_separator: ", ",
+    },
+    Locale {
+        tag: "fr-FR",
+        decimal: ",",
+        group: "\u{202f}",
+        min_grouping_digits: 1,
+        percent: "{}\u{a0}%",
+        date: "dd/MM/y",
+        time: "HH:mm:ss",
+        date_time_separator: " ",
+    },
+    Locale {
+        tag: "ja-JP",
+        decimal: ".",
+        group: ",",
+        min_grouping_digits: 1,
+        percent: "{}%",
+        date: "y/M/d",
+        time: "H:mm:ss",
+        date_time_separator: " ",
+    },
+];
+
+impl Locale {
+    /// Gets the language subtag of the locale.
+    fn language(&self) -> &'static str {
+        language(self.tag)
+    }
+
+    /// Finds the bundled locale which is the best match for the requested locales.
+    ///
+    /// The first requested locale which has the same tag, or else the same language, as a bundled
+    /// locale is used. The default locale is used when none of them match.
+    pub(crate) fn lookup(requested: &[String]) -> &'static Self {
+        requested
+            .iter()
+            .find_map(|tag| {
+                LOCALES
+                    .iter()
+                    .find(|locale| locale.tag.eq_ignore_ascii_case(tag))
+                    .or_else(|| {
+                        LOCALES
+                            .iter()
+                            .find(|locale| locale.language().eq_ignore_ascii_case(language(tag)))
+                    })
+            })
+            .unwrap_or(&LOCALES[0])
+    }
+
+    /// Finds the locale for the `locales` argument of the `Intl` constructors, which is either
+    /// undefined, a language tag, or an array of language tags.
+    pub(crate) fn from_value(
+        locales: &Value,
+        ctx: &mut Interpreter,
+    ) -> Result<&'static Self, Value> {
+        let requested = match locales.data() {
+            ValueData::Undefined => Vec::new(),
+            ValueData::String(ref tag) => vec![tag.to_string()],
+            ValueData::Object(_) => {
+                let length = ctx.to_number(&locales.get_field("length"))?;
+                let mut requested = Vec::new();
+                for index in 0..length as usize {
+                    requested.push(ctx.to_string(&locales.get_field(index))?);
+                }
+                requested
+            }
+            _ => {
+                ctx.throw_type_error("Intl: locales must be a string or an array")?;
+                unreachable!();
+            }
+        };
+        Ok(Self::lookup(&requested))
+    }
+}
+
+/// Gets the language subtag of a language tag.
+fn language(tag: &str) -> &str {
+    tag.split(&['-', '_'][..]).next().unwrap_or(tag)
+}
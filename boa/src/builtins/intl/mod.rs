@@ -0,0 +1,113 @@
+//! This module implements the global `Intl` object.
+//!
+//! The `Intl` object is the namespace of the internationalization API, which formats values
+//! according to the conventions of a locale.
+//!
+//! The locale data is a small bundled subset of the CLDR, so only a few locales are supported, and
+//! the requested locales fall back to the closest one of them, or else to `en-US`. The times are
+//! always formatted in UTC.
+//!
+//! More information:
+//!  - [ECMAScript Internationalization API reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma402/#intl-object
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl
+
+pub mod date_time_format;
+pub(crate) mod locale;
+pub mod number_format;
+
+#[cfg(test)]
+mod tests;
+
+pub(crate) use self::{date_time_format::DateTimeFormat, number_format::NumberFormat};
+
+use crate::{
+    builtins::value::{Value, ValueData},
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// Gets a string option, which must be one of the allowed values.
+///
+/// More information:
+///  - [ECMAScript Internationalization API reference][spec]
+///
+/// [spec]: https://tc39.es/ecma402/#sec-getoption
+pub(crate) fn get_string_option(
+    options: &Value,
+    property: &str,
+    allowed: &[&'static str],
+    ctx: &mut Interpreter,
+) -> Result<Option<&'static str>, Value> {
+    let value = get_option(options, property);
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    let value = ctx.to_string(&value)?;
+    if let Some(allowed) = allowed.iter().find(|allowed| **allowed == value) {
+        return Ok(Some(allowed));
+    }
+    ctx.throw_range_error(format!("Intl: invalid value {} for {}", value, property))?;
+    unreachable!();
+}
+
+/// Gets a boolean option.
+pub(crate) fn get_boolean_option(options: &Value, property: &str) -> Option<bool> {
+    let value = get_option(options, property);
+    if value.is_undefined() {
+        None
+    } else {
+        Some(value.is_true())
+    }
+}
+
+/// Gets an integer option, which must be between `minimum` and `maximum`.
+///
+/// More information:
+///  - [ECMAScript Internationalization API reference][spec]
+///
+/// [spec]: https://tc39.es/ecma402/#sec-getnumberoption
+pub(crate) fn get_number_option(
+    options: &Value,
+    property: &str,
+    minimum: usize,
+    maximum: usize,
+    ctx: &mut Interpreter,
+) -> Result<Option<usize>, Value> {
+    let value = get_option(options, property);
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    let value = ctx.to_number(&value)?;
+    if value.is_nan() || value < minimum as f64 || value > maximum as f64 {
+        ctx.throw_range_error(format!("Intl: {} value is out of range", property))?;
+        unreachable!();
+    }
+    Ok(Some(value.floor() as usize))
+}
+
+/// Gets a property of the `options` argument of the `Intl` constructors, which is ignored when it
+/// is undefined.
+fn get_option(options: &Value, property: &str) -> Value {
+    match options.data() {
+        ValueData::Undefined => Value::undefined(),
+        _ => options.get_field(property),
+    }
+}
+
+/// Create a new `Intl` object.
+pub fn create(global: &Value) -> Value {
+    let intl = Value::new_object(Some(global));
+    intl.set_field("NumberFormat", NumberFormat::create(global));
+    intl.set_field("DateTimeFormat", DateTimeFormat::create(global));
+    intl
+}
+
+/// Initialise the `Intl` object on the global object.
+#[inline]
+pub fn init(global: &Value) {
+    let _timer = BoaProfiler::global().start_event("intl", "init");
+    global.set_field("Intl", create(global));
+}
@@ -0,0 +1,282 @@
+//! This module implements the `Intl.NumberFormat` object.
+//!
+//! An `Intl.NumberFormat` object formats numbers with the separators and the patterns of a locale.
+//!
+//! More information:
+//!  - [ECMAScript Internationalization API reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma402/#numberformat-objects
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat
+
+use super::{get_boolean_option, get_number_option, get_string_option, locale::Locale};
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn},
+        object::InternalState,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+
+/// The style of an `Intl.NumberFormat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    /// Plain numbers.
+    Decimal,
+    /// Percentages, where the number is multiplied by 100.
+    Percent,
+}
+
+impl Style {
+    /// Gets the name of the style, as given in the options.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Decimal => "decimal",
+            Self::Percent => "percent",
+        }
+    }
+}
+
+/// The internal representation of an `Intl.NumberFormat` object.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NumberFormat {
+    /// The locale the numbers are formatted for.
+    locale: &'static Locale,
+    /// The style of the numbers.
+    style: Style,
+    /// The minimum number of digits after the decimal separator.
+    minimum_fraction_digits: usize,
+    /// The maximum number of digits after the decimal separator.
+    maximum_fraction_digits: usize,
+    /// Whether the digits of the integer part are grouped.
+    use_grouping: bool,
+}
+
+impl InternalState for NumberFormat {}
+
+impl NumberFormat {
+    /// Creates a number format from the `locales` and `options` arguments of the constructor.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-initializenumberformat
+    pub(crate) fn new(
+        locales: &Value,
+        options: &Value,
+        ctx: &mut Interpreter,
+    ) -> Result<Self, Value> {
+        let locale = Locale::from_value(locales, ctx)?;
+        let style = match get_string_option(options, "style", &["decimal", "percent"], ctx)? {
+            Some("percent") => Style::Percent,
+            _ => Style::Decimal,
+        };
+        let (default_minimum, default_maximum) = match style {
+            Style::Decimal => (0, 3),
+            Style::Percent => (0, 0),
+        };
+
+        let minimum = get_number_option(options, "minimumFractionDigits", 0, 20, ctx)?;
+        let maximum = get_number_option(options, "maximumFractionDigits", 0, 20, ctx)?;
+        let (minimum_fraction_digits, maximum_fraction_digits) = match (minimum, maximum) {
+            (None, None) => (default_minimum, default_maximum),
+            (Some(minimum), None) => (minimum, minimum.max(default_maximum)),
+            (None, Some(maximum)) => (default_minimum.min(maximum), maximum),
+            (Some(minimum), Some(maximum)) if minimum <= maximum => (minimum, maximum),
+            (Some(_), Some(_)) => {
+                ctx.throw_range_error(
+                    "Intl.NumberFormat: maximumFractionDigits is less than minimumFractionDigits",
+                )?;
+                unreachable!();
+            }
+        };
+        let use_grouping = get_boolean_option(options, "useGrouping").unwrap_or(true);
+
+        Ok(Self {
+            locale,
+            style,
+            minimum_fraction_digits,
+            maximum_fraction_digits,
+            use_grouping,
+        })
+    }
+
+    /// Formats a number.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-formatnumber
+    pub(crate) fn format(&self, x: f64) -> String {
+        let x = match self.style {
+            Style::Decimal => x,
+            Style::Percent => x * 100.0,
+        };
+        let number = if x.is_nan() {
+            return String::from("NaN");
+        } else if x.is_infinite() {
+            String::from("∞")
+        } else {
+            self.format_digits(x.abs())
+        };
+        let number = match self.style {
+            Style::Decimal => number,
+            Style::Percent => self.locale.percent.replace("{}", &number),
+        };
+        if x.is_sign_negative() {
+            format!("-{}", number)
+        } else {
+            number
+        }
+    }
+
+    /// Formats the digits of a finite, positive number, rounded to the fraction digits.
+    fn format_digits(&self, x: f64) -> String {
+        let digits = format!("{:.*}", self.maximum_fraction_digits, x);
+        let (integer, fraction) = match digits.find('.') {
+            Some(index) => (&digits[..index], &digits[index + 1..]),
+            None => (digits.as_str(), ""),
+        };
+
+        // The trailing zeros are kept down to the minimum number of fraction digits.
+        let trimmed = fraction.trim_end_matches('0');
+        let fraction = if trimmed.len() < self.minimum_fraction_digits {
+            &fraction[..self.minimum_fraction_digits]
+        } else {
+            trimmed
+        };
+
+        let mut result = String::new();
+        let grouped = self.use_grouping && integer.len() >= 3 + self.locale.min_grouping_digits;
+        for (index, digit) in integer.chars().enumerate() {
+            if grouped && index != 0 && (integer.len() - index) % 3 == 0 {
+                result.push_str(self.locale.group);
+            }
+            result.push(digit);
+        }
+        if !fraction.is_empty() {
+            result.push_str(self.locale.decimal);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// `Intl.NumberFormat( [ locales [ , options ] ] )`
+    ///
+    /// Creates a new object which formats numbers according to a locale.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/NumberFormat
+    pub(crate) fn construct_number_format(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let locales = args.first().cloned().unwrap_or_else(Value::undefined);
+        let options = args.get(1).cloned().unwrap_or_else(Value::undefined);
+        let number_format = Self::new(&locales, &options, ctx)?;
+        this.set_internal_state(number_format);
+        Ok(this.clone())
+    }
+
+    /// `Intl.NumberFormat.prototype.format( number )`
+    ///
+    /// Formats a number according to the locale and the options of the object.
+    ///
+    /// The specification defines `format` as a getter returning a bound function, but it is a
+    /// method here.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.format
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/format
+    pub(crate) fn format_number(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let number_format = Self::this_number_format(this, "format", ctx)?;
+        let x = match args.first() {
+            Some(value) => ctx.to_number(value)?,
+            None => f64::NAN,
+        };
+        Ok(Value::from(number_format.format(x)))
+    }
+
+    /// `Intl.NumberFormat.prototype.resolvedOptions()`
+    ///
+    /// Returns an object with the locale and the options used by the object.
+    ///
+    /// More information:
+    ///  - [ECMAScript Internationalization API reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma402/#sec-intl.numberformat.prototype.resolvedoptions
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/resolvedOptions
+    pub(crate) fn resolved_options(
+        this: &mut Value,
+        _: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let number_format = Self::this_number_format(this, "resolvedOptions", ctx)?;
+        let global = ctx.realm().global_obj.clone();
+        let options = Value::new_object(Some(&global));
+        options.set_field("locale", number_format.locale.tag);
+        options.set_field("numberingSystem", "latn");
+        options.set_field("style", number_format.style.name());
+        options.set_field(
+            "minimumFractionDigits",
+            number_format.minimum_fraction_digits,
+        );
+        options.set_field(
+            "maximumFractionDigits",
+            number_format.maximum_fraction_digits,
+        );
+        options.set_field("useGrouping", number_format.use_grouping);
+        Ok(options)
+    }
+
+    /// Gets the number format of `this`, and throws a `TypeError` if it isn't an
+    /// `Intl.NumberFormat`.
+    fn this_number_format(
+        this: &Value,
+        method: &str,
+        ctx: &mut Interpreter,
+    ) -> Result<Self, Value> {
+        let number_format = this
+            .get_internal_state()
+            .and_then(|state| state.downcast_ref::<Self>().copied());
+        if let Some(number_format) = number_format {
+            return Ok(number_format);
+        }
+        ctx.throw_type_error(format!(
+            "Intl.NumberFormat.prototype.{}: 'this' is not an Intl.NumberFormat",
+            method
+        ))?;
+        unreachable!();
+    }
+
+    /// Create a new `Intl.NumberFormat` object.
+    pub(crate) fn create(global: &Value) -> Value {
+        let prototype = Value::new_object(Some(global));
+
+        make_builtin_fn(Self::format_number, "format", &prototype, 1);
+        make_builtin_fn(Self::resolved_options, "resolvedOptions", &prototype, 0);
+
+        make_constructor_fn(
+            "NumberFormat",
+            0,
+            Self::construct_number_format,
+            global,
+            prototype,
+            true,
+        )
+    }
+}
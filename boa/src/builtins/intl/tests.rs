@@ -0,0 +1,218 @@
+use crate::{clock::FixedClock, exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn number_format_locales() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.NumberFormat('en-US').format(1234567.891)"
+        ),
+        "1,234,567.891"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.NumberFormat('de-DE').format(1234567.891)"
+        ),
+        "1.234.567,891"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.NumberFormat('fr').format(1234.5)"),
+        "1\u{202f}234,5"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.NumberFormat('es-ES').format(1234)"),
+        "1234"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.NumberFormat('es-ES').format(12345)"),
+        "12.345"
+    );
+}
+
+#[test]
+fn number_format_fallback() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.NumberFormat().resolvedOptions().locale"
+        ),
+        "en-US"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.NumberFormat(['xx', 'de-AT']).resolvedOptions().locale"
+        ),
+        "de-DE"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.NumberFormat('xx').resolvedOptions().locale"
+        ),
+        "en-US"
+    );
+}
+
+#[test]
+fn number_format_options() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var fixed = new Intl.NumberFormat('en-US', { minimumFractionDigits: 2 });
+        var rounded = new Intl.NumberFormat('en-US', { maximumFractionDigits: 1 });
+        var ungrouped = new Intl.NumberFormat('en-US', { useGrouping: false });
+        var percent = new Intl.NumberFormat('de-DE', { style: 'percent' });
+        "#;
+    eprintln!("{}", forward(&mut engine, init));
+    assert_eq!(forward(&mut engine, "fixed.format(5)"), "5.00");
+    assert_eq!(forward(&mut engine, "fixed.format(5.125)"), "5.125");
+    assert_eq!(forward(&mut engine, "rounded.format(-2.25)"), "-2.2");
+    assert_eq!(forward(&mut engine, "ungrouped.format(1234567)"), "1234567");
+    assert_eq!(forward(&mut engine, "percent.format(0.256)"), "26\u{a0}%");
+    assert_eq!(forward(&mut engine, "fixed.format(NaN)"), "NaN");
+    assert_eq!(forward(&mut engine, "fixed.format(-1 / 0)"), "-∞");
+}
+
+#[test]
+fn number_format_invalid_options() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var digits;
+        try {
+            new Intl.NumberFormat('en-US', { maximumFractionDigits: 21 });
+        } catch (e) {
+            digits = e.constructor === RangeError;
+        }
+        var style;
+        try {
+            new Intl.NumberFormat('en-US', { style: 'money' });
+        } catch (e) {
+            style = e.constructor === RangeError;
+        }
+        var brand;
+        try {
+            var other = { format: Intl.NumberFormat.prototype.format };
+            other.format(1);
+        } catch (e) {
+            brand = e.constructor === TypeError;
+        }
+        "#;
+    eprintln!("{}", forward(&mut engine, init));
+    assert_eq!(forward(&mut engine, "digits"), "true");
+    assert_eq!(forward(&mut engine, "style"), "true");
+    assert_eq!(forward(&mut engine, "brand"), "true");
+}
+
+#[test]
+fn number_to_locale_string() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "Number().toLocaleString()"), "0");
+    assert_eq!(
+        forward(&mut engine, "Number('345600').toLocaleString()"),
+        "345,600"
+    );
+    assert_eq!(forward(&mut engine, "Number(-25).toLocaleString()"), "-25");
+    assert_eq!(
+        forward(&mut engine, "Number(1234.5).toLocaleString('de-DE')"),
+        "1.234,5"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Number(0.5).toLocaleString('en-US', { style: 'percent' })"
+        ),
+        "50%"
+    );
+}
+
+#[test]
+fn date_time_format() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    // 2020-01-02T15:04:05Z
+    let init = r#"
+        var time = 1577977445000;
+        var both = { dateStyle: 'short', timeStyle: 'short' };
+        "#;
+    eprintln!("{}", forward(&mut engine, init));
+    assert_eq!(
+        forward(&mut engine, "new Intl.DateTimeFormat('en-US').format(time)"),
+        "1/2/2020"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.DateTimeFormat('en-GB').format(time)"),
+        "02/01/2020"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.DateTimeFormat('ja').format(time)"),
+        "2020/1/2"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.DateTimeFormat('en-US', both).format(time)"
+        ),
+        "1/2/2020, 3:04:05\u{202f}PM"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.DateTimeFormat('de-DE', both).format(time)"
+        ),
+        "2.1.2020, 15:04:05"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.DateTimeFormat('fr-FR', { timeStyle: 'medium' }).format(0)"
+        ),
+        "00:00:00"
+    );
+    assert_eq!(
+        forward(&mut engine, "new Intl.DateTimeFormat('en-US').format(-1)"),
+        "12/31/1969"
+    );
+}
+
+#[test]
+fn date_time_format_invalid_time() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var invalid;
+        try {
+            new Intl.DateTimeFormat().format(NaN);
+        } catch (e) {
+            invalid = e.constructor === RangeError;
+        }
+        "#;
+    eprintln!("{}", forward(&mut engine, init));
+    assert_eq!(forward(&mut engine, "invalid"), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "new Intl.DateTimeFormat('en-GB').resolvedOptions().timeZone"
+        ),
+        "UTC"
+    );
+}
+
+#[test]
+fn date_time_format_fixed_clock() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    // 2020-01-02T15:04:05Z
+    engine.set_clock(Box::new(FixedClock(1_577_977_445_000.0)));
+    assert_eq!(
+        forward(&mut engine, "new Intl.DateTimeFormat('en-GB').format()"),
+        "02/01/2020"
+    );
+}
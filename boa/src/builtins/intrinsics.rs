@@ -0,0 +1,537 @@
+//! This module implements the intrinsics of a realm, the objects which the specification refers
+//! to as `%Name%`, like `%Array.prototype%` or `%ThrowTypeError%`.
+//!
+//! The intrinsics are kept in internal slots of the global object, so the builtins find them
+//! even when a script replaces the global bindings of the builtins.
+//!
+//! Only the builtins which the others are built upon are initialized with the realm. Any other
+//! builtin is initialized the first time one of its global bindings is resolved, or one of its
+//! intrinsics is requested, so creating a realm doesn't pay for the builtins a script never
+//! uses.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-well-known-intrinsic-objects
+
+#[cfg(feature = "intl")]
+use super::intl;
+use super::{
+    console, function, iterable, json, math, object, object::PROTOTYPE, structured_clone, symbol,
+    value::Value, AggregateError, Array, BigInt, Boolean, Error, Number, RangeError,
+    ReferenceError, RegExp, String, SyntaxError, TypeError,
+};
+
+/// An intrinsic object of a realm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intrinsic {
+    /// `%Object%`
+    Object,
+    /// `%Object.prototype%`
+    ObjectPrototype,
+    /// `%Function%`
+    Function,
+    /// `%Function.prototype%`
+    FunctionPrototype,
+    /// `%ThrowTypeError%`
+    ThrowTypeError,
+    /// `%Array%`
+    Array,
+    /// `%Array.prototype%`
+    ArrayPrototype,
+    /// `%String%`
+    String,
+    /// `%String.prototype%`
+    StringPrototype,
+    /// `%Symbol%`
+    Symbol,
+    /// `%Symbol.prototype%`
+    SymbolPrototype,
+    /// `%IteratorPrototype%`
+    IteratorPrototype,
+    /// `%ArrayIteratorPrototype%`
+    ArrayIteratorPrototype,
+    /// `%StringIteratorPrototype%`
+    StringIteratorPrototype,
+    /// `%Boolean%`
+    Boolean,
+    /// `%Boolean.prototype%`
+    BooleanPrototype,
+    /// `%Number%`
+    Number,
+    /// `%Number.prototype%`
+    NumberPrototype,
+    /// `%BigInt%`
+    BigInt,
+    /// `%BigInt.prototype%`
+    BigIntPrototype,
+    /// `%Math%`
+    Math,
+    /// `%JSON%`
+    Json,
+    /// `%RegExp%`
+    RegExp,
+    /// `%RegExp.prototype%`
+    RegExpPrototype,
+    /// `%RegExpStringIteratorPrototype%`
+    RegExpStringIteratorPrototype,
+    /// `%Error%`
+    Error,
+    /// `%Error.prototype%`
+    ErrorPrototype,
+    /// `%AggregateError%`
+    AggregateError,
+    /// `%AggregateError.prototype%`
+    AggregateErrorPrototype,
+    /// `%RangeError%`
+    RangeError,
+    /// `%RangeError.prototype%`
+    RangeErrorPrototype,
+    /// `%ReferenceError%`
+    ReferenceError,
+    /// `%ReferenceError.prototype%`
+    ReferenceErrorPrototype,
+    /// `%SyntaxError%`
+    SyntaxError,
+    /// `%SyntaxError.prototype%`
+    SyntaxErrorPrototype,
+    /// `%TypeError%`
+    TypeError,
+    /// `%TypeError.prototype%`
+    TypeErrorPrototype,
+    /// The `structuredClone` function of the host.
+    StructuredClone,
+    /// The `console` object of the host.
+    Console,
+    /// `%Intl%`
+    #[cfg(feature = "intl")]
+    Intl,
+}
+
+impl Intrinsic {
+    /// All the intrinsics.
+    const ALL: &'static [Self] = &[
+        Self::Object,
+        Self::ObjectPrototype,
+        Self::Function,
+        Self::FunctionPrototype,
+        Self::ThrowTypeError,
+        Self::Array,
+        Self::ArrayPrototype,
+        Self::String,
+        Self::StringPrototype,
+        Self::Symbol,
+        Self::SymbolPrototype,
+        Self::IteratorPrototype,
+        Self::ArrayIteratorPrototype,
+        Self::StringIteratorPrototype,
+        Self::Boolean,
+        Self::BooleanPrototype,
+        Self::Number,
+        Self::NumberPrototype,
+        Self::BigInt,
+        Self::BigIntPrototype,
+        Self::Math,
+        Self::Json,
+        Self::RegExp,
+        Self::RegExpPrototype,
+        Self::RegExpStringIteratorPrototype,
+        Self::Error,
+        Self::ErrorPrototype,
+        Self::AggregateError,
+        Self::AggregateErrorPrototype,
+        Self::RangeError,
+        Self::RangeErrorPrototype,
+        Self::ReferenceError,
+        Self::ReferenceErrorPrototype,
+        Self::SyntaxError,
+        Self::SyntaxErrorPrototype,
+        Self::TypeError,
+        Self::TypeErrorPrototype,
+        Self::StructuredClone,
+        Self::Console,
+        #[cfg(feature = "intl")]
+        Self::Intl,
+    ];
+
+    /// Gets the name of the intrinsic, which is also the name of the internal slot of the global
+    /// object holding it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Object => "%Object%",
+            Self::ObjectPrototype => "%Object.prototype%",
+            Self::Function => "%Function%",
+            Self::FunctionPrototype => "%Function.prototype%",
+            Self::ThrowTypeError => "%ThrowTypeError%",
+            Self::Array => "%Array%",
+            Self::ArrayPrototype => "%Array.prototype%",
+            Self::String => "%String%",
+            Self::StringPrototype => "%String.prototype%",
+            Self::Symbol => "%Symbol%",
+            Self::SymbolPrototype => "%Symbol.prototype%",
+            Self::IteratorPrototype => "%IteratorPrototype%",
+            Self::ArrayIteratorPrototype => "%ArrayIteratorPrototype%",
+            Self::StringIteratorPrototype => "%StringIteratorPrototype%",
+            Self::Boolean => "%Boolean%",
+            Self::BooleanPrototype => "%Boolean.prototype%",
+            Self::Number => "%Number%",
+            Self::NumberPrototype => "%Number.prototype%",
+            Self::BigInt => "%BigInt%",
+            Self::BigIntPrototype => "%BigInt.prototype%",
+            Self::Math => "%Math%",
+            Self::Json => "%JSON%",
+            Self::RegExp => "%RegExp%",
+            Self::RegExpPrototype => "%RegExp.prototype%",
+            Self::RegExpStringIteratorPrototype => "%RegExpStringIteratorPrototype%",
+            Self::Error => "%Error%",
+            Self::ErrorPrototype => "%Error.prototype%",
+            Self::AggregateError => "%AggregateError%",
+            Self::AggregateErrorPrototype => "%AggregateError.prototype%",
+            Self::RangeError => "%RangeError%",
+            Self::RangeErrorPrototype => "%RangeError.prototype%",
+            Self::ReferenceError => "%ReferenceError%",
+            Self::ReferenceErrorPrototype => "%ReferenceError.prototype%",
+            Self::SyntaxError => "%SyntaxError%",
+            Self::SyntaxErrorPrototype => "%SyntaxError.prototype%",
+            Self::TypeError => "%TypeError%",
+            Self::TypeErrorPrototype => "%TypeError.prototype%",
+            Self::StructuredClone => "%structuredClone%",
+            Self::Console => "%console%",
+            #[cfg(feature = "intl")]
+            Self::Intl => "%Intl%",
+        }
+    }
+
+    /// Gets the builtin creating the intrinsic.
+    fn builtin(self) -> Builtin {
+        match self {
+            Self::Object | Self::ObjectPrototype => Builtin::Object,
+            Self::Function | Self::FunctionPrototype | Self::ThrowTypeError => Builtin::Function,
+            Self::Array | Self::ArrayPrototype => Builtin::Array,
+            Self::String | Self::StringPrototype => Builtin::String,
+            Self::Symbol | Self::SymbolPrototype => Builtin::Symbol,
+            Self::IteratorPrototype
+            | Self::ArrayIteratorPrototype
+            | Self::StringIteratorPrototype => Builtin::Iterators,
+            Self::Boolean | Self::BooleanPrototype => Builtin::Boolean,
+            Self::Number | Self::NumberPrototype => Builtin::Number,
+            Self::BigInt | Self::BigIntPrototype => Builtin::BigInt,
+            Self::Math => Builtin::Math,
+            Self::Json => Builtin::Json,
+            Self::RegExp | Self::RegExpPrototype | Self::RegExpStringIteratorPrototype => {
+                Builtin::RegExp
+            }
+            Self::Error
+            | Self::ErrorPrototype
+            | Self::AggregateError
+            | Self::AggregateErrorPrototype
+            | Self::RangeError
+            | Self::RangeErrorPrototype
+            | Self::ReferenceError
+            | Self::ReferenceErrorPrototype
+            | Self::SyntaxError
+            | Self::SyntaxErrorPrototype
+            | Self::TypeError
+            | Self::TypeErrorPrototype => Builtin::Errors,
+            Self::StructuredClone => Builtin::StructuredClone,
+            Self::Console => Builtin::Console,
+            #[cfg(feature = "intl")]
+            Self::Intl => Builtin::Intl,
+        }
+    }
+
+    /// Finds the intrinsic from the global binding of its builtin, right after the builtin is
+    /// initialized.
+    ///
+    /// The intrinsics which aren't reachable from a global binding are recorded by their builtin
+    /// instead, so `None` is returned for them.
+    fn locate(self, global: &Value) -> Option<Value> {
+        let (binding, prototype) = match self {
+            Self::Object => ("Object", false),
+            Self::ObjectPrototype => ("Object", true),
+            Self::Function => ("Function", false),
+            Self::FunctionPrototype => ("Function", true),
+            Self::Array => ("Array", false),
+            Self::ArrayPrototype => ("Array", true),
+            Self::String => ("String", false),
+            Self::StringPrototype => ("String", true),
+            Self::Symbol => ("Symbol", false),
+            Self::SymbolPrototype => ("Symbol", true),
+            Self::Boolean => ("Boolean", false),
+            Self::BooleanPrototype => ("Boolean", true),
+            Self::Number => ("Number", false),
+            Self::NumberPrototype => ("Number", true),
+            Self::BigInt => ("BigInt", false),
+            Self::BigIntPrototype => ("BigInt", true),
+            Self::Math => ("Math", false),
+            Self::Json => ("JSON", false),
+            Self::RegExp => ("RegExp", false),
+            Self::RegExpPrototype => ("RegExp", true),
+            Self::Error => ("Error", false),
+            Self::ErrorPrototype => ("Error", true),
+            Self::AggregateError => ("AggregateError", false),
+            Self::AggregateErrorPrototype => ("AggregateError", true),
+            Self::RangeError => ("RangeError", false),
+            Self::RangeErrorPrototype => ("RangeError", true),
+            Self::ReferenceError => ("ReferenceError", false),
+            Self::ReferenceErrorPrototype => ("ReferenceError", true),
+            Self::SyntaxError => ("SyntaxError", false),
+            Self::SyntaxErrorPrototype => ("SyntaxError", true),
+            Self::TypeError => ("TypeError", false),
+            Self::TypeErrorPrototype => ("TypeError", true),
+            Self::StructuredClone => ("structuredClone", false),
+            Self::Console => ("console", false),
+            #[cfg(feature = "intl")]
+            Self::Intl => ("Intl", false),
+            Self::ThrowTypeError
+            | Self::IteratorPrototype
+            | Self::ArrayIteratorPrototype
+            | Self::StringIteratorPrototype
+            | Self::RegExpStringIteratorPrototype => return None,
+        };
+
+        let constructor = global.get_field(binding);
+        Some(if prototype {
+            constructor.get_field(PROTOTYPE)
+        } else {
+            constructor
+        })
+    }
+}
+
+/// A builtin, which defines global bindings and intrinsics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Builtin {
+    Object,
+    Function,
+    Array,
+    String,
+    Symbol,
+    Iterators,
+    Boolean,
+    Number,
+    BigInt,
+    Math,
+    Json,
+    RegExp,
+    Errors,
+    StructuredClone,
+    Console,
+    #[cfg(feature = "intl")]
+    Intl,
+}
+
+impl Builtin {
+    /// The builtins the others are built upon, initialized in this order with the realm.
+    const EAGER: &'static [Self] = &[
+        Self::Object,
+        Self::Function,
+        Self::Array,
+        Self::String,
+        Self::Symbol,
+        Self::Iterators,
+    ];
+
+    /// The builtins initialized the first time they are used.
+    const LAZY: &'static [Self] = &[
+        Self::Boolean,
+        Self::Number,
+        Self::BigInt,
+        Self::Math,
+        Self::Json,
+        Self::RegExp,
+        Self::Errors,
+        Self::StructuredClone,
+        Self::Console,
+        #[cfg(feature = "intl")]
+        Self::Intl,
+    ];
+
+    /// Gets the global bindings defined by the builtin.
+    fn bindings(self) -> &'static [&'static str] {
+        match self {
+            Self::Object => &["Object"],
+            Self::Function => &["Function"],
+            Self::Array => &["Array"],
+            Self::String => &["String"],
+            Self::Symbol => &["Symbol"],
+            Self::Iterators => &[],
+            Self::Boolean => &["Boolean"],
+            Self::Number => &["Number"],
+            Self::BigInt => &["BigInt"],
+            Self::Math => &["Math"],
+            Self::Json => &["JSON"],
+            Self::RegExp => &["RegExp"],
+            Self::Errors => &[
+                "Error",
+                "AggregateError",
+                "RangeError",
+                "ReferenceError",
+                "SyntaxError",
+                "TypeError",
+            ],
+            Self::StructuredClone => &["structuredClone"],
+            Self::Console => &["console"],
+            #[cfg(feature = "intl")]
+            Self::Intl => &["Intl"],
+        }
+    }
+
+    /// Gets the intrinsic whose internal slot is set once the builtin is initialized.
+    fn marker(self) -> Intrinsic {
+        match self {
+            Self::Object => Intrinsic::Object,
+            Self::Function => Intrinsic::Function,
+            Self::Array => Intrinsic::Array,
+            Self::String => Intrinsic::String,
+            Self::Symbol => Intrinsic::Symbol,
+            Self::Iterators => Intrinsic::IteratorPrototype,
+            Self::Boolean => Intrinsic::Boolean,
+            Self::Number => Intrinsic::Number,
+            Self::BigInt => Intrinsic::BigInt,
+            Self::Math => Intrinsic::Math,
+            Self::Json => Intrinsic::Json,
+            Self::RegExp => Intrinsic::RegExp,
+            Self::Errors => Intrinsic::Error,
+            Self::StructuredClone => Intrinsic::StructuredClone,
+            Self::Console => Intrinsic::Console,
+            #[cfg(feature = "intl")]
+            Self::Intl => Intrinsic::Intl,
+        }
+    }
+
+    /// Initializes the builtin on the global object, and records its intrinsics.
+    fn init(self, global: &Value) {
+        match self {
+            Self::Object => object::init(global),
+            Self::Function => function::init(global),
+            Self::Array => Array::init(global),
+            Self::String => String::init(global),
+            Self::Symbol => symbol::init(global),
+            Self::Iterators => iterable::init(global),
+            Self::Boolean => Boolean::init(global),
+            Self::Number => Number::init(global),
+            Self::BigInt => BigInt::init(global),
+            Self::Math => math::init(global),
+            Self::Json => json::init(global),
+            Self::RegExp => RegExp::init(global),
+            Self::Errors => {
+                Error::init(global);
+                AggregateError::init(global);
+                RangeError::init(global);
+                ReferenceError::init(global);
+                SyntaxError::init(global);
+                TypeError::init(global);
+            }
+            Self::StructuredClone => structured_clone::init(global),
+            Self::Console => console::init(global),
+            #[cfg(feature = "intl")]
+            Self::Intl => intl::init(global),
+        }
+
+        for intrinsic in Intrinsic::ALL.iter().filter(|i| i.builtin() == self) {
+            if let Some(value) = intrinsic.locate(global) {
+                set(global, *intrinsic, value);
+            }
+        }
+    }
+
+    /// Checks if the builtin is initialized on the global object, or being initialized.
+    fn is_initialized(self, global: &Value) -> bool {
+        match global.as_object() {
+            Some(object) => object.internal_slots.contains_key(self.marker().name()),
+            None => false,
+        }
+    }
+
+    /// Initializes a builtin which wasn't used yet.
+    ///
+    /// The global bindings the host defined with the same names as the ones of the builtin are
+    /// kept, as they would have replaced the bindings of the builtin if it was initialized with
+    /// the realm.
+    fn init_lazily(self, global: &Value) {
+        let defined: Vec<_> = self
+            .bindings()
+            .iter()
+            .filter_map(|name| {
+                let property = global.as_object()?.properties.get(name)?.into_owned();
+                Some((*name, property))
+            })
+            .collect();
+
+        // The builtin counts as initialized while it is, for it to get its own intrinsics from
+        // the global bindings it just defined.
+        global.set_internal_slot(self.marker().name(), Value::undefined());
+        self.init(global);
+
+        for (name, property) in defined {
+            global.set_property_slice(name, property);
+        }
+    }
+}
+
+/// Gets the value of the internal slot of the global object holding an intrinsic, if it is set.
+fn slot(global: &Value, intrinsic: Intrinsic) -> Option<Value> {
+    global
+        .as_object()?
+        .internal_slots
+        .get(intrinsic.name())
+        .filter(|value| !value.is_undefined())
+        .cloned()
+}
+
+/// Records an intrinsic in the internal slot of the global object holding it.
+pub(crate) fn set(global: &Value, intrinsic: Intrinsic, value: Value) {
+    global.set_internal_slot(intrinsic.name(), value);
+}
+
+/// Gets an intrinsic of the realm of the given global object, initializing its builtin first if
+/// it wasn't used yet.
+pub fn get(global: &Value, intrinsic: Intrinsic) -> Value {
+    if let Some(value) = slot(global, intrinsic) {
+        return value;
+    }
+
+    let builtin = intrinsic.builtin();
+    if Builtin::LAZY.contains(&builtin) && !builtin.is_initialized(global) {
+        builtin.init_lazily(global);
+        if let Some(value) = slot(global, intrinsic) {
+            return value;
+        }
+    }
+
+    // The builtins initialized with the realm refer to each other before they are all recorded.
+    intrinsic.locate(global).unwrap_or_default()
+}
+
+/// Initializes the builtins which the realm starts with.
+pub(crate) fn init(global: &Value) {
+    for builtin in Builtin::EAGER {
+        builtin.init(global);
+    }
+}
+
+/// Initializes the builtin defining a global binding, if it wasn't used yet.
+///
+/// Returns `true` if the binding was just defined.
+pub(crate) fn init_binding(global: &Value, name: &str) -> bool {
+    match Builtin::LAZY
+        .iter()
+        .find(|builtin| builtin.bindings().contains(&name))
+    {
+        Some(builtin) if !builtin.is_initialized(global) => {
+            builtin.init_lazily(global);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Initializes all the builtins which weren't used yet.
+pub(crate) fn init_all(global: &Value) {
+    for builtin in Builtin::LAZY {
+        if !builtin.is_initialized(global) {
+            builtin.init_lazily(global);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+//! This module implements the iteration protocol, through which the language consumes iterable
+//! objects, and the `%IteratorPrototype%` object inherited by the builtin iterators.
+//!
+//! An object is iterable if it has a `[Symbol.iterator]` method, returning an iterator. The
+//! `next` method of the iterator returns objects with the next `value` of the iteration, until
+//! one of them is `done`. An iterator that isn't consumed until it's done may be closed by
+//! calling its `return` method.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-iteration
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Iteration_protocols
+
+use super::{
+    array::array_iterator::ArrayIterator,
+    function::make_builtin_symbol_fn,
+    intrinsics::{self, Intrinsic},
+    string::string_iterator::StringIterator,
+};
+use crate::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+    BoaProfiler,
+};
+
+/// An iterator with its `next` method, as it is consumed by the iteration protocol.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-iterator-records
+#[derive(Debug, Clone)]
+pub(crate) struct IteratorRecord {
+    iterator: Value,
+    next_method: Value,
+}
+
+impl IteratorRecord {
+    /// Gets the next value produced by the iterator, or `None` if the iterator is done.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-iteratorstep
+    pub(crate) fn step(&self, ctx: &mut Interpreter) -> Result<Option<Value>, Value> {
+        if !self.next_method.is_function() {
+            ctx.throw_type_error("the next method of the iterator is not a function")?;
+        }
+        let result = ctx.call(&self.next_method, &mut self.iterator.clone(), &[])?;
+        if !result.is_object() {
+            ctx.throw_type_error("the iterator result is not an object")?;
+        }
+        if result.get_field("done").is_true() {
+            return Ok(None);
+        }
+        Ok(Some(result.get_field("value")))
+    }
+
+    /// Closes an iterator which isn't done, by calling its `return` method, if it has one.
+    ///
+    /// The given completion is returned, unless it is normal and closing the iterator throws.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-iteratorclose
+    pub(crate) fn close(&self, completion: ResultValue, ctx: &mut Interpreter) -> ResultValue {
+        let return_method = self.iterator.get_field("return");
+        if return_method.is_null_or_undefined() {
+            return completion;
+        }
+        if !return_method.is_function() {
+            completion?;
+            return ctx.throw_type_error("the return method of the iterator is not a function");
+        }
+
+        let result = ctx.call(&return_method, &mut self.iterator.clone(), &[]);
+        let completion = completion?;
+        if !result?.is_object() {
+            return ctx.throw_type_error("the iterator result is not an object");
+        }
+        Ok(completion)
+    }
+}
+
+impl Interpreter {
+    /// Gets an iterator from an iterable value, by calling its `[Symbol.iterator]` method.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-getiterator
+    pub(crate) fn get_iterator(&mut self, value: &Value) -> Result<IteratorRecord, Value> {
+        if value.is_null_or_undefined() {
+            self.throw_type_error(format!("{} is not iterable", value))?;
+        }
+        let iterator_symbol = self.well_known_symbol("iterator");
+        let method = self.to_object(value)?.get_field(iterator_symbol);
+        if !method.is_function() {
+            // Objects are described by their type, as their display may span many lines.
+            let message = if value.is_object() {
+                format!("{} is not iterable", value.get_type())
+            } else {
+                format!("{} is not iterable", value)
+            };
+            self.throw_type_error(message)?;
+        }
+
+        let iterator = self.call(&method, &mut value.clone(), &[])?;
+        if !iterator.is_object() {
+            self.throw_type_error("the iterator is not an object")?;
+        }
+        let next_method = iterator.get_field("next");
+        Ok(IteratorRecord {
+            iterator,
+            next_method,
+        })
+    }
+
+    /// Collects the values produced by iterating over an iterable value, as the spread operator
+    /// does.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-iterabletolist
+    pub(crate) fn iterable_to_list(&mut self, value: &Value) -> Result<Vec<Value>, Value> {
+        let iterator = self.get_iterator(value)?;
+        let mut values = Vec::new();
+        while let Some(value) = iterator.step(self)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+/// Creates an object returned by the `next` method of an iterator.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-createiterresultobject
+pub(crate) fn create_iter_result_object(ctx: &Interpreter, value: Value, done: bool) -> Value {
+    let object = Value::new_object(Some(&ctx.realm().global_obj));
+    object.set_field("value", value);
+    object.set_field("done", done);
+    object
+}
+
+/// `%IteratorPrototype% [ @@iterator ] ( )`
+///
+/// Returns the iterator itself, so that the iterators are iterable too.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-%iteratorprototype%-@@iterator
+fn return_this(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
+    Ok(this.clone())
+}
+
+/// Initialise the prototypes of the builtin iterators, and the `[Symbol.iterator]` methods of
+/// the builtin iterable objects.
+///
+/// The methods are keyed by the well-known `@@iterator` symbol, so this runs after the `Symbol`
+/// object is initialised.
+#[inline]
+pub fn init(global: &Value) {
+    let _timer = BoaProfiler::global().start_event("iterable", "init");
+    let iterator_symbol = intrinsics::get(global, Intrinsic::Symbol).get_field("iterator");
+
+    let iterator_prototype = Value::new_object(Some(global));
+    make_builtin_symbol_fn(
+        return_this,
+        &iterator_symbol,
+        "[Symbol.iterator]",
+        &iterator_prototype,
+        0,
+    );
+    intrinsics::set(
+        global,
+        Intrinsic::IteratorPrototype,
+        iterator_prototype.clone(),
+    );
+
+    ArrayIterator::init(global, &iterator_prototype, &iterator_symbol);
+    StringIterator::init(global, &iterator_prototype, &iterator_symbol);
+}
@@ -17,10 +17,12 @@ use crate::builtins::{
     function::make_builtin_fn,
     object::ObjectKind,
     property::Property,
-    value::{ResultValue, Value},
+    string::JsString,
+    value::{ResultValue, Value, ValueData},
 };
 use crate::{exec::Interpreter, BoaProfiler};
 use serde_json::{self, Value as JSONValue};
+use std::{borrow::Cow, fmt::Write};
 
 #[cfg(test)]
 mod tests;
@@ -63,18 +65,18 @@ pub fn parse(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValu
 ///
 /// [polyfill]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/JSON/parse
 fn walk(reviver: &Value, ctx: &mut Interpreter, holder: &mut Value, key: Value) -> ResultValue {
-    let mut value = holder.get_field(key.clone());
+    let mut value = ctx.get_field_value(holder, key.clone())?;
 
     let obj = value.as_object().as_deref().cloned();
     if let Some(obj) = obj {
         for key in obj.properties.keys() {
-            let v = walk(reviver, ctx, &mut value, Value::from(key.as_str()));
+            let v = walk(reviver, ctx, &mut value, Value::from(&*key));
             match v {
                 Ok(v) if !v.is_undefined() => {
-                    value.set_field(Value::from(key.as_str()), v);
+                    value.set_field(Value::from(&*key), v);
                 }
                 Ok(_) => {
-                    value.remove_property(key.as_str());
+                    value.remove_property(&key);
                 }
                 Err(_v) => {}
             }
@@ -101,64 +103,180 @@ fn walk(reviver: &Value, ctx: &mut Interpreter, holder: &mut Value, key: Value)
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/JSON/stringify
 pub fn stringify(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let object = match args.get(0) {
-        Some(obj) if obj.is_symbol() || obj.is_function() => return Ok(Value::undefined()),
+        Some(obj) if obj.is_undefined() || obj.is_symbol() || obj.is_function() => {
+            return Ok(Value::undefined())
+        }
         None => return Ok(Value::undefined()),
         Some(obj) => obj,
     };
     let replacer = match args.get(1) {
         Some(replacer) if replacer.is_object() => replacer,
-        _ => return Ok(Value::from(object.to_json().to_string())),
+        _ => return serialize(object, ctx),
     };
 
     let replacer_as_object = replacer
         .as_object()
         .expect("JSON.stringify replacer was an object");
     if replacer_as_object.is_callable() {
-        object
+        drop(replacer_as_object);
+        let keys: Vec<String> = object
             .as_object()
-            .map(|obj| {
-                let object_to_return = Value::new_object(None);
-                for (key, val) in obj
-                    .properties
-                    .iter()
-                    .filter_map(|(k, v)| v.value.as_ref().map(|value| (k, value)))
-                {
-                    let mut this_arg = object.clone();
-                    object_to_return.set_property(
-                        key.to_owned(),
-                        Property::default().value(ctx.call(
-                            replacer,
-                            &mut this_arg,
-                            &[Value::string(key), val.clone()],
-                        )?),
-                    );
-                }
-                Ok(Value::from(object_to_return.to_json().to_string()))
-            })
             .ok_or_else(Value::undefined)?
+            .properties
+            .keys()
+            .map(Cow::into_owned)
+            .collect();
+        let object_to_return = Value::new_object(None);
+        for key in keys {
+            let val = ctx.get_field_value(object, key.as_str())?;
+            let mut this_arg = object.clone();
+            let value = ctx.call(replacer, &mut this_arg, &[Value::string(key.as_str()), val])?;
+            object_to_return.set_property(
+                key,
+                Property::default()
+                    .value(value)
+                    .writable(true)
+                    .enumerable(true)
+                    .configurable(true),
+            );
+        }
+        serialize(&object_to_return, ctx)
     } else if replacer_as_object.kind == ObjectKind::Array {
-        let mut obj_to_return =
-            serde_json::Map::with_capacity(replacer_as_object.properties.len() - 1);
-        let fields = replacer_as_object.properties.keys().filter_map(|key| {
-            if key == "length" {
-                None
-            } else {
-                Some(replacer.get_field(key.to_owned()))
-            }
-        });
-        for field in fields {
-            if let Some(value) = object
-                .get_property(&ctx.to_string(&field)?)
-                .map(|prop| prop.value.as_ref().map(|v| v.to_json()))
-                .flatten()
-            {
-                obj_to_return.insert(field.to_string(), value);
+        let keys: Vec<String> = replacer_as_object
+            .properties
+            .keys()
+            .filter(|key| key != "length")
+            .map(Cow::into_owned)
+            .collect();
+        drop(replacer_as_object);
+        let object_to_return = Value::new_object(None);
+        for key in keys {
+            let field = ctx.get_field_value(replacer, key)?;
+            let field = ctx.to_string(&field)?;
+            if object.has_field(&field) {
+                let value = ctx.get_field_value(object, field.as_str())?;
+                object_to_return.set_field(field, value);
             }
         }
-        Ok(Value::from(JSONValue::Object(obj_to_return).to_string()))
+        serialize(&object_to_return, ctx)
     } else {
-        Ok(Value::from(object.to_json().to_string()))
+        serialize(object, ctx)
+    }
+}
+
+/// Serializes a value to a JSON string.
+///
+/// Arrays and objects are serialized recursively. In objects, the properties which are
+/// `undefined`, functions or symbols are skipped, and in arrays, they become `null`.
+fn serialize(value: &Value, ctx: &mut Interpreter) -> ResultValue {
+    let mut json = String::new();
+    serialize_json_property(value, &mut json, ctx)?;
+    Ok(Value::from(json))
+}
+
+/// Checks if a property is skipped by `JSON.stringify`.
+fn is_unserializable(value: &Value) -> bool {
+    value.is_undefined() || value.is_function() || value.is_symbol()
+}
+
+/// Appends the JSON serialization of a value to a string.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-serializejsonproperty
+fn serialize_json_property(
+    value: &Value,
+    json: &mut String,
+    ctx: &mut Interpreter,
+) -> Result<(), Value> {
+    match value.data() {
+        ValueData::Null => json.push_str("null"),
+        ValueData::Boolean(b) => json.push_str(if *b { "true" } else { "false" }),
+        ValueData::String(ref string) => quote_json_string(string, json),
+        ValueData::Integer(num) => write!(json, "{}", num).expect("writing to a string can't fail"),
+        ValueData::Rational(num) if num.is_finite() => json.push_str(&ctx.to_string(value)?),
+        ValueData::Rational(_) => json.push_str("null"),
+        ValueData::BigInt(_) => {
+            ctx.throw_type_error("BigInt value can't be serialized in JSON")?;
+        }
+        ValueData::Object(ref obj) => {
+            let is_array = obj.borrow().kind == ObjectKind::Array;
+            let keys: Vec<String> = obj
+                .borrow()
+                .properties
+                .iter()
+                .filter(|(key, property)| {
+                    if is_array {
+                        key != "length"
+                    } else {
+                        property.enumerable == Some(true)
+                    }
+                })
+                .map(|(key, _)| key.into_owned())
+                .collect();
+
+            json.push(if is_array { '[' } else { '{' });
+            let mut first = true;
+            for key in keys {
+                let property = ctx.get_field_value(value, key.as_str())?;
+                if !is_array && is_unserializable(&property) {
+                    continue;
+                }
+                if !first {
+                    json.push(',');
+                }
+                first = false;
+                if is_array {
+                    if is_unserializable(&property) {
+                        json.push_str("null");
+                        continue;
+                    }
+                } else {
+                    quote_json_string(&JsString::from(key), json);
+                    json.push(':');
+                }
+                serialize_json_property(&property, json, ctx)?;
+            }
+            json.push(if is_array { ']' } else { '}' });
+        }
+        ValueData::Undefined | ValueData::Symbol(_) => {
+            unreachable!("Symbols and Undefined JSON Values depend on parent type");
+        }
+    }
+    Ok(())
+}
+
+/// Appends a string to a JSON text, as a quoted JSON string.
+///
+/// Lone surrogates can't be represented in UTF-8, so they are escaped like the control
+/// characters, and the result is always well-formed.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-quotejsonstring
+fn quote_json_string(string: &JsString, json: &mut String) {
+    json.push('"');
+    for code_point in string.code_points() {
+        match code_point {
+            Ok('\u{8}') => json.push_str("\\b"),
+            Ok('\t') => json.push_str("\\t"),
+            Ok('\n') => json.push_str("\\n"),
+            Ok('\u{c}') => json.push_str("\\f"),
+            Ok('\r') => json.push_str("\\r"),
+            Ok('"') => json.push_str("\\\""),
+            Ok('\\') => json.push_str("\\\\"),
+            Ok(c) if c < ' ' => {
+                write!(json, "\\u{:04x}", u32::from(c)).expect("writing to a string can't fail")
+            }
+            Ok(c) => json.push(c),
+            Err(surrogate) => {
+                write!(json, "\\u{:04x}", surrogate).expect("writing to a string can't fail")
+            }
+        }
     }
+    json.push('"');
 }
 
 /// Create a new `JSON` object.
@@ -46,8 +46,6 @@ fn json_stringify_remove_function_values_from_objects() {
 }
 
 #[test]
-#[ignore]
-// there is a bug for setting a symbol as a field's value
 fn json_stringify_remove_symbols_from_objects() {
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
@@ -144,7 +142,6 @@ fn json_stringify_array_converts_function_to_null() {
 }
 
 #[test]
-#[ignore]
 fn json_stringify_array_converts_symbol_to_null() {
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
@@ -183,11 +180,74 @@ fn json_stringify_return_undefined() {
     let actual_no_args = forward(&mut engine, r#"JSON.stringify()"#);
     let actual_function = forward(&mut engine, r#"JSON.stringify(() => {})"#);
     let actual_symbol = forward(&mut engine, r#"JSON.stringify(Symbol())"#);
+    let actual_undefined = forward(&mut engine, r#"JSON.stringify(undefined)"#);
     let expected = forward(&mut engine, r#"undefined"#);
 
     assert_eq!(actual_no_args, expected);
     assert_eq!(actual_function, expected);
     assert_eq!(actual_symbol, expected);
+    assert_eq!(actual_undefined, expected);
+}
+
+#[test]
+fn json_stringify_escapes_lone_surrogates() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        r#"var pair = "\u{1D306}"; var leading = pair.charAt(0); var trailing = pair.charAt(1);"#,
+    );
+
+    assert_eq!(
+        forward(&mut engine, r#"JSON.stringify(leading)"#),
+        r#""\ud834""#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            r#"JSON.stringify([trailing + leading, 'a' + leading])"#
+        ),
+        r#"["\udf06\ud834","a\ud834"]"#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            r#"JSON.stringify({ a: leading + trailing }) === '{"a":"' + pair + '"}'"#
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, r#"JSON.parse(JSON.stringify(pair)) === pair"#),
+        "true"
+    );
+}
+
+#[test]
+fn json_stringify_escapes_control_characters() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let actual = forward(
+        &mut engine,
+        r#"JSON.stringify("\"\\\b\f\n\r\t\u0000\u001f\u007f")"#,
+    );
+    let expected = "\"\\\"\\\\\\b\\f\\n\\r\\t\\u0000\\u001f\u{7f}\"";
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_stringify_numbers() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let actual = forward(
+        &mut engine,
+        r#"JSON.stringify([1, 1.5, -0.25, NaN, 1 / 0])"#,
+    );
+    let expected = r#"[1,1.5,-0.25,null,null]"#;
+
+    assert_eq!(actual, expected);
 }
 
 #[test]
@@ -236,3 +296,37 @@ fn json_parse_object_with_reviver() {
     );
     assert_eq!(result, r#"{"firstname":"boa","lastname":"interpreter"}"#);
 }
+
+#[test]
+fn json_stringify_calls_getters() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "JSON.stringify({ get a() { return 1 }, b: 2 })"
+        ),
+        r#"{"a":1,"b":2}"#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "JSON.stringify({ get a() { return this.b * 2 }, b: 2 }, ['a'])"
+        ),
+        r#"{"a":4}"#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "JSON.stringify({ get a() { return 1 } }, function (k, v) { return v + 1 })"
+        ),
+        r#"{"a":2}"#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            r#"JSON.parse('{"a":1}', function (k, v) { return v; }).a"#
+        ),
+        "1"
+    );
+}
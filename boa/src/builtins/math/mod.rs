@@ -19,7 +19,6 @@ use crate::{
     exec::Interpreter,
     BoaProfiler,
 };
-use rand::random;
 use std::f64;
 
 #[cfg(test)]
@@ -33,11 +32,12 @@ mod tests;
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.abs
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/abs
-pub fn abs(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn abs(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).abs()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .abs()
     }))
 }
 
@@ -49,11 +49,12 @@ pub fn abs(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.acos
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/acos
-pub fn acos(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn acos(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).acos()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .acos()
     }))
 }
 
@@ -65,11 +66,12 @@ pub fn acos(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.acosh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/acosh
-pub fn acosh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn acosh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).acosh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .acosh()
     }))
 }
 
@@ -81,11 +83,12 @@ pub fn acosh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.asin
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/asin
-pub fn asin(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn asin(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).asin()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .asin()
     }))
 }
 
@@ -97,11 +100,12 @@ pub fn asin(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.asinh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/asinh
-pub fn asinh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn asinh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).asinh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .asinh()
     }))
 }
 
@@ -113,11 +117,12 @@ pub fn asinh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.atan
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atan
-pub fn atan(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn atan(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).atan()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .atan()
     }))
 }
 
@@ -129,11 +134,12 @@ pub fn atan(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.atanh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atanh
-pub fn atanh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn atanh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).atanh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .atanh()
     }))
 }
 
@@ -145,12 +151,12 @@ pub fn atanh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.atan2
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atan2
-pub fn atan2(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn atan2(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument"))
-            .atan2(args.get(1).expect("Could not get argument").to_number())
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .atan2(ctx.to_number(args.get(1).expect("Could not get argument"))?)
     }))
 }
 
@@ -162,11 +168,12 @@ pub fn atan2(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.cbrt
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cbrt
-pub fn cbrt(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn cbrt(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).cbrt()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .cbrt()
     }))
 }
 
@@ -178,11 +185,12 @@ pub fn cbrt(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.ceil
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/ceil
-pub fn ceil(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn ceil(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).ceil()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .ceil()
     }))
 }
 
@@ -194,11 +202,12 @@ pub fn ceil(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.cos
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cos
-pub fn cos(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn cos(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).cos()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .cos()
     }))
 }
 
@@ -210,11 +219,12 @@ pub fn cos(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.cosh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cosh
-pub fn cosh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn cosh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).cosh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .cosh()
     }))
 }
 
@@ -226,11 +236,12 @@ pub fn cosh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.exp
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/exp
-pub fn exp(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn exp(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).exp()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .exp()
     }))
 }
 
@@ -242,11 +253,12 @@ pub fn exp(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.floor
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/floor
-pub fn floor(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn floor(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).floor()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .floor()
     }))
 }
 
@@ -258,11 +270,11 @@ pub fn floor(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.log
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log
-pub fn log(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn log(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        let value = f64::from(args.get(0).expect("Could not get argument"));
+        let value = ctx.to_number(args.get(0).expect("Could not get argument"))?;
 
         if value <= 0.0 {
             f64::NAN
@@ -280,11 +292,11 @@ pub fn log(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.log10
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log10
-pub fn log10(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn log10(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        let value = f64::from(args.get(0).expect("Could not get argument"));
+        let value = ctx.to_number(args.get(0).expect("Could not get argument"))?;
 
         if value <= 0.0 {
             f64::NAN
@@ -302,11 +314,11 @@ pub fn log10(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.log2
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log2
-pub fn log2(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn log2(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        let value = f64::from(args.get(0).expect("Could not get argument"));
+        let value = ctx.to_number(args.get(0).expect("Could not get argument"))?;
 
         if value <= 0.0 {
             f64::NAN
@@ -324,11 +336,18 @@ pub fn log2(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.max
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/max
-pub fn max(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn max(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let mut max = f64::NEG_INFINITY;
     for arg in args {
-        let num = f64::from(arg);
-        max = max.max(num);
+        let num = ctx.to_number(arg)?;
+        // `f64::max` ignores `NaN`, but any `NaN` argument makes the result `NaN`.
+        max = if max.is_nan() {
+            max
+        } else if num.is_nan() {
+            num
+        } else {
+            max.max(num)
+        };
     }
     Ok(Value::from(max))
 }
@@ -341,11 +360,17 @@ pub fn max(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.min
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/min
-pub fn min(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn min(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     let mut max = f64::INFINITY;
     for arg in args {
-        let num = f64::from(arg);
-        max = max.min(num);
+        let num = ctx.to_number(arg)?;
+        max = if max.is_nan() {
+            max
+        } else if num.is_nan() {
+            num
+        } else {
+            max.min(num)
+        };
     }
     Ok(Value::from(max))
 }
@@ -358,10 +383,10 @@ pub fn min(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.pow
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/pow
-pub fn pow(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn pow(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.len() >= 2 {
-        let num = f64::from(args.get(0).expect("Could not get argument"));
-        let power = f64::from(args.get(1).expect("Could not get argument"));
+        let num = ctx.to_number(args.get(0).expect("Could not get argument"))?;
+        let power = ctx.to_number(args.get(1).expect("Could not get argument"))?;
         num.powf(power)
     } else {
         f64::NAN
@@ -376,8 +401,8 @@ pub fn pow(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.random
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
-pub fn _random(_: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-    Ok(Value::from(random::<f64>()))
+pub fn _random(_: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    Ok(Value::from(ctx.random()))
 }
 
 /// Round a number to the nearest integer.
@@ -388,11 +413,12 @@ pub fn _random(_: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.round
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/round
-pub fn round(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn round(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).round()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .round()
     }))
 }
 
@@ -404,11 +430,11 @@ pub fn round(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.sign
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sign
-pub fn sign(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn sign(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        let value = f64::from(args.get(0).expect("Could not get argument"));
+        let value = ctx.to_number(args.get(0).expect("Could not get argument"))?;
 
         if value == 0.0 || value == -0.0 {
             value
@@ -426,11 +452,12 @@ pub fn sign(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.sin
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sin
-pub fn sin(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn sin(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).sin()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .sin()
     }))
 }
 
@@ -442,11 +469,12 @@ pub fn sin(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.sinh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sinh
-pub fn sinh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn sinh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).sinh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .sinh()
     }))
 }
 
@@ -458,19 +486,21 @@ pub fn sinh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.sqrt
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sqrt
-pub fn sqrt(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn sqrt(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).sqrt()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .sqrt()
     }))
 }
 /// Get the tangent of a number
-pub fn tan(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn tan(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).tan()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .tan()
     }))
 }
 
@@ -482,11 +512,12 @@ pub fn tan(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.tanh
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/tanh
-pub fn tanh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn tanh(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).tanh()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .tanh()
     }))
 }
 
@@ -498,11 +529,12 @@ pub fn tanh(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-math.trunc
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/trunc
-pub fn trunc(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+pub fn trunc(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
     Ok(Value::from(if args.is_empty() {
         f64::NAN
     } else {
-        f64::from(args.get(0).expect("Could not get argument")).trunc()
+        ctx.to_number(args.get(0).expect("Could not get argument"))?
+            .trunc()
     }))
 }
 
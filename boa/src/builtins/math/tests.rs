@@ -393,6 +393,39 @@ fn pow() {
     assert_eq!(d.to_number(), 0.020_408_163_265_306_12);
 }
 
+#[test]
+fn random() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var numbers = [];
+        for (var i = 0; i < 100; i++) {
+            numbers.push(Math.random());
+        }
+        "#;
+
+    eprintln!("{}", forward(&mut engine, init));
+    assert_eq!(
+        forward(&mut engine, "numbers.every(n => n >= 0 && n < 1)"),
+        "true"
+    );
+}
+
+#[test]
+fn random_seed() {
+    let numbers = |seed| {
+        let mut engine = Interpreter::new(Realm::create());
+        engine.set_random_seed(seed);
+        forward(
+            &mut engine,
+            "[Math.random(), Math.random(), Math.random()].join()",
+        )
+    };
+
+    assert_eq!(numbers(42), numbers(42));
+    assert_ne!(numbers(42), numbers(43));
+}
+
 #[test]
 fn round() {
     let realm = Realm::create();
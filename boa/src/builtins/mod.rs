@@ -6,6 +6,10 @@ pub mod boolean;
 pub mod console;
 pub mod error;
 pub mod function;
+#[cfg(feature = "intl")]
+pub mod intl;
+pub mod intrinsics;
+pub mod iterable;
 pub mod json;
 pub mod math;
 pub mod number;
@@ -13,6 +17,7 @@ pub mod object;
 pub mod property;
 pub mod regexp;
 pub mod string;
+pub mod structured_clone;
 pub mod symbol;
 pub mod value;
 
@@ -20,7 +25,7 @@ pub(crate) use self::{
     array::Array,
     bigint::BigInt,
     boolean::Boolean,
-    error::{Error, RangeError, TypeError},
+    error::{AggregateError, Error, RangeError, ReferenceError, SyntaxError, TypeError},
     function::Function,
     number::Number,
     regexp::RegExp,
@@ -28,22 +33,10 @@ pub(crate) use self::{
     value::{ResultValue, Value},
 };
 
-/// Initializes builtin objects and functions
+/// Initializes the builtin objects and functions which the realm starts with.
+///
+/// The other builtins are initialized the first time they are used, see the `intrinsics` module.
 #[inline]
 pub fn init(global: &Value) {
-    Array::init(global);
-    BigInt::init(global);
-    Boolean::init(global);
-    json::init(global);
-    math::init(global);
-    Number::init(global);
-    object::init(global);
-    function::init(global);
-    RegExp::init(global);
-    String::init(global);
-    symbol::init(global);
-    console::init(global);
-    Error::init(global);
-    RangeError::init(global);
-    TypeError::init(global);
+    intrinsics::init(global);
 }
@@ -20,10 +20,12 @@ use super::{
     function::{make_builtin_fn, make_constructor_fn},
     object::ObjectKind,
 };
+#[cfg(feature = "intl")]
+use crate::builtins::intl::NumberFormat;
 use crate::{
     builtins::{
         object::internal_methods_trait::ObjectInternalMethods,
-        value::{ResultValue, Value, ValueData},
+        value::{string_to_number, ResultValue, Value, ValueData},
     },
     exec::Interpreter,
     BoaProfiler,
@@ -55,10 +57,7 @@ impl Number {
             ValueData::Null => Value::from(0),
             ValueData::Rational(n) => Value::from(n),
             ValueData::BigInt(ref bigint) => Value::from(bigint.to_f64()),
-            ValueData::String(ref s) => match s.parse::<f64>() {
-                Ok(n) => Value::from(n),
-                Err(_) => Value::from(f64::NAN),
-            },
+            ValueData::String(ref s) => Value::from(string_to_number(&s.to_string_lossy())),
         }
     }
 
@@ -77,11 +76,14 @@ impl Number {
     pub(crate) fn make_number(
         this: &mut Value,
         args: &[Value],
-        _ctx: &mut Interpreter,
+        ctx: &mut Interpreter,
     ) -> ResultValue {
         let data = match args.get(0) {
-            Some(ref value) => Self::to_number(value),
-            None => Self::to_number(&Value::from(0)),
+            Some(ref value) => match ctx.to_numeric(value)?.data() {
+                ValueData::BigInt(ref bigint) => Value::from(bigint.to_f64()),
+                number => Value::from(number.to_number()),
+            },
+            None => Value::from(0.0),
         };
         this.set_kind(ObjectKind::Number);
         this.set_internal_slot("NumberData", data.clone());
@@ -142,8 +144,9 @@ impl Number {
     ///
     /// The `toLocaleString()` method returns a string with a language-sensitive representation of this number.
     ///
-    /// Note that while this technically conforms to the Ecma standard, it does no actual
-    /// internationalization logic.
+    /// Without the `intl` feature, this technically conforms to the Ecma standard but does no
+    /// actual internationalization logic. With it, the number is formatted by an
+    /// `Intl.NumberFormat` created with the same arguments.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -152,14 +155,25 @@ impl Number {
     /// [spec]: https://tc39.es/ecma262/#sec-number.prototype.tolocalestring
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Number/toLocaleString
     #[allow(clippy::wrong_self_convention)]
+    #[cfg_attr(not(feature = "intl"), allow(unused_variables))]
     pub(crate) fn to_locale_string(
         this: &mut Value,
-        _args: &[Value],
-        _ctx: &mut Interpreter,
+        args: &[Value],
+        ctx: &mut Interpreter,
     ) -> ResultValue {
         let this_num = Self::to_number(this).to_number();
-        let this_str_num = format!("{}", this_num);
-        Ok(Value::from(this_str_num))
+        #[cfg(feature = "intl")]
+        {
+            let locales = args.first().cloned().unwrap_or_else(Value::undefined);
+            let options = args.get(1).cloned().unwrap_or_else(Value::undefined);
+            let number_format = NumberFormat::new(&locales, &options, ctx)?;
+            Ok(Value::from(number_format.format(this_num)))
+        }
+        #[cfg(not(feature = "intl"))]
+        {
+            let this_str_num = format!("{}", this_num);
+            Ok(Value::from(this_str_num))
+        }
     }
 
     /// `Number.prototype.toPrecision( [precision] )`
@@ -350,13 +364,17 @@ impl Number {
         let x = Self::to_number(this).to_number();
         // 2. If radix is undefined, let radixNumber be 10.
         // 3. Else, let radixNumber be ? ToInteger(radix).
-        let radix = args.get(0).map_or(10, |arg| arg.to_integer()) as u8;
+        let radix = match args.get(0) {
+            Some(radix) if !radix.is_undefined() => ctx.to_number(radix)?.trunc(),
+            _ => 10.0,
+        };
 
         // 4. If radixNumber < 2 or radixNumber > 36, throw a RangeError exception.
-        if radix < 2 || radix > 36 {
+        if !(2.0..=36.0).contains(&radix) {
             return ctx
                 .throw_range_error("radix must be an integer at least 2 and no greater than 36");
         }
+        let radix = radix as u8;
 
         if x == -0. {
             return Ok(Value::from("0"));
@@ -105,6 +105,7 @@ fn to_fixed() {
 }
 
 #[test]
+#[cfg(not(feature = "intl"))]
 fn to_locale_string() {
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
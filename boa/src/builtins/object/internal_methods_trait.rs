@@ -35,7 +35,7 @@ pub trait ObjectInternalMethods {
     fn has_property(&self, val: &Value) -> bool {
         debug_assert!(Property::is_property_key(val));
         let prop = self.get_own_property(val);
-        if prop.value.is_none() {
+        if prop.value.is_none() && !prop.is_accessor_descriptor() {
             let parent: Value = self.get_prototype_of();
             if !parent.is_null() {
                 // the parent value variant should be an object
@@ -77,19 +77,25 @@ pub trait ObjectInternalMethods {
     }
 
     /// Delete property.
+    ///
+    /// Returns `false`, without removing it, if the property isn't configurable.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-delete-p
     fn delete(&mut self, prop_key: &Value) -> bool {
         debug_assert!(Property::is_property_key(prop_key));
         let desc = self.get_own_property(prop_key);
-        if desc
-            .value
-            .clone()
-            .expect("unable to get value")
-            .is_undefined()
-        {
+        if desc.value.is_none() && !desc.is_accessor_descriptor() {
             return true;
         }
-        if desc.configurable.expect("unable to get value") {
-            self.remove_property(&prop_key.to_string());
+        if desc.configurable.unwrap_or(false) {
+            if prop_key.is_symbol() {
+                self.remove_symbol_property(prop_key);
+            } else {
+                self.remove_property(&prop_key.to_string());
+            }
             return true;
         }
 
@@ -107,9 +113,9 @@ pub trait ObjectInternalMethods {
                 .expect("Failed to get object")
                 .is_undefined()
         {
-            // parent will either be null or an Object
+            // parent will either be null or an Object, unless the prototype isn't set
             let parent = self.get_prototype_of();
-            if parent.is_null() {
+            if !parent.is_object() {
                 return Value::undefined();
             }
 
@@ -140,8 +146,9 @@ pub trait ObjectInternalMethods {
 
         // Fetch property key
         let mut own_desc = self.get_own_property(&field);
+        let is_new = own_desc.is_none();
         // [2]
-        if own_desc.is_none() {
+        if is_new {
             let parent = self.get_prototype_of();
             if !parent.is_null() {
                 // TODO: come back to this
@@ -159,16 +166,19 @@ pub trait ObjectInternalMethods {
 
             // Change value on the current descriptor
             own_desc = own_desc.value(val);
+            if field.is_symbol() {
+                if is_new && !self.is_extensible() {
+                    return false;
+                }
+                self.insert_symbol_property(&field, own_desc);
+                return true;
+            }
             return self.define_own_property(field.to_string(), own_desc);
         }
         // [4]
         debug_assert!(own_desc.is_accessor_descriptor());
-        match own_desc.set {
-            None => false,
-            Some(_) => {
-                unimplemented!();
-            }
-        }
+        // The setters are called by the interpreter, which looks for them before setting a field.
+        false
     }
 
     fn define_own_property(&mut self, property_key: String, desc: Property) -> bool {
@@ -243,7 +253,6 @@ pub trait ObjectInternalMethods {
                     && !same_value(
                         &desc.value.clone().unwrap(),
                         &current.value.clone().unwrap(),
-                        false,
                     )
                 {
                     return false;
@@ -255,21 +264,13 @@ pub trait ObjectInternalMethods {
         } else {
             if !current.configurable.unwrap() {
                 if desc.set.is_some()
-                    && !same_value(
-                        &desc.set.clone().unwrap(),
-                        &current.set.clone().unwrap(),
-                        false,
-                    )
+                    && !same_value(&desc.set.clone().unwrap(), &current.set.clone().unwrap())
                 {
                     return false;
                 }
 
                 if desc.get.is_some()
-                    && !same_value(
-                        &desc.get.clone().unwrap(),
-                        &current.get.clone().unwrap(),
-                        false,
-                    )
+                    && !same_value(&desc.get.clone().unwrap(), &current.get.clone().unwrap())
                 {
                     return false;
                 }
@@ -303,4 +304,8 @@ pub trait ObjectInternalMethods {
     fn insert_property(&mut self, name: String, p: Property);
 
     fn remove_property(&mut self, name: &str);
+
+    fn insert_symbol_property(&mut self, key: &Value, p: Property);
+
+    fn remove_symbol_property(&mut self, key: &Value);
 }
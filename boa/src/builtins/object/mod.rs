@@ -17,25 +17,30 @@ use crate::{
     builtins::{
         function::Function,
         property::Property,
+        string::JsString,
         value::{same_value, ResultValue, Value, ValueData},
+        Array,
     },
     exec::Interpreter,
     BoaProfiler,
 };
 use gc::{unsafe_empty_trace, Finalize, Trace};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::{
-    borrow::Borrow,
+    borrow::{Borrow, Cow},
     fmt::{self, Debug, Display, Error, Formatter},
+    mem,
     ops::Deref,
 };
 
-use super::function::{make_builtin_fn, make_constructor_fn};
+use super::function::{make_builtin_accessor, make_builtin_fn, make_constructor_fn};
 pub use internal_methods_trait::ObjectInternalMethods;
 pub use internal_state::{InternalState, InternalStateCell};
+pub use shape::{OrderedMap, PropertyCache, PropertyMap, Shape};
 
 pub mod internal_methods_trait;
 mod internal_state;
+pub mod shape;
 
 #[cfg(test)]
 mod tests;
@@ -46,6 +51,62 @@ pub static PROTOTYPE: &str = "prototype";
 /// Static `__proto__`, usually set on Object instances as a key to point to their respective prototype object.
 pub static INSTANCE_PROTOTYPE: &str = "__proto__";
 
+/// Gets the unique id of a symbol, which is the key of its properties in `sym_properties`.
+pub(crate) fn symbol_id(symbol: &Value) -> Option<i32> {
+    match symbol.data() {
+        ValueData::Symbol(ref sym) => match *(**sym).borrow().get_internal_slot("SymbolData") {
+            ValueData::Integer(sym_id) => Some(sym_id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Gets the keys of the own enumerable string-keyed properties of an object, in property order:
+/// the array indices in ascending order, then the other keys in the order they were added.
+///
+/// Values that aren't objects have no properties.
+pub(crate) fn enumerable_keys(value: &Value) -> Vec<String> {
+    match value.as_object() {
+        Some(obj) => (0..obj.string_length())
+            .map(|index| index.to_string())
+            .chain(
+                obj.properties
+                    .iter()
+                    .filter(|(_, property)| property.enumerable == Some(true))
+                    .map(|(key, _)| key.into_owned()),
+            )
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Gets the keys of all the string-keyed properties of an object, enumerable or not: its own
+/// keys, then the keys of its prototypes which it doesn't shadow, nearest prototype first.
+///
+/// This is meant for the hosts which reflect on the objects, like the completion of a REPL.
+/// Values that aren't objects have no properties.
+pub fn property_names(value: &Value) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut seen = FxHashSet::default();
+    let mut visited: Vec<Value> = Vec::new();
+    let mut current = value.clone();
+    while current.is_object() && !visited.iter().any(|object| same_value(object, &current)) {
+        let prototype = {
+            let obj = current.as_object().expect("the value is an object");
+            let indices = (0..obj.string_length()).map(|index| index.to_string());
+            for key in indices.chain(obj.properties.keys().map(Cow::into_owned)) {
+                if seen.insert(key.clone()) {
+                    names.push(key);
+                }
+            }
+            obj.get_internal_slot(INSTANCE_PROTOTYPE)
+        };
+        visited.push(mem::replace(&mut current, prototype));
+    }
+    names
+}
+
 /// The internal representation of an JavaScript object.
 #[derive(Trace, Finalize, Clone)]
 pub struct Object {
@@ -54,9 +115,9 @@ pub struct Object {
     /// Internal Slots
     pub internal_slots: FxHashMap<String, Value>,
     /// Properties
-    pub properties: FxHashMap<String, Property>,
-    /// Symbol Properties
-    pub sym_properties: FxHashMap<i32, Property>,
+    pub properties: PropertyMap,
+    /// Symbol Properties, in insertion order
+    pub sym_properties: OrderedMap<i32, Property>,
     /// Some rust object that stores internal state
     pub state: Option<InternalStateCell>,
     /// Function
@@ -93,7 +154,7 @@ impl ObjectInternalMethods for Object {
     fn set_prototype_of(&mut self, val: Value) -> bool {
         debug_assert!(val.is_object() || val.is_null());
         let current = self.get_internal_slot(PROTOTYPE);
-        if same_value(&current, &val, false) {
+        if same_value(&current, &val) {
             return true;
         }
         let extensible = self.get_internal_slot("extensible");
@@ -105,7 +166,7 @@ impl ObjectInternalMethods for Object {
         while !done {
             if p.is_null() {
                 done = true
-            } else if same_value(&Value::from(self.clone()), &p, false) {
+            } else if same_value(&Value::from(self.clone()), &p) {
                 return false;
             } else {
                 p = p.get_internal_slot(PROTOTYPE);
@@ -125,6 +186,20 @@ impl ObjectInternalMethods for Object {
         self.properties.remove(name);
     }
 
+    /// Helper function for symbol keyed property insertion.
+    fn insert_symbol_property(&mut self, key: &Value, p: Property) {
+        if let Some(sym_id) = symbol_id(key) {
+            self.sym_properties.insert(sym_id, p);
+        }
+    }
+
+    /// Helper function for symbol keyed property removal.
+    fn remove_symbol_property(&mut self, key: &Value) {
+        if let Some(sym_id) = symbol_id(key) {
+            self.sym_properties.remove(&sym_id);
+        }
+    }
+
     /// Helper function to set an internal slot
     fn set_internal_slot(&mut self, name: &str, val: Value) {
         self.internal_slots.insert(name.to_string(), val);
@@ -153,10 +228,11 @@ impl ObjectInternalMethods for Object {
         // Prop could either be a String or Symbol
         match *(*prop) {
             ValueData::String(ref st) => {
-                match self.properties.get(st) {
+                let key = st.to_string_lossy();
+                match self.properties.get(&*key) {
                     // If O does not have an own property with key P, return undefined.
                     // In this case we return a new empty Property
-                    None => Property::default(),
+                    None => self.string_get_own_property(&key).unwrap_or_default(),
                     Some(ref v) => {
                         let mut d = Property::default();
                         if v.is_data_descriptor() {
@@ -173,13 +249,8 @@ impl ObjectInternalMethods for Object {
                     }
                 }
             }
-            ValueData::Symbol(ref sym) => {
-                let sym_id = (**sym)
-                    .borrow()
-                    .get_internal_slot("SymbolData")
-                    .to_string()
-                    .parse::<i32>()
-                    .expect("Could not get Symbol ID");
+            ValueData::Symbol(_) => {
+                let sym_id = symbol_id(prop).expect("Could not get Symbol ID");
                 match self.sym_properties.get(&sym_id) {
                     // If O does not have an own property with key P, return undefined.
                     // In this case we return a new empty Property
@@ -206,134 +277,18 @@ impl ObjectInternalMethods for Object {
 
     /// Define an own property.
     ///
+    /// Arrays are exotic objects, which keep their `length` in sync with their elements.
+    ///
     /// More information:
     ///  - [ECMAScript reference][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots-defineownproperty-p-desc
-    #[allow(clippy::option_unwrap_used)]
     fn define_own_property(&mut self, property_key: String, desc: Property) -> bool {
-        let mut current = self.get_own_property(&Value::from(property_key.to_string()));
-        let extensible = self.is_extensible();
-
-        // https://tc39.es/ecma262/#sec-validateandapplypropertydescriptor
-        // There currently isn't a property, lets create a new one
-        if current.value.is_none() || current.value.as_ref().expect("failed").is_undefined() {
-            if !extensible {
-                return false;
-            }
-            if desc.value.is_some() && desc.value.clone().unwrap().is_symbol() {
-                let sym_id = desc
-                    .value
-                    .clone()
-                    .unwrap()
-                    .to_string()
-                    .parse::<i32>()
-                    .expect("parsing failed");
-                self.sym_properties.insert(sym_id, desc);
-            } else {
-                self.properties.insert(property_key, desc);
-            }
-            return true;
-        }
-        // If every field is absent we don't need to set anything
-        if desc.is_none() {
-            return true;
-        }
-
-        // 4
-        if !current.configurable.unwrap_or(false) {
-            if desc.configurable.is_some() && desc.configurable.unwrap() {
-                return false;
-            }
-
-            if desc.enumerable.is_some()
-                && (desc.enumerable.as_ref().unwrap() != current.enumerable.as_ref().unwrap())
-            {
-                return false;
-            }
-        }
-
-        // 5
-        if desc.is_generic_descriptor() {
-            // 6
-        } else if current.is_data_descriptor() != desc.is_data_descriptor() {
-            // a
-            if !current.configurable.unwrap() {
-                return false;
-            }
-            // b
-            if current.is_data_descriptor() {
-                // Convert to accessor
-                current.value = None;
-                current.writable = None;
-            } else {
-                // c
-                // convert to data
-                current.get = None;
-                current.set = None;
-            }
-
-            if current.value.is_some() && current.value.clone().unwrap().is_symbol() {
-                let sym_id = current
-                    .value
-                    .clone()
-                    .unwrap()
-                    .to_string()
-                    .parse::<i32>()
-                    .expect("parsing failed");
-                self.sym_properties.insert(sym_id, current);
-            } else {
-                self.properties.insert(property_key.clone(), current);
-            }
-        // 7
-        } else if current.is_data_descriptor() && desc.is_data_descriptor() {
-            // a
-            if !current.configurable.unwrap() && !current.writable.unwrap() {
-                if desc.writable.is_some() && desc.writable.unwrap() {
-                    return false;
-                }
-
-                if desc.value.is_some()
-                    && !same_value(
-                        &desc.value.clone().unwrap(),
-                        &current.value.clone().unwrap(),
-                        false,
-                    )
-                {
-                    return false;
-                }
-
-                return true;
-            }
-        // 8
+        if self.kind == ObjectKind::Array {
+            self.array_define_own_property(property_key, desc)
         } else {
-            if !current.configurable.unwrap() {
-                if desc.set.is_some()
-                    && !same_value(
-                        &desc.set.clone().unwrap(),
-                        &current.set.clone().unwrap(),
-                        false,
-                    )
-                {
-                    return false;
-                }
-
-                if desc.get.is_some()
-                    && !same_value(
-                        &desc.get.clone().unwrap(),
-                        &current.get.clone().unwrap(),
-                        false,
-                    )
-                {
-                    return false;
-                }
-            }
-
-            return true;
+            self.ordinary_define_own_property(property_key, desc)
         }
-        // 9
-        self.properties.insert(property_key, desc);
-        true
     }
 }
 
@@ -343,8 +298,8 @@ impl Object {
         let mut object = Self {
             kind: ObjectKind::Ordinary,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -359,8 +314,8 @@ impl Object {
         let mut object = Self {
             kind: ObjectKind::Function,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -395,8 +350,8 @@ impl Object {
         let mut obj = Self {
             kind: ObjectKind::Boolean,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -411,8 +366,8 @@ impl Object {
         let mut obj = Self {
             kind: ObjectKind::Number,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -427,8 +382,8 @@ impl Object {
         let mut obj = Self {
             kind: ObjectKind::String,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -443,8 +398,8 @@ impl Object {
         let mut obj = Self {
             kind: ObjectKind::BigInt,
             internal_slots: FxHashMap::default(),
-            properties: FxHashMap::default(),
-            sym_properties: FxHashMap::default(),
+            properties: PropertyMap::new(),
+            sym_properties: OrderedMap::default(),
             state: None,
             func: None,
         };
@@ -496,6 +451,319 @@ impl Object {
             None => false,
         }
     }
+
+    /// Gets the string of a `String` object.
+    fn string_data(&self) -> Option<JsString> {
+        if self.kind != ObjectKind::String {
+            return None;
+        }
+        match *self.internal_slots.get("StringData")?.data() {
+            ValueData::String(ref string) => Some(string.clone()),
+            _ => None,
+        }
+    }
+
+    /// Gets the number of characters of a `String` object, which is `0` for other objects.
+    pub(crate) fn string_length(&self) -> usize {
+        self.string_data().map_or(0, |string| string.len())
+    }
+
+    /// Gets the property of a character of a `String` object, which is an enumerable data
+    /// property that is neither writable nor configurable, keyed by its index in the string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-stringgetownproperty
+    pub(crate) fn string_get_own_property(&self, key: &str) -> Option<Property> {
+        let index = shape::array_index(key)?;
+        let unit = self.string_data()?.code_unit_at(index)?;
+        Some(
+            Property::new()
+                .value(Value::from(JsString::from_utf16(&[unit])))
+                .writable(false)
+                .enumerable(true)
+                .configurable(false),
+        )
+    }
+
+    /// Sets an element of the object without going through its property key, updating the
+    /// length of arrays.
+    ///
+    /// This only handles the elements stored in the vector of elements of the properties, and
+    /// returns `false`, without setting anything, for the others.
+    pub(crate) fn set_element(&mut self, index: usize, value: Value) -> bool {
+        let length = match self.kind {
+            ObjectKind::Array => match self.properties.array_length() {
+                Some(length) => Some(length),
+                None => return false,
+            },
+            // The characters of strings aren't stored as elements, so they can't be shadowed.
+            ObjectKind::String => return false,
+            _ => None,
+        };
+        if self.properties.element(index).is_none() && !self.is_extensible() {
+            return false;
+        }
+        if !self.properties.set_element(index, value) {
+            return false;
+        }
+
+        if let Some(length) = length {
+            if length as usize <= index {
+                self.properties.set_array_length(index as u32 + 1);
+            }
+        }
+        true
+    }
+
+    /// Defines an own property of an ordinary object.
+    ///
+    /// A new property gets the default attributes for the fields absent from the descriptor, and
+    /// an existing one only has the fields present in the descriptor changed.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinarydefineownproperty
+    #[allow(clippy::option_unwrap_used)]
+    pub(crate) fn ordinary_define_own_property(
+        &mut self,
+        property_key: String,
+        desc: Property,
+    ) -> bool {
+        let mut current = self.get_own_property(&Value::from(property_key.to_string()));
+        let extensible = self.is_extensible();
+
+        // https://tc39.es/ecma262/#sec-validateandapplypropertydescriptor
+        // There currently isn't a property, lets create a new one
+        if current.value.is_none() && !current.is_accessor_descriptor() {
+            if !extensible {
+                return false;
+            }
+            self.properties
+                .insert(property_key, with_default_attributes(desc));
+            return true;
+        }
+        // If every field is absent we don't need to set anything
+        if desc.is_none() {
+            return true;
+        }
+
+        // 4
+        if !current.configurable.unwrap_or(false) {
+            if desc.configurable.is_some() && desc.configurable.unwrap() {
+                return false;
+            }
+
+            if desc.enumerable.is_some()
+                && (desc.enumerable.as_ref().unwrap() != current.enumerable.as_ref().unwrap())
+            {
+                return false;
+            }
+        }
+
+        // 5
+        if desc.is_generic_descriptor() {
+            // 6
+        } else if current.is_data_descriptor() != desc.is_data_descriptor() {
+            // a
+            if !current.configurable.unwrap() {
+                return false;
+            }
+            // b
+            if current.is_data_descriptor() {
+                // Convert to accessor, keeping the other attributes
+                current.value = None;
+                current.writable = None;
+                current.get = Some(Value::undefined());
+                current.set = Some(Value::undefined());
+            } else {
+                // c
+                // convert to data
+                current.get = None;
+                current.set = None;
+                current.value = Some(Value::undefined());
+                current.writable = Some(false);
+            }
+        // 7
+        } else if current.is_data_descriptor() && desc.is_data_descriptor() {
+            // a
+            if !current.configurable.unwrap() && !current.writable.unwrap() {
+                if desc.writable.is_some() && desc.writable.unwrap() {
+                    return false;
+                }
+
+                if desc.value.is_some()
+                    && !same_value(
+                        &desc.value.clone().unwrap(),
+                        &current.value.clone().unwrap(),
+                    )
+                {
+                    return false;
+                }
+
+                return true;
+            }
+        // 8
+        } else if !current.configurable.unwrap() {
+            if desc.set.is_some()
+                && !same_value(
+                    &desc.set.clone().unwrap(),
+                    &current.set.clone().unwrap_or_default(),
+                )
+            {
+                return false;
+            }
+
+            if desc.get.is_some()
+                && !same_value(
+                    &desc.get.clone().unwrap(),
+                    &current.get.clone().unwrap_or_default(),
+                )
+            {
+                return false;
+            }
+
+            return true;
+        }
+        // 9
+        let property = Property {
+            configurable: desc.configurable.or(current.configurable),
+            enumerable: desc.enumerable.or(current.enumerable),
+            writable: desc.writable.or(current.writable),
+            value: desc.value.clone().or_else(|| current.value.clone()),
+            get: desc.get.clone().or_else(|| current.get.clone()),
+            set: desc.set.clone().or_else(|| current.set.clone()),
+        };
+        self.properties.insert(property_key, property);
+        true
+    }
+
+    /// Defines an own property of an array.
+    ///
+    /// Defining an element at or past the `length` of the array increases it, unless it isn't
+    /// writable, in which case nothing is defined.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-array-exotic-objects-defineownproperty-p-desc
+    fn array_define_own_property(&mut self, property_key: String, desc: Property) -> bool {
+        if property_key == "length" {
+            return self.array_set_length(desc);
+        }
+        let index = match shape::array_index(&property_key) {
+            Some(index) => index,
+            None => return self.ordinary_define_own_property(property_key, desc),
+        };
+        let (length, writable) = match self.array_length() {
+            Some(length) => length,
+            None => return self.ordinary_define_own_property(property_key, desc),
+        };
+
+        if index >= length as usize && !writable {
+            return false;
+        }
+        if !self.ordinary_define_own_property(property_key, desc) {
+            return false;
+        }
+        if index >= length as usize {
+            self.ordinary_define_own_property(
+                "length".to_string(),
+                Property::new().value(shape::array_length_value(index as u32 + 1)),
+            );
+        }
+        true
+    }
+
+    /// Sets the `length` of an array, deleting the elements past the new length.
+    ///
+    /// Returns `false` if the length isn't writable, or isn't a valid array length, and if an
+    /// element that isn't configurable can't be deleted, in which case the length stops right
+    /// after it.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-arraysetlength
+    fn array_set_length(&mut self, mut desc: Property) -> bool {
+        let new_length = match desc.value {
+            Some(ref value) => match shape::to_array_length(value) {
+                Some(length) => length,
+                None => return false,
+            },
+            None => return self.ordinary_define_own_property("length".to_string(), desc),
+        };
+        desc.value = Some(shape::array_length_value(new_length));
+        let (old_length, writable) = match self.array_length() {
+            Some(length) if new_length < length.0 => length,
+            _ => return self.ordinary_define_own_property("length".to_string(), desc),
+        };
+        if !writable {
+            return false;
+        }
+
+        // The length is made non-writable only after the elements are deleted.
+        let new_writable = desc.writable != Some(false);
+        desc.writable = Some(true);
+        if !self.ordinary_define_own_property("length".to_string(), desc.clone()) {
+            return false;
+        }
+
+        let mut indices: Vec<usize> = self
+            .properties
+            .keys()
+            .filter_map(|key| shape::array_index(&key))
+            .filter(|&index| index >= new_length as usize && index < old_length as usize)
+            .collect();
+        indices.sort_unstable_by(|a, b| b.cmp(a));
+        for index in indices {
+            if !self.delete(&Value::from(index.to_string())) {
+                desc.value = Some(shape::array_length_value(index as u32 + 1));
+                desc.writable = Some(new_writable);
+                self.ordinary_define_own_property("length".to_string(), desc);
+                return false;
+            }
+        }
+
+        if !new_writable {
+            self.ordinary_define_own_property(
+                "length".to_string(),
+                Property::new().writable(false),
+            );
+        }
+        true
+    }
+
+    /// Gets the value of the `length` of an array, and whether it is writable.
+    fn array_length(&self) -> Option<(u32, bool)> {
+        if let Some(length) = self.properties.array_length() {
+            return Some((length, true));
+        }
+        let length = self.properties.get("length")?;
+        let value = shape::to_array_length(length.value.as_ref()?)?;
+        Some((value, length.writable.unwrap_or(false)))
+    }
+}
+
+/// Fills the fields absent from the descriptor of a new property with their default values.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#table-default-attribute-values
+fn with_default_attributes(mut desc: Property) -> Property {
+    if desc.is_accessor_descriptor() {
+        desc.get.get_or_insert_with(Value::undefined);
+        desc.set.get_or_insert_with(Value::undefined);
+    } else {
+        desc.value.get_or_insert_with(Value::undefined);
+        desc.writable.get_or_insert(false);
+    }
+    desc.enumerable.get_or_insert(false);
+    desc.configurable.get_or_insert(false);
+    desc
 }
 
 /// Defines the different types of objects.
@@ -558,27 +826,266 @@ pub fn make_object(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> Resu
     Ok(object)
 }
 
-/// Get the `prototype` of an object.
-pub fn get_prototype_of(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object");
-    Ok(obj.get_field(INSTANCE_PROTOTYPE))
+/// Gets the prototype of an object, which is `null` if it has none.
+fn prototype(object: &Value) -> Value {
+    match object.get_internal_slot(INSTANCE_PROTOTYPE) {
+        prototype if prototype.is_object() => prototype,
+        _ => Value::null(),
+    }
 }
 
-/// Set the `prototype` of an object.
-pub fn set_prototype_of(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object").clone();
-    let proto = args.get(1).expect("Cannot get object").clone();
-    obj.set_internal_slot(INSTANCE_PROTOTYPE, proto);
-    Ok(obj)
+/// Sets the prototype of an object, which must be an object or `null`.
+///
+/// Returns `false`, without changing it, if the object isn't extensible, or if the object would
+/// be in its own prototype chain.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-ordinarysetprototypeof
+pub(crate) fn set_prototype(object: &Value, proto: Value) -> bool {
+    debug_assert!(proto.is_object() || proto.is_null());
+    if same_value(&prototype(object), &proto) {
+        return true;
+    }
+    if !object.as_object().is_some_and(|obj| obj.is_extensible()) {
+        return false;
+    }
+    let mut ancestor = proto.clone();
+    while ancestor.is_object() {
+        if same_value(&ancestor, object) {
+            return false;
+        }
+        ancestor = ancestor.get_internal_slot(INSTANCE_PROTOTYPE);
+    }
+    object.set_internal_slot(INSTANCE_PROTOTYPE, proto);
+    true
 }
 
-/// Define a property in an object
+/// `Object.create( proto [, propertiesObject ] )`
+///
+/// This method creates a new object, using an existing object, or `null`, as its prototype, and
+/// defines the properties described by the second argument on it.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.create
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/create
+pub fn create_object(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let proto = args.first().cloned().unwrap_or_default();
+    if !proto.is_object() && !proto.is_null() {
+        return ctx.throw_type_error(format!(
+            "Object prototype may only be an Object or null: {}",
+            proto
+        ));
+    }
+    let object = Value::new_object_from_prototype(proto, ObjectKind::Ordinary);
+
+    let properties = args.get(1).cloned().unwrap_or_default();
+    if !properties.is_undefined() {
+        let properties = ctx.to_object(&properties)?;
+        let mut descriptors = Vec::new();
+        for key in enumerable_keys(&properties) {
+            let descriptor = ctx.get_field_value(&properties, Value::from(key.as_str()))?;
+            descriptors.push((key, to_property_descriptor(&descriptor, ctx)?));
+        }
+        if let ValueData::Object(ref obj) = *object.data() {
+            let mut obj = obj.borrow_mut();
+            for (key, descriptor) in descriptors {
+                obj.define_own_property(key, descriptor);
+            }
+        }
+    }
+    Ok(object)
+}
+
+/// `Object.getPrototypeOf( obj )`
+///
+/// This method returns the prototype of an object, which is `null` if it has none.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.getprototypeof
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/getPrototypeOf
+pub fn get_prototype_of(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let object = ctx.to_object(&args.first().cloned().unwrap_or_default())?;
+    Ok(prototype(&object))
+}
+
+/// `Object.setPrototypeOf( obj, prototype )`
+///
+/// This method sets the prototype of an object to another object or `null`, and returns the
+/// object. It throws a `TypeError` if the object isn't extensible or if the prototype chain would
+/// have a cycle.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.setprototypeof
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/setPrototypeOf
+pub fn set_prototype_of(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let object = args.first().cloned().unwrap_or_default();
+    if object.is_null_or_undefined() {
+        return ctx.throw_type_error(format!(
+            "Object.setPrototypeOf called on null or undefined: {}",
+            object
+        ));
+    }
+    let proto = args.get(1).cloned().unwrap_or_default();
+    if !proto.is_object() && !proto.is_null() {
+        return ctx.throw_type_error(format!(
+            "Object prototype may only be an Object or null: {}",
+            proto
+        ));
+    }
+    if object.is_object() && !set_prototype(&object, proto) {
+        return ctx.throw_type_error(
+            "Object.setPrototypeOf: the object is not extensible, or the prototype chain would be cyclic",
+        );
+    }
+    Ok(object)
+}
+
+/// `Object.defineProperty( obj, prop, descriptor )`
+///
+/// This method defines a new property directly on an object, or modifies an existing property,
+/// and returns the object.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.defineproperty
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/defineProperty
 pub fn define_property(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-    let obj = args.get(0).expect("Cannot get object");
-    let prop = ctx.to_string(args.get(1).expect("Cannot get object"))?;
-    let desc = Property::from(args.get(2).expect("Cannot get object"));
-    obj.set_property(prop, desc);
-    Ok(Value::undefined())
+    let object = args.first().cloned().unwrap_or_default();
+    if !object.is_object() {
+        return ctx.throw_type_error("Object.defineProperty called on non-object");
+    }
+    let key = ctx.to_string(&args.get(1).cloned().unwrap_or_default())?;
+    let mut desc = to_property_descriptor(&args.get(2).cloned().unwrap_or_default(), ctx)?;
+
+    let is_array = object
+        .as_object()
+        .is_some_and(|obj| obj.kind == ObjectKind::Array);
+    if is_array && key == "length" {
+        if let Some(ref value) = desc.value {
+            let length = Array::to_array_length(value, ctx)?;
+            desc.value = Some(Value::from(f64::from(length)));
+        }
+    }
+
+    let defined = match *object.data() {
+        ValueData::Object(ref obj) => obj.borrow_mut().define_own_property(key.clone(), desc),
+        _ => unreachable!(),
+    };
+    if !defined {
+        return ctx.throw_type_error(format!("cannot redefine property: {}", key));
+    }
+    Ok(object)
+}
+
+/// Converts an object into a property descriptor, which only has the fields the object has.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-topropertydescriptor
+fn to_property_descriptor(object: &Value, ctx: &mut Interpreter) -> Result<Property, Value> {
+    if !object.is_object() {
+        ctx.throw_type_error(format!(
+            "Property description must be an object: {}",
+            object
+        ))?;
+    }
+
+    let mut desc = Property::new();
+    if object.has_field("enumerable") {
+        let enumerable = ctx.get_field_value(object, Value::from("enumerable"))?;
+        desc.enumerable = Some(bool::from(&enumerable));
+    }
+    if object.has_field("configurable") {
+        let configurable = ctx.get_field_value(object, Value::from("configurable"))?;
+        desc.configurable = Some(bool::from(&configurable));
+    }
+    if object.has_field("value") {
+        desc.value = Some(ctx.get_field_value(object, Value::from("value"))?);
+    }
+    if object.has_field("writable") {
+        let writable = ctx.get_field_value(object, Value::from("writable"))?;
+        desc.writable = Some(bool::from(&writable));
+    }
+    desc.get = accessor_field(object, "get", ctx)?;
+    desc.set = accessor_field(object, "set", ctx)?;
+
+    if desc.is_accessor_descriptor() && desc.is_data_descriptor() {
+        ctx.throw_type_error(
+            "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute",
+        )?;
+    }
+    Ok(desc)
+}
+
+/// Gets the getter or the setter of a property descriptor object, which must be a function or
+/// `undefined`.
+fn accessor_field(
+    object: &Value,
+    name: &str,
+    ctx: &mut Interpreter,
+) -> Result<Option<Value>, Value> {
+    if !object.has_field(name) {
+        return Ok(None);
+    }
+    let accessor = ctx.get_field_value(object, Value::from(name))?;
+    if !accessor.is_function() && !accessor.is_undefined() {
+        ctx.throw_type_error(format!(
+            "Property descriptor {} must be a function: {}",
+            name, accessor
+        ))?;
+    }
+    Ok(Some(accessor))
+}
+
+/// `Object.is( value1, value2 )`
+///
+/// This method determines whether two values are the same value.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.is
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/is
+pub fn is(_: &mut Value, args: &[Value], _: &mut Interpreter) -> ResultValue {
+    let undefined = Value::undefined();
+    let x = args.first().unwrap_or(&undefined);
+    let y = args.get(1).unwrap_or(&undefined);
+    Ok(Value::boolean(same_value(x, y)))
+}
+
+/// `Object.keys( obj )`
+///
+/// This method returns an array of the keys of the own enumerable string-keyed properties of an
+/// object, in the same order as a `for...in` loop.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-object.keys
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/keys
+pub fn keys(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let object = ctx.to_object(&args.first().cloned().unwrap_or_default())?;
+    let keys: Vec<Value> = enumerable_keys(&object)
+        .into_iter()
+        .map(Value::from)
+        .collect();
+    Array::construct_array(&Array::new_array(ctx)?, &keys)
 }
 
 /// `Object.prototype.toString()`
@@ -612,7 +1119,8 @@ pub fn has_own_property(this: &mut Value, args: &[Value], ctx: &mut Interpreter)
     } else {
         Some(ctx.to_string(args.get(0).expect("Cannot get object"))?)
     };
-    let own_property = this
+    let object = ctx.to_object(this)?;
+    let own_property = object
         .as_object()
         .as_deref()
         .expect("Cannot get THIS object")
@@ -624,19 +1132,65 @@ pub fn has_own_property(this: &mut Value, args: &[Value], ctx: &mut Interpreter)
     }
 }
 
+/// `get Object.prototype.__proto__`
+///
+/// This accessor returns the prototype of an object, which is `null` if it has none.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-get-object.prototype.__proto__
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/proto
+pub fn get_proto(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let object = ctx.to_object(this)?;
+    Ok(prototype(&object))
+}
+
+/// `set Object.prototype.__proto__`
+///
+/// This accessor sets the prototype of an object, like `Object.setPrototypeOf`, and ignores the
+/// values which are neither objects nor `null`.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-set-object.prototype.__proto__
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Object/proto
+pub fn set_proto(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if this.is_null_or_undefined() {
+        return ctx.throw_type_error(format!(
+            "Object.prototype.__proto__ called on null or undefined: {}",
+            this
+        ));
+    }
+    let proto = args.first().cloned().unwrap_or_default();
+    if (proto.is_object() || proto.is_null()) && this.is_object() && !set_prototype(this, proto) {
+        return ctx.throw_type_error(
+            "Object.prototype.__proto__: the object is not extensible, or the prototype chain would be cyclic",
+        );
+    }
+    Ok(Value::undefined())
+}
+
 /// Create a new `Object` object.
 pub fn create(global: &Value) -> Value {
     let prototype = Value::new_object(None);
 
     make_builtin_fn(has_own_property, "hasOwnProperty", &prototype, 0);
     make_builtin_fn(to_string, "toString", &prototype, 0);
+    make_builtin_accessor(get_proto, Some(set_proto), INSTANCE_PROTOTYPE, &prototype);
 
     let object = make_constructor_fn("Object", 1, make_object, global, prototype, true);
 
     object.set_field("length", Value::from(1));
+    make_builtin_fn(create_object, "create", &object, 2);
     make_builtin_fn(set_prototype_of, "setPrototypeOf", &object, 2);
     make_builtin_fn(get_prototype_of, "getPrototypeOf", &object, 1);
     make_builtin_fn(define_property, "defineProperty", &object, 3);
+    make_builtin_fn(is, "is", &object, 2);
+    make_builtin_fn(keys, "keys", &object, 1);
 
     object
 }
@@ -0,0 +1,735 @@
+//! This module implements the storage of the string keyed properties of objects.
+//!
+//! Objects that get the same properties added in the same order share a `Shape`, also known as
+//! a hidden class, which maps each property key to a slot. The object itself only stores the
+//! properties, in a vector indexed by those slots.
+//!
+//! Shapes form a transition tree: the root shape has no properties, and adding a property to an
+//! object moves it to the child shape for that key, which is created the first time it's needed
+//! and shared afterwards. Since the slot of a key only depends on the shape, the shape can be
+//! used to cache property lookups.
+//!
+//! Deleting a property, or adding too many of them, moves the object to dictionary mode, where
+//! it stores its properties in its own insertion-ordered hash map, as they can't be shared
+//! anymore.
+//!
+//! The elements of arrays and array-like objects are kept apart from the named properties, in a
+//! vector indexed by the array index, and so is the `length` of arrays.
+//!
+//! The properties are visited in the order of the specification: the array indices in ascending
+//! order first, then the other keys in the order they were added.
+//!
+//! A `PropertyCache` remembers the slots of a property for the last few shapes seen at a property
+//! access, which lets the interpreter skip the lookup of the key when the shape matches.
+//!
+//! The slots of the named properties only move when one of them is removed, which changes the
+//! epoch of the property map, so the slots of the properties of an object can also be cached for
+//! as long as its epoch stays the same, whatever its shape, or in dictionary mode.
+
+use crate::builtins::{
+    property::Property,
+    value::{Value, ValueData},
+};
+use gc::{custom_trace, unsafe_empty_trace, Finalize, Trace};
+use indexmap::{map, IndexMap};
+use rustc_hash::{FxHashMap, FxHasher};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    convert::TryFrom,
+    fmt::{self, Debug, Formatter},
+    hash::{BuildHasherDefault, Hash},
+    iter::{Enumerate, Zip},
+    mem,
+    ops::{Deref, DerefMut},
+    rc::{Rc, Weak},
+    slice, vec,
+};
+
+/// The maximum number of properties of an object with a shape.
+///
+/// Every shape has its own copy of the keys of its properties, so shapes with too many properties
+/// would use more memory than they save. Objects that grow beyond this are moved to dictionary
+/// mode.
+pub(crate) const MAX_SHAPED_PROPERTIES: usize = 64;
+
+/// The maximum number of shapes remembered by a property cache.
+///
+/// Accesses that see more shapes than this are megamorphic, and always do a full lookup.
+pub(crate) const MAX_CACHED_SHAPES: usize = 4;
+
+/// The largest array index, as array indices are below `2^32 - 1`.
+pub(crate) const MAX_ARRAY_INDEX: usize = u32::MAX as usize - 1;
+
+thread_local! {
+    /// The shape of the objects without properties, the root of the transition tree.
+    static ROOT_SHAPE: Shape = Shape(Rc::new(ShapeData {
+        parent: None,
+        keys: Vec::new(),
+        slots: FxHashMap::default(),
+        transitions: RefCell::default(),
+    }));
+}
+
+/// The layout of the properties of an object.
+///
+/// Two shapes are equal if, and only if, they are the same shape, so objects with equal shapes
+/// store the same properties in the same slots.
+#[derive(Clone)]
+pub struct Shape(Rc<ShapeData>);
+
+/// The contents of a shape.
+struct ShapeData {
+    /// The shape this one was made from.
+    ///
+    /// Each shape keeps its parent alive, so the shapes on the way to a shape that's in use can
+    /// be shared by the next objects that get the same properties.
+    #[allow(dead_code)]
+    parent: Option<Shape>,
+    /// The property keys, in the order of their slots.
+    keys: Vec<Rc<str>>,
+    /// The slot of each property key.
+    slots: FxHashMap<Rc<str>, usize>,
+    /// The shapes made by adding a property to this one.
+    ///
+    /// The children are weak, so shapes that no object uses anymore are freed.
+    transitions: RefCell<FxHashMap<Rc<str>, Weak<ShapeData>>>,
+}
+
+impl Shape {
+    /// Gets the shape of the objects without properties.
+    pub fn root() -> Self {
+        ROOT_SHAPE.with(Clone::clone)
+    }
+
+    /// Gets the number of properties of this shape.
+    pub(crate) fn len(&self) -> usize {
+        self.0.keys.len()
+    }
+
+    /// Gets the slot of the property with the given key.
+    pub fn lookup(&self, key: &str) -> Option<usize> {
+        self.0.slots.get(key).copied()
+    }
+
+    /// Gets the shape made by adding a property with the given key to this one.
+    ///
+    /// The new property gets the next slot.
+    fn transition(&self, key: &str) -> Self {
+        debug_assert!(self.lookup(key).is_none());
+        let mut transitions = self.0.transitions.borrow_mut();
+        if let Some(shape) = transitions.get(key).and_then(Weak::upgrade) {
+            return Self(shape);
+        }
+
+        // Drop the transitions to the shapes that were freed before adding a new one.
+        transitions.retain(|_, shape| shape.strong_count() > 0);
+
+        let key: Rc<str> = key.into();
+        let mut keys = Vec::with_capacity(self.len() + 1);
+        keys.extend(self.0.keys.iter().cloned());
+        keys.push(key.clone());
+        let mut slots = self.0.slots.clone();
+        slots.insert(key.clone(), self.len());
+
+        let shape = Rc::new(ShapeData {
+            parent: Some(self.clone()),
+            keys,
+            slots,
+            transitions: RefCell::default(),
+        });
+        transitions.insert(key, Rc::downgrade(&shape));
+        Self(shape)
+    }
+}
+
+impl PartialEq for Shape {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Shape {}
+
+impl Debug for Shape {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Shape").field(&self.0.keys).finish()
+    }
+}
+
+impl Finalize for Shape {}
+
+unsafe impl Trace for Shape {
+    unsafe_empty_trace!();
+}
+
+/// A hash map which keeps its entries in insertion order.
+///
+/// Removing an entry with `shift_remove` keeps the order of the other ones.
+#[derive(Debug, Clone)]
+pub struct OrderedMap<K: Hash + Eq, V>(IndexMap<K, V, BuildHasherDefault<FxHasher>>);
+
+impl<K: Hash + Eq, V> Default for OrderedMap<K, V> {
+    fn default() -> Self {
+        Self(IndexMap::default())
+    }
+}
+
+impl<K: Hash + Eq, V> Deref for OrderedMap<K, V> {
+    type Target = IndexMap<K, V, BuildHasherDefault<FxHasher>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K: Hash + Eq, V> DerefMut for OrderedMap<K, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<K: Hash + Eq, V> std::iter::FromIterator<(K, V)> for OrderedMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K: Hash + Eq, V> Finalize for OrderedMap<K, V> {}
+
+unsafe impl<K: Hash + Eq + Trace, V: Trace> Trace for OrderedMap<K, V> {
+    custom_trace!(this, {
+        for (key, value) in this.iter() {
+            mark(key);
+            mark(value);
+        }
+    });
+}
+
+/// The string keyed own properties of an object.
+///
+/// The elements, the properties whose keys are array indices, are stored in a vector as long as
+/// they are contiguous and start at index 0. Holes, huge indices and elements with unusual
+/// attributes fall back to the named properties.
+///
+/// The named properties are stored in slots described by a shared `Shape`, unless the object is
+/// in dictionary mode.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct PropertyMap {
+    /// The values of the elements at the start of the object, which are writable, enumerable and
+    /// configurable data properties.
+    elements: Vec<Value>,
+    /// Whether some array indices are stored as named properties.
+    sparse_elements: bool,
+    /// The value of the `length` property of an array, which is a writable data property that is
+    /// neither enumerable nor configurable.
+    array_length: Option<u32>,
+    /// The shape of the named properties, or `None` in dictionary mode.
+    shape: Option<Shape>,
+    /// The named properties, indexed by the slots of the shape.
+    slots: Vec<Property>,
+    /// The named properties in dictionary mode.
+    dictionary: OrderedMap<String, Property>,
+    /// The number of times a named property was removed, moving the slots of the ones after it.
+    epoch: u32,
+}
+
+impl PropertyMap {
+    /// Creates an empty property map, with the root shape.
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            sparse_elements: false,
+            array_length: None,
+            shape: Some(Shape::root()),
+            slots: Vec::new(),
+            dictionary: OrderedMap::default(),
+            epoch: 0,
+        }
+    }
+
+    /// Gets the shape of the named properties, or `None` if the map is in dictionary mode.
+    pub fn shape(&self) -> Option<&Shape> {
+        self.shape.as_ref()
+    }
+
+    /// Gets the epoch of the slots of the named properties.
+    ///
+    /// The named properties keep their slots until the epoch changes.
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// Gets the slot of the named property with the given key, in the shape or in the dictionary.
+    pub fn named_slot(&self, key: &str) -> Option<usize> {
+        match self.shape {
+            Some(ref shape) => shape.lookup(key),
+            None => self.dictionary.get_index_of(key),
+        }
+    }
+
+    /// Gets the named property in the given slot.
+    pub fn slot(&self, slot: usize) -> Option<&Property> {
+        match self.shape {
+            Some(_) => self.slots.get(slot),
+            None => self.dictionary.get_index(slot).map(|(_, prop)| prop),
+        }
+    }
+
+    /// Gets a mutable reference to the named property in the given slot.
+    pub fn slot_mut(&mut self, slot: usize) -> Option<&mut Property> {
+        match self.shape {
+            Some(_) => self.slots.get_mut(slot),
+            None => self.dictionary.get_index_mut(slot).map(|(_, prop)| prop),
+        }
+    }
+
+    /// Gets the value of an element stored in the vector of elements.
+    pub fn element(&self, index: usize) -> Option<&Value> {
+        self.elements.get(index)
+    }
+
+    /// Sets the value of an element, if it can be stored in the vector of elements.
+    ///
+    /// The element must already be in the vector, or be the next one. Returns `false`, without
+    /// setting anything, otherwise.
+    pub fn set_element(&mut self, index: usize, value: Value) -> bool {
+        if let Some(element) = self.elements.get_mut(index) {
+            *element = value;
+            true
+        } else if index == self.elements.len() && index <= MAX_ARRAY_INDEX && !self.sparse_elements
+        {
+            self.elements.push(value);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gets the value of the `length` property of an array.
+    pub fn array_length(&self) -> Option<u32> {
+        self.array_length
+    }
+
+    /// Sets the value of the `length` property of an array, if it already has one.
+    pub fn set_array_length(&mut self, length: u32) -> bool {
+        match self.array_length {
+            Some(ref mut array_length) => {
+                *array_length = length;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Gets the number of properties.
+    pub fn len(&self) -> usize {
+        let named = match self.shape {
+            Some(_) => self.slots.len(),
+            None => self.dictionary.len(),
+        };
+        self.elements.len() + self.array_length.iter().count() + named
+    }
+
+    /// Checks if there are no properties.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if there is a property with the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        match array_index(key) {
+            Some(index) if index < self.elements.len() => true,
+            _ if key == "length" && self.array_length.is_some() => true,
+            _ => self.get_named(key).is_some(),
+        }
+    }
+
+    /// Gets the property with the given key.
+    ///
+    /// The properties of the elements and of the array length aren't stored, so they are built
+    /// on the fly.
+    pub fn get(&self, key: &str) -> Option<Cow<'_, Property>> {
+        if let Some(index) = array_index(key) {
+            if let Some(value) = self.elements.get(index) {
+                return Some(Cow::Owned(element_property(value)));
+            }
+        } else if key == "length" {
+            if let Some(length) = self.array_length {
+                return Some(Cow::Owned(length_property(length)));
+            }
+        }
+        self.get_named(key).map(Cow::Borrowed)
+    }
+
+    /// Inserts a property, returning the property that had the same key, if any.
+    ///
+    /// A new named property moves the map to the next shape, or to dictionary mode if it has too
+    /// many properties.
+    pub fn insert(&mut self, key: String, property: Property) -> Option<Property> {
+        if let Some(index) = array_index(&key) {
+            let value = plain_element(&property);
+            if index < self.elements.len() {
+                match value {
+                    Some(value) => {
+                        let old = mem::replace(&mut self.elements[index], value.clone());
+                        return Some(element_property(&old));
+                    }
+                    None => self.spill_elements(index),
+                }
+            } else if let Some(value) = value {
+                if self.set_element(index, value.clone()) {
+                    return None;
+                }
+            }
+            self.sparse_elements = true;
+        } else if key == "length" {
+            match array_length(&property) {
+                Some(length) if self.array_length.is_some() || self.get_named(&key).is_none() => {
+                    return self.array_length.replace(length).map(length_property);
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(length) = self.array_length.take() {
+                        self.insert_named(key, property);
+                        return Some(length_property(length));
+                    }
+                }
+            }
+        }
+        self.insert_named(key, property)
+    }
+
+    /// Removes the property with the given key, returning it.
+    ///
+    /// Removing an element moves the elements after it to the named properties, and removing a
+    /// named property moves the map to dictionary mode.
+    pub fn remove(&mut self, key: &str) -> Option<Property> {
+        if let Some(index) = array_index(key) {
+            if index < self.elements.len() {
+                self.spill_elements(index + 1);
+                return self.elements.pop().as_ref().map(element_property);
+            }
+        } else if key == "length" {
+            if let Some(length) = self.array_length.take() {
+                return Some(length_property(length));
+            }
+        }
+
+        if self.get_named(key).is_none() {
+            return None;
+        }
+        self.to_dictionary();
+        self.epoch = self.epoch.wrapping_add(1);
+        self.dictionary.shift_remove(key)
+    }
+
+    /// Gets an iterator over the keys and the properties.
+    ///
+    /// The array indices are visited first, in ascending order, then the array length, then the
+    /// other named properties, in insertion order.
+    pub fn iter(&self) -> Iter<'_> {
+        // The array indices stored as named properties all come after the vector of elements.
+        let mut sparse = Vec::new();
+        if self.sparse_elements {
+            let named: Box<dyn Iterator<Item = (&str, &Property)>> = match self.shape {
+                Some(ref shape) => {
+                    Box::new(shape.0.keys.iter().map(|key| &**key).zip(self.slots.iter()))
+                }
+                None => Box::new(self.dictionary.iter().map(|(key, prop)| (&**key, prop))),
+            };
+            sparse.extend(named.filter_map(|(key, prop)| Some((array_index(key)?, prop))));
+            sparse.sort_by_key(|&(index, _)| index);
+        }
+
+        Iter {
+            elements: self.elements.iter().enumerate(),
+            named_indices: sparse.len(),
+            sparse: sparse.into_iter(),
+            array_length: self.array_length,
+            named: match self.shape {
+                Some(ref shape) => NamedIter::Shaped(shape.0.keys.iter().zip(self.slots.iter())),
+                None => NamedIter::Dictionary(self.dictionary.iter()),
+            },
+        }
+    }
+
+    /// Gets an iterator over the keys of the properties.
+    pub fn keys(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Gets the named property with the given key.
+    fn get_named(&self, key: &str) -> Option<&Property> {
+        match self.shape {
+            Some(ref shape) => shape.lookup(key).map(|slot| &self.slots[slot]),
+            None => self.dictionary.get(key),
+        }
+    }
+
+    /// Inserts a named property, returning the property that had the same key, if any.
+    fn insert_named(&mut self, key: String, property: Property) -> Option<Property> {
+        let existing = match self.shape {
+            Some(ref shape) => shape.lookup(&key).map(|slot| &mut self.slots[slot]),
+            None => self.dictionary.get_mut(&key),
+        };
+        if let Some(existing) = existing {
+            return Some(mem::replace(existing, property));
+        }
+
+        match self.shape {
+            Some(ref shape) if shape.len() < MAX_SHAPED_PROPERTIES => {
+                self.shape = Some(shape.transition(&key));
+                self.slots.push(property);
+            }
+            Some(_) => {
+                self.to_dictionary();
+                self.dictionary.insert(key, property);
+            }
+            None => {
+                self.dictionary.insert(key, property);
+            }
+        }
+        None
+    }
+
+    /// Moves the elements from the given index onwards to the named properties.
+    fn spill_elements(&mut self, index: usize) {
+        if index < self.elements.len() {
+            self.sparse_elements = true;
+            for (i, value) in self.elements.split_off(index).iter().enumerate() {
+                self.insert_named((index + i).to_string(), element_property(value));
+            }
+        }
+    }
+
+    /// Moves the named properties to dictionary mode, if they aren't already.
+    #[allow(clippy::wrong_self_convention)]
+    fn to_dictionary(&mut self) {
+        if let Some(shape) = self.shape.take() {
+            self.dictionary = shape
+                .0
+                .keys
+                .iter()
+                .map(|key| key.to_string())
+                .zip(self.slots.drain(..))
+                .collect();
+        }
+    }
+}
+
+impl Default for PropertyMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parses a property key that is an array index.
+///
+/// Only the canonical form of the indices is accepted, so `"01"` is not an array index.
+pub(crate) fn array_index(key: &str) -> Option<usize> {
+    let bytes = key.as_bytes();
+    match bytes {
+        [] => None,
+        [b'0'] => Some(0),
+        [b'0', ..] => None,
+        _ if bytes.len() <= 10 && bytes.iter().all(u8::is_ascii_digit) => key
+            .parse::<u64>()
+            .ok()
+            .filter(|&index| index <= MAX_ARRAY_INDEX as u64)
+            .map(|index| index as usize),
+        _ => None,
+    }
+}
+
+/// Gets the value of a property that can be stored in the vector of elements.
+fn plain_element(property: &Property) -> Option<&Value> {
+    match *property {
+        Property {
+            value: Some(ref value),
+            writable: Some(true),
+            enumerable: Some(true),
+            configurable: Some(true),
+            get: None,
+            set: None,
+        } => Some(value),
+        _ => None,
+    }
+}
+
+/// Builds the property of an element stored in the vector of elements.
+fn element_property(value: &Value) -> Property {
+    Property::new()
+        .value(value.clone())
+        .writable(true)
+        .enumerable(true)
+        .configurable(true)
+}
+
+/// Gets the length of a property that can be stored as the length of an array.
+fn array_length(property: &Property) -> Option<u32> {
+    match *property {
+        Property {
+            value: Some(ref value),
+            writable: Some(true),
+            enumerable: Some(false),
+            configurable: Some(false),
+            get: None,
+            set: None,
+        } => to_array_length(value),
+        _ => None,
+    }
+}
+
+/// Gets the array length that a number stands for, if it is a valid one.
+pub(crate) fn to_array_length(value: &Value) -> Option<u32> {
+    let length = match *value.data() {
+        ValueData::Integer(length) => f64::from(length),
+        ValueData::Rational(length) => length,
+        _ => return None,
+    };
+
+    if length >= 0.0 && length <= f64::from(u32::MAX) && length.fract() == 0.0 {
+        Some(length as u32)
+    } else {
+        None
+    }
+}
+
+/// Builds the value of the `length` property of an array.
+pub(crate) fn array_length_value(length: u32) -> Value {
+    match i32::try_from(length) {
+        Ok(length) => Value::integer(length),
+        Err(_) => Value::rational(f64::from(length)),
+    }
+}
+
+/// Builds the `length` property of an array.
+fn length_property(length: u32) -> Property {
+    Property::new()
+        .value(array_length_value(length))
+        .writable(true)
+        .enumerable(false)
+        .configurable(false)
+}
+
+/// An iterator over the properties of a `PropertyMap`.
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    elements: Enumerate<slice::Iter<'a, Value>>,
+    /// The array indices stored as named properties, in ascending order.
+    sparse: vec::IntoIter<(usize, &'a Property)>,
+    array_length: Option<u32>,
+    named: NamedIter<'a>,
+    /// The number of array indices left in `named`, which are skipped as they were visited in
+    /// `sparse`.
+    named_indices: usize,
+}
+
+#[derive(Debug, Clone)]
+enum NamedIter<'a> {
+    Shaped(Zip<slice::Iter<'a, Rc<str>>, slice::Iter<'a, Property>>),
+    Dictionary(map::Iter<'a, String, Property>),
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, Property>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((index, value)) = self.elements.next() {
+            return Some((
+                Cow::Owned(index.to_string()),
+                Cow::Owned(element_property(value)),
+            ));
+        }
+        if let Some((index, property)) = self.sparse.next() {
+            return Some((Cow::Owned(index.to_string()), Cow::Borrowed(property)));
+        }
+        if let Some(length) = self.array_length.take() {
+            return Some((Cow::Borrowed("length"), Cow::Owned(length_property(length))));
+        }
+
+        loop {
+            let (key, property) = match self.named {
+                NamedIter::Shaped(ref mut iter) => iter.next().map(|(key, prop)| (&**key, prop))?,
+                NamedIter::Dictionary(ref mut iter) => {
+                    iter.next().map(|(key, prop)| (&**key, prop))?
+                }
+            };
+            if self.named_indices > 0 && array_index(key).is_some() {
+                self.named_indices -= 1;
+                continue;
+            }
+            return Some((Cow::Borrowed(key), Cow::Borrowed(property)));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let named = match self.named {
+            NamedIter::Shaped(ref iter) => iter.len(),
+            NamedIter::Dictionary(ref iter) => iter.len(),
+        };
+        let len =
+            self.elements.len() + self.sparse.len() + self.array_length.iter().count() + named
+                - self.named_indices;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+/// An inline cache for the accesses to a property with a constant key.
+///
+/// It maps the last shapes seen at the access to the slot of the property in them, so it starts
+/// monomorphic and becomes polymorphic as it sees more shapes, up to `MAX_CACHED_SHAPES`.
+#[derive(Debug, Clone, Default)]
+pub struct PropertyCache {
+    entries: RefCell<Vec<(Shape, usize)>>,
+}
+
+impl PropertyCache {
+    /// Creates an empty property cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the cached slot of the property in the given shape.
+    pub fn get(&self, shape: &Shape) -> Option<usize> {
+        self.entries
+            .borrow()
+            .iter()
+            .find(|(cached, _)| cached == shape)
+            .map(|&(_, slot)| slot)
+    }
+
+    /// Remembers the slot of the property in the given shape, unless the cache is full.
+    pub fn insert(&self, shape: &Shape, slot: usize) {
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() < MAX_CACHED_SHAPES && entries.iter().all(|(cached, _)| cached != shape) {
+            entries.push((shape.clone(), slot));
+        }
+    }
+
+    /// Gets the number of shapes in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Checks if the cache has seen no shapes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Caches don't change what an access does, so they are ignored when comparing nodes.
+impl PartialEq for PropertyCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Finalize for PropertyCache {}
+
+unsafe impl Trace for PropertyCache {
+    unsafe_empty_trace!();
+}
@@ -1,4 +1,5 @@
-use crate::{exec::Interpreter, forward, realm::Realm};
+use super::*;
+use crate::{exec::Interpreter, forward, forward_val, realm::Realm};
 
 #[test]
 fn object_has_own_property() {
@@ -20,3 +21,397 @@ fn object_has_own_property() {
         "false"
     );
 }
+
+/// Gets the value of a property holding an integer.
+fn value_of(map: &PropertyMap, key: &str) -> Option<i32> {
+    map.get(key)
+        .and_then(|prop| prop.value.as_ref().map(i32::from))
+}
+
+#[test]
+fn objects_with_the_same_properties_share_a_shape() {
+    let mut a = PropertyMap::new();
+    let mut b = PropertyMap::new();
+    for (map, value) in [(&mut a, 1), (&mut b, 2)].iter_mut() {
+        map.insert(
+            "x".to_string(),
+            Property::default().value(Value::from(*value)),
+        );
+        map.insert(
+            "y".to_string(),
+            Property::default().value(Value::from(*value)),
+        );
+    }
+
+    assert_eq!(a.shape(), b.shape());
+    assert_eq!(a.shape().and_then(|shape| shape.lookup("y")), Some(1));
+    assert_eq!(value_of(&a, "y"), Some(1));
+    assert_eq!(value_of(&b, "y"), Some(2));
+
+    let mut c = PropertyMap::new();
+    c.insert("y".to_string(), Property::default());
+    c.insert("x".to_string(), Property::default());
+    assert_ne!(a.shape(), c.shape());
+}
+
+#[test]
+fn properties_keep_insertion_order() {
+    let mut map = PropertyMap::new();
+    for key in &["b", "a", "c"] {
+        map.insert(key.to_string(), Property::default());
+    }
+    // Updating a property doesn't move it.
+    map.insert("b".to_string(), Property::default().value(Value::from(1)));
+
+    assert_eq!(map.keys().collect::<Vec<_>>(), vec!["b", "a", "c"]);
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn delete_moves_to_dictionary_mode() {
+    let mut map = PropertyMap::new();
+    map.insert("x".to_string(), Property::default());
+    map.insert("y".to_string(), Property::default());
+
+    assert!(map.remove("z").is_none());
+    assert!(map.shape().is_some());
+
+    assert!(map.remove("x").is_some());
+    assert!(map.shape().is_none());
+    assert!(map.get("x").is_none());
+    assert!(map.get("y").is_some());
+
+    map.insert("x".to_string(), Property::default());
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn many_properties_move_to_dictionary_mode() {
+    let mut map = PropertyMap::new();
+    for i in 0..=shape::MAX_SHAPED_PROPERTIES {
+        assert!(map.shape().is_some());
+        map.insert(i.to_string(), Property::default().value(Value::from(i)));
+    }
+
+    assert!(map.shape().is_none());
+    assert_eq!(map.len(), shape::MAX_SHAPED_PROPERTIES + 1);
+    assert_eq!(value_of(&map, "10"), Some(10));
+}
+
+#[test]
+fn delete_and_add_properties() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let a = { x: 1, y: 2 };
+        let b = { x: 3, y: 4 };
+        delete a.x;
+        a.z = 5;
+        b.z = 6;
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "a.x"), "undefined");
+    assert_eq!(forward(&mut engine, "a.y"), "2");
+    assert_eq!(forward(&mut engine, "a.z"), "5");
+    assert_eq!(forward(&mut engine, "b.x"), "3");
+    assert_eq!(forward(&mut engine, "b.z"), "6");
+}
+
+#[test]
+fn property_cache_remembers_a_few_shapes() {
+    let cache = PropertyCache::new();
+    let mut maps = Vec::new();
+    for i in 0..=shape::MAX_CACHED_SHAPES {
+        let mut map = PropertyMap::new();
+        map.insert(i.to_string(), Property::default().value(Value::from(0)));
+        map.insert("x".to_owned(), Property::default().value(Value::from(1)));
+        maps.push(map);
+    }
+
+    for map in &maps {
+        let shape = map.shape().expect("no shape");
+        cache.insert(shape, shape.lookup("x").expect("no slot"));
+    }
+
+    assert_eq!(cache.len(), shape::MAX_CACHED_SHAPES);
+    assert_eq!(cache.get(maps[0].shape().unwrap()), Some(1));
+    assert_eq!(
+        cache.get(maps[shape::MAX_CACHED_SHAPES].shape().unwrap()),
+        None
+    );
+}
+
+#[test]
+fn contiguous_elements_are_dense() {
+    let element = |value: i32| {
+        Property::new()
+            .value(Value::from(value))
+            .writable(true)
+            .enumerable(true)
+            .configurable(true)
+    };
+
+    let mut map = PropertyMap::new();
+    map.insert("0".to_string(), element(0));
+    map.insert("1".to_string(), element(1));
+    map.insert("3".to_string(), element(3));
+    map.insert("x".to_string(), element(10));
+    assert_eq!(map.element(1).map(i32::from), Some(1));
+    assert!(map.element(3).is_none());
+    assert_eq!(value_of(&map, "3"), Some(3));
+    assert_eq!(map.shape().and_then(|shape| shape.lookup("1")), None);
+
+    // An element that isn't a plain data property moves the next ones out of the vector.
+    map.insert("0".to_string(), Property::default().value(Value::from(5)));
+    assert!(map.element(1).is_none());
+    assert_eq!(value_of(&map, "0"), Some(5));
+    assert_eq!(value_of(&map, "1"), Some(1));
+    assert_eq!(map.keys().collect::<Vec<_>>(), vec!["0", "1", "3", "x"]);
+}
+
+#[test]
+fn dictionary_mode_keeps_insertion_order() {
+    let mut map = PropertyMap::new();
+    for key in &["b", "a", "c", "d"] {
+        map.insert(key.to_string(), Property::default());
+    }
+    map.remove("a");
+    map.insert("a".to_string(), Property::default());
+
+    assert!(map.shape().is_none());
+    assert_eq!(map.keys().collect::<Vec<_>>(), vec!["b", "c", "d", "a"]);
+}
+
+#[test]
+fn array_indices_come_first_in_ascending_order() {
+    let mut map = PropertyMap::new();
+    map.insert("b".to_string(), Property::default());
+    map.insert("10".to_string(), Property::default());
+    map.insert("a".to_string(), Property::default());
+    map.insert("2".to_string(), Property::default());
+    map.insert("-1".to_string(), Property::default());
+
+    let keys = map.keys().collect::<Vec<_>>();
+    assert_eq!(keys, vec!["2", "10", "b", "a", "-1"]);
+    assert_eq!(map.iter().len(), keys.len());
+}
+
+#[test]
+fn object_keys_order() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var obj = { b: 1, 2: 2, a: 3, 1: 4 };
+        obj.c = 5;
+        obj[0] = 6;
+        delete obj.b;
+        obj.b = 7;
+    "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "Object.keys(obj).join()"),
+        "0,1,2,a,c,b"
+    );
+    assert_eq!(
+        forward(&mut engine, "JSON.stringify(obj)"),
+        r#"{"0":6,"1":4,"2":2,"a":3,"c":5,"b":7}"#
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "JSON.stringify(JSON.parse('{\"z\":true,\"y\":null}'))"
+        ),
+        r#"{"z":true,"y":null}"#
+    );
+    assert_eq!(forward(&mut engine, "Object.keys([7, 8]).join()"), "0,1");
+}
+
+#[test]
+fn array_indices() {
+    assert_eq!(shape::array_index("0"), Some(0));
+    assert_eq!(shape::array_index("42"), Some(42));
+    assert_eq!(shape::array_index("4294967294"), Some(4_294_967_294));
+    assert_eq!(shape::array_index("4294967295"), None);
+    assert_eq!(shape::array_index("01"), None);
+    assert_eq!(shape::array_index("-1"), None);
+    assert_eq!(shape::array_index("1.5"), None);
+    assert_eq!(shape::array_index(""), None);
+}
+
+#[test]
+fn property_names_include_the_prototypes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let proto = { shared: 1, own: 2 };
+        let obj = { own: 3 };
+        Object.setPrototypeOf(obj, proto);
+        Object.defineProperty(obj, 'hidden', { value: 4, enumerable: false });
+        obj
+    "#;
+    let obj = forward_val(&mut engine, init).unwrap();
+
+    let names = property_names(&obj);
+    assert_eq!(&names[..3], ["own", "hidden", "shared"]);
+    assert!(names.iter().any(|name| name == "hasOwnProperty"));
+    assert_eq!(names.iter().filter(|name| *name == "own").count(), 1);
+
+    assert!(property_names(&Value::from(1)).is_empty());
+}
+
+#[test]
+fn get_and_set_prototype_of() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let proto = { x: 1 };
+        let obj = {};
+    "#;
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(
+            &mut engine,
+            "Object.getPrototypeOf(obj) === Object.prototype"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "Object.setPrototypeOf(obj, proto) === obj"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "obj.x"), "1");
+    assert_eq!(
+        forward(&mut engine, "Object.getPrototypeOf(obj) === proto"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "Object.getPrototypeOf(1) === Number.prototype"),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.setPrototypeOf(proto, obj) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.setPrototypeOf(obj, 1) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn proto_accessor() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let proto = { x: 1 };
+        let obj = {};
+        obj.__proto__ = proto;
+    "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "obj.__proto__ === proto"), "true");
+    assert_eq!(forward(&mut engine, "obj.x"), "1");
+    assert_eq!(
+        forward(&mut engine, "obj.hasOwnProperty('__proto__')"),
+        "false"
+    );
+
+    // Values which are neither objects nor `null` are ignored.
+    forward(&mut engine, "obj.__proto__ = 5;");
+    assert_eq!(forward(&mut engine, "obj.__proto__ === proto"), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { proto.__proto__ = obj } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+
+    forward(&mut engine, "obj.__proto__ = null;");
+    assert_eq!(forward(&mut engine, "Object.getPrototypeOf(obj)"), "null");
+    assert_eq!(forward(&mut engine, "obj.x"), "undefined");
+}
+
+#[test]
+fn object_create() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let proto = { x: 1 };
+        let obj = Object.create(proto, {
+            y: { value: 2, enumerable: true },
+            z: { get: function() { return this.y + 1; } },
+        });
+        let empty = Object.create(null);
+    "#;
+    forward(&mut engine, init);
+    assert_eq!(
+        forward(&mut engine, "Object.getPrototypeOf(obj) === proto"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "obj.x + obj.y + obj.z"), "6");
+    assert_eq!(forward(&mut engine, "Object.keys(obj)"), "[ y ]");
+    assert_eq!(forward(&mut engine, "Object.getPrototypeOf(empty)"), "null");
+    assert_eq!(forward(&mut engine, "'toString' in empty"), "false");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.create(1) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.create({}, { a: 1 }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn define_property() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let obj = {};
+        let result = Object.defineProperty(obj, "x", { value: 1 });
+        Object.defineProperty(obj, "y", {
+            get: function() { return 2; },
+            configurable: true,
+        });
+        Object.defineProperty(obj, "y", { value: 3 });
+    "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "result === obj"), "true");
+    assert_eq!(forward(&mut engine, "obj.x = 2; obj.x"), "1");
+    assert_eq!(forward(&mut engine, "obj.y"), "3");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(obj, 'x', { value: 2 }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(obj, 'z', { get: 1 }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(obj, 'z', { value: 1, set: function() {} }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
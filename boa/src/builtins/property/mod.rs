@@ -113,7 +113,8 @@ impl Property {
     ///
     /// `true` if all fields are set to none
     pub fn is_none(&self) -> bool {
-        self.get.is_none()
+        self.value.is_none()
+            && self.get.is_none()
             && self.set.is_none()
             && self.writable.is_none()
             && self.configurable.is_none()
@@ -140,6 +141,15 @@ impl Property {
         self.value.is_some() || self.writable.is_some()
     }
 
+    /// Checks if this is an accessor property of an object, whose getter or setter is a function.
+    ///
+    /// Unlike `is_accessor_descriptor`, it doesn't count the `undefined` getter and setter of the
+    /// data properties made from a descriptor object.
+    pub fn is_accessor_property(&self) -> bool {
+        self.get.as_ref().is_some_and(|get| get.is_function())
+            || self.set.as_ref().is_some_and(|set| set.is_function())
+    }
+
     /// Check if a property is generic.
     ///
     /// More information:
@@ -9,24 +9,39 @@
 //! [spec]: https://tc39.es/ecma262/#sec-regexp-constructor
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp
 
-use std::ops::Deref;
-
 use regex::Regex;
 
-use super::function::{make_builtin_fn, make_constructor_fn};
+use super::function::{make_builtin_accessor, make_builtin_fn, make_constructor_fn};
 use crate::{
     builtins::{
-        object::{InternalState, ObjectKind},
+        array::Array,
+        intrinsics::{self, Intrinsic},
+        object::{InternalState, ObjectKind, INSTANCE_PROTOTYPE},
         property::Property,
-        value::{ResultValue, Value, ValueData},
+        value::{ResultValue, Value},
     },
     exec::Interpreter,
     BoaProfiler,
 };
 
+mod pattern;
+pub(crate) mod regexp_string_iterator;
 #[cfg(test)]
 mod tests;
 
+use self::regexp_string_iterator::RegExpStringIterator;
+
+/// The flags of regular expressions, in the order they appear in the `flags` string, with the
+/// names of their accessors.
+const FLAGS: [(char, &str); 6] = [
+    ('g', "global"),
+    ('i', "ignoreCase"),
+    ('m', "multiline"),
+    ('s', "dotAll"),
+    ('u', "unicode"),
+    ('y', "sticky"),
+];
+
 /// The internal representation on a `RegExp` object.
 #[derive(Debug)]
 pub(crate) struct RegExp {
@@ -36,9 +51,6 @@ pub(crate) struct RegExp {
     /// Update last_index, set if global or sticky flags are set.
     use_last_index: bool,
 
-    /// String of parsed flags.
-    flags: String,
-
     /// Flag 's' - dot matches newline characters.
     dot_all: bool,
 
@@ -62,94 +74,116 @@ impl InternalState for RegExp {}
 
 impl RegExp {
     /// Create a new `RegExp`
+    ///
+    /// A `RegExp` pattern is copied with its flags, unless other flags are given. Invalid flags,
+    /// or a pattern which isn't supported, throw a `SyntaxError`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-regexpinitialize
     pub(crate) fn make_regexp(
         this: &mut Value,
         args: &[Value],
         ctx: &mut Interpreter,
     ) -> ResultValue {
-        if args.is_empty() {
-            return Err(Value::undefined());
-        }
-        let mut regex_body = String::new();
-        let mut regex_flags = String::new();
-        #[allow(clippy::indexing_slicing)] // length has been checked
-        match args[0].deref() {
-            ValueData::String(ref body) => {
-                // first argument is a string -> use it as regex pattern
-                regex_body = body.into();
-            }
-            ValueData::Object(ref obj) => {
-                let slots = &obj.borrow().internal_slots;
-                if slots.get("RegExpMatcher").is_some() {
-                    // first argument is another `RegExp` object, so copy its pattern and flags
-                    if let Some(body) = slots.get("OriginalSource") {
-                        regex_body = ctx.to_string(body)?;
-                    }
-                    if let Some(flags) = slots.get("OriginalFlags") {
-                        regex_flags = ctx.to_string(flags)?;
-                    }
-                }
+        let pattern = args.first().cloned().unwrap_or_else(Value::undefined);
+        let mut flags = args.get(1).cloned().unwrap_or_else(Value::undefined);
+
+        // Called as a function, `RegExp` creates a new object, unless it is given a `RegExp`
+        // without flags, which is returned as it is.
+        let is_new_instance = !Self::is_regexp(this)
+            && this.is_object()
+            && this
+                .get_internal_slot(INSTANCE_PROTOTYPE)
+                .strict_equals(&Self::prototype(ctx));
+        if !is_new_instance {
+            if Self::is_regexp(&pattern) && flags.is_undefined() {
+                return Ok(pattern);
             }
-            _ => return Err(Value::undefined()),
+            return Self::create_regexp(pattern, flags, ctx);
         }
-        // if a second argument is given and it's a string, use it as flags
-        match args.get(1) {
-            None => {}
-            Some(flags) => {
-                if let ValueData::String(flags) = flags.deref() {
-                    regex_flags = flags.into();
-                }
+
+        let pattern = if Self::is_regexp(&pattern) {
+            if flags.is_undefined() {
+                flags = pattern.get_internal_slot("OriginalFlags");
             }
-        }
+            pattern.get_internal_slot("OriginalSource")
+        } else {
+            pattern
+        };
+        let regex_body = if pattern.is_undefined() {
+            String::new()
+        } else {
+            ctx.to_string(&pattern)?
+        };
+        let regex_flags = if flags.is_undefined() {
+            String::new()
+        } else {
+            ctx.to_string(&flags)?
+        };
 
         // parse flags
-        let mut sorted_flags = String::new();
-        let mut pattern = String::new();
         let mut dot_all = false;
         let mut global = false;
         let mut ignore_case = false;
         let mut multiline = false;
         let mut sticky = false;
         let mut unicode = false;
-        if regex_flags.contains('g') {
-            global = true;
-            sorted_flags.push('g');
-        }
-        if regex_flags.contains('i') {
-            ignore_case = true;
-            sorted_flags.push('i');
-            pattern.push('i');
-        }
-        if regex_flags.contains('m') {
-            multiline = true;
-            sorted_flags.push('m');
-            pattern.push('m');
-        }
-        if regex_flags.contains('s') {
-            dot_all = true;
-            sorted_flags.push('s');
-            pattern.push('s');
-        }
-        if regex_flags.contains('u') {
-            unicode = true;
-            sorted_flags.push('u');
-            //pattern.push('s'); // rust uses utf-8 anyway
-        }
-        if regex_flags.contains('y') {
-            sticky = true;
-            sorted_flags.push('y');
+        // each flag can only be given once
+        for flag in regex_flags.chars() {
+            let enabled = match flag {
+                'g' => Some(&mut global),
+                'i' => Some(&mut ignore_case),
+                'm' => Some(&mut multiline),
+                's' => Some(&mut dot_all),
+                'u' => Some(&mut unicode),
+                'y' => Some(&mut sticky),
+                _ => None,
+            };
+            match enabled {
+                Some(enabled) if !*enabled => *enabled = true,
+                _ => {
+                    return ctx.throw_syntax_error(format!(
+                        "invalid regular expression flags '{}'",
+                        regex_flags
+                    ))
+                }
+            }
         }
+
         // the `regex` crate uses '(?{flags})` inside the pattern to enable flags
-        if !pattern.is_empty() {
-            pattern = format!("(?{})", pattern);
+        let inline_flags: String = [(ignore_case, 'i'), (multiline, 'm'), (dot_all, 's')]
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, flag)| *flag)
+            .collect();
+        let mut pattern = String::new();
+        if !inline_flags.is_empty() {
+            pattern = format!("(?{})", inline_flags);
+        }
+        match pattern::translate(&regex_body, unicode) {
+            Ok(translated) => pattern.push_str(&translated),
+            Err(err) => {
+                return ctx.throw_syntax_error(format!(
+                    "invalid regular expression /{}/: {}",
+                    regex_body, err
+                ))
+            }
         }
-        pattern.push_str(regex_body.as_str());
 
-        let matcher = Regex::new(pattern.as_str()).expect("failed to create matcher");
+        let matcher = match Regex::new(pattern.as_str()) {
+            Ok(matcher) => matcher,
+            Err(err) => {
+                return ctx.throw_syntax_error(format!(
+                    "invalid regular expression /{}/: {}",
+                    regex_body, err
+                ))
+            }
+        };
         let regexp = RegExp {
             matcher,
             use_last_index: global || sticky,
-            flags: sorted_flags,
             dot_all,
             global,
             ignore_case,
@@ -164,11 +198,80 @@ impl RegExp {
         this.set_internal_slot("RegExpMatcher", Value::undefined());
         this.set_internal_slot("OriginalSource", Value::from(regex_body));
         this.set_internal_slot("OriginalFlags", Value::from(regex_flags));
+        this.set_property_slice(
+            "lastIndex",
+            Property::default()
+                .value(Value::from(0))
+                .writable(true)
+                .enumerable(false)
+                .configurable(false),
+        );
 
         this.set_internal_state(regexp);
         Ok(this.clone())
     }
 
+    /// Creates a new `RegExp` object inheriting from `RegExp.prototype`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-regexpcreate
+    pub(crate) fn create_regexp(
+        pattern: Value,
+        flags: Value,
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let prototype = Self::prototype(ctx);
+        let mut regexp = Value::new_object_from_prototype(prototype, ObjectKind::Ordinary);
+        Self::make_regexp(&mut regexp, &[pattern, flags], ctx)
+    }
+
+    /// Checks if a value is a `RegExp` object.
+    pub(crate) fn is_regexp(value: &Value) -> bool {
+        value.as_object().map_or(false, |obj| {
+            obj.internal_slots.contains_key("RegExpMatcher")
+        })
+    }
+
+    /// Gets `%RegExp.prototype%`.
+    ///
+    /// The prototype is taken from the intrinsics of the realm, so that a binding named `RegExp`
+    /// doesn't change the meaning of the regular expression literals.
+    fn prototype(ctx: &Interpreter) -> Value {
+        ctx.realm().intrinsic(Intrinsic::RegExpPrototype)
+    }
+
+    /// Checks if a value is `RegExp.prototype`, whose accessors return `undefined` instead of
+    /// throwing.
+    fn is_regexp_prototype(value: &Value, ctx: &Interpreter) -> bool {
+        value.is_object() && value.strict_equals(&Self::prototype(ctx))
+    }
+
+    /// Gets a flag of a regular expression, for the flag accessors of `RegExp.prototype`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-regexphasflag
+    fn has_flag(
+        this: &Value,
+        name: &str,
+        flag: fn(&RegExp) -> bool,
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        if Self::is_regexp(this) {
+            return Ok(this.with_internal_state_ref(|regex: &RegExp| Value::from(flag(regex))));
+        }
+        if Self::is_regexp_prototype(this, ctx) {
+            return Ok(Value::undefined());
+        }
+        ctx.throw_type_error(format!(
+            "RegExp.prototype.{} getter called on a value which isn't a RegExp",
+            name
+        ))
+    }
+
     /// `RegExp.prototype.dotAll`
     ///
     /// The `dotAll` property indicates whether or not the "`s`" flag is used with the regular expression.
@@ -179,8 +282,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.dotAll
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/dotAll
-    fn get_dot_all(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.dot_all)))
+    fn get_dot_all(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "dotAll", |regex| regex.dot_all, ctx)
     }
 
     /// `RegExp.prototype.flags`
@@ -194,8 +297,17 @@ impl RegExp {
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.flags
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/flags
     /// [flags]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Regular_Expressions#Advanced_searching_with_flags_2
-    fn get_flags(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.flags.clone())))
+    fn get_flags(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if !this.is_object() {
+            return ctx.throw_type_error("RegExp.prototype.flags getter called on a non-object");
+        }
+        let mut flags = String::new();
+        for (flag, name) in FLAGS.iter() {
+            if ctx.get_field_value(this, Value::from(*name))?.is_true() {
+                flags.push(*flag);
+            }
+        }
+        Ok(Value::from(flags))
     }
 
     /// `RegExp.prototype.global`
@@ -208,8 +320,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.global
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/global
-    fn get_global(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.global)))
+    fn get_global(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "global", |regex| regex.global, ctx)
     }
 
     /// `RegExp.prototype.ignoreCase`
@@ -222,8 +334,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.ignorecase
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/ignoreCase
-    fn get_ignore_case(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.ignore_case)))
+    fn get_ignore_case(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "ignoreCase", |regex| regex.ignore_case, ctx)
     }
 
     /// `RegExp.prototype.multiline`
@@ -236,8 +348,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.multiline
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/multiline
-    fn get_multiline(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.multiline)))
+    fn get_multiline(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "multiline", |regex| regex.multiline, ctx)
     }
 
     /// `RegExp.prototype.source`
@@ -251,13 +363,26 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.source
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/source
-    fn get_source(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        Ok(this.get_internal_slot("OriginalSource"))
+    fn get_source(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if Self::is_regexp(this) {
+            let source = this.get_internal_slot("OriginalSource");
+            // An empty pattern would be a comment in a regular expression literal.
+            if ctx.to_string(&source)?.is_empty() {
+                return Ok(Value::from("(?:)"));
+            }
+            return Ok(source);
+        }
+        if Self::is_regexp_prototype(this, ctx) {
+            return Ok(Value::from("(?:)"));
+        }
+        ctx.throw_type_error(
+            "RegExp.prototype.source getter called on a value which isn't a RegExp",
+        )
     }
 
     /// `RegExp.prototype.sticky`
     ///
-    /// The `flags` property returns a string consisting of the [`flags`][flags] of the current regular expression object.
+    /// The `sticky` property indicates whether or not the "`y`" flag is used with the regular expression.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -265,8 +390,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.sticky
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/sticky
-    fn get_sticky(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.sticky)))
+    fn get_sticky(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "sticky", |regex| regex.sticky, ctx)
     }
 
     /// `RegExp.prototype.unicode`
@@ -280,8 +405,8 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-get-regexp.prototype.unicode
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/unicode
-    fn get_unicode(this: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
-        this.with_internal_state_ref(|regex: &RegExp| Ok(Value::from(regex.unicode)))
+    fn get_unicode(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        Self::has_flag(this, "unicode", |regex| regex.unicode, ctx)
     }
 
     /// `RegExp.prototype.test( string )`
@@ -297,24 +422,9 @@ impl RegExp {
     /// [spec]: https://tc39.es/ecma262/#sec-regexp.prototype.test
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/test
     pub(crate) fn test(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let arg_str = ctx.to_string(args.get(0).expect("could not get argument"))?;
-        let mut last_index = usize::from(&this.get_field("lastIndex"));
-        let result = this.with_internal_state_ref(|regex: &RegExp| {
-            let result = if let Some(m) = regex.matcher.find_at(arg_str.as_str(), last_index) {
-                if regex.use_last_index {
-                    last_index = m.end();
-                }
-                true
-            } else {
-                if regex.use_last_index {
-                    last_index = 0;
-                }
-                false
-            };
-            Ok(Value::boolean(result))
-        });
-        this.set_field("lastIndex", Value::from(last_index));
-        result
+        let arg_str = Self::this_regexp_input(this, "test", args, ctx)?;
+        let result = Self::builtin_exec(this, &arg_str, ctx)?;
+        Ok(Value::from(!result.is_null()))
     }
 
     /// `RegExp.prototype.exec( string )`
@@ -330,44 +440,101 @@ impl RegExp {
     /// [spec]: https://tc39.es/ecma262/#sec-regexp.prototype.exec
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/exec
     pub(crate) fn exec(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let arg_str = ctx.to_string(args.get(0).expect("could not get argument"))?;
-        let mut last_index = usize::from(&this.get_field("lastIndex"));
-        let result = this.with_internal_state_ref(|regex: &RegExp| {
-            let mut locations = regex.matcher.capture_locations();
-            let result = if let Some(m) =
-                regex
-                    .matcher
-                    .captures_read_at(&mut locations, arg_str.as_str(), last_index)
-            {
-                if regex.use_last_index {
-                    last_index = m.end();
-                }
-                let mut result = Vec::with_capacity(locations.len());
-                for i in 0..locations.len() {
-                    if let Some((start, end)) = locations.get(i) {
-                        result.push(Value::from(
-                            arg_str.get(start..end).expect("Could not get slice"),
-                        ));
-                    } else {
-                        result.push(Value::undefined());
-                    }
+        let arg_str = Self::this_regexp_input(this, "exec", args, ctx)?;
+        Self::builtin_exec(this, &arg_str, ctx)
+    }
+
+    /// Checks that `this` is a `RegExp` for its methods, and converts their argument to the
+    /// string they match.
+    fn this_regexp_input(
+        this: &Value,
+        method: &str,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> Result<String, Value> {
+        if !Self::is_regexp(this) {
+            ctx.throw_type_error(format!(
+                "RegExp.prototype.{} called on a value which isn't a RegExp",
+                method
+            ))?;
+        }
+        ctx.to_string(args.first().unwrap_or(&Value::undefined()))
+    }
+
+    /// Matches a regular expression against a string, starting at its `lastIndex` if it is
+    /// global or sticky, and returns the match array or `null`.
+    ///
+    /// A global or sticky regular expression updates its `lastIndex` to the end of the match,
+    /// or resets it to 0 when there is no match. A sticky regular expression only matches at its
+    /// `lastIndex`. The indices are counted in UTF-16 code units.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-regexpbuiltinexec
+    pub(crate) fn builtin_exec(this: &Value, input: &str, ctx: &mut Interpreter) -> ResultValue {
+        let last_index = ctx.get_field_value(this, Value::from("lastIndex"))?;
+        let last_index = ctx.to_length(&last_index)?;
+        let (matcher, use_last_index, sticky) = this.with_internal_state_ref(|regex: &RegExp| {
+            (regex.matcher.clone(), regex.use_last_index, regex.sticky)
+        });
+        let last_index = if use_last_index { last_index } else { 0 };
+
+        let length = utf16_index(input, input.len());
+        let start = byte_index(input, last_index);
+        let mut locations = matcher.capture_locations();
+        let found = if last_index > length {
+            None
+        } else {
+            matcher
+                .captures_read_at(&mut locations, input, start)
+                .filter(|m| !sticky || m.start() == start)
+        };
+        let m = match found {
+            Some(m) => m,
+            None => {
+                if use_last_index {
+                    this.set_field("lastIndex", Value::from(0));
                 }
+                return Ok(Value::null());
+            }
+        };
+        if use_last_index {
+            this.set_field("lastIndex", Value::from(utf16_index(input, m.end())));
+        }
 
-                let result = Value::from(result);
-                result
-                    .set_property_slice("index", Property::default().value(Value::from(m.start())));
-                result.set_property_slice("input", Property::default().value(Value::from(arg_str)));
-                result
+        let mut captures = Vec::with_capacity(locations.len());
+        for i in 0..locations.len() {
+            if let Some((start, end)) = locations.get(i) {
+                captures.push(Value::from(
+                    input.get(start..end).expect("Could not get slice"),
+                ));
             } else {
-                if regex.use_last_index {
-                    last_index = 0;
+                captures.push(Value::undefined());
+            }
+        }
+
+        let groups = if matcher.capture_names().any(|name| name.is_some()) {
+            let groups = Value::new_object(None);
+            for (i, name) in matcher.capture_names().enumerate() {
+                if let Some(name) = name {
+                    groups.set_field(name, captures[i].clone());
                 }
-                Value::null()
-            };
-            Ok(result)
-        });
-        this.set_field("lastIndex", Value::from(last_index));
-        result
+            }
+            groups
+        } else {
+            Value::undefined()
+        };
+
+        let result = Array::new_array(ctx)?;
+        Array::construct_array(&result, &captures)?;
+        result.set_property_slice(
+            "index",
+            Property::default().value(Value::from(utf16_index(input, m.start()))),
+        );
+        result.set_property_slice("input", Property::default().value(Value::from(input)));
+        result.set_property_slice("groups", Property::default().value(groups));
+        Ok(result)
     }
 
     /// `RegExp.prototype[ @@match ]( string )`
@@ -381,20 +548,45 @@ impl RegExp {
     /// [spec]: https://tc39.es/ecma262/#sec-regexp.prototype-@@match
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/@@match
     pub(crate) fn r#match(this: &mut Value, arg: String, ctx: &mut Interpreter) -> ResultValue {
-        let (matcher, flags) = this
-            .with_internal_state_ref(|regex: &RegExp| (regex.matcher.clone(), regex.flags.clone()));
-        if flags.contains('g') {
-            let mut matches = Vec::new();
-            for mat in matcher.find_iter(&arg) {
-                matches.push(Value::from(mat.as_str()));
+        let (global, unicode) =
+            this.with_internal_state_ref(|regex: &RegExp| (regex.global, regex.unicode));
+        if !global {
+            return Self::builtin_exec(this, &arg, ctx);
+        }
+
+        this.set_field("lastIndex", Value::from(0));
+        let mut matches = Vec::new();
+        loop {
+            let result = Self::builtin_exec(this, &arg, ctx)?;
+            if result.is_null() {
+                break;
             }
-            if matches.is_empty() {
-                return Ok(Value::null());
+            let matched = ctx.to_string(&result.get_field("0"))?;
+            if matched.is_empty() {
+                Self::advance_last_index(this, &arg, unicode, ctx)?;
             }
-            Ok(Value::from(matches))
-        } else {
-            Self::exec(this, &[Value::from(arg)], ctx)
+            matches.push(Value::from(matched));
+        }
+        if matches.is_empty() {
+            return Ok(Value::null());
         }
+        let result = Array::new_array(ctx)?;
+        Array::construct_array(&result, &matches)
+    }
+
+    /// Moves the `lastIndex` of a regular expression past an empty match, so that matching it
+    /// again doesn't produce the same match.
+    pub(crate) fn advance_last_index(
+        this: &Value,
+        input: &str,
+        unicode: bool,
+        ctx: &mut Interpreter,
+    ) -> Result<(), Value> {
+        let this_index = ctx.get_field_value(this, Value::from("lastIndex"))?;
+        let this_index = ctx.to_length(&this_index)?;
+        let next_index = advance_string_index(input, this_index, unicode);
+        this.set_field("lastIndex", Value::from(next_index));
+        Ok(())
     }
 
     /// `RegExp.prototype.toString()`
@@ -409,14 +601,23 @@ impl RegExp {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/toString
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_string(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let body = ctx.to_string(&this.get_internal_slot("OriginalSource"))?;
-        let flags = this.with_internal_state_ref(|regex: &RegExp| regex.flags.clone());
+        if !this.is_object() {
+            return ctx.throw_type_error("RegExp.prototype.toString called on a non-object");
+        }
+        let body = ctx.get_field_value(this, Value::from("source"))?;
+        let body = ctx.to_string(&body)?;
+        let flags = ctx.get_field_value(this, Value::from("flags"))?;
+        let flags = ctx.to_string(&flags)?;
         Ok(Value::from(format!("/{}/{}", body, flags)))
     }
 
     /// `RegExp.prototype[ @@matchAll ]( string )`
     ///
-    /// The `[@@matchAll]` method returns all matches of the regular expression against a string.
+    /// The `[@@matchAll]` method returns an iterator over the matches of the regular expression
+    /// against a string.
+    ///
+    /// The matches are produced by a copy of the regular expression, starting at its
+    /// `lastIndex`, so the iteration doesn't change the original regular expression.
     ///
     /// More information:
     ///  - [ECMAScript reference][spec]
@@ -424,67 +625,43 @@ impl RegExp {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-regexp-prototype-matchall
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/RegExp/@@matchAll
-    // TODO: it's returning an array, it should return an iterator
-    pub(crate) fn match_all(this: &mut Value, arg_str: String) -> ResultValue {
-        let matches: Vec<Value> = this.with_internal_state_ref(|regex: &RegExp| {
-            let mut matches = Vec::new();
-
-            for m in regex.matcher.find_iter(&arg_str) {
-                if let Some(caps) = regex.matcher.captures(&m.as_str()) {
-                    let match_vec = caps
-                        .iter()
-                        .map(|group| match group {
-                            Some(g) => Value::from(g.as_str()),
-                            None => Value::undefined(),
-                        })
-                        .collect::<Vec<Value>>();
-
-                    let match_val = Value::from(match_vec);
-
-                    match_val.set_property_slice(
-                        "index",
-                        Property::default().value(Value::from(m.start())),
-                    );
-                    match_val.set_property_slice(
-                        "input",
-                        Property::default().value(Value::from(arg_str.clone())),
-                    );
-                    matches.push(match_val);
-
-                    if !regex.flags.contains('g') {
-                        break;
-                    }
-                }
-            }
-
-            matches
-        });
-
-        let length = matches.len();
-        let result = Value::from(matches);
-        result.set_field("length", Value::from(length));
-        result.set_kind(ObjectKind::Array);
-
-        Ok(result)
+    pub(crate) fn match_all(
+        this: &mut Value,
+        arg_str: String,
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let flags = ctx.get_field_value(this, Value::from("flags"))?;
+        let flags = ctx.to_string(&flags)?;
+        let matcher = Self::create_regexp(this.clone(), Value::from(flags.as_str()), ctx)?;
+        let last_index = ctx.get_field_value(this, Value::from("lastIndex"))?;
+        let last_index = ctx.to_length(&last_index)?;
+        matcher.set_field("lastIndex", Value::from(last_index));
+
+        Ok(RegExpStringIterator::create_regexp_string_iterator(
+            ctx,
+            matcher,
+            arg_str,
+            flags.contains('g'),
+            flags.contains('u'),
+        ))
     }
 
     /// Create a new `RegExp` object.
     pub(crate) fn create(global: &Value) -> Value {
         // Create prototype
         let prototype = Value::new_object(Some(global));
-        prototype.set_field("lastIndex", Value::from(0));
 
         make_builtin_fn(Self::test, "test", &prototype, 1);
         make_builtin_fn(Self::exec, "exec", &prototype, 1);
         make_builtin_fn(Self::to_string, "toString", &prototype, 0);
-        make_builtin_fn(Self::get_dot_all, "dotAll", &prototype, 0);
-        make_builtin_fn(Self::get_flags, "flags", &prototype, 0);
-        make_builtin_fn(Self::get_global, "global", &prototype, 0);
-        make_builtin_fn(Self::get_ignore_case, "ignoreCase", &prototype, 0);
-        make_builtin_fn(Self::get_multiline, "multiline", &prototype, 0);
-        make_builtin_fn(Self::get_source, "source", &prototype, 0);
-        make_builtin_fn(Self::get_sticky, "sticky", &prototype, 0);
-        make_builtin_fn(Self::get_unicode, "unicode", &prototype, 0);
+        make_builtin_accessor(Self::get_dot_all, None, "dotAll", &prototype);
+        make_builtin_accessor(Self::get_flags, None, "flags", &prototype);
+        make_builtin_accessor(Self::get_global, None, "global", &prototype);
+        make_builtin_accessor(Self::get_ignore_case, None, "ignoreCase", &prototype);
+        make_builtin_accessor(Self::get_multiline, None, "multiline", &prototype);
+        make_builtin_accessor(Self::get_source, None, "source", &prototype);
+        make_builtin_accessor(Self::get_sticky, None, "sticky", &prototype);
+        make_builtin_accessor(Self::get_unicode, None, "unicode", &prototype);
 
         make_constructor_fn("RegExp", 1, Self::make_regexp, global, prototype, true)
     }
@@ -494,5 +671,50 @@ impl RegExp {
     pub(crate) fn init(global: &Value) {
         let _timer = BoaProfiler::global().start_event("regexp", "init");
         global.set_field("RegExp", Self::create(global));
+
+        let iterator_prototype = intrinsics::get(global, Intrinsic::IteratorPrototype);
+        RegExpStringIterator::init(global, &iterator_prototype);
+    }
+}
+
+/// Converts an index in UTF-16 code units into a byte index of a string, rounded up to the
+/// next character boundary.
+fn byte_index(input: &str, index: usize) -> usize {
+    if input.is_ascii() {
+        return index.min(input.len());
+    }
+    let mut units = 0;
+    for (byte, c) in input.char_indices() {
+        if units >= index {
+            return byte;
+        }
+        units += c.len_utf16();
+    }
+    input.len()
+}
+
+/// Converts a byte index of a string into an index in UTF-16 code units.
+fn utf16_index(input: &str, byte: usize) -> usize {
+    if input.is_ascii() {
+        return byte;
+    }
+    input[..byte].encode_utf16().count()
+}
+
+/// Gets the index following a code unit index of a string, skipping a whole surrogate pair in
+/// Unicode mode.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-advancestringindex
+pub(crate) fn advance_string_index(input: &str, index: usize, unicode: bool) -> usize {
+    if !unicode {
+        return index + 1;
+    }
+    let byte = byte_index(input, index);
+    match input[byte..].chars().next() {
+        Some(c) if utf16_index(input, byte) == index => index + c.len_utf16(),
+        _ => index + 1,
     }
 }
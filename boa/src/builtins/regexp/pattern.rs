@@ -0,0 +1,464 @@
+//! This module translates the patterns of regular expressions to the syntax of the `regex`
+//! crate, which differs from the syntax of the language in its escapes and literal characters.
+//!
+//! The patterns of regular expressions with the `u` flag follow a stricter syntax: they can use
+//! Unicode property escapes, like `\p{Script=Greek}`, but an escape which doesn't mean anything,
+//! or a lone brace or bracket, is an error. Without the `u` flag, such an escape matches the
+//! escaped character, so `\p` matches a `p`.
+//!
+//! Backreferences, lookaround assertions and the `Changes_When_NFKC_Casefolded` property aren't
+//! supported by the `regex` crate, so they are left for it to reject.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-patterns
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Regular_Expressions/Unicode_Property_Escapes
+
+/// The values of the `General_Category` property, with their aliases, which can be used in
+/// property escapes with or without the property name.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#table-unicode-general-category-values
+const GENERAL_CATEGORIES: &[&str] = &[
+    "Cased_Letter",
+    "LC",
+    "Close_Punctuation",
+    "Pe",
+    "Connector_Punctuation",
+    "Pc",
+    "Control",
+    "Cc",
+    "cntrl",
+    "Currency_Symbol",
+    "Sc",
+    "Dash_Punctuation",
+    "Pd",
+    "Decimal_Number",
+    "Nd",
+    "digit",
+    "Enclosing_Mark",
+    "Me",
+    "Final_Punctuation",
+    "Pf",
+    "Format",
+    "Cf",
+    "Initial_Punctuation",
+    "Pi",
+    "Letter",
+    "L",
+    "Letter_Number",
+    "Nl",
+    "Line_Separator",
+    "Zl",
+    "Lowercase_Letter",
+    "Ll",
+    "Mark",
+    "M",
+    "Combining_Mark",
+    "Math_Symbol",
+    "Sm",
+    "Modifier_Letter",
+    "Lm",
+    "Modifier_Symbol",
+    "Sk",
+    "Nonspacing_Mark",
+    "Mn",
+    "Number",
+    "N",
+    "Open_Punctuation",
+    "Ps",
+    "Other",
+    "C",
+    "Other_Letter",
+    "Lo",
+    "Other_Number",
+    "No",
+    "Other_Punctuation",
+    "Po",
+    "Other_Symbol",
+    "So",
+    "Paragraph_Separator",
+    "Zp",
+    "Private_Use",
+    "Co",
+    "Punctuation",
+    "P",
+    "punct",
+    "Separator",
+    "Z",
+    "Space_Separator",
+    "Zs",
+    "Spacing_Mark",
+    "Mc",
+    "Surrogate",
+    "Cs",
+    "Symbol",
+    "S",
+    "Titlecase_Letter",
+    "Lt",
+    "Unassigned",
+    "Cn",
+    "Uppercase_Letter",
+    "Lu",
+];
+
+/// The binary properties, with their aliases, which can be used in property escapes.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#table-binary-unicode-properties
+const BINARY_PROPERTIES: &[&str] = &[
+    "ASCII",
+    "ASCII_Hex_Digit",
+    "AHex",
+    "Alphabetic",
+    "Alpha",
+    "Any",
+    "Assigned",
+    "Bidi_Control",
+    "Bidi_C",
+    "Bidi_Mirrored",
+    "Bidi_M",
+    "Case_Ignorable",
+    "CI",
+    "Cased",
+    "Changes_When_Casefolded",
+    "CWCF",
+    "Changes_When_Casemapped",
+    "CWCM",
+    "Changes_When_Lowercased",
+    "CWL",
+    "Changes_When_NFKC_Casefolded",
+    "CWKCF",
+    "Changes_When_Titlecased",
+    "CWT",
+    "Changes_When_Uppercased",
+    "CWU",
+    "Dash",
+    "Default_Ignorable_Code_Point",
+    "DI",
+    "Deprecated",
+    "Dep",
+    "Diacritic",
+    "Dia",
+    "Emoji",
+    "Emoji_Component",
+    "EComp",
+    "Emoji_Modifier",
+    "EMod",
+    "Emoji_Modifier_Base",
+    "EBase",
+    "Emoji_Presentation",
+    "EPres",
+    "Extended_Pictographic",
+    "ExtPict",
+    "Extender",
+    "Ext",
+    "Grapheme_Base",
+    "Gr_Base",
+    "Grapheme_Extend",
+    "Gr_Ext",
+    "Hex_Digit",
+    "Hex",
+    "IDS_Binary_Operator",
+    "IDSB",
+    "IDS_Trinary_Operator",
+    "IDST",
+    "ID_Continue",
+    "IDC",
+    "ID_Start",
+    "IDS",
+    "Ideographic",
+    "Ideo",
+    "Join_Control",
+    "Join_C",
+    "Logical_Order_Exception",
+    "LOE",
+    "Lowercase",
+    "Lower",
+    "Math",
+    "Noncharacter_Code_Point",
+    "NChar",
+    "Pattern_Syntax",
+    "Pat_Syn",
+    "Pattern_White_Space",
+    "Pat_WS",
+    "Quotation_Mark",
+    "QMark",
+    "Radical",
+    "Regional_Indicator",
+    "RI",
+    "Sentence_Terminal",
+    "STerm",
+    "Soft_Dotted",
+    "SD",
+    "Terminal_Punctuation",
+    "Term",
+    "Unified_Ideograph",
+    "UIdeo",
+    "Uppercase",
+    "Upper",
+    "Variation_Selector",
+    "VS",
+    "White_Space",
+    "space",
+    "XID_Continue",
+    "XIDC",
+    "XID_Start",
+    "XIDS",
+];
+
+/// The characters with a meaning in patterns, which can always be escaped.
+const SYNTAX_CHARACTERS: &str = "^$\\.*+?()[]{}|/";
+
+/// A class matching any character.
+const ANYTHING: &str = "[\\x{0}-\\x{10FFFF}]";
+
+/// A pattern matching nothing, as the `regex` crate doesn't allow empty classes.
+const NOTHING: &str = "(?:\\b\\B)";
+
+/// A class matching no character, which the `regex` crate allows only nested in another class.
+const NO_CHARACTER: &str = "[^\\x{0}-\\x{10FFFF}]";
+
+/// Translates a pattern to the syntax of the `regex` crate, or returns the reason why it is
+/// invalid.
+pub(super) fn translate(pattern: &str, unicode: bool) -> Result<String, String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut translated = String::with_capacity(pattern.len());
+    let mut in_class = false;
+    let mut i = 0;
+    while let Some(&c) = chars.get(i) {
+        i += 1;
+        match c {
+            '\\' => i = translate_escape(&chars, i, in_class, unicode, &mut translated)?,
+            '[' if !in_class => {
+                let negated = chars.get(i) == Some(&'^');
+                if negated {
+                    i += 1;
+                }
+                // `[]` matches nothing and `[^]` matches anything, but the `regex` crate reads
+                // the bracket as the first character of the class.
+                if chars.get(i) == Some(&']') {
+                    i += 1;
+                    translated.push_str(if negated { ANYTHING } else { NOTHING });
+                } else {
+                    in_class = true;
+                    translated.push_str(if negated { "[^" } else { "[" });
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                translated.push(']');
+            }
+            // Nested classes and set operations of the `regex` crate are plain characters.
+            '[' | '&' | '~' if in_class => {
+                translated.push('\\');
+                translated.push(c);
+            }
+            '{' if !in_class => match quantifier_len(&chars[i..]) {
+                Some(len) => {
+                    translated.push('{');
+                    translated.extend(&chars[i..i + len]);
+                    i += len;
+                }
+                None => translate_lone_character(c, unicode, &mut translated)?,
+            },
+            '}' | ']' if !in_class => translate_lone_character(c, unicode, &mut translated)?,
+            // Named groups are written `(?P<name>...)` in the `regex` crate, while `(?<=` and
+            // `(?<!` are lookbehind assertions.
+            '(' if chars.get(i) == Some(&'?')
+                && chars.get(i + 1) == Some(&'<')
+                && !matches!(chars.get(i + 2), Some('=') | Some('!')) =>
+            {
+                i += 2;
+                translated.push_str("(?P<");
+            }
+            _ => translated.push(c),
+        }
+    }
+    Ok(translated)
+}
+
+/// Gets the length of the bounds of a quantifier, like `2,3}`, at the start of the given
+/// characters, which follow a `{`.
+fn quantifier_len(chars: &[char]) -> Option<usize> {
+    let digits = |start: usize| {
+        chars[start.min(chars.len())..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count()
+    };
+    let min = digits(0);
+    if min == 0 {
+        return None;
+    }
+    let mut len = min;
+    if chars.get(len) == Some(&',') {
+        len += 1 + digits(len + 1);
+    }
+    if chars.get(len) == Some(&'}') {
+        Some(len + 1)
+    } else {
+        None
+    }
+}
+
+/// Translates a brace or a bracket which doesn't delimit anything, and is only allowed
+/// without the `u` flag.
+fn translate_lone_character(c: char, unicode: bool, translated: &mut String) -> Result<(), String> {
+    if unicode {
+        return Err(format!("lone '{}'", c));
+    }
+    translated.push('\\');
+    translated.push(c);
+    Ok(())
+}
+
+/// Translates the escape following a backslash at the given index, and returns the index
+/// following it.
+fn translate_escape(
+    chars: &[char],
+    mut i: usize,
+    in_class: bool,
+    unicode: bool,
+    translated: &mut String,
+) -> Result<usize, String> {
+    let c = *chars.get(i).ok_or("\\ at end of pattern")?;
+    i += 1;
+    match c {
+        'd' | 'D' | 'w' | 'W' | 's' | 'S' | 'f' | 'n' | 'r' | 't' | 'v' => {
+            translated.push('\\');
+            translated.push(c);
+        }
+        'b' if in_class => translated.push_str("\\x08"),
+        'b' | 'B' if !in_class => {
+            translated.push('\\');
+            translated.push(c);
+        }
+        '0' if !matches!(chars.get(i), Some(c) if c.is_ascii_digit()) => {
+            translated.push_str("\\x00");
+        }
+        // Backreferences are left for the `regex` crate to reject.
+        '0'..='9' | 'k' => {
+            translated.push('\\');
+            translated.push(c);
+        }
+        'c' if matches!(chars.get(i), Some(c) if c.is_ascii_alphabetic()) => {
+            push_code_point(u32::from(chars[i]) % 32, translated);
+            i += 1;
+        }
+        // Without the `u` flag, a `\c` which isn't followed by a letter matches itself.
+        'c' if !unicode => translated.push_str("\\\\c"),
+        'x' if hex_value(chars, i, 2).is_some() => {
+            push_code_point(hex_value(chars, i, 2).unwrap_or_default(), translated);
+            i += 2;
+        }
+        'u' if unicode && chars.get(i) == Some(&'{') => {
+            let len = chars[i + 1..].iter().take_while(|c| **c != '}').count();
+            match hex_value(chars, i + 1, len) {
+                Some(code_point) if len > 0 && chars.get(i + len + 1) == Some(&'}') => {
+                    push_code_point(code_point, translated);
+                    i += len + 2;
+                }
+                _ => return Err("invalid Unicode escape".into()),
+            }
+        }
+        'u' if hex_value(chars, i, 4).is_some() => {
+            let mut code_point = hex_value(chars, i, 4).unwrap_or_default();
+            i += 4;
+            // With the `u` flag, a surrogate pair is a single code point.
+            if unicode && (0xD800..0xDC00).contains(&code_point) {
+                let trail = match (chars.get(i), chars.get(i + 1)) {
+                    (Some('\\'), Some('u')) => hex_value(chars, i + 2, 4),
+                    _ => None,
+                };
+                if let Some(trail @ 0xDC00..=0xDFFF) = trail {
+                    code_point = 0x10000 + ((code_point - 0xD800) << 10) + (trail - 0xDC00);
+                    i += 6;
+                }
+            }
+            push_code_point(code_point, translated);
+        }
+        'p' | 'P' if unicode => {
+            let len = chars[i..].iter().take_while(|c| **c != '}').count();
+            if chars.get(i) != Some(&'{') || i + len == chars.len() {
+                return Err("invalid property name".into());
+            }
+            let property: String = chars[i + 1..i + len].iter().collect();
+            if !is_property(&property) {
+                return Err(format!("invalid property name '{}'", property));
+            }
+            // Strings don't contain surrogates, which the `regex` crate has no property for, and
+            // it can't compile the empty class of `\P{Any}`.
+            let category = property.rsplit('=').next().unwrap_or_default();
+            let surrogate = category == "Surrogate" || category == "Cs";
+            if surrogate || property == "Any" {
+                let empty = surrogate == (c == 'p');
+                translated.push_str(match (empty, in_class) {
+                    (true, true) => NO_CHARACTER,
+                    (true, false) => NOTHING,
+                    (false, _) => ANYTHING,
+                });
+                return Ok(i + len + 1);
+            }
+            translated.push('\\');
+            translated.push(c);
+            translated.push('{');
+            translated.push_str(&property);
+            translated.push('}');
+            i += len + 1;
+        }
+        '-' if in_class => translated.push_str("\\-"),
+        '/' => translated.push('/'),
+        _ if SYNTAX_CHARACTERS.contains(c) => {
+            translated.push('\\');
+            translated.push(c);
+        }
+        _ if unicode => return Err(format!("invalid escape '\\{}'", c)),
+        // Without the `u` flag, an escape of any other character matches it.
+        _ => translated.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+    }
+    Ok(i)
+}
+
+/// Gets the value of the hexadecimal number of the given length at an index, if the
+/// characters there are hexadecimal digits.
+fn hex_value(chars: &[char], i: usize, len: usize) -> Option<u32> {
+    let digits = chars.get(i..i + len)?;
+    digits.iter().try_fold(0, |value, c| {
+        c.to_digit(16)
+            .map(|digit| value * 16 + digit)
+            .filter(|value| *value <= 0x10_FFFF)
+    })
+}
+
+/// Appends the escape of a code point, which the `regex` crate rejects if it is a surrogate.
+fn push_code_point(code_point: u32, translated: &mut String) {
+    translated.push_str(&format!("\\x{{{:X}}}", code_point));
+}
+
+/// Checks if the name of a property escape, like `Script=Greek`, is valid.
+///
+/// The values of the `Script` and `Script_Extensions` properties are checked by the `regex`
+/// crate, which also accepts them in other cases, so they are required to be capitalized here.
+fn is_property(property: &str) -> bool {
+    match property.find('=') {
+        Some(index) => {
+            let value = &property[index + 1..];
+            match &property[..index] {
+                "General_Category" | "gc" => GENERAL_CATEGORIES.contains(&value),
+                "Script" | "sc" | "Script_Extensions" | "scx" => {
+                    value.starts_with(|c: char| c.is_ascii_uppercase())
+                        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                }
+                _ => false,
+            }
+        }
+        None => GENERAL_CATEGORIES.contains(&property) || BINARY_PROPERTIES.contains(&property),
+    }
+}
@@ -0,0 +1,119 @@
+//! This module implements the RegExp string iterators, returned by `String.prototype.matchAll`
+//! and `RegExp.prototype[Symbol.matchAll]`.
+//!
+//! A RegExp string iterator produces the match arrays of a regular expression against a string.
+//! A global regular expression is matched until there is no match left, and any other one only
+//! once.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-regexp-string-iterator-objects
+
+use super::RegExp;
+use crate::{
+    builtins::{
+        function::make_builtin_fn,
+        intrinsics::{self, Intrinsic},
+        iterable::create_iter_result_object,
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+};
+
+/// The internal slot holding the regular expression matched by a RegExp string iterator.
+const ITERATING_REGEXP: &str = "IteratingRegExp";
+
+/// The internal slot holding the string matched by a RegExp string iterator.
+const ITERATED_STRING: &str = "IteratedString";
+
+/// The internal slot holding whether the regular expression of a RegExp string iterator is
+/// global.
+const GLOBAL: &str = "Global";
+
+/// The internal slot holding whether the regular expression of a RegExp string iterator is
+/// in Unicode mode.
+const UNICODE: &str = "Unicode";
+
+/// The internal slot holding whether a RegExp string iterator is done.
+const DONE: &str = "Done";
+
+/// `RegExpStringIterator` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RegExpStringIterator;
+
+impl RegExpStringIterator {
+    /// Creates a new iterator over the matches of a regular expression against a string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createregexpstringiterator
+    pub(crate) fn create_regexp_string_iterator(
+        ctx: &Interpreter,
+        matcher: Value,
+        string: String,
+        global: bool,
+        unicode: bool,
+    ) -> Value {
+        let prototype = ctx
+            .realm()
+            .intrinsic(Intrinsic::RegExpStringIteratorPrototype);
+        let iterator = Value::new_object_from_prototype(prototype, ObjectKind::Ordinary);
+        iterator.set_internal_slot(ITERATING_REGEXP, matcher);
+        iterator.set_internal_slot(ITERATED_STRING, Value::from(string));
+        iterator.set_internal_slot(GLOBAL, Value::from(global));
+        iterator.set_internal_slot(UNICODE, Value::from(unicode));
+        iterator.set_internal_slot(DONE, Value::from(false));
+        iterator
+    }
+
+    /// `%RegExpStringIteratorPrototype%.next( )`
+    ///
+    /// Returns the next match array, or a result which is `done` once there is no match left.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-%regexpstringiteratorprototype%.next
+    pub(crate) fn next(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let is_regexp_string_iterator = match this.as_object() {
+            Some(obj) => obj.internal_slots.contains_key(ITERATING_REGEXP),
+            None => false,
+        };
+        if !is_regexp_string_iterator {
+            return ctx.throw_type_error("'this' is not a RegExp string iterator");
+        }
+        if this.get_internal_slot(DONE).is_true() {
+            return Ok(create_iter_result_object(ctx, Value::undefined(), true));
+        }
+
+        let matcher = this.get_internal_slot(ITERATING_REGEXP);
+        let string = ctx.to_string(&this.get_internal_slot(ITERATED_STRING))?;
+        let result = RegExp::builtin_exec(&matcher, &string, ctx)?;
+        if result.is_null() {
+            this.set_internal_slot(DONE, Value::from(true));
+            return Ok(create_iter_result_object(ctx, Value::undefined(), true));
+        }
+
+        if this.get_internal_slot(GLOBAL).is_true() {
+            let matched = ctx.to_string(&result.get_field("0"))?;
+            if matched.is_empty() {
+                let unicode = this.get_internal_slot(UNICODE).is_true();
+                RegExp::advance_last_index(&matcher, &string, unicode, ctx)?;
+            }
+        } else {
+            this.set_internal_slot(DONE, Value::from(true));
+        }
+        Ok(create_iter_result_object(ctx, result, false))
+    }
+
+    /// Creates `%RegExpStringIteratorPrototype%`.
+    pub(crate) fn init(global: &Value, iterator_prototype: &Value) {
+        let prototype = Value::new_object(Some(global));
+        prototype.set_internal_slot(INSTANCE_PROTOTYPE, iterator_prototype.clone());
+        make_builtin_fn(Self::next, "next", &prototype, 0);
+        intrinsics::set(global, Intrinsic::RegExpStringIteratorPrototype, prototype);
+    }
+}
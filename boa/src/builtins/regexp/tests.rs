@@ -17,6 +17,22 @@ fn constructors() {
     assert_eq!(forward(&mut engine, "ctor_literal.test('1.0')"), "true");
 }
 
+#[test]
+fn literal_uses_intrinsic_constructor() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        function test() {
+            var RegExp = function() { return { test: function() { return false; } }; };
+            return /a+/.test('aaa') && /[/]b/i.test('/B');
+        }
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "test()"), "true");
+    assert_eq!(forward(&mut engine, "10 / 2 / 5"), "1");
+}
+
 #[test]
 fn check_regexp_constructor_is_function() {
     let global = Value::new_object(None);
@@ -24,33 +40,80 @@ fn check_regexp_constructor_is_function() {
     assert_eq!(regexp_constructor.is_function(), true);
 }
 
-// TODO: uncomment this test when property getters are supported
-
-//    #[test]
-//    fn flags() {
-//        let mut engine = Interpreter::new();
-//        let init = r#"
-//                var re_gi = /test/gi;
-//                var re_sm = /test/sm;
-//                "#;
-//
-//        eprintln!("{}", forward(&mut engine, init));
-//        assert_eq!(forward(&mut engine, "re_gi.global"), "true");
-//        assert_eq!(forward(&mut engine, "re_gi.ignoreCase"), "true");
-//        assert_eq!(forward(&mut engine, "re_gi.multiline"), "false");
-//        assert_eq!(forward(&mut engine, "re_gi.dotAll"), "false");
-//        assert_eq!(forward(&mut engine, "re_gi.unicode"), "false");
-//        assert_eq!(forward(&mut engine, "re_gi.sticky"), "false");
-//        assert_eq!(forward(&mut engine, "re_gi.flags"), "gi");
-//
-//        assert_eq!(forward(&mut engine, "re_sm.global"), "false");
-//        assert_eq!(forward(&mut engine, "re_sm.ignoreCase"), "false");
-//        assert_eq!(forward(&mut engine, "re_sm.multiline"), "true");
-//        assert_eq!(forward(&mut engine, "re_sm.dotAll"), "true");
-//        assert_eq!(forward(&mut engine, "re_sm.unicode"), "false");
-//        assert_eq!(forward(&mut engine, "re_sm.sticky"), "false");
-//        assert_eq!(forward(&mut engine, "re_sm.flags"), "ms");
-//    }
+#[test]
+fn flags() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var re_gi = /test/gi;
+        var re_sm = /test/sm;
+        var re_uy = new RegExp("test", "yu");
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "re_gi.global"), "true");
+    assert_eq!(forward(&mut engine, "re_gi.ignoreCase"), "true");
+    assert_eq!(forward(&mut engine, "re_gi.multiline"), "false");
+    assert_eq!(forward(&mut engine, "re_gi.dotAll"), "false");
+    assert_eq!(forward(&mut engine, "re_gi.unicode"), "false");
+    assert_eq!(forward(&mut engine, "re_gi.sticky"), "false");
+    assert_eq!(forward(&mut engine, "re_gi.flags"), "gi");
+
+    assert_eq!(forward(&mut engine, "re_sm.global"), "false");
+    assert_eq!(forward(&mut engine, "re_sm.ignoreCase"), "false");
+    assert_eq!(forward(&mut engine, "re_sm.multiline"), "true");
+    assert_eq!(forward(&mut engine, "re_sm.dotAll"), "true");
+    assert_eq!(forward(&mut engine, "re_sm.unicode"), "false");
+    assert_eq!(forward(&mut engine, "re_sm.sticky"), "false");
+    assert_eq!(forward(&mut engine, "re_sm.flags"), "ms");
+
+    assert_eq!(forward(&mut engine, "re_uy.unicode"), "true");
+    assert_eq!(forward(&mut engine, "re_uy.sticky"), "true");
+    assert_eq!(forward(&mut engine, "re_uy.flags"), "uy");
+    assert_eq!(forward(&mut engine, "re_uy.toString()"), "/test/uy");
+
+    // The flags of a copied regular expression can be replaced.
+    assert_eq!(forward(&mut engine, "new RegExp(re_gi).flags"), "gi");
+    assert_eq!(forward(&mut engine, "new RegExp(re_gi, 'm').flags"), "m");
+
+    assert_eq!(forward(&mut engine, "RegExp.prototype.global"), "undefined");
+    assert_eq!(forward(&mut engine, "RegExp.prototype.flags"), "");
+    assert_eq!(forward(&mut engine, "RegExp.prototype.source"), "(?:)");
+    assert_eq!(forward(&mut engine, "new RegExp().toString()"), "/(?:)/");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var getter = { global: re_gi.global }; try { Object.create(re_gi).global } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn invalid_flags() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    for flags in &["gg", "x", "gimsuyg", "G"] {
+        assert_eq!(
+            forward(
+                &mut engine,
+                &format!(
+                    "try {{ new RegExp('a', '{}') }} catch (e) {{ e instanceof SyntaxError }}",
+                    flags
+                )
+            ),
+            "true"
+        );
+    }
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { new RegExp('(') } catch (e) { e instanceof SyntaxError }"
+        ),
+        "true"
+    );
+}
 
 #[test]
 fn last_index() {
@@ -66,6 +129,168 @@ fn last_index() {
     assert_eq!(forward(&mut engine, "regex.lastIndex"), "3");
     assert_eq!(forward(&mut engine, "regex.test('1.0foo')"), "false");
     assert_eq!(forward(&mut engine, "regex.lastIndex"), "0");
+
+    // `lastIndex` is an own property of each regular expression.
+    assert_eq!(forward(&mut engine, "/a/.lastIndex"), "0");
+    assert_eq!(
+        forward(&mut engine, "'lastIndex' in RegExp.prototype"),
+        "false"
+    );
+
+    // It's ignored, and left as it is, without the global and sticky flags.
+    forward(&mut engine, "var plain = /o/; plain.lastIndex = 2;");
+    assert_eq!(forward(&mut engine, "plain.exec('foo').index"), "1");
+    assert_eq!(forward(&mut engine, "plain.exec('bar')"), "null");
+    assert_eq!(forward(&mut engine, "plain.lastIndex"), "2");
+
+    // It's reset when it's beyond the end of the string.
+    forward(&mut engine, "regex.lastIndex = 10;");
+    assert_eq!(forward(&mut engine, "regex.test('1.0foo')"), "false");
+    assert_eq!(forward(&mut engine, "regex.lastIndex"), "0");
+}
+
+#[test]
+fn sticky() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var re = /foo/y;");
+
+    assert_eq!(forward(&mut engine, "re.test('barfoo')"), "false");
+    assert_eq!(forward(&mut engine, "re.lastIndex"), "0");
+    forward(&mut engine, "re.lastIndex = 3;");
+    assert_eq!(forward(&mut engine, "re.exec('barfoo').index"), "3");
+    assert_eq!(forward(&mut engine, "re.lastIndex"), "6");
+    assert_eq!(forward(&mut engine, "re.exec('barfoofoo')[0]"), "foo");
+    assert_eq!(forward(&mut engine, "re.lastIndex"), "9");
+    assert_eq!(forward(&mut engine, "re.test('barfoofoo')"), "false");
+    assert_eq!(forward(&mut engine, "re.lastIndex"), "0");
+
+    // A sticky and global regular expression only matches consecutive occurrences.
+    assert_eq!(forward(&mut engine, "'aab a'.match(/a/gy).join()"), "a,a");
+}
+
+#[test]
+fn unicode_indices() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var re = /b/g; var str = '\\u{1F600}éb';");
+
+    assert_eq!(forward(&mut engine, "re.exec(str).index"), "3");
+    assert_eq!(forward(&mut engine, "re.lastIndex"), "4");
+    assert_eq!(forward(&mut engine, "str.match(/b/).index"), "3");
+
+    // Empty matches advance by a code point in Unicode mode, and by a code unit otherwise.
+    assert_eq!(
+        forward(&mut engine, "'\\u{1F600}'.match(/(?:)/gu).length"),
+        "2"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "[...'ab'.matchAll(/(?:)/g)].map(m => m.index).join()"
+        ),
+        "0,1,2"
+    );
+}
+
+#[test]
+fn property_escapes() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(&mut engine, "'aβγ1'.match(/\\p{Script=Greek}+/u)[0]"),
+        "βγ"
+    );
+    assert_eq!(
+        forward(&mut engine, "'aβγ1'.match(/\\P{sc=Greek}+/gu).join()"),
+        "a,1"
+    );
+    assert_eq!(forward(&mut engine, "/^\\p{Lu}$/u.test('É')"), "true");
+    assert_eq!(
+        forward(&mut engine, "/^[\\p{Letter}\\d]+$/u.test('a1b')"),
+        "true"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "/\\p{General_Category=Decimal_Number}/u.test('x')"
+        ),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "/\\p{Emoji_Presentation}/u.test('\\u{1F600}')"),
+        "true"
+    );
+
+    assert_eq!(
+        forward(&mut engine, "/\\p{Cs}|\\P{Any}/u.test('a')"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "/^[\\P{Any}\\p{Cs}a]\\p{Any}$/u.test('ab')"),
+        "true"
+    );
+
+    // Without the `u` flag, `\p` matches a `p`.
+    assert_eq!(forward(&mut engine, "/\\p{L}/.test('p{L}')"), "true");
+
+    for pattern in &[
+        "\\\\p{Letter",
+        "\\\\p{letter}",
+        "\\\\p{Script=greek}",
+        "\\\\p{Foo=Bar}",
+        "\\\\p",
+    ] {
+        assert_eq!(
+            forward(
+                &mut engine,
+                &format!(
+                    "try {{ new RegExp('{}', 'u') }} catch (e) {{ e instanceof SyntaxError }}",
+                    pattern
+                )
+            ),
+            "true"
+        );
+    }
+}
+
+#[test]
+fn unicode_syntax() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    // Escapes of characters without a meaning are only allowed without the `u` flag.
+    assert_eq!(forward(&mut engine, "/\\a\\-\\A/.test('a-A')"), "true");
+    assert_eq!(forward(&mut engine, "/a{/.test('a{')"), "true");
+    assert_eq!(forward(&mut engine, "/]}/.test(']}')"), "true");
+    for pattern in &["\\\\a", "\\\\-", "a{", "a}", "]", "\\\\u{110000}"] {
+        assert_eq!(
+            forward(
+                &mut engine,
+                &format!(
+                    "try {{ new RegExp('{}', 'u') }} catch (e) {{ e instanceof SyntaxError }}",
+                    pattern
+                )
+            ),
+            "true"
+        );
+    }
+
+    assert_eq!(forward(&mut engine, "/^[\\-\\/]\\/$/u.test('-/')"), "true");
+    assert_eq!(forward(&mut engine, "/a{1,2}b/u.test('aab')"), "true");
+    assert_eq!(
+        forward(&mut engine, "/\\u{1F600}/u.test('\\u{1F600}')"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "/^\\uD83D\\uDE00$/u.test('\\u{1F600}')"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "/\\cJ\\0/.test('\\n\\0')"), "true");
+    assert_eq!(forward(&mut engine, "/[]/.test('a')"), "false");
+    assert_eq!(forward(&mut engine, "/[^]/.test('\\n')"), "true");
+    assert_eq!(forward(&mut engine, "/[[&&~]+/.exec('a[&~')[0]"), "[&~");
 }
 
 #[test]
@@ -86,6 +311,19 @@ fn exec() {
         forward(&mut engine, "result.input"),
         "The Quick Brown Fox Jumps Over The Lazy Dog"
     );
+    assert_eq!(forward(&mut engine, "result.length"), "3");
+    assert_eq!(forward(&mut engine, "result.groups"), "undefined");
+    assert_eq!(
+        forward(&mut engine, "/(?<digit>\\d)/.exec('a1').groups.digit"),
+        "1"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var other = { exec: re.exec }; try { other.exec('a') } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
 }
 
 #[test]
@@ -0,0 +1,576 @@
+//! This module implements `JsString`, the string type of JavaScript values.
+//!
+//! A JavaScript string is a sequence of UTF-16 code units. It doesn't need to be valid UTF-16:
+//! it can hold lone surrogates, for example after slicing a surrogate pair in half.
+//!
+//! Concatenating long strings doesn't copy them: it creates a rope, which keeps both halves and
+//! only copies them into a single buffer the first time the contents of the string are needed.
+//! This makes building a string piece by piece, as in `s += chunk`, take linear time.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type
+
+use gc::{unsafe_empty_trace, Finalize, Trace};
+use once_cell::unsync::OnceCell;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    char::decode_utf16,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Copied,
+    rc::Rc,
+    slice, str,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A JavaScript string.
+///
+/// Lengths and indices are counted in UTF-16 code units, as in the specification, so a character
+/// outside of the Basic Multilingual Plane has a length of 2.
+///
+/// Strings that only hold ASCII characters are stored as UTF-8, which is the same as their
+/// UTF-16 code units narrowed to bytes, so they can be used as a `&str` without converting them.
+/// Any other string is stored as UTF-16. A flat string always has the same storage for the same
+/// contents, so two flat strings are equal if, and only if, their storage is equal.
+///
+/// The concatenation of two strings with a total length of at least `MIN_ROPE_LENGTH` is a rope,
+/// which is flattened the first time its contents are needed.
+#[derive(Clone)]
+pub struct JsString {
+    inner: Inner,
+}
+
+/// The storage of a `JsString`.
+#[derive(Clone)]
+enum Inner {
+    /// A string with only ASCII characters.
+    Ascii(Rc<str>),
+    /// A string with at least one code unit outside of the ASCII range.
+    Utf16(Rc<[u16]>),
+    /// The concatenation of two strings.
+    Rope(Rc<Rope>),
+}
+
+/// The contents of a flat string.
+#[derive(Debug, Clone, Copy)]
+enum Flat<'a> {
+    Ascii(&'a str),
+    Utf16(&'a [u16]),
+}
+
+/// The minimum length of the concatenation of two strings for it to be a rope.
+///
+/// Copying shorter strings is cheaper than keeping track of their halves.
+const MIN_ROPE_LENGTH: usize = 256;
+
+/// A string made by concatenating two strings.
+struct Rope {
+    /// The length of the string, in UTF-16 code units.
+    len: usize,
+    /// Whether the string only holds ASCII characters.
+    is_ascii: bool,
+    /// The two halves of the string, which are released once the rope is flattened.
+    parts: RefCell<Option<(JsString, JsString)>>,
+    /// The flattened string.
+    flat: OnceCell<JsString>,
+}
+
+impl JsString {
+    /// Creates an empty string.
+    pub fn new() -> Self {
+        Self::from("")
+    }
+
+    /// Creates a string from its UTF-16 code units, which may hold lone surrogates.
+    pub fn from_utf16(units: &[u16]) -> Self {
+        let inner = if units.iter().all(|&unit| unit < 0x80) {
+            let ascii: String = units.iter().map(|&unit| unit as u8 as char).collect();
+            Inner::Ascii(ascii.into())
+        } else {
+            Inner::Utf16(units.into())
+        };
+
+        Self { inner }
+    }
+
+    /// Gets the length of the string, in UTF-16 code units.
+    pub fn len(&self) -> usize {
+        match self.inner {
+            Inner::Ascii(ref s) => s.len(),
+            Inner::Utf16(ref units) => units.len(),
+            Inner::Rope(ref rope) => rope.len,
+        }
+    }
+
+    /// Checks if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the string only holds ASCII characters.
+    fn is_ascii(&self) -> bool {
+        match self.inner {
+            Inner::Ascii(_) => true,
+            Inner::Utf16(_) => false,
+            Inner::Rope(ref rope) => rope.is_ascii,
+        }
+    }
+
+    /// Gets the contents of the string, flattening it if it's a rope.
+    fn flat(&self) -> Flat<'_> {
+        match self.inner {
+            Inner::Ascii(ref s) => Flat::Ascii(s),
+            Inner::Utf16(ref units) => Flat::Utf16(units),
+            Inner::Rope(ref rope) => rope.flatten().flat(),
+        }
+    }
+
+    /// Gets the string as a `&str`, if it only holds ASCII characters.
+    pub fn as_ascii(&self) -> Option<&str> {
+        match self.flat() {
+            Flat::Ascii(s) => Some(s),
+            Flat::Utf16(_) => None,
+        }
+    }
+
+    /// Converts the string to a Rust string, replacing lone surrogates with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// ASCII strings are borrowed, without allocating.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        match self.flat() {
+            Flat::Ascii(s) => Cow::Borrowed(s),
+            Flat::Utf16(units) => Cow::Owned(String::from_utf16_lossy(units)),
+        }
+    }
+
+    /// Gets the UTF-16 code units of the string.
+    ///
+    /// Strings stored as UTF-16 are borrowed, without allocating.
+    pub fn to_utf16(&self) -> Cow<'_, [u16]> {
+        match self.flat() {
+            Flat::Ascii(s) => Cow::Owned(s.encode_utf16().collect()),
+            Flat::Utf16(units) => Cow::Borrowed(units),
+        }
+    }
+
+    /// Gets an iterator over the UTF-16 code units of the string.
+    pub fn code_units(&self) -> CodeUnits<'_> {
+        CodeUnits {
+            inner: match self.flat() {
+                Flat::Ascii(s) => CodeUnitsInner::Ascii(s.bytes()),
+                Flat::Utf16(units) => CodeUnitsInner::Utf16(units.iter().copied()),
+            },
+        }
+    }
+
+    /// Gets an iterator over the code points of the string.
+    ///
+    /// Surrogate pairs are combined into a single character, and lone surrogates are returned
+    /// as an error holding the surrogate.
+    pub fn code_points(&self) -> impl Iterator<Item = Result<char, u16>> + '_ {
+        decode_utf16(self.code_units()).map(|res| res.map_err(|err| err.unpaired_surrogate()))
+    }
+
+    /// Gets the UTF-16 code unit at the given index.
+    pub fn code_unit_at(&self, index: usize) -> Option<u16> {
+        match self.flat() {
+            Flat::Ascii(s) => s.as_bytes().get(index).map(|&byte| u16::from(byte)),
+            Flat::Utf16(units) => units.get(index).copied(),
+        }
+    }
+
+    /// Gets the code point starting at the given index.
+    ///
+    /// If the index is the start of a surrogate pair, the code point of the pair is returned.
+    /// Otherwise, the code unit at the index is returned, even if it's a lone surrogate.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-codepointat
+    pub fn code_point_at(&self, index: usize) -> Option<u32> {
+        let first = self.code_unit_at(index)?;
+        if is_leading_surrogate(first) {
+            if let Some(second) = self
+                .code_unit_at(index + 1)
+                .filter(|&u| is_trailing_surrogate(u))
+            {
+                return Some(
+                    ((u32::from(first) - 0xD800) << 10) + (u32::from(second) - 0xDC00) + 0x1_0000,
+                );
+            }
+        }
+
+        Some(u32::from(first))
+    }
+
+    /// Gets the part of the string between the `start` and `end` code unit indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is greater than `end`, or if `end` is greater than the length.
+    pub fn substring(&self, start: usize, end: usize) -> Self {
+        match self.flat() {
+            Flat::Ascii(s) => Self::from(&s[start..end]),
+            Flat::Utf16(units) => Self::from_utf16(&units[start..end]),
+        }
+    }
+
+    /// Finds the first index, at or after `from`, where `search` appears in the string.
+    pub fn index_of(&self, search: &Self, from: usize) -> Option<usize> {
+        if let (Some(s), Some(search)) = (self.as_ascii(), search.as_ascii()) {
+            return s.get(from..)?.find(search).map(|index| index + from);
+        }
+
+        let (units, search) = (self.to_utf16(), search.to_utf16());
+        (from..=units.len().checked_sub(search.len())?)
+            .find(|&index| units[index..].starts_with(&search))
+    }
+
+    /// Finds the last index, at or before `from`, where `search` appears in the string.
+    pub fn last_index_of(&self, search: &Self, from: usize) -> Option<usize> {
+        let (units, search) = (self.to_utf16(), search.to_utf16());
+        let last = units.len().checked_sub(search.len())?;
+        (0..=from.min(last))
+            .rev()
+            .find(|&index| units[index..].starts_with(&search))
+    }
+
+    /// Checks if `search` appears in the string at the given index.
+    pub fn has_at(&self, search: &Self, index: usize) -> bool {
+        if let (Some(s), Some(search)) = (self.as_ascii(), search.as_ascii()) {
+            return s
+                .get(index..)
+                .map_or(false, |rest| rest.starts_with(search));
+        }
+
+        self.to_utf16()
+            .get(index..)
+            .map_or(false, |rest| rest.starts_with(&search.to_utf16()))
+    }
+
+    /// Creates a new string by appending `other` to this string.
+    ///
+    /// If the new string is long enough, it's a rope and neither string is copied.
+    pub fn concat(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            return self.clone();
+        }
+        if self.is_empty() {
+            return other.clone();
+        }
+
+        let len = self.len() + other.len();
+        if len >= MIN_ROPE_LENGTH {
+            let rope = Rope {
+                len,
+                is_ascii: self.is_ascii() && other.is_ascii(),
+                parts: RefCell::new(Some((self.clone(), other.clone()))),
+                flat: OnceCell::new(),
+            };
+            return Self {
+                inner: Inner::Rope(Rc::new(rope)),
+            };
+        }
+
+        if let (Some(a), Some(b)) = (self.as_ascii(), other.as_ascii()) {
+            let mut s = String::with_capacity(a.len() + b.len());
+            s.push_str(a);
+            s.push_str(b);
+            return Self::from(s);
+        }
+
+        let mut units = Vec::with_capacity(self.len() + other.len());
+        units.extend(self.code_units());
+        units.extend(other.code_units());
+        Self::from(units)
+    }
+
+    /// Creates a new string by repeating this one `count` times.
+    pub fn repeat(&self, count: usize) -> Self {
+        match self.flat() {
+            Flat::Ascii(s) => Self::from(s.repeat(count)),
+            Flat::Utf16(units) => Self::from(units.repeat(count)),
+        }
+    }
+}
+
+impl Rope {
+    /// Gets the flattened string, copying the halves of the rope into a single buffer the first
+    /// time it's called.
+    fn flatten(&self) -> &JsString {
+        self.flat.get_or_init(|| {
+            let parts = self
+                .parts
+                .borrow_mut()
+                .take()
+                .expect("a rope that isn't flat should have its parts");
+
+            // The halves can be ropes too, so they are walked with a stack instead of recursion,
+            // which could overflow for long chains of concatenations.
+            let mut stack = vec![parts.1, parts.0];
+            if self.is_ascii {
+                let mut buf = String::with_capacity(self.len);
+                while let Some(string) = Self::next_leaf(&mut stack) {
+                    if let Flat::Ascii(s) = string.flat() {
+                        buf.push_str(s);
+                    }
+                }
+                JsString {
+                    inner: Inner::Ascii(buf.into()),
+                }
+            } else {
+                let mut buf = Vec::with_capacity(self.len);
+                while let Some(string) = Self::next_leaf(&mut stack) {
+                    buf.extend(string.code_units());
+                }
+                JsString {
+                    inner: Inner::Utf16(buf.into()),
+                }
+            }
+        })
+    }
+
+    /// Pops strings from the stack, pushing the halves of the ropes that aren't flat, until a flat
+    /// string, or a rope that was already flattened, is found.
+    fn next_leaf(stack: &mut Vec<JsString>) -> Option<JsString> {
+        while let Some(string) = stack.pop() {
+            if let Inner::Rope(ref rope) = string.inner {
+                if rope.flat.get().is_none() {
+                    let parts = rope.parts.borrow();
+                    let (left, right) = parts
+                        .as_ref()
+                        .expect("a rope that isn't flat should have its parts");
+                    stack.push(right.clone());
+                    stack.push(left.clone());
+                    continue;
+                }
+            }
+            return Some(string);
+        }
+        None
+    }
+}
+
+/// Ropes are dropped with a stack instead of recursion, which could overflow for long chains of
+/// concatenations.
+impl Drop for Rope {
+    fn drop(&mut self) {
+        let mut stack: Vec<JsString> = Vec::new();
+        if let Some((left, right)) = self.parts.get_mut().take() {
+            stack.push(left);
+            stack.push(right);
+        }
+
+        while let Some(string) = stack.pop() {
+            if let Inner::Rope(rope) = string.inner {
+                // Only the ropes that aren't shared are dropped here, taking their halves first.
+                if let Ok(mut rope) = Rc::try_unwrap(rope) {
+                    if let Some((left, right)) = rope.parts.get_mut().take() {
+                        stack.push(left);
+                        stack.push(right);
+                    }
+                    drop(rope);
+                }
+            }
+        }
+    }
+}
+
+/// Checks if a code unit is the first half of a surrogate pair.
+pub(crate) fn is_leading_surrogate(unit: u16) -> bool {
+    (0xD800..=0xDBFF).contains(&unit)
+}
+
+/// Checks if a code unit is the second half of a surrogate pair.
+pub(crate) fn is_trailing_surrogate(unit: u16) -> bool {
+    (0xDC00..=0xDFFF).contains(&unit)
+}
+
+/// An iterator over the UTF-16 code units of a `JsString`.
+#[derive(Debug, Clone)]
+pub struct CodeUnits<'a> {
+    inner: CodeUnitsInner<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum CodeUnitsInner<'a> {
+    Ascii(str::Bytes<'a>),
+    Utf16(Copied<slice::Iter<'a, u16>>),
+}
+
+impl Iterator for CodeUnits<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self.inner {
+            CodeUnitsInner::Ascii(ref mut bytes) => bytes.next().map(u16::from),
+            CodeUnitsInner::Utf16(ref mut units) => units.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner {
+            CodeUnitsInner::Ascii(ref bytes) => bytes.size_hint(),
+            CodeUnitsInner::Utf16(ref units) => units.size_hint(),
+        }
+    }
+}
+
+impl Default for JsString {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Finalize for JsString {}
+
+unsafe impl Trace for JsString {
+    unsafe_empty_trace!();
+}
+
+/// Strings are ordered by their code units, as in the specification.
+impl Ord for JsString {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.flat(), other.flat()) {
+            (Flat::Ascii(a), Flat::Ascii(b)) => a.cmp(b),
+            _ => self.code_units().cmp(other.code_units()),
+        }
+    }
+}
+
+impl PartialOrd for JsString {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for JsString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.flat() {
+            Flat::Ascii(s) => s.hash(state),
+            Flat::Utf16(units) => units.hash(state),
+        }
+    }
+}
+
+impl PartialEq for JsString {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        match (self.flat(), other.flat()) {
+            (Flat::Ascii(a), Flat::Ascii(b)) => a == b,
+            (Flat::Utf16(a), Flat::Utf16(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for JsString {}
+
+impl PartialEq<str> for JsString {
+    fn eq(&self, other: &str) -> bool {
+        match self.flat() {
+            Flat::Ascii(s) => s == other,
+            Flat::Utf16(units) => units.iter().copied().eq(other.encode_utf16()),
+        }
+    }
+}
+
+impl PartialEq<&str> for JsString {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl fmt::Debug for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+impl fmt::Display for JsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
+
+impl From<&str> for JsString {
+    fn from(s: &str) -> Self {
+        let inner = if s.is_ascii() {
+            Inner::Ascii(s.into())
+        } else {
+            Inner::Utf16(s.encode_utf16().collect::<Vec<_>>().into())
+        };
+
+        Self { inner }
+    }
+}
+
+impl From<&String> for JsString {
+    fn from(s: &String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<String> for JsString {
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<Box<str>> for JsString {
+    fn from(s: Box<str>) -> Self {
+        Self::from(&*s)
+    }
+}
+
+impl From<char> for JsString {
+    fn from(c: char) -> Self {
+        Self::from(c.encode_utf8(&mut [0; 4]) as &str)
+    }
+}
+
+impl From<&[u16]> for JsString {
+    fn from(units: &[u16]) -> Self {
+        Self::from_utf16(units)
+    }
+}
+
+impl From<Vec<u16>> for JsString {
+    fn from(units: Vec<u16>) -> Self {
+        Self::from_utf16(&units)
+    }
+}
+
+/// Strings are serialized as Rust strings, so lone surrogates are replaced with
+/// `U+FFFD REPLACEMENT CHARACTER`.
+#[cfg(feature = "serde")]
+impl Serialize for JsString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string_lossy())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for JsString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(Self::from(s))
+    }
+}
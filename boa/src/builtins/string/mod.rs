@@ -9,15 +9,21 @@
 //! [spec]: https://tc39.es/ecma262/#sec-string-object
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String
 
+mod js_string;
+pub(crate) mod string_iterator;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "unicode")]
+mod unicode;
+
+pub use self::js_string::{CodeUnits, JsString};
 
 use super::function::{make_builtin_fn, make_constructor_fn};
 use crate::{
     builtins::{
-        object::{Object, ObjectKind},
+        object::ObjectKind,
         property::Property,
-        value::{ResultValue, Value, ValueData},
+        value::{f64_to_uint32, ResultValue, Value, ValueData},
         RegExp,
     },
     exec::Interpreter,
@@ -26,11 +32,15 @@ use crate::{
 use regex::Regex;
 use std::string::String as StdString;
 use std::{
+    char::{from_u32, REPLACEMENT_CHARACTER},
     cmp::{max, min},
-    f64::NAN,
+    f64::{INFINITY, NAN},
     ops::Deref,
 };
 
+/// The normalization forms accepted by `String.prototype.normalize`.
+const NORMALIZATION_FORMS: [&str; 4] = ["NFC", "NFD", "NFKC", "NFKD"];
+
 /// JavaScript `String` implementation.
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct String;
@@ -43,39 +53,59 @@ impl String {
     pub(crate) fn make_string(
         this: &mut Value,
         args: &[Value],
-        _: &mut Interpreter,
+        ctx: &mut Interpreter,
     ) -> ResultValue {
-        // This value is used by console.log and other routines to match Obexpecty"failed to parse argument for String method"pe
-        // to its Javascript Identifier (global constructor method name)
-        let s = args.get(0).unwrap_or(&Value::string("")).clone();
-        let length_str = s.to_string().chars().count();
+        // Symbols are converted to their descriptive string, anything else goes through ToString.
+        let string = match args.get(0) {
+            Some(value) if value.is_symbol() => JsString::from(value.to_string()),
+            Some(value) => ctx.to_js_string(value)?,
+            None => JsString::new(),
+        };
 
-        this.set_field("length", Value::from(length_str as i32));
+        Self::define_length(this, &string);
 
         this.set_kind(ObjectKind::String);
-        this.set_internal_slot("StringData", s);
+        this.set_internal_slot("StringData", Value::from(string.clone()));
 
-        let arg = match args.get(0) {
-            Some(v) => v.clone(),
-            None => Value::undefined(),
-        };
+        Ok(Value::from(string))
+    }
 
-        if arg.is_undefined() {
-            return Ok("".into());
+    /// Defines the `length` of a `String` object, which is the number of UTF-16 code units of its
+    /// string, and is neither writable, enumerable nor configurable.
+    pub(crate) fn define_length(object: &Value, string: &JsString) {
+        let length = Property::new()
+            .value(Value::from(string.len()))
+            .writable(false)
+            .enumerable(false)
+            .configurable(false);
+        object.set_property("length".to_string(), length);
+    }
+
+    /// Gets the string of a primitive string, or of a `String` object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-thisstringvalue
+    fn this_string_value(this: &Value, ctx: &mut Interpreter) -> Result<JsString, Value> {
+        match this.data() {
+            ValueData::String(ref string) => return Ok(string.clone()),
+            ValueData::Object(_) => {
+                if let ValueData::String(ref string) = this.get_internal_slot("StringData").data() {
+                    return Ok(string.clone());
+                }
+            }
+            _ => {}
         }
 
-        Ok(Value::from(arg.to_string()))
+        ctx.throw_type_error("'this' is not a string")?;
+        unreachable!();
     }
 
     /// Get the string value to a primitive string
     #[allow(clippy::wrong_self_convention)]
     pub(crate) fn to_string(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        // Get String from String Object and send it back as a new value
-        match this.get_internal_slot("StringData").data() {
-            ValueData::String(ref string) => Ok(Value::from(string.clone())),
-            // Throw expection here:
-            _ => ctx.throw_type_error("'this' is not a string"),
-        }
+        Ok(Value::from(Self::this_string_value(this, ctx)?))
     }
 
     /// `String.prototype.charAt( index )`
@@ -97,29 +127,21 @@ impl String {
     pub(crate) fn char_at(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
         let pos = i32::from(
             args.get(0)
                 .expect("failed to get argument for String method"),
         );
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of
-        // unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of
-        // bytes is an O(1) operation.
-        let length = primitive_val.chars().count();
-
         // We should return an empty string is pos is out of range
-        if pos >= length as i32 || pos < 0 {
+        if pos < 0 {
             return Ok("".into());
         }
 
-        Ok(Value::from(
-            primitive_val
-                .chars()
-                .nth(pos as usize)
-                .expect("failed to get value"),
-        ))
+        match primitive_val.code_unit_at(pos as usize) {
+            Some(unit) => Ok(Value::from(JsString::from_utf16(&[unit]))),
+            None => Ok("".into()),
+        }
     }
 
     /// `String.prototype.charCodeAt( index )`
@@ -143,27 +165,52 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
-
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        let length = primitive_val.chars().count();
+        let primitive_val = ctx.to_js_string(this)?;
         let pos = i32::from(
             args.get(0)
                 .expect("failed to get argument for String method"),
         );
 
-        if pos >= length as i32 || pos < 0 {
+        if pos < 0 {
             return Ok(Value::from(NAN));
         }
 
-        let utf16_val = primitive_val
-            .encode_utf16()
-            .nth(pos as usize)
-            .expect("failed to get utf16 value");
         // If there is no element at that index, the result is NaN
-        // TODO: We currently don't have NaN
-        Ok(Value::from(f64::from(utf16_val)))
+        match primitive_val.code_unit_at(pos as usize) {
+            Some(unit) => Ok(Value::from(f64::from(unit))),
+            None => Ok(Value::from(NAN)),
+        }
+    }
+
+    /// `String.prototype.codePointAt( pos )`
+    ///
+    /// The `codePointAt()` method returns a non-negative integer that is the Unicode code point value at the given position.
+    ///
+    /// If the code unit at the position is a leading surrogate followed by a trailing surrogate, the code point of the pair
+    /// is returned. Otherwise the code unit itself is returned. If there is no element at the position, the result is `undefined`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.codepointat
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/codePointAt
+    pub(crate) fn code_point_at(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let primitive_val = ctx.to_js_string(this)?;
+        let pos = args.get(0).map(i32::from).unwrap_or(0);
+
+        if pos < 0 {
+            return Ok(Value::undefined());
+        }
+
+        match primitive_val.code_point_at(pos as usize) {
+            Some(code_point) => Ok(Value::from(f64::from(code_point))),
+            None => Ok(Value::undefined()),
+        }
     }
 
     /// `String.prototype.concat( str1[, ...strN] )`
@@ -183,11 +230,11 @@ impl String {
     pub(crate) fn concat(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let mut new_str = ctx.to_string(this)?;
+        let mut new_str = ctx.to_js_string(this)?;
 
         for arg in args {
-            let concat_str = arg.to_string();
-            new_str.push_str(&concat_str);
+            let concat_str = ctx.to_js_string(arg)?;
+            new_str = new_str.concat(&concat_str);
         }
 
         Ok(Value::from(new_str))
@@ -207,7 +254,7 @@ impl String {
     pub(crate) fn repeat(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         let repeat_times = usize::from(
             args.get(0)
@@ -230,7 +277,7 @@ impl String {
     pub(crate) fn slice(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         let start = i32::from(
             args.get(0)
@@ -239,9 +286,7 @@ impl String {
 
         let end = i32::from(args.get(1).expect("failed to get argument in slice"));
 
-        // Calling .len() on a string would give the wrong result, as they are bytes not the number of unicode code points
-        // Note that this is an O(N) operation (because UTF-8 is complex) while getting the number of bytes is an O(1) operation.
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
 
         let from = if start < 0 {
             max(length.wrapping_add(start), 0)
@@ -256,12 +301,10 @@ impl String {
 
         let span = max(to.wrapping_sub(from), 0);
 
-        let new_str: StdString = primitive_val
-            .chars()
-            .skip(from as usize)
-            .take(span as usize)
-            .collect();
-        Ok(Value::from(new_str))
+        Ok(Value::from(primitive_val.substring(
+            from as usize,
+            from.wrapping_add(span) as usize,
+        )))
     }
 
     /// `String.prototype.startWith( searchString[, position] )`
@@ -281,16 +324,16 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         // TODO: Should throw TypeError if pattern is regular expression
-        let search_string = ctx.to_string(
+        let search_string = ctx.to_js_string(
             args.get(0)
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len() as i32;
+        let search_length = search_string.len() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
@@ -305,9 +348,9 @@ impl String {
         if end > length {
             Ok(Value::from(false))
         } else {
-            // Only use the part of the string from "start"
-            let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
-            Ok(Value::from(this_string.starts_with(&search_string)))
+            Ok(Value::from(
+                primitive_val.has_at(&search_string, start as usize),
+            ))
         }
     }
 
@@ -328,16 +371,16 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         // TODO: Should throw TypeError if search_string is regular expression
-        let search_string = ctx.to_string(
+        let search_string = ctx.to_js_string(
             args.get(0)
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
-        let search_length = search_string.chars().count() as i32;
+        let length = primitive_val.len() as i32;
+        let search_length = search_string.len() as i32;
 
         // If less than 2 args specified, end_position is 'undefined', defaults to
         // length of this
@@ -353,9 +396,9 @@ impl String {
         if start < 0 {
             Ok(Value::from(false))
         } else {
-            // Only use the part of the string up to "end"
-            let this_string: StdString = primitive_val.chars().take(end as usize).collect();
-            Ok(Value::from(this_string.ends_with(&search_string)))
+            Ok(Value::from(
+                primitive_val.has_at(&search_string, start as usize),
+            ))
         }
     }
 
@@ -372,15 +415,15 @@ impl String {
     pub(crate) fn includes(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         // TODO: Should throw TypeError if search_string is regular expression
-        let search_string = ctx.to_string(
+        let search_string = ctx.to_js_string(
             args.get(0)
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
@@ -391,16 +434,17 @@ impl String {
 
         let start = min(max(position, 0), length);
 
-        // Take the string from "this" and use only the part of it after "start"
-        let this_string: StdString = primitive_val.chars().skip(start as usize).collect();
-
-        Ok(Value::from(this_string.contains(&search_string)))
+        Ok(Value::from(
+            primitive_val
+                .index_of(&search_string, start as usize)
+                .is_some(),
+        ))
     }
 
     /// Return either the string itself or the string of the regex equivalent
     fn get_regex_string(value: &Value) -> StdString {
         match value.deref() {
-            ValueData::String(ref body) => body.into(),
+            ValueData::String(ref body) => body.to_string(),
             ValueData::Object(ref obj) => {
                 let slots = &obj.borrow().internal_slots;
                 if slots.get("RegExpMatcher").is_some() {
@@ -450,7 +494,8 @@ impl String {
             match replace_object.deref() {
                 ValueData::String(val) => {
                     // https://tc39.es/ecma262/#table-45
-                    let mut result = val.to_string();
+                    let val = val.to_string();
+                    let mut result = val.clone();
                     let re = Regex::new(r"\$(\d)").unwrap();
 
                     if val.find("$$").is_some() {
@@ -494,12 +539,12 @@ impl String {
                         .map(|capture| Value::from(capture.unwrap().as_str()))
                         .collect();
 
-                    // Returns the starting byte offset of the match
+                    // Returns the starting byte offset of the match, converted to an index in code units
                     let start = caps
                         .get(0)
                         .expect("Unable to get Byte offset from string for match")
                         .start();
-                    results.push(Value::from(start));
+                    results.push(Value::from(primitive_val[..start].encode_utf16().count()));
                     // Push the whole string being examined
                     results.push(Value::from(primitive_val.to_string()));
 
@@ -535,15 +580,15 @@ impl String {
     pub(crate) fn index_of(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         // TODO: Should throw TypeError if search_string is regular expression
-        let search_string = ctx.to_string(
+        let search_string = ctx.to_js_string(
             args.get(0)
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
 
         // If less than 2 args specified, position is 'undefined', defaults to 0
         let position = if args.len() < 2 {
@@ -554,19 +599,11 @@ impl String {
 
         let start = min(max(position, 0), length);
 
-        // Here cannot use the &str method "find", because this returns the byte
-        // index: we need to return the char index in the JS String
-        // Instead, iterate over the part we're checking until the slice we're
-        // checking "starts with" the search string
-        for index in start..length {
-            let this_string: StdString = primitive_val.chars().skip(index as usize).collect();
-            if this_string.starts_with(&search_string) {
-                // Explicitly return early with the index value
-                return Ok(Value::from(index));
-            }
+        match primitive_val.index_of(&search_string, start as usize) {
+            Some(index) => Ok(Value::from(index)),
+            // Didn't find a match, so return -1
+            None => Ok(Value::from(-1)),
         }
-        // Didn't find a match, so return -1
-        Ok(Value::from(-1))
     }
 
     /// `String.prototype.lastIndexOf( searchValue[, fromIndex] )`
@@ -588,39 +625,34 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
 
         // TODO: Should throw TypeError if search_string is regular expression
-        let search_string = ctx.to_string(
+        let search_string = ctx.to_js_string(
             args.get(0)
                 .expect("failed to get argument for String method"),
         )?;
 
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as f64;
 
-        // If less than 2 args specified, position is 'undefined', defaults to 0
-        let position = if args.len() < 2 {
-            0
+        // The search starts at `position`, or at the end of the string if it's missing or NaN
+        let position = match args.get(1) {
+            Some(position) => ctx.to_number(position)?,
+            None => f64::NAN,
+        };
+        let position = if position.is_nan() {
+            INFINITY
         } else {
-            i32::from(args.get(1).expect("Could not get argument"))
+            position.trunc()
         };
 
-        let start = min(max(position, 0), length);
+        let start = position.max(0.0).min(length) as usize;
 
-        // Here cannot use the &str method "rfind", because this returns the last
-        // byte index: we need to return the last char index in the JS String
-        // Instead, iterate over the part we're checking keeping track of the higher
-        // index we found that "starts with" the search string
-        let mut highest_index = -1;
-        for index in start..length {
-            let this_string: StdString = primitive_val.chars().skip(index as usize).collect();
-            if this_string.starts_with(&search_string) {
-                highest_index = index;
-            }
+        match primitive_val.last_index_of(&search_string, start) {
+            Some(index) => Ok(Value::from(index)),
+            // Didn't find a match, so return -1
+            None => Ok(Value::from(-1)),
         }
-
-        // This will still be -1 if no matches were found, else with be >= 0
-        Ok(Value::from(highest_index))
     }
 
     /// `String.prototype.match( regexp )`
@@ -635,9 +667,16 @@ impl String {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/match
     /// [regex]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Regular_Expressions
     pub(crate) fn r#match(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let mut re =
-            RegExp::make_regexp(&mut Value::from(Object::default()), &[args[0].clone()], ctx)?;
-        RegExp::r#match(&mut re, ctx.to_string(this)?, ctx)
+        let primitive_val = ctx.to_string(this)?;
+        let mut re = match args.first() {
+            Some(regexp) if RegExp::is_regexp(regexp) => regexp.clone(),
+            regexp => RegExp::create_regexp(
+                regexp.cloned().unwrap_or_else(Value::undefined),
+                Value::undefined(),
+                ctx,
+            )?,
+        };
+        RegExp::r#match(&mut re, primitive_val, ctx)
     }
 
     /// Abstract method `StringPad`.
@@ -645,9 +684,9 @@ impl String {
     /// Performs the actual string padding for padStart/End.
     /// <https://tc39.es/ecma262/#sec-stringpad/>
     fn string_pad(
-        primitive: StdString,
+        primitive: JsString,
         max_length: i32,
-        fill_string: Option<StdString>,
+        fill_string: Option<JsString>,
         at_start: bool,
     ) -> ResultValue {
         let primitive_length = primitive.len() as i32;
@@ -656,28 +695,23 @@ impl String {
             return Ok(Value::from(primitive));
         }
 
-        let filler = match fill_string {
-            Some(filler) => filler,
-            None => " ".to_owned(),
-        };
+        let filler = fill_string.unwrap_or_else(|| JsString::from(" "));
 
-        if filler == "" {
+        if filler.is_empty() {
             return Ok(Value::from(primitive));
         }
 
-        let fill_len = max_length.wrapping_sub(primitive_length);
-        let mut fill_str = StdString::new();
+        let fill_len = max_length.wrapping_sub(primitive_length) as usize;
 
-        while fill_str.len() < fill_len as usize {
-            fill_str.push_str(&filler);
-        }
         // Cut to size max_length
-        let concat_fill_str: StdString = fill_str.chars().take(fill_len as usize).collect();
+        let fill_str = filler
+            .repeat(fill_len / filler.len() + 1)
+            .substring(0, fill_len);
 
         if at_start {
-            Ok(Value::from(format!("{}{}", concat_fill_str, &primitive)))
+            Ok(Value::from(fill_str.concat(&primitive)))
         } else {
-            Ok(Value::from(format!("{}{}", primitive, &concat_fill_str)))
+            Ok(Value::from(primitive.concat(&fill_str)))
         }
     }
 
@@ -694,7 +728,7 @@ impl String {
     /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.padend
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/padEnd
     pub(crate) fn pad_end(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
         if args.is_empty() {
             return Err(Value::from("padEnd requires maxLength argument"));
         }
@@ -704,7 +738,7 @@ impl String {
         );
 
         let fill_string = if args.len() != 1 {
-            Some(ctx.to_string(args.get(1).expect("Could not get argument"))?)
+            Some(ctx.to_js_string(args.get(1).expect("Could not get argument"))?)
         } else {
             None
         };
@@ -729,7 +763,7 @@ impl String {
         args: &[Value],
         ctx: &mut Interpreter,
     ) -> ResultValue {
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
         if args.is_empty() {
             return Err(Value::from("padStart requires maxLength argument"));
         }
@@ -740,7 +774,7 @@ impl String {
 
         let fill_string = match args.len() {
             1 => None,
-            _ => Some(ctx.to_string(args.get(1).expect("Could not get argument"))?),
+            _ => Some(ctx.to_js_string(args.get(1).expect("Could not get argument"))?),
         };
 
         Self::string_pad(primitive_val, max_length, fill_string, true)
@@ -765,6 +799,54 @@ impl String {
     }
     }
 
+    /// Removes the trimmable whitespace from the start and/or the end of a string.
+    ///
+    /// All the trimmable whitespace characters are in the Basic Multilingual Plane, so this works on code units.
+    fn trim_string(string: &JsString, start: bool, end: bool) -> JsString {
+        let is_whitespace = |index| match string.code_unit_at(index).map(u32::from) {
+            Some(unit) => from_u32(unit).map_or(false, Self::is_trimmable_whitespace),
+            None => false,
+        };
+
+        let mut from = 0;
+        let mut to = string.len();
+        if start {
+            while from < to && is_whitespace(from) {
+                from += 1;
+            }
+        }
+        if end {
+            while to > from && is_whitespace(to - 1) {
+                to -= 1;
+            }
+        }
+
+        string.substring(from, to)
+    }
+
+    /// Maps the code points of a string, leaving its lone surrogates as they are.
+    fn map_code_points(string: &JsString, map: fn(&str) -> StdString) -> JsString {
+        if let Some(ascii) = string.as_ascii() {
+            return JsString::from(map(ascii));
+        }
+
+        let mut units = Vec::with_capacity(string.len());
+        let mut run = StdString::new();
+        for code_point in string.code_points() {
+            match code_point {
+                Ok(c) => run.push(c),
+                Err(surrogate) => {
+                    units.extend(map(&run).encode_utf16());
+                    units.push(surrogate);
+                    run.clear();
+                }
+            }
+        }
+        units.extend(map(&run).encode_utf16());
+
+        JsString::from(units)
+    }
+
     /// String.prototype.trim()
     ///
     /// The `trim()` method removes whitespace from both ends of a string.
@@ -778,10 +860,8 @@ impl String {
     /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.trim
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/trim
     pub(crate) fn trim(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let this_str = ctx.to_string(this)?;
-        Ok(Value::from(
-            this_str.trim_matches(Self::is_trimmable_whitespace),
-        ))
+        let this_str = ctx.to_js_string(this)?;
+        Ok(Value::from(Self::trim_string(&this_str, true, true)))
     }
 
     /// `String.prototype.trimStart()`
@@ -797,10 +877,8 @@ impl String {
     /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.trimstart
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/trimStart
     pub(crate) fn trim_start(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let this_str = ctx.to_string(this)?;
-        Ok(Value::from(
-            this_str.trim_start_matches(Self::is_trimmable_whitespace),
-        ))
+        let this_str = ctx.to_js_string(this)?;
+        Ok(Value::from(Self::trim_string(&this_str, true, false)))
     }
 
     /// String.prototype.trimEnd()
@@ -816,10 +894,8 @@ impl String {
     /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.trimend
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/trimEnd
     pub(crate) fn trim_end(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
-        let this_str = ctx.to_string(this)?;
-        Ok(Value::from(
-            this_str.trim_end_matches(Self::is_trimmable_whitespace),
-        ))
+        let this_str = ctx.to_js_string(this)?;
+        Ok(Value::from(Self::trim_string(&this_str, false, true)))
     }
 
     /// `String.prototype.toLowerCase()`
@@ -840,10 +916,13 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let this_str = ctx.to_string(this)?;
-        // The Rust String is mapped to uppercase using the builtin .to_lowercase().
+        let this_str = ctx.to_js_string(this)?;
+        // The string is mapped to lowercase using the builtin .to_lowercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(Value::from(this_str.to_lowercase()))
+        Ok(Value::from(Self::map_code_points(
+            &this_str,
+            str::to_lowercase,
+        )))
     }
 
     /// `String.prototype.toUpperCase()`
@@ -866,10 +945,239 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let this_str = ctx.to_string(this)?;
-        // The Rust String is mapped to uppercase using the builtin .to_uppercase().
+        let this_str = ctx.to_js_string(this)?;
+        // The string is mapped to uppercase using the builtin .to_uppercase().
         // There might be corner cases where it does not behave exactly like Javascript expects
-        Ok(Value::from(this_str.to_uppercase()))
+        Ok(Value::from(Self::map_code_points(
+            &this_str,
+            str::to_uppercase,
+        )))
+    }
+
+    /// `String.prototype.toLocaleLowerCase( [ locales ] )`
+    ///
+    /// The `toLocaleLowerCase()` method returns the calling string value converted to lower case,
+    /// according to any locale-specific case mappings.
+    ///
+    /// Without the `unicode` feature, the locales are ignored, like with `toLowerCase()`. With it,
+    /// the Turkic languages map the capital `I` to the dotless `ı`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.tolocalelowercase
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toLocaleLowerCase
+    #[allow(clippy::wrong_self_convention)]
+    #[cfg_attr(not(feature = "unicode"), allow(unused_variables))]
+    pub(crate) fn to_locale_lowercase(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        #[cfg(feature = "unicode")]
+        {
+            if unicode::is_turkic(&Self::requested_language(args, ctx)?) {
+                let lowercase = Self::map_code_points(&this_str, unicode::to_turkic_lowercase);
+                return Ok(Value::from(lowercase));
+            }
+        }
+        Ok(Value::from(Self::map_code_points(
+            &this_str,
+            str::to_lowercase,
+        )))
+    }
+
+    /// `String.prototype.toLocaleUpperCase( [ locales ] )`
+    ///
+    /// The `toLocaleUpperCase()` method returns the calling string value converted to upper case,
+    /// according to any locale-specific case mappings.
+    ///
+    /// Without the `unicode` feature, the locales are ignored, like with `toUpperCase()`. With it,
+    /// the Turkic languages map the dotted `i` to the dotted capital `İ`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.tolocaleuppercase
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toLocaleUpperCase
+    #[allow(clippy::wrong_self_convention)]
+    #[cfg_attr(not(feature = "unicode"), allow(unused_variables))]
+    pub(crate) fn to_locale_uppercase(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        #[cfg(feature = "unicode")]
+        {
+            if unicode::is_turkic(&Self::requested_language(args, ctx)?) {
+                let uppercase = Self::map_code_points(&this_str, unicode::to_turkic_uppercase);
+                return Ok(Value::from(uppercase));
+            }
+        }
+        Ok(Value::from(Self::map_code_points(
+            &this_str,
+            str::to_uppercase,
+        )))
+    }
+
+    /// Gets the language subtag of the first locale of the `locales` argument, which is either
+    /// undefined, a language tag, or an array of language tags.
+    #[cfg(feature = "unicode")]
+    fn requested_language(args: &[Value], ctx: &mut Interpreter) -> Result<StdString, Value> {
+        let locale = match args.first() {
+            None => return Ok(StdString::new()),
+            Some(locales) if locales.is_undefined() => return Ok(StdString::new()),
+            Some(locales) if locales.is_object() => locales.get_field(0),
+            Some(locale) => locale.clone(),
+        };
+        if locale.is_undefined() {
+            return Ok(StdString::new());
+        }
+        let locale = ctx.to_string(&locale)?;
+        Ok(locale
+            .split(&['-', '_'][..])
+            .next()
+            .unwrap_or_default()
+            .to_owned())
+    }
+
+    /// `String.prototype.localeCompare( that [ , locales [ , options ] ] )`
+    ///
+    /// The `localeCompare()` method returns a negative number, zero, or a positive number,
+    /// depending on whether the calling string comes before, is equivalent to, or comes after the
+    /// given string in sort order.
+    ///
+    /// Without the `unicode` feature, the strings are compared by their code units. With it, they
+    /// are compared by their base letters, then by their accents, then by their case, and
+    /// canonically equivalent strings are equal. The locales and the options are ignored.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.localecompare
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/localeCompare
+    pub(crate) fn locale_compare(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        let that_str = ctx.to_js_string(&args.first().cloned().unwrap_or_default())?;
+
+        #[cfg(feature = "unicode")]
+        let ordering = unicode::compare(&this_str.to_string_lossy(), &that_str.to_string_lossy());
+        #[cfg(not(feature = "unicode"))]
+        let ordering = this_str.cmp(&that_str);
+
+        Ok(Value::from(ordering as i32))
+    }
+
+    /// `String.prototype.normalize( [ form ] )`
+    ///
+    /// The `normalize()` method returns the Unicode Normalization Form of the calling string. The
+    /// form is one of `"NFC"`, the default, `"NFD"`, `"NFKC"` or `"NFKD"`.
+    ///
+    /// The data tables are provided by the `unicode` feature. Without it, only the ASCII strings,
+    /// which all the forms leave unchanged, can be normalized.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.normalize
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/normalize
+    pub(crate) fn normalize(
+        this: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        let form = match args.first() {
+            Some(form) if !form.is_undefined() => ctx.to_string(form)?,
+            _ => StdString::from("NFC"),
+        };
+        if !NORMALIZATION_FORMS.contains(&form.as_str()) {
+            return ctx.throw_range_error(format!(
+                "The normalization form should be one of NFC, NFD, NFKC, NFKD, not {}",
+                form
+            ));
+        }
+        Self::normalize_to(&this_str, &form, ctx)
+    }
+
+    /// Normalizes a string to one of the normalization forms.
+    #[cfg(feature = "unicode")]
+    fn normalize_to(string: &JsString, form: &str, _: &mut Interpreter) -> ResultValue {
+        Ok(Value::from(Self::map_code_points(
+            string,
+            unicode::normalizer(form),
+        )))
+    }
+
+    /// Normalizes a string to one of the normalization forms, which is only possible for the ASCII
+    /// strings without the data tables.
+    #[cfg(not(feature = "unicode"))]
+    fn normalize_to(string: &JsString, _: &str, ctx: &mut Interpreter) -> ResultValue {
+        match string.as_ascii() {
+            Some(_) => Ok(Value::from(string.clone())),
+            None => ctx.throw_range_error(
+                "Normalizing a string which isn't ASCII needs the `unicode` feature of Boa",
+            ),
+        }
+    }
+
+    /// `String.prototype.isWellFormed( )`
+    ///
+    /// The `isWellFormed()` method returns `true` if the string has no lone surrogates, so that
+    /// it can be encoded in UTF-8 without losing information.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.iswellformed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/isWellFormed
+    pub(crate) fn is_well_formed(
+        this: &mut Value,
+        _: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        let is_well_formed = this_str.code_points().all(|c| c.is_ok());
+        Ok(Value::from(is_well_formed))
+    }
+
+    /// `String.prototype.toWellFormed( )`
+    ///
+    /// The `toWellFormed()` method returns a copy of the string where the lone surrogates are
+    /// replaced by the replacement character, `U+FFFD`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype.towellformed
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/toWellFormed
+    pub(crate) fn to_well_formed(
+        this: &mut Value,
+        _: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let this_str = ctx.to_js_string(this)?;
+        if this_str.as_ascii().is_some() {
+            return Ok(Value::from(this_str));
+        }
+
+        let well_formed: StdString = this_str
+            .code_points()
+            .map(|c| c.unwrap_or(REPLACEMENT_CHARACTER))
+            .collect();
+        Ok(Value::from(well_formed))
     }
 
     /// `String.prototype.substring( indexStart[, indexEnd] )`
@@ -889,7 +1197,7 @@ impl String {
     ) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
         // If no args are specified, start is 'undefined', defaults to 0
         let start = if args.is_empty() {
             0
@@ -899,7 +1207,7 @@ impl String {
                     .expect("failed to get argument for String method"),
             )
         };
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
         // If less than 2 args specified, end is the length of the this object converted to a String
         let end = if args.len() < 2 {
             length
@@ -915,12 +1223,7 @@ impl String {
         let to = max(final_start, final_end) as usize;
         // Extract the part of the string contained between the start index and the end index
         // where start is guaranteed to be smaller or equals to end
-        let extracted_string: StdString = primitive_val
-            .chars()
-            .skip(from)
-            .take(to.wrapping_sub(from))
-            .collect();
-        Ok(Value::from(extracted_string))
+        Ok(Value::from(primitive_val.substring(from, to)))
     }
 
     /// `String.prototype.substr( start[, length] )`
@@ -937,7 +1240,7 @@ impl String {
     pub(crate) fn substr(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
         // First we get it the actual string a private field stored on the object only the engine has access to.
         // Then we convert it into a Rust String by wrapping it in from_value
-        let primitive_val = ctx.to_string(this)?;
+        let primitive_val = ctx.to_js_string(this)?;
         // If no args are specified, start is 'undefined', defaults to 0
         let mut start = if args.is_empty() {
             0
@@ -947,7 +1250,7 @@ impl String {
                     .expect("failed to get argument for String method"),
             )
         };
-        let length = primitive_val.chars().count() as i32;
+        let length = primitive_val.len() as i32;
         // If less than 2 args specified, end is +infinity, the maximum number value.
         // Using i32::max_value() should be safe because the final length used is at most
         // the number of code units from start to the end of the string,
@@ -969,13 +1272,10 @@ impl String {
         if result_length <= 0 {
             Ok(Value::from(""))
         } else {
-            let extracted_string: StdString = primitive_val
-                .chars()
-                .skip(start as usize)
-                .take(result_length as usize)
-                .collect();
-
-            Ok(Value::from(extracted_string))
+            let start = start as usize;
+            Ok(Value::from(
+                primitive_val.substring(start, start + result_length as usize),
+            ))
         }
     }
 
@@ -1006,38 +1306,91 @@ impl String {
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/matchAll
     /// [regex]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Regular_Expressions
     /// [cg]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Regular_Expressions/Groups_and_Ranges
-    // TODO: update this method to return iterator
     pub(crate) fn match_all(
         this: &mut Value,
         args: &[Value],
         ctx: &mut Interpreter,
     ) -> ResultValue {
-        let mut re: Value = match args.get(0) {
-            Some(arg) => {
-                if arg.is_null() {
-                    RegExp::make_regexp(
-                        &mut Value::from(Object::default()),
-                        &[Value::from(ctx.to_string(arg)?), Value::from("g")],
-                        ctx,
-                    )
-                } else if arg.is_undefined() {
-                    RegExp::make_regexp(
-                        &mut Value::from(Object::default()),
-                        &[Value::undefined(), Value::from("g")],
-                        ctx,
-                    )
-                } else {
-                    Ok(arg.clone())
+        let primitive_val = ctx.to_string(this)?;
+        let mut re = match args.first() {
+            Some(regexp) if RegExp::is_regexp(regexp) => {
+                let flags = ctx.get_field_value(regexp, Value::from("flags"))?;
+                if !ctx.to_string(&flags)?.contains('g') {
+                    return ctx.throw_type_error(
+                        "String.prototype.matchAll called with a non-global RegExp",
+                    );
                 }
+                regexp.clone()
             }
-            None => RegExp::make_regexp(
-                &mut Value::from(Object::default()),
-                &[Value::from(""), Value::from("g")],
+            regexp => RegExp::create_regexp(
+                regexp.cloned().unwrap_or_else(Value::undefined),
+                Value::from("g"),
                 ctx,
-            ),
-        }?;
+            )?,
+        };
+        RegExp::match_all(&mut re, primitive_val, ctx)
+    }
 
-        RegExp::match_all(&mut re, ctx.to_string(this)?)
+    /// `String.fromCharCode( num1[, ...[, numN]] )`
+    ///
+    /// The static `String.fromCharCode()` method returns a string created from the specified sequence of UTF-16 code units.
+    ///
+    /// Each argument is converted to a 16-bit unsigned integer, so surrogates, paired or not, can be created.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.fromcharcode
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/fromCharCode
+    pub(crate) fn from_char_code(
+        _: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let mut units = Vec::with_capacity(args.len());
+        for arg in args {
+            // ToUint16
+            units.push(f64_to_uint32(ctx.to_number(arg)?) as u16);
+        }
+
+        Ok(Value::from(JsString::from(units)))
+    }
+
+    /// `String.fromCodePoint( num1[, ...[, numN]] )`
+    ///
+    /// The static `String.fromCodePoint()` method returns a string created from the specified sequence of code points.
+    ///
+    /// A `RangeError` is thrown if an argument is not an integer between `0` and `0x10FFFF`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.fromcodepoint
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/fromCodePoint
+    pub(crate) fn from_code_point(
+        _: &mut Value,
+        args: &[Value],
+        ctx: &mut Interpreter,
+    ) -> ResultValue {
+        let mut units = Vec::with_capacity(args.len());
+
+        for arg in args {
+            let number = ctx.to_number(arg)?;
+            if number.trunc() != number || !(0.0..=1_114_111.0).contains(&number) {
+                return ctx.throw_range_error(format!("invalid code point: {}", arg));
+            }
+
+            let code_point = number as u32;
+            match from_u32(code_point) {
+                Some(c) => units.extend(c.encode_utf16(&mut [0; 2]).iter()),
+                // Surrogates are added as lone code units.
+                None => units.push(code_point as u16),
+            }
+        }
+
+        Ok(Value::from(JsString::from(units)))
     }
 
     /// Create a new `String` object.
@@ -1049,6 +1402,7 @@ impl String {
         prototype.set_property_slice("length", length);
         make_builtin_fn(Self::char_at, "charAt", &prototype, 1);
         make_builtin_fn(Self::char_code_at, "charCodeAt", &prototype, 1);
+        make_builtin_fn(Self::code_point_at, "codePointAt", &prototype, 1);
         make_builtin_fn(Self::to_string, "toString", &prototype, 0);
         make_builtin_fn(Self::concat, "concat", &prototype, 1);
         make_builtin_fn(Self::repeat, "repeat", &prototype, 1);
@@ -1066,13 +1420,35 @@ impl String {
         make_builtin_fn(Self::trim_end, "trimEnd", &prototype, 0);
         make_builtin_fn(Self::to_lowercase, "toLowerCase", &prototype, 0);
         make_builtin_fn(Self::to_uppercase, "toUpperCase", &prototype, 0);
+        make_builtin_fn(
+            Self::to_locale_lowercase,
+            "toLocaleLowerCase",
+            &prototype,
+            0,
+        );
+        make_builtin_fn(
+            Self::to_locale_uppercase,
+            "toLocaleUpperCase",
+            &prototype,
+            0,
+        );
+        make_builtin_fn(Self::locale_compare, "localeCompare", &prototype, 1);
+        make_builtin_fn(Self::normalize, "normalize", &prototype, 0);
+        make_builtin_fn(Self::is_well_formed, "isWellFormed", &prototype, 0);
+        make_builtin_fn(Self::to_well_formed, "toWellFormed", &prototype, 0);
         make_builtin_fn(Self::substring, "substring", &prototype, 2);
         make_builtin_fn(Self::substr, "substr", &prototype, 2);
         make_builtin_fn(Self::value_of, "valueOf", &prototype, 0);
         make_builtin_fn(Self::match_all, "matchAll", &prototype, 1);
         make_builtin_fn(Self::replace, "replace", &prototype, 2);
 
-        make_constructor_fn("String", 1, Self::make_string, global, prototype, true)
+        let string = make_constructor_fn("String", 1, Self::make_string, global, prototype, true);
+
+        // Static Methods
+        make_builtin_fn(Self::from_char_code, "fromCharCode", &string, 1);
+        make_builtin_fn(Self::from_code_point, "fromCodePoint", &string, 1);
+
+        string
     }
 
     /// Initialise the `String` object on the global object.
@@ -0,0 +1,125 @@
+//! This module implements the string iterators, returned by `String.prototype[Symbol.iterator]`.
+//!
+//! A string iterator produces the code points of the string: a surrogate pair is produced as a
+//! single string of two code units, and a lone surrogate on its own.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-string-iterator-objects
+
+use super::JsString;
+use crate::{
+    builtins::{
+        function::{make_builtin_fn, make_builtin_symbol_fn},
+        intrinsics::{self, Intrinsic},
+        iterable::create_iter_result_object,
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
+        value::{ResultValue, Value, ValueData},
+    },
+    exec::Interpreter,
+};
+
+/// The internal slot holding the string iterated by a string iterator, which is `undefined`
+/// once the iterator is done.
+const ITERATED_STRING: &str = "IteratedString";
+
+/// The internal slot holding the code unit index of the next code point produced by a string
+/// iterator.
+const STRING_NEXT_INDEX: &str = "StringNextIndex";
+
+/// `StringIterator` implementation.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StringIterator;
+
+impl StringIterator {
+    /// Creates a new iterator over the code points of a string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-createstringiterator
+    fn create_string_iterator(ctx: &Interpreter, string: JsString) -> Value {
+        let prototype = ctx.realm().intrinsic(Intrinsic::StringIteratorPrototype);
+        let iterator = Value::new_object_from_prototype(prototype, ObjectKind::Ordinary);
+        iterator.set_internal_slot(ITERATED_STRING, Value::from(string));
+        iterator.set_internal_slot(STRING_NEXT_INDEX, Value::from(0));
+        iterator
+    }
+
+    /// `String.prototype [ @@iterator ] ( )`
+    ///
+    /// Returns an iterator over the code points of the string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-string.prototype-@@iterator
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/String/@@iterator
+    pub(crate) fn iterator(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        if this.is_null_or_undefined() {
+            return ctx.throw_type_error(format!(
+                "String.prototype[Symbol.iterator] called on {}",
+                this
+            ));
+        }
+        let string = ctx.to_js_string(this)?;
+        Ok(Self::create_string_iterator(ctx, string))
+    }
+
+    /// `%StringIteratorPrototype%.next( )`
+    ///
+    /// Returns the next code point of the string, or a result which is `done` once the whole
+    /// string has been produced.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-%stringiteratorprototype%.next
+    pub(crate) fn next(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+        let is_string_iterator = match this.as_object() {
+            Some(obj) => obj.internal_slots.contains_key(ITERATED_STRING),
+            None => false,
+        };
+        if !is_string_iterator {
+            return ctx.throw_type_error("'this' is not a string iterator");
+        }
+
+        let string = match this.get_internal_slot(ITERATED_STRING).data() {
+            ValueData::String(ref string) => string.clone(),
+            _ => return Ok(create_iter_result_object(ctx, Value::undefined(), true)),
+        };
+
+        let index = ctx.to_number(&this.get_internal_slot(STRING_NEXT_INDEX))? as usize;
+        let end = match string.code_point_at(index) {
+            Some(code_point) if code_point > 0xFFFF => index + 2,
+            Some(_) => index + 1,
+            None => {
+                this.set_internal_slot(ITERATED_STRING, Value::undefined());
+                return Ok(create_iter_result_object(ctx, Value::undefined(), true));
+            }
+        };
+        this.set_internal_slot(STRING_NEXT_INDEX, Value::from(end));
+
+        let result = Value::from(string.substring(index, end));
+        Ok(create_iter_result_object(ctx, result, false))
+    }
+
+    /// Creates `%StringIteratorPrototype%`, and the `[Symbol.iterator]` method of strings.
+    pub(crate) fn init(global: &Value, iterator_prototype: &Value, iterator_symbol: &Value) {
+        let prototype = Value::new_object(Some(global));
+        prototype.set_internal_slot(INSTANCE_PROTOTYPE, iterator_prototype.clone());
+        make_builtin_fn(Self::next, "next", &prototype, 0);
+        intrinsics::set(global, Intrinsic::StringIteratorPrototype, prototype);
+
+        let string_prototype = intrinsics::get(global, Intrinsic::StringPrototype);
+        make_builtin_symbol_fn(
+            Self::iterator,
+            iterator_symbol,
+            "[Symbol.iterator]",
+            &string_prototype,
+            0,
+        );
+    }
+}
@@ -217,14 +217,21 @@ fn match_all() {
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
 
-    assert_eq!(forward(&mut engine, "'aa'.matchAll(null).length"), "0");
-    assert_eq!(forward(&mut engine, "'aa'.matchAll(/b/).length"), "0");
-    assert_eq!(forward(&mut engine, "'aa'.matchAll(/a/).length"), "1");
-    assert_eq!(forward(&mut engine, "'aa'.matchAll(/a/g).length"), "2");
+    assert_eq!(forward(&mut engine, "[...'aa'.matchAll(null)].length"), "0");
+    assert_eq!(forward(&mut engine, "[...'aa'.matchAll(/b/g)].length"), "0");
+    assert_eq!(forward(&mut engine, "[...'aa'.matchAll('a')].length"), "2");
+    assert_eq!(forward(&mut engine, "[...'aa'.matchAll(/a/g)].length"), "2");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { 'aa'.matchAll(/a/) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
 
     forward(
         &mut engine,
-        "var groupMatches = 'test1test2'.matchAll(/t(e)(st(\\d?))/g)",
+        "var groupMatches = [...'test1test2'.matchAll(/t(e)(st(\\d?))/g)]",
     );
 
     assert_eq!(forward(&mut engine, "groupMatches.length"), "2");
@@ -233,14 +240,6 @@ fn match_all() {
     assert_eq!(forward(&mut engine, "groupMatches[0][3]"), "1");
     assert_eq!(forward(&mut engine, "groupMatches[1][3]"), "2");
 
-    assert_eq!(
-        forward(
-            &mut engine,
-            "'test1test2'.matchAll(/t(e)(st(\\d?))/).length"
-        ),
-        "1"
-    );
-
     let init = r#"
         var regexp = RegExp('foo[a-z]*','g');
         var str = 'table football, foosball';
@@ -249,10 +248,20 @@ fn match_all() {
 
     forward(&mut engine, init);
 
-    assert_eq!(forward(&mut engine, "matches[0][0]"), "football");
-    assert_eq!(forward(&mut engine, "matches[0].index"), "6");
-    assert_eq!(forward(&mut engine, "matches[1][0]"), "foosball");
-    assert_eq!(forward(&mut engine, "matches[1].index"), "16");
+    assert_eq!(
+        forward(&mut engine, "matches[Symbol.iterator]() === matches"),
+        "true"
+    );
+    forward(&mut engine, "var first = matches.next();");
+    assert_eq!(forward(&mut engine, "first.done"), "false");
+    assert_eq!(forward(&mut engine, "first.value[0]"), "football");
+    assert_eq!(forward(&mut engine, "first.value.index"), "6");
+    forward(&mut engine, "var second = matches.next();");
+    assert_eq!(forward(&mut engine, "second.value[0]"), "foosball");
+    assert_eq!(forward(&mut engine, "second.value.index"), "16");
+    assert_eq!(forward(&mut engine, "matches.next().done"), "true");
+    // The iteration matches a copy of the regular expression.
+    assert_eq!(forward(&mut engine, "regexp.lastIndex"), "0");
 }
 
 #[test]
@@ -296,3 +305,465 @@ fn test_match() {
     );
     assert_eq!(forward(&mut engine, "result4[0]"), "B");
 }
+
+#[test]
+fn surrogate_pair_has_length_two() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var pair = "𝌆";
+        var object = new String('𝌆');
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "pair.length"), "2");
+    assert_eq!(forward(&mut engine, "object.length"), "2");
+    assert_eq!(forward(&mut engine, "'\\u{1F600}'.length"), "2");
+    assert_eq!(forward(&mut engine, "'中文'.length"), "2");
+}
+
+#[test]
+fn char_code_at() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var pair = "𝌆";
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "pair.charCodeAt(0)"), "55348");
+    assert_eq!(forward(&mut engine, "pair.charCodeAt(1)"), "57094");
+    assert_eq!(forward(&mut engine, "pair.charCodeAt(2)"), "NaN");
+    assert_eq!(forward(&mut engine, "'中文'.charCodeAt(1)"), "25991");
+}
+
+#[test]
+fn code_point_at() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var pair = "a𝌆";
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "pair.codePointAt(0)"), "97");
+    assert_eq!(forward(&mut engine, "pair.codePointAt(1)"), "119558");
+    assert_eq!(forward(&mut engine, "pair.codePointAt(2)"), "57094");
+    assert_eq!(forward(&mut engine, "pair.codePointAt(3)"), "undefined");
+}
+
+#[test]
+fn from_char_code() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "String.fromCharCode(72, 105)"), "Hi");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "String.fromCharCode(0xD834, 0xDF06) === '\\u{1D306}'"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "String.fromCharCode(0xD834).length"),
+        "1"
+    );
+    assert_eq!(forward(&mut engine, "String.fromCharCode(65601)"), "A");
+}
+
+#[test]
+fn from_code_point() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        forward(
+            &mut engine,
+            "String.fromCodePoint(0x1D306) === '\\ud834\\udf06'"
+        ),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "String.fromCodePoint(0xD834).charCodeAt(0)"),
+        "55348"
+    );
+}
+
+#[test]
+fn lone_surrogates() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var pair = "𝌆";
+        var leading = pair.slice(0, 1);
+        var trailing = pair.charAt(1);
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "leading.length"), "1");
+    assert_eq!(forward(&mut engine, "leading.charCodeAt(0)"), "55348");
+    assert_eq!(forward(&mut engine, "leading + trailing === pair"), "true");
+    assert_eq!(
+        forward(&mut engine, "leading.concat(trailing) === pair"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "pair.indexOf(trailing)"), "1");
+    assert_eq!(
+        forward(&mut engine, "leading.toUpperCase() === leading"),
+        "true"
+    );
+
+    // The string literals keep their lone surrogates as well.
+    assert_eq!(forward(&mut engine, r#""\uD800".charCodeAt(0)"#), "55296");
+    assert_eq!(forward(&mut engine, r#""\uD834" === leading"#), "true");
+    assert_eq!(forward(&mut engine, r#""\uD800".isWellFormed()"#), "false");
+    assert_eq!(
+        forward(&mut engine, r#"JSON.stringify("\uD800")"#),
+        r#""\ud800""#
+    );
+}
+
+#[test]
+fn last_index_of() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var str = "canal";
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "str.lastIndexOf('a')"), "3");
+    assert_eq!(forward(&mut engine, "str.lastIndexOf('a', 2)"), "1");
+    assert_eq!(forward(&mut engine, "str.lastIndexOf('a', 0)"), "-1");
+    assert_eq!(forward(&mut engine, "str.lastIndexOf('x')"), "-1");
+    assert_eq!(forward(&mut engine, "str.lastIndexOf('')"), "5");
+}
+
+#[test]
+fn rope_concatenation() {
+    let chunk = JsString::from("chunk");
+    let mut rope = JsString::new();
+    let mut flat = StdString::new();
+    for _ in 0..1000 {
+        rope = rope.concat(&chunk);
+        flat.push_str("chunk");
+    }
+
+    assert_eq!(rope.len(), 5000);
+    assert_eq!(rope, JsString::from(flat.as_str()));
+    assert_eq!(rope.code_unit_at(4999), Some(u16::from(b'k')));
+    assert_eq!(rope.substring(5, 10), "chunk");
+}
+
+#[test]
+fn rope_with_utf16_parts() {
+    let long = JsString::from("a".repeat(300));
+    let pair = JsString::from_utf16(&[0xD834, 0xDF06]);
+    let rope = long.concat(&JsString::from_utf16(&[0xD834]));
+    let rope = rope.concat(&JsString::from_utf16(&[0xDF06]));
+
+    assert_eq!(rope.len(), 302);
+    assert_eq!(rope.code_point_at(300), Some(0x1D306));
+    assert_eq!(rope, long.concat(&pair));
+    assert_eq!(
+        rope.to_string_lossy(),
+        format!("{}\u{1D306}", "a".repeat(300))
+    );
+}
+
+#[test]
+fn deep_rope() {
+    // Flattening and dropping a long chain of concatenations must not overflow the stack.
+    let chunk = JsString::from("xxxx");
+    let mut rope = JsString::new();
+    for _ in 0..100_000 {
+        rope = rope.concat(&chunk);
+    }
+    assert_eq!(rope.len(), 400_000);
+    assert_eq!(rope.code_unit_at(0), Some(u16::from(b'x')));
+
+    let mut unflattened = JsString::new();
+    for _ in 0..100_000 {
+        unflattened = unflattened.concat(&chunk);
+    }
+    drop(unflattened);
+}
+
+#[test]
+fn string_accumulation() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var s = "";
+        for (let i = 0; i < 500; i++) {
+            s += "chunk";
+        }
+        "#;
+
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "s.length"), "2500");
+    assert_eq!(forward(&mut engine, "s.slice(2495, 2500)"), "chunk");
+    assert_eq!(forward(&mut engine, "s === 'chunk'.repeat(500)"), "true");
+}
+
+#[test]
+fn locale_compare() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "'a'.localeCompare('b')"), "-1");
+    assert_eq!(forward(&mut engine, "'b'.localeCompare('a')"), "1");
+    assert_eq!(forward(&mut engine, "'abc'.localeCompare('abc')"), "0");
+    assert_eq!(forward(&mut engine, "'ab'.localeCompare('abc')"), "-1");
+}
+
+#[test]
+fn to_locale_case() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(&mut engine, "'Straße'.toLocaleUpperCase()"),
+        "STRASSE"
+    );
+    assert_eq!(forward(&mut engine, "'ÀBC'.toLocaleLowerCase()"), "àbc");
+    assert_eq!(
+        forward(&mut engine, "'istanbul'.toLocaleUpperCase('en-US')"),
+        "ISTANBUL"
+    );
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn normalize() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var composed = "\u00e9";
+        var decomposed = "e\u0301";
+        var ligature = "\ufb01";
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(
+        forward(&mut engine, "decomposed.normalize() === composed"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "composed.normalize('NFD') === decomposed"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "ligature.normalize('NFC')"),
+        "\u{fb01}"
+    );
+    assert_eq!(forward(&mut engine, "ligature.normalize('NFKC')"), "fi");
+    assert_eq!(forward(&mut engine, "ligature.normalize('NFKD')"), "fi");
+
+    let invalid = r#"
+        var invalid;
+        try {
+            composed.normalize('NFX');
+        } catch (e) {
+            invalid = e.constructor === RangeError;
+        }
+        "#;
+    forward(&mut engine, invalid);
+    assert_eq!(forward(&mut engine, "invalid"), "true");
+}
+
+#[test]
+fn normalize_ascii() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "typeof ''.normalize"), "function");
+    assert_eq!(forward(&mut engine, "'abc'.normalize()"), "abc");
+    assert_eq!(forward(&mut engine, "'abc'.normalize('NFKD')"), "abc");
+
+    let invalid = r#"
+        var invalid;
+        try {
+            'abc'.normalize('NFX');
+        } catch (e) {
+            invalid = e.constructor === RangeError;
+        }
+        "#;
+    forward(&mut engine, invalid);
+    assert_eq!(forward(&mut engine, "invalid"), "true");
+}
+
+#[cfg(not(feature = "unicode"))]
+#[test]
+fn normalize_without_tables() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var unsupported;
+        try {
+            'é'.normalize();
+        } catch (e) {
+            unsupported = e.constructor === RangeError;
+        }
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "unsupported"), "true");
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn locale_compare_unicode() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "'a'.localeCompare('A')"), "-1");
+    assert_eq!(forward(&mut engine, "'A'.localeCompare('á')"), "-1");
+    assert_eq!(forward(&mut engine, "'á'.localeCompare('b')"), "-1");
+    assert_eq!(forward(&mut engine, "'Zebra'.localeCompare('apple')"), "1");
+    assert_eq!(
+        forward(&mut engine, "'\\u00e9'.localeCompare('e\\u0301')"),
+        "0"
+    );
+}
+
+#[test]
+fn string_object_characters() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let s = new String("abc");
+        s[0] = "z";
+        s[5] = "f";
+        s.length = 10;
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "s[0]"), "a");
+    assert_eq!(forward(&mut engine, "s[2]"), "c");
+    assert_eq!(forward(&mut engine, "s[3]"), "undefined");
+    assert_eq!(forward(&mut engine, "s[5]"), "f");
+    assert_eq!(forward(&mut engine, "s.length"), "3");
+    assert_eq!(forward(&mut engine, "1 in s"), "true");
+    assert_eq!(forward(&mut engine, "delete s[1]"), "false");
+    assert_eq!(forward(&mut engine, "Object.keys(s)"), "[ 0, 1, 2, 5 ]");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { Object.defineProperty(s, '0', { value: 'y' }) } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn primitive_string_index() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "'abc'[1]"), "b");
+    assert_eq!(forward(&mut engine, "'abc'['2']"), "c");
+    assert_eq!(forward(&mut engine, "'abc'[3]"), "undefined");
+    assert_eq!(forward(&mut engine, "'\\u{1F600}'[0].length"), "1");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { undefined[0] } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn iterator() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var it = 'a\\u{1F600}b'[Symbol.iterator]();");
+    assert_eq!(forward(&mut engine, "it[Symbol.iterator]() === it"), "true");
+    assert_eq!(forward(&mut engine, "it.next().value"), "a");
+    assert_eq!(
+        forward(&mut engine, "it.next().value === '\\u{1F600}'"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "it.next().value"), "b");
+    assert_eq!(forward(&mut engine, "it.next().done"), "true");
+    assert_eq!(forward(&mut engine, "it.next().done"), "true");
+
+    // Lone surrogates are produced on their own.
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var pair = '\\u{1D306}'; [...pair.charAt(1) + pair.charAt(0) + 'a'].map(s => s.length).join()"
+        ),
+        "1,1,1"
+    );
+    assert_eq!(
+        forward(&mut engine, "[...new String('\\uD83D\\uDE00')].length"),
+        "1"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "var other = { next: it.next }; try { other.next() } catch (e) { e instanceof TypeError }"
+        ),
+        "true"
+    );
+}
+
+#[test]
+fn well_formed() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(
+        &mut engine,
+        "var pair = '\\u{1D306}'; var leading = pair.charAt(0); var trailing = pair.charAt(1);",
+    );
+
+    assert_eq!(forward(&mut engine, "'abc'.isWellFormed()"), "true");
+    assert_eq!(forward(&mut engine, "pair.isWellFormed()"), "true");
+    assert_eq!(
+        forward(&mut engine, "(leading + 'a').isWellFormed()"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "(trailing + leading).isWellFormed()"),
+        "false"
+    );
+    assert_eq!(
+        forward(&mut engine, "(leading + trailing).isWellFormed()"),
+        "true"
+    );
+
+    assert_eq!(forward(&mut engine, "'abc'.toWellFormed()"), "abc");
+    assert_eq!(forward(&mut engine, "pair.toWellFormed() === pair"), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "(trailing + 'a' + pair + leading).toWellFormed()"
+        ),
+        "\u{FFFD}a\u{1D306}\u{FFFD}"
+    );
+    assert_eq!(
+        forward(&mut engine, "(leading + 'a').toWellFormed().isWellFormed()"),
+        "true"
+    );
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn to_locale_case_turkic() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(
+        forward(&mut engine, "'istanbul'.toLocaleUpperCase('tr')"),
+        "İSTANBUL"
+    );
+    assert_eq!(
+        forward(&mut engine, "'DİYARBAKIR'.toLocaleLowerCase(['tr-TR'])"),
+        "diyarbakır"
+    );
+    assert_eq!(
+        forward(&mut engine, "'DİYARBAKIR'.toLocaleLowerCase('az')"),
+        "diyarbakır"
+    );
+}
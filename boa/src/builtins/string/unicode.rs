@@ -0,0 +1,87 @@
+//! The Unicode algorithms used by the `String` methods when the `unicode` feature is enabled.
+//!
+//! The normalization uses the data tables of the `unicode-normalization` crate. The comparison
+//! is a lightweight approximation of the [Unicode Collation Algorithm][uca], built on the
+//! canonical decomposition of the strings.
+//!
+//! [uca]: https://www.unicode.org/reports/tr10/
+
+use std::{cmp::Ordering, string::String as StdString};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+/// A function normalizing a string to one of the normalization forms.
+type Normalizer = fn(&str) -> StdString;
+
+/// Gets the function normalizing a string to one of the normalization forms accepted by
+/// `String.prototype.normalize`.
+pub(super) fn normalizer(form: &str) -> Normalizer {
+    match form {
+        "NFD" => |s| s.nfd().collect(),
+        "NFKC" => |s| s.nfkc().collect(),
+        "NFKD" => |s| s.nfkd().collect(),
+        _ => |s| s.nfc().collect(),
+    }
+}
+
+/// The collation weights of a string, compared level by level.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct SortKey {
+    /// The base letters, without their case.
+    primary: Vec<char>,
+    /// The combining marks, like the accents, with the index of the base letter they follow.
+    secondary: Vec<(usize, char)>,
+    /// Whether each base letter is uppercase, lowercase letters sorting first.
+    tertiary: Vec<bool>,
+}
+
+impl SortKey {
+    /// Computes the collation weights of a string, from its canonical decomposition.
+    fn new(string: &str) -> Self {
+        let mut key = Self::default();
+        for c in string.nfd() {
+            if is_combining_mark(c) {
+                key.secondary.push((key.primary.len(), c));
+                continue;
+            }
+            let mut lowercase = c.to_lowercase();
+            if let (Some(lower), None) = (lowercase.next(), lowercase.next()) {
+                key.primary.push(lower);
+                key.tertiary.push(lower != c);
+            } else {
+                // The letters whose lowercase mapping has more than one character keep their case.
+                key.primary.push(c);
+                key.tertiary.push(false);
+            }
+        }
+        key
+    }
+}
+
+/// Compares two strings in a locale-sensitive way.
+///
+/// The strings are compared by their base letters first, then by their accents, then by their
+/// case, so that `"a" < "A" < "á" < "b"`. Canonically equivalent strings are equal.
+pub(super) fn compare(x: &str, y: &str) -> Ordering {
+    SortKey::new(x).cmp(&SortKey::new(y))
+}
+
+/// Maps a string to uppercase with the rules of the Turkic languages, where the dotted `i` maps
+/// to the dotted capital `İ`.
+pub(super) fn to_turkic_uppercase(string: &str) -> StdString {
+    string.replace('i', "\u{130}").to_uppercase()
+}
+
+/// Maps a string to lowercase with the rules of the Turkic languages, where the capital `I` maps
+/// to the dotless `ı`, and the dotted capital `İ` maps to `i`.
+pub(super) fn to_turkic_lowercase(string: &str) -> StdString {
+    string
+        .replace("I\u{307}", "i")
+        .replace('I', "\u{131}")
+        .replace('\u{130}', "i")
+        .to_lowercase()
+}
+
+/// Whether the language of a locale uses the Turkic case mapping rules.
+pub(super) fn is_turkic(language: &str) -> bool {
+    language.eq_ignore_ascii_case("tr") || language.eq_ignore_ascii_case("az")
+}
@@ -0,0 +1,370 @@
+//! This module implements the global `structuredClone` function.
+//!
+//! The structured clone algorithm makes a deep copy of a value, keeping the cycles and the shared
+//! references between its objects. It is used by hosts to pass values between realms and workers.
+//!
+//! Only the kinds of objects this engine implements can be cloned: the plain objects, the arrays,
+//! the primitive wrappers, the errors and the regular expressions. The other objects, like the
+//! functions, throw a `TypeError`, which stands in for the `DataCloneError` of the specification.
+//!
+//! The values are first serialized to a `StructuredData`, which holds no reference to the objects of
+//! the engine and can be sent to another thread, to be deserialized in the realm of another
+//! interpreter. `structuredClone` does both steps in the same realm.
+//!
+//! More information:
+//!  - [HTML specification][spec]
+//!  - [MDN documentation][mdn]
+//!
+//! [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structured-cloning
+//! [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    builtins::{
+        error::{JsNativeError, JsNativeErrorKind},
+        function::make_builtin_fn,
+        intrinsics::Intrinsic,
+        object::{enumerable_keys, ObjectKind, INSTANCE_PROTOTYPE},
+        string::JsString,
+        value::{same_value, ResultValue, Value, ValueData},
+        Array, BigInt, RegExp,
+    },
+    exec::Interpreter,
+    BoaProfiler,
+};
+use rustc_hash::FxHashMap;
+
+/// The internal slots holding the primitive value of each kind of wrapper object.
+const WRAPPERS: &[(ObjectKind, Intrinsic, &str)] = &[
+    (
+        ObjectKind::Boolean,
+        Intrinsic::BooleanPrototype,
+        "BooleanData",
+    ),
+    (ObjectKind::Number, Intrinsic::NumberPrototype, "NumberData"),
+    (ObjectKind::String, Intrinsic::StringPrototype, "StringData"),
+    (ObjectKind::BigInt, Intrinsic::BigIntPrototype, "BigIntData"),
+];
+
+/// The kinds of the errors that keep their type when they are cloned.
+const ERRORS: &[JsNativeErrorKind] = &[
+    JsNativeErrorKind::Error,
+    JsNativeErrorKind::Range,
+    JsNativeErrorKind::Reference,
+    JsNativeErrorKind::Syntax,
+    JsNativeErrorKind::Type,
+];
+
+/// The internal slots of the plain objects.
+const PLAIN_OBJECT_SLOTS: &[&str] = &[INSTANCE_PROTOTYPE, "extensible"];
+
+/// A value serialized by the structured clone algorithm, which can be deserialized in any realm,
+/// even on another thread.
+#[derive(Debug, Clone)]
+pub struct StructuredData {
+    /// The serialized value.
+    root: Entry,
+    /// The serialized objects, referenced by their index.
+    objects: Vec<Record>,
+}
+
+/// A serialized value, which is a primitive or the index of an object.
+#[derive(Debug, Clone)]
+enum Entry {
+    Undefined,
+    Null,
+    Boolean(bool),
+    /// The UTF-16 code units of a string.
+    String(Box<[u16]>),
+    Rational(f64),
+    Integer(i32),
+    BigInt(BigInt),
+    Object(usize),
+}
+
+/// A serialized object.
+#[derive(Debug, Clone)]
+enum Record {
+    /// A primitive wrapper, of the given kind.
+    Wrapper {
+        kind: ObjectKind,
+        primitive: Entry,
+    },
+    /// A regular expression, with its source and its flags.
+    RegExp {
+        source: Entry,
+        flags: Entry,
+    },
+    /// An error, with its kind and its own message.
+    Error {
+        kind: JsNativeErrorKind,
+        message: Option<String>,
+    },
+    Array {
+        length: Entry,
+        properties: Vec<(String, Entry)>,
+    },
+    Object {
+        properties: Vec<(String, Entry)>,
+    },
+}
+
+/// The state of a serialization.
+#[derive(Debug, Default)]
+struct Serializer {
+    /// The indices of the objects serialized so far, by address, to keep cycles and shared
+    /// references.
+    memory: FxHashMap<usize, usize>,
+    objects: Vec<Record>,
+}
+
+impl Serializer {
+    /// Serializes a value.
+    fn serialize(&mut self, value: &Value, ctx: &mut Interpreter) -> Result<Entry, Value> {
+        let address = match *value.data() {
+            ValueData::Undefined => return Ok(Entry::Undefined),
+            ValueData::Null => return Ok(Entry::Null),
+            ValueData::Boolean(boolean) => return Ok(Entry::Boolean(boolean)),
+            ValueData::String(ref string) => return Ok(Entry::String(string.to_utf16().into())),
+            ValueData::Rational(number) => return Ok(Entry::Rational(number)),
+            ValueData::Integer(number) => return Ok(Entry::Integer(number)),
+            ValueData::BigInt(ref bigint) => return Ok(Entry::BigInt(bigint.clone())),
+            ValueData::Symbol(_) => {
+                ctx.throw_type_error("structuredClone: a symbol could not be cloned")?;
+                unreachable!();
+            }
+            ValueData::Object(ref obj) => &**obj as *const _ as usize,
+        };
+        if let Some(&index) = self.memory.get(&address) {
+            return Ok(Entry::Object(index));
+        }
+
+        if value.is_function() {
+            ctx.throw_type_error("structuredClone: a function could not be cloned")?;
+            unreachable!();
+        }
+
+        let (kind, is_regexp, is_plain) = {
+            let obj = value.as_object().expect("checked to be an object");
+            // The objects with other internal slots than the ones of the plain objects are
+            // platform objects, which can't be cloned.
+            let is_plain = obj.state.is_none()
+                && obj
+                    .internal_slots
+                    .keys()
+                    .all(|slot| PLAIN_OBJECT_SLOTS.contains(&slot.as_str()));
+            (
+                obj.kind,
+                obj.internal_slots.contains_key("RegExpMatcher"),
+                is_plain,
+            )
+        };
+
+        // The wrappers and the regular expressions don't have properties to copy.
+        let record = if let Some((_, _, slot)) = WRAPPERS.iter().find(|(k, _, _)| *k == kind) {
+            Record::Wrapper {
+                kind,
+                primitive: self.serialize(&value.get_internal_slot(slot), ctx)?,
+            }
+        } else if kind == ObjectKind::Ordinary && is_regexp {
+            Record::RegExp {
+                source: self.serialize(&value.get_internal_slot("OriginalSource"), ctx)?,
+                flags: self.serialize(&value.get_internal_slot("OriginalFlags"), ctx)?,
+            }
+        } else if kind == ObjectKind::Error {
+            let prototype = value.get_internal_slot(INSTANCE_PROTOTYPE);
+            let kind = ERRORS
+                .iter()
+                .copied()
+                .find(|kind| same_value(&kind.prototype(ctx), &prototype))
+                .unwrap_or(JsNativeErrorKind::Error);
+            let message = match own_property(value, "message") {
+                Some(message) => Some(ctx.to_string(&message)?),
+                None => None,
+            };
+            Record::Error { kind, message }
+        } else if kind == ObjectKind::Array || (kind == ObjectKind::Ordinary && is_plain) {
+            // The object is recorded before its properties, which may reference it.
+            let index = self.objects.len();
+            self.memory.insert(address, index);
+            self.objects.push(Record::Object {
+                properties: Vec::new(),
+            });
+
+            let mut properties = Vec::new();
+            for key in enumerable_keys(value) {
+                // The properties are read with [[Get]], so the getters are called.
+                let property = ctx.get_field_value(value, key.as_str())?;
+                properties.push((key, self.serialize(&property, ctx)?));
+            }
+            self.objects[index] = if kind == ObjectKind::Array {
+                let length = ctx.get_field_value(value, "length")?;
+                Record::Array {
+                    length: self.serialize(&length, ctx)?,
+                    properties,
+                }
+            } else {
+                Record::Object { properties }
+            };
+            return Ok(Entry::Object(index));
+        } else {
+            ctx.throw_type_error(format!(
+                "structuredClone: {} object could not be cloned",
+                kind
+            ))?;
+            unreachable!();
+        };
+
+        let index = self.objects.len();
+        self.memory.insert(address, index);
+        self.objects.push(record);
+        Ok(Entry::Object(index))
+    }
+}
+
+/// Gets the value of an own property of an object, if it has it.
+fn own_property(value: &Value, key: &str) -> Option<Value> {
+    value
+        .as_object()?
+        .properties
+        .get(key)
+        .and_then(|property| property.value.clone())
+}
+
+impl StructuredData {
+    /// Gets the value of an entry, once the objects are created.
+    fn value(entry: &Entry, objects: &[Value]) -> Value {
+        match *entry {
+            Entry::Undefined => Value::undefined(),
+            Entry::Null => Value::null(),
+            Entry::Boolean(boolean) => Value::from(boolean),
+            Entry::String(ref units) => Value::from(JsString::from_utf16(units)),
+            Entry::Rational(number) => Value::from(number),
+            Entry::Integer(number) => Value::from(number),
+            Entry::BigInt(ref bigint) => Value::from(bigint.clone()),
+            Entry::Object(index) => objects[index].clone(),
+        }
+    }
+
+    /// Creates the objects in a realm, then sets their properties, which may reference any of
+    /// them.
+    fn deserialize(&self, ctx: &mut Interpreter) -> ResultValue {
+        let global = ctx.realm().global_obj.clone();
+        let mut objects = Vec::with_capacity(self.objects.len());
+        for record in &self.objects {
+            let object = match *record {
+                Record::Wrapper {
+                    kind,
+                    ref primitive,
+                } => {
+                    let (_, prototype, slot) = WRAPPERS
+                        .iter()
+                        .find(|(k, _, _)| *k == kind)
+                        .expect("only the wrappers are serialized as wrappers");
+                    let prototype = ctx.realm().intrinsic(*prototype);
+                    let object = Value::new_object_from_prototype(prototype, kind);
+                    object.set_internal_slot(slot, Self::value(primitive, &objects));
+                    object
+                }
+                Record::RegExp {
+                    ref source,
+                    ref flags,
+                } => {
+                    let prototype = ctx.realm().intrinsic(Intrinsic::RegExpPrototype);
+                    let mut object =
+                        Value::new_object_from_prototype(prototype, ObjectKind::Ordinary);
+                    let args = [Self::value(source, &objects), Self::value(flags, &objects)];
+                    RegExp::make_regexp(&mut object, &args, ctx)?;
+                    object
+                }
+                Record::Error { kind, ref message } => {
+                    let error = JsNativeError::new(kind);
+                    match message {
+                        Some(message) => error.with_message(message.as_str()),
+                        None => error,
+                    }
+                    .to_value(ctx)
+                }
+                Record::Array { ref length, .. } => {
+                    let object = Array::new_array(ctx)?;
+                    object.set_field("length", Self::value(length, &objects));
+                    object
+                }
+                Record::Object { .. } => Value::new_object(Some(&global)),
+            };
+            objects.push(object);
+        }
+
+        for (record, object) in self.objects.iter().zip(&objects) {
+            if let Record::Array { properties, .. } | Record::Object { properties } = record {
+                for (key, property) in properties {
+                    object.set_field(key.as_str(), Self::value(property, &objects));
+                }
+            }
+        }
+        Ok(Self::value(&self.root, &objects))
+    }
+}
+
+impl Interpreter {
+    /// Makes a deep copy of a value with the structured clone algorithm.
+    ///
+    /// More information:
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structured-cloning
+    pub fn structured_clone(&mut self, value: &Value) -> ResultValue {
+        let data = self.structured_serialize(value)?;
+        self.structured_deserialize(&data)
+    }
+
+    /// Serializes a value with the structured clone algorithm, to deserialize it in another realm.
+    ///
+    /// More information:
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structuredserialize
+    pub fn structured_serialize(&mut self, value: &Value) -> Result<StructuredData, Value> {
+        let mut serializer = Serializer::default();
+        let root = serializer.serialize(value, self)?;
+        Ok(StructuredData {
+            root,
+            objects: serializer.objects,
+        })
+    }
+
+    /// Deserializes a value serialized by `structured_serialize`, in the realm of this
+    /// interpreter.
+    ///
+    /// More information:
+    ///  - [HTML specification][spec]
+    ///
+    /// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#structureddeserialize
+    pub fn structured_deserialize(&mut self, data: &StructuredData) -> ResultValue {
+        data.deserialize(self)
+    }
+}
+
+/// `structuredClone( value )`
+///
+/// Returns a deep copy of the value.
+///
+/// More information:
+///  - [HTML specification][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://html.spec.whatwg.org/multipage/structured-data.html#dom-structuredclone
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/structuredClone
+pub fn structured_clone(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let value = args.first().cloned().unwrap_or_default();
+    ctx.structured_clone(&value)
+}
+
+/// Initialise the `structuredClone` function on the global object.
+#[inline]
+pub fn init(global: &Value) {
+    let _timer = BoaProfiler::global().start_event("structuredClone", "init");
+    make_builtin_fn(structured_clone, "structuredClone", global, 1);
+}
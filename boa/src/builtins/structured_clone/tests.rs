@@ -0,0 +1,156 @@
+use crate::{builtins::value::same_value, exec::Interpreter, forward, realm::Realm};
+
+#[test]
+fn clones_primitives() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert_eq!(forward(&mut engine, "structuredClone(1)"), "1");
+    assert_eq!(forward(&mut engine, "structuredClone('text')"), "text");
+    assert_eq!(forward(&mut engine, "structuredClone(null)"), "null");
+    assert_eq!(forward(&mut engine, "structuredClone()"), "undefined");
+}
+
+#[test]
+fn clones_objects_deeply() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var original = { a: 1, nested: { b: [1, 2, { c: "three" }] } };
+        var clone = structuredClone(original);
+        original.nested.b[2].c = "changed";
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "clone === original"), "false");
+    assert_eq!(forward(&mut engine, "clone.a"), "1");
+    assert_eq!(forward(&mut engine, "clone.nested.b.length"), "3");
+    assert_eq!(forward(&mut engine, "clone.nested.b[1]"), "2");
+    assert_eq!(forward(&mut engine, "clone.nested.b[2].c"), "three");
+    assert_eq!(
+        forward(&mut engine, "Array.isArray(clone.nested.b)"),
+        "true"
+    );
+}
+
+#[test]
+fn keeps_cycles_and_shared_references() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var shared = { value: 1 };
+        var original = { first: shared, second: shared };
+        original.self = original;
+        var clone = structuredClone(original);
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "clone.self === clone"), "true");
+    assert_eq!(forward(&mut engine, "clone.first === clone.second"), "true");
+    assert_eq!(forward(&mut engine, "clone.first === shared"), "false");
+}
+
+#[test]
+fn clones_wrappers_errors_and_regexps() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var number = structuredClone(new Number(5));
+        var string = structuredClone(new String("text"));
+        var error;
+        try {
+            throw new RangeError("out of range");
+        } catch (e) {
+            error = structuredClone(e);
+        }
+        var regexp = structuredClone(new RegExp("a+", "g"));
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "typeof number"), "object");
+    assert_eq!(forward(&mut engine, "number.valueOf()"), "5");
+    assert_eq!(forward(&mut engine, "string.valueOf()"), "text");
+    assert_eq!(
+        forward(&mut engine, "error.constructor === RangeError"),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, "error.message"), "out of range");
+    assert_eq!(forward(&mut engine, "regexp.toString()"), "/a+/g");
+    assert_eq!(forward(&mut engine, "regexp.test('baa')"), "true");
+}
+
+#[test]
+fn calls_getters() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var calls = 0;
+        var clone = structuredClone({ get a() { calls += 1; return 1; } });
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "clone.a"), "1");
+    assert_eq!(forward(&mut engine, "clone.a"), "1");
+    // The clone has a data property, so the getter was only called once.
+    assert_eq!(forward(&mut engine, "calls"), "1");
+}
+
+#[test]
+fn throws_for_uncloneable_values() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var errors = 0;
+        try { structuredClone(function () {}); } catch (e) { errors += 1; }
+        try { structuredClone({ symbol: Symbol() }); } catch (e) { errors += 1; }
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "errors"), "2");
+}
+
+#[test]
+fn structured_clone_api() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var original = { a: [1] };");
+    let original = crate::forward_val(&mut engine, "original").unwrap();
+    let clone = engine.structured_clone(&original).unwrap();
+    assert_eq!(clone.get_field("a").get_field("0").to_string(), "1");
+    assert!(!same_value(&clone, &original));
+}
+
+#[test]
+fn deserializes_in_another_realm_on_another_thread() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var original = { list: [1, "two", 3n] };
+        original.self = original;
+        try {
+            throw new TypeError("bad");
+        } catch (e) {
+            original.error = e;
+        }
+        "#;
+    forward(&mut engine, init);
+    let original = crate::forward_val(&mut engine, "original").unwrap();
+    let data = engine.structured_serialize(&original).unwrap();
+
+    let results = std::thread::spawn(move || {
+        let mut engine = Interpreter::new(Realm::create());
+        let clone = engine.structured_deserialize(&data).unwrap();
+        let list = clone.get_field("list");
+        let type_error = engine.realm.global_obj.get_field("TypeError");
+        (
+            same_value(&clone.get_field("self"), &clone),
+            list.get_field("length").to_string(),
+            list.get_field("1").to_string(),
+            list.get_field("2").is_bigint(),
+            same_value(
+                &clone.get_field("error").get_field("constructor"),
+                &type_error,
+            ),
+        )
+    })
+    .join()
+    .unwrap();
+    assert_eq!(
+        results,
+        (true, "3".to_owned(), "two".to_owned(), true, true)
+    );
+}
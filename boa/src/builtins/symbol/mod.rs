@@ -21,18 +21,33 @@ mod tests;
 use super::function::{make_builtin_fn, make_constructor_fn};
 use crate::{
     builtins::{
+        intrinsics::Intrinsic,
         object::{
             internal_methods_trait::ObjectInternalMethods, Object, ObjectKind, INSTANCE_PROTOTYPE,
-            PROTOTYPE,
         },
+        property::Property,
         value::{ResultValue, Value, ValueData},
     },
     exec::Interpreter,
     BoaProfiler,
 };
-use gc::{Gc, GcCell};
+use gc::GcCell;
 use rand::random;
 
+/// The names of the well-known symbols that are available as properties of `Symbol`.
+///
+/// More information:
+/// - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-well-known-symbols
+const WELL_KNOWN_SYMBOLS: &[&str] = &[
+    "hasInstance",
+    "isConcatSpreadable",
+    "iterator",
+    "toPrimitive",
+    "unscopables",
+];
+
 /// Creates Symbol instances.
 ///
 /// Symbol instances are ordinary objects that inherit properties from the Symbol prototype object.
@@ -44,33 +59,31 @@ use rand::random;
 ///
 /// [spec]: https://tc39.es/ecma262/#sec-symbol-description
 pub fn call_symbol(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    // Set description which should either be undefined or a string
+    let description = match args.get(0) {
+        Some(value) => Value::from(value.to_string()),
+        None => Value::undefined(),
+    };
+
+    let proto = ctx.realm.intrinsic(Intrinsic::SymbolPrototype);
+    Ok(new_symbol(description, proto))
+}
+
+/// Creates a new unique symbol with the given description and prototype.
+fn new_symbol(description: Value, proto: Value) -> Value {
     // From an implementation and specificaition perspective Symbols are similar to Objects.
     // They have internal slots to hold the SymbolData and Description, they also have methods and a prototype.
     // So we start by creating an Object
-    // TODO: Set prototype to Symbol.prototype (by changing to Object::create(), use interpreter to get Symbol.prototype)
     let mut sym_instance = Object::default();
     sym_instance.kind = ObjectKind::Symbol;
 
-    // Set description which should either be undefined or a string
-    let desc_string = match args.get(0) {
-        Some(value) => Value::from(value.to_string()),
-        None => Value::undefined(),
-    };
-
-    sym_instance.set_internal_slot("Description", desc_string);
+    sym_instance.set_internal_slot("Description", description);
     sym_instance.set_internal_slot("SymbolData", Value::from(random::<i32>()));
 
     // Set __proto__ internal slot
-    let proto = ctx
-        .realm
-        .global_obj
-        .get_field("Symbol")
-        .get_field(PROTOTYPE);
     sym_instance.set_internal_slot(INSTANCE_PROTOTYPE, proto);
 
-    Ok(Value(Gc::new(ValueData::Symbol(Box::new(GcCell::new(
-        sym_instance,
-    ))))))
+    Value::new(ValueData::Symbol(Box::new(GcCell::new(sym_instance))))
 }
 
 /// `Symbol.prototype.toString()`
@@ -95,7 +108,20 @@ pub fn create(global: &Value) -> Value {
     let prototype = Value::new_object(Some(global));
 
     make_builtin_fn(to_string, "toString", &prototype, 0);
-    make_constructor_fn("Symbol", 1, call_symbol, global, prototype, false)
+    let symbol = make_constructor_fn("Symbol", 1, call_symbol, global, prototype.clone(), false);
+
+    // Well-known symbols
+    for name in WELL_KNOWN_SYMBOLS {
+        let description = Value::from(format!("Symbol.{}", name));
+        let property = Property::default()
+            .value(new_symbol(description, prototype.clone()))
+            .writable(false)
+            .enumerable(false)
+            .configurable(false);
+        symbol.set_property_slice(name, property);
+    }
+
+    symbol
 }
 
 /// Initialise the `Symbol` object on the global object.
@@ -1,4 +1,8 @@
 use super::*;
+use crate::{
+    builtins::{self, Number},
+    exec::Interpreter,
+};
 use std::convert::TryFrom;
 
 impl From<&Value> for Value {
@@ -14,6 +18,12 @@ impl From<String> for Value {
     }
 }
 
+impl From<JsString> for Value {
+    fn from(value: JsString) -> Self {
+        Self::string(value)
+    }
+}
+
 impl From<Box<str>> for Value {
     fn from(value: Box<str>) -> Self {
         Self::string(value)
@@ -172,7 +182,7 @@ impl TryFrom<&Value> for Object {
 
 impl From<JSONValue> for Value {
     fn from(value: JSONValue) -> Self {
-        Self(Gc::new(ValueData::from_json(value)))
+        Self::new(ValueData::from_json(value))
     }
 }
 
@@ -199,3 +209,416 @@ where
         }
     }
 }
+
+/// The type a value should preferably be converted to by [`Interpreter::to_primitive`].
+///
+/// It is passed as the `hint` argument of a `@@toPrimitive` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredType {
+    Default,
+    String,
+    Number,
+}
+
+impl PreferredType {
+    /// The hint string that is passed to a `@@toPrimitive` method.
+    fn hint(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::String => "string",
+            Self::Number => "number",
+        }
+    }
+}
+
+/// Checks if a character is white space or a line terminator in JavaScript.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-StrWhiteSpaceChar
+fn is_str_white_space(c: char) -> bool {
+    match c {
+        // NEXT LINE is white space in Unicode, but not in JavaScript.
+        '\u{0085}' => false,
+        '\u{FEFF}' => true,
+        _ => c.is_whitespace(),
+    }
+}
+
+/// Converts a string to a number, returning `NaN` if it is not a valid numeric literal.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-tonumber-applied-to-the-string-type
+pub(crate) fn string_to_number(string: &str) -> f64 {
+    let string = string.trim_matches(is_str_white_space);
+    match string {
+        "" => return 0.0,
+        "Infinity" | "+Infinity" => return f64::INFINITY,
+        "-Infinity" => return f64::NEG_INFINITY,
+        _ => {}
+    }
+
+    let radix = match string.get(..2) {
+        Some("0x") | Some("0X") => 16,
+        Some("0o") | Some("0O") => 8,
+        Some("0b") | Some("0B") => 2,
+        _ => 10,
+    };
+    if radix != 10 {
+        let digits = &string[2..];
+        if digits.is_empty() {
+            return f64::NAN;
+        }
+        return digits
+            .chars()
+            .try_fold(0.0, |acc, c| {
+                c.to_digit(radix)
+                    .map(|digit| acc * f64::from(radix) + f64::from(digit))
+            })
+            .unwrap_or(f64::NAN);
+    }
+
+    // Rust also parses forms like `inf` or `NaN`, which are not numeric literals.
+    let is_decimal = string
+        .chars()
+        .all(|c| c.is_ascii_digit() || "+-.eE".contains(c));
+    if !is_decimal {
+        return f64::NAN;
+    }
+    f64::from_str(string).unwrap_or(f64::NAN)
+}
+
+/// Converts a number to a 32-bit integer, wrapping it modulo 2<sup>32</sup>.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-touint32
+pub(crate) fn f64_to_uint32(number: f64) -> u32 {
+    if !number.is_finite() {
+        return 0;
+    }
+    number.trunc().rem_euclid(4_294_967_296.0) as u32
+}
+
+/// Converts a number to a signed 32-bit integer, wrapping it modulo 2<sup>32</sup>.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-toint32
+pub(crate) fn f64_to_int32(number: f64) -> i32 {
+    f64_to_uint32(number) as i32
+}
+
+impl Interpreter {
+    /// Gets one of the well-known symbols, like `@@toPrimitive`, from the `%Symbol%` intrinsic.
+    pub(crate) fn well_known_symbol(&self, name: &str) -> Value {
+        self.realm.intrinsic(Intrinsic::Symbol).get_field(name)
+    }
+
+    /// The abstract operation ToPrimitive takes an input argument and a preferred type.
+    ///
+    /// Objects are converted with their `@@toPrimitive` method if they have one, and with their
+    /// `valueOf` and `toString` methods otherwise.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-toprimitive
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_primitive(&mut self, input: &Value, preferred_type: PreferredType) -> ResultValue {
+        if !input.is_object() {
+            return Ok(input.clone());
+        }
+
+        let to_primitive = self.well_known_symbol("toPrimitive");
+        if to_primitive.is_symbol() {
+            let exotic_to_primitive = input.get_field(to_primitive);
+            if exotic_to_primitive.is_function() {
+                let hint = Value::from(preferred_type.hint());
+                let result = self.call(&exotic_to_primitive, &mut input.clone(), &[hint])?;
+                if result.is_object() {
+                    return self.throw_type_error("Symbol.toPrimitive returned an object");
+                }
+                return Ok(result);
+            } else if !exotic_to_primitive.is_null_or_undefined() {
+                return self.throw_type_error("Symbol.toPrimitive is not a function");
+            }
+        }
+
+        let hint = match preferred_type {
+            PreferredType::Default => PreferredType::Number,
+            hint => hint,
+        };
+        self.ordinary_to_primitive(input, hint)
+    }
+
+    /// Converts an object to a primitive, by calling its `valueOf` and `toString` methods in the
+    /// order given by the hint.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinarytoprimitive
+    pub(crate) fn ordinary_to_primitive(&mut self, o: &Value, hint: PreferredType) -> ResultValue {
+        debug_assert!(o.is_object());
+        debug_assert!(hint != PreferredType::Default);
+        let method_names = if hint == PreferredType::String {
+            ["toString", "valueOf"]
+        } else {
+            ["valueOf", "toString"]
+        };
+        for name in &method_names {
+            let method = o.get_field(*name);
+            if method.is_function() {
+                let result = self.call(&method, &mut o.clone(), &[])?;
+                if !result.is_object() {
+                    return Ok(result);
+                }
+            }
+        }
+
+        self.throw_type_error("cannot convert object to primitive value")
+    }
+
+    /// Converts a value into a rust heap allocated string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tostring
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_string(&mut self, value: &Value) -> Result<String, Value> {
+        match value.data() {
+            ValueData::Null => Ok("null".to_owned()),
+            ValueData::Undefined => Ok("undefined".to_owned()),
+            ValueData::Boolean(boolean) => Ok(boolean.to_string()),
+            ValueData::Rational(rational) => Ok(Number::to_native_string(*rational)),
+            ValueData::Integer(integer) => Ok(integer.to_string()),
+            ValueData::String(string) => Ok(string.to_string()),
+            ValueData::Symbol(_) => {
+                self.throw_type_error("cannot convert a Symbol to a string")?;
+                unreachable!();
+            }
+            ValueData::BigInt(ref bigint) => Ok(bigint.to_string()),
+            ValueData::Object(_) => {
+                let primitive = self.to_primitive(value, PreferredType::String)?;
+                self.to_string(&primitive)
+            }
+        }
+    }
+
+    /// Converts a value into a JavaScript string, keeping the code units of string values intact.
+    ///
+    /// Unlike `to_string`, this does not lose lone surrogates.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_js_string(&mut self, value: &Value) -> Result<JsString, Value> {
+        match value.data() {
+            ValueData::String(string) => Ok(string.clone()),
+            ValueData::Object(_) => {
+                let primitive = self.to_primitive(value, PreferredType::String)?;
+                self.to_js_string(&primitive)
+            }
+            _ => self.to_string(value).map(JsString::from),
+        }
+    }
+
+    /// Converts a value into a number.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tonumber
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_number(&mut self, value: &Value) -> Result<f64, Value> {
+        match value.data() {
+            ValueData::Undefined => Ok(f64::NAN),
+            ValueData::Null => Ok(0.0),
+            ValueData::Boolean(boolean) => Ok(if *boolean { 1.0 } else { 0.0 }),
+            ValueData::Rational(rational) => Ok(*rational),
+            ValueData::Integer(integer) => Ok(f64::from(*integer)),
+            ValueData::String(ref string) => Ok(string_to_number(&string.to_string_lossy())),
+            ValueData::Symbol(_) => {
+                self.throw_type_error("cannot convert a Symbol to a number")?;
+                unreachable!();
+            }
+            ValueData::BigInt(_) => {
+                self.throw_type_error("cannot convert a BigInt to a number")?;
+                unreachable!();
+            }
+            ValueData::Object(_) => {
+                let primitive = self.to_primitive(value, PreferredType::Number)?;
+                self.to_number(&primitive)
+            }
+        }
+    }
+
+    /// Converts a value into either a number or a BigInt.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tonumeric
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_numeric(&mut self, value: &Value) -> ResultValue {
+        let primitive = self.to_primitive(value, PreferredType::Number)?;
+        match primitive.data() {
+            ValueData::BigInt(_) | ValueData::Integer(_) | ValueData::Rational(_) => Ok(primitive),
+            _ => self.to_number(&primitive).map(Value::rational),
+        }
+    }
+
+    /// Converts a value into a signed 32-bit integer.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-toint32
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_int32(&mut self, value: &Value) -> Result<i32, Value> {
+        if let ValueData::Integer(integer) = *value.data() {
+            return Ok(integer);
+        }
+        self.to_number(value).map(f64_to_int32)
+    }
+
+    /// Converts a value into an unsigned 32-bit integer.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-touint32
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_uint32(&mut self, value: &Value) -> Result<u32, Value> {
+        self.to_number(value).map(f64_to_uint32)
+    }
+
+    /// Converts a value into an integral number, truncated toward zero, keeping the infinities
+    /// and converting `NaN` to 0.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tointegerorinfinity
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_integer_or_infinity(&mut self, value: &Value) -> Result<f64, Value> {
+        let number = self.to_number(value)?;
+        if number.is_nan() {
+            Ok(0.0)
+        } else {
+            Ok(number.trunc())
+        }
+    }
+
+    /// Converts a value into an integer suitable for use as the length of an array-like object,
+    /// clamped between 0 and `Number.MAX_SAFE_INTEGER`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tolength
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_length(&mut self, value: &Value) -> Result<usize, Value> {
+        let length = self.to_integer_or_infinity(value)?;
+        Ok(length.clamp(0.0, 9_007_199_254_740_991.0) as usize)
+    }
+
+    /// Converts a value into a BigInt.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-tobigint
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_bigint(&mut self, value: &Value) -> Result<BigInt, Value> {
+        match value.data() {
+            ValueData::Null => {
+                self.throw_type_error("cannot convert null to a BigInt")?;
+                unreachable!();
+            }
+            ValueData::Undefined => {
+                self.throw_type_error("cannot convert undefined to a BigInt")?;
+                unreachable!();
+            }
+            ValueData::String(ref string) => {
+                Ok(BigInt::from_string(&string.to_string_lossy(), self)?)
+            }
+            ValueData::Boolean(true) => Ok(BigInt::from(1)),
+            ValueData::Boolean(false) => Ok(BigInt::from(0)),
+            ValueData::Integer(num) => Ok(BigInt::from(*num)),
+            ValueData::Rational(num) => {
+                if let Ok(bigint) = BigInt::try_from(*num) {
+                    return Ok(bigint);
+                }
+                self.throw_type_error(format!(
+                    "The number {} cannot be converted to a BigInt because it is not an integer",
+                    num
+                ))?;
+                unreachable!();
+            }
+            ValueData::BigInt(b) => Ok(b.clone()),
+            ValueData::Object(_) => {
+                let primitive = self.to_primitive(value, PreferredType::Number)?;
+                self.to_bigint(&primitive)
+            }
+            ValueData::Symbol(_) => {
+                self.throw_type_error("cannot convert Symbol to a BigInt")?;
+                unreachable!();
+            }
+        }
+    }
+
+    /// The abstract operation ToPropertyKey takes argument argument. It converts argument to a value that can be used as a property key.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-topropertykey
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_property_key(&mut self, value: &Value) -> ResultValue {
+        let key = self.to_primitive(value, PreferredType::String)?;
+        if key.is_symbol() {
+            Ok(key)
+        } else {
+            self.to_string(&key).map(Value::from)
+        }
+    }
+
+    /// The abstract operation ToObject converts argument to a value of type Object.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-toobject
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_object(&mut self, value: &Value) -> ResultValue {
+        let (prototype, kind, slot) = match value.data() {
+            ValueData::Undefined | ValueData::Null => {
+                return self.throw_type_error(format!("cannot convert {} to an object", value));
+            }
+            ValueData::Boolean(_) => (
+                Intrinsic::BooleanPrototype,
+                ObjectKind::Boolean,
+                "BooleanData",
+            ),
+            ValueData::Integer(_) | ValueData::Rational(_) => {
+                (Intrinsic::NumberPrototype, ObjectKind::Number, "NumberData")
+            }
+            ValueData::String(_) => (Intrinsic::StringPrototype, ObjectKind::String, "StringData"),
+            ValueData::BigInt(_) => (Intrinsic::BigIntPrototype, ObjectKind::BigInt, "BigIntData"),
+            ValueData::Object(_) | ValueData::Symbol(_) => return Ok(value.clone()),
+        };
+
+        let proto = self.realm.intrinsic(prototype);
+        let object = Value::new_object_from_prototype(proto, kind);
+        object.set_internal_slot(slot, value.clone());
+        if let ValueData::String(ref string) = value.data() {
+            builtins::String::define_length(&object, string);
+        }
+        Ok(object)
+    }
+}
@@ -38,17 +38,17 @@ macro_rules! print_obj_value {
     };
     (props of $obj:expr, $display_fn:ident, $indent:expr, $encounters:expr, $print_internals:expr) => {
         print_obj_value!(impl properties, $obj, |(key, val)| {
-            let v = &val
-                .value
-                .as_ref()
-                .expect("Could not get the property's value");
+            // The getters aren't called, so the accessor properties are only labelled.
+            let v = match val.value {
+                Some(ref v) => $display_fn(v, $encounters, $indent.wrapping_add(4), $print_internals),
+                None => String::from(match (val.get.is_some(), val.set.is_some()) {
+                    (true, true) => "[Getter/Setter]",
+                    (true, false) => "[Getter]",
+                    _ => "[Setter]",
+                }),
+            };
 
-            format!(
-                "{:>width$}: {}",
-                key,
-                $display_fn(v, $encounters, $indent.wrapping_add(4), $print_internals),
-                width = $indent,
-            )
+            format!("{:>width$}: {}", key, v, width = $indent)
         })
     };
 
@@ -64,7 +64,8 @@ macro_rules! print_obj_value {
     };
 }
 
-pub(crate) fn log_string_from(x: &ValueData, print_internals: bool) -> String {
+/// Formats a value as the `console` prints it, optionally with the internal slots of objects.
+pub fn log_string_from(x: &ValueData, print_internals: bool) -> String {
     match x {
         // We don't want to print private (compiler) or prototype properties
         ValueData::Object(ref v) => {
@@ -87,15 +88,12 @@ pub(crate) fn log_string_from(x: &ValueData, print_internals: bool) -> String {
                     format!("Boolean {{ {} }}", bool_data)
                 }
                 ObjectKind::Array => {
-                    let len = i32::from(
-                        &v.borrow()
-                            .properties
-                            .get("length")
-                            .unwrap()
-                            .value
-                            .clone()
-                            .expect("Could not borrow value"),
-                    );
+                    let len = v
+                        .borrow()
+                        .properties
+                        .get("length")
+                        .and_then(|prop| prop.value.as_ref().map(i32::from))
+                        .unwrap_or(0);
 
                     if len == 0 {
                         return String::from("[]");
@@ -104,17 +102,22 @@ pub(crate) fn log_string_from(x: &ValueData, print_internals: bool) -> String {
                     let arr = (0..len)
                         .map(|i| {
                             // Introduce recursive call to stringify any objects
-                            // which are part of the Array
-                            log_string_from(
-                                &v.borrow()
-                                    .properties
-                                    .get(&i.to_string())
-                                    .unwrap()
-                                    .value
-                                    .clone()
-                                    .expect("Could not borrow value"),
-                                print_internals,
-                            )
+                            // which are part of the Array. Holes print as `undefined`
+                            // and accessors are only labelled, as for other objects.
+                            match v.borrow().properties.get(&i.to_string()) {
+                                Some(prop) => match prop.value {
+                                    Some(ref value) => log_string_from(value, print_internals),
+                                    None => String::from(
+                                        match (prop.get.is_some(), prop.set.is_some()) {
+                                            (true, true) => "[Getter/Setter]",
+                                            (true, false) => "[Getter]",
+                                            (false, true) => "[Setter]",
+                                            (false, false) => "undefined",
+                                        },
+                                    ),
+                                },
+                                None => String::from("undefined"),
+                            }
                         })
                         .collect::<Vec<String>>()
                         .join(", ");
@@ -1,7 +1,7 @@
 use super::*;
 use crate::{builtins::Number, Interpreter};
 
-use std::borrow::Borrow;
+use std::{borrow::Borrow, ptr};
 
 impl Value {
     /// Strict equality comparison.
@@ -10,28 +10,10 @@ impl Value {
     /// For more information, check <https://tc39.es/ecma262/#sec-strict-equality-comparison>.
     pub fn strict_equals(&self, other: &Self) -> bool {
         // 1. If Type(x) is different from Type(y), return false.
-        if self.get_type() != other.get_type() {
-            return false;
-        }
-
-        match (self.data(), other.data()) {
-            // 2. If Type(x) is Number or BigInt, then
-            //    a. Return ! Type(x)::equal(x, y).
-            (ValueData::BigInt(x), ValueData::BigInt(y)) => BigInt::equal(x, y),
-            (ValueData::Rational(x), ValueData::Rational(y)) => Number::equal(*x, *y),
-            (ValueData::Rational(x), ValueData::Integer(y)) => Number::equal(*x, f64::from(*y)),
-            (ValueData::Integer(x), ValueData::Rational(y)) => Number::equal(f64::from(*x), *y),
-            (ValueData::Integer(x), ValueData::Integer(y)) => x == y,
-
-            //Null has to be handled specially because "typeof null" returns object and if we managed
-            //this without a special case we would compare self and other as if they were actually
-            //objects which unfortunately fails
-            //Specification Link: https://tc39.es/ecma262/#sec-typeof-operator
-            (ValueData::Null, ValueData::Null) => true,
-
-            // 3. Return ! SameValueNonNumeric(x, y).
-            (_, _) => same_value_non_numeric(self, other),
-        }
+        // 2. If Type(x) is Number or BigInt, then
+        //    a. Return ! Type(x)::equal(x, y).
+        // 3. Return ! SameValueNonNumeric(x, y).
+        compare(self, other, Number::equal, BigInt::equal)
     }
 
     /// Abstract equality comparison.
@@ -39,17 +21,21 @@ impl Value {
     /// This method is executed when doing abstract equality comparisons with the `==` operator.
     ///  For more information, check <https://tc39.es/ecma262/#sec-abstract-equality-comparison>
     #[allow(clippy::float_cmp)]
-    pub fn equals(&mut self, other: &mut Self, interpreter: &mut Interpreter) -> bool {
+    pub fn equals(
+        &mut self,
+        other: &mut Self,
+        interpreter: &mut Interpreter,
+    ) -> Result<bool, Value> {
         // 1. If Type(x) is the same as Type(y), then
         //     a. Return the result of performing Strict Equality Comparison x === y.
         if self.get_type() == other.get_type() {
-            return self.strict_equals(other);
+            return Ok(self.strict_equals(other));
         }
 
         match (self.data(), other.data()) {
             // 2. If x is null and y is undefined, return true.
             // 3. If x is undefined and y is null, return true.
-            _ if self.is_null_or_undefined() && other.is_null_or_undefined() => true,
+            _ if self.is_null_or_undefined() && other.is_null_or_undefined() => Ok(true),
 
             // 3. If Type(x) is Number and Type(y) is String, return the result of the comparison x == ! ToNumber(y).
             // 4. If Type(x) is String and Type(y) is Number, return the result of the comparison ! ToNumber(x) == y.
@@ -63,23 +49,27 @@ impl Value {
             | (ValueData::Integer(_), ValueData::Boolean(_)) => {
                 let a: &Value = self.borrow();
                 let b: &Value = other.borrow();
-                Number::equal(f64::from(a), f64::from(b))
+                Ok(Number::equal(f64::from(a), f64::from(b)))
             }
 
             // 6. If Type(x) is BigInt and Type(y) is String, then
             //    a. Let n be ! StringToBigInt(y).
             //    b. If n is NaN, return false.
             //    c. Return the result of the comparison x == n.
-            (ValueData::BigInt(ref a), ValueData::String(ref b)) => match string_to_bigint(b) {
-                Some(ref b) => a == b,
-                None => false,
-            },
+            (ValueData::BigInt(ref a), ValueData::String(ref b)) => {
+                Ok(match string_to_bigint(&b.to_string_lossy()) {
+                    Some(ref b) => a == b,
+                    None => false,
+                })
+            }
 
             // 7. If Type(x) is String and Type(y) is BigInt, return the result of the comparison y == x.
-            (ValueData::String(ref a), ValueData::BigInt(ref b)) => match string_to_bigint(a) {
-                Some(ref a) => a == b,
-                None => false,
-            },
+            (ValueData::String(ref a), ValueData::BigInt(ref b)) => {
+                Ok(match string_to_bigint(&a.to_string_lossy()) {
+                    Some(ref a) => a == b,
+                    None => false,
+                })
+            }
 
             // 8. If Type(x) is Boolean, return the result of the comparison ! ToNumber(x) == y.
             (ValueData::Boolean(_), _) => {
@@ -94,27 +84,27 @@ impl Value {
             // 10. If Type(x) is either String, Number, BigInt, or Symbol and Type(y) is Object, return the result
             // of the comparison x == ? ToPrimitive(y).
             (ValueData::Object(_), _) => {
-                let mut primitive = interpreter.to_primitive(self, None);
+                let mut primitive = interpreter.to_primitive(self, PreferredType::Default)?;
                 primitive.equals(other, interpreter)
             }
 
             // 11. If Type(x) is Object and Type(y) is either String, Number, BigInt, or Symbol, return the result
             // of the comparison ? ToPrimitive(x) == y.
             (_, ValueData::Object(_)) => {
-                let mut primitive = interpreter.to_primitive(other, None);
+                let mut primitive = interpreter.to_primitive(other, PreferredType::Default)?;
                 primitive.equals(self, interpreter)
             }
 
             // 12. If Type(x) is BigInt and Type(y) is Number, or if Type(x) is Number and Type(y) is BigInt, then
             //    a. If x or y are any of NaN, +∞, or -∞, return false.
             //    b. If the mathematical value of x is equal to the mathematical value of y, return true; otherwise return false.
-            (ValueData::BigInt(ref a), ValueData::Rational(ref b)) => a == b,
-            (ValueData::Rational(ref a), ValueData::BigInt(ref b)) => a == b,
-            (ValueData::BigInt(ref a), ValueData::Integer(ref b)) => a == b,
-            (ValueData::Integer(ref a), ValueData::BigInt(ref b)) => a == b,
+            (ValueData::BigInt(ref a), ValueData::Rational(ref b)) => Ok(a == b),
+            (ValueData::Rational(ref a), ValueData::BigInt(ref b)) => Ok(a == b),
+            (ValueData::BigInt(ref a), ValueData::Integer(ref b)) => Ok(a == b),
+            (ValueData::Integer(ref a), ValueData::BigInt(ref b)) => Ok(a == b),
 
             // 13. Return false.
-            _ => false,
+            _ => Ok(false),
         }
     }
 }
@@ -137,33 +127,17 @@ pub fn string_to_bigint(string: &str) -> Option<BigInt> {
 
 /// The internal comparison abstract operation SameValue(x, y),
 /// where x and y are ECMAScript language values, produces true or false.
-/// Such a comparison is performed as follows:
 ///
-/// https://tc39.es/ecma262/#sec-samevalue
-/// strict mode currently compares the pointers
-pub fn same_value(x: &Value, y: &Value, strict: bool) -> bool {
-    if strict {
-        // Do both Values point to the same underlying valueData?
-        return std::ptr::eq(x.data(), y.data());
-    }
-
+/// It's used by `Object.is`, and differs from strict equality in its treatment of `NaN`, which
+/// is the same value as itself, and of +0 and -0, which aren't.
+///
+/// <https://tc39.es/ecma262/#sec-samevalue>
+pub fn same_value(x: &Value, y: &Value) -> bool {
     // 1. If Type(x) is different from Type(y), return false.
-    if x.get_type() != y.get_type() {
-        return false;
-    }
-
-    match (x.data(), y.data()) {
-        // 2. If Type(x) is Number or BigInt, then
-        //    a. Return ! Type(x)::SameValue(x, y).
-        (ValueData::BigInt(x), ValueData::BigInt(y)) => BigInt::same_value(x, y),
-        (ValueData::Rational(x), ValueData::Rational(y)) => Number::same_value(*x, *y),
-        (ValueData::Rational(x), ValueData::Integer(y)) => Number::same_value(*x, f64::from(*y)),
-        (ValueData::Integer(x), ValueData::Rational(y)) => Number::same_value(f64::from(*x), *y),
-        (ValueData::Integer(x), ValueData::Integer(y)) => x == y,
-
-        // 3. Return ! SameValueNonNumeric(x, y).
-        (_, _) => same_value_non_numeric(x, y),
-    }
+    // 2. If Type(x) is Number or BigInt, then
+    //    a. Return ! Type(x)::SameValue(x, y).
+    // 3. Return ! SameValueNonNumeric(x, y).
+    compare(x, y, Number::same_value, BigInt::same_value)
 }
 
 /// The internal comparison abstract operation SameValueZero(x, y),
@@ -174,42 +148,46 @@ pub fn same_value(x: &Value, y: &Value, strict: bool) -> bool {
 ///
 /// <https://tc39.es/ecma262/#sec-samevaluezero>
 pub fn same_value_zero(x: &Value, y: &Value) -> bool {
-    if x.get_type() != y.get_type() {
-        return false;
-    }
+    // 1. If Type(x) is different from Type(y), return false.
+    // 2. If Type(x) is Number or BigInt, then
+    //    a. Return ! Type(x)::SameValueZero(x, y).
+    // 3. Return ! SameValueNonNumeric(x, y).
+    compare(x, y, Number::same_value_zero, BigInt::same_value_zero)
+}
 
+/// The internal comparison abstract operation SameValueNonNumeric(x, y), where neither x nor y
+/// are numeric values.
+///
+/// Values of different types are never the same value.
+///
+/// <https://tc39.es/ecma262/#sec-samevaluenonnumeric>
+pub fn same_value_non_numeric(x: &Value, y: &Value) -> bool {
     match (x.data(), y.data()) {
-        // 2. If Type(x) is Number or BigInt, then
-        //    a. Return ! Type(x)::SameValueZero(x, y).
-        (ValueData::BigInt(x), ValueData::BigInt(y)) => BigInt::same_value_zero(x, y),
-
-        (ValueData::Rational(x), ValueData::Rational(y)) => Number::same_value_zero(*x, *y),
-        (ValueData::Rational(x), ValueData::Integer(y)) => {
-            Number::same_value_zero(*x, f64::from(*y))
-        }
-        (ValueData::Integer(x), ValueData::Rational(y)) => {
-            Number::same_value_zero(f64::from(*x), *y)
-        }
-        (ValueData::Integer(x), ValueData::Integer(y)) => x == y,
-
-        // 3. Return ! SameValueNonNumeric(x, y).
-        (_, _) => same_value_non_numeric(x, y),
+        (ValueData::Undefined, ValueData::Undefined) | (ValueData::Null, ValueData::Null) => true,
+        (ValueData::String(x), ValueData::String(y)) => x == y,
+        (ValueData::Boolean(x), ValueData::Boolean(y)) => x == y,
+        // Objects and symbols are the same value only if they are the same object.
+        (ValueData::Object(x), ValueData::Object(y))
+        | (ValueData::Symbol(x), ValueData::Symbol(y)) => ptr::eq::<GcCell<Object>>(&**x, &**y),
+        _ => false,
     }
 }
 
-pub fn same_value_non_numeric(x: &Value, y: &Value) -> bool {
-    debug_assert!(x.get_type() == y.get_type());
-    match x.get_type() {
-        "undefined" => true,
-        "null" => true,
-        "string" => {
-            if x.to_string() == y.to_string() {
-                return true;
-            }
-            false
-        }
-        "boolean" => bool::from(x) == bool::from(y),
-        "object" => std::ptr::eq(x, y),
-        _ => false,
+/// The steps shared by the strict equality comparison, SameValue and SameValueZero, which only
+/// differ in how they compare numbers.
+fn compare(
+    x: &Value,
+    y: &Value,
+    number: fn(f64, f64) -> bool,
+    bigint: fn(&BigInt, &BigInt) -> bool,
+) -> bool {
+    match (x.data(), y.data()) {
+        (ValueData::BigInt(x), ValueData::BigInt(y)) => bigint(x, y),
+        // Integers are neither NaN nor -0, so all the algorithms agree on them.
+        (ValueData::Integer(x), ValueData::Integer(y)) => x == y,
+        (ValueData::Integer(x), ValueData::Rational(y)) => number(f64::from(*x), *y),
+        (ValueData::Rational(x), ValueData::Integer(y)) => number(*x, f64::from(*y)),
+        (ValueData::Rational(x), ValueData::Rational(y)) => number(*x, *y),
+        _ => same_value_non_numeric(x, y),
     }
 }
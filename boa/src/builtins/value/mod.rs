@@ -6,19 +6,22 @@
 mod tests;
 
 use crate::builtins::{
+    intrinsics::{self, Intrinsic},
     object::{
-        internal_methods_trait::ObjectInternalMethods, InternalState, InternalStateCell, Object,
-        ObjectKind, INSTANCE_PROTOTYPE, PROTOTYPE,
+        internal_methods_trait::ObjectInternalMethods, shape::MAX_ARRAY_INDEX, InternalState,
+        InternalStateCell, Object, ObjectKind, PropertyCache, INSTANCE_PROTOTYPE,
     },
     property::Property,
+    string::JsString,
     BigInt, Function,
 };
-use crate::BoaProfiler;
+use crate::{exec::heap, BoaProfiler};
 
 use gc::{Finalize, Gc, GcCell, GcCellRef, Trace};
 use serde_json::{map::Map, Number as JSONNumber, Value as JSONValue};
 use std::{
     any::Any,
+    borrow::Cow,
     collections::HashSet,
     f64::NAN,
     fmt::{self, Display},
@@ -40,30 +43,91 @@ pub use operations::*;
 #[must_use]
 pub type ResultValue = Result<Value, Value>;
 
-/// A Garbage-collected Javascript value as represented in the interpreter.
-#[derive(Debug, Clone, Trace, Finalize, Default)]
-pub struct Value(pub(crate) Gc<ValueData>);
+/// A field of an object, resolved by `Value::lookup_field`.
+#[derive(Debug, Clone)]
+pub(crate) enum Field {
+    /// The value of a data property, or undefined if there is no such property.
+    Data(Value),
+    /// An accessor property, whose getter gives the value of the field.
+    Accessor(Property),
+}
+
+/// A Javascript value as represented in the interpreter.
+///
+/// `undefined`, `null`, the booleans and the numbers are immediate values, held by the `Value`
+/// itself, so creating them doesn't allocate. The other values are allocated on the garbage
+/// collected heap, and the clones of a value share its allocation.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub struct Value(pub(crate) Repr);
+
+/// The representation of a `Value`.
+#[derive(Debug, Clone, Trace, Finalize)]
+pub(crate) enum Repr {
+    /// A primitive without data of its own on the heap.
+    Immediate(ValueData),
+    /// A value allocated on the heap.
+    Heap(Gc<HeapData>),
+}
+
+/// The data of a value allocated on the heap.
+///
+/// The `Drop` implementation generated by the `Trace` derive finalizes a `ValueData` whenever it
+/// is dropped, but a `HeapData` only exists inside the `Gc` box of its value, so it is only
+/// finalized when the collector frees the value.
+#[derive(Debug, Trace)]
+pub(crate) struct HeapData(ValueData);
+
+impl Finalize for HeapData {
+    fn finalize(&self) {
+        heap::record_finalization(&self.0);
+    }
+}
 
 impl Value {
+    /// Creates a new value, allocating its data on the heap unless it is an immediate value.
+    #[inline]
+    pub(crate) fn new(data: ValueData) -> Self {
+        match data {
+            ValueData::Undefined
+            | ValueData::Null
+            | ValueData::Boolean(_)
+            | ValueData::Rational(_)
+            | ValueData::Integer(_) => Self(Repr::Immediate(data)),
+            _ => {
+                heap::record_allocation(&data);
+                Self(Repr::Heap(Gc::new(HeapData(data))))
+            }
+        }
+    }
+
+    /// Returns true if the value is held by the `Value` itself rather than allocated on the heap.
+    #[inline]
+    pub(crate) fn is_immediate(&self) -> bool {
+        match self.0 {
+            Repr::Immediate(_) => true,
+            Repr::Heap(_) => false,
+        }
+    }
+
     /// Creates a new `undefined` value.
     #[inline]
     pub fn undefined() -> Self {
-        Self(Gc::new(ValueData::Undefined))
+        Self::new(ValueData::Undefined)
     }
 
     /// Creates a new `null` value.
     #[inline]
     pub fn null() -> Self {
-        Self(Gc::new(ValueData::Null))
+        Self::new(ValueData::Null)
     }
 
     /// Creates a new string value.
     #[inline]
     pub fn string<S>(value: S) -> Self
     where
-        S: Into<String>,
+        S: Into<JsString>,
     {
-        Self(Gc::new(ValueData::String(value.into())))
+        Self::new(ValueData::String(value.into()))
     }
 
     /// Creates a new number value.
@@ -72,7 +136,7 @@ impl Value {
     where
         N: Into<f64>,
     {
-        Self(Gc::new(ValueData::Rational(value.into())))
+        Self::new(ValueData::Rational(value.into()))
     }
 
     /// Creates a new number value.
@@ -81,7 +145,7 @@ impl Value {
     where
         I: Into<i32>,
     {
-        Self(Gc::new(ValueData::Integer(value.into())))
+        Self::new(ValueData::Integer(value.into()))
     }
 
     /// Creates a new number value.
@@ -96,25 +160,28 @@ impl Value {
     /// Creates a new bigint value.
     #[inline]
     pub fn bigint(value: BigInt) -> Self {
-        Self(Gc::new(ValueData::BigInt(value)))
+        Self::new(ValueData::BigInt(value))
     }
 
     /// Creates a new boolean value.
     #[inline]
     pub fn boolean(value: bool) -> Self {
-        Self(Gc::new(ValueData::Boolean(value)))
+        Self::new(ValueData::Boolean(value))
     }
 
     /// Creates a new object value.
     #[inline]
     pub fn object(object: Object) -> Self {
-        Self(Gc::new(ValueData::Object(Box::new(GcCell::new(object)))))
+        Self::new(ValueData::Object(Box::new(GcCell::new(object))))
     }
 
     /// Gets the underlying `ValueData` structure.
     #[inline]
     pub fn data(&self) -> &ValueData {
-        &*self.0
+        match self.0 {
+            Repr::Immediate(ref data) => data,
+            Repr::Heap(ref data) => &data.0,
+        }
     }
 
     /// Helper function to convert the `Value` to a number and compute its power.
@@ -129,7 +196,7 @@ impl Value {
     pub fn new_object(global: Option<&Value>) -> Self {
         let _timer = BoaProfiler::global().start_event("new_object", "value");
         if let Some(global) = global {
-            let object_prototype = global.get_field("Object").get_field(PROTOTYPE);
+            let object_prototype = intrinsics::get(global, Intrinsic::ObjectPrototype);
 
             let object = Object::create(object_prototype);
             Self::object(object)
@@ -160,7 +227,7 @@ impl Deref for Value {
 }
 
 /// A Javascript value
-#[derive(Trace, Finalize, Debug, Clone)]
+#[derive(Trace, Debug, Clone)]
 pub enum ValueData {
     /// `null` - A null value, for when a value doesn't exist.
     Null,
@@ -168,8 +235,8 @@ pub enum ValueData {
     Undefined,
     /// `boolean` - A `true` / `false` value, for if a certain criteria is met.
     Boolean(bool),
-    /// `String` - A UTF-8 string, such as `"Hello, world"`.
-    String(String),
+    /// `String` - A sequence of UTF-16 code units, such as `"Hello, world"`.
+    String(JsString),
     /// `Number` - A 64-bit floating point number, such as `3.1415`
     Rational(f64),
     /// `Number` - A 32-bit integer, such as `42`.
@@ -320,16 +387,7 @@ impl ValueData {
     pub fn to_number(&self) -> f64 {
         match *self {
             Self::Object(_) | Self::Symbol(_) | Self::Undefined => NAN,
-            Self::String(ref str) => {
-                if str.is_empty() {
-                    return 0.0;
-                }
-
-                match FromStr::from_str(str) {
-                    Ok(num) => num,
-                    Err(_) => NAN,
-                }
-            }
+            Self::String(ref str) => string_to_number(&str.to_string_lossy()),
             Self::Boolean(true) => 1.0,
             Self::Boolean(false) | Self::Null => 0.0,
             Self::Rational(num) => num,
@@ -348,7 +406,7 @@ impl ValueData {
             | Self::Symbol(_)
             | Self::Null
             | Self::Boolean(false) => 0,
-            Self::String(ref str) => match FromStr::from_str(str) {
+            Self::String(ref str) => match FromStr::from_str(&str.to_string_lossy()) {
                 Ok(num) => num,
                 Err(_) => 0,
             },
@@ -380,6 +438,16 @@ impl ValueData {
         removed.is_some()
     }
 
+    /// Deletes a property of a Value object, unless it isn't configurable.
+    ///
+    /// Like the `delete` operator, it returns `false` only if the property is still there.
+    pub fn delete_property(&self, key: &Value) -> bool {
+        match *self {
+            Self::Object(ref obj) => obj.borrow_mut().delete(key),
+            _ => true,
+        }
+    }
+
     /// Resolve the property in the object.
     ///
     /// A copy of the Property is returned.
@@ -397,27 +465,22 @@ impl ValueData {
             return None;
         }
 
-        let obj: Object = match *self {
-            Self::Object(ref obj) => {
-                let hash = obj.clone();
-                // TODO: This will break, we should return a GcCellRefMut instead
-                // into_inner will consume the wrapped value and remove it from the hashmap
-                hash.into_inner()
-            }
-            Self::Symbol(ref obj) => {
-                let hash = obj.clone();
-                hash.into_inner()
+        // Only the property is copied, the object is borrowed.
+        let prototype = match *self {
+            Self::Object(ref obj) | Self::Symbol(ref obj) => {
+                let obj = obj.borrow();
+                if let Some(prop) = obj.properties.get(field) {
+                    return Some(prop.into_owned());
+                }
+                if let Some(prop) = obj.string_get_own_property(field) {
+                    return Some(prop);
+                }
+                obj.internal_slots.get(INSTANCE_PROTOTYPE).cloned()
             }
             _ => return None,
         };
 
-        match obj.properties.get(field) {
-            Some(val) => Some(val.clone()),
-            None => match obj.internal_slots.get(&INSTANCE_PROTOTYPE.to_string()) {
-                Some(value) => value.get_property(field),
-                None => None,
-            },
-        }
+        prototype.and_then(|prototype| prototype.get_property(field))
     }
 
     /// update_prop will overwrite individual [Property] fields, unlike
@@ -439,11 +502,12 @@ impl ValueData {
 
         if let Some(mut obj_data) = obj {
             // Use value, or walk up the prototype chain
-            if let Some(ref mut prop) = obj_data.properties.get_mut(field) {
+            if let Some(mut prop) = obj_data.properties.get(field).map(Cow::into_owned) {
                 prop.value = value;
                 prop.enumerable = enumerable;
                 prop.writable = writable;
                 prop.configurable = configurable;
+                obj_data.properties.insert(field.to_owned(), prop);
             }
         }
     }
@@ -453,60 +517,130 @@ impl ValueData {
     /// Returns a copy of the Property.
     pub fn get_internal_slot(&self, field: &str) -> Value {
         let _timer = BoaProfiler::global().start_event("Value::get_internal_slot", "value");
-        let obj: Object = match *self {
-            Self::Object(ref obj) => {
-                let hash = obj.clone();
-                hash.into_inner()
-            }
-            Self::Symbol(ref obj) => {
-                let hash = obj.clone();
-                hash.into_inner()
-            }
-            _ => return Value::undefined(),
-        };
-
-        match obj.internal_slots.get(field) {
-            Some(val) => val.clone(),
-            None => Value::undefined(),
+        match *self {
+            Self::Object(ref obj) | Self::Symbol(ref obj) => obj
+                .borrow()
+                .internal_slots
+                .get(field)
+                .cloned()
+                .unwrap_or_else(Value::undefined),
+            _ => Value::undefined(),
         }
     }
 
     /// Resolve the property in the object and get its value, or undefined if this is not an object or the field doesn't exist
-    /// get_field recieves a Property from get_prop(). It should then return the [[Get]] result value if that's set, otherwise fall back to [[Value]]
-    /// TODO: this function should use the get Value if its set
+    ///
+    /// The getters of the accessor properties aren't called, as they need an interpreter, so the
+    /// value of an accessor property is undefined. The interpreter uses `lookup_field` instead.
     pub fn get_field<F>(&self, field: F) -> Value
     where
         F: Into<Value>,
     {
+        match self.lookup_field(field.into()) {
+            Field::Data(value) => value,
+            Field::Accessor(_) => Value::undefined(),
+        }
+    }
+
+    /// Resolve the property in the object, like `get_field`, and get either its value or, if it
+    /// is an accessor property, the property itself, so that its getter can be called.
+    pub(crate) fn lookup_field(&self, field: Value) -> Field {
         let _timer = BoaProfiler::global().start_event("Value::get_field", "value");
-        match *field.into() {
-            // Our field will either be a String or a Symbol
-            Self::String(ref s) => {
-                match self.get_property(s) {
-                    Some(prop) => {
-                        // If the Property has [[Get]] set to a function, we should run that and return the Value
-                        let prop_getter = match prop.get {
-                            Some(_) => None,
-                            None => None,
-                        };
-
-                        // If the getter is populated, use that. If not use [[Value]] instead
-                        if let Some(val) = prop_getter {
-                            val
-                        } else {
-                            let val = prop
-                                .value
-                                .as_ref()
-                                .expect("Could not get property as reference");
-                            val.clone()
+        if let Some(index) = field.as_array_index() {
+            if let Self::Object(ref obj) = *self {
+                if let Some(value) = obj.borrow().properties.element(index) {
+                    return Field::Data(value.clone());
+                }
+            }
+        }
+
+        let key = match *field {
+            Self::String(ref s) => s.to_string_lossy(),
+            Self::Symbol(_) => {
+                return Field::Data(match *self {
+                    Self::Object(ref obj) => obj.borrow().get(&field),
+                    _ => Value::undefined(),
+                });
+            }
+            _ => Cow::Owned(field.to_string()),
+        };
+        match self.get_property(&key) {
+            Some(prop) if prop.is_accessor_property() => Field::Accessor(prop),
+            Some(prop) => Field::Data(prop.value.clone().unwrap_or_default()),
+            None => Field::Data(Value::undefined()),
+        }
+    }
+
+    /// Finds the accessor property of a string keyed field, in the object or its prototype chain.
+    ///
+    /// Returns `None` if the field is a data property, or if there is no such field.
+    pub(crate) fn find_accessor(&self, field: &str) -> Option<Property> {
+        let prototype = match *self {
+            Self::Object(ref obj) => {
+                let obj = obj.borrow();
+                if let Some(prop) = obj.properties.get(field) {
+                    return if prop.is_accessor_property() {
+                        Some(prop.into_owned())
+                    } else {
+                        None
+                    };
+                }
+                obj.internal_slots.get(INSTANCE_PROTOTYPE).cloned()
+            }
+            _ => return None,
+        };
+        prototype.and_then(|prototype| prototype.find_accessor(field))
+    }
+
+    /// Gets the array index that a number is, if any.
+    ///
+    /// Only numbers are considered, as the strings that are array indices are recognized by the
+    /// property maps.
+    pub(crate) fn as_array_index(&self) -> Option<usize> {
+        let index = match *self {
+            Self::Integer(index) => f64::from(index),
+            Self::Rational(index) => index,
+            _ => return None,
+        };
+
+        if index >= 0.0 && index <= MAX_ARRAY_INDEX as f64 && index.fract() == 0.0 {
+            Some(index as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Resolve a string keyed field, using an inline cache for the own properties of objects.
+    ///
+    /// It behaves like `lookup_field`, but takes the data property straight from its slot when
+    /// the shape of the object was seen before, and remembers the slot otherwise.
+    pub(crate) fn lookup_field_cached(&self, field: &str, cache: &PropertyCache) -> Field {
+        if let Self::Object(ref obj) = *self {
+            let obj = obj.borrow();
+            if let Some(shape) = obj.properties.shape() {
+                match cache.get(shape) {
+                    Some(slot) => {
+                        let value = obj
+                            .properties
+                            .slot(slot)
+                            .filter(|p| !p.is_accessor_property())
+                            .and_then(|p| p.value.clone());
+                        if let Some(value) = value {
+                            BoaProfiler::global().record_load(true);
+                            return Field::Data(value);
+                        }
+                    }
+                    None => {
+                        if let Some(slot) = shape.lookup(field) {
+                            cache.insert(shape, slot);
                         }
                     }
-                    None => Value::undefined(),
                 }
             }
-            Self::Symbol(_) => unimplemented!(),
-            _ => Value::undefined(),
         }
+
+        BoaProfiler::global().record_load(false);
+        self.lookup_field(Value::from(field))
     }
 
     /// Check whether an object has an internal state set.
@@ -593,14 +727,9 @@ impl ValueData {
         let val = val.into();
 
         if let Self::Object(ref obj) = *self {
-            if obj.borrow().kind == ObjectKind::Array {
-                if let Ok(num) = field.to_string().parse::<usize>() {
-                    if num > 0 {
-                        let len = i32::from(&self.get_field("length"));
-                        if len < (num + 1) as i32 {
-                            self.set_field("length", Value::from(num + 1));
-                        }
-                    }
+            if let Some(index) = field.as_array_index() {
+                if obj.borrow_mut().set_element(index, val.clone()) {
+                    return val;
                 }
             }
 
@@ -616,6 +745,52 @@ impl ValueData {
         val
     }
 
+    /// Set the value of a string keyed field, using an inline cache for the own properties of
+    /// objects.
+    ///
+    /// Writable data properties found through the cache are overwritten in place, anything else
+    /// goes through `set_field`, after which the slot of the property is remembered.
+    ///
+    /// If the field is an accessor property, of the object or of its prototype chain, nothing is
+    /// set, and the property is returned, so that its setter can be called.
+    pub(crate) fn set_field_cached(
+        &self,
+        field: &str,
+        val: Value,
+        cache: &PropertyCache,
+    ) -> Option<Property> {
+        if let Self::Object(ref obj) = *self {
+            let mut obj = obj.borrow_mut();
+            if let Some(slot) = obj.properties.shape().and_then(|shape| cache.get(shape)) {
+                if let Some(prop) = obj.properties.slot_mut(slot) {
+                    if let Some(ref mut value) = prop.value {
+                        if prop.writable == Some(true) {
+                            *value = val;
+                            BoaProfiler::global().record_store(true);
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+
+        BoaProfiler::global().record_store(false);
+        if let Some(accessor) = self.find_accessor(field) {
+            return Some(accessor);
+        }
+        self.set_field(field, val);
+
+        if let Self::Object(ref obj) = *self {
+            let obj = obj.borrow();
+            if let Some(shape) = obj.properties.shape() {
+                if let Some(slot) = shape.lookup(field) {
+                    cache.insert(shape, slot);
+                }
+            }
+        }
+        None
+    }
+
     /// Set the private field in the value
     pub fn set_internal_slot(&self, field: &str, val: Value) -> Value {
         let _timer = BoaProfiler::global().start_event("Value::set_internal_slot", "exec");
@@ -677,7 +852,7 @@ impl ValueData {
             JSONValue::Number(v) => {
                 Self::Rational(v.as_f64().expect("Could not convert value to f64"))
             }
-            JSONValue::String(v) => Self::String(v),
+            JSONValue::String(v) => Self::String(v.into()),
             JSONValue::Bool(v) => Self::Boolean(v),
             JSONValue::Array(vs) => {
                 let mut new_obj = Object::default();
@@ -687,12 +862,17 @@ impl ValueData {
                         Property::default()
                             .value(Value::from(json.clone()))
                             .writable(true)
+                            .enumerable(true)
                             .configurable(true),
                     );
                 }
                 new_obj.properties.insert(
                     "length".to_string(),
-                    Property::default().value(Value::from(vs.len())),
+                    Property::default()
+                        .value(Value::from(vs.len()))
+                        .writable(true)
+                        .enumerable(false)
+                        .configurable(false),
                 );
                 Self::Object(Box::new(GcCell::new(new_obj)))
             }
@@ -704,6 +884,7 @@ impl ValueData {
                         Property::default()
                             .value(Value::from(json.clone()))
                             .writable(true)
+                            .enumerable(true)
                             .configurable(true),
                     );
                 }
@@ -735,17 +916,23 @@ impl ValueData {
                     JSONValue::Array(arr)
                 } else {
                     let mut new_obj = Map::new();
-                    obj.borrow().properties.keys().for_each(|k| {
-                        let key = k.clone();
-                        let value = self.get_field(k.to_string());
+                    let keys: Vec<String> = obj
+                        .borrow()
+                        .properties
+                        .iter()
+                        .filter(|(_, property)| property.enumerable == Some(true))
+                        .map(|(key, _)| key.into_owned())
+                        .collect();
+                    for key in keys {
+                        let value = self.get_field(key.as_str());
                         if !value.is_undefined() && !value.is_function() {
                             new_obj.insert(key, value.to_json());
                         }
-                    });
+                    }
                     JSONValue::Object(new_obj)
                 }
             }
-            Self::String(ref str) => JSONValue::String(str.clone()),
+            Self::String(ref str) => JSONValue::String(str.to_string()),
             Self::Rational(num) => JSONValue::Number(
                 JSONNumber::from_f64(num).expect("Could not convert to JSONNumber"),
             ),
@@ -784,6 +971,14 @@ impl ValueData {
     }
 }
 
+impl Default for Value {
+    fn default() -> Self {
+        Self::undefined()
+    }
+}
+
+impl Finalize for ValueData {}
+
 impl Default for ValueData {
     fn default() -> Self {
         Self::Undefined
@@ -4,13 +4,16 @@ impl Add for Value {
     type Output = Self;
     fn add(self, other: Self) -> Self {
         match (self.data(), other.data()) {
+            (ValueData::String(ref s), ValueData::String(ref o)) => Self::string(s.concat(o)),
             (ValueData::String(ref s), ref o) => {
-                Self::string(format!("{}{}", s.clone(), &o.to_string()))
+                Self::string(s.concat(&JsString::from(o.to_string())))
             }
             (ValueData::BigInt(ref n1), ValueData::BigInt(ref n2)) => {
                 Self::bigint(n1.clone() + n2.clone())
             }
-            (ref s, ValueData::String(ref o)) => Self::string(format!("{}{}", s.to_string(), o)),
+            (ref s, ValueData::String(ref o)) => {
+                Self::string(JsString::from(s.to_string()).concat(o))
+            }
             (ref s, ref o) => Self::rational(s.to_number() + o.to_number()),
         }
     }
@@ -130,10 +133,7 @@ impl Neg for Value {
             ValueData::Object(_) | ValueData::Symbol(_) | ValueData::Undefined => {
                 Self::rational(NAN)
             }
-            ValueData::String(ref str) => Self::rational(match f64::from_str(str) {
-                Ok(num) => -num,
-                Err(_) => NAN,
-            }),
+            ValueData::String(ref str) => Self::rational(-string_to_number(&str.to_string_lossy())),
             ValueData::Rational(num) => Self::rational(-num),
             ValueData::Integer(num) => Self::rational(-f64::from(*num)),
             ValueData::Boolean(true) => Self::integer(1),
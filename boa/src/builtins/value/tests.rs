@@ -92,3 +92,291 @@ fn abstract_equality_comparison() {
     assert_eq!(forward(&mut engine, "'foo' == NaN"), "false");
     assert_eq!(forward(&mut engine, "NaN == NaN"), "false");
 }
+
+#[test]
+fn strict_equality_comparison() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let obj = {};
+        let func = function () {};
+        let sym = Symbol();
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "obj === obj"), "true");
+    assert_eq!(forward(&mut engine, "obj === {}"), "false");
+    assert_eq!(forward(&mut engine, "func === func"), "true");
+    assert_eq!(forward(&mut engine, "sym === sym"), "true");
+    assert_eq!(forward(&mut engine, "sym === Symbol()"), "false");
+    assert_eq!(forward(&mut engine, "null === null"), "true");
+    assert_eq!(forward(&mut engine, "null === undefined"), "false");
+    assert_eq!(forward(&mut engine, "'foo' === 'foo'"), "true");
+    assert_eq!(forward(&mut engine, "1 === 1.0"), "true");
+    assert_eq!(forward(&mut engine, "0 === -0"), "true");
+    assert_eq!(forward(&mut engine, "NaN === NaN"), "false");
+    assert_eq!(forward(&mut engine, "1 === '1'"), "false");
+}
+
+#[test]
+fn same_value_comparisons() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let obj = {};
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "Object.is(NaN, NaN)"), "true");
+    assert_eq!(forward(&mut engine, "Object.is(0, -0)"), "false");
+    assert_eq!(forward(&mut engine, "Object.is(-0, -0)"), "true");
+    assert_eq!(forward(&mut engine, "Object.is(1, 1.0)"), "true");
+    assert_eq!(forward(&mut engine, "Object.is(obj, obj)"), "true");
+    assert_eq!(forward(&mut engine, "Object.is(obj, {})"), "false");
+    assert_eq!(forward(&mut engine, "Object.is()"), "true");
+
+    assert_eq!(forward(&mut engine, "[NaN].includes(NaN)"), "true");
+    assert_eq!(forward(&mut engine, "[-0].includes(0)"), "true");
+    assert_eq!(forward(&mut engine, "[obj].includes(obj)"), "true");
+    assert_eq!(forward(&mut engine, "[NaN].indexOf(NaN)"), "-1");
+    assert_eq!(forward(&mut engine, "[obj].indexOf(obj)"), "0");
+}
+
+#[test]
+fn string_to_number_conversion() {
+    assert_eq!(string_to_number(""), 0.0);
+    assert_eq!(string_to_number(" \n 12 \t"), 12.0);
+    assert_eq!(string_to_number("-1.5e3"), -1500.0);
+    assert_eq!(string_to_number(".5"), 0.5);
+    assert_eq!(string_to_number("0x10"), 16.0);
+    assert_eq!(string_to_number("0o17"), 15.0);
+    assert_eq!(string_to_number("0B101"), 5.0);
+    assert_eq!(string_to_number("-Infinity"), f64::NEG_INFINITY);
+    assert!(string_to_number("0x").is_nan());
+    assert!(string_to_number("-0x10").is_nan());
+    assert!(string_to_number("abc").is_nan());
+    assert!(string_to_number("1px").is_nan());
+    assert!(string_to_number("inf").is_nan());
+    assert!(string_to_number("NaN").is_nan());
+}
+
+#[test]
+fn int32_conversions() {
+    assert_eq!(f64_to_int32(2_147_483_648.0), -2_147_483_648);
+    assert_eq!(f64_to_int32(4_294_967_297.5), 1);
+    assert_eq!(f64_to_int32(-1.9), -1);
+    assert_eq!(f64_to_int32(f64::NAN), 0);
+    assert_eq!(f64_to_uint32(-1.0), 4_294_967_295);
+    assert_eq!(f64_to_uint32(f64::INFINITY), 0);
+}
+
+#[test]
+fn to_primitive_conversions() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let calls = [];
+        let both = {
+            valueOf() { calls.push("valueOf"); return 2; },
+            toString() { calls.push("toString"); return "str"; }
+        };
+        let hints = [];
+        let exotic = {};
+        exotic[Symbol.toPrimitive] = function(hint) {
+            hints.push(hint);
+            if (hint === "number") {
+                return 42;
+            }
+            return "exotic";
+        };
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(forward(&mut engine, "1 + both"), "3");
+    assert_eq!(forward(&mut engine, "String(both)"), "str");
+    assert_eq!(forward(&mut engine, "calls.join()"), "valueOf,toString");
+    assert_eq!(forward(&mut engine, "both * 3"), "6");
+
+    assert_eq!(forward(&mut engine, "exotic * 1"), "42");
+    assert_eq!(forward(&mut engine, "exotic + ''"), "exotic");
+    assert_eq!(forward(&mut engine, "String(exotic)"), "exotic");
+    assert_eq!(
+        forward(&mut engine, "hints.join()"),
+        "number,default,string"
+    );
+
+    assert_eq!(forward(&mut engine, "[1, 2] + ''"), "1,2");
+}
+
+#[test]
+fn to_primitive_hint_ordering() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let log = [];
+        function tracked(name, value) {
+            let o = {};
+            o[Symbol.toPrimitive] = function(hint) {
+                log.push(name + ":" + hint);
+                return value;
+            };
+            return o;
+        }
+        let a = tracked("a", 1);
+        let b = tracked("b", "key");
+        function result(value) {
+            let calls = log.join();
+            log = [];
+            return value + " " + calls;
+        }
+        "#;
+    forward(&mut engine, init);
+
+    assert_eq!(
+        forward(&mut engine, "result(a + b)"),
+        "1key a:default,b:default"
+    );
+    assert_eq!(
+        forward(&mut engine, "result(a < b)"),
+        "false a:number,b:number"
+    );
+    assert_eq!(
+        forward(&mut engine, "result(a > b)"),
+        "false a:number,b:number"
+    );
+    assert_eq!(
+        forward(&mut engine, "result(b >= a)"),
+        "false b:number,a:number"
+    );
+    assert_eq!(forward(&mut engine, "result(a == 1)"), "true a:default");
+    assert_eq!(forward(&mut engine, "result(a - 0)"), "1 a:number");
+    assert_eq!(forward(&mut engine, "result(String(b))"), "key b:string");
+
+    // Computed member keys are converted with the string hint.
+    assert_eq!(
+        forward(&mut engine, "let o = {}; o[b] = 1; result(o.key)"),
+        "1 b:string"
+    );
+    assert_eq!(forward(&mut engine, "result(o[b])"), "1 b:string");
+    assert_eq!(
+        forward(&mut engine, "o[b] += 2; result(o.key)"),
+        "3 b:string"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "o.key = function() { return 'called'; }; result(o[b]())"
+        ),
+        "called b:string"
+    );
+}
+
+#[test]
+fn to_number_conversions() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "'0x10' - 0"), "16");
+    assert_eq!(forward(&mut engine, "' 12 ' * 1"), "12");
+    assert_eq!(forward(&mut engine, "'abc' - 1"), "NaN");
+    assert_eq!(forward(&mut engine, "'' * 1"), "0");
+    assert_eq!(forward(&mut engine, "+'Infinity'"), "Infinity");
+    assert_eq!(forward(&mut engine, "+'inf'"), "NaN");
+    assert_eq!(forward(&mut engine, "-'5'"), "-5");
+    assert_eq!(forward(&mut engine, "null + 1"), "1");
+    assert_eq!(forward(&mut engine, "undefined + 1"), "NaN");
+    assert_eq!(forward(&mut engine, "true + true"), "2");
+    assert_eq!(forward(&mut engine, "Number('  0b11  ')"), "3");
+    assert_eq!(forward(&mut engine, "Math.max(1, NaN, 3)"), "NaN");
+    assert_eq!(
+        forward(&mut engine, "Math.abs({ valueOf() { return -2; } })"),
+        "2"
+    );
+}
+
+#[test]
+fn numeric_operators() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "-1 >>> 0"), "4294967295");
+    assert_eq!(forward(&mut engine, "-16 >> 2"), "-4");
+    assert_eq!(forward(&mut engine, "1 << 33"), "2");
+    assert_eq!(forward(&mut engine, "2147483648 | 0"), "-2147483648");
+    assert_eq!(forward(&mut engine, "~'3'"), "-4");
+    assert_eq!(forward(&mut engine, "let a = -16; a >>= 2; a"), "-4");
+    assert_eq!(forward(&mut engine, "1 ** NaN"), "NaN");
+    assert_eq!(forward(&mut engine, "'10' < '9'"), "true");
+    assert_eq!(forward(&mut engine, "'10' < 9"), "false");
+    assert_eq!(forward(&mut engine, "NaN <= NaN"), "false");
+    assert_eq!(forward(&mut engine, "1n < 2"), "true");
+    assert_eq!(forward(&mut engine, "let b = '5'; b++; b"), "6");
+}
+
+#[test]
+fn conversion_errors_are_catchable() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        let bad = {};
+        bad[Symbol.toPrimitive] = function() { return {}; };
+        "#;
+    forward(&mut engine, init);
+    let catch = |src: &str| {
+        format!(
+            "try {{ {}; 'no error' }} catch (e) {{ e.constructor === TypeError }}",
+            src
+        )
+    };
+
+    assert_eq!(forward(&mut engine, &catch("Symbol() + ''")), "true");
+    assert_eq!(forward(&mut engine, &catch("+Symbol()")), "true");
+    assert_eq!(forward(&mut engine, &catch("1n + 1")), "true");
+    assert_eq!(forward(&mut engine, &catch("undefined.x")), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            &catch("({ valueOf: null, toString: null }) + 1")
+        ),
+        "true"
+    );
+    assert_eq!(forward(&mut engine, &catch("bad + 1")), "true");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { ({ valueOf() { throw 'oops'; } }) * 1 } catch (e) { e }"
+        ),
+        "oops"
+    );
+}
+
+#[test]
+fn primitives_are_converted_to_objects() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(forward(&mut engine, "(5).toString()"), "5");
+    assert_eq!(forward(&mut engine, "(255).toString(16)"), "ff");
+    assert_eq!(forward(&mut engine, "true.toString()"), "true");
+    assert_eq!(forward(&mut engine, "'abc'.length"), "3");
+}
+
+#[test]
+fn primitives_are_immediate() {
+    let before = heap::stats().values;
+    let immediates = [
+        Value::undefined(),
+        Value::null(),
+        Value::boolean(true),
+        Value::integer(42),
+        Value::rational(1.5),
+    ];
+    assert!(immediates.iter().all(Value::is_immediate));
+    assert_eq!(heap::stats().values, before);
+    drop(immediates.clone());
+    drop(immediates);
+    assert_eq!(heap::stats().values, before);
+
+    assert!(!Value::string("heap").is_immediate());
+    assert!(!Value::new_object(None).is_immediate());
+}
@@ -0,0 +1,91 @@
+//! The clocks of the host, and the `Clock` trait through which an embedder can replace the time
+//! seen by the scripts.
+//!
+//! The standard library has no clock on `wasm32-unknown-unknown`, where `SystemTime::now` and
+//! `Instant::now` panic, so the time is read from the `Date` object of the JavaScript host there.
+
+use std::{fmt::Debug, time::Duration};
+
+/// A clock, set with `Interpreter::set_clock`, which the scripts read the current time from
+/// instead of the clock of the host.
+///
+/// The durations measured by the engine itself, like the profiled calls, still use the clock of
+/// the host.
+pub trait Clock: Debug {
+    /// Gets the number of milliseconds elapsed since the Unix epoch.
+    fn now(&mut self) -> f64;
+}
+
+/// A clock which is stopped at a given time, in milliseconds since the Unix epoch, so that the
+/// scripts run the same way whenever they run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock(pub f64);
+
+impl Clock for FixedClock {
+    fn now(&mut self) -> f64 {
+        self.0
+    }
+}
+
+/// Gets the number of milliseconds elapsed since the Unix epoch.
+pub(crate) fn now() -> f64 {
+    imp::now()
+}
+
+/// A point in time, used to measure how long something takes.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Instant(imp::Instant);
+
+impl Instant {
+    /// Gets the current point in time.
+    pub(crate) fn now() -> Self {
+        Self(imp::instant())
+    }
+
+    /// Gets the time elapsed since this point in time.
+    pub(crate) fn elapsed(self) -> Duration {
+        imp::elapsed(self.0)
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod imp {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    pub(super) type Instant = std::time::Instant;
+
+    pub(super) fn now() -> f64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+
+    pub(super) fn instant() -> Instant {
+        Instant::now()
+    }
+
+    pub(super) fn elapsed(start: Instant) -> Duration {
+        start.elapsed()
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod imp {
+    use std::time::Duration;
+
+    /// The number of milliseconds since the Unix epoch, as `Date` has no monotonic clock.
+    pub(super) type Instant = f64;
+
+    pub(super) fn now() -> f64 {
+        js_sys::Date::now()
+    }
+
+    pub(super) fn instant() -> Instant {
+        now()
+    }
+
+    pub(super) fn elapsed(start: Instant) -> Duration {
+        Duration::from_secs_f64((now() - start).max(0.0) / 1000.0)
+    }
+}
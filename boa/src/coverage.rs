@@ -0,0 +1,215 @@
+//! Code coverage, which counts how many times the statements and the branches of the scripts run.
+//!
+//! Counting is started with `Interpreter::enable_coverage`, and the counts are collected with
+//! `Interpreter::take_coverage`. The statements are the ones of the parsed statement lists, keyed
+//! by the position where they start, like the statements seen by a
+//! [`Debugger`](../debugger/trait.Debugger.html).
+//!
+//! The branches are the ones of the `if` statements. A branch point is keyed by the statement
+//! containing the `if`, and by the number of `if` branches between that statement and the `if`,
+//! which tells apart the `if` statements of an `else if` chain.
+//!
+//! The interpreter only sees the statements which run, so the ones which never ran are added by
+//! `Coverage::add_script`, with a count of 0.
+
+use crate::{
+    exec::Interpreter,
+    syntax::ast::{
+        node::{ArrowFunctionDecl, Block, FunctionDecl, FunctionExpr, If, StatementList},
+        visitor::Visitor,
+        Position,
+    },
+};
+use std::{collections::BTreeMap, mem};
+
+/// An `if` statement, whose branches are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BranchPoint {
+    /// The position of the statement containing the `if`, which is the `if` itself unless it is
+    /// nested in the branch of another `if`.
+    pub statement: Position,
+    /// The number of `if` branches between the statement and the `if`.
+    pub nesting: u32,
+}
+
+/// The number of times each statement and branch ran.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    /// The count of each statement, by the position where it starts.
+    statements: BTreeMap<Position, u32>,
+    /// The counts of the `if` branch and of the `else` branch of each branch point.
+    branches: BTreeMap<BranchPoint, [u32; 2]>,
+    /// The running statement, which is `None` if it isn't in a parsed statement list.
+    statement: Option<Position>,
+    /// The number of `if` branches entered since the start of the running statement.
+    nesting: u32,
+}
+
+impl Coverage {
+    /// Gets the statements, sorted by their position, with the number of times they ran.
+    pub fn statements(&self) -> impl Iterator<Item = (Position, u32)> + '_ {
+        self.statements
+            .iter()
+            .map(|(&position, &count)| (position, count))
+    }
+
+    /// Gets the branch points, sorted by their statement, with the number of times their `if`
+    /// branch and their `else` branch ran.
+    pub fn branches(&self) -> impl Iterator<Item = (BranchPoint, [u32; 2])> + '_ {
+        self.branches
+            .iter()
+            .map(|(&point, &counts)| (point, counts))
+    }
+
+    /// Adds the statements and the branch points of a script which didn't run, with a count of 0,
+    /// so that the coverage has all of them.
+    pub fn add_script(&mut self, script: &StatementList) {
+        let mut collector = Collector {
+            coverage: self,
+            statement: None,
+            nesting: 0,
+        };
+        collector.visit_statement_list(script);
+    }
+}
+
+/// Visits a script to add its statements and branch points to the coverage, keyed like the
+/// interpreter keys them.
+#[derive(Debug)]
+struct Collector<'a> {
+    coverage: &'a mut Coverage,
+    statement: Option<Position>,
+    nesting: u32,
+}
+
+impl<'ast> Visitor<'ast> for Collector<'_> {
+    fn visit_statement_list(&mut self, statement_list: &'ast StatementList) {
+        let outer = (self.statement, self.nesting);
+        for (index, statement) in statement_list.statements().iter().enumerate() {
+            self.statement = statement_list.position(index);
+            self.nesting = 0;
+            if let Some(position) = self.statement {
+                self.coverage.statements.entry(position).or_insert(0);
+            }
+            self.visit_node(statement);
+        }
+        self.statement = outer.0;
+        self.nesting = outer.1;
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.visit_statement_list(block.statement_list());
+    }
+
+    fn visit_function_decl(&mut self, function_decl: &'ast FunctionDecl) {
+        for parameter in function_decl.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.visit_statement_list(function_decl.statement_list());
+    }
+
+    fn visit_function_expr(&mut self, function_expr: &'ast FunctionExpr) {
+        for parameter in function_expr.parameters() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.visit_statement_list(function_expr.statement_list());
+    }
+
+    fn visit_arrow_function_decl(&mut self, arrow_function_decl: &'ast ArrowFunctionDecl) {
+        for parameter in arrow_function_decl.params() {
+            self.visit_formal_parameter(parameter);
+        }
+        self.visit_statement_list(arrow_function_decl.statement_list());
+    }
+
+    fn visit_if(&mut self, if_stmt: &'ast If) {
+        self.visit_node(if_stmt.cond());
+        if let Some(statement) = self.statement {
+            let point = BranchPoint {
+                statement,
+                nesting: self.nesting,
+            };
+            self.coverage.branches.entry(point).or_insert([0, 0]);
+        }
+
+        self.nesting += 1;
+        self.visit_node(if_stmt.body());
+        if let Some(node) = if_stmt.else_node() {
+            self.visit_node(node);
+        }
+        self.nesting -= 1;
+    }
+}
+
+/// The statement running when a statement list is entered, restored when it is left.
+pub(crate) type CoverageState = Option<(Option<Position>, u32)>;
+
+impl Interpreter {
+    /// Starts counting the statements and the branches which run, if it isn't counting them
+    /// already.
+    pub fn enable_coverage(&mut self) {
+        if self.coverage.is_none() {
+            self.coverage = Some(Coverage::default());
+        }
+    }
+
+    /// Takes the coverage counted since it was enabled or last taken, and starts counting again
+    /// from 0. Returns `None` if the coverage isn't enabled.
+    pub fn take_coverage(&mut self) -> Option<Coverage> {
+        let coverage = self.coverage.as_mut()?;
+        let running = Coverage {
+            statement: coverage.statement,
+            nesting: coverage.nesting,
+            ..Coverage::default()
+        };
+        Some(mem::replace(coverage, running))
+    }
+
+    /// Gets the running statement, to restore it once a statement list is done.
+    pub(crate) fn coverage_state(&self) -> CoverageState {
+        self.coverage
+            .as_ref()
+            .map(|coverage| (coverage.statement, coverage.nesting))
+    }
+
+    /// Restores the running statement saved when a statement list was entered.
+    pub(crate) fn restore_coverage_state(&mut self, state: CoverageState) {
+        if let (Some(coverage), Some((statement, nesting))) = (self.coverage.as_mut(), state) {
+            coverage.statement = statement;
+            coverage.nesting = nesting;
+        }
+    }
+
+    /// Counts a statement of a statement list, which starts at the given position if it is known.
+    pub(crate) fn cover_statement(&mut self, position: Option<Position>) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.statement = position;
+            coverage.nesting = 0;
+            if let Some(position) = position {
+                *coverage.statements.entry(position).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Counts the branch of an `if` statement which runs, the `if` branch being 0 and the `else`
+    /// one 1, before running it.
+    pub(crate) fn cover_branch(&mut self, branch: usize) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            if let Some(statement) = coverage.statement {
+                let point = BranchPoint {
+                    statement,
+                    nesting: coverage.nesting,
+                };
+                coverage.branches.entry(point).or_insert([0, 0])[branch] += 1;
+            }
+            coverage.nesting += 1;
+        }
+    }
+
+    /// Leaves the branch of an `if` statement, once it ran.
+    pub(crate) fn leave_branch(&mut self) {
+        if let Some(coverage) = self.coverage.as_mut() {
+            coverage.nesting = coverage.nesting.saturating_sub(1);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Hooks for debuggers, which pause the scripts on their statements to inspect them.
+//!
+//! The interpreter calls the `Debugger` set with `Interpreter::set_debugger` before each statement
+//! of a parsed statement list, with the position where the statement starts. The script is paused
+//! for as long as the hook runs, so a debugger implements breakpoints and stepping by waiting in
+//! it, for example for the commands of a client. Meanwhile, it can list the scopes visible from
+//! the statement with `Interpreter::scopes`, and evaluate code in them with
+//! `Interpreter::evaluate`.
+//!
+//! The statements which aren't in a list, like the body of `if (a) f();` or of an arrow function
+//! without braces, are run as part of the statement containing them.
+
+use crate::{
+    builtins::value::{ResultValue, Value},
+    environment::lexical_environment::EnvironmentType,
+    exec::{Executable, Interpreter},
+    syntax::{ast::Position, lexer::Lexer, parser::Parser},
+};
+use std::fmt::Debug;
+
+/// A debugger, implemented by the host.
+pub trait Debugger: Debug {
+    /// Called before running the statement which starts at the given position.
+    ///
+    /// The debugger is taken out of the interpreter while this runs, so the code it evaluates
+    /// doesn't call it again.
+    fn on_statement(&mut self, interpreter: &mut Interpreter, position: Position);
+}
+
+/// A scope visible from the running code, with its bindings.
+#[derive(Debug, Clone)]
+pub struct Scope {
+    /// The type of the environment of the scope.
+    pub kind: EnvironmentType,
+    /// The names of the bindings of the scope, with their values.
+    pub bindings: Vec<(String, Value)>,
+}
+
+impl Interpreter {
+    /// Calls the debugger, if there is one, before running the statement which starts at the
+    /// given position.
+    pub(crate) fn on_statement(&mut self, position: Position) {
+        if let Some(mut debugger) = self.debugger.take() {
+            debugger.on_statement(self, position);
+            // Keep the debugger set by the hook, if it set one.
+            if self.debugger.is_none() {
+                self.debugger = Some(debugger);
+            }
+        }
+    }
+
+    /// Lists the scopes visible from the running code, from the innermost one to the global one.
+    ///
+    /// The scopes of the callers of the running function aren't visible, so they are skipped.
+    pub fn scopes(&self) -> Vec<Scope> {
+        let mut scopes = Vec::new();
+        let mut environments = self.realm.environment.environments();
+        for environment in &mut environments {
+            let environment = (**environment).borrow();
+            let kind = environment.get_environment_type();
+            scopes.push(Scope {
+                kind,
+                bindings: environment.bindings(),
+            });
+            if let EnvironmentType::Function | EnvironmentType::Global = kind {
+                break;
+            }
+        }
+        if let Some(global) = environments.last() {
+            let global = (**global).borrow();
+            scopes.push(Scope {
+                kind: global.get_environment_type(),
+                bindings: global.bindings(),
+            });
+        }
+        scopes
+    }
+
+    /// Evaluates code in the innermost scope of the running code, like the console of a debugger
+    /// paused on a statement, and returns its completion value.
+    ///
+    /// The identifiers aren't resolved ahead of time, so they are looked up in the scopes visible
+    /// from the running code. If the code doesn't parse, its syntax error is thrown as a string.
+    pub fn evaluate(&mut self, source: &str) -> ResultValue {
+        match Parser::from_lexer(Lexer::new(source.as_bytes())).parse_all() {
+            Ok(script) => script.run(self),
+            Err(e) => Err(Value::from(format!("SyntaxError: {}", e))),
+        }
+    }
+}
@@ -4,17 +4,29 @@
 //! `constant`, `let`, `class`, `module`, `import`, and/or function declarations.
 //! A declarative Environment Record binds the set of identifiers defined by the declarations contained within its scope.
 //! More info:  [ECMA-262 sec-declarative-environment-records](https://tc39.es/ecma262/#sec-declarative-environment-records)
+//!
+//! The bindings are stored in slots, in the order they are created. The scope analysis numbers the
+//! declarations of each scope in the same order, so the identifiers it resolves find their binding
+//! in its slot instead of looking up its name.
 
 use crate::{
     builtins::value::Value,
     environment::{
         environment_record_trait::EnvironmentRecordTrait,
+        function_environment_record::FunctionEnvironmentRecord,
         lexical_environment::{Environment, EnvironmentType},
     },
+    interner::Sym,
 };
 use gc::{Finalize, Trace};
 use rustc_hash::FxHashMap;
 
+/// The number of bindings up to which a binding is looked up by name by going through the slots.
+///
+/// Most scopes only declare a few bindings, for which comparing the names is faster than hashing
+/// them. The records with more bindings keep a map of the slot of each name.
+const MAX_SCANNED_BINDINGS: usize = 8;
+
 /// Declarative Bindings have a few properties for book keeping purposes, such as mutability (const vs let).
 /// Can it be deleted? and strict mode.
 ///
@@ -22,63 +34,156 @@ use rustc_hash::FxHashMap;
 /// From this point onwards, a binding is referring to one of these structures.
 #[derive(Trace, Finalize, Debug, Clone)]
 pub struct DeclarativeEnvironmentRecordBinding {
+    pub name: Sym,
     pub value: Option<Value>,
     pub can_delete: bool,
     pub mutable: bool,
     pub strict: bool,
 }
 
-/// A declarative Environment Record binds the set of identifiers defined by the
-/// declarations contained within its scope.
-#[derive(Debug, Trace, Finalize, Clone)]
-pub struct DeclarativeEnvironmentRecord {
-    pub env_rec: FxHashMap<String, DeclarativeEnvironmentRecordBinding>,
-    pub outer_env: Option<Environment>,
+/// The bindings of a declarative or function Environment Record, stored in slots.
+#[derive(Debug, Trace, Finalize, Clone, Default)]
+pub struct DeclarativeBindings {
+    slots: Vec<DeclarativeEnvironmentRecordBinding>,
+    /// The slot of each name, once there are more than `MAX_SCANNED_BINDINGS` bindings.
+    names: FxHashMap<Sym, usize>,
 }
 
-impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
-    fn has_binding(&self, name: &str) -> bool {
-        self.env_rec.contains_key(name)
+impl DeclarativeBindings {
+    /// Creates an empty set of bindings.
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+    /// Gets the number of bindings.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Checks if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Gets the slot of the binding with the given name.
+    pub fn slot_of(&self, name: Sym) -> Option<usize> {
+        if self.slots.len() > MAX_SCANNED_BINDINGS {
+            self.names.get(&name).copied()
+        } else {
+            self.slots.iter().position(|binding| binding.name == name)
         }
+    }
 
-        self.env_rec.insert(
-            name,
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: deletion,
-                mutable: true,
-                strict: false,
-            },
-        );
+    /// Gets the binding with the given name.
+    pub fn get(&self, name: Sym) -> Option<&DeclarativeEnvironmentRecordBinding> {
+        self.slot_of(name).map(|slot| &self.slots[slot])
+    }
+
+    /// Gets a mutable reference to the binding with the given name.
+    pub fn get_mut(&mut self, name: Sym) -> Option<&mut DeclarativeEnvironmentRecordBinding> {
+        let slot = self.slot_of(name)?;
+        Some(&mut self.slots[slot])
+    }
+
+    /// Gets the binding in the given slot, if it has the given name.
+    pub fn get_slot(&self, slot: usize, name: Sym) -> Option<&DeclarativeEnvironmentRecordBinding> {
+        self.slots.get(slot).filter(|binding| binding.name == name)
+    }
+
+    /// Gets a mutable reference to the binding in the given slot, if it has the given name.
+    pub fn get_slot_mut(
+        &mut self,
+        slot: usize,
+        name: Sym,
+    ) -> Option<&mut DeclarativeEnvironmentRecordBinding> {
+        self.slots
+            .get_mut(slot)
+            .filter(|binding| binding.name == name)
+    }
+
+    /// Checks if there is a binding with the given name.
+    pub fn contains(&self, name: Sym) -> bool {
+        self.slot_of(name).is_some()
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.env_rec.contains_key(&name) {
+    /// Adds a new binding in the next slot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there already is a binding with the same name.
+    pub fn insert(&mut self, binding: DeclarativeEnvironmentRecordBinding) {
+        if self.contains(binding.name) {
             // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
+            panic!("Identifier {} has already been declared", binding.name);
+        }
+
+        self.slots.push(binding);
+        match self.slots.len() {
+            len if len == MAX_SCANNED_BINDINGS + 1 => self.index_names(),
+            len if len > MAX_SCANNED_BINDINGS + 1 => {
+                self.names.insert(self.slots[len - 1].name, len - 1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Removes the binding with the given name, moving the bindings after it to the previous slot.
+    pub fn remove(&mut self, name: Sym) -> Option<DeclarativeEnvironmentRecordBinding> {
+        let slot = self.slot_of(name)?;
+        let binding = self.slots.remove(slot);
+        self.index_names();
+        Some(binding)
+    }
+
+    /// Removes all the bindings, keeping the allocated slots.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.names.clear();
+    }
+
+    /// Gets an iterator over the bindings, in the order of their slots.
+    pub fn iter(&self) -> impl Iterator<Item = &DeclarativeEnvironmentRecordBinding> {
+        self.slots.iter()
+    }
+
+    /// Builds the map of the slot of each name, if there are too many bindings to go through them.
+    fn index_names(&mut self) {
+        self.names.clear();
+        if self.slots.len() > MAX_SCANNED_BINDINGS {
+            self.names.extend(
+                self.slots
+                    .iter()
+                    .enumerate()
+                    .map(|(slot, binding)| (binding.name, slot)),
+            );
         }
+    }
 
-        self.env_rec.insert(
+    /// Creates a new but uninitialized mutable binding.
+    pub(crate) fn create_mutable_binding(&mut self, name: Sym, deletion: bool) {
+        self.insert(DeclarativeEnvironmentRecordBinding {
             name,
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: true,
-                mutable: false,
-                strict,
-            },
-        );
+            value: None,
+            can_delete: deletion,
+            mutable: true,
+            strict: false,
+        });
+    }
 
-        true
+    /// Creates a new but uninitialized immutable binding.
+    pub(crate) fn create_immutable_binding(&mut self, name: Sym, strict: bool) {
+        self.insert(DeclarativeEnvironmentRecordBinding {
+            name,
+            value: None,
+            can_delete: false,
+            mutable: false,
+            strict,
+        });
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
-        if let Some(ref mut record) = self.env_rec.get_mut(name) {
+    /// Sets the value of an existing but uninitialized binding.
+    pub(crate) fn initialize_binding(&mut self, name: Sym, value: Value) {
+        if let Some(record) = self.get_mut(name) {
             if record.value.is_none() {
                 record.value = Some(value);
             } else {
@@ -88,20 +193,22 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
+    /// Sets the value of a binding, creating it if it doesn't exist and `strict` is false.
     #[allow(clippy::else_if_without_else)]
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
-        if self.env_rec.get(name).is_none() {
+    pub(crate) fn set_mutable_binding(&mut self, name: Sym, value: Value, mut strict: bool) {
+        let record = if let Some(record) = self.get_mut(name) {
+            record
+        } else {
             if strict {
                 // TODO: change this when error handling comes into play
                 panic!("Reference Error: Cannot set mutable binding for {}", name);
             }
 
-            self.create_mutable_binding(name.to_owned(), true);
+            self.create_mutable_binding(name, true);
             self.initialize_binding(name, value);
             return;
-        }
+        };
 
-        let record: &mut DeclarativeEnvironmentRecordBinding = self.env_rec.get_mut(name).unwrap();
         if record.strict {
             strict = true
         }
@@ -118,8 +225,19 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
-    fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
-        if let Some(binding) = self.env_rec.get(name) {
+    /// Checks if a binding is initialized, which is the case of the bindings that don't exist.
+    pub(crate) fn is_initialized(&self, name: Sym) -> bool {
+        self.get(name).is_none_or(|binding| binding.value.is_some())
+    }
+
+    /// Checks if a binding is mutable, which is the case of the bindings that don't exist.
+    pub(crate) fn is_mutable(&self, name: Sym) -> bool {
+        self.get(name).is_none_or(|binding| binding.mutable)
+    }
+
+    /// Gets the value of an initialized binding.
+    pub(crate) fn get_binding_value(&self, name: Sym) -> Value {
+        if let Some(binding) = self.get(name) {
             binding
                 .value
                 .as_ref()
@@ -131,20 +249,72 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         }
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        match self.env_rec.get(name) {
-            Some(binding) => {
-                if binding.can_delete {
-                    self.env_rec.remove(name);
-                    true
-                } else {
-                    false
-                }
+    /// Deletes a binding, if it can be deleted.
+    pub(crate) fn delete_binding(&mut self, name: Sym) -> bool {
+        match self.get(name) {
+            Some(binding) if binding.can_delete => {
+                self.remove(name);
+                true
             }
-            None => false,
+            _ => false,
         }
     }
 
+    /// Gets the names and the values of the initialized bindings.
+    pub(crate) fn bindings(&self) -> Vec<(String, Value)> {
+        self.slots
+            .iter()
+            .filter_map(|binding| Some((binding.name.to_string(), binding.value.clone()?)))
+            .collect()
+    }
+}
+
+/// A declarative Environment Record binds the set of identifiers defined by the
+/// declarations contained within its scope.
+#[derive(Debug, Trace, Finalize, Clone)]
+pub struct DeclarativeEnvironmentRecord {
+    pub env_rec: DeclarativeBindings,
+    pub outer_env: Option<Environment>,
+}
+
+impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
+    fn has_binding(&self, name: Sym) -> bool {
+        self.env_rec.contains(name)
+    }
+
+    fn create_mutable_binding(&mut self, name: Sym, deletion: bool) {
+        self.env_rec.create_mutable_binding(name, deletion)
+    }
+
+    fn create_immutable_binding(&mut self, name: Sym, strict: bool) -> bool {
+        self.env_rec.create_immutable_binding(name, strict);
+        true
+    }
+
+    fn initialize_binding(&mut self, name: Sym, value: Value) {
+        self.env_rec.initialize_binding(name, value)
+    }
+
+    fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool) {
+        self.env_rec.set_mutable_binding(name, value, strict)
+    }
+
+    fn is_initialized(&self, name: Sym) -> bool {
+        self.env_rec.is_initialized(name)
+    }
+
+    fn is_mutable(&self, name: Sym) -> bool {
+        self.env_rec.is_mutable(name)
+    }
+
+    fn get_binding_value(&self, name: Sym, _strict: bool) -> Value {
+        self.env_rec.get_binding_value(name)
+    }
+
+    fn delete_binding(&mut self, name: Sym) -> bool {
+        self.env_rec.delete_binding(name)
+    }
+
     fn has_this_binding(&self) -> bool {
         false
     }
@@ -153,6 +323,10 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         Value::undefined()
     }
 
+    fn get_new_target(&self) -> Value {
+        Value::undefined()
+    }
+
     fn has_super_binding(&self) -> bool {
         false
     }
@@ -161,6 +335,22 @@ impl EnvironmentRecordTrait for DeclarativeEnvironmentRecord {
         Value::undefined()
     }
 
+    fn bindings(&self) -> Vec<(String, Value)> {
+        self.env_rec.bindings()
+    }
+
+    fn declarative_bindings(&self) -> Option<&DeclarativeBindings> {
+        Some(&self.env_rec)
+    }
+
+    fn declarative_bindings_mut(&mut self) -> Option<&mut DeclarativeBindings> {
+        Some(&mut self.env_rec)
+    }
+
+    fn as_function_record_mut(&mut self) -> Option<&mut FunctionEnvironmentRecord> {
+        None
+    }
+
     fn get_outer_environment(&self) -> Option<Environment> {
         self.outer_env.as_ref().cloned()
     }
@@ -10,7 +10,12 @@
 //!
 use crate::{
     builtins::value::Value,
-    environment::lexical_environment::{Environment, EnvironmentType},
+    environment::{
+        declarative_environment_record::DeclarativeBindings,
+        function_environment_record::FunctionEnvironmentRecord,
+        lexical_environment::{Environment, EnvironmentType},
+    },
+    interner::Sym,
 };
 use gc::{Finalize, Trace};
 use std::fmt::Debug;
@@ -19,42 +24,53 @@ use std::fmt::Debug;
 ///
 /// In the ECMAScript specification Environment Records are hierachical and have a base class with abstract methods.   
 /// In this implementation we have a trait which represents the behaviour of all `EnvironmentRecord` types.
+///
+/// Bindings are looked up by their interned name, so the records compare symbols instead of strings.
 pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Determine if an Environment Record has a binding for the String value N. Return true if it does and false if it does not.
-    fn has_binding(&self, name: &str) -> bool;
+    fn has_binding(&self, name: Sym) -> bool;
 
     /// Create a new but uninitialized mutable binding in an Environment Record. The String value N is the text of the bound name.
     /// If the Boolean argument deletion is true the binding may be subsequently deleted.
-    fn create_mutable_binding(&mut self, name: String, deletion: bool);
+    fn create_mutable_binding(&mut self, name: Sym, deletion: bool);
 
     /// Create a new but uninitialized immutable binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// If strict is true then attempts to set it after it has been initialized will always throw an exception,
     /// regardless of the strict mode setting of operations that reference that binding.
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool;
+    fn create_immutable_binding(&mut self, name: Sym, strict: bool) -> bool;
 
     /// Set the value of an already existing but uninitialized binding in an Environment Record.
     /// The String value N is the text of the bound name.
     /// V is the value for the binding and is a value of any ECMAScript language type.
-    fn initialize_binding(&mut self, name: &str, value: Value);
+    fn initialize_binding(&mut self, name: Sym, value: Value);
 
     /// Set the value of an already existing mutable binding in an Environment Record.
     /// The String value `name` is the text of the bound name.
     /// value is the `value` for the binding and may be a value of any ECMAScript language type. S is a Boolean flag.
     /// If `strict` is true and the binding cannot be set throw a TypeError exception.
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool);
+    fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool);
 
     /// Returns the value of an already existing binding from an Environment Record.
     /// The String value N is the text of the bound name.
     /// S is used to identify references originating in strict mode code or that
     /// otherwise require strict mode reference semantics.
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value;
+    fn get_binding_value(&self, name: Sym, strict: bool) -> Value;
+
+    /// Determine if an existing binding of an Environment Record is initialized.
+    /// The `let` and `const` bindings are created uninitialized when their scope is entered, and are
+    /// in their temporal dead zone until their declaration runs.
+    fn is_initialized(&self, name: Sym) -> bool;
+
+    /// Determine if an existing binding of an Environment Record is mutable.
+    /// The `const` bindings are immutable, and assigning to them throws a `TypeError`.
+    fn is_mutable(&self, name: Sym) -> bool;
 
     /// Delete a binding from an Environment Record.
     /// The String value name is the text of the bound name.
     /// If a binding for name exists, remove the binding and return true.
     /// If the binding exists but cannot be removed return false. If the binding does not exist return true.
-    fn delete_binding(&mut self, name: &str) -> bool;
+    fn delete_binding(&mut self, name: Sym) -> bool;
 
     /// Determine if an Environment Record establishes a this binding.
     /// Return true if it does and false if it does not.
@@ -63,6 +79,10 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Return the `this` binding from the environment
     fn get_this_binding(&self) -> Value;
 
+    /// Return the `new.target` value of the environment, which is `undefined` unless it is the
+    /// environment of a function called by `new`.
+    fn get_new_target(&self) -> Value;
+
     /// Determine if an Environment Record establishes a super method binding.
     /// Return true if it does and false if it does not.
     fn has_super_binding(&self) -> bool;
@@ -71,6 +91,22 @@ pub trait EnvironmentRecordTrait: Debug + Trace + Finalize {
     /// Otherwise, return undefined.
     fn with_base_object(&self) -> Value;
 
+    /// Gets the names and the values of the initialized bindings of an Environment Record, in no
+    /// particular order, for the hosts which reflect on the environments.
+    fn bindings(&self) -> Vec<(String, Value)>;
+
+    /// Gets the bindings of an Environment Record which stores them in slots, where the
+    /// identifiers resolved by the scope analysis find them.
+    fn declarative_bindings(&self) -> Option<&DeclarativeBindings>;
+
+    /// Gets a mutable reference to the bindings of an Environment Record which stores them in
+    /// slots.
+    fn declarative_bindings_mut(&mut self) -> Option<&mut DeclarativeBindings>;
+
+    /// Gets a mutable reference to a function Environment Record, or `None` for the other kinds
+    /// of Environment Records.
+    fn as_function_record_mut(&mut self) -> Option<&mut FunctionEnvironmentRecord>;
+
     /// Get the next environment up
     fn get_outer_environment(&self) -> Option<Environment>;
 
@@ -11,13 +11,13 @@
 use crate::{
     builtins::value::Value,
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecordBinding,
+        declarative_environment_record::DeclarativeBindings,
         environment_record_trait::EnvironmentRecordTrait,
         lexical_environment::{Environment, EnvironmentType},
     },
+    interner::Sym,
 };
 use gc::{Finalize, Trace};
-use rustc_hash::FxHashMap;
 
 /// Different binding status for `this`.
 /// Usually set on a function environment record
@@ -34,7 +34,7 @@ pub enum BindingStatus {
 /// <https://tc39.es/ecma262/#table-16>
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct FunctionEnvironmentRecord {
-    pub env_rec: FxHashMap<String, DeclarativeEnvironmentRecordBinding>,
+    pub env_rec: DeclarativeBindings,
     /// This is the this value used for this invocation of the function.
     pub this_value: Value,
     /// If the value is "lexical", this is an ArrowFunction and does not have a local this value.
@@ -74,30 +74,27 @@ impl FunctionEnvironmentRecord {
             }
         }
     }
+
+    /// Removes the bindings and the values of the call, keeping the memory of the bindings for
+    /// the next call reusing this Environment Record.
+    pub(crate) fn clear(&mut self) {
+        self.env_rec.clear();
+        self.this_value = Value::undefined();
+        self.function = Value::undefined();
+        self.new_target = Value::undefined();
+        self.outer_env = None;
+    }
 }
 
 impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
     // TODO: get_super_base can't implement until GetPrototypeof is implemented on object
 
-    fn has_binding(&self, name: &str) -> bool {
-        self.env_rec.contains_key(name)
+    fn has_binding(&self, name: Sym) -> bool {
+        self.env_rec.contains(name)
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
-        }
-
-        self.env_rec.insert(
-            name,
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: deletion,
-                mutable: true,
-                strict: false,
-            },
-        );
+    fn create_mutable_binding(&mut self, name: Sym, deletion: bool) {
+        self.env_rec.create_mutable_binding(name, deletion)
     }
 
     fn get_this_binding(&self) -> Value {
@@ -115,93 +112,37 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         }
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.env_rec.contains_key(&name) {
-            // TODO: change this when error handling comes into play
-            panic!("Identifier {} has already been declared", name);
-        }
-
-        self.env_rec.insert(
-            name,
-            DeclarativeEnvironmentRecordBinding {
-                value: None,
-                can_delete: true,
-                mutable: false,
-                strict,
-            },
-        );
+    fn get_new_target(&self) -> Value {
+        self.new_target.clone()
+    }
 
+    fn create_immutable_binding(&mut self, name: Sym, strict: bool) -> bool {
+        self.env_rec.create_immutable_binding(name, strict);
         true
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
-        if let Some(ref mut record) = self.env_rec.get_mut(name) {
-            match record.value {
-                Some(_) => {
-                    // TODO: change this when error handling comes into play
-                    panic!("Identifier {} has already been defined", name);
-                }
-                None => record.value = Some(value),
-            }
-        }
+    fn initialize_binding(&mut self, name: Sym, value: Value) {
+        self.env_rec.initialize_binding(name, value)
     }
 
-    #[allow(clippy::else_if_without_else)]
-    fn set_mutable_binding(&mut self, name: &str, value: Value, mut strict: bool) {
-        if self.env_rec.get(name).is_none() {
-            if strict {
-                // TODO: change this when error handling comes into play
-                panic!("Reference Error: Cannot set mutable binding for {}", name);
-            }
-
-            self.create_mutable_binding(name.to_owned(), true);
-            self.initialize_binding(name, value);
-            return;
-        }
-
-        let record: &mut DeclarativeEnvironmentRecordBinding = self.env_rec.get_mut(name).unwrap();
-        if record.strict {
-            strict = true
-        }
+    fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool) {
+        self.env_rec.set_mutable_binding(name, value, strict)
+    }
 
-        if record.value.is_none() {
-            // TODO: change this when error handling comes into play
-            panic!("Reference Error: Cannot set mutable binding for {}", name);
-        }
+    fn is_initialized(&self, name: Sym) -> bool {
+        self.env_rec.is_initialized(name)
+    }
 
-        if record.mutable {
-            record.value = Some(value);
-        } else if strict {
-            // TODO: change this when error handling comes into play
-            panic!("TypeError: Cannot mutate an immutable binding {}", name);
-        }
+    fn is_mutable(&self, name: Sym) -> bool {
+        self.env_rec.is_mutable(name)
     }
 
-    fn get_binding_value(&self, name: &str, _strict: bool) -> Value {
-        if let Some(binding) = self.env_rec.get(name) {
-            binding
-                .value
-                .as_ref()
-                .expect("Could not get record as reference")
-                .clone()
-        } else {
-            // TODO: change this when error handling comes into play
-            panic!("ReferenceError: Cannot get binding value for {}", name);
-        }
+    fn get_binding_value(&self, name: Sym, _strict: bool) -> Value {
+        self.env_rec.get_binding_value(name)
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        match self.env_rec.get(name) {
-            Some(binding) => {
-                if binding.can_delete {
-                    self.env_rec.remove(name);
-                    true
-                } else {
-                    false
-                }
-            }
-            None => false,
-        }
+    fn delete_binding(&mut self, name: Sym) -> bool {
+        self.env_rec.delete_binding(name)
     }
 
     fn has_super_binding(&self) -> bool {
@@ -223,6 +164,22 @@ impl EnvironmentRecordTrait for FunctionEnvironmentRecord {
         Value::undefined()
     }
 
+    fn bindings(&self) -> Vec<(String, Value)> {
+        self.env_rec.bindings()
+    }
+
+    fn declarative_bindings(&self) -> Option<&DeclarativeBindings> {
+        Some(&self.env_rec)
+    }
+
+    fn declarative_bindings_mut(&mut self) -> Option<&mut DeclarativeBindings> {
+        Some(&mut self.env_rec)
+    }
+
+    fn as_function_record_mut(&mut self) -> Option<&mut FunctionEnvironmentRecord> {
+        Some(self)
+    }
+
     fn get_outer_environment(&self) -> Option<Environment> {
         match &self.outer_env {
             Some(outer) => Some(outer.clone()),
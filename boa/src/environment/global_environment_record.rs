@@ -8,37 +8,80 @@
 //! More info:  <https://tc39.es/ecma262/#sec-global-environment-records>
 
 use crate::{
-    builtins::value::Value,
+    builtins::{intrinsics, value::Value},
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecord,
+        declarative_environment_record::{DeclarativeBindings, DeclarativeEnvironmentRecord},
         environment_record_trait::EnvironmentRecordTrait,
+        function_environment_record::FunctionEnvironmentRecord,
         lexical_environment::{Environment, EnvironmentType},
         object_environment_record::ObjectEnvironmentRecord,
     },
+    interner::Sym,
 };
 use gc::{Finalize, Trace};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cell::RefCell;
 
 #[derive(Debug, Trace, Finalize, Clone)]
 pub struct GlobalEnvironmentRecord {
     pub object_record: Box<ObjectEnvironmentRecord>,
     pub global_this_binding: Value,
     pub declarative_record: Box<DeclarativeEnvironmentRecord>,
-    pub var_names: FxHashSet<String>,
+    pub var_names: FxHashSet<Sym>,
+    #[unsafe_ignore_trace]
+    pub(crate) property_slots: RefCell<PropertySlots>,
+}
+
+/// The slots of the properties of the global object read through the global bindings.
+///
+/// Reading a property of the global object would otherwise look up its key, and the global object
+/// has the properties of all the builtins. The slots are valid as long as the epoch of the
+/// properties of the global object stays the same.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PropertySlots {
+    epoch: u32,
+    slots: FxHashMap<Sym, usize>,
 }
 
 impl GlobalEnvironmentRecord {
-    pub fn has_var_declaration(&self, name: &str) -> bool {
-        self.var_names.contains(name)
+    /// Gets the value of an own data property of the global object, from its cached slot.
+    ///
+    /// Returns `None` if the global object has no such data property, in which case the binding
+    /// may still be an accessor property, or a property of the prototype of the global object.
+    fn get_global_property(&self, name: Sym) -> Option<Value> {
+        let global = self.object_record.bindings.as_object()?;
+        let properties = &global.properties;
+
+        let mut cache = self.property_slots.borrow_mut();
+        if cache.epoch != properties.epoch() {
+            cache.epoch = properties.epoch();
+            cache.slots.clear();
+        }
+        let slot = if let Some(&slot) = cache.slots.get(&name) {
+            slot
+        } else {
+            let slot = properties.named_slot(name.as_str())?;
+            cache.slots.insert(name, slot);
+            slot
+        };
+
+        properties
+            .slot(slot)
+            .filter(|prop| !prop.is_accessor_property())
+            .and_then(|prop| prop.value.clone())
+    }
+
+    pub fn has_var_declaration(&self, name: Sym) -> bool {
+        self.var_names.contains(&name)
     }
 
-    pub fn has_lexical_declaration(&self, name: &str) -> bool {
+    pub fn has_lexical_declaration(&self, name: Sym) -> bool {
         self.declarative_record.has_binding(name)
     }
 
-    pub fn has_restricted_global_property(&self, name: &str) -> bool {
+    pub fn has_restricted_global_property(&self, name: Sym) -> bool {
         let global_object = &self.object_record.bindings;
-        let existing_prop = global_object.get_property(name);
+        let existing_prop = global_object.get_property(name.as_str());
         match existing_prop {
             Some(prop) => {
                 if prop.value.is_none() || prop.configurable.unwrap_or(false) {
@@ -50,14 +93,14 @@ impl GlobalEnvironmentRecord {
         }
     }
 
-    pub fn create_global_var_binding(&mut self, name: String, deletion: bool) {
+    pub fn create_global_var_binding(&mut self, name: Sym, deletion: bool) {
         let obj_rec = &mut self.object_record;
         let global_object = &obj_rec.bindings;
-        let has_property = global_object.has_field(&name);
+        let has_property = global_object.has_field(name.as_str());
         let extensible = global_object.is_extensible();
         if !has_property && extensible {
-            obj_rec.create_mutable_binding(name.clone(), deletion);
-            obj_rec.initialize_binding(&name, Value::undefined());
+            obj_rec.create_mutable_binding(name, deletion);
+            obj_rec.initialize_binding(name, Value::undefined());
         }
 
         let var_declared_names = &mut self.var_names;
@@ -66,13 +109,13 @@ impl GlobalEnvironmentRecord {
         }
     }
 
-    pub fn create_global_function_binding(&mut self, name: &str, value: Value, deletion: bool) {
+    pub fn create_global_function_binding(&mut self, name: Sym, value: Value, deletion: bool) {
         let global_object = &mut self.object_record.bindings;
-        let existing_prop = global_object.get_property(&name);
+        let existing_prop = global_object.get_property(name.as_str());
         if let Some(prop) = existing_prop {
             if prop.value.is_none() || prop.configurable.unwrap_or(false) {
                 global_object.update_property(
-                    name,
+                    name.as_str(),
                     Some(value),
                     Some(true),
                     Some(true),
@@ -81,7 +124,7 @@ impl GlobalEnvironmentRecord {
             }
         } else {
             global_object.update_property(
-                name,
+                name.as_str(),
                 Some(value),
                 Some(true),
                 Some(true),
@@ -96,15 +139,21 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         self.global_this_binding.clone()
     }
 
-    fn has_binding(&self, name: &str) -> bool {
-        if self.declarative_record.has_binding(name) {
+    fn get_new_target(&self) -> Value {
+        Value::undefined()
+    }
+
+    fn has_binding(&self, name: Sym) -> bool {
+        if self.declarative_record.has_binding(name) || self.get_global_property(name).is_some() {
             return true;
         }
+        // The builtins which weren't used yet have no property on the global object.
         self.object_record.has_binding(name)
+            || intrinsics::init_binding(&self.object_record.bindings, name.as_str())
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
-        if self.declarative_record.has_binding(&name) {
+    fn create_mutable_binding(&mut self, name: Sym, deletion: bool) {
+        if self.declarative_record.has_binding(name) {
             // TODO: change to exception
             panic!("Binding already exists!");
         }
@@ -113,8 +162,8 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
             .create_mutable_binding(name, deletion)
     }
 
-    fn create_immutable_binding(&mut self, name: String, strict: bool) -> bool {
-        if self.declarative_record.has_binding(&name) {
+    fn create_immutable_binding(&mut self, name: Sym, strict: bool) -> bool {
+        if self.declarative_record.has_binding(name) {
             // TODO: change to exception
             panic!("Binding already exists!");
         }
@@ -123,8 +172,8 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
             .create_immutable_binding(name, strict)
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
-        if self.declarative_record.has_binding(&name) {
+    fn initialize_binding(&mut self, name: Sym, value: Value) {
+        if self.declarative_record.has_binding(name) {
             // TODO: assert binding is in the object environment record
             return self.declarative_record.initialize_binding(name, value);
         }
@@ -132,8 +181,8 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         panic!("Should not initialized binding without creating first.");
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
-        if self.declarative_record.has_binding(&name) {
+    fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool) {
+        if self.declarative_record.has_binding(name) {
             return self
                 .declarative_record
                 .set_mutable_binding(name, value, strict);
@@ -141,25 +190,36 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         self.object_record.set_mutable_binding(name, value, strict)
     }
 
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value {
-        if self.declarative_record.has_binding(&name) {
+    fn get_binding_value(&self, name: Sym, strict: bool) -> Value {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.get_binding_value(name, strict);
         }
+        if let Some(value) = self.get_global_property(name) {
+            return value;
+        }
         self.object_record.get_binding_value(name, strict)
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        if self.declarative_record.has_binding(&name) {
+    fn is_initialized(&self, name: Sym) -> bool {
+        self.declarative_record.is_initialized(name)
+    }
+
+    fn is_mutable(&self, name: Sym) -> bool {
+        self.declarative_record.is_mutable(name)
+    }
+
+    fn delete_binding(&mut self, name: Sym) -> bool {
+        if self.declarative_record.has_binding(name) {
             return self.declarative_record.delete_binding(name);
         }
 
         let global: &Value = &self.object_record.bindings;
-        if global.has_field(name) {
+        if global.has_field(name.as_str()) {
             let status = self.object_record.delete_binding(name);
             if status {
                 let var_names = &mut self.var_names;
-                if var_names.contains(name) {
-                    var_names.remove(name);
+                if var_names.contains(&name) {
+                    var_names.remove(&name);
                     return status;
                 }
             }
@@ -179,6 +239,25 @@ impl EnvironmentRecordTrait for GlobalEnvironmentRecord {
         Value::undefined()
     }
 
+    fn bindings(&self) -> Vec<(String, Value)> {
+        intrinsics::init_all(&self.object_record.bindings);
+        let mut bindings = self.declarative_record.bindings();
+        bindings.extend(self.object_record.bindings());
+        bindings
+    }
+
+    fn declarative_bindings(&self) -> Option<&DeclarativeBindings> {
+        None
+    }
+
+    fn declarative_bindings_mut(&mut self) -> Option<&mut DeclarativeBindings> {
+        None
+    }
+
+    fn as_function_record_mut(&mut self) -> Option<&mut FunctionEnvironmentRecord> {
+        None
+    }
+
     fn get_outer_environment(&self) -> Option<Environment> {
         None
     }
@@ -8,24 +8,26 @@
 use crate::{
     builtins::value::Value,
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecord,
+        declarative_environment_record::{DeclarativeBindings, DeclarativeEnvironmentRecord},
         environment_record_trait::EnvironmentRecordTrait,
         function_environment_record::{BindingStatus, FunctionEnvironmentRecord},
         global_environment_record::GlobalEnvironmentRecord,
         object_environment_record::ObjectEnvironmentRecord,
     },
+    interner::Sym,
+    syntax::ast::node::Identifier,
     BoaProfiler,
 };
 use gc::{Gc, GcCell};
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::{collections::VecDeque, error, fmt};
+use rustc_hash::FxHashSet;
+use std::{cell::RefCell, collections::VecDeque, error, fmt, mem};
 
 /// Environments are wrapped in a Box and then in a GC wrapper
 pub type Environment = Gc<GcCell<Box<dyn EnvironmentRecordTrait>>>;
 
 /// Give each environment an easy way to declare its own type
 /// This helps with comparisons
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnvironmentType {
     Declarative,
     Function,
@@ -42,9 +44,28 @@ pub enum VariableScope {
     Function,
 }
 
+/// The outcome of setting the binding of an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingUpdate {
+    /// The binding was set.
+    Set,
+    /// The binding is in its temporal dead zone.
+    Uninitialized,
+    /// The binding is a `const` one.
+    Immutable,
+}
+
+/// The number of environments of finished calls kept for the next calls of simple functions.
+///
+/// The calls of a recursion deeper than that allocate the environments which aren't kept.
+const MAX_SPARE_FUNCTION_ENVIRONMENTS: usize = 64;
+
 #[derive(Debug)]
 pub struct LexicalEnvironment {
     environment_stack: VecDeque<Environment>,
+    /// The environments of the finished calls of simple functions, reused by the next calls of
+    /// simple functions.
+    spare_function_environments: Vec<Environment>,
 }
 
 /// An error that occurred during lexing or compiling of the source input.
@@ -84,6 +105,7 @@ impl LexicalEnvironment {
         let global_env = new_global_environment(global.clone(), global);
         let mut lexical_env = Self {
             environment_stack: VecDeque::new(),
+            spare_function_environments: Vec::new(),
         };
 
         // lexical_env.push(global_env);
@@ -101,6 +123,85 @@ impl LexicalEnvironment {
         self.environment_stack.pop_back()
     }
 
+    /// Pushes the environment of a function call, whose outer environments are the ones of the
+    /// scope where the function was created, instead of the ones of the caller, so that the
+    /// closures see the bindings of their scope.
+    ///
+    /// Returns the environments of the caller, if they had to be replaced, which must be given
+    /// back to `pop_function` once the call is done.
+    pub fn push_function(&mut self, env: Environment) -> Option<VecDeque<Environment>> {
+        let outer = env
+            .borrow()
+            .get_outer_environment()
+            .expect("a function environment has an outer environment");
+        if Gc::ptr_eq(&outer, self.get_current_environment_ref()) {
+            self.environment_stack.push_back(env);
+            return None;
+        }
+
+        let mut scope = VecDeque::new();
+        let mut next = Some(env);
+        while let Some(env) = next {
+            next = env.borrow().get_outer_environment();
+            scope.push_front(env);
+        }
+        Some(mem::replace(&mut self.environment_stack, scope))
+    }
+
+    /// Pops the environment of a function call, restoring the environments of the caller.
+    pub fn pop_function(&mut self, caller: Option<VecDeque<Environment>>) {
+        match caller {
+            Some(caller) => self.environment_stack = caller,
+            None => {
+                self.environment_stack.pop_back();
+            }
+        }
+    }
+
+    /// Creates the environment of a call of a simple function, reusing the environment of a
+    /// finished call if there is one.
+    ///
+    /// The simple functions are found by the scope analysis. They don't create closures, nor use
+    /// `arguments`, `eval` or `with`, so nothing refers to their environment once the call is done.
+    pub(crate) fn new_simple_function_environment(
+        &mut self,
+        f: Value,
+        this: Option<Value>,
+        outer: Option<Environment>,
+        binding_status: BindingStatus,
+        new_target: Value,
+    ) -> Environment {
+        let env = match self.spare_function_environments.pop() {
+            Some(env) => env,
+            None => return new_function_environment(f, this, outer, binding_status, new_target),
+        };
+
+        {
+            let mut env = env.borrow_mut();
+            let record = env
+                .as_function_record_mut()
+                .expect("a spare environment is a function environment");
+            record.function = f;
+            record.this_value = this.unwrap_or_else(Value::undefined);
+            record.this_binding_status = binding_status;
+            record.new_target = new_target;
+            record.outer_env = outer;
+        }
+        env
+    }
+
+    /// Gives back the environment of a finished call of a simple function, for the next calls of
+    /// simple functions to reuse.
+    pub(crate) fn release_function_environment(&mut self, env: Environment) {
+        if self.spare_function_environments.len() < MAX_SPARE_FUNCTION_ENVIRONMENTS {
+            // The values of the call are dropped now, rather than when the environment is reused.
+            if let Some(record) = env.borrow_mut().as_function_record_mut() {
+                record.clear();
+            }
+            self.spare_function_environments.push(env);
+        }
+    }
+
     pub fn environments(&self) -> impl Iterator<Item = &Environment> {
         self.environment_stack.iter().rev()
     }
@@ -120,30 +221,42 @@ impl LexicalEnvironment {
             .unwrap_or_else(Value::undefined)
     }
 
-    pub fn create_mutable_binding(&mut self, name: String, deletion: bool, scope: VariableScope) {
+    /// Gets the `new.target` value of the innermost function defining its `this`, which is
+    /// `undefined` outside of the functions called by `new`.
+    pub fn get_new_target(&self) -> Value {
+        self.environments()
+            .find(|env| env.borrow().has_this_binding())
+            .map(|env| env.borrow().get_new_target())
+            .unwrap_or_else(Value::undefined)
+    }
+
+    /// Gets the environment of the `var` and function declarations, which is the first function
+    /// or global environment from the top of the stack.
+    pub fn get_var_environment(&self) -> &Environment {
+        self.environments()
+            .find(|env| match env.borrow().get_environment_type() {
+                EnvironmentType::Function | EnvironmentType::Global => true,
+                _ => false,
+            })
+            .expect("No function or global environment")
+    }
+
+    pub fn create_mutable_binding(&mut self, name: Sym, deletion: bool, scope: VariableScope) {
         match scope {
             VariableScope::Block => self
                 .get_current_environment()
                 .borrow_mut()
                 .create_mutable_binding(name, deletion),
-            VariableScope::Function => {
-                // Find the first function or global environment (from the top of the stack)
-                let env = self
-                    .environments()
-                    .find(|env| match env.borrow().get_environment_type() {
-                        EnvironmentType::Function | EnvironmentType::Global => true,
-                        _ => false,
-                    })
-                    .expect("No function or global environment");
-
-                env.borrow_mut().create_mutable_binding(name, deletion);
-            }
+            VariableScope::Function => self
+                .get_var_environment()
+                .borrow_mut()
+                .create_mutable_binding(name, deletion),
         }
     }
 
     pub fn create_immutable_binding(
         &mut self,
-        name: String,
+        name: Sym,
         deletion: bool,
         scope: VariableScope,
     ) -> bool {
@@ -153,15 +266,7 @@ impl LexicalEnvironment {
                 .borrow_mut()
                 .create_immutable_binding(name, deletion),
             VariableScope::Function => {
-                // Find the first function or global environment (from the top of the stack)
-                let env = self
-                    .environments()
-                    .find(|env| match env.borrow().get_environment_type() {
-                        EnvironmentType::Function | EnvironmentType::Global => true,
-                        _ => false,
-                    })
-                    .expect("No function or global environment");
-
+                let env = self.get_var_environment();
                 #[allow(clippy::let_and_return)]
                 // FIXME need to assign result to a variable to avoid borrow checker error
                 // (borrowed value `env` does not live long enough)
@@ -171,7 +276,7 @@ impl LexicalEnvironment {
         }
     }
 
-    pub fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
+    pub fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool) {
         // Find the first environment which has the given binding
         let env = self
             .environments()
@@ -181,7 +286,7 @@ impl LexicalEnvironment {
         env.borrow_mut().set_mutable_binding(name, value, strict);
     }
 
-    pub fn initialize_binding(&mut self, name: &str, value: Value) {
+    pub fn initialize_binding(&mut self, name: Sym, value: Value) {
         // Find the first environment which has the given binding
         let env = self
             .environments()
@@ -207,23 +312,193 @@ impl LexicalEnvironment {
             .expect("Could not get mutable reference to back object")
     }
 
-    pub fn has_binding(&self, name: &str) -> bool {
+    pub fn has_binding(&self, name: Sym) -> bool {
         self.environments()
             .any(|env| env.borrow().has_binding(name))
     }
 
-    pub fn get_binding_value(&self, name: &str) -> Value {
-        self.environments()
+    /// Returns true if a binding with the given name is declared in the environments up to the
+    /// environment of the `var` declarations, in which case a `var` declaration with this name
+    /// doesn't create a new binding.
+    pub fn has_var_scoped_binding(&self, name: Sym) -> bool {
+        for env in self.environments() {
+            let env = env.borrow();
+            if env.has_binding(name) {
+                return true;
+            }
+            if let EnvironmentType::Function | EnvironmentType::Global = env.get_environment_type()
+            {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Deletes the binding with the given name from the innermost environment which has it, as
+    /// done by the `delete` operator, returning `false` if the binding can't be deleted.
+    pub fn delete_binding(&self, name: Sym) -> bool {
+        match self
+            .environments()
             .find(|env| env.borrow().has_binding(name))
-            .map(|env| env.borrow().get_binding_value(name, false))
+        {
+            Some(env) => env.borrow_mut().delete_binding(name),
+            None => true,
+        }
+    }
+
+    /// Gets the value of the binding with the given name, which is undefined if there is no such
+    /// binding or if it isn't initialized yet.
+    pub fn get_binding_value(&self, name: Sym) -> Value {
+        self.get_initialized_binding_value(name)
             .unwrap_or_else(Value::undefined)
     }
+
+    /// Gets the value of the binding with the given name, which is undefined if there is no such
+    /// binding, or `None` if the binding isn't initialized yet.
+    fn get_initialized_binding_value(&self, name: Sym) -> Option<Value> {
+        let env = self
+            .environments()
+            .find(|env| env.borrow().has_binding(name));
+        initialized_binding_value(env, name)
+    }
+
+    /// Finds the environment holding the binding of an identifier which wasn't resolved by the
+    /// scope analysis.
+    ///
+    /// The binding of a global identifier is only looked for in the global environment.
+    fn find_environment(&self, ident: &Identifier) -> Option<&Environment> {
+        let name = ident.sym();
+        if ident.is_global() {
+            self.environment_stack
+                .front()
+                .filter(|env| env.borrow().has_binding(name))
+        } else {
+            self.environments()
+                .find(|env| env.borrow().has_binding(name))
+        }
+    }
+
+    /// Gets the binding object of the `with` statement providing the binding of an identifier,
+    /// which is the `this` value of the function called through it, if any.
+    ///
+    /// Identifiers resolved by the scope analysis are never bound by a `with` statement.
+    pub fn get_with_base_object(&self, ident: &Identifier) -> Option<Value> {
+        if ident.binding().is_some() {
+            return None;
+        }
+        let env = self.find_environment(ident)?;
+        let base = env.borrow().with_base_object();
+        if base.is_undefined() {
+            None
+        } else {
+            Some(base)
+        }
+    }
+
+    /// Gets the environment where the scope analysis expects the binding of an identifier, and
+    /// the slot of the binding in it.
+    ///
+    /// Returns `None` if the identifier wasn't resolved, or if it must be checked for the temporal
+    /// dead zone. The binding must still be checked to be in that slot, as it isn't if the
+    /// bindings of the environment weren't created in the order of the scope analysis.
+    fn get_resolved_slot(&self, ident: &Identifier) -> Option<(&Environment, usize)> {
+        let binding = ident.binding().filter(|b| !b.needs_tdz_check())?;
+        let index = self
+            .environment_stack
+            .len()
+            .checked_sub(binding.depth() as usize + 1)?;
+        let env = self.environment_stack.get(index)?;
+        Some((env, binding.slot() as usize))
+    }
+
+    /// Checks if there is a binding for an identifier.
+    pub fn has_identifier_binding(&self, ident: &Identifier) -> bool {
+        let resolved = self.get_resolved_slot(ident).is_some_and(|(env, slot)| {
+            env.borrow()
+                .declarative_bindings()
+                .and_then(|bindings| bindings.get_slot(slot, ident.sym()))
+                .is_some()
+        });
+        resolved || self.find_environment(ident).is_some()
+    }
+
+    /// Gets the value of the binding of an identifier.
+    ///
+    /// Identifiers resolved by the scope analysis are read from their slot, and the global ones
+    /// are looked up in the global environment. Returns `None` if the binding is in its temporal
+    /// dead zone.
+    pub fn get_identifier_value(&self, ident: &Identifier) -> Option<Value> {
+        let resolved = self.get_resolved_slot(ident).and_then(|(env, slot)| {
+            env.borrow()
+                .declarative_bindings()?
+                .get_slot(slot, ident.sym())?
+                .value
+                .clone()
+        });
+        match resolved {
+            Some(value) => Some(value),
+            None => initialized_binding_value(self.find_environment(ident), ident.sym()),
+        }
+    }
+
+    /// Sets the value of the existing binding of an identifier.
+    ///
+    /// Identifiers resolved by the scope analysis are set in their slot. The binding isn't set if
+    /// it is in its temporal dead zone, or if it is immutable.
+    pub fn set_identifier_value(
+        &mut self,
+        ident: &Identifier,
+        value: Value,
+        strict: bool,
+    ) -> BindingUpdate {
+        let name = ident.sym();
+        if let Some((env, slot)) = self.get_resolved_slot(ident) {
+            let mut env = env.borrow_mut();
+            let binding = env
+                .declarative_bindings_mut()
+                .and_then(|bindings| bindings.get_slot_mut(slot, name))
+                .filter(|binding| binding.mutable && binding.value.is_some());
+            if let Some(binding) = binding {
+                binding.value = Some(value);
+                return BindingUpdate::Set;
+            }
+        }
+
+        let env = self
+            .find_environment(ident)
+            .expect("Binding does not exists"); // TODO graceful error handling
+
+        if !env.borrow().is_initialized(name) {
+            return BindingUpdate::Uninitialized;
+        }
+        if !env.borrow().is_mutable(name) {
+            return BindingUpdate::Immutable;
+        }
+        env.borrow_mut().set_mutable_binding(name, value, strict);
+        BindingUpdate::Set
+    }
+}
+
+/// Gets the value of a binding of the given environment, which is undefined if there is no such
+/// environment, or `None` if the binding isn't initialized yet.
+fn initialized_binding_value(env: Option<&Environment>, name: Sym) -> Option<Value> {
+    match env {
+        Some(env) => {
+            let env = env.borrow();
+            if env.is_initialized(name) {
+                Some(env.get_binding_value(name, false))
+            } else {
+                None
+            }
+        }
+        None => Some(Value::undefined()),
+    }
 }
 
 pub fn new_declarative_environment(env: Option<Environment>) -> Environment {
     let _timer = BoaProfiler::global().start_event("new_declarative_environment", "env");
     let boxed_env = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: FxHashMap::default(),
+        env_rec: DeclarativeBindings::new(),
         outer_env: env,
     });
 
@@ -235,13 +510,14 @@ pub fn new_function_environment(
     this: Option<Value>,
     outer: Option<Environment>,
     binding_status: BindingStatus,
+    new_target: Value,
 ) -> Environment {
     Gc::new(GcCell::new(Box::new(FunctionEnvironmentRecord {
-        env_rec: FxHashMap::default(),
+        env_rec: DeclarativeBindings::new(),
         function: f,
         this_binding_status: binding_status,
         home_object: Value::undefined(),
-        new_target: Value::undefined(),
+        new_target,
         outer_env: outer, // this will come from Environment set as a private property of F - https://tc39.es/ecma262/#sec-ecmascript-function-objects
         this_value: this.unwrap_or_else(Value::undefined),
     })))
@@ -260,6 +536,21 @@ pub fn new_object_environment(object: Value, environment: Option<Environment>) -
     })))
 }
 
+/// Creates the object environment of a `with` statement, whose binding object is the `this`
+/// value of the functions called through it.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-with-statement-runtime-semantics-evaluation
+pub fn new_with_environment(object: Value, environment: Option<Environment>) -> Environment {
+    Gc::new(GcCell::new(Box::new(ObjectEnvironmentRecord {
+        bindings: object,
+        outer_env: environment,
+        with_environment: true,
+    })))
+}
+
 pub fn new_global_environment(global: Value, this_value: Value) -> Environment {
     let obj_rec = Box::new(ObjectEnvironmentRecord {
         bindings: global,
@@ -273,7 +564,7 @@ pub fn new_global_environment(global: Value, this_value: Value) -> Environment {
     });
 
     let dcl_rec = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: FxHashMap::default(),
+        env_rec: DeclarativeBindings::new(),
         outer_env: None,
     });
 
@@ -282,6 +573,7 @@ pub fn new_global_environment(global: Value, this_value: Value) -> Environment {
         global_this_binding: this_value,
         declarative_record: dcl_rec,
         var_names: FxHashSet::default(),
+        property_slots: RefCell::default(),
     })))
 }
 
@@ -7,11 +7,18 @@
 //! More info:  [Object Records](https://tc39.es/ecma262/#sec-object-environment-records)
 
 use crate::{
-    builtins::{property::Property, value::Value},
+    builtins::{
+        intrinsics::{self, Intrinsic},
+        property::Property,
+        value::Value,
+    },
     environment::{
+        declarative_environment_record::DeclarativeBindings,
         environment_record_trait::EnvironmentRecordTrait,
+        function_environment_record::FunctionEnvironmentRecord,
         lexical_environment::{Environment, EnvironmentType},
     },
+    interner::Sym,
 };
 use gc::{Finalize, Trace};
 
@@ -23,18 +30,26 @@ pub struct ObjectEnvironmentRecord {
 }
 
 impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
-    fn has_binding(&self, name: &str) -> bool {
-        if self.bindings.has_field(name) {
-            if self.with_environment {
-                // TODO: implement unscopables
-            }
-            true
-        } else {
-            false
+    fn has_binding(&self, name: Sym) -> bool {
+        if !self.bindings.has_field(name.as_str()) {
+            return false;
         }
+        if !self.with_environment {
+            return true;
+        }
+
+        // The properties listed by the `@@unscopables` object of the binding object of a `with`
+        // statement are left out of its scope.
+        let unscopables = match self.get_global_object() {
+            Some(global) => self
+                .bindings
+                .get_field(intrinsics::get(&global, Intrinsic::Symbol).get_field("unscopables")),
+            None => return true,
+        };
+        !(unscopables.is_object() && unscopables.get_field(name.as_str()).is_true())
     }
 
-    fn create_mutable_binding(&mut self, name: String, deletion: bool) {
+    fn create_mutable_binding(&mut self, name: Sym, deletion: bool) {
         // TODO: could save time here and not bother generating a new undefined object,
         // only for it to be replace with the real value later. We could just add the name to a Vector instead
         let bindings = &mut self.bindings;
@@ -44,31 +59,35 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
             .enumerable(true)
             .configurable(deletion);
 
-        bindings.set_property(name, prop);
+        bindings.set_property(name.as_str().to_owned(), prop);
     }
 
-    fn create_immutable_binding(&mut self, _name: String, _strict: bool) -> bool {
+    fn create_immutable_binding(&mut self, _name: Sym, _strict: bool) -> bool {
         true
     }
 
-    fn initialize_binding(&mut self, name: &str, value: Value) {
+    fn initialize_binding(&mut self, name: Sym, value: Value) {
         // We should never need to check if a binding has been created,
         // As all calls to create_mutable_binding are followed by initialized binding
         // The below is just a check.
-        debug_assert!(self.has_binding(&name));
+        debug_assert!(self.has_binding(name));
         self.set_mutable_binding(name, value, false)
     }
 
-    fn set_mutable_binding(&mut self, name: &str, value: Value, strict: bool) {
+    fn set_mutable_binding(&mut self, name: Sym, value: Value, strict: bool) {
+        if self.with_environment {
+            self.bindings.set_field(name.as_str(), value);
+            return;
+        }
         debug_assert!(value.is_object() || value.is_function());
 
         let bindings = &mut self.bindings;
-        bindings.update_property(name, Some(value), None, None, Some(strict));
+        bindings.update_property(name.as_str(), Some(value), None, None, Some(strict));
     }
 
-    fn get_binding_value(&self, name: &str, strict: bool) -> Value {
-        if self.bindings.has_field(name) {
-            self.bindings.get_field(name)
+    fn get_binding_value(&self, name: Sym, strict: bool) -> Value {
+        if self.bindings.has_field(name.as_str()) {
+            self.bindings.get_field(name.as_str())
         } else {
             if strict {
                 // TODO: throw error here
@@ -78,11 +97,18 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         }
     }
 
-    fn delete_binding(&mut self, name: &str) -> bool {
-        self.bindings.remove_property(name);
+    fn is_initialized(&self, _name: Sym) -> bool {
+        true
+    }
+
+    fn is_mutable(&self, _name: Sym) -> bool {
         true
     }
 
+    fn delete_binding(&mut self, name: Sym) -> bool {
+        self.bindings.delete_property(&Value::from(name.as_str()))
+    }
+
     fn has_this_binding(&self) -> bool {
         false
     }
@@ -91,6 +117,10 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         Value::undefined()
     }
 
+    fn get_new_target(&self) -> Value {
+        Value::undefined()
+    }
+
     fn has_super_binding(&self) -> bool {
         false
     }
@@ -105,6 +135,32 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
         Value::undefined()
     }
 
+    fn bindings(&self) -> Vec<(String, Value)> {
+        match self.bindings.as_object() {
+            Some(bindings) => bindings
+                .properties
+                .iter()
+                .map(|(name, property)| {
+                    let value = property.value.clone().unwrap_or_else(Value::undefined);
+                    (name.into_owned(), value)
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn declarative_bindings(&self) -> Option<&DeclarativeBindings> {
+        None
+    }
+
+    fn declarative_bindings_mut(&mut self) -> Option<&mut DeclarativeBindings> {
+        None
+    }
+
+    fn as_function_record_mut(&mut self) -> Option<&mut FunctionEnvironmentRecord> {
+        None
+    }
+
     fn get_outer_environment(&self) -> Option<Environment> {
         match &self.outer_env {
             Some(outer) => Some(outer.clone()),
@@ -117,7 +173,7 @@ impl EnvironmentRecordTrait for ObjectEnvironmentRecord {
     }
 
     fn get_environment_type(&self) -> EnvironmentType {
-        EnvironmentType::Function
+        EnvironmentType::Object
     }
 
     fn get_global_object(&self) -> Option<Value> {
@@ -15,7 +15,7 @@ impl Executable for ArrayDecl {
         for elem in self.as_ref() {
             if let Node::Spread(ref x) = elem {
                 let val = x.run(interpreter)?;
-                let mut vals = interpreter.extract_array_properties(&val).unwrap();
+                let mut vals = interpreter.iterable_to_list(&val)?;
                 elements.append(&mut vals);
                 continue; // Don't push array after spread
             }
@@ -17,20 +17,24 @@ impl Executable for Block {
                 env.get_current_environment_ref().clone(),
             )));
         }
+        interpreter.declare_lexical(self.statements());
 
-        let mut obj = Value::null();
-        for statement in self.statements() {
-            obj = statement.run(interpreter)?;
+        let coverage = interpreter.coverage_state();
+        let mut obj = Ok(Value::null());
+        for (i, statement) in self.statements().iter().enumerate() {
+            interpreter.enter_statement(self.statement_list().position(i));
+            obj = statement.run(interpreter);
 
             // early return
-            if interpreter.is_return {
+            if obj.is_err() || interpreter.is_return {
                 break;
             }
         }
 
-        // pop the block env
+        // pop the block env, even if a statement failed
         let _ = interpreter.realm_mut().environment.pop();
+        interpreter.restore_coverage_state(coverage);
 
-        Ok(obj)
+        obj
     }
 }
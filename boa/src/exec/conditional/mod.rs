@@ -7,13 +7,17 @@ use std::borrow::Borrow;
 
 impl Executable for If {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
-        Ok(if self.cond().run(interpreter)?.borrow().is_true() {
+        let result = if self.cond().run(interpreter)?.borrow().is_true() {
+            interpreter.cover_branch(0);
             self.body().run(interpreter)?
         } else {
+            interpreter.cover_branch(1);
             match self.else_node() {
                 Some(ref else_e) => else_e.run(interpreter)?,
                 None => Value::undefined(),
             }
-        })
+        };
+        interpreter.leave_branch();
+        Ok(result)
     }
 }
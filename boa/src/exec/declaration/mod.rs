@@ -4,11 +4,17 @@ use super::{Executable, Interpreter};
 use crate::{
     builtins::{
         function::ThisMode,
+        iterable::IteratorRecord,
         value::{ResultValue, Value},
+        Array,
     },
-    environment::lexical_environment::VariableScope,
+    environment::lexical_environment::{
+        BindingUpdate, Environment, EnvironmentType, VariableScope,
+    },
+    interner::Sym,
     syntax::ast::node::{
-        ArrowFunctionDecl, ConstDeclList, FunctionDecl, FunctionExpr, LetDeclList, VarDeclList,
+        ArrayPattern, ArrowFunctionDecl, Binding, ConstDeclList, FunctionDecl, FunctionExpr,
+        Identifier, LetDeclList, Node, VarDeclList,
     },
     BoaProfiler,
 };
@@ -18,41 +24,58 @@ impl Executable for FunctionDecl {
         let _timer = BoaProfiler::global().start_event("FunctionDecl", "exec");
         let val = interpreter.create_function(
             self.parameters().to_vec(),
-            self.body().to_vec(),
+            self.statement_list().clone(),
             ThisMode::NonLexical,
             true,
             true,
+            self.is_simple(),
         );
 
         // Set the name and assign it in the current environment
-        val.set_field("name", self.name());
-        interpreter.realm_mut().environment.create_mutable_binding(
-            self.name().to_owned(),
-            false,
-            VariableScope::Function,
-        );
-
-        interpreter
-            .realm_mut()
-            .environment
-            .initialize_binding(self.name(), val.clone());
+        val.set_field("name", self.name().as_str());
+        let environment = &interpreter.realm().environment;
+        let current = environment.get_current_environment_ref();
+        // A function declared in a block is bound in the block, and also in the scope of the
+        // enclosing function, like in the Annex B of the specification.
+        if current.borrow().get_environment_type() == EnvironmentType::Declarative {
+            bind_function(current, self.name(), val.clone());
+        }
+        bind_function(environment.get_var_environment(), self.name(), val.clone());
 
         Ok(val)
     }
 }
 
+/// Binds a declared function in an environment, replacing the function of an earlier
+/// declaration with the same name.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-block-level-function-declarations-web-legacy-compatibility-semantics
+fn bind_function(env: &Environment, name: Sym, function: Value) {
+    let mut env = env.borrow_mut();
+    if env.has_binding(name) {
+        env.set_mutable_binding(name, function, false);
+    } else {
+        env.create_mutable_binding(name, false);
+        env.initialize_binding(name, function);
+    }
+}
+
 impl Executable for FunctionExpr {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let val = interpreter.create_function(
             self.parameters().to_vec(),
-            self.body().to_vec(),
+            self.statement_list().clone(),
             ThisMode::NonLexical,
             true,
             true,
+            self.is_simple(),
         );
 
         if let Some(name) = self.name() {
-            val.set_field("name", Value::from(name));
+            val.set_field("name", Value::from(name.as_str()));
         }
 
         Ok(val)
@@ -62,23 +85,26 @@ impl Executable for FunctionExpr {
 impl Executable for VarDeclList {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         for var in self.as_ref() {
-            let val = match var.init() {
-                Some(v) => v.run(interpreter)?,
-                None => Value::undefined(),
-            };
-            let environment = &mut interpreter.realm_mut().environment;
-
-            if environment.has_binding(var.name()) {
-                if var.init().is_some() {
-                    environment.set_mutable_binding(var.name(), val, true);
+            match (var.binding(), var.init()) {
+                // A declaration without a value doesn't change an existing variable.
+                (Binding::Identifier(ident), None) => {
+                    let environment = &mut interpreter.realm_mut().environment;
+                    if !environment.has_var_scoped_binding(ident.sym()) {
+                        environment.create_mutable_binding(
+                            ident.sym(),
+                            false,
+                            VariableScope::Function,
+                        );
+                        environment.initialize_binding(ident.sym(), Value::undefined());
+                    }
+                }
+                (binding, init) => {
+                    let val = match init {
+                        Some(v) => v.run(interpreter)?,
+                        None => Value::undefined(),
+                    };
+                    interpreter.bind_declaration(binding, val, DeclarationKind::Var)?;
                 }
-            } else {
-                environment.create_mutable_binding(
-                    var.name().to_owned(),
-                    false,
-                    VariableScope::Function,
-                );
-                environment.initialize_binding(var.name(), val);
             }
         }
         Ok(Value::undefined())
@@ -89,16 +115,7 @@ impl Executable for ConstDeclList {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         for decl in self.as_ref() {
             let val = decl.init().run(interpreter)?;
-
-            interpreter
-                .realm_mut()
-                .environment
-                .create_immutable_binding(decl.name().to_owned(), false, VariableScope::Block);
-
-            interpreter
-                .realm_mut()
-                .environment
-                .initialize_binding(decl.name(), val);
+            interpreter.bind_declaration(decl.binding(), val, DeclarationKind::Const)?;
         }
         Ok(Value::undefined())
     }
@@ -111,28 +128,212 @@ impl Executable for LetDeclList {
                 Some(v) => v.run(interpreter)?,
                 None => Value::undefined(),
             };
-            interpreter.realm_mut().environment.create_mutable_binding(
-                var.name().to_owned(),
-                false,
-                VariableScope::Block,
-            );
-            interpreter
-                .realm_mut()
-                .environment
-                .initialize_binding(var.name(), val);
+            interpreter.bind_declaration(var.binding(), val, DeclarationKind::Let)?;
         }
         Ok(Value::undefined())
     }
 }
 
+/// The kind of a declaration, which determines how the declared names are bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclarationKind {
+    Var,
+    Let,
+    Const,
+}
+
+impl Interpreter {
+    /// Binds the value of a declaration to its identifier, or destructures it with its binding
+    /// pattern.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-runtime-semantics-bindinginitialization
+    fn bind_declaration(
+        &mut self,
+        binding: &Binding,
+        value: Value,
+        kind: DeclarationKind,
+    ) -> Result<(), Value> {
+        match binding {
+            Binding::Identifier(ident) => {
+                self.initialize_declared(ident.sym(), value, kind);
+                Ok(())
+            }
+            Binding::Array(pattern) => {
+                let iterator = self.get_iterator(&value)?;
+                let mut done = false;
+                let result = self.bind_array_pattern(pattern, &iterator, kind, &mut done);
+
+                // An iterator which isn't done is closed, even if the binding failed.
+                if done {
+                    result
+                } else {
+                    iterator
+                        .close(result.map(|()| Value::undefined()), self)
+                        .map(|_| ())
+                }
+            }
+        }
+    }
+
+    /// Binds the values produced by an iterator to the elements of an array binding pattern.
+    ///
+    /// `done` is set once the iterator is done, or has thrown an error, and mustn't be closed.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-runtime-semantics-iteratorbindinginitialization
+    fn bind_array_pattern(
+        &mut self,
+        pattern: &ArrayPattern,
+        iterator: &IteratorRecord,
+        kind: DeclarationKind,
+        done: &mut bool,
+    ) -> Result<(), Value> {
+        for element in pattern.elements() {
+            let value = if *done {
+                None
+            } else {
+                self.step_pattern_iterator(iterator, done)?
+            };
+
+            if let Some(element) = element {
+                let value = match (value, element.init()) {
+                    (Some(value), _) if !value.is_undefined() => value,
+                    (_, Some(init)) => init.run(self)?,
+                    (value, None) => value.unwrap_or_else(Value::undefined),
+                };
+                self.bind_declaration(element.target(), value, kind)?;
+            }
+        }
+
+        if let Some(rest) = pattern.rest() {
+            let mut values = Vec::new();
+            while !*done {
+                if let Some(value) = self.step_pattern_iterator(iterator, done)? {
+                    values.push(value);
+                }
+            }
+            let array = Array::new_array(self)?;
+            Array::construct_array(&array, &values)?;
+            self.bind_declaration(rest, array, kind)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the next value of the iterator of a binding pattern, setting `done` once the
+    /// iterator is done or throws an error.
+    fn step_pattern_iterator(
+        &mut self,
+        iterator: &IteratorRecord,
+        done: &mut bool,
+    ) -> Result<Option<Value>, Value> {
+        let next = iterator.step(self);
+        if !matches!(next, Ok(Some(_))) {
+            *done = true;
+        }
+        next
+    }
+
+    /// Initializes the binding of a declared name with its value.
+    ///
+    /// The bindings of `let` and `const` declarations are normally created, uninitialized,
+    /// when their scope is entered, and `var` declarations assign their existing variable.
+    fn initialize_declared(&mut self, name: Sym, value: Value, kind: DeclarationKind) {
+        let environment = &mut self.realm.environment;
+        match kind {
+            DeclarationKind::Var => {
+                if environment.has_var_scoped_binding(name) {
+                    environment.set_mutable_binding(name, value, true);
+                    return;
+                }
+                environment.create_mutable_binding(name, false, VariableScope::Function);
+            }
+            DeclarationKind::Let | DeclarationKind::Const => {
+                let declared = environment
+                    .get_current_environment_ref()
+                    .borrow()
+                    .has_binding(name);
+                if !declared && kind == DeclarationKind::Let {
+                    environment.create_mutable_binding(name, false, VariableScope::Block);
+                } else if !declared {
+                    environment.create_immutable_binding(name, false, VariableScope::Block);
+                }
+            }
+        }
+        environment.initialize_binding(name, value);
+    }
+
+    /// Creates the bindings of the `let` and `const` declarations of a list of statements in the
+    /// current environment, uninitialized, so that they are in their temporal dead zone until
+    /// their declaration runs.
+    pub(crate) fn declare_lexical(&mut self, statements: &[Node]) {
+        let environment = &mut self.realm.environment;
+        for statement in statements {
+            match statement {
+                Node::LetDeclList(list) => {
+                    for name in list.as_ref().iter().flat_map(|decl| decl.names()) {
+                        environment.create_mutable_binding(name, false, VariableScope::Block);
+                    }
+                }
+                Node::ConstDeclList(list) => {
+                    for name in list.as_ref().iter().flat_map(|decl| decl.names()) {
+                        environment.create_immutable_binding(name, false, VariableScope::Block);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Gets the value of the binding of an identifier, throwing a `ReferenceError` if it is in
+    /// its temporal dead zone.
+    pub(crate) fn get_identifier_value(&mut self, ident: &Identifier) -> ResultValue {
+        match self.realm.environment.get_identifier_value(ident) {
+            Some(value) => Ok(value),
+            None => self.throw_uninitialized(ident),
+        }
+    }
+
+    /// Sets the value of the existing binding of an identifier, throwing a `ReferenceError` if
+    /// it is in its temporal dead zone, or a `TypeError` if it is a `const` binding.
+    pub(crate) fn set_identifier_value(
+        &mut self,
+        ident: &Identifier,
+        value: Value,
+    ) -> Result<(), Value> {
+        match self
+            .realm
+            .environment
+            .set_identifier_value(ident, value, true)
+        {
+            BindingUpdate::Set => Ok(()),
+            BindingUpdate::Uninitialized => self.throw_uninitialized(ident).map(|_| ()),
+            BindingUpdate::Immutable => self
+                .throw_type_error("Assignment to constant variable.")
+                .map(|_| ()),
+        }
+    }
+
+    /// Throws the `ReferenceError` of an identifier used in its temporal dead zone.
+    fn throw_uninitialized(&mut self, ident: &Identifier) -> ResultValue {
+        self.throw_reference_error(format!("Cannot access '{}' before initialization", ident))
+    }
+}
+
 impl Executable for ArrowFunctionDecl {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         Ok(interpreter.create_function(
             self.params().to_vec(),
-            self.body().to_vec(),
+            self.statement_list().clone(),
             ThisMode::Lexical,
             false,
             true,
+            self.is_simple(),
         ))
     }
 }
@@ -1,36 +1,44 @@
 use super::*;
-use crate::{
-    exec::Executable,
-    syntax::ast::{
-        node::{Call, Identifier, New},
-        Const,
-    },
-};
+use crate::builtins::error::JsNativeError;
 
 impl Interpreter {
     /// Throws a `RangeError` with the specified message.
+    ///
+    /// The error is returned in the `Err` variant, so it can be propagated with `?`.
     pub fn throw_range_error<M>(&mut self, message: M) -> ResultValue
     where
         M: Into<String>,
     {
-        // Runs a `new RangeError(message)`.
-        New::from(Call::new(
-            Identifier::from("RangeError"),
-            vec![Const::from(message.into()).into()],
-        ))
-        .run(self)
+        JsNativeError::range().with_message(message).throw(self)
+    }
+
+    /// Throws a `ReferenceError` with the specified message.
+    ///
+    /// The error is returned in the `Err` variant, so it can be propagated with `?`.
+    pub fn throw_reference_error<M>(&mut self, message: M) -> ResultValue
+    where
+        M: Into<String>,
+    {
+        JsNativeError::reference().with_message(message).throw(self)
+    }
+
+    /// Throws a `SyntaxError` with the specified message.
+    ///
+    /// The error is returned in the `Err` variant, so it can be propagated with `?`.
+    pub fn throw_syntax_error<M>(&mut self, message: M) -> ResultValue
+    where
+        M: Into<String>,
+    {
+        JsNativeError::syntax().with_message(message).throw(self)
     }
 
     /// Throws a `TypeError` with the specified message.
+    ///
+    /// The error is returned in the `Err` variant, so it can be propagated with `?`.
     pub fn throw_type_error<M>(&mut self, message: M) -> ResultValue
     where
         M: Into<String>,
     {
-        // Runs a `new TypeError(message)`.
-        New::from(Call::new(
-            Identifier::from("TypeError"),
-            vec![Const::from(message.into()).into()],
-        ))
-        .run(self)
+        JsNativeError::typ().with_message(message).throw(self)
     }
 }
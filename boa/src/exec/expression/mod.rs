@@ -10,23 +10,44 @@ use crate::{
     BoaProfiler,
 };
 
-impl Executable for Call {
-    fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
-        let _timer = BoaProfiler::global().start_event("Call", "exec");
-        let (mut this, func) = match self.expr() {
+impl Call {
+    /// Evaluates the function, the `this` value and the arguments of the call, without calling the
+    /// function.
+    ///
+    /// Returns the `this` value, the function and the arguments, in that order.
+    pub(crate) fn evaluate(
+        &self,
+        interpreter: &mut Interpreter,
+    ) -> Result<(Value, Value, Vec<Value>), Value> {
+        let (this, func) = match self.expr() {
+            // The methods of primitives are looked up in a wrapper object, but they get the
+            // primitive itself as their `this`.
             Node::GetConstField(ref get_const_field) => {
-                let mut obj = get_const_field.obj().run(interpreter)?;
-                if obj.get_type() != "object" || obj.get_type() != "symbol" {
-                    obj = interpreter
-                        .to_object(&obj)
-                        .expect("failed to convert to object");
-                }
-                (obj.clone(), obj.get_field(get_const_field.field()))
+                let this = get_const_field.obj().run(interpreter)?;
+                let obj = interpreter.to_object(&this)?;
+                let func = interpreter.get_field_value_cached(
+                    &obj,
+                    get_const_field.field(),
+                    get_const_field.cache(),
+                )?;
+                (this, func)
             }
             Node::GetField(ref get_field) => {
-                let obj = get_field.obj().run(interpreter)?;
+                let this = get_field.obj().run(interpreter)?;
                 let field = get_field.field().run(interpreter)?;
-                (obj.clone(), obj.get_field(field.to_string()))
+                let field = interpreter.member_key(field)?;
+                let obj = interpreter.to_object(&this)?;
+                let func = interpreter.get_field_value(&obj, field)?;
+                (this, func)
+            }
+            Node::Identifier(ref ident) => {
+                let func = interpreter.get_identifier_value(ident)?;
+                let this = interpreter
+                    .realm()
+                    .environment
+                    .get_with_base_object(ident)
+                    .unwrap_or_else(|| interpreter.realm().global_obj.clone());
+                (this, func)
             }
             _ => (
                 interpreter.realm().global_obj.clone(),
@@ -37,12 +58,20 @@ impl Executable for Call {
         for arg in self.args() {
             if let Node::Spread(ref x) = arg {
                 let val = x.run(interpreter)?;
-                let mut vals = interpreter.extract_array_properties(&val).unwrap();
+                let mut vals = interpreter.iterable_to_list(&val)?;
                 v_args.append(&mut vals);
                 break; // after spread we don't accept any new arguments
             }
             v_args.push(arg.run(interpreter)?);
         }
+        Ok((this, func, v_args))
+    }
+}
+
+impl Executable for Call {
+    fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        let _timer = BoaProfiler::global().start_event("Call", "exec");
+        let (mut this, func, v_args) = self.evaluate(interpreter)?;
 
         // execute the function call itself
         let fnct_result = interpreter.call(&func, &mut this, &v_args);
@@ -70,14 +99,22 @@ impl Executable for New {
         // Create a blank object, then set its __proto__ property to the [Constructor].prototype
         this.set_internal_slot(INSTANCE_PROTOTYPE, func_object.get_field(PROTOTYPE));
 
-        match func_object.data() {
+        interpreter.enter_profile_frame(&func_object);
+        let result = match func_object.data() {
             ValueData::Object(ref o) => o.clone().borrow_mut().func.as_ref().unwrap().construct(
                 &mut func_object.clone(),
                 &v_args,
+                &func_object,
                 interpreter,
                 &mut this,
             ),
             _ => Ok(Value::undefined()),
-        }
+        };
+        interpreter.exit_profile_frame();
+
+        // unset the early return flag
+        interpreter.is_return = false;
+
+        result
     }
 }
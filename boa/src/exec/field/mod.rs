@@ -1,27 +1,151 @@
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::value::ResultValue,
+    builtins::{
+        object::{ObjectKind, PropertyCache},
+        property::Property,
+        value::{Field, ResultValue, Value},
+        Array,
+    },
     syntax::ast::node::{GetConstField, GetField},
 };
 
 impl Executable for GetConstField {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let mut obj = self.obj().run(interpreter)?;
-        if obj.get_type() != "object" || obj.get_type() != "symbol" {
-            obj = interpreter
-                .to_object(&obj)
-                .expect("failed to convert to object");
+        if !obj.is_object() {
+            obj = interpreter.to_object(&obj)?;
         }
 
-        Ok(obj.get_field(self.field()))
+        interpreter.get_field_value_cached(&obj, self.field(), self.cache())
     }
 }
 
 impl Executable for GetField {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
-        let obj = self.obj().run(interpreter)?;
+        let mut obj = self.obj().run(interpreter)?;
         let field = self.field().run(interpreter)?;
+        let field = interpreter.member_key(field)?;
+        if !obj.is_object() {
+            obj = interpreter.to_object(&obj)?;
+        }
 
-        Ok(obj.get_field(field.to_string()))
+        interpreter.get_field_value(&obj, field)
+    }
+}
+
+impl Interpreter {
+    /// Converts the key of a computed member access, like `o[key]`, with `ToPropertyKey`.
+    ///
+    /// Only objects are converted here, since the primitive keys are converted when the property
+    /// is looked up.
+    pub(crate) fn member_key(&mut self, key: Value) -> ResultValue {
+        if key.is_object() {
+            self.to_property_key(&key)
+        } else {
+            Ok(key)
+        }
+    }
+
+    /// Gets the value of a field of an object, calling its getter, with the object as `this`, if
+    /// it is an accessor property.
+    ///
+    /// This is the `[[Get]]` of the builtins, which use it instead of `Value::get_field` whenever
+    /// the field may be an accessor property.
+    pub(crate) fn get_field_value<F>(&mut self, obj: &Value, field: F) -> ResultValue
+    where
+        F: Into<Value>,
+    {
+        let field = obj.lookup_field(field.into());
+        self.field_value(obj, field)
+    }
+
+    /// Gets the value of a string keyed field of an object, like `get_field_value`, using an
+    /// inline cache.
+    pub(crate) fn get_field_value_cached(
+        &mut self,
+        obj: &Value,
+        field: &str,
+        cache: &PropertyCache,
+    ) -> ResultValue {
+        let field = obj.lookup_field_cached(field, cache);
+        self.field_value(obj, field)
+    }
+
+    /// Sets a field of an object, calling its setter, with the object as `this`, if it is an
+    /// accessor property of the object or of its prototype chain.
+    ///
+    /// An accessor property without a setter is left as it is.
+    pub(crate) fn set_field_value(
+        &mut self,
+        obj: &Value,
+        field: Value,
+        value: Value,
+    ) -> Result<(), Value> {
+        let value = if field.is_string() && field.to_string() == "length" {
+            self.length_value(obj, value)?
+        } else {
+            value
+        };
+        if !field.is_symbol() && field.as_array_index().is_none() {
+            if let Some(accessor) = obj.find_accessor(&field.to_string()) {
+                return self.call_setter(obj, accessor, value);
+            }
+        }
+        obj.set_field(field, value);
+        Ok(())
+    }
+
+    /// Sets a string keyed field of an object, like `set_field_value`, using an inline cache.
+    pub(crate) fn set_field_value_cached(
+        &mut self,
+        obj: &Value,
+        field: &str,
+        value: Value,
+        cache: &PropertyCache,
+    ) -> Result<(), Value> {
+        let value = if field == "length" {
+            self.length_value(obj, value)?
+        } else {
+            value
+        };
+        match obj.set_field_cached(field, value.clone(), cache) {
+            Some(accessor) => self.call_setter(obj, accessor, value),
+            None => Ok(()),
+        }
+    }
+
+    /// Converts the value assigned to the `length` of an object into an array length if the
+    /// object is an array, which throws a `RangeError` if it isn't a valid one.
+    fn length_value(&mut self, obj: &Value, value: Value) -> ResultValue {
+        if obj
+            .as_object()
+            .is_some_and(|obj| obj.kind == ObjectKind::Array)
+        {
+            let length = Array::to_array_length(&value, self)?;
+            return Ok(Value::from(f64::from(length)));
+        }
+        Ok(value)
+    }
+
+    /// Gets the value of a resolved field, calling the getter of an accessor property.
+    fn field_value(&mut self, obj: &Value, field: Field) -> ResultValue {
+        match field {
+            Field::Data(value) => Ok(value),
+            Field::Accessor(Property {
+                get: Some(ref getter),
+                ..
+            }) if getter.is_function() => self.call(getter, &mut obj.clone(), &[]),
+            Field::Accessor(_) => Ok(Value::undefined()),
+        }
+    }
+
+    /// Calls the setter of an accessor property, if it has one.
+    fn call_setter(&mut self, obj: &Value, accessor: Property, value: Value) -> Result<(), Value> {
+        match accessor.set {
+            Some(ref setter) if setter.is_function() => {
+                self.call(setter, &mut obj.clone(), &[value]).map(|_| ())
+            }
+            _ => Ok(()),
+        }
     }
 }
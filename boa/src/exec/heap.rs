@@ -0,0 +1,103 @@
+//! Statistics about the garbage collected heap, and explicit collections.
+//!
+//! The collector itself lives in the `gc` crate, which doesn't report what it does, so the
+//! values are counted here when they are allocated, and when the collector finalizes them.
+
+use crate::{
+    builtins::{object::Object, value::ValueData},
+    clock::Instant,
+};
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    mem::size_of,
+    time::Duration,
+};
+
+thread_local! {
+    /// The heap is local to a thread, and so are its statistics.
+    static STATS: RefCell<GcStats> = RefCell::new(GcStats::default());
+}
+
+/// Statistics about the garbage collected heap of the current thread.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    /// The number of live values allocated on the heap, which excludes the immediate values.
+    pub values: usize,
+    /// The number of live objects, which are values too.
+    pub objects: usize,
+    /// The number of collections started with `Interpreter::gc`.
+    pub collections: usize,
+    /// The total time spent in the collections started with `Interpreter::gc`.
+    pub pause_time: Duration,
+    /// The largest heap size reached by the thread, as computed by `heap_size`.
+    pub peak_heap_size: usize,
+}
+
+impl GcStats {
+    /// The approximate size in bytes of the live values.
+    ///
+    /// Memory owned by the values, like the contents of strings or the properties of objects, is
+    /// not counted.
+    pub fn heap_size(&self) -> usize {
+        self.values * size_of::<ValueData>() + self.objects * size_of::<Object>()
+    }
+}
+
+impl Display for GcStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "GC statistics:")?;
+        writeln!(f, "  heap size:   {} bytes", self.heap_size())?;
+        writeln!(f, "  peak size:   {} bytes", self.peak_heap_size)?;
+        writeln!(f, "  values:      {}", self.values)?;
+        writeln!(f, "  objects:     {}", self.objects)?;
+        writeln!(f, "  collections: {}", self.collections)?;
+        write!(
+            f,
+            "  pause time:  {:.3} ms",
+            self.pause_time.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Records that a value was allocated on the heap.
+pub(crate) fn record_allocation(data: &ValueData) {
+    STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.values += 1;
+        if let ValueData::Object(_) = data {
+            stats.objects += 1;
+        }
+        stats.peak_heap_size = stats.peak_heap_size.max(stats.heap_size());
+    });
+}
+
+/// Records that a value was finalized by the collector.
+pub(crate) fn record_finalization(data: &ValueData) {
+    // The statistics may already be gone while the heap is torn down at the end of the thread.
+    let _ = STATS.try_with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.values = stats.values.saturating_sub(1);
+        if let ValueData::Object(_) = data {
+            stats.objects = stats.objects.saturating_sub(1);
+        }
+    });
+}
+
+/// Runs a collection, and records how long it took.
+pub(crate) fn collect() {
+    let start = Instant::now();
+    gc::force_collect();
+    let pause = start.elapsed();
+
+    STATS.with(|stats| {
+        let mut stats = stats.borrow_mut();
+        stats.collections += 1;
+        stats.pause_time += pause;
+    });
+}
+
+/// Gets the statistics of the heap of the current thread.
+pub(crate) fn stats() -> GcStats {
+    STATS.with(|stats| *stats.borrow())
+}
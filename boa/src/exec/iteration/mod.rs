@@ -4,10 +4,12 @@ use super::{Executable, Interpreter};
 use crate::{
     builtins::value::{ResultValue, Value},
     environment::lexical_environment::new_declarative_environment,
-    syntax::ast::node::{DoWhileLoop, ForLoop, WhileLoop},
+    interner::Sym,
+    syntax::ast::node::{DoWhileLoop, ForLoop, Node, WhileLoop},
     BoaProfiler,
 };
-use std::borrow::Borrow;
+use gc::GcCell;
+use std::{borrow::Borrow, slice};
 
 impl Executable for ForLoop {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
@@ -20,9 +22,27 @@ impl Executable for ForLoop {
             )));
         }
 
+        let res = self.run_iterations(interpreter);
+
+        // pop the block env, even if the loop failed
+        let _ = interpreter.realm_mut().environment.pop();
+
+        res
+    }
+}
+
+impl ForLoop {
+    /// Runs the initialization and the iterations of the loop, in its block environment.
+    fn run_iterations(&self, interpreter: &mut Interpreter) -> ResultValue {
+        let mut per_iteration = Vec::new();
         if let Some(init) = self.init() {
+            interpreter.declare_lexical(slice::from_ref(init));
             init.run(interpreter)?;
+            if let Node::LetDeclList(list) = init {
+                per_iteration.extend(list.as_ref().iter().flat_map(|decl| decl.names()));
+            }
         }
+        copy_iteration_environment(interpreter, &per_iteration);
 
         while self
             .condition()
@@ -31,19 +51,37 @@ impl Executable for ForLoop {
             .unwrap_or(true)
         {
             self.body().run(interpreter)?;
+            copy_iteration_environment(interpreter, &per_iteration);
 
             if let Some(final_expr) = self.final_expr() {
                 final_expr.run(interpreter)?;
             }
         }
 
-        // pop the block env
-        let _ = interpreter.realm_mut().environment.pop();
-
         Ok(Value::undefined())
     }
 }
 
+/// Replaces the environment of a loop with a new one, holding a copy of its `let` bindings, so
+/// that the closures created by an iteration keep the values of that iteration.
+///
+/// More: <https://tc39.es/ecma262/#sec-createperiterationenvironment>
+fn copy_iteration_environment(interpreter: &mut Interpreter, bindings: &[Sym]) {
+    if bindings.is_empty() {
+        return;
+    }
+
+    let env = &mut interpreter.realm_mut().environment;
+    let last = env.pop().expect("the loop has an environment");
+    let next = new_declarative_environment(Some(env.get_current_environment_ref().clone()));
+    for &name in bindings {
+        let value = GcCell::borrow(&last).get_binding_value(name, true);
+        next.borrow_mut().create_mutable_binding(name, false);
+        next.borrow_mut().initialize_binding(name, value);
+    }
+    env.push(next);
+}
+
 impl Executable for WhileLoop {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let mut result = Value::undefined();
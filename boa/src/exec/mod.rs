@@ -7,9 +7,11 @@ mod declaration;
 mod exception;
 mod expression;
 mod field;
+pub(crate) mod heap;
 mod iteration;
 mod object;
 mod operator;
+mod regexp;
 mod return_smt;
 mod spread;
 mod statement_list;
@@ -18,27 +20,48 @@ mod switch;
 mod tests;
 mod throw;
 mod try_node;
+mod with;
 
 use crate::{
     builtins::{
-        function::{Function as FunctionObject, FunctionBody, ThisMode},
+        array::Array,
+        function::{Function as FunctionObject, FunctionBody, TailCall, ThisMode},
+        intrinsics::Intrinsic,
         object::{
-            internal_methods_trait::ObjectInternalMethods, Object, ObjectKind, INSTANCE_PROTOTYPE,
-            PROTOTYPE,
+            internal_methods_trait::ObjectInternalMethods, Object, INSTANCE_PROTOTYPE, PROTOTYPE,
         },
         property::Property,
         value::{ResultValue, Value, ValueData},
-        BigInt, Number,
     },
+    clock::{self, Clock},
+    coverage::Coverage,
+    debugger::Debugger,
+    host::HostPolicy,
+    job::Job,
+    profiler::ProfileRecorder,
+    random::{self, Xorshift128Plus},
     realm::Realm,
-    syntax::ast::{
-        constant::Const,
-        node::{FormalParameter, Node, StatementList},
+    syntax::{
+        ast::{
+            constant::Const,
+            node::{FormalParameter, Node, StatementList},
+        },
+        optimizer::OptimizerOptions,
     },
     BoaProfiler,
 };
-use std::convert::TryFrom;
-use std::{borrow::Borrow, ops::Deref};
+use std::{future::Future, mem, rc::Rc};
+
+pub use self::heap::GcStats;
+
+/// The default maximum number of nested calls, beyond which a `RangeError` is thrown.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
+/// The stack space left below which a call runs on a new stack segment.
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// The size of the stack segments allocated for deep recursion.
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
 
 pub trait Executable {
     /// Runs this executable in the given executor.
@@ -50,8 +73,30 @@ pub trait Executable {
 pub struct Interpreter {
     /// Wether it's running a return statement.
     is_return: bool,
+    /// The call made by the running `return` statement, which is run by the caller of the function.
+    pub(crate) tail_call: Option<TailCall>,
+    /// The number of nested calls.
+    call_depth: usize,
+    /// The maximum number of nested calls.
+    max_call_depth: usize,
+    /// The number of `try` blocks, in the running function, that the code runs in.
+    pub(crate) try_depth: usize,
     /// realm holds both the global object and the environment
     pub realm: Realm,
+    /// The optimizations to run on the scripts before executing them.
+    optimizer_options: OptimizerOptions,
+    /// The debugger called before each statement, if one is attached.
+    pub(crate) debugger: Option<Box<dyn Debugger>>,
+    /// The counts of the statements and branches which ran, if the coverage is enabled.
+    pub(crate) coverage: Option<Coverage>,
+    /// The recorder of the calls, if the profiling is started.
+    pub(crate) profile: Option<ProfileRecorder>,
+    /// The clock the scripts read the time from, if it isn't the clock of the host.
+    clock: Option<Box<dyn Clock>>,
+    /// The generator of `Math.random`, if it is seeded.
+    random: Option<Xorshift128Plus>,
+    /// The policy deciding which operations of the builtins happen, if there is one.
+    pub(crate) host_policy: Option<Box<dyn HostPolicy>>,
 }
 
 impl Interpreter {
@@ -60,7 +105,190 @@ impl Interpreter {
         Self {
             realm,
             is_return: false,
+            tail_call: None,
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            try_depth: 0,
+            optimizer_options: OptimizerOptions::default(),
+            debugger: None,
+            coverage: None,
+            profile: None,
+            clock: None,
+            random: None,
+            host_policy: None,
+        }
+    }
+
+    /// Gets the optimizations run on the scripts before executing them.
+    pub fn optimizer_options(&self) -> OptimizerOptions {
+        self.optimizer_options
+    }
+
+    /// Sets the optimizations to run on the scripts before executing them.
+    pub fn set_optimizer_options(&mut self, options: OptimizerOptions) {
+        self.optimizer_options = options;
+    }
+
+    /// Gets the maximum number of nested calls.
+    pub fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Sets the maximum number of nested calls, beyond which a `RangeError` is thrown.
+    ///
+    /// Calls in tail position don't count, as they replace the frame of their caller.
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    /// Gets the number of nested calls of the running code, which is 0 in the script itself.
+    ///
+    /// Calls in tail position replace the frame of their caller, so they don't add to it.
+    pub fn call_depth(&self) -> usize {
+        self.call_depth
+    }
+
+    /// Attaches a debugger, which is called before each statement, replacing the previous one.
+    pub fn set_debugger(&mut self, debugger: Box<dyn Debugger>) {
+        self.debugger = Some(debugger);
+    }
+
+    /// Detaches the debugger, and returns it.
+    pub fn take_debugger(&mut self) -> Option<Box<dyn Debugger>> {
+        self.debugger.take()
+    }
+
+    /// Sets the clock the scripts read the current time from, instead of the clock of the host.
+    ///
+    /// With a `FixedClock` and a seed set with `set_random_seed`, a script runs the same way
+    /// every time, which is how replayed or sandboxed code is made reproducible.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Some(clock);
+    }
+
+    /// Seeds the generator of `Math.random`, which then produces the same numbers for the same
+    /// seed, instead of the random numbers of the host.
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.random = Some(Xorshift128Plus::new(seed));
+    }
+
+    /// Gets the number of milliseconds elapsed since the Unix epoch, from the clock set with
+    /// `set_clock`, or the clock of the host.
+    pub(crate) fn now(&mut self) -> f64 {
+        match self.clock.as_mut() {
+            Some(clock) => clock.now(),
+            None => clock::now(),
+        }
+    }
+
+    /// Gets a random number in the range `[0, 1)`, from the seeded generator if there is one.
+    pub(crate) fn random(&mut self) -> f64 {
+        match self.random.as_mut() {
+            Some(generator) => generator.next_f64(),
+            None => random::random(),
+        }
+    }
+
+    /// Runs the body of a function in a new frame of the call stack.
+    ///
+    /// The native stack is extended on the heap when it runs low, so the depth of the recursion
+    /// is only bounded by the maximum call depth.
+    pub(crate) fn run_frame<F>(&mut self, body: F) -> ResultValue
+    where
+        F: FnOnce(&mut Self) -> ResultValue,
+    {
+        if self.call_depth >= self.max_call_depth {
+            return self.throw_range_error("Maximum call stack size exceeded");
         }
+        self.call_depth += 1;
+        let try_depth = mem::replace(&mut self.try_depth, 0);
+
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || body(self));
+
+        self.try_depth = try_depth;
+        self.call_depth -= 1;
+        result
+    }
+
+    /// Checks if a call made by a `return` statement here would be in tail position.
+    pub(crate) fn in_tail_position(&self) -> bool {
+        self.call_depth > 0 && self.try_depth == 0
+    }
+
+    /// Runs the calls left by the `return` statements of the running function, until there is
+    /// none, and returns the result of the last one.
+    pub(crate) fn run_tail_calls(&mut self, mut result: ResultValue) -> ResultValue {
+        while let Some(tail_call) = self.tail_call.take() {
+            self.is_return = false;
+            result = tail_call.run(self);
+        }
+        result
+    }
+
+    /// Runs a garbage collection on the heap of the current thread.
+    pub fn gc(&mut self) {
+        heap::collect();
+    }
+
+    /// Gets statistics about the garbage collected heap of the current thread.
+    pub fn gc_stats(&self) -> GcStats {
+        heap::stats()
+    }
+
+    /// Enqueues a job in the job queue of the realm.
+    pub fn enqueue_job(&mut self, job: Job) {
+        let job_queue = self.realm.job_queue.clone();
+        job_queue.enqueue_promise_job(job, self);
+    }
+
+    /// Runs the jobs in the job queue of the realm, until it is empty.
+    ///
+    /// If a job throws, the error is returned.
+    pub fn run_jobs(&mut self) -> Result<(), Value> {
+        let job_queue = self.realm.job_queue.clone();
+        job_queue.run_jobs(self)
+    }
+
+    /// Runs the jobs in the job queue of the realm, until it is empty, from an asynchronous
+    /// context.
+    pub fn run_jobs_async(&mut self) -> impl Future<Output = Result<(), Value>> + '_ {
+        let job_queue = self.realm.job_queue.clone();
+        async move { job_queue.run_jobs_async(self).await }
+    }
+
+    /// Adds a function to the global object, which runs the given closure when it is called.
+    ///
+    /// The state captured by the closure isn't traced by the garbage collector, so it must not
+    /// hold any `Value`.
+    pub fn register_global_closure<F>(&mut self, name: &str, length: i32, closure: F)
+    where
+        F: Fn(&mut Value, &[Value], &mut Interpreter) -> ResultValue + 'static,
+    {
+        let mut func = Object::function();
+        func.set_func(FunctionObject::closure(Vec::new(), Rc::new(closure)));
+
+        let func = Value::from(func);
+        func.set_field("length", length);
+        self.realm.global_obj.set_field(Value::from(name), func);
+    }
+
+    /// Gets the names and the values of the global bindings: the top-level declarations, then the
+    /// properties of the global object.
+    ///
+    /// The top-level `var`, `let`, `const` and function declarations aren't properties of the
+    /// global object in this engine, so this is the way to list every name a script can refer to.
+    pub fn global_bindings(&self) -> Vec<(String, Value)> {
+        self.realm
+            .environment
+            .environments()
+            .last()
+            .map_or_else(Vec::new, |global| (**global).borrow().bindings())
+    }
+
+    /// Creates an array holding the given values, for the hosts which pass lists to the scripts.
+    pub fn create_array(&self, elements: &[Value]) -> Value {
+        let array = Array::new_array(self).expect("could not create an array");
+        Array::construct_array(&array, elements).expect("could not fill an array")
     }
 
     /// Retrieves the `Realm` of this executor.
@@ -74,6 +302,8 @@ impl Interpreter {
     }
 
     /// Utility to create a function Value for Function Declarations, Arrow Functions or Function Expressions
+    ///
+    /// `simple` tells if the scope analysis found that the function is a simple function.
     pub(crate) fn create_function<P, B>(
         &mut self,
         params: P,
@@ -81,18 +311,13 @@ impl Interpreter {
         this_mode: ThisMode,
         constructable: bool,
         callable: bool,
+        simple: bool,
     ) -> Value
     where
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
-        let function_prototype = &self
-            .realm
-            .environment
-            .get_global_object()
-            .expect("Could not get the global object")
-            .get_field("Function")
-            .get_field(PROTOTYPE);
+        let function_prototype = &self.realm.intrinsic(Intrinsic::FunctionPrototype);
 
         // Every new function has a prototype property pre-made
         let global_val = &self
@@ -104,7 +329,7 @@ impl Interpreter {
 
         let params = params.into();
         let params_len = params.len();
-        let func = FunctionObject::new(
+        let mut func = FunctionObject::new(
             params,
             Some(self.realm.environment.get_current_environment().clone()),
             FunctionBody::Ordinary(body.into()),
@@ -112,6 +337,7 @@ impl Interpreter {
             constructable,
             callable,
         );
+        func.set_simple(simple);
 
         let mut new_func = Object::function();
         new_func.set_func(func);
@@ -130,169 +356,35 @@ impl Interpreter {
         this: &mut Value,
         arguments_list: &[Value],
     ) -> ResultValue {
-        match *f.data() {
+        let is_callable = match *f.data() {
+            ValueData::Object(ref obj) => obj.borrow().func.is_some(),
+            _ => false,
+        };
+        if !is_callable {
+            let description = match *f.data() {
+                ValueData::String(ref string) => format!("\"{}\"", string),
+                ValueData::Object(_) => String::from("object"),
+                ref data => data.to_string(),
+            };
+            return self.throw_type_error(format!("{} is not a function", description));
+        }
+
+        self.enter_profile_frame(f);
+        let result = match *f.data() {
             ValueData::Object(ref obj) => {
                 let obj = (**obj).borrow();
-                let func = obj.func.as_ref().expect("Expected function");
+                let func = obj.func.as_ref().expect("the callable was checked");
                 func.call(&mut f.clone(), arguments_list, self, this)
             }
-            _ => Err(Value::undefined()),
-        }
-    }
-
-    /// Converts a value into a rust heap allocated string.
-    #[allow(clippy::wrong_self_convention)]
-    pub fn to_string(&mut self, value: &Value) -> Result<String, Value> {
-        match value.data() {
-            ValueData::Null => Ok("null".to_owned()),
-            ValueData::Undefined => Ok("undefined".to_owned()),
-            ValueData::Boolean(boolean) => Ok(boolean.to_string()),
-            ValueData::Rational(rational) => Ok(Number::to_native_string(*rational)),
-            ValueData::Integer(integer) => Ok(integer.to_string()),
-            ValueData::String(string) => Ok(string.clone()),
-            ValueData::Symbol(_) => {
-                self.throw_type_error("can't convert symbol to string")?;
-                unreachable!();
-            }
-            ValueData::BigInt(ref bigint) => Ok(bigint.to_string()),
-            ValueData::Object(_) => {
-                let primitive = self.to_primitive(&mut value.clone(), Some("string"));
-                self.to_string(&primitive)
-            }
-        }
-    }
-
-    /// Helper function.
-    #[allow(clippy::wrong_self_convention)]
-    pub fn to_bigint(&mut self, value: &Value) -> Result<BigInt, Value> {
-        match value.data() {
-            ValueData::Null => {
-                self.throw_type_error("cannot convert null to a BigInt")?;
-                unreachable!();
-            }
-            ValueData::Undefined => {
-                self.throw_type_error("cannot convert undefined to a BigInt")?;
-                unreachable!();
-            }
-            ValueData::String(ref string) => Ok(BigInt::from_string(string, self)?),
-            ValueData::Boolean(true) => Ok(BigInt::from(1)),
-            ValueData::Boolean(false) => Ok(BigInt::from(0)),
-            ValueData::Integer(num) => Ok(BigInt::from(*num)),
-            ValueData::Rational(num) => {
-                if let Ok(bigint) = BigInt::try_from(*num) {
-                    return Ok(bigint);
-                }
-                self.throw_type_error(format!(
-                    "The number {} cannot be converted to a BigInt because it is not an integer",
-                    num
-                ))?;
-                unreachable!();
-            }
-            ValueData::BigInt(b) => Ok(b.clone()),
-            ValueData::Object(_) => {
-                let primitive = self.to_primitive(&mut value.clone(), Some("number"));
-                self.to_bigint(&primitive)
-            }
-            ValueData::Symbol(_) => {
-                self.throw_type_error("cannot convert Symbol to a BigInt")?;
-                unreachable!();
-            }
-        }
-    }
-
-    /// Converts an array object into a rust vector of values.
-    ///
-    /// This is useful for the spread operator, for any other object an `Err` is returned
-    pub(crate) fn extract_array_properties(&mut self, value: &Value) -> Result<Vec<Value>, ()> {
-        if let ValueData::Object(ref x) = *value.deref().borrow() {
-            // Check if object is array
-            if x.deref().borrow().kind == ObjectKind::Array {
-                let length: i32 = self.value_to_rust_number(&value.get_field("length")) as i32;
-                let values: Vec<Value> = (0..length)
-                    .map(|idx| value.get_field(idx.to_string()))
-                    .collect();
-                return Ok(values);
-            }
-
-            return Err(());
-        }
-
-        Err(())
-    }
-
-    /// <https://tc39.es/ecma262/#sec-ordinarytoprimitive>
-    pub(crate) fn ordinary_to_primitive(&mut self, o: &mut Value, hint: &str) -> Value {
-        debug_assert!(o.get_type() == "object");
-        debug_assert!(hint == "string" || hint == "number");
-        let method_names: Vec<&str> = if hint == "string" {
-            vec!["toString", "valueOf"]
-        } else {
-            vec!["valueOf", "toString"]
+            _ => unreachable!("only objects can be callable"),
         };
-        for name in method_names.iter() {
-            let method: Value = o.get_field(*name);
-            if method.is_function() {
-                let result = self.call(&method, o, &[]);
-                match result {
-                    Ok(val) => {
-                        if val.is_object() {
-                            // TODO: throw exception
-                            continue;
-                        } else {
-                            return val;
-                        }
-                    }
-                    Err(_) => continue,
-                }
-            }
-        }
 
-        Value::undefined()
-    }
+        self.exit_profile_frame();
 
-    /// The abstract operation ToPrimitive takes an input argument and an optional argument PreferredType.
-    /// <https://tc39.es/ecma262/#sec-toprimitive>
-    #[allow(clippy::wrong_self_convention)]
-    pub(crate) fn to_primitive(
-        &mut self,
-        input: &mut Value,
-        preferred_type: Option<&str>,
-    ) -> Value {
-        let mut hint: &str;
-        match (*input).deref() {
-            ValueData::Object(_) => {
-                hint = match preferred_type {
-                    None => "default",
-                    Some(pt) => match pt {
-                        "string" => "string",
-                        "number" => "number",
-                        _ => "default",
-                    },
-                };
-
-                // Skip d, e we don't support Symbols yet
-                // TODO: add when symbols are supported
-                if hint == "default" {
-                    hint = "number";
-                };
-
-                self.ordinary_to_primitive(input, hint)
-            }
-            _ => input.clone(),
-        }
-    }
+        // unset the early return flag, as the callers of builtins don't
+        self.is_return = false;
 
-    /// The abstract operation ToPropertyKey takes argument argument. It converts argument to a value that can be used as a property key.
-    ///
-    /// https://tc39.es/ecma262/#sec-topropertykey
-    #[allow(clippy::wrong_self_convention)]
-    pub(crate) fn to_property_key(&mut self, value: &mut Value) -> ResultValue {
-        let key = self.to_primitive(value, Some("string"));
-        if key.is_symbol() {
-            Ok(key)
-        } else {
-            self.to_string(&key).map(Value::from)
-        }
+        result
     }
 
     /// https://tc39.es/ecma262/#sec-hasproperty
@@ -307,107 +399,6 @@ impl Interpreter {
             false
         }
     }
-
-    /// The abstract operation ToObject converts argument to a value of type Object
-    /// https://tc39.es/ecma262/#sec-toobject
-    #[allow(clippy::wrong_self_convention)]
-    pub(crate) fn to_object(&mut self, value: &Value) -> ResultValue {
-        match value.data() {
-            ValueData::Undefined | ValueData::Integer(_) | ValueData::Null => {
-                Err(Value::undefined())
-            }
-            ValueData::Boolean(_) => {
-                let proto = self
-                    .realm
-                    .environment
-                    .get_binding_value("Boolean")
-                    .get_field(PROTOTYPE);
-
-                let bool_obj = Value::new_object_from_prototype(proto, ObjectKind::Boolean);
-                bool_obj.set_internal_slot("BooleanData", value.clone());
-                Ok(bool_obj)
-            }
-            ValueData::Rational(_) => {
-                let proto = self
-                    .realm
-                    .environment
-                    .get_binding_value("Number")
-                    .get_field(PROTOTYPE);
-                let number_obj = Value::new_object_from_prototype(proto, ObjectKind::Number);
-                number_obj.set_internal_slot("NumberData", value.clone());
-                Ok(number_obj)
-            }
-            ValueData::String(_) => {
-                let proto = self
-                    .realm
-                    .environment
-                    .get_binding_value("String")
-                    .get_field(PROTOTYPE);
-                let string_obj = Value::new_object_from_prototype(proto, ObjectKind::String);
-                string_obj.set_internal_slot("StringData", value.clone());
-                Ok(string_obj)
-            }
-            ValueData::Object(_) | ValueData::Symbol(_) => Ok(value.clone()),
-            ValueData::BigInt(_) => {
-                let proto = self
-                    .realm
-                    .environment
-                    .get_binding_value("BigInt")
-                    .get_field(PROTOTYPE);
-                let bigint_obj = Value::new_object_from_prototype(proto, ObjectKind::BigInt);
-                bigint_obj.set_internal_slot("BigIntData", value.clone());
-                Ok(bigint_obj)
-            }
-        }
-    }
-
-    pub(crate) fn value_to_rust_number(&mut self, value: &Value) -> f64 {
-        match *value.deref().borrow() {
-            ValueData::Null => f64::from(0),
-            ValueData::Boolean(boolean) => {
-                if boolean {
-                    f64::from(1)
-                } else {
-                    f64::from(0)
-                }
-            }
-            ValueData::Rational(num) => num,
-            ValueData::Integer(num) => f64::from(num),
-            ValueData::String(ref string) => string.parse::<f64>().unwrap(),
-            ValueData::BigInt(ref bigint) => bigint.to_f64(),
-            ValueData::Object(_) => {
-                let prim_value = self.to_primitive(&mut (value.clone()), Some("number"));
-                self.to_string(&prim_value)
-                    .expect("cannot convert value to string")
-                    .parse::<f64>()
-                    .expect("cannot parse value to f64")
-            }
-            _ => {
-                // TODO: Make undefined?
-                f64::from(0)
-            }
-        }
-    }
-
-    fn set_value(&mut self, node: &Node, value: Value) -> ResultValue {
-        match node {
-            Node::Identifier(ref name) => {
-                self.realm
-                    .environment
-                    .set_mutable_binding(name.as_ref(), value.clone(), true);
-                Ok(value)
-            }
-            Node::GetConstField(ref get_const_field_node) => Ok(get_const_field_node
-                .obj()
-                .run(self)?
-                .set_field(get_const_field_node.field(), value)),
-            Node::GetField(ref get_field) => Ok(get_field
-                .obj()
-                .run(self)?
-                .set_field(get_field.field().run(self)?, value)),
-            _ => panic!("TypeError: invalid assignment to {}", node),
-        }
-    }
 }
 
 impl Executable for Node {
@@ -422,16 +413,10 @@ impl Executable for Node {
             // we can't move String from Const into value, because const is a garbage collected value
             // Which means Drop() get's called on Const, but str will be gone at that point.
             // Do Const values need to be garbage collected? We no longer need them once we've generated Values
-            Node::Const(Const::String(ref value)) => Ok(Value::string(value.to_string())),
+            Node::Const(Const::String(ref value)) => Ok(Value::string(value.clone())),
             Node::Const(Const::Bool(value)) => Ok(Value::boolean(value)),
             Node::Block(ref block) => block.run(interpreter),
-            Node::Identifier(ref name) => {
-                let val = interpreter
-                    .realm()
-                    .environment
-                    .get_binding_value(name.as_ref());
-                Ok(val)
-            }
+            Node::Identifier(ref name) => interpreter.get_identifier_value(name),
             Node::GetConstField(ref get_const_field_node) => get_const_field_node.run(interpreter),
             Node::GetField(ref get_field) => get_field.run(interpreter),
             Node::Call(ref expr) => expr.run(interpreter),
@@ -450,6 +435,7 @@ impl Executable for Node {
             Node::BinOp(ref op) => op.run(interpreter),
             Node::UnaryOp(ref op) => op.run(interpreter),
             Node::New(ref call) => call.run(interpreter),
+            Node::RegExpLiteral(ref regexp) => regexp.run(interpreter),
             Node::Return(ref ret) => ret.run(interpreter),
             Node::Throw(ref throw) => throw.run(interpreter),
             Node::Assign(ref op) => op.run(interpreter),
@@ -459,9 +445,17 @@ impl Executable for Node {
             Node::Spread(ref spread) => spread.run(interpreter),
             Node::This => {
                 // Will either return `this` binding or undefined
-                Ok(interpreter.realm().environment.get_this_binding())
+                let this = interpreter.realm().environment.get_this_binding();
+                // The builtins are only added to the global object as they are used, so they are
+                // all added before a script gets hold of it.
+                if this.strict_equals(&interpreter.realm().global_obj) {
+                    interpreter.realm().init_builtins();
+                }
+                Ok(this)
             }
+            Node::NewTarget => Ok(interpreter.realm().environment.get_new_target()),
             Node::Try(ref try_node) => try_node.run(interpreter),
+            Node::With(ref with) => with.run(interpreter),
             ref i => unimplemented!("{:?}", i),
         }
     }
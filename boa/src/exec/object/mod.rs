@@ -2,12 +2,15 @@
 
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::value::{ResultValue, Value},
+    builtins::{
+        property::Property,
+        value::{ResultValue, Value},
+    },
     syntax::ast::node::MethodDefinitionKind,
     syntax::ast::node::{Object, PropertyDefinition},
 };
 
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 
 impl Executable for Object {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
@@ -26,12 +29,28 @@ impl Executable for Object {
                         .set_field(&key.clone(), value.run(interpreter)?);
                 }
                 PropertyDefinition::MethodDefinition(kind, name, func) => {
-                    if let MethodDefinitionKind::Ordinary = kind {
-                        obj.borrow()
-                            .set_field(&name.clone(), func.run(interpreter)?);
-                    } else {
-                        // TODO: Implement other types of MethodDefinitionKinds.
-                        unimplemented!("other types of property method definitions.");
+                    let func = func.run(interpreter)?;
+                    match kind {
+                        MethodDefinitionKind::Ordinary => {
+                            obj.borrow().set_field(&name.clone(), func);
+                        }
+                        MethodDefinitionKind::Get | MethodDefinitionKind::Set => {
+                            // A getter and a setter of the same name make up one property.
+                            let own = obj
+                                .as_object()
+                                .and_then(|o| o.properties.get(name).map(Cow::into_owned));
+                            let accessor = own
+                                .filter(Property::is_accessor_property)
+                                .unwrap_or_else(|| {
+                                    Property::new().enumerable(true).configurable(true)
+                                });
+                            let accessor = if let MethodDefinitionKind::Get = kind {
+                                accessor.get(func)
+                            } else {
+                                accessor.set(func)
+                            };
+                            obj.set_property(name.to_string(), accessor);
+                        }
                     }
                 }
                 i => unimplemented!("{:?} type of property", i),
@@ -2,7 +2,14 @@
 
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::value::{ResultValue, Value},
+    builtins::{
+        object::{INSTANCE_PROTOTYPE, PROTOTYPE},
+        value::{
+            f64_to_int32, f64_to_uint32, string_to_bigint, PreferredType, ResultValue, Value,
+            ValueData,
+        },
+        BigInt,
+    },
     environment::lexical_environment::VariableScope,
     syntax::ast::{
         node::{Assign, BinOp, Node, UnaryOp},
@@ -10,7 +17,7 @@ use crate::{
     },
     BoaProfiler,
 };
-use std::borrow::BorrowMut;
+use std::{borrow::BorrowMut, slice};
 
 impl Executable for Assign {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
@@ -20,26 +27,28 @@ impl Executable for Assign {
             Node::Identifier(ref name) => {
                 let environment = &mut interpreter.realm_mut().environment;
 
-                if environment.has_binding(name.as_ref()) {
+                if environment.has_identifier_binding(name) {
                     // Binding already exists
-                    environment.set_mutable_binding(name.as_ref(), val.clone(), true);
+                    interpreter.set_identifier_value(name, val.clone())?;
                 } else {
-                    environment.create_mutable_binding(
-                        name.as_ref().to_owned(),
-                        true,
-                        VariableScope::Function,
-                    );
-                    environment.initialize_binding(name.as_ref(), val.clone());
+                    environment.create_mutable_binding(name.sym(), true, VariableScope::Function);
+                    environment.initialize_binding(name.sym(), val.clone());
                 }
             }
             Node::GetConstField(ref get_const_field) => {
                 let val_obj = get_const_field.obj().run(interpreter)?;
-                val_obj.set_field(get_const_field.field(), val.clone());
+                interpreter.set_field_value_cached(
+                    &val_obj,
+                    get_const_field.field(),
+                    val.clone(),
+                    get_const_field.cache(),
+                )?;
             }
             Node::GetField(ref get_field) => {
                 let val_obj = get_field.obj().run(interpreter)?;
                 let val_field = get_field.field().run(interpreter)?;
-                val_obj.set_field(val_field, val.clone());
+                let val_field = interpreter.member_key(val_field)?;
+                interpreter.set_field_value(&val_obj, val_field, val.clone())?;
             }
             _ => (),
         }
@@ -53,40 +62,34 @@ impl Executable for BinOp {
             op::BinOp::Num(op) => {
                 let v_a = self.lhs().run(interpreter)?;
                 let v_b = self.rhs().run(interpreter)?;
-                Ok(match op {
-                    NumOp::Add => v_a + v_b,
-                    NumOp::Sub => v_a - v_b,
-                    NumOp::Mul => v_a * v_b,
-                    NumOp::Exp => v_a.as_num_to_power(v_b),
-                    NumOp::Div => v_a / v_b,
-                    NumOp::Mod => v_a % v_b,
-                })
+                Self::run_num_op(interpreter, op, &v_a, &v_b)
             }
             op::BinOp::Bit(op) => {
                 let v_a = self.lhs().run(interpreter)?;
                 let v_b = self.rhs().run(interpreter)?;
-                Ok(match op {
-                    BitOp::And => v_a & v_b,
-                    BitOp::Or => v_a | v_b,
-                    BitOp::Xor => v_a ^ v_b,
-                    BitOp::Shl => v_a << v_b,
-                    BitOp::Shr => v_a >> v_b,
-                    // TODO Fix
-                    BitOp::UShr => v_a >> v_b,
-                })
+                Self::run_bit_op(interpreter, op, &v_a, &v_b)
             }
             op::BinOp::Comp(op) => {
                 let mut v_a = self.lhs().run(interpreter)?;
                 let mut v_b = self.rhs().run(interpreter)?;
                 Ok(Value::from(match op {
-                    CompOp::Equal => v_a.equals(v_b.borrow_mut(), interpreter),
-                    CompOp::NotEqual => !v_a.equals(v_b.borrow_mut(), interpreter),
+                    CompOp::Equal => v_a.equals(v_b.borrow_mut(), interpreter)?,
+                    CompOp::NotEqual => !v_a.equals(v_b.borrow_mut(), interpreter)?,
                     CompOp::StrictEqual => v_a.strict_equals(&v_b),
                     CompOp::StrictNotEqual => !v_a.strict_equals(&v_b),
-                    CompOp::GreaterThan => v_a.to_number() > v_b.to_number(),
-                    CompOp::GreaterThanOrEqual => v_a.to_number() >= v_b.to_number(),
-                    CompOp::LessThan => v_a.to_number() < v_b.to_number(),
-                    CompOp::LessThanOrEqual => v_a.to_number() <= v_b.to_number(),
+                    CompOp::GreaterThan => {
+                        Self::less_than(interpreter, &v_b, &v_a, false)? == Some(true)
+                    }
+                    CompOp::GreaterThanOrEqual => {
+                        Self::less_than(interpreter, &v_a, &v_b, true)? == Some(false)
+                    }
+                    CompOp::LessThan => {
+                        Self::less_than(interpreter, &v_a, &v_b, true)? == Some(true)
+                    }
+                    CompOp::LessThanOrEqual => {
+                        Self::less_than(interpreter, &v_b, &v_a, false)? == Some(false)
+                    }
+                    CompOp::InstanceOf => Self::instance_of(interpreter, &v_a, &v_b)?,
                     CompOp::In => {
                         if !v_b.is_object() {
                             return interpreter.throw_type_error(format!(
@@ -94,7 +97,7 @@ impl Executable for BinOp {
                                 v_b.get_type()
                             ));
                         }
-                        let key = interpreter.to_property_key(&mut v_a)?;
+                        let key = interpreter.to_property_key(&v_a)?;
                         interpreter.has_property(&mut v_b, &key)
                     }
                 }))
@@ -115,111 +118,478 @@ impl Executable for BinOp {
             }
             op::BinOp::Assign(op) => match self.lhs() {
                 Node::Identifier(ref name) => {
-                    let v_a = interpreter
-                        .realm()
-                        .environment
-                        .get_binding_value(name.as_ref());
+                    let v_a = interpreter.get_identifier_value(name)?;
                     let v_b = self.rhs().run(interpreter)?;
-                    let value = Self::run_assign(op, v_a, v_b);
-                    interpreter.realm.environment.set_mutable_binding(
-                        name.as_ref(),
-                        value.clone(),
-                        true,
-                    );
+                    let value = Self::run_assign(interpreter, op, &v_a, &v_b)?;
+                    interpreter.set_identifier_value(name, value.clone())?;
                     Ok(value)
                 }
                 Node::GetConstField(ref get_const_field) => {
                     let v_r_a = get_const_field.obj().run(interpreter)?;
-                    let v_a = v_r_a.get_field(get_const_field.field());
+                    let v_a = interpreter.get_field_value_cached(
+                        &v_r_a,
+                        get_const_field.field(),
+                        get_const_field.cache(),
+                    )?;
+                    let v_b = self.rhs().run(interpreter)?;
+                    let value = Self::run_assign(interpreter, op, &v_a, &v_b)?;
+                    interpreter.set_field_value_cached(
+                        &v_r_a,
+                        get_const_field.field(),
+                        value.clone(),
+                        get_const_field.cache(),
+                    )?;
+                    Ok(value)
+                }
+                Node::GetField(ref get_field) => {
+                    let v_r_a = get_field.obj().run(interpreter)?;
+                    let field = get_field.field().run(interpreter)?;
+                    let field = interpreter.member_key(field)?;
+                    let v_a = interpreter.get_field_value(&v_r_a, field.clone())?;
                     let v_b = self.rhs().run(interpreter)?;
-                    let value = Self::run_assign(op, v_a, v_b);
-                    v_r_a.set_field(get_const_field.field(), value.clone());
+                    let value = Self::run_assign(interpreter, op, &v_a, &v_b)?;
+                    interpreter.set_field_value(&v_r_a, field, value.clone())?;
                     Ok(value)
                 }
                 _ => Ok(Value::undefined()),
             },
+            op::BinOp::Comma => {
+                self.lhs().run(interpreter)?;
+                self.rhs().run(interpreter)
+            }
         }
     }
 }
 
 impl BinOp {
+    /// Runs the numeric operators, which convert their operands with `ToNumeric`.
+    ///
+    /// The addition operator converts its operands with `ToPrimitive` first, and concatenates
+    /// them if either of them is a string.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-applystringornumericbinaryoperator
+    fn run_num_op(interpreter: &mut Interpreter, op: NumOp, x: &Value, y: &Value) -> ResultValue {
+        // Loop counters and indices are mostly integers, which don't need to be converted.
+        if let (ValueData::Integer(a), ValueData::Integer(b)) = (x.data(), y.data()) {
+            if let Some(result) = integer_num_op(op, *a, *b) {
+                return Ok(Value::integer(result));
+            }
+        }
+
+        if op == NumOp::Add {
+            let x = interpreter.to_primitive(x, PreferredType::Default)?;
+            let y = interpreter.to_primitive(y, PreferredType::Default)?;
+            if x.is_string() || y.is_string() {
+                let x = interpreter.to_js_string(&x)?;
+                let y = interpreter.to_js_string(&y)?;
+                return Ok(Value::from(x.concat(&y)));
+            }
+            return Self::run_num_op_numeric(interpreter, op, &x, &y);
+        }
+
+        Self::run_num_op_numeric(interpreter, op, x, y)
+    }
+
+    /// Runs a numeric operator once the operands are known not to be concatenated.
+    fn run_num_op_numeric(
+        interpreter: &mut Interpreter,
+        op: NumOp,
+        x: &Value,
+        y: &Value,
+    ) -> ResultValue {
+        let x = interpreter.to_numeric(x)?;
+        let y = interpreter.to_numeric(y)?;
+        match (x.data(), y.data()) {
+            (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => {
+                let (a, b) = (a.clone(), b.clone());
+                Ok(Value::bigint(match op {
+                    NumOp::Add => a + b,
+                    NumOp::Sub => a - b,
+                    NumOp::Mul => a * b,
+                    NumOp::Div | NumOp::Mod if b == 0 => {
+                        return interpreter.throw_range_error("BigInt division by zero");
+                    }
+                    NumOp::Div => a / b,
+                    NumOp::Mod => a % b,
+                    NumOp::Exp if b.to_f64() < 0.0 => {
+                        return interpreter.throw_range_error("BigInt negative exponent");
+                    }
+                    NumOp::Exp => a.pow(&b),
+                }))
+            }
+            (ValueData::BigInt(_), _) | (_, ValueData::BigInt(_)) => interpreter
+                .throw_type_error("cannot mix BigInt and other types, use explicit conversions"),
+            _ => {
+                let (a, b) = (x.to_number(), y.to_number());
+                Ok(Value::rational(match op {
+                    NumOp::Add => a + b,
+                    NumOp::Sub => a - b,
+                    NumOp::Mul => a * b,
+                    NumOp::Div => a / b,
+                    NumOp::Mod => a % b,
+                    NumOp::Exp => exponentiate(a, b),
+                }))
+            }
+        }
+    }
+
+    /// Runs the bitwise and shift operators, which convert their operands with `ToNumeric`.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-applystringornumericbinaryoperator
+    fn run_bit_op(interpreter: &mut Interpreter, op: BitOp, x: &Value, y: &Value) -> ResultValue {
+        let x = interpreter.to_numeric(x)?;
+        let y = interpreter.to_numeric(y)?;
+        match (x.data(), y.data()) {
+            (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => {
+                let (a, b) = (a.clone(), b.clone());
+                Ok(Value::bigint(match op {
+                    BitOp::And => a & b,
+                    BitOp::Or => a | b,
+                    BitOp::Xor => a ^ b,
+                    BitOp::Shl => a << b,
+                    BitOp::Shr => a >> b,
+                    BitOp::UShr => {
+                        return interpreter.throw_type_error(
+                            "BigInts have no unsigned right shift, use >> instead",
+                        );
+                    }
+                }))
+            }
+            (ValueData::BigInt(_), _) | (_, ValueData::BigInt(_)) => interpreter
+                .throw_type_error("cannot mix BigInt and other types, use explicit conversions"),
+            _ => {
+                let (a, b) = (x.to_number(), y.to_number());
+                let shift = f64_to_uint32(b) & 0x1f;
+                Ok(match op {
+                    BitOp::And => Value::integer(f64_to_int32(a) & f64_to_int32(b)),
+                    BitOp::Or => Value::integer(f64_to_int32(a) | f64_to_int32(b)),
+                    BitOp::Xor => Value::integer(f64_to_int32(a) ^ f64_to_int32(b)),
+                    BitOp::Shl => Value::integer(f64_to_int32(a).wrapping_shl(shift)),
+                    BitOp::Shr => Value::integer(f64_to_int32(a) >> shift),
+                    BitOp::UShr => Value::rational(f64::from(f64_to_uint32(a) >> shift)),
+                })
+            }
+        }
+    }
+
+    /// Compares two values with the `<` relation, returning `None` if either of them is `NaN`.
+    ///
+    /// `left_first` tells which operand is converted to a primitive first.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-abstract-relational-comparison
+    fn less_than(
+        interpreter: &mut Interpreter,
+        x: &Value,
+        y: &Value,
+        left_first: bool,
+    ) -> Result<Option<bool>, Value> {
+        if let (ValueData::Integer(a), ValueData::Integer(b)) = (x.data(), y.data()) {
+            return Ok(Some(a < b));
+        }
+
+        let (px, py) = if left_first {
+            let px = interpreter.to_primitive(x, PreferredType::Number)?;
+            (px, interpreter.to_primitive(y, PreferredType::Number)?)
+        } else {
+            let py = interpreter.to_primitive(y, PreferredType::Number)?;
+            (interpreter.to_primitive(x, PreferredType::Number)?, py)
+        };
+
+        match (px.data(), py.data()) {
+            (ValueData::String(ref a), ValueData::String(ref b)) => return Ok(Some(a < b)),
+            (ValueData::BigInt(ref a), ValueData::String(ref b)) => {
+                return Ok(string_to_bigint(&b.to_string_lossy()).map(|b| *a < b));
+            }
+            (ValueData::String(ref a), ValueData::BigInt(ref b)) => {
+                return Ok(string_to_bigint(&a.to_string_lossy()).map(|a| a < *b));
+            }
+            _ => {}
+        }
+
+        let nx = interpreter.to_numeric(&px)?;
+        let ny = interpreter.to_numeric(&py)?;
+        let (a, b) = match (nx.data(), ny.data()) {
+            (ValueData::BigInt(ref a), ValueData::BigInt(ref b)) => return Ok(Some(a < b)),
+            (ValueData::BigInt(ref a), _) => (a.to_f64(), ny.to_number()),
+            (_, ValueData::BigInt(ref b)) => (nx.to_number(), b.to_f64()),
+            _ => (nx.to_number(), ny.to_number()),
+        };
+        if a.is_nan() || b.is_nan() {
+            Ok(None)
+        } else {
+            Ok(Some(a < b))
+        }
+    }
+
+    /// Checks if a value is an instance of a constructor, with the `@@hasInstance` method of the
+    /// constructor if it has one.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-instanceofoperator
+    fn instance_of(
+        interpreter: &mut Interpreter,
+        value: &Value,
+        target: &Value,
+    ) -> Result<bool, Value> {
+        if !target.is_object() {
+            interpreter.throw_type_error(format!(
+                "right-hand side of 'instanceof' should be an object, got {}",
+                target.get_type()
+            ))?;
+        }
+
+        let has_instance = interpreter.well_known_symbol("hasInstance");
+        if has_instance.is_symbol() {
+            let handler = target.get_field(has_instance);
+            if handler.is_function() {
+                let result =
+                    interpreter.call(&handler, &mut target.clone(), slice::from_ref(value))?;
+                return Ok(result.is_true());
+            } else if !handler.is_null_or_undefined() {
+                interpreter.throw_type_error("Symbol.hasInstance is not a function")?;
+            }
+        }
+
+        if !target.is_function() {
+            interpreter.throw_type_error("right-hand side of 'instanceof' is not callable")?;
+        }
+        Self::ordinary_has_instance(interpreter, value, target)
+    }
+
+    /// Checks if the `prototype` of a constructor is in the prototype chain of a value.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-ordinaryhasinstance
+    fn ordinary_has_instance(
+        interpreter: &mut Interpreter,
+        value: &Value,
+        target: &Value,
+    ) -> Result<bool, Value> {
+        if !target.is_function() || !value.is_object() {
+            return Ok(false);
+        }
+
+        let prototype = target.get_field(PROTOTYPE);
+        if !prototype.is_object() {
+            interpreter.throw_type_error(
+                "the prototype of the right-hand side of 'instanceof' is not an object",
+            )?;
+        }
+        let mut object = value.get_internal_slot(INSTANCE_PROTOTYPE);
+        while object.is_object() {
+            if object.strict_equals(&prototype) {
+                return Ok(true);
+            }
+            object = object.get_internal_slot(INSTANCE_PROTOTYPE);
+        }
+        Ok(false)
+    }
+
     /// Runs the assignment operators.
-    fn run_assign(op: AssignOp, v_a: Value, v_b: Value) -> Value {
+    fn run_assign(
+        interpreter: &mut Interpreter,
+        op: AssignOp,
+        v_a: &Value,
+        v_b: &Value,
+    ) -> ResultValue {
         match op {
-            AssignOp::Add => v_a + v_b,
-            AssignOp::Sub => v_a - v_b,
-            AssignOp::Mul => v_a * v_b,
-            AssignOp::Exp => v_a.as_num_to_power(v_b),
-            AssignOp::Div => v_a / v_b,
-            AssignOp::Mod => v_a % v_b,
-            AssignOp::And => v_a & v_b,
-            AssignOp::Or => v_a | v_b,
-            AssignOp::Xor => v_a ^ v_b,
-            AssignOp::Shl => v_a << v_b,
-            AssignOp::Shr => v_a << v_b,
+            AssignOp::Add => Self::run_num_op(interpreter, NumOp::Add, v_a, v_b),
+            AssignOp::Sub => Self::run_num_op(interpreter, NumOp::Sub, v_a, v_b),
+            AssignOp::Mul => Self::run_num_op(interpreter, NumOp::Mul, v_a, v_b),
+            AssignOp::Exp => Self::run_num_op(interpreter, NumOp::Exp, v_a, v_b),
+            AssignOp::Div => Self::run_num_op(interpreter, NumOp::Div, v_a, v_b),
+            AssignOp::Mod => Self::run_num_op(interpreter, NumOp::Mod, v_a, v_b),
+            AssignOp::And => Self::run_bit_op(interpreter, BitOp::And, v_a, v_b),
+            AssignOp::Or => Self::run_bit_op(interpreter, BitOp::Or, v_a, v_b),
+            AssignOp::Xor => Self::run_bit_op(interpreter, BitOp::Xor, v_a, v_b),
+            AssignOp::Shl => Self::run_bit_op(interpreter, BitOp::Shl, v_a, v_b),
+            AssignOp::Shr => Self::run_bit_op(interpreter, BitOp::Shr, v_a, v_b),
         }
     }
 }
 
+/// Raises a number to a power.
+///
+/// Unlike `f64::powf`, this returns `NaN` for a `NaN` exponent, and for `1` or `-1` raised to an
+/// infinite power.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-numeric-types-number-exponentiate
+fn exponentiate(base: f64, exponent: f64) -> f64 {
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        f64::NAN
+    } else {
+        base.powf(exponent)
+    }
+}
+
+/// Runs a numeric operator on integers, returning `None` if its result isn't an integer, like an
+/// overflowing result or a negative zero, so that the operator is run on `f64` values instead.
+fn integer_num_op(op: NumOp, a: i32, b: i32) -> Option<i32> {
+    match op {
+        NumOp::Add => a.checked_add(b),
+        NumOp::Sub => a.checked_sub(b),
+        // `-1 * 0` is `-0`.
+        NumOp::Mul => a
+            .checked_mul(b)
+            .filter(|&product| product != 0 || (a >= 0 && b >= 0)),
+        NumOp::Div | NumOp::Mod | NumOp::Exp => None,
+    }
+}
+
 impl Executable for UnaryOp {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        // The operands of `delete` and of the update operators are references, which aren't
+        // evaluated to their values.
+        match self.op() {
+            op::UnaryOp::Delete => return self.delete(interpreter).map(Value::from),
+            op::UnaryOp::IncrementPost => return self.update(interpreter, 1, false),
+            op::UnaryOp::IncrementPre => return self.update(interpreter, 1, true),
+            op::UnaryOp::DecrementPost => return self.update(interpreter, -1, false),
+            op::UnaryOp::DecrementPre => return self.update(interpreter, -1, true),
+            _ => {}
+        }
         let v_a = self.target().run(interpreter)?;
 
         Ok(match self.op() {
-            op::UnaryOp::Minus => -v_a,
-            op::UnaryOp::Plus => Value::from(v_a.to_number()),
-            op::UnaryOp::IncrementPost => {
-                let ret = v_a.clone();
-                interpreter.set_value(self.target(), Value::from(v_a.to_number() + 1.0))?;
-                ret
+            op::UnaryOp::Minus => match interpreter.to_numeric(&v_a)?.data() {
+                ValueData::BigInt(ref num) => Value::bigint(-num.clone()),
+                num => Value::rational(-num.to_number()),
+            },
+            op::UnaryOp::Plus => Value::from(interpreter.to_number(&v_a)?),
+            op::UnaryOp::Not => !v_a,
+            op::UnaryOp::Tilde => match interpreter.to_numeric(&v_a)?.data() {
+                ValueData::BigInt(ref num) => Value::bigint(-num.clone() - BigInt::from(1)),
+                num => Value::from(!f64_to_int32(num.to_number())),
+            },
+            op::UnaryOp::Void => Value::undefined(),
+            op::UnaryOp::Delete
+            | op::UnaryOp::IncrementPost
+            | op::UnaryOp::IncrementPre
+            | op::UnaryOp::DecrementPost
+            | op::UnaryOp::DecrementPre => unreachable!("references are run on their own"),
+            op::UnaryOp::TypeOf => Value::from(v_a.get_type()),
+        })
+    }
+}
+
+impl UnaryOp {
+    /// Runs the `delete` operator, which returns `false` if the property or the binding it
+    /// references can't be deleted, and `true` otherwise, including for the other expressions.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-delete-operator-runtime-semantics-evaluation
+    fn delete(&self, interpreter: &mut Interpreter) -> Result<bool, Value> {
+        match *self.target() {
+            Node::GetConstField(ref get_const_field) => {
+                let obj = get_const_field.obj().run(interpreter)?;
+                let obj = interpreter.to_object(&obj)?;
+                Ok(obj.delete_property(&Value::from(get_const_field.field())))
             }
-            op::UnaryOp::IncrementPre => {
-                interpreter.set_value(self.target(), Value::from(v_a.to_number() + 1.0))?
+            Node::GetField(ref get_field) => {
+                let obj = get_field.obj().run(interpreter)?;
+                let field = get_field.field().run(interpreter)?;
+                let obj = interpreter.to_object(&obj)?;
+                let key = interpreter.to_property_key(&field)?;
+                Ok(obj.delete_property(&key))
             }
-            op::UnaryOp::DecrementPost => {
-                let ret = v_a.clone();
-                interpreter.set_value(self.target(), Value::from(v_a.to_number() - 1.0))?;
-                ret
+            Node::Identifier(ref name) => {
+                Ok(interpreter.realm().environment.delete_binding(name.sym()))
             }
-            op::UnaryOp::DecrementPre => {
-                interpreter.set_value(self.target(), Value::from(v_a.to_number() - 1.0))?
+            ref target => {
+                target.run(interpreter)?;
+                Ok(true)
             }
-            op::UnaryOp::Not => !v_a,
-            op::UnaryOp::Tilde => {
-                let num_v_a = v_a.to_number();
-                // NOTE: possible UB: https://github.com/rust-lang/rust/issues/10184
-                Value::from(if num_v_a.is_nan() {
-                    -1
-                } else {
-                    !(num_v_a as i32)
-                })
+        }
+    }
+
+    /// Runs the increment and decrement operators, evaluating the object and the key of a
+    /// property only once, and returning the old or the new value.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-update-expressions
+    fn update(&self, interpreter: &mut Interpreter, step: i32, prefix: bool) -> ResultValue {
+        let (old_value, new_value) = match *self.target() {
+            Node::Identifier(ref name) => {
+                let environment = &interpreter.realm().environment;
+                if !environment.has_identifier_binding(name) {
+                    return interpreter.throw_reference_error(format!("{} is not defined", name));
+                }
+                let value = interpreter.get_identifier_value(name)?;
+                let old_value = interpreter.to_numeric(&value)?;
+                let new_value = Self::increment(&old_value, step);
+                interpreter.set_identifier_value(name, new_value.clone())?;
+                (old_value, new_value)
             }
-            op::UnaryOp::Void => Value::undefined(),
-            op::UnaryOp::Delete => match *self.target() {
-                Node::GetConstField(ref get_const_field) => Value::boolean(
-                    get_const_field
-                        .obj()
-                        .run(interpreter)?
-                        .remove_property(get_const_field.field()),
-                ),
-                Node::GetField(ref get_field) => Value::boolean(
-                    get_field
-                        .obj()
-                        .run(interpreter)?
-                        .remove_property(&get_field.field().run(interpreter)?.to_string()),
-                ),
-                Node::Identifier(_) => Value::boolean(false),
-                Node::ArrayDecl(_)
-                | Node::Block(_)
-                | Node::Const(_)
-                | Node::FunctionDecl(_)
-                | Node::FunctionExpr(_)
-                | Node::New(_)
-                | Node::Object(_)
-                | Node::UnaryOp(_) => Value::boolean(true),
-                _ => panic!("SyntaxError: wrong delete argument {}", self),
+            Node::GetConstField(ref get_const_field) => {
+                let mut obj = get_const_field.obj().run(interpreter)?;
+                if !obj.is_object() {
+                    obj = interpreter.to_object(&obj)?;
+                }
+                let field = get_const_field.field();
+                let value =
+                    interpreter.get_field_value_cached(&obj, field, get_const_field.cache())?;
+                let old_value = interpreter.to_numeric(&value)?;
+                let new_value = Self::increment(&old_value, step);
+                interpreter.set_field_value_cached(
+                    &obj,
+                    field,
+                    new_value.clone(),
+                    get_const_field.cache(),
+                )?;
+                (old_value, new_value)
+            }
+            Node::GetField(ref get_field) => {
+                let mut obj = get_field.obj().run(interpreter)?;
+                if !obj.is_object() {
+                    obj = interpreter.to_object(&obj)?;
+                }
+                let field = get_field.field().run(interpreter)?;
+                let key = interpreter.to_property_key(&field)?;
+                let value = interpreter.get_field_value(&obj, key.clone())?;
+                let old_value = interpreter.to_numeric(&value)?;
+                let new_value = Self::increment(&old_value, step);
+                interpreter.set_field_value(&obj, key, new_value.clone())?;
+                (old_value, new_value)
+            }
+            _ => {
+                return interpreter.throw_reference_error(format!(
+                    "invalid target of an increment or decrement operator: {}",
+                    self.target()
+                ))
+            }
+        };
+        Ok(if prefix { new_value } else { old_value })
+    }
+
+    /// Adds a step to a numeric value, as done by the increment and decrement operators.
+    fn increment(value: &Value, step: i32) -> Value {
+        match value.data() {
+            ValueData::Integer(num) => match num.checked_add(step) {
+                Some(num) => Value::integer(num),
+                None => Value::rational(f64::from(*num) + f64::from(step)),
             },
-            op::UnaryOp::TypeOf => Value::from(v_a.get_type()),
-        })
+            ValueData::BigInt(ref num) => Value::bigint(num.clone() + BigInt::from(step)),
+            num => Value::rational(num.to_number() + f64::from(step)),
+        }
     }
 }
@@ -0,0 +1,18 @@
+use super::{Executable, Interpreter};
+use crate::{
+    builtins::{
+        value::{ResultValue, Value},
+        RegExp,
+    },
+    syntax::ast::node::RegExpLiteral,
+};
+
+impl Executable for RegExpLiteral {
+    fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        RegExp::create_regexp(
+            Value::from(self.pattern()),
+            Value::from(self.flags().to_string()),
+            interpreter,
+        )
+    }
+}
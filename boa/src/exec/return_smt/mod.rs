@@ -1,12 +1,30 @@
 use super::{Executable, Interpreter};
 use crate::{
-    builtins::value::{ResultValue, Value},
-    syntax::ast::node::Return,
+    builtins::{
+        function::{Function, TailCall},
+        value::{ResultValue, Value},
+    },
+    syntax::ast::node::{Node, Return},
 };
 
 impl Executable for Return {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let result = match self.expr() {
+            // A call in tail position is left to the caller of the function, which runs it once
+            // the frame of this function is gone, so that tail recursion doesn't grow the stack.
+            // An arrow function takes `this` from the frame of this function, so it is called here.
+            Some(Node::Call(ref call)) if interpreter.in_tail_position() => {
+                let (mut this, func, args) = call.evaluate(interpreter)?;
+                let lexical_this = func
+                    .as_object()
+                    .is_some_and(|obj| obj.func.as_ref().is_some_and(Function::has_lexical_this));
+                if lexical_this {
+                    interpreter.call(&func, &mut this, &args)
+                } else {
+                    interpreter.tail_call = Some(TailCall::new(func, this, args));
+                    Ok(Value::undefined())
+                }
+            }
             Some(ref v) => v.run(interpreter),
             None => Ok(Value::undefined()),
         };
@@ -3,26 +3,46 @@
 use super::{Executable, Interpreter};
 use crate::{
     builtins::value::{ResultValue, Value},
-    syntax::ast::node::StatementList,
+    syntax::ast::{node::StatementList, Position},
     BoaProfiler,
 };
 
 impl Executable for StatementList {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("StatementList", "exec");
-        let mut obj = Value::null();
-        for (i, item) in self.statements().iter().enumerate() {
-            let val = item.run(interpreter)?;
-            // early return
-            if interpreter.is_return {
-                obj = val;
-                break;
-            }
-            if i + 1 == self.statements().len() {
-                obj = val;
-            }
+        let coverage = interpreter.coverage_state();
+        interpreter.declare_lexical(self.statements());
+        let result = run_statements(self, interpreter);
+        interpreter.restore_coverage_state(coverage);
+        result
+    }
+}
+
+/// Runs the statements of a list, and returns the value of the last one.
+fn run_statements(list: &StatementList, interpreter: &mut Interpreter) -> ResultValue {
+    let mut obj = Value::null();
+    for (i, item) in list.statements().iter().enumerate() {
+        interpreter.enter_statement(list.position(i));
+        let val = item.run(interpreter)?;
+        // early return
+        if interpreter.is_return {
+            obj = val;
+            break;
+        }
+        if i + 1 == list.statements().len() {
+            obj = val;
         }
+    }
+
+    Ok(obj)
+}
 
-        Ok(obj)
+impl Interpreter {
+    /// Counts a statement of a list for the coverage, and calls the debugger, before running it.
+    pub(crate) fn enter_statement(&mut self, position: Option<Position>) {
+        self.cover_statement(position);
+        if let Some(position) = position {
+            self.on_statement(position);
+        }
     }
 }
@@ -1,4 +1,20 @@
-use crate::{builtins::Value, exec, exec::Interpreter, forward, realm::Realm};
+use crate::{
+    builtins::{intrinsics::Intrinsic, object::INSTANCE_PROTOTYPE, Value},
+    debugger::Debugger,
+    exec,
+    exec::{Executable, Interpreter},
+    forward, forward_val,
+    heap_snapshot::{EdgeKind, NodeKind},
+    job::Job,
+    parse,
+    profiler::FrameEvent,
+    realm::Realm,
+    syntax::{
+        analyzer::analyze, ast::Position, lexer::Lexer, optimizer::OptimizerOptions,
+        parser::Parser, ScriptError,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
 
 #[test]
 fn empty_let_decl_undefined() {
@@ -31,6 +47,166 @@ fn empty_var_decl_undefined() {
     assert_eq!(&exec(scenario), "true");
 }
 
+#[test]
+fn let_temporal_dead_zone() {
+    let scenario = r#"
+        let errors = [];
+        try { a; } catch (e) { errors.push(e.constructor === ReferenceError); }
+        try { a = 1; } catch (e) { errors.push(e.constructor === ReferenceError); }
+        try { a += 1; } catch (e) { errors.push(e.constructor === ReferenceError); }
+        let a = 2;
+        errors.push(a);
+        errors.join()
+        "#;
+    assert_eq!(&exec(scenario), "true,true,true,2");
+}
+
+#[test]
+fn const_temporal_dead_zone_in_block() {
+    let scenario = r#"
+        const a = 'outer';
+        let result;
+        {
+            try {
+                result = a;
+            } catch (e) {
+                result = e.constructor === ReferenceError;
+            }
+            const a = 'inner';
+        }
+        result
+        "#;
+    assert_eq!(&exec(scenario), "true");
+}
+
+#[test]
+fn closure_capturing_temporal_dead_zone() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let scenario = r#"
+        function read() {
+            return value;
+        }
+        function before() {
+            try {
+                return read();
+            } catch (e) {
+                return e.constructor === ReferenceError;
+            }
+        }
+        let early = before();
+        let value = 'initialized';
+        "#;
+    forward(&mut engine, scenario);
+    assert_eq!(forward(&mut engine, "early"), "true");
+    assert_eq!(forward(&mut engine, "read()"), "initialized");
+
+    let scenario = r#"
+        function inner() {
+            let read = () => local;
+            let early;
+            try {
+                early = read();
+            } catch (e) {
+                early = e.constructor === ReferenceError;
+            }
+            let local = 'local';
+            return [early, read()].join();
+        }
+        inner()
+        "#;
+    assert_eq!(forward(&mut engine, scenario), "true,local");
+}
+
+#[test]
+fn const_assignment() {
+    let scenario = r#"
+        const a = 1;
+        let errors = [];
+        try { a = 2; } catch (e) { errors.push(e.constructor === TypeError); }
+        try { a += 2; } catch (e) { errors.push(e.constructor === TypeError); }
+        function inner() {
+            const b = 1;
+            try { b = 2; } catch (e) { errors.push(e.constructor === TypeError); }
+            return b;
+        }
+        errors.push(a, inner());
+        errors.join()
+        "#;
+    assert_eq!(&exec(scenario), "true,true,true,1,1");
+}
+
+#[test]
+fn closure_scope() {
+    let scenario = r#"
+        function counter() {
+            let count = 0;
+            return () => {
+                count += 1;
+                return count;
+            };
+        }
+        function call(f) {
+            let count = 100;
+            return f();
+        }
+        let next = counter();
+        call(next);
+        [call(next), counter()()].join()
+        "#;
+    assert_eq!(&exec(scenario), "2,1");
+}
+
+#[test]
+fn for_loop_iteration_bindings() {
+    let scenario = r#"
+        let closures = [];
+        for (let i = 0; i < 3; i++) {
+            closures.push(() => i);
+        }
+        closures.map(f => f()).join()
+        "#;
+    assert_eq!(&exec(scenario), "0,1,2");
+
+    let scenario = r#"
+        let closures = [];
+        for (let i = 0; i < 3; i++) {
+            closures.push(() => i);
+            i += 1;
+        }
+        closures.map(f => f()).join()
+        "#;
+    assert_eq!(&exec(scenario), "1,3");
+}
+
+#[test]
+fn function_declaration_in_block() {
+    let scenario = r#"
+        let result = [typeof inBlock];
+        {
+            result.push(inBlock());
+            function inBlock() {
+                return 'block';
+            }
+        }
+        result.push(typeof inBlock);
+        result.join()
+        "#;
+    assert_eq!(&exec(scenario), "undefined,block,function");
+
+    let scenario = r#"
+        function redeclared() {
+            return 1;
+        }
+        function redeclared() {
+            return 2;
+        }
+        redeclared()
+        "#;
+    assert_eq!(&exec(scenario), "2");
+}
+
 #[test]
 fn object_field_set() {
     let scenario = r#"
@@ -41,6 +217,224 @@ fn object_field_set() {
     assert_eq!(&exec(scenario), "22");
 }
 
+#[test]
+fn object_getter_and_setter() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let scenario = r#"
+        let o = {
+            value: 1,
+            get double() { return this.value * 2; },
+            set double(x) { this.value = x / 2; },
+            get readOnly() { return 'read only'; },
+        };
+        "#;
+    forward(&mut engine, scenario);
+    assert_eq!(forward(&mut engine, "o.double"), "2");
+    assert_eq!(forward(&mut engine, "o['double']"), "2");
+    assert_eq!(forward(&mut engine, "o.double = 10; o.value"), "5");
+    assert_eq!(forward(&mut engine, "o['double'] = 6; o.value"), "3");
+    assert_eq!(forward(&mut engine, "o.double += 2; o.value"), "4");
+    assert_eq!(forward(&mut engine, "o.double++; o.value"), "4.5");
+    assert_eq!(
+        forward(&mut engine, "o.readOnly = 1; o.readOnly"),
+        "read only"
+    );
+}
+
+#[test]
+fn object_setter_without_getter() {
+    let scenario = r#"
+        let log = [];
+        let o = { set last(x) { log.push(x); } };
+        o.last = 'a';
+        o.last = 'b';
+        [typeof o.last, log.join()].join(' ')
+        "#;
+    assert_eq!(&exec(scenario), "undefined a,b");
+}
+
+#[test]
+fn function_this_value() {
+    let scenario = r#"
+        let o = {
+            name: 'o',
+            method() { return this.name; },
+            arrow() { return (() => this.name)(); },
+        };
+        o.method() + o.arrow()
+        "#;
+    assert_eq!(&exec(scenario), "oo");
+}
+
+#[test]
+fn new_target() {
+    let scenario = r#"
+        function F() {
+            this.constructed = new.target === F;
+            this.arrow = (() => new.target)() === F;
+            return typeof new.target;
+        }
+        new F().constructed + " " + new F().arrow + " " + F()
+    "#;
+    assert_eq!(&exec(scenario), "true true undefined");
+
+    let guard = r#"
+        function Point(x) {
+            if (!new.target) {
+                return new Point(x);
+            }
+            this.x = x;
+        }
+        Point(1).x + new Point(2).x
+    "#;
+    assert_eq!(&exec(guard), "3");
+
+    assert_eq!(
+        &exec("new.target"),
+        "Syntax Error: new.target expression is not allowed here"
+    );
+}
+
+#[test]
+fn array_destructuring() {
+    let scenario = r#"
+        let [a, , b = 5, [c, d], ...rest] = [1, 2, undefined, [3, 4], 6, 7];
+        var [e = a + b, f = 10] = [];
+        const [g] = [];
+        [a, b, c, d, rest.join(), e, f, typeof g].join(" ")
+    "#;
+    assert_eq!(&exec(scenario), "1 5 3 4 6,7 6 10 undefined");
+
+    let not_iterable = r#"
+        let messages = [];
+        try { let [a] = {}; } catch (e) { messages.push(e instanceof TypeError); }
+        try { let [a] = null; } catch (e) { messages.push(e instanceof TypeError); }
+        try { let [a = b, b] = []; } catch (e) { messages.push(e instanceof ReferenceError); }
+        messages.join(" ")
+    "#;
+    assert_eq!(&exec(not_iterable), "true true true");
+}
+
+#[test]
+fn array_destructuring_of_iterables() {
+    let scenario = r#"
+        let log = [];
+        function range(n) {
+            let iterable = {};
+            iterable[Symbol.iterator] = function () {
+                let i = 0;
+                return {
+                    next: function () {
+                        log.push("next");
+                        i++;
+                        return { value: i, done: i > n };
+                    },
+                    return: function () {
+                        log.push("return");
+                        return {};
+                    }
+                };
+            };
+            return iterable;
+        }
+    "#;
+    let mut engine = Interpreter::new(Realm::create());
+    forward(&mut engine, scenario);
+
+    // The iterator is closed when the pattern doesn't consume all of its values.
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let [a, b] = range(5); a + b + ' ' + log.join()"
+        ),
+        "3 next,next,return"
+    );
+
+    // It isn't closed once it's done.
+    forward(&mut engine, "log = [];");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let [c, d, e] = range(2); e + ' ' + log.join()"
+        ),
+        "undefined next,next,next"
+    );
+    forward(&mut engine, "log = [];");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "let [...f] = range(2); f.join() + ' ' + log.join()"
+        ),
+        "1,2 next,next,next"
+    );
+
+    // It's closed when the binding throws, keeping the error.
+    forward(&mut engine, "log = [];");
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { let [g = (() => { throw 'error'; })()] = [undefined].concat(); } catch (e) { log.push(e); }
+             try { let [h, [i]] = range(5); } catch (e) { log.push(e instanceof TypeError); }
+             log.join()"
+        ),
+        "error,next,next,return,true"
+    );
+}
+
+#[test]
+fn array_destructuring_iterator_errors() {
+    let scenario = r#"
+        let closed = false;
+        let throwing = {};
+        throwing[Symbol.iterator] = function () {
+            return {
+                next: function () { throw "next"; },
+                return: function () { closed = true; }
+            };
+        };
+        let bad_return = {};
+        bad_return[Symbol.iterator] = function () {
+            return {
+                next: function () { return { value: 1, done: false }; },
+                return: function () { return 1; }
+            };
+        };
+        let messages = [];
+        try { let [a] = throwing; } catch (e) { messages.push(e + " " + closed); }
+        try { let [a] = bad_return; } catch (e) { messages.push(e instanceof TypeError); }
+        messages.join(" ")
+    "#;
+    assert_eq!(&exec(scenario), "next false true");
+}
+
+#[test]
+fn spread_iterables() {
+    let scenario = r#"
+        let iterable = {};
+        iterable[Symbol.iterator] = function () {
+            let i = 0;
+            return { next: function () { i++; return { value: i, done: i > 3 }; } };
+        };
+        function count() { return arguments.length; }
+        let chars = [..."a\u{1F600}b"];
+        let [first, second] = "\u{1F600}!";
+        [chars.length, chars[1] === "\u{1F600}", [...iterable, 4].join(), count(..."abc"),
+            count(...iterable), second].join(" ")
+    "#;
+    assert_eq!(&exec(scenario), "3 true 1,2,3,4 3 3 !");
+
+    let not_iterable = r#"
+        let messages = [];
+        try { [...1]; } catch (e) { messages.push(e instanceof TypeError); }
+        try { count(...{}); } catch (e) { messages.push(e.message); }
+        function count() { return arguments.length; }
+        messages.join(" ")
+    "#;
+    assert_eq!(&exec(not_iterable), "true object is not iterable");
+}
+
 #[test]
 fn spread_with_arguments() {
     let realm = Realm::create();
@@ -82,6 +476,71 @@ fn array_rest_with_arguments() {
     assert_eq!(one, String::from("[ 1, 2, 3, 4, 5, 6 ]"));
 }
 
+#[test]
+fn default_parameters() {
+    let scenario = r#"
+        let calls = 0;
+        function g() {
+            calls++;
+            return 10;
+        }
+        function f(a, b = a + 1, c = g()) {
+            return a + " " + b + " " + c;
+        }
+        f(1) + " | " + f(1, 5, 7) + " | " + f(1, undefined, null) + " | " + calls
+    "#;
+    assert_eq!(&exec(scenario), "1 2 10 | 1 5 7 | 1 2 null | 1");
+
+    let missing = r#"
+        function f(a, b, ...rest) {
+            return typeof b + " " + rest.length;
+        }
+        f(1)
+    "#;
+    assert_eq!(&exec(missing), "undefined 0");
+
+    let arguments = r#"
+        function f(a, b = arguments.length) {
+            return arguments.length + " " + b + " " + arguments[1];
+        }
+        f(1)
+    "#;
+    assert_eq!(&exec(arguments), "1 1 undefined");
+
+    let later_parameter = r#"
+        function f(a = b, b) {
+            return a;
+        }
+        let result;
+        try {
+            f();
+        } catch (e) {
+            result = e instanceof ReferenceError;
+        }
+        result
+    "#;
+    assert_eq!(&exec(later_parameter), "true");
+
+    let body_scope = r#"
+        var x = "outer";
+        function f(a, get = () => a + " " + x) {
+            var x = "inner";
+            var a = "shadowed";
+            return get() + " " + a + " " + x;
+        }
+        f("param")
+    "#;
+    assert_eq!(&exec(body_scope), "param outer shadowed inner");
+
+    let constructor = r#"
+        function Point(x = 1, y = x * 2) {
+            this.sum = x + y;
+        }
+        new Point().sum + new Point(2, 3).sum
+    "#;
+    assert_eq!(&exec(constructor), "8");
+}
+
 #[test]
 fn array_field_set() {
     let element_changes = r#"
@@ -113,6 +572,29 @@ fn array_field_set() {
     assert_eq!(&exec(non_num_key_wont_affect_length), "3");
 }
 
+#[test]
+fn integer_arithmetic() {
+    let scenario = r#"
+        let sum = 0;
+        for (let i = 0; i < 10; i++) {
+            sum += i * 2 - 1;
+        }
+        sum
+        "#;
+    assert_eq!(&exec(scenario), "80");
+
+    assert_eq!(&exec("2147483647 + 1"), "2147483648");
+    assert_eq!(&exec("-2147483648 - 1"), "-2147483649");
+    assert_eq!(&exec("65536 * 65536"), "4294967296");
+    assert_eq!(&exec("let max = 2147483647; max++; max"), "2147483648");
+    // The negation of an integer isn't an integer, so the negative operand is a difference.
+    assert_eq!(&exec("let m = 0 - 1; 1 / (m * 0)"), "-Infinity");
+    assert_eq!(&exec("let m = 0 - 1; 1 / (0 * m)"), "-Infinity");
+    assert_eq!(&exec("-2 < 3"), "true");
+    assert_eq!(&exec("3 <= 3"), "true");
+    assert_eq!(&exec("4 > 5"), "false");
+}
+
 #[test]
 fn tilde_operator() {
     let float = r#"
@@ -479,6 +961,42 @@ fn unary_post() {
     assert_eq!(&exec(execs_after_dec), "true");
 }
 
+#[test]
+fn unary_update_evaluates_reference_once() {
+    let scenario = r#"
+        let calls = 0;
+        const a = { b: 1, c: [5] };
+        function obj() { calls++; return a; }
+        function key() { calls++; return 'b'; }
+        const old = obj()[key()]++;
+        const value = ++obj().c[0];
+        [calls, old, a.b, value, a.c[0]]
+    "#;
+    assert_eq!(&exec(scenario), "[ 3, 1, 2, 6, 6 ]");
+
+    let to_numeric = r#"
+        let s = '5';
+        let big = 10n;
+        let o = { valueOf() { return 3; } };
+        [s++, s, big--, big, o++, o]
+    "#;
+    assert_eq!(&exec(to_numeric), "[ 5, 6, 10n, 9n, 3, 4 ]");
+}
+
+#[test]
+fn unary_update_of_undeclared_variable_throws() {
+    let scenario = r#"
+        let result = 'no error';
+        try {
+            undeclared++;
+        } catch (e) {
+            result = e.message;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "undeclared is not defined");
+}
+
 #[test]
 fn unary_void() {
     let void_should_return_undefined = r#"
@@ -517,7 +1035,7 @@ fn unary_delete() {
         const c = delete a.c + '';
         a.b + c
     "#;
-    assert_eq!(&exec(delete_not_existing_prop), "5false");
+    assert_eq!(&exec(delete_not_existing_prop), "5true");
 
     let delete_field = r#"
         const a = { b: 5 };
@@ -548,6 +1066,127 @@ fn unary_delete() {
     assert_eq!(&exec(delete_recursive), "true");
 }
 
+#[test]
+fn primitive_method_calls() {
+    let builtins = r#"
+        (5).toFixed(2) + " " + "x".toUpperCase() + " " + true.toString() + " " + "ab"["charAt"](1)
+    "#;
+    assert_eq!(&exec(builtins), "5.00 X true b");
+
+    let own_methods = r#"
+        String.prototype.kind = function() { return typeof this; };
+        Number.prototype.twice = function() { return this * 2; };
+        "x".kind() + " " + "x"["kind"]() + " " + (21).twice()
+    "#;
+    assert_eq!(&exec(own_methods), "object object 42");
+
+    let object_methods = r#"
+        "abc".hasOwnProperty("length") + " " + "abc".hasOwnProperty("1")
+    "#;
+    assert_eq!(&exec(object_methods), "true true");
+
+    let nullish_receiver = r#"
+        let result = 'no error';
+        try {
+            undefined.toString();
+        } catch (e) {
+            result = e instanceof TypeError;
+        }
+        result
+    "#;
+    assert_eq!(&exec(nullish_receiver), "true");
+}
+
+#[test]
+fn calling_non_callables() {
+    let scenario = r#"
+        let errors = [];
+        try { ({})(); } catch (e) { errors.push(e instanceof TypeError, e.message); }
+        try { (1)(); } catch (e) { errors.push(e instanceof TypeError, e.message); }
+        try { 'abc'(); } catch (e) { errors.push(e.message); }
+        errors.join(', ')
+    "#;
+    assert_eq!(
+        &exec(scenario),
+        r#"true, object is not a function, true, 1 is not a function, "abc" is not a function"#
+    );
+}
+
+#[test]
+fn comma_operator() {
+    let scenario = r#"
+        let calls = '';
+        function f(x) { calls += x; return x; }
+        const result = (f('a'), f('b'), f('c'));
+        result + calls
+    "#;
+    assert_eq!(&exec(scenario), "cabc");
+
+    let in_for_loop = r#"
+        let sum = 0;
+        for (var i = 0, j = 10; i < j; i++, j--) {
+            sum += j - i;
+        }
+        sum
+    "#;
+    assert_eq!(&exec(in_for_loop), "30");
+
+    let assignments = r#"
+        var a, b;
+        a = 1, b = 2;
+        a + b
+    "#;
+    assert_eq!(&exec(assignments), "3");
+}
+
+#[test]
+fn unary_delete_non_configurable() {
+    let scenario = r#"
+        const a = {};
+        Object.defineProperty(a, 'b', { value: 5, configurable: false });
+        const c = delete a.b;
+        a.b + ' ' + c
+    "#;
+    assert_eq!(&exec(scenario), "5 false");
+
+    let delete_symbol = r#"
+        const sym = Symbol();
+        const a = {};
+        a[sym] = 5;
+        const b = delete a[sym];
+        b + ' ' + (sym in a)
+    "#;
+    assert_eq!(&exec(delete_symbol), "true false");
+
+    let delete_global = r#"
+        var declared = 1;
+        undeclared = 2;
+        delete declared + ' ' + delete undeclared + ' ' + typeof undeclared
+    "#;
+    assert_eq!(&exec(delete_global), "false true undefined");
+
+    let delete_expression = r#"
+        let called = false;
+        function f() { called = true; }
+        delete f() + ' ' + called
+    "#;
+    assert_eq!(&exec(delete_expression), "true true");
+}
+
+#[test]
+fn unary_delete_of_undefined_throws() {
+    let scenario = r#"
+        let result = 'no error';
+        try {
+            delete undefined.a;
+        } catch (e) {
+            result = e.message;
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "cannot convert undefined to an object");
+}
+
 #[cfg(test)]
 mod in_operator {
     use super::*;
@@ -608,6 +1247,15 @@ mod in_operator {
         assert_eq!(&exec(sym_in_object), "true");
     }
 
+    #[test]
+    fn accessor_in_object() {
+        let acc_in_o = r#"
+            var o = { get a() { return 1; } };
+            'a' in o
+        "#;
+        assert_eq!(&exec(acc_in_o), "true");
+    }
+
     #[test]
     fn should_type_error_when_rhs_not_object() {
         let realm = Realm::create();
@@ -660,6 +1308,51 @@ mod in_operator {
     }
 }
 
+#[cfg(test)]
+mod instanceof_operator {
+    use super::*;
+
+    #[test]
+    fn instance_of_constructor() {
+        let scenario = r#"
+            function Foo() {}
+            function Bar() {}
+            var foo = new Foo();
+            [foo instanceof Foo, foo instanceof Object, foo instanceof Bar, 1 instanceof Foo]
+        "#;
+        assert_eq!(&exec(scenario), "[ true, true, false, false ]");
+    }
+
+    #[test]
+    fn instance_of_uses_has_instance() {
+        let scenario = r#"
+            var Even = {};
+            Even[Symbol.hasInstance] = function(n) { return n % 2 === 0; };
+            [2 instanceof Even, 3 instanceof Even]
+        "#;
+        assert_eq!(&exec(scenario), "[ true, false ]");
+    }
+
+    #[test]
+    fn should_type_error_when_rhs_not_callable() {
+        let scenario = r#"
+            var errors = [];
+            try {
+                ({}) instanceof 1;
+            } catch (e) {
+                errors.push(e instanceof TypeError);
+            }
+            try {
+                ({}) instanceof {};
+            } catch (e) {
+                errors.push(e instanceof TypeError);
+            }
+            errors
+        "#;
+        assert_eq!(&exec(scenario), "[ true, true ]");
+    }
+}
+
 #[test]
 fn var_decl_hoisting() {
     let scenario = r#"
@@ -777,3 +1470,856 @@ fn to_string() {
     assert_eq!(engine.to_string(&Value::rational(55.0)).unwrap(), "55");
     assert_eq!(engine.to_string(&Value::string("hello")).unwrap(), "hello");
 }
+
+#[test]
+fn hashbang_script() {
+    let scenario = "#!/usr/bin/env boa\nlet a = 20;\na + 22";
+
+    assert_eq!(&exec(scenario), "42");
+}
+
+#[test]
+fn resolved_bindings() {
+    let shadowed = r#"
+        function f() {
+            let a = 1;
+            {
+                let a = 2;
+                a += 1;
+            }
+            return a;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(shadowed), "1");
+
+    let after_construct = r#"
+        function F() {
+            this.x = 1;
+        }
+        function f() {
+            let a = 10;
+            new F();
+            {
+                a = a + 1;
+            }
+            return a;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(after_construct), "11");
+
+    let after_exception = r#"
+        function f() {
+            let a = 1;
+            try {
+                let b = 2;
+                throw b;
+            } catch (e) {
+                a = a + e;
+            }
+            return a;
+        }
+        f();
+    "#;
+    assert_eq!(&exec(after_exception), "3");
+}
+
+#[test]
+fn lexical_redeclaration() {
+    assert_eq!(
+        &exec("let a = 1; let a = 2;"),
+        "Syntax Error: Identifier 'a' has already been declared"
+    );
+}
+
+#[test]
+fn parse_reports_the_phase_of_errors() {
+    let options = OptimizerOptions::empty();
+    assert!(matches!(
+        parse("let a = 1; let a = 2;".as_bytes(), options),
+        Err(ScriptError::Early(_))
+    ));
+    let error = parse("let a = ;".as_bytes(), options).expect_err("should not parse");
+    assert!(matches!(error, ScriptError::Parse(_)));
+    assert_eq!(error.position(), Some(Position::new(1, 7)));
+    assert!(parse("let a = 1;".as_bytes(), options).is_ok());
+}
+
+#[test]
+fn inline_cached_property_accesses() {
+    let scenario = r#"
+        function getX(o) {
+            return o.x;
+        }
+        let objects = [{ x: 1 }, { y: 0, x: 2 }, { x: 3 }, { z: 0, x: 4 }];
+        let sum = 0;
+        for (let i = 0; i < 4; i++) {
+            sum += getX(objects[i]);
+        }
+        delete objects[0].x;
+        objects[0].x = 10;
+        sum += getX(objects[0]) + getX(objects[2]);
+        sum
+        "#;
+
+    assert_eq!(&exec(scenario), "23");
+}
+
+#[test]
+fn inline_cached_property_stores() {
+    let scenario = r#"
+        function setX(o, x) {
+            o.x = x;
+        }
+        let a = { x: 1 };
+        let b = { x: 2 };
+        Object.defineProperty(b, "x", { value: 2, writable: false });
+        setX(a, 3);
+        setX(b, 4);
+        setX(a, 5);
+        a.x += b.x;
+        a.x + "," + b.x
+        "#;
+
+    assert_eq!(&exec(scenario), "7,2");
+}
+
+#[test]
+fn gc_stats() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    // The collector also runs on its own while the script allocates, so the values are compared
+    // once the garbage has been freed.
+    engine.gc();
+    let before = engine.gc_stats();
+    assert!(before.values >= before.objects);
+    assert!(before.heap_size() > 0);
+    assert_eq!(before.collections, 1);
+
+    forward(&mut engine, "let objects = [{}, {}, {}];");
+    engine.gc();
+    let after = engine.gc_stats();
+    assert!(after.objects >= before.objects + 4);
+    assert!(after.values > before.values);
+    assert_eq!(after.collections, 2);
+    assert!(after.pause_time >= before.pause_time);
+    assert!(after.peak_heap_size >= after.heap_size());
+}
+
+#[test]
+fn job_queue() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        var log = [];
+        function first(value) {
+            log.push("first " + value);
+        }
+        function second() {
+            log.push("second");
+        }
+        function fail() {
+            throw "failed";
+        }
+        "#;
+    forward(&mut engine, init);
+    let first = forward_val(&mut engine, "first").unwrap();
+    let second = forward_val(&mut engine, "second").unwrap();
+    let fail = forward_val(&mut engine, "fail").unwrap();
+
+    engine.enqueue_job(Job::new(second.clone(), Vec::new()));
+    engine.enqueue_job(Job::new(first, vec![Value::from(1)]));
+    assert_eq!(forward(&mut engine, "log.length"), "0");
+    assert!(engine.run_jobs().is_ok());
+    assert_eq!(forward(&mut engine, "log.join()"), "second,first 1");
+
+    engine.enqueue_job(Job::new(fail, Vec::new()));
+    engine.enqueue_job(Job::new(second, Vec::new()));
+    assert_eq!(engine.run_jobs().unwrap_err().to_string(), "failed");
+    assert!(engine.run_jobs().is_ok());
+    assert_eq!(forward(&mut engine, "log.join()"), "second,first 1,second");
+}
+
+#[test]
+fn deep_recursion() {
+    let scenario = r#"
+        function depth(n) {
+            if (n === 0) {
+                return 0;
+            }
+            return 1 + depth(n - 1);
+        }
+        depth(5000)
+        "#;
+
+    assert_eq!(&exec(scenario), "5000");
+}
+
+#[test]
+fn tail_calls_run_in_constant_space() {
+    let scenario = r#"
+        function count(n, total) {
+            if (n === 0) {
+                return total;
+            }
+            return count(n - 1, total + 1);
+        }
+        function isEven(n) {
+            if (n === 0) {
+                return true;
+            }
+            return isOdd(n - 1);
+        }
+        function isOdd(n) {
+            if (n === 0) {
+                return false;
+            }
+            return isEven(n - 1);
+        }
+        count(100000, 0) + "," + isEven(20001)
+        "#;
+
+    assert_eq!(&exec(scenario), "100000,false");
+}
+
+#[test]
+fn maximum_call_depth() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.set_max_call_depth(100);
+    let init = r#"
+        function depth(n) {
+            if (n === 0) {
+                return 0;
+            }
+            return 1 + depth(n - 1);
+        }
+        function overflow() {
+            try {
+                depth(1000);
+            } catch (e) {
+                return e.constructor === RangeError;
+            }
+            return false;
+        }
+        "#;
+    forward(&mut engine, init);
+    assert_eq!(forward(&mut engine, "depth(50)"), "50");
+    assert_eq!(forward(&mut engine, "overflow()"), "true");
+    // The frames unwound by the error are available again.
+    assert_eq!(forward(&mut engine, "depth(90)"), "90");
+}
+
+#[test]
+fn calls_in_try_blocks_are_not_tail_calls() {
+    let scenario = r#"
+        function fail() {
+            throw "failed";
+        }
+        function caught() {
+            try {
+                return fail();
+            } catch (e) {
+                return "caught " + e;
+            }
+        }
+        function finalized() {
+            try {
+                return fail();
+            } catch (e) {
+                return fail();
+            } finally {
+                log.push("finally");
+            }
+        }
+        var log = [];
+        try {
+            finalized();
+        } catch (e) {
+            log.push(e);
+        }
+        caught() + "," + log.join()
+        "#;
+
+    assert_eq!(&exec(scenario), "caught failed,finally,failed");
+}
+
+#[test]
+fn global_closures() {
+    use std::{cell::Cell, rc::Rc};
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    engine.register_global_closure("add", 2, move |_, args, ctx| {
+        counter.set(counter.get() + 1);
+        let a = ctx.to_number(args.first().unwrap_or(&Value::undefined()))?;
+        let b = ctx.to_number(args.get(1).unwrap_or(&Value::undefined()))?;
+        Ok(Value::from(a + b))
+    });
+
+    assert_eq!(&forward(&mut engine, "add(1, 2) + add.length"), "5");
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn global_bindings() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    forward(&mut engine, "var a = 1; let b = 'b'; function c() {}");
+
+    let bindings = engine.global_bindings();
+    let value = |name: &str| {
+        bindings
+            .iter()
+            .find(|(binding, _)| binding == name)
+            .map(|(_, value)| value.to_string())
+    };
+    assert_eq!(value("a").as_deref(), Some("1"));
+    assert_eq!(value("b").as_deref(), Some("b"));
+    assert!(value("c").is_some());
+    assert!(value("Math").is_some());
+    assert_eq!(value("d"), None);
+}
+
+#[test]
+fn global_property_reads() {
+    let scenario = r#"
+        this.a = 1;
+        this.b = 2;
+        this.c = 3;
+        function read() {
+            return [String(a), b, c].join();
+        }
+        let results = [read()];
+        delete this.a;
+        results.push(read());
+        this.a = 4;
+        results.push(read());
+        results.join(' ')
+        "#;
+    assert_eq!(&exec(scenario), "1,2,3 undefined,2,3 4,2,3");
+}
+
+#[test]
+fn hoisted_var_bindings() {
+    let scenario = r#"
+        var x = 'global';
+        function f() {
+            let before = String(x);
+            var x = 1;
+            return before + ' ' + x;
+        }
+        function g(a = 1) {
+            var a;
+            var b;
+            return a + ' ' + String(b);
+        }
+        [f(), g(2), g()].join()
+        "#;
+    assert_eq!(&exec(scenario), "undefined 1,2 undefined,1 undefined");
+}
+
+#[test]
+fn many_local_bindings() {
+    let scenario = r#"
+        function sum(a, b) {
+            var c = a + b, d = c + 1, e = d + 1, f = e + 1, g = f + 1;
+            let h = g + 1, i = h + 1, j = i + 1;
+            {
+                let k = j + 1;
+                j = k;
+            }
+            return [a, b, c, d, e, f, g, h, i, j].join();
+        }
+        sum(1, 2)
+        "#;
+    assert_eq!(&exec(scenario), "1,2,3,4,5,6,7,8,9,11");
+}
+
+#[test]
+fn simple_function_calls() {
+    let scenario = r#"
+        function fact(n) {
+            if (n <= 1) {
+                return 1;
+            }
+            let rest = fact(n - 1);
+            return n * rest;
+        }
+        function Point(x, y) {
+            this.x = x;
+            this.y = y;
+        }
+        function thrower(a) {
+            var b = a;
+            throw b;
+        }
+        function caught() {
+            try {
+                thrower(5);
+            } catch (e) {
+                return e;
+            }
+        }
+        function hoisted() {
+            var before = String(x);
+            var x = 1;
+            return before;
+        }
+        var p = new Point(1, 2);
+        [fact(5), p.x + p.y, caught(), hoisted(), hoisted()].join()
+        "#;
+    assert_eq!(&exec(scenario), "120,3,5,undefined,undefined");
+}
+
+#[test]
+fn lazy_builtins() {
+    let realm = Realm::create();
+    let global = realm.global_obj.clone();
+    let mut engine = Interpreter::new(realm);
+    assert!(!global.has_field("Math"));
+
+    assert_eq!(&forward(&mut engine, "typeof Math.max"), "function");
+    assert!(global.has_field("Math"));
+    assert_eq!(
+        &forward(&mut engine, "var JSON; typeof JSON.parse"),
+        "function"
+    );
+    assert_eq!(
+        &forward(&mut engine, "TypeError.prototype instanceof Error"),
+        "true"
+    );
+    assert_eq!(&forward(&mut engine, "'console' in this"), "true");
+}
+
+#[test]
+fn lazy_builtins_keep_host_bindings() {
+    let realm = Realm::create();
+    let global = realm.global_obj.clone();
+    global.set_field("TypeError", 1);
+    let mut engine = Interpreter::new(realm);
+
+    assert_eq!(
+        &forward(
+            &mut engine,
+            "try { null.x } catch (e) { e.name + ' ' + TypeError }"
+        ),
+        "TypeError 1"
+    );
+}
+
+#[test]
+fn intrinsics() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let init = r#"
+        function literals() {
+            var Array = {}, Object = {}, RegExp = {};
+            return [[1, 2].concat([3]).length, typeof ({}).hasOwnProperty, /a/.test('a')];
+        }
+        "#;
+
+    forward(&mut engine, init);
+    assert_eq!(
+        &forward(&mut engine, "literals().join()"),
+        "3,function,true"
+    );
+
+    let throw_type_error = engine.realm().intrinsic(Intrinsic::ThrowTypeError);
+    let error = engine
+        .call(&throw_type_error, &mut Value::undefined(), &[])
+        .expect_err("%ThrowTypeError% should throw");
+    assert!(error
+        .get_internal_slot(INSTANCE_PROTOTYPE)
+        .strict_equals(&engine.realm().intrinsic(Intrinsic::TypeErrorPrototype)));
+}
+
+#[test]
+fn host_arrays() {
+    let realm = Realm::create();
+    let global = realm.global_obj.clone();
+    let mut engine = Interpreter::new(realm);
+
+    let list = engine.create_array(&[Value::from(1), Value::from("two")]);
+    global.set_field("list", list);
+    assert_eq!(
+        &forward(
+            &mut engine,
+            "Array.isArray(list) + ' ' + list.length + ' ' + list.join()"
+        ),
+        "true 2 1,two"
+    );
+}
+
+/// A debugger which records the line and the call depth of the statements it pauses on.
+#[derive(Debug, Default)]
+struct PauseRecorder {
+    pauses: Rc<RefCell<Vec<(u32, usize)>>>,
+}
+
+impl Debugger for PauseRecorder {
+    fn on_statement(&mut self, engine: &mut Interpreter, position: Position) {
+        self.pauses
+            .borrow_mut()
+            .push((position.line_number(), engine.call_depth()));
+    }
+}
+
+#[test]
+fn debugger_pauses_on_statements() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let recorder = PauseRecorder::default();
+    let pauses = recorder.pauses.clone();
+    engine.set_debugger(Box::new(recorder));
+
+    let scenario = r#"
+        let a = 1;
+        var f = function (x) {
+            let y = x + a;
+            return y;
+        };
+        f(2);
+        "#;
+    assert_eq!(&forward(&mut engine, scenario), "3");
+    assert_eq!(*pauses.borrow(), [(2, 0), (3, 0), (7, 0), (4, 1), (5, 1)]);
+
+    assert!(engine.take_debugger().is_some());
+    forward(&mut engine, "f(3);");
+    assert_eq!(pauses.borrow().len(), 5);
+}
+
+/// A debugger which inspects the scopes of the statements on the given line.
+#[derive(Debug)]
+struct ScopeInspector {
+    line: u32,
+    seen: Rc<RefCell<Vec<String>>>,
+}
+
+impl Debugger for ScopeInspector {
+    fn on_statement(&mut self, engine: &mut Interpreter, position: Position) {
+        if position.line_number() != self.line {
+            return;
+        }
+        let mut seen = self.seen.borrow_mut();
+        for scope in engine.scopes() {
+            let mut names: Vec<_> = scope.bindings.into_iter().map(|(name, _)| name).collect();
+            names.retain(|name| name.len() == 1);
+            names.sort();
+            seen.push(format!("{:?} {}", scope.kind, names.join(",")));
+        }
+        let result = engine.evaluate("x + y").unwrap();
+        seen.push(result.to_string());
+        let error = engine.evaluate("x +").unwrap_err();
+        assert!(error.to_string().starts_with("SyntaxError"));
+    }
+}
+
+#[test]
+fn debugger_scopes_and_evaluation() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    engine.set_debugger(Box::new(ScopeInspector {
+        line: 4,
+        seen: seen.clone(),
+    }));
+
+    let scenario = r#"
+        let a = 1;
+        function f(x) { let y = x + a;
+            return y;
+        }
+        f(2);
+        "#;
+    forward(&mut engine, scenario);
+    assert_eq!(*seen.borrow(), ["Function x,y", "Global a,f", "5"]);
+}
+
+/// A script whose `if` statements run their branches a different number of times, and whose
+/// `return 3;` never runs.
+const COVERED_SCRIPT: &str = r#"
+        function f(x) {
+            if (x > 1) {
+                return 1;
+            } else if (x > 0) {
+                return 2;
+            }
+            return 3;
+        }
+        f(2);
+        f(1);
+        f(1);
+        if (false) f(0);
+        "#;
+
+#[test]
+fn coverage_counts_statements_and_branches() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert!(engine.take_coverage().is_none());
+    engine.enable_coverage();
+    forward(&mut engine, COVERED_SCRIPT);
+
+    let coverage = engine.take_coverage().expect("the coverage is enabled");
+    let statements: Vec<_> = coverage
+        .statements()
+        .map(|(position, count)| (position.line_number(), count))
+        .collect();
+    assert_eq!(
+        statements,
+        [
+            (2, 1),
+            (3, 3),
+            (4, 1),
+            (6, 2),
+            (10, 1),
+            (11, 1),
+            (12, 1),
+            (13, 1)
+        ]
+    );
+    let branches: Vec<_> = coverage
+        .branches()
+        .map(|(point, counts)| (point.statement.line_number(), point.nesting, counts))
+        .collect();
+    assert_eq!(branches, [(3, 0, [1, 2]), (3, 1, [2, 0]), (13, 0, [0, 1])]);
+
+    // The counts start again from 0 once taken.
+    forward(&mut engine, "f(2);");
+    let coverage = engine.take_coverage().expect("the coverage is enabled");
+    assert_eq!(coverage.statements().count(), 3);
+}
+
+#[test]
+fn coverage_of_statements_which_never_ran() {
+    let mut script = Parser::from_lexer(Lexer::new(COVERED_SCRIPT.as_bytes()))
+        .parse_all()
+        .expect("failed to parse");
+    analyze(&mut script).expect("failed to analyze");
+
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.enable_coverage();
+    script.run(&mut engine).expect("failed to run");
+    let mut coverage = engine.take_coverage().expect("the coverage is enabled");
+    coverage.add_script(&script);
+
+    let never_ran: Vec<_> = coverage
+        .statements()
+        .filter(|&(_, count)| count == 0)
+        .map(|(position, _)| position.line_number())
+        .collect();
+    assert_eq!(never_ran, [8]);
+    assert_eq!(coverage.branches().count(), 3);
+}
+
+#[test]
+fn profile_records_the_calls() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    assert!(engine.stop_profiling().is_none());
+    engine.start_profiling();
+
+    let scenario = r#"
+        function square(x) {
+            return x * x;
+        }
+        function sum(n) {
+            let total = 0;
+            for (let i = 0; i < n; i++) {
+                total += square(i);
+            }
+            return total;
+        }
+        new Array(sum(3));
+        "#;
+    forward(&mut engine, scenario);
+    let profile = engine.stop_profiling().expect("the profiling is started");
+
+    let calls: Vec<_> = profile
+        .events()
+        .iter()
+        .map(|event| match event {
+            FrameEvent::Enter { name, .. } => name.as_str(),
+            FrameEvent::Exit { .. } => "<",
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        ["sum", "square", "<", "square", "<", "square", "<", "<", "Array", "<"]
+    );
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&profile.to_trace_events()).expect("invalid trace events");
+    assert_eq!(trace["traceEvents"].as_array().map(Vec::len), Some(10));
+    assert_eq!(trace["traceEvents"][0]["name"], "sum");
+    assert_eq!(trace["traceEvents"][0]["ph"], "B");
+
+    // The stacks which took less than a microsecond are left out.
+    for line in profile.to_collapsed_stacks().lines() {
+        let (stack, _) = line.rsplit_once(' ').expect("no time in the line");
+        assert!([
+            "(script)",
+            "(script);sum",
+            "(script);sum;square",
+            "(script);Array"
+        ]
+        .contains(&stack));
+    }
+}
+
+#[test]
+fn profile_ends_the_running_frames() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let profile = Rc::new(RefCell::new(None));
+    let stopped = profile.clone();
+    engine.register_global_closure("stop", 0, move |_, _, engine| {
+        *stopped.borrow_mut() = engine.stop_profiling();
+        Ok(Value::undefined())
+    });
+    engine.start_profiling();
+    forward(&mut engine, "function f() { stop(); } f();");
+
+    let profile = profile
+        .borrow_mut()
+        .take()
+        .expect("the profiling is started");
+    let exits = profile
+        .events()
+        .iter()
+        .filter(|event| matches!(event, FrameEvent::Exit { .. }))
+        .count();
+    assert_eq!(profile.events().len(), 4);
+    assert_eq!(exits, 2);
+}
+
+#[test]
+fn profile_replaces_the_frames_of_tail_calls() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    engine.start_profiling();
+    forward(
+        &mut engine,
+        "function f() { return g(); } function g() { return 1; } f();",
+    );
+    let profile = engine.stop_profiling().expect("the profiling is started");
+
+    let events = profile.events();
+    assert_eq!(events.len(), 4);
+    assert!(matches!(&events[2], FrameEvent::Enter { name, .. } if name == "g"));
+}
+
+#[test]
+fn heap_snapshot_follows_the_values_of_the_realm() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let scenario = r#"
+        let points = [{ x: 1, y: 2 }, { x: 3, y: 4 }];
+        let boxed = new String("boxed");
+        let counter = (function () {
+            let count = 0;
+            return function increment() {
+                return ++count;
+            };
+        })();
+        "#;
+    forward(&mut engine, scenario);
+    let snapshot = engine.heap_snapshot(&[]);
+    let nodes = snapshot.nodes();
+    let find = |name: &str| nodes.iter().position(|node| node.name == name);
+
+    // The objects are named by the constructor of their prototype.
+    assert!(nodes
+        .iter()
+        .any(|node| node.kind == NodeKind::Object && node.name == "String"));
+    let points = nodes
+        .iter()
+        .position(|node| {
+            node.kind == NodeKind::Array
+                && node.edges.iter().any(|edge| edge.kind == EdgeKind::Element)
+        })
+        .expect("the array of points is missing");
+    let elements: Vec<_> = nodes[points]
+        .edges
+        .iter()
+        .filter(|edge| edge.kind == EdgeKind::Element)
+        .map(|edge| (edge.name.as_str(), nodes[edge.to].name.as_str()))
+        .collect();
+    assert_eq!(elements, [("0", "Object"), ("1", "Object")]);
+
+    // The closure keeps its environment alive, with the count in it.
+    let increment = find("increment").expect("the closure is missing");
+    let context = nodes[increment]
+        .edges
+        .iter()
+        .find(|edge| edge.name == "context")
+        .expect("the closure has no environment");
+    assert_eq!(nodes[context.to].kind, NodeKind::Environment);
+    assert!(nodes[context.to]
+        .edges
+        .iter()
+        .any(|edge| edge.kind == EdgeKind::Context && edge.name == "count"));
+
+    // The closure is retained by the binding of the global environment.
+    let path = snapshot
+        .retaining_path(increment)
+        .expect("the closure is unreachable");
+    assert_eq!(
+        path.last().map(|(_, edge)| edge.name.as_str()),
+        Some("counter")
+    );
+    assert!(snapshot
+        .retainers(increment)
+        .any(|(_, edge)| edge.name == "counter"));
+
+    let chrome: serde_json::Value =
+        serde_json::from_str(&snapshot.to_chrome_json()).expect("invalid heap snapshot");
+    assert_eq!(chrome["snapshot"]["node_count"], nodes.len());
+    assert_eq!(
+        chrome["nodes"].as_array().map(Vec::len),
+        Some(nodes.len() * 6)
+    );
+    assert_eq!(
+        chrome["edges"].as_array().map(Vec::len),
+        chrome["snapshot"]["edge_count"]
+            .as_u64()
+            .map(|count| count as usize * 3)
+    );
+}
+
+#[test]
+fn heap_snapshot_of_host_values() {
+    let realm = Realm::create();
+    let mut engine = Interpreter::new(realm);
+    let leaked = forward_val(&mut engine, "({ cache: [1, 2, 3] })").unwrap();
+    engine.gc();
+
+    let without = engine.heap_snapshot(&[]);
+    assert!(without.unreached_values() > 0);
+    assert!(!without
+        .nodes()
+        .iter()
+        .any(|node| node.edges.iter().any(|edge| edge.name == "cache")));
+
+    let snapshot = engine.heap_snapshot(&[("leaked", &leaked)]);
+    assert!(snapshot.unreached_values() < without.unreached_values());
+    let cache = snapshot
+        .nodes()
+        .iter()
+        .position(|node| node.edges.iter().any(|edge| edge.name == "cache"))
+        .expect("the host value is missing");
+    let path: Vec<_> = snapshot
+        .retaining_path(cache)
+        .expect("the host value is unreachable")
+        .iter()
+        .map(|(from, edge)| (snapshot.nodes()[*from].name.as_str(), edge.name.as_str()))
+        .collect();
+    assert_eq!(path, [("(roots)", "1"), ("(host)", "leaked")]);
+    assert!(snapshot.to_string().contains("Object"));
+}
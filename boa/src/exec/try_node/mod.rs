@@ -7,6 +7,7 @@ use crate::{
     syntax::ast::node::Try,
     BoaProfiler,
 };
+use std::mem;
 
 #[cfg(test)]
 mod tests;
@@ -14,7 +15,13 @@ mod tests;
 impl Executable for Try {
     fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
         let _timer = BoaProfiler::global().start_event("Try", "exec");
-        let res = self.block().run(interpreter).map_or_else(
+        // The calls in the block, and in the catch block if there is a finally block, aren't in
+        // tail position, as they must run before the handlers.
+        interpreter.try_depth += 1;
+        let res = self.block().run(interpreter);
+        interpreter.try_depth -= 1;
+
+        let res = res.map_or_else(
             |err| {
                 if let Some(catch) = self.catch() {
                     {
@@ -24,17 +31,20 @@ impl Executable for Try {
                         )));
 
                         if let Some(param) = catch.parameter() {
-                            env.create_mutable_binding(
-                                param.to_owned(),
-                                false,
-                                VariableScope::Block,
-                            );
+                            env.create_mutable_binding(param, false, VariableScope::Block);
 
                             env.initialize_binding(param, err);
                         }
                     }
 
+                    let in_try = self.finally().is_some();
+                    if in_try {
+                        interpreter.try_depth += 1;
+                    }
                     let res = catch.block().run(interpreter);
+                    if in_try {
+                        interpreter.try_depth -= 1;
+                    }
 
                     // pop the block env
                     let _ = interpreter.realm_mut().environment.pop();
@@ -47,8 +57,15 @@ impl Executable for Try {
             Ok,
         );
 
+        // The finally block runs like after a normal completion, and only replaces the completion
+        // of the try statement if it returns or throws.
         if let Some(finally) = self.finally() {
-            finally.run(interpreter)?;
+            let is_return = mem::replace(&mut interpreter.is_return, false);
+            let value = finally.run(interpreter)?;
+            if interpreter.is_return {
+                return Ok(value);
+            }
+            interpreter.is_return = is_return;
         }
 
         res
@@ -93,3 +93,98 @@ fn catch_binding_finally() {
     "#;
     assert_eq!(&exec(scenario), "30");
 }
+
+#[test]
+fn finally_after_return() {
+    let scenario = r#"
+        let a = 10;
+        function f() {
+            try {
+                return 20;
+            } finally {
+                a = 30;
+                a += 1;
+            }
+        }
+
+        f() + a;
+    "#;
+    assert_eq!(&exec(scenario), "51");
+}
+
+#[test]
+fn finally_return_overrides_return() {
+    let scenario = r#"
+        function f() {
+            try {
+                return 20;
+            } finally {
+                return 30;
+            }
+        }
+
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "30");
+}
+
+#[test]
+fn finally_return_overrides_throw() {
+    let scenario = r#"
+        function f() {
+            try {
+                throw 20;
+            } catch (err) {
+                throw err + 1;
+            } finally {
+                return 30;
+            }
+        }
+
+        f();
+    "#;
+    assert_eq!(&exec(scenario), "30");
+}
+
+#[test]
+fn finally_throw_overrides_return() {
+    let scenario = r#"
+        function f() {
+            try {
+                return 20;
+            } finally {
+                throw 30;
+            }
+        }
+
+        let a = 10;
+        try {
+            a = f();
+        } catch (err) {
+            a = err;
+        }
+
+        a;
+    "#;
+    assert_eq!(&exec(scenario), "30");
+}
+
+#[test]
+fn catch_return_finally() {
+    let scenario = r#"
+        let a = 10;
+        function f() {
+            try {
+                throw 20;
+            } catch {
+                return 40;
+            } finally {
+                a = 30;
+            }
+            return 50;
+        }
+
+        f() + a;
+    "#;
+    assert_eq!(&exec(scenario), "70");
+}
@@ -0,0 +1,33 @@
+//! With statement execution.
+
+#[cfg(test)]
+mod tests;
+
+use super::{Executable, Interpreter};
+use crate::{
+    builtins::value::ResultValue, environment::lexical_environment::new_with_environment,
+    syntax::ast::node::With, BoaProfiler,
+};
+
+impl Executable for With {
+    fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        let _timer = BoaProfiler::global().start_event("With", "exec");
+        let object = self.object().run(interpreter)?;
+        let object = interpreter.to_object(&object)?;
+
+        {
+            let env = &mut interpreter.realm_mut().environment;
+            env.push(new_with_environment(
+                object,
+                Some(env.get_current_environment_ref().clone()),
+            ));
+        }
+
+        let result = self.body().run(interpreter);
+
+        // pop the object env, even if the body failed
+        let _ = interpreter.realm_mut().environment.pop();
+
+        result
+    }
+}
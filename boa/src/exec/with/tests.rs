@@ -0,0 +1,95 @@
+use crate::exec;
+
+#[test]
+fn with_binding_lookup() {
+    let scenario = r#"
+        let a = 'outer';
+        let b = 'outer';
+        let obj = { a: 'object' };
+        let result;
+        with (obj) {
+            result = [a, b];
+        }
+        result.join()
+    "#;
+    assert_eq!(&exec(scenario), "object,outer");
+}
+
+#[test]
+fn with_assignment() {
+    let scenario = r#"
+        let b = 1;
+        let obj = { a: 1 };
+        with (obj) {
+            a = 2;
+            b = 3;
+        }
+        [obj.a, b, obj.b === undefined].join()
+    "#;
+    assert_eq!(&exec(scenario), "2,3,true");
+}
+
+#[test]
+fn with_method_call() {
+    let scenario = r#"
+        let obj = {
+            value: 'object',
+            read() {
+                return this.value;
+            }
+        };
+        let result;
+        with (obj) {
+            result = read();
+        }
+        result
+    "#;
+    assert_eq!(&exec(scenario), "object");
+}
+
+#[test]
+fn with_unscopables() {
+    let scenario = r#"
+        let a = 'outer';
+        let obj = { a: 'object', b: 'object' };
+        obj[Symbol.unscopables] = { a: true, b: false };
+        let result;
+        with (obj) {
+            result = [a, b];
+        }
+        result.join()
+    "#;
+    assert_eq!(&exec(scenario), "outer,object");
+}
+
+#[test]
+fn with_in_function() {
+    let scenario = r#"
+        function read(obj) {
+            let a = 'local';
+            with (obj) {
+                return a;
+            }
+        }
+        [read({ a: 'object' }), read({})].join()
+    "#;
+    assert_eq!(&exec(scenario), "object,local");
+}
+
+#[test]
+fn with_primitive_and_null() {
+    let scenario = r#"
+        let result;
+        with ('text') {
+            result = length;
+        }
+        let error;
+        try {
+            with (null) {}
+        } catch (e) {
+            error = e.constructor === TypeError;
+        }
+        [result, error].join()
+    "#;
+    assert_eq!(&exec(scenario), "4,true");
+}
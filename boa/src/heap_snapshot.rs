@@ -0,0 +1,605 @@
+//! Heap snapshots, which capture the graph of the values reachable from the realm, to find out what
+//! keeps them alive.
+//!
+//! The `gc` crate doesn't let the heap be walked, so a snapshot is taken by following the values
+//! from the roots: the global object and the environments of the realm, and the values held by the
+//! host, which are given to `Interpreter::heap_snapshot` with a name. The values which are still
+//! alive without being reached, like the ones held by the host and not given, are only counted.
+//!
+//! A snapshot can be written in the `.heapsnapshot` format of the Chrome DevTools, which load it in
+//! their Memory panel, or be summed up by the class of its objects with its `Display`
+//! implementation. The retainers of a node, and the shortest path from the roots to it, are found
+//! with `HeapSnapshot::retainers` and `HeapSnapshot::retaining_path`.
+
+use crate::{
+    builtins::{
+        object::{
+            internal_methods_trait::ObjectInternalMethods, shape::array_index, Object, ObjectKind,
+            INSTANCE_PROTOTYPE,
+        },
+        property::Property,
+        value::{Value, ValueData},
+    },
+    environment::lexical_environment::{Environment, EnvironmentType},
+    exec::{heap, Interpreter},
+};
+use rustc_hash::FxHashMap;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{self, Display},
+    mem::{size_of, size_of_val},
+};
+
+/// The type of a node of a heap snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// A node which isn't a value, like the roots.
+    Synthetic,
+    /// The `undefined`, `null` and boolean values.
+    Hidden,
+    /// An object which is neither an array nor a function.
+    Object,
+    /// An array.
+    Array,
+    /// A function.
+    Closure,
+    /// The environment of a scope, holding its bindings.
+    Environment,
+    /// A string.
+    String,
+    /// A number.
+    Number,
+    /// A bigint.
+    BigInt,
+    /// A symbol.
+    Symbol,
+}
+
+impl NodeKind {
+    /// The types of the nodes in the `.heapsnapshot` format, in the order of `NodeKind::index`.
+    const CHROME_TYPES: [&'static str; 14] = [
+        "hidden",
+        "array",
+        "string",
+        "object",
+        "code",
+        "closure",
+        "regexp",
+        "number",
+        "native",
+        "synthetic",
+        "concatenated string",
+        "sliced string",
+        "symbol",
+        "bigint",
+    ];
+
+    /// Gets the index of the type of the node in the `.heapsnapshot` format. The environments are
+    /// objects, like the contexts of V8.
+    fn index(self) -> usize {
+        match self {
+            Self::Hidden => 0,
+            Self::Array => 1,
+            Self::String => 2,
+            Self::Object | Self::Environment => 3,
+            Self::Closure => 5,
+            Self::Number => 7,
+            Self::Synthetic => 9,
+            Self::Symbol => 12,
+            Self::BigInt => 13,
+        }
+    }
+}
+
+/// The type of an edge of a heap snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// A binding of an environment.
+    Context,
+    /// An array index.
+    Element,
+    /// A property, or one of its accessors.
+    Property,
+    /// An internal slot, or a link which isn't visible to the scripts.
+    Internal,
+}
+
+impl EdgeKind {
+    /// The types of the edges in the `.heapsnapshot` format, in the order of `EdgeKind::index`.
+    const CHROME_TYPES: [&'static str; 7] = [
+        "context", "element", "property", "internal", "hidden", "shortcut", "weak",
+    ];
+
+    /// Gets the index of the type of the edge in the `.heapsnapshot` format.
+    fn index(self) -> usize {
+        match self {
+            Self::Context => 0,
+            Self::Element => 1,
+            Self::Property => 2,
+            Self::Internal => 3,
+        }
+    }
+}
+
+/// A reference from a node of a heap snapshot to another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeapEdge {
+    pub kind: EdgeKind,
+    /// The name of the property or binding, or the index of the element.
+    pub name: String,
+    /// The index of the node which is referenced.
+    pub to: usize,
+}
+
+/// A value or an environment of a heap snapshot.
+#[derive(Debug, Clone)]
+pub struct HeapNode {
+    pub kind: NodeKind,
+    /// The class of an object, the name of a function, or the value of a primitive.
+    pub name: String,
+    /// The approximate size in bytes of the node itself, like `GcStats::heap_size`, with the
+    /// storage of its properties and of its characters.
+    pub self_size: usize,
+    /// The references of the node to other nodes.
+    pub edges: Vec<HeapEdge>,
+}
+
+impl HeapNode {
+    /// Gets the name under which the node is summed up: the name of the objects, and the type of
+    /// the other values.
+    fn class(&self) -> &str {
+        match self.kind {
+            NodeKind::Hidden => "(hidden)",
+            NodeKind::String => "(string)",
+            NodeKind::Number => "(number)",
+            NodeKind::BigInt => "(bigint)",
+            NodeKind::Symbol => "(symbol)",
+            _ => &self.name,
+        }
+    }
+}
+
+/// The graph of the values reachable from the roots, when the snapshot was taken.
+#[derive(Debug, Clone)]
+pub struct HeapSnapshot {
+    /// The nodes, the first one being the roots.
+    nodes: Vec<HeapNode>,
+    /// The number of live values which weren't reached.
+    unreached_values: usize,
+}
+
+impl HeapSnapshot {
+    /// Gets the nodes of the snapshot. The first one is the roots, with the realm and the host
+    /// values after it.
+    pub fn nodes(&self) -> &[HeapNode] {
+        &self.nodes
+    }
+
+    /// Gets the number of live values which weren't reached from the roots. They are held by the
+    /// host without being given to the snapshot, or are garbage which wasn't collected yet, unless
+    /// `Interpreter::gc` runs before the snapshot.
+    pub fn unreached_values(&self) -> usize {
+        self.unreached_values
+    }
+
+    /// Gets the edges which reference a node, with the index of the node they come from.
+    pub fn retainers(&self, node: usize) -> impl Iterator<Item = (usize, &HeapEdge)> + '_ {
+        self.nodes.iter().enumerate().flat_map(move |(from, n)| {
+            n.edges
+                .iter()
+                .filter(move |edge| edge.to == node)
+                .map(move |edge| (from, edge))
+        })
+    }
+
+    /// Finds the shortest path of edges from the roots to a node, with the index of the node
+    /// each edge comes from. Returns `None` if the node doesn't exist.
+    pub fn retaining_path(&self, node: usize) -> Option<Vec<(usize, &HeapEdge)>> {
+        let mut previous: Vec<Option<(usize, &HeapEdge)>> = vec![None; self.nodes.len()];
+        let mut queue = VecDeque::new();
+        queue.push_back(0);
+        while let Some(from) = queue.pop_front() {
+            if from == node {
+                break;
+            }
+            for edge in &self.nodes[from].edges {
+                if edge.to != 0 && previous[edge.to].is_none() {
+                    previous[edge.to] = Some((from, edge));
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = node;
+        while current != 0 {
+            let step = (*previous.get(current)?)?;
+            path.push(step);
+            current = step.0;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Serializes the snapshot in the `.heapsnapshot` format of the Chrome DevTools.
+    pub fn to_chrome_json(&self) -> String {
+        let mut strings = Vec::new();
+        let mut string_indices = FxHashMap::default();
+        let mut string_index = |string: &str| -> usize {
+            *string_indices.entry(string.to_owned()).or_insert_with(|| {
+                strings.push(string.to_owned());
+                strings.len() - 1
+            })
+        };
+
+        // The edges point to the index of the first field of their node.
+        const NODE_FIELDS: usize = 6;
+        let mut nodes = Vec::with_capacity(self.nodes.len() * NODE_FIELDS);
+        let mut edges = Vec::new();
+        for (index, node) in self.nodes.iter().enumerate() {
+            nodes.extend_from_slice(&[
+                node.kind.index(),
+                string_index(&node.name),
+                index * 2 + 1,
+                node.self_size,
+                node.edges.len(),
+                0,
+            ]);
+            for edge in &node.edges {
+                let name = match edge.kind {
+                    EdgeKind::Element => edge.name.parse().unwrap_or_default(),
+                    _ => string_index(&edge.name),
+                };
+                edges.extend_from_slice(&[edge.kind.index(), name, edge.to * NODE_FIELDS]);
+            }
+        }
+
+        json!({
+            "snapshot": {
+                "meta": {
+                    "node_fields": ["type", "name", "id", "self_size", "edge_count", "trace_node_id"],
+                    "node_types": [NodeKind::CHROME_TYPES, "string", "number", "number", "number", "number"],
+                    "edge_fields": ["type", "name_or_index", "to_node"],
+                    "edge_types": [EdgeKind::CHROME_TYPES, "string_or_number", "node"],
+                    "trace_function_info_fields": ["function_id", "name", "script_name", "script_id", "line", "column"],
+                    "trace_node_fields": ["id", "function_info_index", "count", "size", "children"],
+                    "sample_fields": ["timestamp_us", "last_assigned_id"],
+                    "location_fields": ["object_index", "script_id", "line", "column"],
+                },
+                "node_count": self.nodes.len(),
+                "edge_count": edges.len() / 3,
+                "trace_function_count": 0,
+            },
+            "nodes": nodes,
+            "edges": edges,
+            "trace_function_infos": [],
+            "trace_tree": [],
+            "samples": [],
+            "locations": [],
+            "strings": strings,
+        })
+        .to_string()
+    }
+}
+
+impl Display for HeapSnapshot {
+    /// Sums up the nodes by class, from the one taking the most memory.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut classes = BTreeMap::new();
+        for node in self.nodes.iter().skip(1) {
+            if node.kind != NodeKind::Synthetic {
+                let (count, size) = classes.entry(node.class()).or_insert((0, 0));
+                *count += 1;
+                *size += node.self_size;
+            }
+        }
+        let mut classes: Vec<_> = classes.into_iter().collect();
+        classes.sort_by(|(_, (_, a)), (_, (_, b))| b.cmp(a));
+
+        let total: usize = classes.iter().map(|(_, (_, size))| size).sum();
+        writeln!(
+            f,
+            "Heap snapshot: {} nodes, {} bytes, {} unreached values",
+            self.nodes.len(),
+            total,
+            self.unreached_values
+        )?;
+        write!(f, "{:>8} {:>12}  class", "count", "self size")?;
+        for (class, (count, size)) in classes {
+            write!(f, "\n{:>8} {:>12}  {}", count, size, class)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something which is followed to find the nodes of a snapshot.
+#[derive(Debug)]
+enum Item {
+    Value(Value),
+    Environment(Environment),
+}
+
+/// Walks the graph from the roots, giving an index to each value and environment the first time
+/// it is reached.
+#[derive(Debug, Default)]
+struct Walker {
+    nodes: Vec<HeapNode>,
+    /// The index of the nodes, by the address of their value or environment.
+    indices: FxHashMap<usize, usize>,
+    /// The nodes which were reached, but whose edges weren't followed yet.
+    pending: VecDeque<(usize, Item)>,
+}
+
+impl Walker {
+    /// Adds a node which isn't a value, whose edges are added by the caller.
+    fn synthetic(&mut self, name: &str) -> usize {
+        self.add_node(NodeKind::Synthetic, name.to_owned(), 0)
+    }
+
+    fn add_node(&mut self, kind: NodeKind, name: String, self_size: usize) -> usize {
+        self.nodes.push(HeapNode {
+            kind,
+            name,
+            self_size,
+            edges: Vec::new(),
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Gets the index of the node of a value, adding it if it wasn't reached yet.
+    ///
+    /// The immediate values have no address on the heap, so each of them gets its own node.
+    fn value(&mut self, value: &Value) -> usize {
+        if value.is_immediate() {
+            let index = self.add_node(NodeKind::Hidden, String::new(), 0);
+            self.pending.push_back((index, Item::Value(value.clone())));
+            return index;
+        }
+        let address = value.data() as *const ValueData as usize;
+        if let Some(&index) = self.indices.get(&address) {
+            return index;
+        }
+        let index = self.add_node(NodeKind::Hidden, String::new(), 0);
+        self.indices.insert(address, index);
+        self.pending.push_back((index, Item::Value(value.clone())));
+        index
+    }
+
+    /// Gets the index of the node of an environment, adding it if it wasn't reached yet.
+    fn environment(&mut self, environment: &Environment) -> usize {
+        let address = &**environment as *const _ as *const u8 as usize;
+        if let Some(&index) = self.indices.get(&address) {
+            return index;
+        }
+        let index = self.add_node(NodeKind::Environment, String::new(), 0);
+        self.indices.insert(address, index);
+        self.pending
+            .push_back((index, Item::Environment(environment.clone())));
+        index
+    }
+
+    fn edge(&mut self, from: usize, kind: EdgeKind, name: String, value: &Value) {
+        let to = self.value(value);
+        self.nodes[from].edges.push(HeapEdge { kind, name, to });
+    }
+
+    /// Follows the nodes which were reached, until all of them are.
+    fn walk(&mut self) {
+        while let Some((index, item)) = self.pending.pop_front() {
+            match item {
+                Item::Value(value) => self.walk_value(index, &value),
+                Item::Environment(environment) => self.walk_environment(index, &environment),
+            }
+        }
+    }
+
+    fn walk_value(&mut self, index: usize, value: &Value) {
+        let base = if value.is_immediate() {
+            0
+        } else {
+            size_of::<ValueData>()
+        };
+        let (kind, name, self_size) = match value.data() {
+            ValueData::Undefined | ValueData::Null | ValueData::Boolean(_) => {
+                (NodeKind::Hidden, value.to_string(), base)
+            }
+            ValueData::String(ref string) => (
+                NodeKind::String,
+                string.to_string(),
+                base + string.len() * size_of::<u16>(),
+            ),
+            ValueData::Rational(_) | ValueData::Integer(_) => {
+                (NodeKind::Number, value.to_string(), base)
+            }
+            ValueData::BigInt(_) => (NodeKind::BigInt, value.to_string(), base),
+            ValueData::Symbol(ref symbol) => {
+                let symbol = symbol.borrow();
+                self.walk_object(index, &symbol);
+                (
+                    NodeKind::Symbol,
+                    value.to_string(),
+                    base + object_size(&symbol),
+                )
+            }
+            ValueData::Object(ref object) => {
+                let object = object.borrow();
+                self.walk_object(index, &object);
+                let (kind, name) = if object.func.is_some() {
+                    (NodeKind::Closure, function_name(&object))
+                } else if let ObjectKind::Array = object.kind {
+                    (NodeKind::Array, "Array".to_owned())
+                } else {
+                    (NodeKind::Object, class_name(&object))
+                };
+                (kind, name, base + object_size(&object))
+            }
+        };
+
+        let node = &mut self.nodes[index];
+        node.kind = kind;
+        node.name = name;
+        node.self_size = self_size;
+    }
+
+    /// Adds the edges of the properties, the internal slots and the environment of an object.
+    fn walk_object(&mut self, index: usize, object: &Object) {
+        for (key, property) in object.properties.iter() {
+            let kind = if array_index(&key).is_some() {
+                EdgeKind::Element
+            } else {
+                EdgeKind::Property
+            };
+            self.property_edges(index, kind, &key, &property);
+        }
+        for (id, property) in object.sym_properties.iter() {
+            let key = format!("<symbol {}>", id);
+            self.property_edges(index, EdgeKind::Property, &key, property);
+        }
+
+        let mut slots: Vec<_> = object.internal_slots.iter().collect();
+        slots.sort_by_key(|&(slot, _)| slot);
+        for (slot, value) in slots {
+            self.edge(index, EdgeKind::Internal, slot.clone(), value);
+        }
+
+        if let Some(environment) = object.func.as_ref().and_then(|f| f.environment.as_ref()) {
+            let to = self.environment(environment);
+            self.nodes[index].edges.push(HeapEdge {
+                kind: EdgeKind::Internal,
+                name: "context".to_owned(),
+                to,
+            });
+        }
+    }
+
+    /// Adds the edges of the value and the accessors of a property.
+    fn property_edges(&mut self, index: usize, kind: EdgeKind, key: &str, property: &Property) {
+        if let Some(ref value) = property.value {
+            self.edge(index, kind, key.to_owned(), value);
+        }
+        if let Some(ref get) = property.get {
+            self.edge(index, EdgeKind::Property, format!("get {}", key), get);
+        }
+        if let Some(ref set) = property.set {
+            self.edge(index, EdgeKind::Property, format!("set {}", key), set);
+        }
+    }
+
+    /// Adds the edges of the bindings of an environment, and of its outer environment.
+    fn walk_environment(&mut self, index: usize, environment: &Environment) {
+        let record = environment.borrow();
+        let mut bindings = record.bindings();
+        bindings.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, value) in &bindings {
+            self.edge(index, EdgeKind::Context, name.clone(), value);
+        }
+
+        let kind = record.get_environment_type();
+        if let (EnvironmentType::Global, Some(global)) = (kind, record.get_global_object()) {
+            self.edge(index, EdgeKind::Internal, "global".to_owned(), &global);
+        }
+        if let Some(outer) = record.get_outer_environment() {
+            let to = self.environment(&outer);
+            self.nodes[index].edges.push(HeapEdge {
+                kind: EdgeKind::Internal,
+                name: "outer".to_owned(),
+                to,
+            });
+        }
+
+        let node = &mut self.nodes[index];
+        node.name = match kind {
+            EnvironmentType::Declarative => "(declarative environment)",
+            EnvironmentType::Function => "(function environment)",
+            EnvironmentType::Global => "(global environment)",
+            EnvironmentType::Object => "(object environment)",
+        }
+        .to_owned();
+        node.self_size = size_of_val(&**record) + bindings.len() * size_of::<Value>();
+    }
+}
+
+/// The approximate size of an object, with the storage of its properties and internal slots.
+fn object_size(object: &Object) -> usize {
+    size_of::<Object>()
+        + (object.properties.len() + object.sym_properties.len()) * size_of::<Property>()
+        + object.internal_slots.len() * size_of::<(String, Value)>()
+}
+
+/// Gets the name of a function, or `(anonymous)` if it doesn't have one.
+fn function_name(function: &Object) -> String {
+    match function
+        .properties
+        .get("name")
+        .and_then(|p| p.value.clone())
+    {
+        Some(name) if name.is_string() && !name.to_string().is_empty() => name.to_string(),
+        _ => "(anonymous)".to_owned(),
+    }
+}
+
+/// Gets the class of an object, which is the name of the constructor of its prototype, or
+/// `Object` if it doesn't have one.
+fn class_name(object: &Object) -> String {
+    let prototype = object.get_internal_slot(INSTANCE_PROTOTYPE);
+    let constructor = prototype
+        .as_object()
+        .and_then(|prototype| prototype.properties.get("constructor")?.value.clone());
+    let constructor = constructor.as_ref().and_then(|c| c.as_object());
+    match constructor {
+        Some(ref constructor) if constructor.func.is_some() => function_name(constructor),
+        _ => "Object".to_owned(),
+    }
+}
+
+impl Interpreter {
+    /// Takes a snapshot of the values reachable from the realm and from the given host values,
+    /// which are named in the snapshot like properties.
+    ///
+    /// The values held by the host appear in the snapshot only if they are given, so that a value
+    /// the host leaked can be found with what it retains.
+    pub fn heap_snapshot(&self, host_values: &[(&str, &Value)]) -> HeapSnapshot {
+        let mut walker = Walker::default();
+        let roots = walker.synthetic("(roots)");
+        let realm = walker.synthetic("(realm)");
+        let host = walker.synthetic("(host)");
+        for (index, &to) in [realm, host].iter().enumerate() {
+            walker.nodes[roots].edges.push(HeapEdge {
+                kind: EdgeKind::Element,
+                name: index.to_string(),
+                to,
+            });
+        }
+
+        walker.edge(
+            realm,
+            EdgeKind::Property,
+            "global".to_owned(),
+            &self.realm.global_obj,
+        );
+        for (index, environment) in self.realm.environment.environments().enumerate() {
+            let to = walker.environment(environment);
+            walker.nodes[realm].edges.push(HeapEdge {
+                kind: EdgeKind::Element,
+                name: index.to_string(),
+                to,
+            });
+        }
+        for &(name, value) in host_values {
+            walker.edge(host, EdgeKind::Property, name.to_owned(), value);
+        }
+        walker.walk();
+
+        let values = walker.indices.len()
+            - walker
+                .nodes
+                .iter()
+                .filter(|node| node.kind == NodeKind::Environment)
+                .count();
+        HeapSnapshot {
+            nodes: walker.nodes,
+            unreached_values: heap::stats().values.saturating_sub(values),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Hooks for embedders to audit and restrict what the scripts do outside of the engine.
+//!
+//! Before a builtin does something observable from outside the engine, it asks the `HostPolicy`
+//! set with `Interpreter::set_host_policy`. The policy sees every operation, so it can log them,
+//! and it decides whether each one happens, is skipped, or makes the builtin throw a `TypeError`.
+//! Without a policy, every operation is allowed.
+//!
+//! The engine itself only writes to the console. The timers, the modules and the workers are
+//! provided by the host, like the CLI, whose builtins consult the policy too, with
+//! `Interpreter::check_host_operation`.
+
+use crate::{
+    builtins::{console::LogMessage, value::Value},
+    exec::Interpreter,
+};
+use std::fmt::Debug;
+
+/// An operation of a builtin which is observable from outside the engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HostOperation<'a> {
+    /// A message written with the `console` object, once it is formatted and indented.
+    ConsoleOutput(&'a LogMessage),
+    /// A timer started with `setTimeout`, or with `setInterval` if it repeats.
+    CreateTimer {
+        /// The delay of the timer, in milliseconds.
+        delay: f64,
+        /// Whether the timer runs again after each delay.
+        repeat: bool,
+    },
+    /// A module loaded from the given path.
+    LoadModule(&'a str),
+    /// A worker started with the script of the given path.
+    SpawnWorker(&'a str),
+}
+
+/// The decision of a `HostPolicy` about an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostDecision {
+    /// The operation happens.
+    Allow,
+    /// The operation doesn't happen, and the builtin returns as if it did, with `undefined` if it
+    /// was to produce a value.
+    Skip,
+    /// The operation doesn't happen, and the builtin throws a `TypeError` with the given message.
+    Deny(String),
+}
+
+/// A policy deciding which operations of the builtins happen, implemented by the host.
+pub trait HostPolicy: Debug {
+    /// Decides whether an operation happens.
+    fn check(&mut self, operation: &HostOperation<'_>) -> HostDecision;
+}
+
+impl Interpreter {
+    /// Sets the policy deciding which operations of the builtins happen, replacing the previous
+    /// one.
+    pub fn set_host_policy(&mut self, policy: Box<dyn HostPolicy>) {
+        self.host_policy = Some(policy);
+    }
+
+    /// Removes the policy, and returns it.
+    pub fn take_host_policy(&mut self) -> Option<Box<dyn HostPolicy>> {
+        self.host_policy.take()
+    }
+
+    /// Asks the policy whether an operation happens.
+    ///
+    /// Returns `false` if the operation is skipped, and throws a `TypeError` if it is denied.
+    pub fn check_host_operation(&mut self, operation: &HostOperation<'_>) -> Result<bool, Value> {
+        let decision = match self.host_policy.as_mut() {
+            Some(policy) => policy.check(operation),
+            None => HostDecision::Allow,
+        };
+        match decision {
+            HostDecision::Allow => Ok(true),
+            HostDecision::Skip => Ok(false),
+            HostDecision::Deny(message) => self.throw_type_error(message).map(|_| false),
+        }
+    }
+}
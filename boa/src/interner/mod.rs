@@ -0,0 +1,154 @@
+//! String interning.
+//!
+//! The names found in the source code, like identifiers and property names, are interned by the
+//! lexer. Each distinct name is stored once, and it's represented everywhere else by a `Sym`,
+//! a small integer id. The parser, the scope analysis and the environments compare and hash
+//! these ids instead of the strings.
+//!
+//! Interned strings are never freed, so only the names written in the source code, and the
+//! names used by the engine itself, should be interned. Strings created while running a script
+//! must not be interned.
+
+use gc::{unsafe_empty_trace, Finalize, Trace};
+use rustc_hash::FxHashMap;
+use std::{fmt, num::NonZeroU32, sync::Mutex};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(test)]
+mod tests;
+
+/// The interner shared by all the threads.
+static INTERNER: Mutex<Option<Interner>> = Mutex::new(None);
+
+/// An interned string.
+///
+/// Two symbols are equal if, and only if, they were created from the same string.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sym(NonZeroU32);
+
+impl Sym {
+    /// Interns a string, returning its symbol.
+    ///
+    /// Interning the same string again returns the same symbol.
+    pub fn new(string: &str) -> Self {
+        with_interner(|interner| interner.intern(string))
+    }
+
+    /// Gets the string this symbol was created from.
+    pub fn as_str(self) -> &'static str {
+        with_interner(|interner| interner.resolve(self))
+    }
+}
+
+impl Finalize for Sym {}
+
+unsafe impl Trace for Sym {
+    unsafe_empty_trace!();
+}
+
+impl fmt::Debug for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Sym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for Sym {
+    fn from(string: &str) -> Self {
+        Self::new(string)
+    }
+}
+
+impl From<&String> for Sym {
+    fn from(string: &String) -> Self {
+        Self::new(string)
+    }
+}
+
+impl From<String> for Sym {
+    fn from(string: String) -> Self {
+        Self::new(&string)
+    }
+}
+
+impl From<Box<str>> for Sym {
+    fn from(string: Box<str>) -> Self {
+        Self::new(&string)
+    }
+}
+
+impl PartialEq<str> for Sym {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Sym {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Sym {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Sym {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+        Ok(Self::new(&string))
+    }
+}
+
+/// Runs the given function with the interner locked.
+fn with_interner<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Interner) -> R,
+{
+    let mut interner = INTERNER.lock().expect("the interner lock was poisoned");
+    f(interner.get_or_insert_with(Interner::default))
+}
+
+/// The table of interned strings.
+#[derive(Debug, Default)]
+struct Interner {
+    symbols: FxHashMap<&'static str, Sym>,
+    strings: Vec<&'static str>,
+}
+
+impl Interner {
+    /// Interns a string, leaking it the first time it's seen.
+    fn intern(&mut self, string: &str) -> Sym {
+        if let Some(&sym) = self.symbols.get(string) {
+            return sym;
+        }
+
+        let string: &'static str = Box::leak(string.into());
+        self.strings.push(string);
+        let sym = Sym(NonZeroU32::new(self.strings.len() as u32).expect("too many symbols"));
+        self.symbols.insert(string, sym);
+        sym
+    }
+
+    /// Gets the string of a symbol.
+    fn resolve(&self, sym: Sym) -> &'static str {
+        self.strings[sym.0.get() as usize - 1]
+    }
+}
@@ -0,0 +1,28 @@
+//! Tests for the string interner.
+
+use super::*;
+
+#[test]
+fn same_string_same_symbol() {
+    let a = Sym::new("interner_test_a");
+    let b = Sym::new("interner_test_b");
+
+    assert_eq!(a, Sym::new("interner_test_a"));
+    assert_eq!(b, Sym::from(String::from("interner_test_b")));
+    assert_ne!(a, b);
+}
+
+#[test]
+fn resolve() {
+    let sym = Sym::new("interner_test_resolve");
+
+    assert_eq!(sym.as_str(), "interner_test_resolve");
+    assert_eq!(sym.to_string(), "interner_test_resolve");
+    assert_eq!(format!("{:?}", sym), "\"interner_test_resolve\"");
+    assert!(sym == "interner_test_resolve");
+}
+
+#[test]
+fn empty_string() {
+    assert_eq!(Sym::new("").as_str(), "");
+}
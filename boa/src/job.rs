@@ -0,0 +1,104 @@
+//! The queue of jobs run by the host once the running script has finished.
+//!
+//! The specification leaves the scheduling of jobs, like the reactions of promises, to the host.
+//! Embedders can drive the jobs from their own event loop by implementing the `JobQueue` trait,
+//! and setting it on the realm. The default queue runs the jobs in the order they were enqueued.
+//!
+//! More information:
+//!  - [ECMAScript reference][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-jobs
+
+use crate::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+};
+use std::{cell::RefCell, collections::VecDeque, fmt::Debug, future::Future, pin::Pin};
+
+/// A job, which calls a function with the given arguments.
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// The function called by the job.
+    callback: Value,
+    /// The arguments passed to the function.
+    args: Vec<Value>,
+}
+
+impl Job {
+    /// Creates a new job, which calls `callback` with `args`.
+    pub fn new(callback: Value, args: Vec<Value>) -> Self {
+        Self { callback, args }
+    }
+
+    /// Runs the job, with an undefined `this` value.
+    pub fn run(&self, interpreter: &mut Interpreter) -> ResultValue {
+        interpreter.call(&self.callback, &mut Value::undefined(), &self.args)
+    }
+}
+
+/// A queue of jobs, implemented by the host.
+///
+/// The methods take the queue by shared reference, so that a job can enqueue other jobs while the
+/// queue is running.
+pub trait JobQueue: Debug {
+    /// Enqueues a job, to be run after the running script and the jobs enqueued before it.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-hostenqueuepromisejob
+    fn enqueue_promise_job(&self, job: Job, interpreter: &mut Interpreter);
+
+    /// Runs the jobs until the queue is empty.
+    ///
+    /// If a job throws, the error is returned, and the remaining jobs are left in the queue.
+    fn run_jobs(&self, interpreter: &mut Interpreter) -> Result<(), Value>;
+
+    /// Runs the jobs until the queue is empty, from an asynchronous context.
+    ///
+    /// The default implementation runs all the jobs at once, with `run_jobs`. Queues integrated
+    /// with an async runtime can yield to it between the jobs instead.
+    fn run_jobs_async<'a>(
+        &'a self,
+        interpreter: &'a mut Interpreter,
+    ) -> Pin<Box<dyn Future<Output = Result<(), Value>> + 'a>> {
+        Box::pin(async move { self.run_jobs(interpreter) })
+    }
+}
+
+/// A job queue which runs the jobs in the order they were enqueued.
+#[derive(Debug, Default)]
+pub struct SimpleJobQueue {
+    jobs: RefCell<VecDeque<Job>>,
+}
+
+impl SimpleJobQueue {
+    /// Creates a new, empty job queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Discards the jobs which haven't run yet.
+    pub fn clear(&self) {
+        self.jobs.borrow_mut().clear();
+    }
+}
+
+impl JobQueue for SimpleJobQueue {
+    fn enqueue_promise_job(&self, job: Job, _: &mut Interpreter) {
+        self.jobs.borrow_mut().push_back(job);
+    }
+
+    fn run_jobs(&self, interpreter: &mut Interpreter) -> Result<(), Value> {
+        // The borrow must end before the job runs, as the job can enqueue other jobs.
+        loop {
+            let job = self.jobs.borrow_mut().pop_front();
+            match job {
+                Some(job) => {
+                    job.run(interpreter)?;
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+}
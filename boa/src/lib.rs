@@ -35,34 +35,46 @@
 )]
 
 pub mod builtins;
+pub mod clock;
+pub mod coverage;
+pub mod debugger;
 pub mod environment;
 pub mod exec;
+pub mod heap_snapshot;
+pub mod host;
+pub mod interner;
+pub mod job;
 pub mod profiler;
 pub mod realm;
 pub mod syntax;
 
-use crate::{builtins::value::ResultValue, syntax::ast::node::StatementList};
+mod random;
+
+use crate::{
+    builtins::value::ResultValue,
+    syntax::{ast::node::StatementList, optimizer::OptimizerOptions, parser::error::ParseError},
+};
 pub use crate::{
     exec::{Executable, Interpreter},
     profiler::BoaProfiler,
     realm::Realm,
-    syntax::{lexer::Lexer, parser::Parser},
+    syntax::{lexer::Lexer, parse, parser::Parser, ScriptError},
 };
 
-fn parser_expr(src: &str) -> Result<StatementList, String> {
-    let mut lexer = Lexer::new(src);
-    lexer.lex().map_err(|e| format!("Syntax Error: {}", e))?;
-    let tokens = lexer.tokens;
-    Parser::new(&tokens)
-        .parse_all()
-        .map_err(|e| format!("Parsing Error: {}", e))
+/// Parses the code with `parse`, formatting its errors as `forward` returns them.
+fn parser_expr(src: &str, options: OptimizerOptions) -> Result<StatementList, String> {
+    parse(src.as_bytes(), options).map_err(|e| match e {
+        ScriptError::Parse(ParseError::Lex { err }) => format!("Syntax Error: {}", err),
+        ScriptError::Parse(e) => format!("Parsing Error: {}", e),
+        ScriptError::Early(e) => format!("Syntax Error: {}", e),
+    })
 }
 
 /// Execute the code using an existing Interpreter
 /// The str is consumed and the state of the Interpreter is changed
 pub fn forward(engine: &mut Interpreter, src: &str) -> String {
     // Setup executor
-    let expr = match parser_expr(src) {
+    let expr = match parser_expr(src, engine.optimizer_options()) {
         Ok(res) => res,
         Err(e) => return e,
     };
@@ -78,7 +90,7 @@ pub fn forward(engine: &mut Interpreter, src: &str) -> String {
 pub fn forward_val(engine: &mut Interpreter, src: &str) -> ResultValue {
     let main_timer = BoaProfiler::global().start_event("Main", "Main");
     // Setup executor
-    let result = match parser_expr(src) {
+    let result = match parser_expr(src, engine.optimizer_options()) {
         Ok(expr) => expr.run(engine),
         Err(e) => {
             eprintln!("{}", e);
@@ -1,13 +1,24 @@
 #![allow(missing_copy_implementations, missing_debug_implementations)]
 
+use crate::{
+    builtins::value::{Value, ValueData},
+    clock::Instant,
+    exec::Interpreter,
+};
 #[cfg(feature = "profiler")]
 use measureme::{EventId, Profiler, TimingGuard};
 #[cfg(feature = "profiler")]
 use once_cell::sync::OnceCell;
-use std::fmt::{self, Debug};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug, Write},
+    time::Duration,
+};
 #[cfg(feature = "profiler")]
 use std::{
     path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
     thread::{current, ThreadId},
 };
 
@@ -22,6 +33,19 @@ type SerializationSink = measureme::FileSerializationSink;
 #[cfg(feature = "profiler")]
 pub struct BoaProfiler {
     profiler: Profiler<SerializationSink>,
+    load_hits: AtomicUsize,
+    load_misses: AtomicUsize,
+    store_hits: AtomicUsize,
+    store_misses: AtomicUsize,
+}
+
+/// The number of hits and misses of the inline caches of the property accesses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InlineCacheStats {
+    pub load_hits: usize,
+    pub load_misses: usize,
+    pub store_hits: usize,
+    pub store_misses: usize,
 }
 
 /// This static instance should never be public, and its only access should be done through the `global()` and `drop()` methods
@@ -41,7 +65,43 @@ impl BoaProfiler {
 
     pub fn default() -> BoaProfiler {
         let profiler = Profiler::new(Path::new("./my_trace")).unwrap();
-        BoaProfiler { profiler }
+        BoaProfiler {
+            profiler,
+            load_hits: AtomicUsize::new(0),
+            load_misses: AtomicUsize::new(0),
+            store_hits: AtomicUsize::new(0),
+            store_misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Counts a property load that hit or missed its inline cache.
+    pub fn record_load(&self, hit: bool) {
+        let counter = if hit {
+            &self.load_hits
+        } else {
+            &self.load_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts a property store that hit or missed its inline cache.
+    pub fn record_store(&self, hit: bool) {
+        let counter = if hit {
+            &self.store_hits
+        } else {
+            &self.store_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Gets the number of hits and misses of the inline caches so far.
+    pub fn inline_cache_stats(&self) -> InlineCacheStats {
+        InlineCacheStats {
+            load_hits: self.load_hits.load(Ordering::Relaxed),
+            load_misses: self.load_misses.load(Ordering::Relaxed),
+            store_hits: self.store_hits.load(Ordering::Relaxed),
+            store_misses: self.store_misses.load(Ordering::Relaxed),
+        }
     }
 
     pub fn global() -> &'static BoaProfiler {
@@ -89,7 +149,186 @@ impl BoaProfiler {
         ()
     }
 
+    pub fn record_load(&self, _hit: bool) {}
+
+    pub fn record_store(&self, _hit: bool) {}
+
+    /// The inline cache statistics are only collected with the `profiler` feature.
+    pub fn inline_cache_stats(&self) -> InlineCacheStats {
+        InlineCacheStats::default()
+    }
+
     pub fn global() -> BoaProfiler {
         BoaProfiler
     }
 }
+
+/// A profile of the JavaScript functions called by the scripts, recorded between
+/// `Interpreter::start_profiling` and `Interpreter::stop_profiling`.
+///
+/// Unlike the events of the `BoaProfiler`, which time the phases of the engine and need the
+/// `profiler` feature, the frames of a profile are the calls made by the scripts, so they show
+/// which functions the time is spent in. Each call is recorded, including the ones of the
+/// built-in functions, rather than sampled.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    events: Vec<FrameEvent>,
+    duration: Duration,
+}
+
+/// An event of a profile, at the time elapsed since the start of the profiling.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameEvent {
+    /// A function is called.
+    Enter { name: String, time: Duration },
+    /// The last function called returns.
+    Exit { time: Duration },
+}
+
+impl Profile {
+    /// Gets the events of the profile, in the order they happened.
+    pub fn events(&self) -> &[FrameEvent] {
+        &self.events
+    }
+
+    /// Gets the time elapsed between the start and the end of the profiling.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Formats the profile in the [Trace Event Format][format], which is loaded by the
+    /// performance panel of the Chrome DevTools, or by `chrome://tracing`.
+    ///
+    /// [format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+    pub fn to_trace_events(&self) -> String {
+        let micros = |time: Duration| time.as_secs_f64() * 1_000_000.0;
+        let events: Vec<_> = self
+            .events
+            .iter()
+            .map(|event| match event {
+                FrameEvent::Enter { name, time } => json!({
+                    "name": name, "cat": "js", "ph": "B", "ts": micros(*time), "pid": 1, "tid": 1,
+                }),
+                FrameEvent::Exit { time } => json!({
+                    "ph": "E", "ts": micros(*time), "pid": 1, "tid": 1,
+                }),
+            })
+            .collect();
+        json!({ "traceEvents": events, "displayTimeUnit": "ms" }).to_string()
+    }
+
+    /// Formats the profile as collapsed stacks, read by flame graph tools like `flamegraph.pl`
+    /// or `inferno-flamegraph`.
+    ///
+    /// Each line has the names of the frames of a stack, from the outermost one, separated by
+    /// `;`, followed by the number of microseconds spent in its innermost frame. The code
+    /// running outside of the functions is in the `(script)` frame.
+    pub fn to_collapsed_stacks(&self) -> String {
+        let mut stacks = BTreeMap::new();
+        let mut stack = vec![String::from("(script)")];
+        let mut last = Duration::default();
+        let mut spend = |stack: &[String], time: Duration| {
+            *stacks.entry(stack.join(";")).or_insert(0) += (time - last).as_micros();
+            last = time;
+        };
+        for event in &self.events {
+            match event {
+                FrameEvent::Enter { name, time } => {
+                    spend(&stack, *time);
+                    stack.push(name.replace([';', '\n'], "_"));
+                }
+                FrameEvent::Exit { time } => {
+                    spend(&stack, *time);
+                    stack.pop();
+                }
+            }
+        }
+        spend(&stack, self.duration);
+
+        let mut collapsed = String::new();
+        for (stack, micros) in stacks.into_iter().filter(|&(_, micros)| micros > 0) {
+            let _ = writeln!(collapsed, "{} {}", stack, micros);
+        }
+        collapsed
+    }
+}
+
+/// Records the frames of a profile while the scripts run.
+#[derive(Debug)]
+pub(crate) struct ProfileRecorder {
+    start: Instant,
+    events: Vec<FrameEvent>,
+    /// The number of frames entered and not exited yet.
+    depth: usize,
+}
+
+impl ProfileRecorder {
+    /// Records a call of the given function, named by its `name` property.
+    fn enter(&mut self, function: &Value) {
+        let name = match function.get_field("name").data() {
+            ValueData::String(ref name) if !name.is_empty() => name.to_string(),
+            _ => String::from("(anonymous)"),
+        };
+        let time = self.start.elapsed();
+        self.events.push(FrameEvent::Enter { name, time });
+        self.depth += 1;
+    }
+
+    /// Records the return of the last function called, unless it was called before the start of
+    /// the profiling.
+    fn exit(&mut self) {
+        if self.depth > 0 {
+            let time = self.start.elapsed();
+            self.events.push(FrameEvent::Exit { time });
+            self.depth -= 1;
+        }
+    }
+}
+
+impl Interpreter {
+    /// Starts recording the calls made by the scripts, if it isn't recording them already.
+    pub fn start_profiling(&mut self) {
+        if self.profile.is_none() {
+            self.profile = Some(ProfileRecorder {
+                start: Instant::now(),
+                events: Vec::new(),
+                depth: 0,
+            });
+        }
+    }
+
+    /// Stops recording the calls, and returns the profile, whose frames still running end now.
+    /// Returns `None` if the profiling wasn't started.
+    pub fn stop_profiling(&mut self) -> Option<Profile> {
+        let mut recorder = self.profile.take()?;
+        while recorder.depth > 0 {
+            recorder.exit();
+        }
+        Some(Profile {
+            duration: recorder.start.elapsed(),
+            events: recorder.events,
+        })
+    }
+
+    /// Records a call of the given function, if the profiling is started.
+    pub(crate) fn enter_profile_frame(&mut self, function: &Value) {
+        if let Some(ref mut recorder) = self.profile {
+            recorder.enter(function);
+        }
+    }
+
+    /// Records the return of the last function called, if the profiling is started.
+    pub(crate) fn exit_profile_frame(&mut self) {
+        if let Some(ref mut recorder) = self.profile {
+            recorder.exit();
+        }
+    }
+
+    /// Records a call in tail position, which replaces the frame of the last function called.
+    pub(crate) fn replace_profile_frame(&mut self, function: &Value) {
+        if let Some(ref mut recorder) = self.profile {
+            recorder.exit();
+            recorder.enter(function);
+        }
+    }
+}
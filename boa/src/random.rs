@@ -0,0 +1,51 @@
+//! The sources of the random numbers produced by `Math.random`.
+//!
+//! The numbers come from the generator of the host, unless the interpreter is given a seed with
+//! `Interpreter::set_random_seed`. They then come from a xorshift128+ generator, the algorithm
+//! of the major engines, which produces the same sequence for the same seed on every platform.
+
+/// Gets a random number in the range `[0, 1)` from the generator of the host.
+pub(crate) fn random() -> f64 {
+    rand::random::<f64>()
+}
+
+/// A xorshift128+ generator of random numbers.
+///
+/// More information:
+///  - [Further scramblings of Marsaglia's xorshift generators][paper]
+///
+/// [paper]: https://arxiv.org/abs/1404.0390
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Xorshift128Plus {
+    state: [u64; 2],
+}
+
+impl Xorshift128Plus {
+    /// Creates a generator from a seed.
+    ///
+    /// The state is derived from the seed with SplitMix64, so that similar seeds produce
+    /// unrelated sequences, and the state is never all zeros.
+    pub(crate) fn new(seed: u64) -> Self {
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        let state = [split_mix(), split_mix()];
+        Self { state }
+    }
+
+    /// Gets the next random number, in the range `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        let [mut s1, s0] = self.state;
+        let result = s0.wrapping_add(s1);
+        s1 ^= s1 << 23;
+        s1 ^= s0 ^ (s1 >> 17) ^ (s0 >> 26);
+        self.state = [s0, s1];
+        // The 53 high bits make a number with every bit of the mantissa random.
+        (result >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
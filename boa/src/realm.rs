@@ -8,18 +8,21 @@ use crate::{
     builtins::{
         self,
         function::{Function, NativeFunctionData},
+        intrinsics::{self, Intrinsic},
         value::{Value, ValueData},
     },
     environment::{
-        declarative_environment_record::DeclarativeEnvironmentRecord,
+        declarative_environment_record::{DeclarativeBindings, DeclarativeEnvironmentRecord},
         global_environment_record::GlobalEnvironmentRecord,
         lexical_environment::LexicalEnvironment,
         object_environment_record::ObjectEnvironmentRecord,
     },
+    job::{JobQueue, SimpleJobQueue},
     BoaProfiler,
 };
 use gc::{Gc, GcCell};
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::FxHashSet;
+use std::{cell::RefCell, rc::Rc};
 
 /// Representation of a Realm.
 ///
@@ -29,6 +32,8 @@ pub struct Realm {
     pub global_obj: Value,
     pub global_env: Gc<GcCell<Box<GlobalEnvironmentRecord>>>,
     pub environment: LexicalEnvironment,
+    /// The queue of the jobs run once the running script has finished.
+    pub job_queue: Rc<dyn JobQueue>,
 }
 
 impl Realm {
@@ -44,6 +49,7 @@ impl Realm {
             global_obj: global.clone(),
             global_env,
             environment: LexicalEnvironment::new(global),
+            job_queue: Rc::new(SimpleJobQueue::new()),
         };
 
         // Add new builtIns to Realm
@@ -61,6 +67,21 @@ impl Realm {
         builtins::init(global);
     }
 
+    /// Gets an intrinsic object of the realm, like `%Array.prototype%`, initializing its builtin
+    /// first if it wasn't used yet.
+    pub fn intrinsic(&self, intrinsic: Intrinsic) -> Value {
+        intrinsics::get(&self.global_obj, intrinsic)
+    }
+
+    /// Initializes all the builtins which weren't used yet.
+    ///
+    /// The builtins are only added to the global object when a script uses them, so a host
+    /// exposing the global object to the scripts under another name, like `self`, calls this
+    /// first for the scripts to find all of them on it.
+    pub fn init_builtins(&self) {
+        intrinsics::init_all(&self.global_obj);
+    }
+
     /// Utility to add a function to the global object
     pub fn register_global_func(self, func_name: &str, func: NativeFunctionData) -> Self {
         let func = Function::builtin(Vec::new(), func);
@@ -69,6 +90,13 @@ impl Realm {
 
         self
     }
+
+    /// Utility to replace the default job queue with one implemented by the host
+    pub fn with_job_queue(mut self, job_queue: Rc<dyn JobQueue>) -> Self {
+        self.job_queue = job_queue;
+
+        self
+    }
 }
 
 // Similar to new_global_environment in lexical_environment, except we need to return a GlobalEnvirionment
@@ -88,7 +116,7 @@ fn new_global_environment(
     });
 
     let dcl_rec = Box::new(DeclarativeEnvironmentRecord {
-        env_rec: FxHashMap::default(),
+        env_rec: DeclarativeBindings::new(),
         outer_env: None,
     });
 
@@ -97,5 +125,6 @@ fn new_global_environment(
         global_this_binding: this_value,
         declarative_record: dcl_rec,
         var_names: FxHashSet::default(),
+        property_slots: RefCell::default(),
     })))
 }
@@ -0,0 +1,581 @@
+//! Semantic analysis of the AST.
+//!
+//! The scope analysis runs between parsing and execution. It resolves each identifier to the
+//! location of its binding, so that the interpreter doesn't need to look for it through every
+//! environment, and it reports the errors that can be found before running the code, like
+//! redeclared `let` or `const` bindings.
+//!
+//! The scopes follow the environments created by the interpreter: one for each function call,
+//! block, `for` loop, `catch` clause and `with` statement. Identifiers are only resolved when
+//! their binding is declared in the same function, outside of the global scope, and isn't
+//! shadowed by the object of a `with` statement. The identifiers which no scope declares, outside
+//! of the `with` statements, are marked as global, so that the interpreter looks for them in the
+//! global environment only.
+//!
+//! The slot of a binding is its index in the declarations of its scope, which are numbered in the
+//! order the interpreter creates the bindings of the environment: the parameters of a function,
+//! its `arguments` object and its `var` declarations, then the `let` and `const` declarations.
+//!
+//! The analysis also finds the simple functions, which don't create closures, nor use
+//! `arguments`, `eval` or `with`. The environment of their calls can't be referred to once they
+//! are done, so the interpreter reuses it instead of allocating a new one for each call.
+
+use crate::{
+    interner::Sym,
+    syntax::ast::{
+        node::{
+            ArrowFunctionDecl, BindingLocation, Block, Catch, ConstDeclList, ForLoop,
+            FormalParameter, FunctionDecl, FunctionExpr, Identifier, LetDeclList, Node,
+            StatementList, VarDeclList, With,
+        },
+        visitor::{self, Visitor, VisitorMut},
+    },
+};
+use rustc_hash::FxHashMap;
+use std::{error, fmt};
+
+#[cfg(test)]
+mod tests;
+
+/// An error found by the semantic analysis.
+#[derive(Debug, Clone)]
+pub struct AnalyzerError {
+    details: String,
+}
+
+impl AnalyzerError {
+    /// Creates a new analyzer error with the given details.
+    fn new<M>(details: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            details: details.into(),
+        }
+    }
+}
+
+impl fmt::Display for AnalyzerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl error::Error for AnalyzerError {}
+
+/// Runs the scope analysis on the given script, resolving its identifiers.
+///
+/// # Errors
+///
+/// Returns the first early error found in the script, like a redeclared lexical binding.
+pub fn analyze(script: &mut StatementList) -> Result<(), AnalyzerError> {
+    let mut analyzer = ScopeAnalyzer::default();
+
+    analyzer.enter_scope(ScopeKind::Global);
+    analyzer.declare_var_scoped(script.statements());
+    analyzer.declare_lexical(script.statements());
+    analyzer.visit_statement_list_mut(script);
+    analyzer.exit_scope();
+
+    analyzer.error.map_or(Ok(()), Err)
+}
+
+/// Returns the names of the `var` and function declarations of a function body, which are
+/// hoisted to the function scope.
+pub(crate) fn var_declared_names(statements: &[Node]) -> Vec<Sym> {
+    let mut collector = VarNameCollector::default();
+    for statement in statements {
+        collector.visit_node(statement);
+    }
+    collector.names
+}
+
+/// Checks if a function is simple: its parameters have no default values, and its body doesn't
+/// create closures, nor refer to `arguments` or `eval`, nor contain `with` statements.
+///
+/// Nothing can refer to the environment of a call of a simple function once the call is done, so
+/// the interpreter reuses it for the next calls, and doesn't create the `arguments` object.
+fn is_simple_function(parameters: &[FormalParameter], body: &[Node]) -> bool {
+    if parameters.iter().any(|param| param.init().is_some()) {
+        return false;
+    }
+
+    let mut checker = SimpleFunctionChecker::new();
+    for statement in body {
+        checker.visit_node(statement);
+    }
+    checker.simple
+}
+
+/// The kind of a scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScopeKind {
+    /// The top level scope of the script.
+    Global,
+    /// The top level scope of a function, holding its parameters and `var` declarations.
+    Function,
+    /// The scope of the body of a function with default parameter values, whose declarations
+    /// are separate from the parameters.
+    Body,
+    /// A scope created by a block, a `for` loop or a `catch` clause.
+    Block,
+    /// The scope of the body of a `with` statement, whose bindings are the properties of an
+    /// object, only known at runtime.
+    With,
+}
+
+/// The kind of a declared binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclarationKind {
+    /// A `var` or function declaration.
+    Var,
+    /// A `let` or `const` declaration.
+    Lexical,
+    /// A function or `catch` parameter.
+    Parameter,
+    /// A binding created by the interpreter without a declaration, like the `arguments` object
+    /// of a function, which the declarations of the code replace.
+    Implicit,
+}
+
+/// A binding declared in a scope.
+#[derive(Debug, Clone, Copy)]
+struct Declaration {
+    kind: DeclarationKind,
+    slot: u32,
+    initialized: bool,
+}
+
+/// A scope, with the bindings declared in it.
+#[derive(Debug)]
+struct Scope {
+    kind: ScopeKind,
+    declarations: FxHashMap<Sym, Declaration>,
+}
+
+/// The scope analyzer, which keeps track of the scopes enclosing the node being visited.
+#[derive(Debug, Default)]
+struct ScopeAnalyzer {
+    scopes: Vec<Scope>,
+    error: Option<AnalyzerError>,
+    in_parameters: bool,
+    /// Whether the node being visited is in a function other than an arrow function, where
+    /// `new.target` is allowed.
+    in_function: bool,
+}
+
+impl ScopeAnalyzer {
+    /// Records an error, keeping only the first one found.
+    fn fail(&mut self, error: AnalyzerError) {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+    }
+
+    /// Enters a new scope.
+    fn enter_scope(&mut self, kind: ScopeKind) {
+        self.scopes.push(Scope {
+            kind,
+            declarations: FxHashMap::default(),
+        });
+    }
+
+    /// Exits the current scope.
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Declares a binding in the current scope.
+    fn declare(&mut self, name: Sym, kind: DeclarationKind) {
+        let scope = self
+            .scopes
+            .last_mut()
+            .expect("there is no scope to declare in");
+
+        if let Some(existing) = scope.declarations.get_mut(&name) {
+            if existing.kind == DeclarationKind::Implicit {
+                existing.kind = kind;
+                existing.initialized = kind != DeclarationKind::Lexical;
+            } else if kind == DeclarationKind::Lexical || existing.kind == DeclarationKind::Lexical
+            {
+                self.fail(AnalyzerError::new(format!(
+                    "Identifier '{}' has already been declared",
+                    name
+                )));
+            }
+            return;
+        }
+
+        let slot = scope.declarations.len() as u32;
+        scope.declarations.insert(
+            name,
+            Declaration {
+                kind,
+                slot,
+                initialized: kind != DeclarationKind::Lexical,
+            },
+        );
+    }
+
+    /// Declares the `var` and function declarations of a function body, which are hoisted to
+    /// the function scope from any nested block.
+    fn declare_var_scoped(&mut self, statements: &[Node]) {
+        for name in var_declared_names(statements) {
+            self.declare(name, DeclarationKind::Var);
+        }
+    }
+
+    /// Declares the `let` and `const` declarations of a list of statements in the current scope.
+    fn declare_lexical(&mut self, statements: &[Node]) {
+        for statement in statements {
+            self.declare_lexical_statement(statement);
+        }
+    }
+
+    /// Declares the `let` and `const` declarations of a statement in the current scope.
+    ///
+    /// Statements that don't create an environment when executed, like `if` statements without
+    /// a block, declare their bindings in the current scope too.
+    fn declare_lexical_statement(&mut self, statement: &Node) {
+        match statement {
+            Node::LetDeclList(list) => {
+                for name in list.as_ref().iter().flat_map(|decl| decl.names()) {
+                    self.declare(name, DeclarationKind::Lexical);
+                }
+            }
+            Node::ConstDeclList(list) => {
+                for name in list.as_ref().iter().flat_map(|decl| decl.names()) {
+                    self.declare(name, DeclarationKind::Lexical);
+                }
+            }
+            Node::If(if_stmt) => {
+                self.declare_lexical_statement(if_stmt.body());
+                if let Some(else_node) = if_stmt.else_node() {
+                    self.declare_lexical_statement(else_node);
+                }
+            }
+            Node::WhileLoop(while_loop) => self.declare_lexical_statement(while_loop.expr()),
+            Node::DoWhileLoop(do_while) => self.declare_lexical_statement(do_while.body()),
+            Node::Switch(switch) => {
+                for case in switch.cases() {
+                    self.declare_lexical(case.body().statements());
+                }
+                if let Some(default) = switch.default() {
+                    self.declare_lexical_statement(default);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Declares the function declarations of a block in its scope, where the interpreter binds
+    /// them besides the function scope.
+    fn declare_block_functions(&mut self, statements: &[Node]) {
+        for statement in statements {
+            if let Node::FunctionDecl(function) = statement {
+                self.declare(function.name(), DeclarationKind::Var);
+            }
+        }
+    }
+
+    /// Marks a lexical binding of the current scope as initialized.
+    fn initialize(&mut self, name: Sym) {
+        if let Some(decl) = self
+            .scopes
+            .last_mut()
+            .and_then(|scope| scope.declarations.get_mut(&name))
+        {
+            decl.initialized = true;
+        }
+    }
+
+    /// Checks that a `var` declaration doesn't conflict with a lexical binding in the scopes it
+    /// is hoisted through.
+    fn check_var_declaration(&mut self, name: Sym) {
+        for scope in self.scopes.iter().rev() {
+            if let Some(decl) = scope.declarations.get(&name) {
+                if decl.kind == DeclarationKind::Lexical {
+                    self.fail(AnalyzerError::new(format!(
+                        "Identifier '{}' has already been declared",
+                        name
+                    )));
+                    return;
+                }
+            }
+            if scope.kind != ScopeKind::Block && scope.kind != ScopeKind::With {
+                return;
+            }
+        }
+    }
+
+    /// Finds the location of the binding with the given name, if it's declared in the current
+    /// function and not in the global scope.
+    fn resolve(&self, name: Sym) -> Option<BindingLocation> {
+        if self.in_parameters {
+            return None;
+        }
+
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if let Some(decl) = scope.declarations.get(&name) {
+                return if scope.kind == ScopeKind::Global {
+                    None
+                } else {
+                    Some(BindingLocation::new(
+                        depth as u32,
+                        decl.slot,
+                        !decl.initialized,
+                    ))
+                };
+            }
+            if scope.kind != ScopeKind::Block && scope.kind != ScopeKind::Body {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Checks if the binding with the given name can only be in the global environment, that is,
+    /// if the scopes enclosing the current one don't declare it, and the object of a `with`
+    /// statement can't provide it.
+    fn is_global(&self, name: Sym) -> bool {
+        // Like the other identifiers of the parameters, they are left to the full lookup.
+        if self.in_parameters {
+            return false;
+        }
+
+        self.scopes.iter().rev().all(|scope| match scope.kind {
+            ScopeKind::Global => true,
+            ScopeKind::With => false,
+            _ => !scope.declarations.contains_key(&name),
+        })
+    }
+
+    /// Analyzes a function defining its own `new.target`, unlike the arrow functions, and
+    /// returns whether it is a simple function.
+    fn analyze_ordinary_function(
+        &mut self,
+        parameters: &mut [FormalParameter],
+        body: &mut [Node],
+    ) -> bool {
+        let in_function = self.in_function;
+        self.in_function = true;
+        let simple = self.analyze_function(parameters, body);
+        self.in_function = in_function;
+        simple
+    }
+
+    /// Analyzes a function with the given parameters and body, and returns whether it is a
+    /// simple function.
+    fn analyze_function(&mut self, parameters: &mut [FormalParameter], body: &mut [Node]) -> bool {
+        let simple = is_simple_function(parameters, body);
+        self.enter_scope(ScopeKind::Function);
+        for param in parameters.iter() {
+            self.declare(param.name(), DeclarationKind::Parameter);
+        }
+        // The function calls bind an `arguments` object after the parameters, unless a parameter
+        // has the same name or the function is simple.
+        if !simple {
+            self.declare(Sym::new("arguments"), DeclarationKind::Implicit);
+        }
+        for param in parameters.iter_mut() {
+            self.visit_formal_parameter_mut(param);
+        }
+
+        // The body of a function with default parameter values has its own environment, whose
+        // declarations aren't seen by the closures created by the default values.
+        let separate_body = parameters.iter().any(|param| param.init().is_some());
+        if separate_body {
+            self.enter_scope(ScopeKind::Body);
+        }
+        self.declare_var_scoped(body);
+        self.declare_lexical(body);
+        for node in body {
+            self.visit_node_mut(node);
+        }
+        if separate_body {
+            self.exit_scope();
+        }
+        self.exit_scope();
+        simple
+    }
+}
+
+impl VisitorMut for ScopeAnalyzer {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        if let (Node::NewTarget, false) = (&node, self.in_function) {
+            self.fail(AnalyzerError::new(
+                "new.target expression is not allowed here",
+            ));
+        }
+        visitor::walk_node_mut(self, node);
+    }
+
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+        let binding = self.resolve(identifier.sym());
+        identifier.set_binding(binding);
+        identifier.set_global(binding.is_none() && self.is_global(identifier.sym()));
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        self.enter_scope(ScopeKind::Block);
+        self.declare_lexical(block.statements());
+        self.declare_block_functions(block.statements());
+        visitor::walk_block_mut(self, block);
+        self.exit_scope();
+    }
+
+    fn visit_for_loop_mut(&mut self, for_loop: &mut ForLoop) {
+        self.enter_scope(ScopeKind::Block);
+        if let Some(init) = for_loop.init() {
+            self.declare_lexical_statement(init);
+        }
+        visitor::walk_for_loop_mut(self, for_loop);
+        self.exit_scope();
+    }
+
+    fn visit_catch_mut(&mut self, catch: &mut Catch) {
+        self.enter_scope(ScopeKind::Block);
+        if let Some(param) = catch.parameter() {
+            self.declare(param, DeclarationKind::Parameter);
+        }
+        visitor::walk_catch_mut(self, catch);
+        self.exit_scope();
+    }
+
+    fn visit_with_mut(&mut self, with: &mut With) {
+        self.visit_node_mut(with.object_mut());
+        self.enter_scope(ScopeKind::With);
+        self.visit_node_mut(with.body_mut());
+        self.exit_scope();
+    }
+
+    fn visit_var_decl_list_mut(&mut self, list: &mut VarDeclList) {
+        for decl in list.as_mut() {
+            for name in decl.names() {
+                self.check_var_declaration(name);
+            }
+            if let Some(init) = decl.init_mut() {
+                self.visit_node_mut(init);
+            }
+            self.visit_binding_mut(decl.binding_mut());
+        }
+    }
+
+    fn visit_let_decl_list_mut(&mut self, list: &mut LetDeclList) {
+        for decl in list.as_mut() {
+            if let Some(init) = decl.init_mut() {
+                self.visit_node_mut(init);
+            }
+            self.visit_binding_mut(decl.binding_mut());
+            for name in decl.names() {
+                self.initialize(name);
+            }
+        }
+    }
+
+    fn visit_const_decl_list_mut(&mut self, list: &mut ConstDeclList) {
+        for decl in list.as_mut() {
+            self.visit_node_mut(decl.init_mut());
+            self.visit_binding_mut(decl.binding_mut());
+            for name in decl.names() {
+                self.initialize(name);
+            }
+        }
+    }
+
+    fn visit_formal_parameter_mut(&mut self, param: &mut FormalParameter) {
+        let in_parameters = self.in_parameters;
+        self.in_parameters = true;
+        visitor::walk_formal_parameter_mut(self, param);
+        self.in_parameters = in_parameters;
+    }
+
+    fn visit_function_decl_mut(&mut self, function: &mut FunctionDecl) {
+        // The parameters and the body are borrowed separately.
+        let mut parameters = function.parameters().to_vec();
+        let simple =
+            self.analyze_ordinary_function(&mut parameters, function.body_mut().statements_mut());
+        function.parameters_mut().clone_from_slice(&parameters);
+        function.set_simple(simple);
+    }
+
+    fn visit_function_expr_mut(&mut self, function: &mut FunctionExpr) {
+        let mut parameters = function.parameters().to_vec();
+        let simple =
+            self.analyze_ordinary_function(&mut parameters, function.body_mut().statements_mut());
+        function.parameters_mut().clone_from_slice(&parameters);
+        function.set_simple(simple);
+    }
+
+    fn visit_arrow_function_decl_mut(&mut self, function: &mut ArrowFunctionDecl) {
+        let mut parameters = function.params().to_vec();
+        let simple = self.analyze_function(&mut parameters, function.body_mut().statements_mut());
+        function.params_mut().clone_from_slice(&parameters);
+        function.set_simple(simple);
+    }
+}
+
+/// Collects the names of the `var` and function declarations hoisted to a function scope.
+#[derive(Debug, Default)]
+struct VarNameCollector {
+    names: Vec<Sym>,
+}
+
+impl<'ast> Visitor<'ast> for VarNameCollector {
+    fn visit_var_decl_list(&mut self, list: &'ast VarDeclList) {
+        self.names
+            .extend(list.as_ref().iter().flat_map(|decl| decl.names()));
+    }
+
+    fn visit_function_decl(&mut self, function: &'ast FunctionDecl) {
+        self.names.push(function.name());
+    }
+
+    fn visit_function_expr(&mut self, _: &'ast FunctionExpr) {}
+
+    fn visit_arrow_function_decl(&mut self, _: &'ast ArrowFunctionDecl) {}
+}
+
+/// Checks if the body of a function makes it a simple function.
+#[derive(Debug)]
+struct SimpleFunctionChecker {
+    simple: bool,
+    arguments: Sym,
+    eval: Sym,
+}
+
+impl SimpleFunctionChecker {
+    /// Creates a checker which didn't find anything preventing a function from being simple yet.
+    fn new() -> Self {
+        Self {
+            simple: true,
+            arguments: Sym::new("arguments"),
+            eval: Sym::new("eval"),
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for SimpleFunctionChecker {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        if identifier.sym() == self.arguments || identifier.sym() == self.eval {
+            self.simple = false;
+        }
+    }
+
+    fn visit_with(&mut self, _: &'ast With) {
+        self.simple = false;
+    }
+
+    fn visit_function_decl(&mut self, _: &'ast FunctionDecl) {
+        self.simple = false;
+    }
+
+    fn visit_function_expr(&mut self, _: &'ast FunctionExpr) {
+        self.simple = false;
+    }
+
+    fn visit_arrow_function_decl(&mut self, _: &'ast ArrowFunctionDecl) {
+        self.simple = false;
+    }
+}
@@ -0,0 +1,289 @@
+//! Tests for the scope analysis.
+
+use super::*;
+use crate::syntax::{lexer::Lexer, parser::Parser};
+
+/// Parses and analyzes the given JavaScript source code.
+fn analyze_src(src: &str) -> Result<StatementList, AnalyzerError> {
+    let mut script = Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .expect("failed to parse");
+    analyze(&mut script).map(|_| script)
+}
+
+/// Collects the bindings of all the identifiers found in the AST.
+#[derive(Debug, Default)]
+struct BindingCollector<'ast> {
+    bindings: Vec<(&'ast str, Option<BindingLocation>)>,
+}
+
+impl<'ast> Visitor<'ast> for BindingCollector<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        self.bindings
+            .push((identifier.as_ref(), identifier.binding()));
+    }
+}
+
+/// The name of an identifier, with the depth, slot and TDZ flag of its binding.
+type ResolvedIdentifier = (String, Option<(u32, u32, bool)>);
+
+/// Analyzes the given source code and returns the bindings of its identifiers, in order.
+fn bindings(src: &str) -> Vec<ResolvedIdentifier> {
+    let script = analyze_src(src).expect("failed to analyze");
+    let mut collector = BindingCollector::default();
+    collector.visit_statement_list(&script);
+    collector
+        .bindings
+        .into_iter()
+        .map(|(name, binding)| {
+            (
+                name.to_owned(),
+                binding.map(|b| (b.depth(), b.slot(), b.needs_tdz_check())),
+            )
+        })
+        .collect()
+}
+
+/// Checks that analyzing the given source code fails with the given message.
+fn check_error(src: &str, msg: &str) {
+    match analyze_src(src) {
+        Ok(_) => panic!("analyzing {:?} should have failed", src),
+        Err(e) => assert_eq!(e.to_string(), msg),
+    }
+}
+
+#[test]
+fn lexical_redeclaration() {
+    check_error(
+        "let a = 1; let a = 2;",
+        "Identifier 'a' has already been declared",
+    );
+    check_error(
+        "const a = 1; var a = 2;",
+        "Identifier 'a' has already been declared",
+    );
+    check_error(
+        "var a = 1; let a = 2;",
+        "Identifier 'a' has already been declared",
+    );
+    check_error(
+        "function f(a) { let a = 1; }",
+        "Identifier 'a' has already been declared",
+    );
+    check_error(
+        "let b = 1; { var b = 2; }",
+        "Identifier 'b' has already been declared",
+    );
+    check_error(
+        "function f() {} let f = 1;",
+        "Identifier 'f' has already been declared",
+    );
+}
+
+#[test]
+fn new_target_outside_functions() {
+    check_error("new.target", "new.target expression is not allowed here");
+    check_error(
+        "let f = () => new.target;",
+        "new.target expression is not allowed here",
+    );
+    assert!(analyze_src("function f() { return () => new.target; }").is_ok());
+}
+
+#[test]
+fn allowed_redeclaration() {
+    assert!(analyze_src("var a = 1; var a = 2;").is_ok());
+    assert!(analyze_src("let a = 1; { let a = 2; }").is_ok());
+    assert!(analyze_src("function f(a) { var a = 1; }").is_ok());
+    assert!(analyze_src("let a = 1; function f() { var a = 2; }").is_ok());
+    assert!(analyze_src("try {} catch (e) { let f = e; }").is_ok());
+}
+
+#[test]
+fn resolved_locations() {
+    assert_eq!(
+        bindings(
+            r#"
+            function f(a, b) {
+                let c = a;
+                {
+                    let d = b;
+                    c = d;
+                }
+            }
+            "#
+        ),
+        vec![
+            ("a".to_owned(), Some((0, 0, false))),
+            ("b".to_owned(), Some((1, 1, false))),
+            ("c".to_owned(), Some((1, 2, false))),
+            ("d".to_owned(), Some((0, 0, false))),
+        ]
+    );
+}
+
+#[test]
+fn hoisted_declarations() {
+    assert_eq!(
+        bindings(
+            r#"
+            function f() {
+                g(x);
+                function g() {}
+                { var x = 1; }
+            }
+            "#
+        ),
+        vec![
+            ("g".to_owned(), Some((0, 1, false))),
+            ("x".to_owned(), Some((0, 2, false))),
+        ]
+    );
+}
+
+#[test]
+fn arguments_object() {
+    assert_eq!(
+        bindings(
+            r#"
+            function f(a) {
+                var arguments;
+                let b = arguments;
+            }
+            function g(arguments) {
+                arguments;
+            }
+            "#
+        ),
+        vec![
+            ("arguments".to_owned(), Some((0, 1, false))),
+            ("arguments".to_owned(), Some((0, 0, false))),
+        ]
+    );
+}
+
+#[test]
+fn temporal_dead_zone() {
+    assert_eq!(
+        bindings(
+            r#"
+            function f() {
+                a;
+                let a = a;
+                a;
+            }
+            "#
+        ),
+        vec![
+            ("a".to_owned(), Some((0, 0, true))),
+            ("a".to_owned(), Some((0, 0, true))),
+            ("a".to_owned(), Some((0, 0, false))),
+        ]
+    );
+}
+
+#[test]
+fn unresolved_identifiers() {
+    assert_eq!(
+        bindings(
+            r#"
+            let a = 1;
+            function f(b = a) {
+                let c = b;
+                return function () { return c; };
+            }
+            a;
+            "#
+        ),
+        vec![
+            ("a".to_owned(), None),
+            ("b".to_owned(), Some((1, 0, false))),
+            ("c".to_owned(), None),
+            ("a".to_owned(), None),
+        ]
+    );
+}
+
+/// Collects the names of the global identifiers found in the AST.
+#[derive(Debug, Default)]
+struct GlobalCollector<'ast> {
+    names: Vec<&'ast str>,
+}
+
+impl<'ast> Visitor<'ast> for GlobalCollector<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        if identifier.is_global() {
+            self.names.push(identifier.as_ref());
+        }
+    }
+}
+
+/// Analyzes the given source code and returns the names of its global identifiers, in order.
+fn globals(src: &str) -> Vec<String> {
+    let script = analyze_src(src).expect("failed to analyze");
+    let mut collector = GlobalCollector::default();
+    collector.visit_statement_list(&script);
+    collector.names.into_iter().map(str::to_owned).collect()
+}
+
+#[test]
+fn global_identifiers() {
+    assert_eq!(
+        globals(
+            r#"
+            let a = 1;
+            function f(b = c) {
+                var d = a + b;
+                return function () { return arguments.length + d + Math.PI; };
+            }
+            with (a) { a + e; }
+            for (let i = 0; i < a; i++) { g(i); }
+            "#
+        ),
+        vec!["a", "Math", "a", "a", "g"]
+    );
+}
+
+/// Collects whether the functions found in the AST are simple functions.
+#[derive(Debug, Default)]
+struct SimpleFunctionCollector {
+    simple: Vec<bool>,
+}
+
+impl<'ast> Visitor<'ast> for SimpleFunctionCollector {
+    fn visit_function_decl(&mut self, function: &'ast FunctionDecl) {
+        self.simple.push(function.is_simple());
+        visitor::walk_function_decl(self, function);
+    }
+
+    fn visit_function_expr(&mut self, function: &'ast FunctionExpr) {
+        self.simple.push(function.is_simple());
+        visitor::walk_function_expr(self, function);
+    }
+
+    fn visit_arrow_function_decl(&mut self, function: &'ast ArrowFunctionDecl) {
+        self.simple.push(function.is_simple());
+        visitor::walk_arrow_function_decl(self, function);
+    }
+}
+
+#[test]
+fn simple_functions() {
+    let script = analyze_src(
+        r#"
+        function add(a, b) { let c = a + b; return c; }
+        function outer() { return () => 1; }
+        function args() { return arguments.length; }
+        function scoped(o) { with (o) { return x; } }
+        function defaults(a = 1) { return a; }
+        let double = x => x * 2;
+        "#,
+    )
+    .expect("failed to analyze");
+    let mut collector = SimpleFunctionCollector::default();
+    collector.visit_statement_list(&script);
+    assert_eq!(
+        collector.simple,
+        vec![true, false, true, false, false, false, true]
+    );
+}
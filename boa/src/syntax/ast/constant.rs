@@ -7,7 +7,7 @@
 //! [spec]: https://tc39.es/ecma262/#sec-primary-expression-literals
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Grammar_and_types#Literals
 
-use crate::builtins::bigint::BigInt;
+use crate::builtins::{bigint::BigInt, string::JsString};
 use gc::{Finalize, Trace};
 use std::fmt::{Display, Formatter, Result};
 
@@ -40,7 +40,7 @@ pub enum Const {
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-terms-and-definitions-string-value
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Grammar_and_types#String_literals
-    String(Box<str>),
+    String(JsString),
 
     /// A floating-point number literal.
     ///
@@ -115,25 +115,31 @@ pub enum Const {
 
 impl From<&str> for Const {
     fn from(s: &str) -> Self {
-        Self::String(s.to_owned().into_boxed_str())
+        Self::String(s.into())
     }
 }
 
 impl From<&String> for Const {
     fn from(s: &String) -> Self {
-        Self::String(s.clone().into_boxed_str())
+        Self::String(s.into())
     }
 }
 
 impl From<Box<str>> for Const {
     fn from(s: Box<str>) -> Self {
-        Self::String(s)
+        Self::String(s.into())
     }
 }
 
 impl From<String> for Const {
     fn from(s: String) -> Self {
-        Self::String(s.into_boxed_str())
+        Self::String(s.into())
+    }
+}
+
+impl From<JsString> for Const {
+    fn from(s: JsString) -> Self {
+        Self::String(s)
     }
 }
 
@@ -164,13 +170,48 @@ impl From<bool> for Const {
 impl Display for Const {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match *self {
-            Self::String(ref st) => write!(f, "\"{}\"", st),
+            Self::String(ref st) => display_code_points(f, st.code_points()),
+            Self::Num(num) if num.is_nan() => f.write_str("NaN"),
+            Self::Num(num) if num.is_infinite() => {
+                f.write_str(if num < 0.0 { "-Infinity" } else { "Infinity" })
+            }
             Self::Num(num) => write!(f, "{}", num),
             Self::Int(num) => write!(f, "{}", num),
-            Self::BigInt(ref num) => write!(f, "{}", num),
+            Self::BigInt(ref num) => write!(f, "{}n", num),
             Self::Bool(v) => write!(f, "{}", v),
             Self::Null => write!(f, "null"),
             Self::Undefined => write!(f, "undefined"),
         }
     }
 }
+
+/// Displays a string as a double quoted string literal, escaping the characters that can't be
+/// written in it.
+pub(in crate::syntax::ast) fn display_string(f: &mut Formatter<'_>, st: &str) -> Result {
+    display_code_points(f, st.chars().map(Ok))
+}
+
+/// Displays a sequence of code points as a double quoted string literal. Lone surrogates are
+/// written as escape sequences.
+fn display_code_points<I>(f: &mut Formatter<'_>, code_points: I) -> Result
+where
+    I: Iterator<Item = std::result::Result<char, u16>>,
+{
+    f.write_str("\"")?;
+    for c in code_points {
+        match c {
+            Ok('"') => f.write_str("\\\"")?,
+            Ok('\\') => f.write_str("\\\\")?,
+            Ok('\n') => f.write_str("\\n")?,
+            Ok('\r') => f.write_str("\\r")?,
+            Ok('\t') => f.write_str("\\t")?,
+            Ok('\u{8}') => f.write_str("\\b")?,
+            Ok('\u{c}') => f.write_str("\\f")?,
+            Ok(c @ '\u{2028}') | Ok(c @ '\u{2029}') => write!(f, "\\u{{{:x}}}", c as u32)?,
+            Ok(c) if c.is_control() && (c as u32) < 0x100 => write!(f, "\\x{:02x}", c as u32)?,
+            Ok(c) => write!(f, "{}", c)?,
+            Err(surrogate) => write!(f, "\\u{{{:x}}}", surrogate)?,
+        }
+    }
+    f.write_str("\"")
+}
@@ -0,0 +1,100 @@
+//! Rendering of the AST as a [Graphviz][graphviz] graph, in the DOT language.
+//!
+//! The graph is built from the [ESTree](../estree/index.html) form of the AST, so its nodes have
+//! the names of the ESTree nodes, and its edges the names of their fields. The scalar fields, like
+//! the names of the identifiers or the operators, are written in the label of their node.
+//!
+//! The output can be turned into an image with `dot -Tsvg ast.dot > ast.svg`.
+//!
+//! [graphviz]: https://graphviz.org/
+
+use super::{estree::to_estree, node::StatementList};
+use serde_json::Value;
+use std::fmt::Write;
+
+#[cfg(test)]
+mod tests;
+
+/// Renders a script as a DOT graph.
+pub fn to_dot(script: &StatementList) -> String {
+    let mut graph = Graph::default();
+    graph.add(&to_estree(script));
+
+    let mut dot = String::from("digraph ast {\n    node [shape=box, fontname=\"monospace\"];\n");
+    dot.push_str(&graph.nodes);
+    dot.push_str(&graph.edges);
+    dot.push_str("}\n");
+    dot
+}
+
+/// The statements of the graph, which are written as the ESTree nodes are visited.
+#[derive(Debug, Default)]
+struct Graph {
+    /// The node statements, each with its label.
+    nodes: String,
+    /// The edge statements, each labeled with the field of its child.
+    edges: String,
+    /// The number of nodes added so far, which is the identifier of the next one.
+    count: usize,
+}
+
+impl Graph {
+    /// Adds an ESTree node and its children.
+    fn add(&mut self, node: &Value) {
+        let id = self.count;
+        self.count += 1;
+
+        let mut label = match node.get("type") {
+            Some(Value::String(kind)) => kind.clone(),
+            _ => String::new(),
+        };
+        let mut children = Vec::new();
+        if let Value::Object(fields) = node {
+            for (field, value) in fields.iter().filter(|(field, _)| *field != "type") {
+                match value {
+                    Value::Null => {}
+                    Value::Object(_) => children.push((field.clone(), value)),
+                    Value::Array(elements) => {
+                        for (index, element) in elements.iter().enumerate() {
+                            let field = format!("{}[{}]", field, index);
+                            if element.is_object() {
+                                children.push((field, element));
+                            } else {
+                                let _ = write!(label, "\n{}: {}", field, element);
+                            }
+                        }
+                    }
+                    scalar => {
+                        let _ = write!(label, "\n{}: {}", field, scalar);
+                    }
+                }
+            }
+        }
+
+        let _ = writeln!(self.nodes, "    n{} [label=\"{}\"];", id, escape(&label));
+        for (field, child) in children {
+            let _ = writeln!(
+                self.edges,
+                "    n{} -> n{} [label=\"{}\"];",
+                id,
+                self.count,
+                escape(&field)
+            );
+            self.add(child);
+        }
+    }
+}
+
+/// Escapes a string to be put between the quotes of a DOT string.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,47 @@
+//! Tests for the DOT rendering.
+
+use super::*;
+use crate::syntax::{lexer::Lexer, parser::Parser};
+
+/// Parses the given JavaScript source code and renders it as a DOT graph.
+fn dot(src: &str) -> String {
+    let script = Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .expect("failed to parse");
+    to_dot(&script)
+}
+
+#[test]
+fn expression_statement() {
+    assert_eq!(
+        dot("a + 1;"),
+        r#"digraph ast {
+    node [shape=box, fontname="monospace"];
+    n0 [label="Program\nsourceType: \"script\""];
+    n1 [label="ExpressionStatement"];
+    n2 [label="BinaryExpression\noperator: \"+\""];
+    n3 [label="Identifier\nname: \"a\""];
+    n4 [label="Literal\nvalue: 1\nraw: \"1\""];
+    n0 -> n1 [label="body[0]"];
+    n1 -> n2 [label="expression"];
+    n2 -> n3 [label="left"];
+    n2 -> n4 [label="right"];
+}
+"#
+    );
+}
+
+#[test]
+fn escapes_the_labels() {
+    let graph = dot(r#""say \"hi\"";"#);
+    assert!(graph.contains(r#"value: \"say \\\"hi\\\"\""#));
+}
+
+#[test]
+fn one_node_per_estree_node() {
+    let graph = dot("if (a) { f(1, 2); } else { g(); }");
+    assert_eq!(graph.matches(" [label=").count(), 2 * 13 - 1);
+    assert!(graph.contains("[label=\"consequent\"]"));
+    assert!(graph.contains("[label=\"arguments[1]\"]"));
+    assert!(!graph.contains("null"));
+}
@@ -0,0 +1,512 @@
+//! Serialization of the AST to the ESTree format.
+//!
+//! [ESTree][estree] is the AST format used by most of the JavaScript tooling, like Babel, ESLint
+//! or the AST explorers, so the AST dumped in this format can be inspected with them.
+//!
+//! The nodes don't have location information, since it's not kept in the AST.
+//!
+//! [estree]: https://github.com/estree/estree
+
+use crate::syntax::{
+    ast::{
+        node::{
+            Binding, Block, Case, FormalParameter, MethodDefinitionKind, Node, PropertyDefinition,
+            StatementList,
+        },
+        op, Const,
+    },
+    lexer::is_identifier_name,
+};
+use serde_json::{json, Value};
+
+#[cfg(test)]
+mod tests;
+
+/// Converts a script to an ESTree `Program` node.
+pub fn to_estree(script: &StatementList) -> Value {
+    json!({
+        "type": "Program",
+        "sourceType": "script",
+        "body": convert_statements(script.statements()),
+    })
+}
+
+/// Converts a list of statements.
+fn convert_statements(nodes: &[Node]) -> Vec<Value> {
+    nodes.iter().map(convert_statement).collect()
+}
+
+/// Converts a list of expressions.
+fn convert_expressions(nodes: &[Node]) -> Vec<Value> {
+    nodes.iter().map(convert).collect()
+}
+
+/// Converts a node in a statement position, wrapping the expressions in an
+/// `ExpressionStatement`.
+fn convert_statement(node: &Node) -> Value {
+    match node {
+        Node::Block(_)
+        | Node::Break(_)
+        | Node::Continue(_)
+        | Node::DoWhileLoop(_)
+        | Node::ForLoop(_)
+        | Node::FunctionDecl(_)
+        | Node::If(_)
+        | Node::Return(_)
+        | Node::Switch(_)
+        | Node::Throw(_)
+        | Node::Try(_)
+        | Node::WhileLoop(_)
+        | Node::With(_)
+        | Node::VarDeclList(_)
+        | Node::LetDeclList(_)
+        | Node::ConstDeclList(_) => convert(node),
+        _ => json!({
+            "type": "ExpressionStatement",
+            "expression": convert(node),
+        }),
+    }
+}
+
+/// Converts a node in an expression position, or a statement.
+fn convert(node: &Node) -> Value {
+    match node {
+        Node::ArrayDecl(array) => json!({
+            "type": "ArrayExpression",
+            "elements": convert_expressions(array.as_ref()),
+        }),
+        Node::ArrowFunctionDecl(function) => json!({
+            "type": "ArrowFunctionExpression",
+            "id": null,
+            "params": parameters(function.params()),
+            "body": block_statement(function.body()),
+            "expression": false,
+            "generator": false,
+            "async": false,
+        }),
+        Node::Assign(assign) => json!({
+            "type": "AssignmentExpression",
+            "operator": "=",
+            "left": convert(assign.lhs()),
+            "right": convert(assign.rhs()),
+        }),
+        Node::BinOp(bin_op) if bin_op.op() == op::BinOp::Comma => json!({
+            "type": "SequenceExpression",
+            "expressions": sequence_expressions(node),
+        }),
+        Node::BinOp(bin_op) => {
+            let kind = match bin_op.op() {
+                op::BinOp::Assign(_) => "AssignmentExpression",
+                op::BinOp::Log(_) => "LogicalExpression",
+                _ => "BinaryExpression",
+            };
+            json!({
+                "type": kind,
+                "operator": bin_op.op().to_string(),
+                "left": convert(bin_op.lhs()),
+                "right": convert(bin_op.rhs()),
+            })
+        }
+        Node::Block(block) => convert_block(block),
+        Node::Break(break_smt) => json!({
+            "type": "BreakStatement",
+            "label": break_smt.label().map(|label| identifier(label.as_str())),
+        }),
+        Node::Call(call) => json!({
+            "type": "CallExpression",
+            "callee": convert(call.expr()),
+            "arguments": convert_expressions(call.args()),
+            "optional": false,
+        }),
+        Node::ConditionalOp(cond_op) => json!({
+            "type": "ConditionalExpression",
+            "test": convert(cond_op.cond()),
+            "consequent": convert(cond_op.if_true()),
+            "alternate": convert(cond_op.if_false()),
+        }),
+        Node::Const(c) => constant(c),
+        Node::ConstDeclList(list) => variable_declaration(
+            "const",
+            list.as_ref()
+                .iter()
+                .map(|decl| (decl.binding(), Some(decl.init()))),
+        ),
+        Node::Continue(cont) => json!({
+            "type": "ContinueStatement",
+            "label": cont.label().map(|label| identifier(label.as_str())),
+        }),
+        Node::DoWhileLoop(do_while) => json!({
+            "type": "DoWhileStatement",
+            "body": convert_statement(do_while.body()),
+            "test": convert(do_while.cond()),
+        }),
+        Node::FunctionDecl(function) => json!({
+            "type": "FunctionDeclaration",
+            "id": identifier(function.name().as_str()),
+            "params": parameters(function.parameters()),
+            "body": block_statement(function.body()),
+            "expression": false,
+            "generator": false,
+            "async": false,
+        }),
+        Node::FunctionExpr(function) => json!({
+            "type": "FunctionExpression",
+            "id": function.name().map(|name| identifier(name.as_str())),
+            "params": parameters(function.parameters()),
+            "body": block_statement(function.body()),
+            "expression": false,
+            "generator": false,
+            "async": false,
+        }),
+        Node::GetConstField(get_const_field) => json!({
+            "type": "MemberExpression",
+            "object": convert(get_const_field.obj()),
+            "property": identifier(get_const_field.field()),
+            "computed": false,
+            "optional": false,
+        }),
+        Node::GetField(get_field) => json!({
+            "type": "MemberExpression",
+            "object": convert(get_field.obj()),
+            "property": convert(get_field.field()),
+            "computed": true,
+            "optional": false,
+        }),
+        Node::ForLoop(for_loop) => json!({
+            "type": "ForStatement",
+            "init": for_loop.init().map(convert),
+            "test": for_loop.condition().map(convert),
+            "update": for_loop.final_expr().map(convert),
+            "body": convert_statement(for_loop.body()),
+        }),
+        Node::If(if_smt) => json!({
+            "type": "IfStatement",
+            "test": convert(if_smt.cond()),
+            "consequent": convert_statement(if_smt.body()),
+            "alternate": if_smt.else_node().map(convert_statement),
+        }),
+        Node::LetDeclList(list) => variable_declaration(
+            "let",
+            list.as_ref()
+                .iter()
+                .map(|decl| (decl.binding(), decl.init())),
+        ),
+        Node::Identifier(ident) => identifier(ident.as_ref()),
+        Node::New(new) => json!({
+            "type": "NewExpression",
+            "callee": convert(new.expr()),
+            "arguments": convert_expressions(new.args()),
+        }),
+        Node::Object(object) => json!({
+            "type": "ObjectExpression",
+            "properties": object.properties().iter().map(property).collect::<Vec<_>>(),
+        }),
+        Node::Return(ret) => json!({
+            "type": "ReturnStatement",
+            "argument": ret.expr().map(convert),
+        }),
+        Node::Switch(switch) => {
+            let mut cases: Vec<_> = switch.cases().iter().map(case).collect();
+            if let Some(default) = switch.default() {
+                let consequent = match default {
+                    Node::Block(block) => convert_statements(block.statements()),
+                    _ => vec![convert_statement(default)],
+                };
+                cases.push(json!({
+                    "type": "SwitchCase",
+                    "test": null,
+                    "consequent": consequent,
+                }));
+            }
+            json!({
+                "type": "SwitchStatement",
+                "discriminant": convert(switch.val()),
+                "cases": cases,
+            })
+        }
+        Node::Spread(spread) => json!({
+            "type": "SpreadElement",
+            "argument": convert(spread.val()),
+        }),
+        Node::Throw(throw) => json!({
+            "type": "ThrowStatement",
+            "argument": convert(throw.expr()),
+        }),
+        Node::Try(try_node) => json!({
+            "type": "TryStatement",
+            "block": convert_block(try_node.block()),
+            "handler": try_node.catch().map(|catch| json!({
+                "type": "CatchClause",
+                "param": catch.parameter().map(|param| identifier(param.as_str())),
+                "body": convert_block(catch.block()),
+            })),
+            "finalizer": try_node.finally().map(convert_block),
+        }),
+        Node::This => json!({ "type": "ThisExpression" }),
+        Node::NewTarget => json!({
+            "type": "MetaProperty",
+            "meta": identifier("new"),
+            "property": identifier("target"),
+        }),
+        Node::UnaryOp(unary_op) => {
+            let (kind, prefix) = match unary_op.op() {
+                op::UnaryOp::IncrementPost | op::UnaryOp::DecrementPost => {
+                    ("UpdateExpression", false)
+                }
+                op::UnaryOp::IncrementPre | op::UnaryOp::DecrementPre => ("UpdateExpression", true),
+                _ => ("UnaryExpression", true),
+            };
+            json!({
+                "type": kind,
+                "operator": unary_op.op().to_string(),
+                "prefix": prefix,
+                "argument": convert(unary_op.target()),
+            })
+        }
+        Node::VarDeclList(list) => variable_declaration(
+            "var",
+            list.as_ref()
+                .iter()
+                .map(|decl| (decl.binding(), decl.init())),
+        ),
+        Node::WhileLoop(while_loop) => json!({
+            "type": "WhileStatement",
+            "test": convert(while_loop.cond()),
+            "body": convert_statement(while_loop.expr()),
+        }),
+        Node::With(with) => json!({
+            "type": "WithStatement",
+            "object": convert(with.object()),
+            "body": convert_statement(with.body()),
+        }),
+        Node::RegExpLiteral(regexp) => json!({
+            "type": "Literal",
+            "value": null,
+            "raw": regexp.to_string(),
+            "regex": {
+                "pattern": regexp.pattern(),
+                "flags": regexp.flags().to_string(),
+            },
+        }),
+    }
+}
+
+/// Converts the operands of nested comma operators, which are left-associative, in order.
+fn sequence_expressions(node: &Node) -> Vec<Value> {
+    match node {
+        Node::BinOp(bin_op) if bin_op.op() == op::BinOp::Comma => {
+            let mut expressions = sequence_expressions(bin_op.lhs());
+            expressions.push(convert(bin_op.rhs()));
+            expressions
+        }
+        _ => vec![convert(node)],
+    }
+}
+
+/// Converts a block.
+fn convert_block(block: &Block) -> Value {
+    block_statement(block.statements())
+}
+
+/// Creates a `BlockStatement` node with the given statements.
+fn block_statement(body: &[Node]) -> Value {
+    json!({
+        "type": "BlockStatement",
+        "body": convert_statements(body),
+    })
+}
+
+/// Creates an `Identifier` node.
+fn identifier(name: &str) -> Value {
+    json!({
+        "type": "Identifier",
+        "name": name,
+    })
+}
+
+/// Converts a constant.
+///
+/// The values that can't be written as literals, like `undefined` or `NaN`, are converted to the
+/// global identifiers that hold them.
+fn constant(c: &Const) -> Value {
+    let value = match *c {
+        Const::String(ref s) => json!(s.to_string()),
+        Const::Num(num) if num.is_nan() => return identifier("NaN"),
+        Const::Num(num) if num.is_infinite() => {
+            return if num.is_sign_negative() {
+                json!({
+                    "type": "UnaryExpression",
+                    "operator": "-",
+                    "prefix": true,
+                    "argument": identifier("Infinity"),
+                })
+            } else {
+                identifier("Infinity")
+            };
+        }
+        Const::Num(num) => json!(num),
+        Const::Int(num) => json!(num),
+        Const::BigInt(ref num) => {
+            return json!({
+                "type": "Literal",
+                "value": null,
+                "raw": c.to_string(),
+                "bigint": num.to_string(),
+            })
+        }
+        Const::Bool(b) => json!(b),
+        Const::Null => Value::Null,
+        Const::Undefined => return identifier("undefined"),
+    };
+
+    json!({
+        "type": "Literal",
+        "value": value,
+        "raw": c.to_string(),
+    })
+}
+
+/// Converts the parameters of a function.
+fn parameters(parameters: &[FormalParameter]) -> Vec<Value> {
+    parameters
+        .iter()
+        .map(|param| {
+            let name = identifier(param.name().as_str());
+            if param.is_rest_param() {
+                json!({
+                    "type": "RestElement",
+                    "argument": name,
+                })
+            } else if let Some(init) = param.init() {
+                json!({
+                    "type": "AssignmentPattern",
+                    "left": name,
+                    "right": convert(init),
+                })
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+/// Creates a `VariableDeclaration` node with the given kind and declarations.
+fn variable_declaration<'a, D>(kind: &str, declarations: D) -> Value
+where
+    D: Iterator<Item = (&'a Binding, Option<&'a Node>)>,
+{
+    let declarations: Vec<_> = declarations
+        .map(|(target, init)| {
+            json!({
+                "type": "VariableDeclarator",
+                "id": binding(target),
+                "init": init.map(convert),
+            })
+        })
+        .collect();
+
+    json!({
+        "type": "VariableDeclaration",
+        "kind": kind,
+        "declarations": declarations,
+    })
+}
+
+/// Converts the identifier or the binding pattern of a declaration.
+fn binding(binding: &Binding) -> Value {
+    let pattern = match binding {
+        Binding::Identifier(ident) => return identifier(ident.as_ref()),
+        Binding::Array(pattern) => pattern,
+    };
+
+    let mut elements: Vec<_> = pattern
+        .elements()
+        .iter()
+        .map(|element| {
+            element.as_ref().map(|element| match element.init() {
+                Some(init) => json!({
+                    "type": "AssignmentPattern",
+                    "left": self::binding(element.target()),
+                    "right": convert(init),
+                }),
+                None => self::binding(element.target()),
+            })
+        })
+        .map(|element| element.unwrap_or(Value::Null))
+        .collect();
+    if let Some(rest) = pattern.rest() {
+        elements.push(json!({
+            "type": "RestElement",
+            "argument": self::binding(rest),
+        }));
+    }
+
+    json!({
+        "type": "ArrayPattern",
+        "elements": elements,
+    })
+}
+
+/// Converts a case of a `switch` statement.
+fn case(case: &Case) -> Value {
+    json!({
+        "type": "SwitchCase",
+        "test": convert(case.condition()),
+        "consequent": convert_statements(case.body().statements()),
+    })
+}
+
+/// Converts a property definition of an object literal.
+fn property(property: &PropertyDefinition) -> Value {
+    let (key, value, kind, method, shorthand) = match property {
+        PropertyDefinition::IdentifierReference(name) => {
+            (identifier(name), identifier(name), "init", false, true)
+        }
+        PropertyDefinition::Property(name, value) => {
+            (property_key(name), convert(value), "init", false, false)
+        }
+        PropertyDefinition::MethodDefinition(kind, name, function) => {
+            let value = json!({
+                "type": "FunctionExpression",
+                "id": null,
+                "params": parameters(function.parameters()),
+                "body": block_statement(function.body()),
+                "expression": false,
+                "generator": false,
+                "async": false,
+            });
+            let (kind, method) = match kind {
+                MethodDefinitionKind::Get => ("get", false),
+                MethodDefinitionKind::Set => ("set", false),
+                MethodDefinitionKind::Ordinary => ("init", true),
+            };
+            (property_key(name), value, kind, method, false)
+        }
+        PropertyDefinition::SpreadObject(obj) => {
+            return json!({
+                "type": "SpreadElement",
+                "argument": convert(obj),
+            })
+        }
+    };
+
+    json!({
+        "type": "Property",
+        "key": key,
+        "value": value,
+        "kind": kind,
+        "method": method,
+        "shorthand": shorthand,
+        "computed": false,
+    })
+}
+
+/// Converts the name of a property to an `Identifier` node, or to a string `Literal` node if it's
+/// not an identifier name.
+fn property_key(name: &str) -> Value {
+    if is_identifier_name(name) {
+        identifier(name)
+    } else {
+        constant(&Const::from(name))
+    }
+}
@@ -0,0 +1,183 @@
+//! Tests for the ESTree serialization.
+
+use super::*;
+use crate::syntax::{lexer::Lexer, parser::Parser};
+
+/// Parses the given JavaScript source code and converts it to ESTree.
+fn estree(src: &str) -> Value {
+    let script = Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .expect("failed to parse");
+    to_estree(&script)
+}
+
+/// Converts the given JavaScript source code with a single statement, and returns the ESTree
+/// node of the statement.
+fn single_statement(src: &str) -> Value {
+    let mut program = estree(src);
+    assert_eq!(program["type"], "Program");
+    let body = program["body"]
+        .as_array_mut()
+        .expect("body is not an array");
+    assert_eq!(body.len(), 1);
+    body.remove(0)
+}
+
+#[test]
+fn expression_statement() {
+    assert_eq!(
+        single_statement("a + 1;"),
+        json!({
+            "type": "ExpressionStatement",
+            "expression": {
+                "type": "BinaryExpression",
+                "operator": "+",
+                "left": { "type": "Identifier", "name": "a" },
+                "right": { "type": "Literal", "value": 1, "raw": "1" },
+            },
+        })
+    );
+}
+
+#[test]
+fn variable_declarations() {
+    assert_eq!(
+        single_statement("let a = 'x', b;"),
+        json!({
+            "type": "VariableDeclaration",
+            "kind": "let",
+            "declarations": [
+                {
+                    "type": "VariableDeclarator",
+                    "id": { "type": "Identifier", "name": "a" },
+                    "init": { "type": "Literal", "value": "x", "raw": "\"x\"" },
+                },
+                {
+                    "type": "VariableDeclarator",
+                    "id": { "type": "Identifier", "name": "b" },
+                    "init": null,
+                },
+            ],
+        })
+    );
+}
+
+#[test]
+fn functions() {
+    assert_eq!(
+        single_statement("function f(a, b = 1, ...c) { return a; }"),
+        json!({
+            "type": "FunctionDeclaration",
+            "id": { "type": "Identifier", "name": "f" },
+            "params": [
+                { "type": "Identifier", "name": "a" },
+                {
+                    "type": "AssignmentPattern",
+                    "left": { "type": "Identifier", "name": "b" },
+                    "right": { "type": "Literal", "value": 1, "raw": "1" },
+                },
+                {
+                    "type": "RestElement",
+                    "argument": { "type": "Identifier", "name": "c" },
+                },
+            ],
+            "body": {
+                "type": "BlockStatement",
+                "body": [
+                    {
+                        "type": "ReturnStatement",
+                        "argument": { "type": "Identifier", "name": "a" },
+                    },
+                ],
+            },
+            "expression": false,
+            "generator": false,
+            "async": false,
+        })
+    );
+}
+
+#[test]
+fn operators() {
+    let statement = single_statement("a.b = !c || d[0]++;");
+    let expression = &statement["expression"];
+    assert_eq!(expression["type"], "AssignmentExpression");
+    assert_eq!(expression["left"]["type"], "MemberExpression");
+    assert_eq!(expression["left"]["computed"], false);
+    assert_eq!(expression["right"]["type"], "LogicalExpression");
+    assert_eq!(expression["right"]["left"]["type"], "UnaryExpression");
+    assert_eq!(expression["right"]["right"]["type"], "UpdateExpression");
+    assert_eq!(expression["right"]["right"]["prefix"], false);
+    assert_eq!(expression["right"]["right"]["argument"]["computed"], true);
+
+    let statement = single_statement("x += 2;");
+    assert_eq!(statement["expression"]["type"], "AssignmentExpression");
+    assert_eq!(statement["expression"]["operator"], "+=");
+
+    let statement = single_statement("a, b, c;");
+    assert_eq!(statement["expression"]["type"], "SequenceExpression");
+    assert_eq!(
+        statement["expression"]["expressions"],
+        json!([
+            { "type": "Identifier", "name": "a" },
+            { "type": "Identifier", "name": "b" },
+            { "type": "Identifier", "name": "c" },
+        ])
+    );
+}
+
+#[test]
+fn object_properties() {
+    let statement = single_statement("({ a: 1, 'b c': 2, get d() { return 3; }, e() {} });");
+    let properties = statement["expression"]["properties"]
+        .as_array()
+        .expect("properties is not an array");
+
+    assert_eq!(
+        properties[0]["key"],
+        json!({ "type": "Identifier", "name": "a" })
+    );
+    assert_eq!(properties[0]["kind"], "init");
+    assert_eq!(
+        properties[1]["key"],
+        json!({ "type": "Literal", "value": "b c", "raw": "\"b c\"" })
+    );
+    assert_eq!(properties[2]["kind"], "get");
+    assert_eq!(properties[2]["value"]["type"], "FunctionExpression");
+    assert_eq!(properties[3]["kind"], "init");
+    assert_eq!(properties[3]["method"], true);
+}
+
+#[test]
+fn special_values() {
+    assert_eq!(
+        single_statement("undefined;")["expression"],
+        json!({ "type": "Identifier", "name": "undefined" })
+    );
+    assert_eq!(
+        single_statement("10n;")["expression"],
+        json!({ "type": "Literal", "value": null, "raw": "10n", "bigint": "10" })
+    );
+    assert_eq!(
+        single_statement("/a+/g;")["expression"]["regex"],
+        json!({ "pattern": "a+", "flags": "g" })
+    );
+}
+
+#[test]
+fn control_flow() {
+    let statement = single_statement("try { if (a) throw b; else c(); } catch (e) {} finally {}");
+    assert_eq!(statement["type"], "TryStatement");
+    assert_eq!(statement["handler"]["param"]["name"], "e");
+    assert_eq!(statement["finalizer"]["type"], "BlockStatement");
+
+    let if_statement = &statement["block"]["body"][0];
+    assert_eq!(if_statement["type"], "IfStatement");
+    assert_eq!(if_statement["consequent"]["type"], "ThrowStatement");
+    assert_eq!(if_statement["alternate"]["type"], "ExpressionStatement");
+
+    let statement = single_statement("for (let i = 0; i < 3; i++) continue;");
+    assert_eq!(statement["type"], "ForStatement");
+    assert_eq!(statement["init"]["type"], "VariableDeclaration");
+    assert_eq!(statement["body"]["type"], "ContinueStatement");
+}
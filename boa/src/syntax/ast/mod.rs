@@ -1,12 +1,15 @@
 //! The Javascript Abstract Syntax Tree.
 
 pub mod constant;
+pub mod dot;
+pub mod estree;
 pub mod keyword;
 pub mod node;
 pub mod op;
 pub mod position;
 pub mod punctuator;
 pub mod token;
+pub mod visitor;
 
 pub use self::{
     constant::Const,
@@ -1,6 +1,6 @@
 //! Array declaration node.
 
-use super::{join_nodes, Node};
+use super::{join_operands, Node};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -24,9 +24,9 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-ArrayLiteral
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Array
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ArrayDecl {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     arr: Box<[Node]>,
 }
 
@@ -36,6 +36,12 @@ impl AsRef<[Node]> for ArrayDecl {
     }
 }
 
+impl AsMut<[Node]> for ArrayDecl {
+    fn as_mut(&mut self) -> &mut [Node] {
+        &mut self.arr
+    }
+}
+
 impl<T> From<T> for ArrayDecl
 where
     T: Into<Box<[Node]>>,
@@ -45,14 +51,21 @@ where
     }
 }
 
-impl fmt::Display for ArrayDecl {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl ArrayDecl {
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str("[")?;
-        join_nodes(f, &self.arr)?;
+        join_operands(f, &self.arr, indentation)?;
         f.write_str("]")
     }
 }
 
+impl fmt::Display for ArrayDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(f, 0)
+    }
+}
+
 impl From<ArrayDecl> for Node {
     fn from(arr: ArrayDecl) -> Self {
         Self::ArrayDecl(arr)
@@ -26,7 +26,6 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Block {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     statements: StatementList,
 }
 
@@ -36,11 +35,24 @@ impl Block {
         self.statements.statements()
     }
 
+    /// Gets a mutable reference to the list of statements in this block.
+    pub(crate) fn statements_mut(&mut self) -> &mut [Node] {
+        self.statements.statements_mut()
+    }
+
+    /// Gets the statement list of this block, with the positions of its statements.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.statements
+    }
+
+    /// Gets a mutable reference to the statement list of this block.
+    pub(crate) fn statement_list_mut(&mut self) -> &mut StatementList {
+        &mut self.statements
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        writeln!(f, "{{")?;
-        self.statements.display(f, indentation + 1)?;
-        write!(f, "{}}}", "    ".repeat(indentation))
+        self.statements.display_block(f, indentation)
     }
 }
 
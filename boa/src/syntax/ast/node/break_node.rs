@@ -1,4 +1,5 @@
 use super::Node;
+use crate::interner::Sym;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -22,18 +23,18 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Break {
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl Break {
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
     /// Creates a `Break` AST node.
     pub fn new<OL, L>(label: OL) -> Self
     where
-        L: Into<Box<str>>,
+        L: Into<Sym>,
         OL: Into<Option<L>>,
     {
         Self {
@@ -48,7 +49,7 @@ impl fmt::Display for Break {
             f,
             "break{}",
             if self.label().is_some() {
-                format!(" {}", self.label().as_ref().unwrap())
+                format!(" {}", self.label().unwrap())
             } else {
                 String::new()
             }
@@ -1,4 +1,4 @@
-use super::Node;
+use super::{Node, ASSIGNMENT, CONDITIONAL};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -24,22 +24,36 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct If {
-    cond: Box<Node>,
-    body: Box<Node>,
-    else_node: Option<Box<Node>>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerIf>,
 }
 
 impl If {
     pub fn cond(&self) -> &Node {
-        &self.cond
+        &self.inner.cond
     }
 
     pub fn body(&self) -> &Node {
-        &self.body
+        &self.inner.body
     }
 
     pub fn else_node(&self) -> Option<&Node> {
-        self.else_node.as_ref().map(Box::as_ref)
+        self.inner.else_node.as_ref()
+    }
+
+    /// Gets a mutable reference to the condition of the `if` statement.
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.inner.cond
+    }
+
+    /// Gets a mutable reference to the body of the `if` statement.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
+    /// Gets a mutable reference to the `else` node, if any.
+    pub(crate) fn else_node_mut(&mut self) -> Option<&mut Node> {
+        self.inner.else_node.as_mut()
     }
 
     /// Creates an `If` AST node.
@@ -51,21 +65,44 @@ impl If {
         OE: Into<Option<E>>,
     {
         Self {
-            cond: Box::new(condition.into()),
-            body: Box::new(body.into()),
-            else_node: else_node.into().map(E::into).map(Box::new),
+            inner: Box::new(InnerIf {
+                cond: condition.into(),
+                body: body.into(),
+                else_node: else_node.into().map(E::into),
+            }),
         }
     }
 
-    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
-        write!(f, "if ({}) ", self.cond())?;
-        match self.else_node() {
-            Some(else_e) => {
-                self.body().display(f, indent)?;
-                f.write_str(" else ")?;
-                else_e.display(f, indent)
-            }
-            None => self.body().display(f, indent),
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("if (")?;
+        self.cond().display(f, indentation)?;
+        f.write_str(")")?;
+
+        let else_node = match self.else_node() {
+            Some(else_node) => else_node,
+            None => return self.body().display_body(f, indentation),
+        };
+
+        let indent = "    ".repeat(indentation);
+        if self.body().ends_with_if_without_else() {
+            // The braces keep the `else` branch from being taken by the nested `if` statement.
+            write!(f, " {{\n{}    ", indent)?;
+            self.body().display_statement(f, indentation + 1)?;
+            write!(f, "\n{}}} else", indent)?;
+        } else if let Node::Block(_) = self.body() {
+            self.body().display_body(f, indentation)?;
+            f.write_str(" else")?;
+        } else {
+            self.body().display_body(f, indentation)?;
+            write!(f, "\n{}else", indent)?;
+        }
+
+        if let Node::If(ref else_if) = else_node {
+            f.write_str(" ")?;
+            else_if.display(f, indentation)
+        } else {
+            else_node.display_body(f, indentation)
         }
     }
 }
@@ -82,6 +119,15 @@ impl From<If> for Node {
     }
 }
 
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerIf {
+    cond: Node,
+    body: Node,
+    else_node: Option<Node>,
+}
+
 /// The `conditional` (ternary) operator is the only JavaScript operator that takes three
 /// operands.
 ///
@@ -99,22 +145,36 @@ impl From<If> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ConditionalOp {
-    condition: Box<Node>,
-    if_true: Box<Node>,
-    if_false: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerConditionalOp>,
 }
 
 impl ConditionalOp {
     pub fn cond(&self) -> &Node {
-        &self.condition
+        &self.inner.condition
     }
 
     pub fn if_true(&self) -> &Node {
-        &self.if_true
+        &self.inner.if_true
     }
 
     pub fn if_false(&self) -> &Node {
-        &self.if_false
+        &self.inner.if_false
+    }
+
+    /// Gets a mutable reference to the condition of the operator.
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.inner.condition
+    }
+
+    /// Gets a mutable reference to the expression used if the condition is true.
+    pub(crate) fn if_true_mut(&mut self) -> &mut Node {
+        &mut self.inner.if_true
+    }
+
+    /// Gets a mutable reference to the expression used if the condition is false.
+    pub(crate) fn if_false_mut(&mut self) -> &mut Node {
+        &mut self.inner.if_false
     }
 
     /// Creates a `ConditionalOp` AST node.
@@ -125,22 +185,28 @@ impl ConditionalOp {
         F: Into<Node>,
     {
         Self {
-            condition: Box::new(condition.into()),
-            if_true: Box::new(if_true.into()),
-            if_false: Box::new(if_false.into()),
+            inner: Box::new(InnerConditionalOp {
+                condition: condition.into(),
+                if_true: if_true.into(),
+                if_false: if_false.into(),
+            }),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.cond()
+            .display_operand(f, CONDITIONAL + 1, indentation)?;
+        f.write_str(" ? ")?;
+        self.if_true().display_operand(f, ASSIGNMENT, indentation)?;
+        f.write_str(" : ")?;
+        self.if_false().display_operand(f, ASSIGNMENT, indentation)
+    }
 }
 
 impl fmt::Display for ConditionalOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{} ? {} : {}",
-            self.cond(),
-            self.if_true(),
-            self.if_false()
-        )
+        self.display(f, 0)
     }
 }
 
@@ -149,3 +215,12 @@ impl From<ConditionalOp> for Node {
         Self::ConditionalOp(cond_op)
     }
 }
+
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerConditionalOp {
+    condition: Node,
+    if_true: Node,
+    if_false: Node,
+}
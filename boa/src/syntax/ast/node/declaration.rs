@@ -1,6 +1,7 @@
 //! Declaration nodes.
 
-use super::{join_nodes, FormalParameter, Identifier, Node, StatementList};
+use super::{display_parameters, Binding, FormalParameter, Node, StatementList, ASSIGNMENT};
+use crate::interner::Sym;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -26,9 +27,9 @@ use serde::{Deserialize, Serialize};
 /// [spec]: https://tc39.es/ecma262/#prod-VariableStatement
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/var
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct VarDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     vars: Box<[VarDecl]>,
 }
 
@@ -55,14 +56,31 @@ impl AsRef<[VarDecl]> for VarDeclList {
     }
 }
 
+impl AsMut<[VarDecl]> for VarDeclList {
+    fn as_mut(&mut self) -> &mut [VarDecl] {
+        &mut self.vars
+    }
+}
+
+impl VarDeclList {
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("var ")?;
+        let mut first = true;
+        for decl in self.vars.iter() {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            decl.display(f, indentation)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for VarDeclList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !self.vars.is_empty() {
-            write!(f, "var ")?;
-            join_nodes(f, &self.vars)
-        } else {
-            Ok(())
-        }
+        self.display(f, 0)
     }
 }
 
@@ -76,42 +94,63 @@ impl From<VarDeclList> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct VarDecl {
-    name: Identifier,
+    binding: Binding,
     init: Option<Node>,
 }
 
 impl fmt::Display for VarDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.name, f)?;
-        if let Some(ref init) = self.init {
-            write!(f, " = {}", init)?;
-        }
-        Ok(())
+        self.display(f, 0)
     }
 }
 
 impl VarDecl {
     /// Creates a new variable declaration.
-    pub(in crate::syntax) fn new<N, I>(name: N, init: I) -> Self
+    pub(in crate::syntax) fn new<B, I>(binding: B, init: I) -> Self
     where
-        N: Into<Identifier>,
+        B: Into<Binding>,
         I: Into<Option<Node>>,
     {
         Self {
-            name: name.into(),
+            binding: binding.into(),
             init: init.into(),
         }
     }
 
-    /// Gets the name of the variable.
-    pub fn name(&self) -> &str {
-        self.name.as_ref()
+    /// Gets the identifier or the binding pattern the variable is declared with.
+    pub fn binding(&self) -> &Binding {
+        &self.binding
+    }
+
+    /// Gets a mutable reference to the identifier or the binding pattern of the declaration.
+    pub(crate) fn binding_mut(&mut self) -> &mut Binding {
+        &mut self.binding
+    }
+
+    /// Gets the names of the declared variables.
+    pub fn names(&self) -> Vec<Sym> {
+        self.binding.names()
     }
 
     /// Gets the initialization node for the variable, if any.
     pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
+
+    /// Gets a mutable reference to the initialization node for the variable, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.binding.display(f, indentation)?;
+        if let Some(ref init) = self.init {
+            f.write_str(" = ")?;
+            init.display_operand(f, ASSIGNMENT, indentation)?;
+        }
+        Ok(())
+    }
 }
 
 /// The `function` expression defines a function with the specified parameters.
@@ -133,16 +172,18 @@ impl VarDecl {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct FunctionExpr {
-    name: Option<Box<str>>,
+    name: Option<Sym>,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    simple: bool,
 }
 
 impl FunctionExpr {
     /// Creates a new function expression
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Option<Box<str>>>,
+        N: Into<Option<Sym>>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -150,12 +191,13 @@ impl FunctionExpr {
             name: name.into(),
             parameters: parameters.into(),
             body: body.into(),
+            simple: false,
         }
     }
 
     /// Gets the name of the function declaration.
-    pub fn name(&self) -> Option<&str> {
-        self.name.as_ref().map(Box::as_ref)
+    pub fn name(&self) -> Option<Sym> {
+        self.name
     }
 
     /// Gets the list of parameters of the function declaration.
@@ -168,19 +210,51 @@ impl FunctionExpr {
         self.body.statements()
     }
 
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub(crate) fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
+
+    /// Gets the body of the function declaration, with the positions of its statements.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the function declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
+    /// Checks if the scope analysis found that this is a simple function, whose calls don't need
+    /// their environment once they are done.
+    pub fn is_simple(&self) -> bool {
+        self.simple
+    }
+
+    /// Sets whether this is a simple function.
+    pub(crate) fn set_simple(&mut self, simple: bool) {
+        self.simple = simple;
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str("function")?;
         if let Some(ref name) = self.name {
             write!(f, " {}", name)?;
         }
-        f.write_str("(")?;
-        join_nodes(f, &self.parameters)?;
-        f.write_str(") {{")?;
-
-        self.body.display(f, indentation + 1)?;
+        self.display_method(f, indentation)
+    }
 
-        writeln!(f, "}}")
+    /// Implements the display formatting of the parameters and the body of the function, as
+    /// they are written in a method definition.
+    pub(super) fn display_method(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        indentation: usize,
+    ) -> fmt::Result {
+        display_parameters(f, &self.parameters, indentation)?;
+        f.write_str(" ")?;
+        self.body.display_block(f, indentation)
     }
 }
 
@@ -217,16 +291,18 @@ impl From<FunctionExpr> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct FunctionDecl {
-    name: Box<str>,
+    name: Sym,
     parameters: Box<[FormalParameter]>,
     body: StatementList,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    simple: bool,
 }
 
 impl FunctionDecl {
     /// Creates a new function declaration.
     pub(in crate::syntax) fn new<N, P, B>(name: N, parameters: P, body: B) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
         P: Into<Box<[FormalParameter]>>,
         B: Into<StatementList>,
     {
@@ -234,12 +310,13 @@ impl FunctionDecl {
             name: name.into(),
             parameters: parameters.into(),
             body: body.into(),
+            simple: false,
         }
     }
 
     /// Gets the name of the function declaration.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the list of parameters of the function declaration.
@@ -252,15 +329,38 @@ impl FunctionDecl {
         self.body.statements()
     }
 
-    /// Implements the display formatting with indentation.
-    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        write!(f, "function {}(", self.name)?;
-        join_nodes(f, &self.parameters)?;
-        f.write_str(") {{")?;
+    /// Gets a mutable reference to the list of parameters of the function declaration.
+    pub(crate) fn parameters_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.parameters
+    }
 
-        self.body.display(f, indentation + 1)?;
+    /// Gets the body of the function declaration, with the positions of its statements.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the function declaration.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
+    /// Checks if the scope analysis found that this is a simple function, whose calls don't need
+    /// their environment once they are done.
+    pub fn is_simple(&self) -> bool {
+        self.simple
+    }
+
+    /// Sets whether this is a simple function.
+    pub(crate) fn set_simple(&mut self, simple: bool) {
+        self.simple = simple;
+    }
 
-        writeln!(f, "}}")
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        write!(f, "function {}", self.name)?;
+        display_parameters(f, &self.parameters, indentation)?;
+        f.write_str(" ")?;
+        self.body.display_block(f, indentation)
     }
 }
 
@@ -294,6 +394,8 @@ impl fmt::Display for FunctionDecl {
 pub struct ArrowFunctionDecl {
     params: Box<[FormalParameter]>,
     body: StatementList,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    simple: bool,
 }
 
 impl ArrowFunctionDecl {
@@ -306,6 +408,7 @@ impl ArrowFunctionDecl {
         Self {
             params: params.into(),
             body: body.into(),
+            simple: false,
         }
     }
 
@@ -319,12 +422,37 @@ impl ArrowFunctionDecl {
         &self.body.statements()
     }
 
+    /// Gets a mutable reference to the list of parameters of the arrow function.
+    pub(crate) fn params_mut(&mut self) -> &mut [FormalParameter] {
+        &mut self.params
+    }
+
+    /// Gets the body of the arrow function, with the positions of its statements.
+    pub(crate) fn statement_list(&self) -> &StatementList {
+        &self.body
+    }
+
+    /// Gets a mutable reference to the body of the arrow function.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
+
+    /// Checks if the scope analysis found that this is a simple function, whose calls don't need
+    /// their environment once they are done.
+    pub fn is_simple(&self) -> bool {
+        self.simple
+    }
+
+    /// Sets whether this is a simple function.
+    pub(crate) fn set_simple(&mut self, simple: bool) {
+        self.simple = simple;
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        write!(f, "(")?;
-        join_nodes(f, &self.params)?;
-        f.write_str(") => ")?;
-        self.body.display(f, indentation)
+        display_parameters(f, &self.params, indentation)?;
+        f.write_str(" => ")?;
+        self.body.display_block(f, indentation)
     }
 }
 
@@ -359,9 +487,9 @@ impl From<ArrowFunctionDecl> for Node {
 /// [identifier]: https://developer.mozilla.org/en-US/docs/Glossary/identifier
 /// [expression]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Expressions_and_Operators#Expressions
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ConstDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     list: Box<[ConstDecl]>,
 }
 
@@ -388,14 +516,31 @@ impl AsRef<[ConstDecl]> for ConstDeclList {
     }
 }
 
+impl AsMut<[ConstDecl]> for ConstDeclList {
+    fn as_mut(&mut self) -> &mut [ConstDecl] {
+        &mut self.list
+    }
+}
+
+impl ConstDeclList {
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("const ")?;
+        let mut first = true;
+        for decl in self.list.iter() {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            decl.display(f, indentation)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for ConstDeclList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !self.list.is_empty() {
-            write!(f, "const ")?;
-            join_nodes(f, &self.list)
-        } else {
-            Ok(())
-        }
+        self.display(f, 0)
     }
 }
 
@@ -409,38 +554,60 @@ impl From<ConstDeclList> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct ConstDecl {
-    name: Identifier,
+    binding: Binding,
     init: Node,
 }
 
 impl fmt::Display for ConstDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} = {}", self.name, self.init)
+        self.display(f, 0)
     }
 }
 
 impl ConstDecl {
     /// Creates a new variable declaration.
-    pub(in crate::syntax) fn new<N, I>(name: N, init: I) -> Self
+    pub(in crate::syntax) fn new<B, I>(binding: B, init: I) -> Self
     where
-        N: Into<Identifier>,
+        B: Into<Binding>,
         I: Into<Node>,
     {
         Self {
-            name: name.into(),
+            binding: binding.into(),
             init: init.into(),
         }
     }
 
-    /// Gets the name of the variable.
-    pub fn name(&self) -> &str {
-        self.name.as_ref()
+    /// Gets the identifier or the binding pattern the constant is declared with.
+    pub fn binding(&self) -> &Binding {
+        &self.binding
+    }
+
+    /// Gets a mutable reference to the identifier or the binding pattern of the declaration.
+    pub(crate) fn binding_mut(&mut self) -> &mut Binding {
+        &mut self.binding
+    }
+
+    /// Gets the names of the declared constants.
+    pub fn names(&self) -> Vec<Sym> {
+        self.binding.names()
     }
 
     /// Gets the initialization node for the variable, if any.
     pub fn init(&self) -> &Node {
         &self.init
     }
+
+    /// Gets a mutable reference to the initialization node for the variable.
+    pub(crate) fn init_mut(&mut self) -> &mut Node {
+        &mut self.init
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.binding.display(f, indentation)?;
+        f.write_str(" = ")?;
+        self.init.display_operand(f, ASSIGNMENT, indentation)
+    }
 }
 
 /// The `let` statement declares a block scope local variable, optionally initializing it to a
@@ -461,9 +628,9 @@ impl ConstDecl {
 /// [spec]: https://tc39.es/ecma262/#sec-let-and-const-declarations
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/let
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct LetDeclList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     list: Box<[LetDecl]>,
 }
 
@@ -490,14 +657,31 @@ impl AsRef<[LetDecl]> for LetDeclList {
     }
 }
 
+impl AsMut<[LetDecl]> for LetDeclList {
+    fn as_mut(&mut self) -> &mut [LetDecl] {
+        &mut self.list
+    }
+}
+
+impl LetDeclList {
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("let ")?;
+        let mut first = true;
+        for decl in self.list.iter() {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            decl.display(f, indentation)?;
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Display for LetDeclList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if !self.list.is_empty() {
-            write!(f, "let ")?;
-            join_nodes(f, &self.list)
-        } else {
-            Ok(())
-        }
+        self.display(f, 0)
     }
 }
 
@@ -511,40 +695,61 @@ impl From<LetDeclList> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct LetDecl {
-    name: Identifier,
+    binding: Binding,
     init: Option<Node>,
 }
 
 impl fmt::Display for LetDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.name, f)?;
-        if let Some(ref init) = self.init {
-            write!(f, " = {}", init)?;
-        }
-        Ok(())
+        self.display(f, 0)
     }
 }
 
 impl LetDecl {
     /// Creates a new variable declaration.
-    pub(in crate::syntax) fn new<N, I>(name: N, init: I) -> Self
+    pub(in crate::syntax) fn new<B, I>(binding: B, init: I) -> Self
     where
-        N: Into<Identifier>,
+        B: Into<Binding>,
         I: Into<Option<Node>>,
     {
         Self {
-            name: name.into(),
+            binding: binding.into(),
             init: init.into(),
         }
     }
 
-    /// Gets the name of the variable.
-    pub fn name(&self) -> &str {
-        self.name.as_ref()
+    /// Gets the identifier or the binding pattern the variable is declared with.
+    pub fn binding(&self) -> &Binding {
+        &self.binding
+    }
+
+    /// Gets a mutable reference to the identifier or the binding pattern of the declaration.
+    pub(crate) fn binding_mut(&mut self) -> &mut Binding {
+        &mut self.binding
+    }
+
+    /// Gets the names of the declared variables.
+    pub fn names(&self) -> Vec<Sym> {
+        self.binding.names()
     }
 
     /// Gets the initialization node for the variable, if any.
     pub fn init(&self) -> Option<&Node> {
         self.init.as_ref()
     }
+
+    /// Gets a mutable reference to the initialization node for the variable, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.binding.display(f, indentation)?;
+        if let Some(ref init) = self.init {
+            f.write_str(" = ")?;
+            init.display_operand(f, ASSIGNMENT, indentation)?;
+        }
+        Ok(())
+    }
 }
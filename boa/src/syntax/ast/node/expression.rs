@@ -1,6 +1,6 @@
 //! Expression nodes.
 
-use super::{join_nodes, Node};
+use super::{join_operands, Node, MEMBER};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -50,13 +50,29 @@ impl Call {
     pub fn args(&self) -> &[Node] {
         &self.args
     }
+
+    /// Gets a mutable reference to the name of the function call.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
+    /// Gets a mutable reference to the arguments passed to the function.
+    pub(crate) fn args_mut(&mut self) -> &mut [Node] {
+        &mut self.args
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.expr.display_operand(f, MEMBER, indentation)?;
+        f.write_str("(")?;
+        join_operands(f, &self.args, indentation)?;
+        f.write_str(")")
+    }
 }
 
 impl fmt::Display for Call {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}(", self.expr)?;
-        join_nodes(f, &self.args)?;
-        f.write_str(")")
+        self.display(f, 0)
     }
 }
 
@@ -97,6 +113,32 @@ impl New {
     pub fn args(&self) -> &[Node] {
         &self.call.args()
     }
+
+    /// Gets a mutable reference to the name of the function call.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        self.call.expr_mut()
+    }
+
+    /// Gets a mutable reference to the arguments passed to the function.
+    pub(crate) fn args_mut(&mut self) -> &mut [Node] {
+        self.call.args_mut()
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("new ")?;
+        // The arguments of a call in the constructor would be taken as the arguments of `new`.
+        if let Node::Call(_) = self.expr() {
+            f.write_str("(")?;
+            self.expr().display(f, indentation)?;
+            f.write_str(")")?;
+        } else {
+            self.expr().display_operand(f, MEMBER, indentation)?;
+        }
+        f.write_str("(")?;
+        join_operands(f, self.args(), indentation)?;
+        f.write_str(")")
+    }
 }
 
 impl From<Call> for New {
@@ -107,7 +149,7 @@ impl From<Call> for New {
 
 impl fmt::Display for New {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "new {}", self.call)
+        self.display(f, 0)
     }
 }
 
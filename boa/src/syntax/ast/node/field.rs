@@ -1,6 +1,7 @@
 //! Field AST node.
 //!
-use super::Node;
+use super::{Node, MEMBER};
+use crate::{builtins::object::PropertyCache, syntax::ast::Const};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -33,6 +34,8 @@ use serde::{Deserialize, Serialize};
 pub struct GetConstField {
     obj: Box<Node>,
     field: Box<str>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    cache: PropertyCache,
 }
 
 impl GetConstField {
@@ -44,6 +47,16 @@ impl GetConstField {
         &self.field
     }
 
+    /// Gets the inline cache of the accesses to the field.
+    pub(crate) fn cache(&self) -> &PropertyCache {
+        &self.cache
+    }
+
+    /// Gets a mutable reference to the object whose field is accessed.
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.obj
+    }
+
     /// Creates a `GetConstField` AST node.
     pub fn new<V, L>(value: V, label: L) -> Self
     where
@@ -53,13 +66,27 @@ impl GetConstField {
         Self {
             obj: Box::new(value.into()),
             field: label.into(),
+            cache: PropertyCache::new(),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        // A dot right after a number would be read as its decimal point.
+        if let Node::Const(Const::Int(_)) | Node::Const(Const::Num(_)) = self.obj() {
+            f.write_str("(")?;
+            self.obj().display(f, indentation)?;
+            f.write_str(")")?;
+        } else {
+            self.obj().display_operand(f, MEMBER, indentation)?;
+        }
+        write!(f, ".{}", self.field())
+    }
 }
 
 impl fmt::Display for GetConstField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}.{}", self.obj(), self.field())
+        self.display(f, 0)
     }
 }
 
@@ -94,17 +121,27 @@ impl From<GetConstField> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct GetField {
-    obj: Box<Node>,
-    field: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerGetField>,
 }
 
 impl GetField {
     pub fn obj(&self) -> &Node {
-        &self.obj
+        &self.inner.obj
     }
 
     pub fn field(&self) -> &Node {
-        &self.field
+        &self.inner.field
+    }
+
+    /// Gets a mutable reference to the object whose field is accessed.
+    pub(crate) fn obj_mut(&mut self) -> &mut Node {
+        &mut self.inner.obj
+    }
+
+    /// Gets a mutable reference to the expression of the accessed field.
+    pub(crate) fn field_mut(&mut self) -> &mut Node {
+        &mut self.inner.field
     }
 
     /// Creates a `GetField` AST node.
@@ -114,15 +151,25 @@ impl GetField {
         F: Into<Node>,
     {
         Self {
-            obj: Box::new(value.into()),
-            field: Box::new(field.into()),
+            inner: Box::new(InnerGetField {
+                obj: value.into(),
+                field: field.into(),
+            }),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.obj().display_operand(f, MEMBER, indentation)?;
+        f.write_str("[")?;
+        self.field().display(f, indentation)?;
+        f.write_str("]")
+    }
 }
 
 impl fmt::Display for GetField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}[{}]", self.obj(), self.field())
+        self.display(f, 0)
     }
 }
 
@@ -131,3 +178,11 @@ impl From<GetField> for Node {
         Self::GetField(get_field)
     }
 }
+
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerGetField {
+    obj: Node,
+    field: Node,
+}
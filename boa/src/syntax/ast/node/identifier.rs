@@ -1,7 +1,8 @@
 //! Local identifier node.
 
 use super::Node;
-use gc::{Finalize, Trace};
+use crate::interner::Sym;
+use gc::{unsafe_empty_trace, Finalize, Trace};
 use std::fmt;
 
 #[cfg(feature = "serde")]
@@ -25,9 +26,51 @@ use serde::{Deserialize, Serialize};
 /// [mdn]: https://developer.mozilla.org/en-US/docs/Glossary/Identifier
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(transparent))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct Identifier {
-    ident: Box<str>,
+    ident: Sym,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    binding: Option<BindingLocation>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    global: bool,
+}
+
+impl Identifier {
+    /// Gets the interned name of the identifier.
+    pub fn sym(&self) -> Sym {
+        self.ident
+    }
+
+    /// Gets the location of the binding this identifier refers to, if it has been resolved by
+    /// the scope analysis.
+    pub fn binding(&self) -> Option<BindingLocation> {
+        self.binding
+    }
+
+    /// Sets the location of the binding this identifier refers to.
+    pub(crate) fn set_binding(&mut self, binding: Option<BindingLocation>) {
+        self.binding = binding;
+    }
+
+    /// Checks if the scope analysis found that the binding of this identifier can only be in the
+    /// global environment, as no scope between the identifier and the global scope declares it.
+    ///
+    /// The binding may not exist, like the binding of an undeclared variable.
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+
+    /// Sets whether the binding of this identifier can only be in the global environment.
+    pub(crate) fn set_global(&mut self, global: bool) {
+        self.global = global;
+    }
+}
+
+/// Two identifiers are equal if they have the same name, whether they have been resolved or not.
+impl PartialEq for Identifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.ident == other.ident
+    }
 }
 
 impl fmt::Display for Identifier {
@@ -38,16 +81,20 @@ impl fmt::Display for Identifier {
 
 impl AsRef<str> for Identifier {
     fn as_ref(&self) -> &str {
-        &self.ident
+        self.ident.as_str()
     }
 }
 
 impl<T> From<T> for Identifier
 where
-    T: Into<Box<str>>,
+    T: Into<Sym>,
 {
     fn from(stm: T) -> Self {
-        Self { ident: stm.into() }
+        Self {
+            ident: stm.into(),
+            binding: None,
+            global: false,
+        }
     }
 }
 
@@ -56,3 +103,44 @@ impl From<Identifier> for Node {
         Self::Identifier(local)
     }
 }
+
+/// The location of the binding an identifier refers to, as resolved by the scope analysis.
+///
+/// The location is relative to the environment where the identifier is evaluated: `depth` is
+/// the number of environments to go through to reach the one holding the binding, and `slot` is
+/// the index of the binding in the declarations of that environment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BindingLocation {
+    depth: u32,
+    slot: u32,
+    tdz: bool,
+}
+
+impl BindingLocation {
+    /// Creates a new binding location.
+    pub(crate) fn new(depth: u32, slot: u32, tdz: bool) -> Self {
+        Self { depth, slot, tdz }
+    }
+
+    /// Gets the number of environments between the identifier and its binding.
+    pub fn depth(self) -> u32 {
+        self.depth
+    }
+
+    /// Gets the index of the binding in its environment.
+    pub fn slot(self) -> u32 {
+        self.slot
+    }
+
+    /// Checks if the identifier might be evaluated before the binding is initialized, that is,
+    /// in its temporal dead zone.
+    pub fn needs_tdz_check(self) -> bool {
+        self.tdz
+    }
+}
+
+impl Finalize for BindingLocation {}
+
+unsafe impl Trace for BindingLocation {
+    unsafe_empty_trace!();
+}
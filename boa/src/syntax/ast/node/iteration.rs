@@ -1,4 +1,5 @@
 use super::Node;
+use crate::interner::Sym;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -57,24 +58,44 @@ impl ForLoop {
         self.inner.body()
     }
 
+    /// Gets a mutable reference to the initialization node.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.inner.init.as_mut()
+    }
+
+    /// Gets a mutable reference to the loop condition node.
+    pub(crate) fn condition_mut(&mut self) -> Option<&mut Node> {
+        self.inner.condition.as_mut()
+    }
+
+    /// Gets a mutable reference to the final expression node.
+    pub(crate) fn final_expr_mut(&mut self) -> Option<&mut Node> {
+        self.inner.final_expr.as_mut()
+    }
+
+    /// Gets a mutable reference to the body of the for loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
+    /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str("for (")?;
         if let Some(init) = self.init() {
-            fmt::Display::fmt(init, f)?;
+            init.display(f, indentation)?;
         }
         f.write_str(";")?;
         if let Some(condition) = self.condition() {
-            fmt::Display::fmt(condition, f)?;
+            f.write_str(" ")?;
+            condition.display(f, indentation)?;
         }
         f.write_str(";")?;
         if let Some(final_expr) = self.final_expr() {
-            fmt::Display::fmt(final_expr, f)?;
+            f.write_str(" ")?;
+            final_expr.display(f, indentation)?;
         }
-        writeln!(f, ") {{")?;
-
-        self.inner.body().display(f, indentation + 1)?;
-
-        write!(f, "}}")
+        f.write_str(")")?;
+        self.body().display_body(f, indentation)
     }
 }
 
@@ -152,17 +173,27 @@ impl InnerForLoop {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct WhileLoop {
-    cond: Box<Node>,
-    expr: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerWhileLoop>,
 }
 
 impl WhileLoop {
     pub fn cond(&self) -> &Node {
-        &self.cond
+        &self.inner.cond
     }
 
     pub fn expr(&self) -> &Node {
-        &self.expr
+        &self.inner.expr
+    }
+
+    /// Gets a mutable reference to the condition of the loop.
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.inner.cond
+    }
+
+    /// Gets a mutable reference to the body of the loop.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.inner.expr
     }
 
     /// Creates a `WhileLoop` AST node.
@@ -172,14 +203,19 @@ impl WhileLoop {
         B: Into<Node>,
     {
         Self {
-            cond: Box::new(condition.into()),
-            expr: Box::new(body.into()),
+            inner: Box::new(InnerWhileLoop {
+                cond: condition.into(),
+                expr: body.into(),
+            }),
         }
     }
 
+    /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        write!(f, "while ({}) ", self.cond())?;
-        self.expr().display(f, indentation)
+        f.write_str("while (")?;
+        self.cond().display(f, indentation)?;
+        f.write_str(")")?;
+        self.expr().display_body(f, indentation)
     }
 }
 
@@ -195,6 +231,14 @@ impl From<WhileLoop> for Node {
     }
 }
 
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerWhileLoop {
+    cond: Node,
+    expr: Node,
+}
+
 /// The `do...while` statement creates a loop that executes a specified statement until the
 /// test condition evaluates to false.
 ///
@@ -210,17 +254,27 @@ impl From<WhileLoop> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct DoWhileLoop {
-    body: Box<Node>,
-    cond: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerDoWhileLoop>,
 }
 
 impl DoWhileLoop {
     pub fn body(&self) -> &Node {
-        &self.body
+        &self.inner.body
     }
 
     pub fn cond(&self) -> &Node {
-        &self.cond
+        &self.inner.cond
+    }
+
+    /// Gets a mutable reference to the body of the loop.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
+    /// Gets a mutable reference to the condition of the loop.
+    pub(crate) fn cond_mut(&mut self) -> &mut Node {
+        &mut self.inner.cond
     }
 
     /// Creates a `DoWhileLoop` AST node.
@@ -230,15 +284,24 @@ impl DoWhileLoop {
         C: Into<Node>,
     {
         Self {
-            body: Box::new(body.into()),
-            cond: Box::new(condition.into()),
+            inner: Box::new(InnerDoWhileLoop {
+                body: body.into(),
+                cond: condition.into(),
+            }),
         }
     }
 
+    /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        write!(f, "do")?;
-        self.body().display(f, indentation)?;
-        write!(f, "while ({})", self.cond())
+        f.write_str("do")?;
+        self.body().display_body(f, indentation)?;
+        if let Node::Block(_) = self.body() {
+            f.write_str(" while (")?;
+        } else {
+            write!(f, "\n{}while (", "    ".repeat(indentation))?;
+        }
+        self.cond().display(f, indentation)?;
+        f.write_str(")")
     }
 }
 
@@ -254,6 +317,14 @@ impl From<DoWhileLoop> for Node {
     }
 }
 
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerDoWhileLoop {
+    body: Node,
+    cond: Node,
+}
+
 /// The `continue` statement terminates execution of the statements in the current iteration of
 /// the current or labeled loop, and continues execution of the loop with the next iteration.
 ///
@@ -270,18 +341,18 @@ impl From<DoWhileLoop> for Node {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Continue {
-    label: Option<Box<str>>,
+    label: Option<Sym>,
 }
 
 impl Continue {
-    pub fn label(&self) -> Option<&str> {
-        self.label.as_ref().map(Box::as_ref)
+    pub fn label(&self) -> Option<Sym> {
+        self.label
     }
 
     /// Creates a `Continue` AST node.
     pub fn new<OL, L>(label: OL) -> Self
     where
-        L: Into<Box<str>>,
+        L: Into<Sym>,
         OL: Into<Option<L>>,
     {
         Self {
@@ -11,12 +11,15 @@ pub mod identifier;
 pub mod iteration;
 pub mod object;
 pub mod operator;
+pub mod pattern;
+pub mod regexp;
 pub mod return_smt;
 pub mod spread;
 pub mod statement_list;
 pub mod switch;
 pub mod throw;
 pub mod try_node;
+pub mod with;
 
 pub use self::{
     array::ArrayDecl,
@@ -29,18 +32,22 @@ pub use self::{
     },
     expression::{Call, New},
     field::{GetConstField, GetField},
-    identifier::Identifier,
+    identifier::{BindingLocation, Identifier},
     iteration::{Continue, DoWhileLoop, ForLoop, WhileLoop},
     object::Object,
     operator::{Assign, BinOp, UnaryOp},
+    pattern::{ArrayPattern, Binding, PatternElement},
+    regexp::RegExpLiteral,
     return_smt::Return,
     spread::Spread,
     statement_list::StatementList,
     switch::{Case, Switch},
     throw::Throw,
     try_node::{Catch, Finally, Try},
+    with::With,
 };
 use super::Const;
+use crate::interner::Sym;
 use gc::{Finalize, Trace};
 use std::{
     cmp::Ordering,
@@ -50,6 +57,9 @@ use std::{
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(test)]
+mod tests;
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub enum Node {
@@ -125,9 +135,23 @@ pub enum Node {
     /// A `new` expression. [More information](./expression/struct.New.html).
     New(New),
 
+    /// The `new.target` meta property, which is the constructor called by `new`, or `undefined`
+    /// in a plain function call.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-NewTarget
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/new.target
+    NewTarget,
+
     /// An object. [More information](./object/struct.Object.html).
     Object(Object),
 
+    /// A regular expression literal. [More information](./regexp/struct.RegExpLiteral.html).
+    RegExpLiteral(RegExpLiteral),
+
     /// A return statement. [More information](./object/struct.Return.html).
     Return(Return),
 
@@ -165,6 +189,9 @@ pub enum Node {
 
     /// A 'while {...}' node. [More information](./iteration/struct.WhileLoop.html).
     WhileLoop(WhileLoop),
+
+    /// A `with` statement. [More information](./with/struct.With.html).
+    With(With),
 }
 
 impl Display for Node {
@@ -197,65 +224,205 @@ impl Node {
     }
 
     /// Implements the display formatting with indentation.
+    ///
+    /// The indentation is the level of the line where the node starts, and it's used for the
+    /// lines of the nested blocks and functions.
     fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        let indent = "    ".repeat(indentation);
-        match *self {
-            Self::Block(_) => {}
-            _ => write!(f, "{}", indent)?,
-        }
-
         match *self {
             Self::Const(ref c) => write!(f, "{}", c),
-            Self::ConditionalOp(ref cond_op) => Display::fmt(cond_op, f),
+            Self::ConditionalOp(ref cond_op) => cond_op.display(f, indentation),
             Self::ForLoop(ref for_loop) => for_loop.display(f, indentation),
             Self::This => write!(f, "this"),
+            Self::NewTarget => write!(f, "new.target"),
             Self::Try(ref try_catch) => try_catch.display(f, indentation),
             Self::Break(ref break_smt) => Display::fmt(break_smt, f),
             Self::Continue(ref cont) => Display::fmt(cont, f),
-            Self::Spread(ref spread) => Display::fmt(spread, f),
+            Self::Spread(ref spread) => spread.display(f, indentation),
             Self::Block(ref block) => block.display(f, indentation),
             Self::Identifier(ref s) => Display::fmt(s, f),
-            Self::GetConstField(ref get_const_field) => Display::fmt(get_const_field, f),
-            Self::GetField(ref get_field) => Display::fmt(get_field, f),
-            Self::Call(ref expr) => Display::fmt(expr, f),
-            Self::New(ref expr) => Display::fmt(expr, f),
+            Self::GetConstField(ref get_const_field) => get_const_field.display(f, indentation),
+            Self::GetField(ref get_field) => get_field.display(f, indentation),
+            Self::Call(ref expr) => expr.display(f, indentation),
+            Self::New(ref expr) => expr.display(f, indentation),
             Self::WhileLoop(ref while_loop) => while_loop.display(f, indentation),
             Self::DoWhileLoop(ref do_while) => do_while.display(f, indentation),
+            Self::With(ref with) => with.display(f, indentation),
             Self::If(ref if_smt) => if_smt.display(f, indentation),
             Self::Switch(ref switch) => switch.display(f, indentation),
             Self::Object(ref obj) => obj.display(f, indentation),
-            Self::ArrayDecl(ref arr) => Display::fmt(arr, f),
-            Self::VarDeclList(ref list) => Display::fmt(list, f),
+            Self::ArrayDecl(ref arr) => arr.display(f, indentation),
+            Self::VarDeclList(ref list) => list.display(f, indentation),
             Self::FunctionDecl(ref decl) => decl.display(f, indentation),
             Self::FunctionExpr(ref expr) => expr.display(f, indentation),
             Self::ArrowFunctionDecl(ref decl) => decl.display(f, indentation),
-            Self::BinOp(ref op) => Display::fmt(op, f),
-            Self::UnaryOp(ref op) => Display::fmt(op, f),
-            Self::Return(ref ret) => Display::fmt(ret, f),
-            Self::Throw(ref throw) => Display::fmt(throw, f),
-            Self::Assign(ref op) => Display::fmt(op, f),
-            Self::LetDeclList(ref decl) => Display::fmt(decl, f),
-            Self::ConstDeclList(ref decl) => Display::fmt(decl, f),
+            Self::BinOp(ref op) => op.display(f, indentation),
+            Self::UnaryOp(ref op) => op.display(f, indentation),
+            Self::RegExpLiteral(ref regexp) => Display::fmt(regexp, f),
+            Self::Return(ref ret) => ret.display(f, indentation),
+            Self::Throw(ref throw) => throw.display(f, indentation),
+            Self::Assign(ref op) => op.display(f, indentation),
+            Self::LetDeclList(ref decl) => decl.display(f, indentation),
+            Self::ConstDeclList(ref decl) => decl.display(f, indentation),
+        }
+    }
+
+    /// Implements the display formatting of the node as a statement.
+    ///
+    /// Statements that don't end with a block are terminated by a semicolon, and expressions that
+    /// would be parsed as a block or a function declaration are put between parentheses.
+    fn display_statement(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        match *self {
+            Self::Block(_)
+            | Self::If(_)
+            | Self::WhileLoop(_)
+            | Self::With(_)
+            | Self::ForLoop(_)
+            | Self::Switch(_)
+            | Self::Try(_)
+            | Self::FunctionDecl(_) => self.display(f, indentation),
+            Self::DoWhileLoop(_)
+            | Self::VarDeclList(_)
+            | Self::LetDeclList(_)
+            | Self::ConstDeclList(_)
+            | Self::Return(_)
+            | Self::Throw(_)
+            | Self::Break(_)
+            | Self::Continue(_) => {
+                self.display(f, indentation)?;
+                f.write_str(";")
+            }
+            _ => {
+                let expr = Indented(self, indentation).to_string();
+                let starts_with_function = expr.starts_with("function")
+                    && !expr["function".len()..]
+                        .starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$');
+                if expr.starts_with('{') || starts_with_function {
+                    write!(f, "({});", expr)
+                } else {
+                    write!(f, "{};", expr)
+                }
+            }
+        }
+    }
+
+    /// Implements the display formatting of the body of a compound statement, like a loop.
+    ///
+    /// Bodies that are not blocks are indented on their own line.
+    fn display_body(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        if let Self::Block(ref block) = *self {
+            f.write_str(" ")?;
+            block.display(f, indentation)
+        } else {
+            writeln!(f)?;
+            f.write_str(&"    ".repeat(indentation + 1))?;
+            self.display_statement(f, indentation + 1)
+        }
+    }
+
+    /// Checks if the statement ends with an `if` statement without an `else` branch, which would
+    /// take an `else` branch written after it.
+    fn ends_with_if_without_else(&self) -> bool {
+        match *self {
+            Self::If(ref if_smt) => match if_smt.else_node() {
+                Some(else_node) => else_node.ends_with_if_without_else(),
+                None => true,
+            },
+            Self::WhileLoop(ref while_loop) => while_loop.expr().ends_with_if_without_else(),
+            Self::ForLoop(ref for_loop) => for_loop.body().ends_with_if_without_else(),
+            Self::With(ref with) => with.body().ends_with_if_without_else(),
+            _ => false,
+        }
+    }
+
+    /// Gets the precedence of the node when it's displayed as an expression.
+    ///
+    /// The higher the precedence, the tighter the node binds to its operands.
+    fn precedence(&self) -> u8 {
+        match *self {
+            Self::Assign(_) | Self::ArrowFunctionDecl(_) | Self::Spread(_) => ASSIGNMENT,
+            Self::ConditionalOp(_) => CONDITIONAL,
+            Self::BinOp(ref op) => op.precedence(),
+            Self::UnaryOp(ref op) => op.precedence(),
+            Self::Const(Const::Num(num)) if num.is_sign_negative() => UNARY,
+            Self::Const(Const::Int(num)) if num < 0 => UNARY,
+            Self::Call(_) | Self::New(_) | Self::GetConstField(_) | Self::GetField(_) => MEMBER,
+            _ => PRIMARY,
+        }
+    }
+
+    /// Implements the display formatting of the node as an operand, putting it between
+    /// parentheses if it binds less tightly than the given precedence.
+    fn display_operand(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        precedence: u8,
+        indentation: usize,
+    ) -> fmt::Result {
+        if self.precedence() < precedence {
+            f.write_str("(")?;
+            self.display(f, indentation)?;
+            f.write_str(")")
+        } else {
+            self.display(f, indentation)
         }
     }
 }
 
-/// Utility to join multiple Nodes into a single string.
-fn join_nodes<N>(f: &mut fmt::Formatter<'_>, nodes: &[N]) -> fmt::Result
-where
-    N: Display,
-{
+/// Precedence of the comma operator.
+const COMMA: u8 = 0;
+/// Precedence of assignments, arrow functions and spread elements.
+const ASSIGNMENT: u8 = 1;
+/// Precedence of the conditional operator.
+const CONDITIONAL: u8 = 2;
+/// Precedence of the prefix unary operators.
+const UNARY: u8 = 14;
+/// Precedence of the postfix update operators.
+const POSTFIX: u8 = 15;
+/// Precedence of calls, `new` expressions and property accesses.
+const MEMBER: u8 = 16;
+/// Precedence of the primary expressions, like literals and identifiers.
+const PRIMARY: u8 = 17;
+
+/// Helper to display a node with the given indentation.
+struct Indented<'a>(&'a Node, usize);
+
+impl Display for Indented<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.display(f, self.1)
+    }
+}
+
+/// Utility to join multiple expressions, like arguments or array elements, into a single string.
+fn join_operands(f: &mut fmt::Formatter<'_>, nodes: &[Node], indentation: usize) -> fmt::Result {
     let mut first = true;
     for e in nodes {
         if !first {
             f.write_str(", ")?;
         }
         first = false;
-        Display::fmt(e, f)?;
+        e.display_operand(f, ASSIGNMENT, indentation)?;
     }
     Ok(())
 }
 
+/// Utility to display the parameters of a function between parentheses.
+fn display_parameters(
+    f: &mut fmt::Formatter<'_>,
+    parameters: &[FormalParameter],
+    indentation: usize,
+) -> fmt::Result {
+    f.write_str("(")?;
+    let mut first = true;
+    for param in parameters {
+        if !first {
+            f.write_str(", ")?;
+        }
+        first = false;
+        param.display(f, indentation)?;
+    }
+    f.write_str(")")
+}
+
 /// "Formal parameter" is a fancy way of saying "function parameter".
 ///
 /// In the declaration of a function, the parameters must be identifiers,
@@ -274,7 +441,7 @@ where
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Trace, Finalize)]
 pub struct FormalParameter {
-    name: Box<str>,
+    name: Sym,
     init: Option<Node>,
     is_rest_param: bool,
 }
@@ -283,7 +450,7 @@ impl FormalParameter {
     /// Creates a new formal parameter.
     pub(in crate::syntax) fn new<N>(name: N, init: Option<Node>, is_rest_param: bool) -> Self
     where
-        N: Into<Box<str>>,
+        N: Into<Sym>,
     {
         Self {
             name: name.into(),
@@ -293,8 +460,8 @@ impl FormalParameter {
     }
 
     /// Gets the name of the formal parameter.
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn name(&self) -> Sym {
+        self.name
     }
 
     /// Gets the initialization node of the formal parameter, if any.
@@ -302,25 +469,36 @@ impl FormalParameter {
         self.init.as_ref()
     }
 
+    /// Gets a mutable reference to the initialization node of the formal parameter, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
     /// Gets wether the parameter is a rest parameter.
     pub fn is_rest_param(&self) -> bool {
         self.is_rest_param
     }
-}
 
-impl Display for FormalParameter {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// Implements the display formatting with indentation.
+    fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         if self.is_rest_param {
             write!(f, "...")?;
         }
         write!(f, "{}", self.name)?;
         if let Some(n) = self.init.as_ref() {
-            write!(f, " = {}", n)?;
+            f.write_str(" = ")?;
+            n.display_operand(f, ASSIGNMENT, indentation)?;
         }
         Ok(())
     }
 }
 
+impl Display for FormalParameter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(f, 0)
+    }
+}
+
 /// A JavaScript property is a characteristic of an object, often describing attributes associated with a data structure.
 ///
 /// A property has a name (a string) and a value (primitive, method, or object reference).
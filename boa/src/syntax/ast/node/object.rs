@@ -1,10 +1,13 @@
 //! Object node.
 
-use super::Node;
+use super::{MethodDefinitionKind, Node, ASSIGNMENT};
 use gc::{Finalize, Trace};
 use std::fmt;
 
-use crate::syntax::ast::node::PropertyDefinition;
+use crate::syntax::{
+    ast::{constant::display_string, node::PropertyDefinition},
+    lexer::is_identifier_name,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -40,27 +43,60 @@ impl Object {
         &self.properties
     }
 
+    /// Gets a mutable reference to the property definitions of the object.
+    pub(crate) fn properties_mut(&mut self) -> &mut [PropertyDefinition] {
+        &mut self.properties
+    }
+
     /// Implements the display formatting with indentation.
-    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        if self.properties.is_empty() {
+            return f.write_str("{}");
+        }
+
+        let indent = "    ".repeat(indentation);
         f.write_str("{\n")?;
-        for property in self.properties().iter() {
+        for (i, property) in self.properties().iter().enumerate() {
+            if i != 0 {
+                f.write_str(",\n")?;
+            }
+            write!(f, "{}    ", indent)?;
             match property {
-                PropertyDefinition::IdentifierReference(key) => {
-                    write!(f, "{}    {},", indent, key)?;
-                }
+                PropertyDefinition::IdentifierReference(key) => f.write_str(key)?,
                 PropertyDefinition::Property(key, value) => {
-                    write!(f, "{}    {}: {},", indent, key, value)?;
+                    display_property_name(f, key)?;
+                    f.write_str(": ")?;
+                    value.display_operand(f, ASSIGNMENT, indentation + 1)?;
                 }
-                PropertyDefinition::SpreadObject(key) => {
-                    write!(f, "{}    ...{},", indent, key)?;
+                PropertyDefinition::SpreadObject(obj) => {
+                    f.write_str("...")?;
+                    obj.display_operand(f, ASSIGNMENT, indentation + 1)?;
                 }
-                PropertyDefinition::MethodDefinition(_kind, _key, _node) => {
-                    // TODO: Implement display for PropertyDefinition::MethodDefinition.
-                    unimplemented!("Display for PropertyDefinition::MethodDefinition");
+                PropertyDefinition::MethodDefinition(kind, key, function) => {
+                    match kind {
+                        MethodDefinitionKind::Get => f.write_str("get ")?,
+                        MethodDefinitionKind::Set => f.write_str("set ")?,
+                        MethodDefinitionKind::Ordinary => {}
+                    }
+                    display_property_name(f, key)?;
+                    function.display_method(f, indentation + 1)?;
                 }
             }
         }
-        f.write_str("}")
+        write!(f, "\n{}}}", indent)
+    }
+}
+
+/// Displays the name of a property, putting it between quotes if it's not an identifier name or
+/// a number.
+fn display_property_name(f: &mut fmt::Formatter<'_>, name: &str) -> fmt::Result {
+    let is_index =
+        name == "0" || (!name.starts_with('0') && name.bytes().all(|b| b.is_ascii_digit()));
+
+    if is_identifier_name(name) || (is_index && !name.is_empty()) {
+        f.write_str(name)
+    } else {
+        display_string(f, name)
     }
 }
 
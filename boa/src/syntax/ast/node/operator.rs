@@ -1,5 +1,8 @@
-use super::Node;
-use crate::syntax::ast::op;
+use super::{Node, ASSIGNMENT, COMMA, MEMBER, POSTFIX, UNARY};
+use crate::syntax::ast::{
+    op::{self, BitOp, CompOp, LogOp, NumOp},
+    Const,
+};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -20,8 +23,8 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct Assign {
-    lhs: Box<Node>,
-    rhs: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    operands: Box<Operands>,
 }
 
 impl Assign {
@@ -32,25 +35,46 @@ impl Assign {
         R: Into<Node>,
     {
         Self {
-            lhs: Box::new(lhs.into()),
-            rhs: Box::new(rhs.into()),
+            operands: Box::new(Operands {
+                lhs: lhs.into(),
+                rhs: rhs.into(),
+            }),
         }
     }
 
     /// Gets the left hand side of the assignment operation.
     pub fn lhs(&self) -> &Node {
-        &self.lhs
+        &self.operands.lhs
     }
 
     /// Gets the right hand side of the assignment operation.
     pub fn rhs(&self) -> &Node {
-        &self.rhs
+        &self.operands.rhs
+    }
+
+    /// Gets a mutable reference to the left hand side of the operation.
+    pub(crate) fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.operands.lhs
+    }
+
+    /// Gets a mutable reference to the right hand side of the operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.operands.rhs
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.operands.lhs.display_operand(f, MEMBER, indentation)?;
+        f.write_str(" = ")?;
+        self.operands
+            .rhs
+            .display_operand(f, ASSIGNMENT, indentation)
     }
 }
 
 impl fmt::Display for Assign {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} = {}", self.lhs, self.rhs)
+        self.display(f, 0)
     }
 }
 
@@ -60,6 +84,15 @@ impl From<Assign> for Node {
     }
 }
 
+/// The operands of an assignment or a binary operation, stored together to avoid multiple
+/// indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct Operands {
+    lhs: Node,
+    rhs: Node,
+}
+
 /// Binary operators requires two operands, one before the operator and one after the operator.
 ///
 /// More information:
@@ -70,8 +103,8 @@ impl From<Assign> for Node {
 #[derive(Clone, Debug, Trace, Finalize, PartialEq)]
 pub struct BinOp {
     op: op::BinOp,
-    lhs: Box<Node>,
-    rhs: Box<Node>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    operands: Box<Operands>,
 }
 
 impl BinOp {
@@ -84,8 +117,10 @@ impl BinOp {
     {
         Self {
             op: op.into(),
-            lhs: Box::new(lhs.into()),
-            rhs: Box::new(rhs.into()),
+            operands: Box::new(Operands {
+                lhs: lhs.into(),
+                rhs: rhs.into(),
+            }),
         }
     }
 
@@ -96,18 +131,70 @@ impl BinOp {
 
     /// Gets the left hand side of the binary operation.
     pub fn lhs(&self) -> &Node {
-        &self.lhs
+        &self.operands.lhs
     }
 
     /// Gets the right hand side of the binary operation.
     pub fn rhs(&self) -> &Node {
-        &self.rhs
+        &self.operands.rhs
+    }
+
+    /// Gets a mutable reference to the left hand side of the operation.
+    pub(crate) fn lhs_mut(&mut self) -> &mut Node {
+        &mut self.operands.lhs
+    }
+
+    /// Gets a mutable reference to the right hand side of the operation.
+    pub(crate) fn rhs_mut(&mut self) -> &mut Node {
+        &mut self.operands.rhs
+    }
+
+    /// Gets the precedence of the operation.
+    pub(super) fn precedence(&self) -> u8 {
+        match self.op {
+            op::BinOp::Comma => COMMA,
+            op::BinOp::Assign(_) => ASSIGNMENT,
+            op::BinOp::Log(LogOp::Or) => 3,
+            op::BinOp::Log(LogOp::And) => 4,
+            op::BinOp::Bit(BitOp::Or) => 5,
+            op::BinOp::Bit(BitOp::Xor) => 6,
+            op::BinOp::Bit(BitOp::And) => 7,
+            op::BinOp::Comp(CompOp::Equal)
+            | op::BinOp::Comp(CompOp::NotEqual)
+            | op::BinOp::Comp(CompOp::StrictEqual)
+            | op::BinOp::Comp(CompOp::StrictNotEqual) => 8,
+            op::BinOp::Comp(_) => 9,
+            op::BinOp::Bit(_) => 10,
+            op::BinOp::Num(NumOp::Add) | op::BinOp::Num(NumOp::Sub) => 11,
+            op::BinOp::Num(NumOp::Exp) => 13,
+            op::BinOp::Num(_) => 12,
+        }
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        let precedence = self.precedence();
+        let (lhs, rhs) = match self.op {
+            // Assignments and exponentiations are right-associative, and the base of an
+            // exponentiation can't be an unary expression.
+            op::BinOp::Assign(_) => (MEMBER, ASSIGNMENT),
+            op::BinOp::Num(NumOp::Exp) => (POSTFIX, precedence),
+            _ => (precedence, precedence + 1),
+        };
+
+        self.operands.lhs.display_operand(f, lhs, indentation)?;
+        if let op::BinOp::Comma = self.op {
+            f.write_str(", ")?;
+        } else {
+            write!(f, " {} ", self.op)?;
+        }
+        self.operands.rhs.display_operand(f, rhs, indentation)
     }
 }
 
 impl fmt::Display for BinOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.lhs, self.op, self.rhs)
+        self.display(f, 0)
     }
 }
 
@@ -153,11 +240,77 @@ impl UnaryOp {
     pub fn target(&self) -> &Node {
         self.target.as_ref()
     }
+
+    /// Gets a mutable reference to the target of this unary operator.
+    pub(crate) fn target_mut(&mut self) -> &mut Node {
+        &mut self.target
+    }
+
+    /// Checks if the operator is written after its operand.
+    fn is_postfix(&self) -> bool {
+        matches!(
+            self.op,
+            op::UnaryOp::IncrementPost | op::UnaryOp::DecrementPost
+        )
+    }
+
+    /// Gets the precedence of the operation.
+    pub(super) fn precedence(&self) -> u8 {
+        if self.is_postfix() {
+            POSTFIX
+        } else {
+            UNARY
+        }
+    }
+
+    /// Checks if the displayed operand starts with the same sign as the operator, so they must be
+    /// separated to not be read as a single token, like in `- -a`.
+    fn operand_starts_with_sign(&self) -> bool {
+        let sign = match self.op {
+            op::UnaryOp::Plus => '+',
+            op::UnaryOp::Minus => '-',
+            _ => return false,
+        };
+
+        match *self.target {
+            Node::UnaryOp(ref target) => {
+                !target.is_postfix() && target.op.to_string().starts_with(sign)
+            }
+            Node::Const(Const::Num(num)) => sign == '-' && num.is_sign_negative(),
+            Node::Const(Const::Int(num)) => sign == '-' && num < 0,
+            _ => false,
+        }
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        match self.op {
+            op::UnaryOp::IncrementPost | op::UnaryOp::DecrementPost => {
+                self.target.display_operand(f, MEMBER, indentation)?;
+                write!(f, "{}", self.op)
+            }
+            op::UnaryOp::Delete | op::UnaryOp::TypeOf | op::UnaryOp::Void => {
+                write!(f, "{} ", self.op)?;
+                self.target.display_operand(f, UNARY, indentation)
+            }
+            op::UnaryOp::IncrementPre | op::UnaryOp::DecrementPre => {
+                write!(f, "{}", self.op)?;
+                self.target.display_operand(f, MEMBER, indentation)
+            }
+            _ => {
+                write!(f, "{}", self.op)?;
+                if self.operand_starts_with_sign() {
+                    f.write_str(" ")?;
+                }
+                self.target.display_operand(f, UNARY, indentation)
+            }
+        }
+    }
 }
 
 impl fmt::Display for UnaryOp {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.op, self.target)
+        self.display(f, 0)
     }
 }
 
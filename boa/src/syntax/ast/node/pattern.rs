@@ -0,0 +1,218 @@
+//! Binding pattern nodes.
+
+use super::{Identifier, Node, ASSIGNMENT};
+use crate::interner::Sym;
+use gc::{Finalize, Trace};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The target of a declaration, which is either a single identifier or a binding pattern
+/// destructuring the declared value.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-LexicalBinding
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub enum Binding {
+    /// A binding identifier.
+    Identifier(Identifier),
+    /// An array binding pattern.
+    Array(ArrayPattern),
+}
+
+impl Binding {
+    /// Gets the names of the bindings declared by this target, in the order they appear.
+    pub fn names(&self) -> Vec<Sym> {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    /// Appends the names of the bindings declared by this target to a list.
+    fn collect_names(&self, names: &mut Vec<Sym>) {
+        match self {
+            Self::Identifier(ident) => names.push(ident.sym()),
+            Self::Array(pattern) => {
+                for element in pattern.elements().iter().flatten() {
+                    element.target().collect_names(names);
+                }
+                if let Some(rest) = pattern.rest() {
+                    rest.collect_names(names);
+                }
+            }
+        }
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        match self {
+            Self::Identifier(ident) => fmt::Display::fmt(ident, f),
+            Self::Array(pattern) => pattern.display(f, indentation),
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(f, 0)
+    }
+}
+
+impl<T> From<T> for Binding
+where
+    T: Into<Identifier>,
+{
+    fn from(ident: T) -> Self {
+        Self::Identifier(ident.into())
+    }
+}
+
+impl From<ArrayPattern> for Binding {
+    fn from(pattern: ArrayPattern) -> Self {
+        Self::Array(pattern)
+    }
+}
+
+/// An array binding pattern assigns the values produced by iterating over the declared value
+/// to its elements, in order.
+///
+/// An element can be skipped with an elision, take a default value when the iterator produces
+/// `undefined` or no value, or be a nested pattern. A rest element at the end collects the
+/// remaining values in an array.
+///
+/// ```text
+/// let [first, , third = 3, [nested], ...rest] = iterable;
+/// ```
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayBindingPattern
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Destructuring_assignment#Array_destructuring
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub struct ArrayPattern {
+    elements: Box<[Option<PatternElement>]>,
+    rest: Option<Box<Binding>>,
+}
+
+impl ArrayPattern {
+    /// Creates a new array binding pattern.
+    pub(in crate::syntax) fn new<E>(elements: E, rest: Option<Binding>) -> Self
+    where
+        E: Into<Box<[Option<PatternElement>]>>,
+    {
+        Self {
+            elements: elements.into(),
+            rest: rest.map(Box::new),
+        }
+    }
+
+    /// Gets the elements of the pattern, where `None` is an elision.
+    pub fn elements(&self) -> &[Option<PatternElement>] {
+        &self.elements
+    }
+
+    /// Gets a mutable reference to the elements of the pattern.
+    pub(crate) fn elements_mut(&mut self) -> &mut [Option<PatternElement>] {
+        &mut self.elements
+    }
+
+    /// Gets the target of the rest element of the pattern, if any.
+    pub fn rest(&self) -> Option<&Binding> {
+        self.rest.as_deref()
+    }
+
+    /// Gets a mutable reference to the target of the rest element of the pattern, if any.
+    pub(crate) fn rest_mut(&mut self) -> Option<&mut Binding> {
+        self.rest.as_deref_mut()
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("[")?;
+        let mut first = true;
+        for element in self.elements.iter() {
+            if !first {
+                f.write_str(", ")?;
+            }
+            first = false;
+            if let Some(element) = element {
+                element.display(f, indentation)?;
+            }
+        }
+        if let Some(rest) = self.rest.as_ref() {
+            if !first {
+                f.write_str(", ")?;
+            }
+            f.write_str("...")?;
+            rest.display(f, indentation)?;
+        } else if let Some(None) = self.elements.last() {
+            // A trailing elision needs its own comma.
+            f.write_str(",")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl fmt::Display for ArrayPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(f, 0)
+    }
+}
+
+/// An element of an array binding pattern, with its default value.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub struct PatternElement {
+    target: Binding,
+    init: Option<Node>,
+}
+
+impl PatternElement {
+    /// Creates a new element of a binding pattern.
+    pub(in crate::syntax) fn new<T>(target: T, init: Option<Node>) -> Self
+    where
+        T: Into<Binding>,
+    {
+        Self {
+            target: target.into(),
+            init,
+        }
+    }
+
+    /// Gets the target the value of the element is bound to.
+    pub fn target(&self) -> &Binding {
+        &self.target
+    }
+
+    /// Gets a mutable reference to the target of the element.
+    pub(crate) fn target_mut(&mut self) -> &mut Binding {
+        &mut self.target
+    }
+
+    /// Gets the default value of the element, if any.
+    pub fn init(&self) -> Option<&Node> {
+        self.init.as_ref()
+    }
+
+    /// Gets a mutable reference to the default value of the element, if any.
+    pub(crate) fn init_mut(&mut self) -> Option<&mut Node> {
+        self.init.as_mut()
+    }
+
+    /// Implements the display formatting with indentation.
+    fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        self.target.display(f, indentation)?;
+        if let Some(ref init) = self.init {
+            f.write_str(" = ")?;
+            init.display_operand(f, ASSIGNMENT, indentation)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use super::Node;
+use crate::syntax::ast::token::RegExpFlags;
+use gc::{Finalize, Trace};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A regular expression literal creates a new `RegExp` object each time it's evaluated.
+///
+/// Syntax: `/pattern/flags`
+///
+/// The pattern and flags are kept as written in the source code, and the `RegExp` object is
+/// only built at evaluation time, using the intrinsic `RegExp` constructor.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-primary-expression-regular-expression-literals
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Guide/Regular_Expressions
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub struct RegExpLiteral {
+    pattern: Box<str>,
+    flags: RegExpFlags,
+}
+
+impl RegExpLiteral {
+    /// Creates a `RegExpLiteral` AST node.
+    pub fn new<P>(pattern: P, flags: RegExpFlags) -> Self
+    where
+        P: Into<Box<str>>,
+    {
+        Self {
+            pattern: pattern.into(),
+            flags,
+        }
+    }
+
+    /// Gets the pattern of the regular expression, as written in the source code.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Gets the flags of the regular expression.
+    pub fn flags(&self) -> RegExpFlags {
+        self.flags
+    }
+}
+
+impl fmt::Display for RegExpLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/{}", self.pattern, self.flags)
+    }
+}
+
+impl From<RegExpLiteral> for Node {
+    fn from(regexp: RegExpLiteral) -> Self {
+        Self::RegExpLiteral(regexp)
+    }
+}
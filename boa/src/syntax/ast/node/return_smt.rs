@@ -35,6 +35,11 @@ impl Return {
         self.expr.as_ref().map(Box::as_ref)
     }
 
+    /// Gets a mutable reference to the returned expression, if any.
+    pub(crate) fn expr_mut(&mut self) -> Option<&mut Node> {
+        self.expr.as_mut().map(Box::as_mut)
+    }
+
     /// Creates a `Return` AST node.
     pub fn new<E, OE>(expr: OE) -> Self
     where
@@ -45,6 +50,16 @@ impl Return {
             expr: expr.into().map(E::into).map(Box::new),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("return")?;
+        if let Some(expr) = self.expr() {
+            f.write_str(" ")?;
+            expr.display(f, indentation)?;
+        }
+        Ok(())
+    }
 }
 
 impl From<Return> for Node {
@@ -55,9 +70,6 @@ impl From<Return> for Node {
 
 impl fmt::Display for Return {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.expr() {
-            Some(ex) => write!(f, "return {}", ex),
-            None => write!(f, "return"),
-        }
+        self.display(f, 0)
     }
 }
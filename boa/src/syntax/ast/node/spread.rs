@@ -1,4 +1,4 @@
-use super::Node;
+use super::{Node, ASSIGNMENT};
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -33,6 +33,11 @@ impl Spread {
         &self.val
     }
 
+    /// Gets a mutable reference to the spread value.
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
     /// Creates a `Spread` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -42,11 +47,17 @@ impl Spread {
             val: Box::new(val.into()),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("...")?;
+        self.val().display_operand(f, ASSIGNMENT, indentation)
+    }
 }
 
 impl fmt::Display for Spread {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "...{}", self.val())
+        self.display(f, 0)
     }
 }
 
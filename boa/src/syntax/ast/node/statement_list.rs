@@ -1,8 +1,9 @@
 //! Statement list node.
 
 use super::Node;
+use crate::syntax::ast::Position;
 use gc::{Finalize, Trace};
-use std::fmt;
+use std::{fmt, mem};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -11,39 +12,91 @@ use serde::{Deserialize, Serialize};
 ///
 /// Similar to `Node::Block` but without the braces.
 ///
+/// The lists built by the parser also know where each of their statements starts in the source,
+/// which is what the debugger uses to pause on them. The positions aren't compared, so a parsed
+/// list equals the one built from the same nodes.
+///
 /// More information:
 ///  - [ECMAScript reference][spec]
 ///
 /// [spec]: https://tc39.es/ecma262/#prod-StatementList
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Clone, Debug, Trace, Finalize)]
 pub struct StatementList {
-    #[cfg_attr(feature = "serde", serde(flatten))]
     statements: Box<[Node]>,
+    /// The positions where the statements start, or nothing if the list wasn't parsed.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    positions: Box<[Position]>,
 }
 
 impl StatementList {
+    /// Creates a list of statements, each starting at the position of the same index.
+    pub(crate) fn with_positions(statements: Vec<Node>, positions: Vec<Position>) -> Self {
+        debug_assert_eq!(statements.len(), positions.len());
+        Self {
+            statements: statements.into_boxed_slice(),
+            positions: positions.into_boxed_slice(),
+        }
+    }
+
     /// Gets the list of statements.
     pub fn statements(&self) -> &[Node] {
         &self.statements
     }
 
+    /// Gets the position where the statement at the given index starts, if the list was parsed.
+    pub fn position(&self, index: usize) -> Option<Position> {
+        self.positions.get(index).copied()
+    }
+
+    /// Gets a mutable reference to the list of statements.
+    pub(crate) fn statements_mut(&mut self) -> &mut [Node] {
+        &mut self.statements
+    }
+
+    /// Removes all the statements after the first `len` ones.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        if len < self.statements.len() {
+            let mut statements = mem::take(&mut self.statements).into_vec();
+            statements.truncate(len);
+            self.statements = statements.into_boxed_slice();
+        }
+        if len < self.positions.len() {
+            let mut positions = mem::take(&mut self.positions).into_vec();
+            positions.truncate(len);
+            self.positions = positions.into_boxed_slice();
+        }
+    }
+
     /// Implements the display formatting with indentation.
+    ///
+    /// Each statement is written on its own line, with the given indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         let indent = "    ".repeat(indentation);
-        // Print statements
         for node in self.statements.iter() {
             f.write_str(&indent)?;
-            node.display(f, indentation + 1)?;
-
-            match node {
-                Node::Block(_) | Node::If(_) | Node::Switch(_) | Node::WhileLoop(_) => {}
-                _ => write!(f, ";")?,
-            }
+            node.display_statement(f, indentation)?;
             writeln!(f)?;
         }
         Ok(())
     }
+
+    /// Implements the display formatting of the statements between braces, like the body of a
+    /// block or a function.
+    pub(super) fn display_block(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        indentation: usize,
+    ) -> fmt::Result {
+        if self.statements.is_empty() {
+            return f.write_str("{}");
+        }
+
+        writeln!(f, "{{")?;
+        self.display(f, indentation + 1)?;
+        write!(f, "{}}}", "    ".repeat(indentation))
+    }
 }
 
 impl<T> From<T> for StatementList
@@ -53,10 +106,17 @@ where
     fn from(stm: T) -> Self {
         Self {
             statements: stm.into(),
+            positions: Box::default(),
         }
     }
 }
 
+impl PartialEq for StatementList {
+    fn eq(&self, other: &Self) -> bool {
+        self.statements == other.statements
+    }
+}
+
 impl fmt::Display for StatementList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.display(f, 0)
@@ -24,6 +24,16 @@ impl Case {
     pub fn body(&self) -> &StatementList {
         &self.body
     }
+
+    /// Gets a mutable reference to the condition of the case.
+    pub(crate) fn condition_mut(&mut self) -> &mut Node {
+        &mut self.condition
+    }
+
+    /// Gets a mutable reference to the statements of the case.
+    pub(crate) fn body_mut(&mut self) -> &mut StatementList {
+        &mut self.body
+    }
 }
 
 /// The `switch` statement evaluates an expression, matching the expression's value to a case
@@ -63,6 +73,21 @@ impl Switch {
         self.default.as_ref().map(Box::as_ref)
     }
 
+    /// Gets a mutable reference to the value to match.
+    pub(crate) fn val_mut(&mut self) -> &mut Node {
+        &mut self.val
+    }
+
+    /// Gets a mutable reference to the list of cases.
+    pub(crate) fn cases_mut(&mut self) -> &mut [Case] {
+        &mut self.cases
+    }
+
+    /// Gets a mutable reference to the default case, if any.
+    pub(crate) fn default_mut(&mut self) -> Option<&mut Node> {
+        self.default.as_mut().map(Box::as_mut)
+    }
+
     /// Creates a `Switch` AST node.
     pub fn new<V, C>(val: V, cases: C, default: Option<V>) -> Self
     where
@@ -77,18 +102,25 @@ impl Switch {
     }
 
     /// Implements the display formatting with indentation.
-    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
-        writeln!(f, "switch ({}) {{", self.val())?;
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        let indent = "    ".repeat(indentation);
+        f.write_str("switch (")?;
+        self.val().display(f, indentation)?;
+        writeln!(f, ") {{")?;
         for e in self.cases().iter() {
-            writeln!(f, "{}case {}:", indent, e.condition())?;
-            e.body().display(f, indent)?;
+            write!(f, "{}    case ", indent)?;
+            e.condition().display(f, indentation + 1)?;
+            writeln!(f, ":")?;
+            e.body().display(f, indentation + 2)?;
         }
 
-        if self.default().is_some() {
-            writeln!(f, "{}default:", indent)?;
-            self.default().as_ref().unwrap().display(f, indent + 1)?;
+        if let Some(default) = self.default() {
+            writeln!(f, "{}    default:", indent)?;
+            write!(f, "{}        ", indent)?;
+            default.display_statement(f, indentation + 2)?;
+            writeln!(f)?;
         }
-        writeln!(f, "{}}}", indent)
+        write!(f, "{}}}", indent)
     }
 }
 
@@ -0,0 +1,139 @@
+//! Tests for the display formatting of the AST.
+
+use super::*;
+use crate::syntax::{lexer::Lexer, parser::Parser};
+
+/// Parses the given JavaScript source code.
+fn parse(src: &str) -> StatementList {
+    Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .unwrap_or_else(|e| panic!("failed to parse {:?}: {}", src, e))
+}
+
+/// Checks that the source code generated from the AST of the given source code is the expected
+/// one.
+fn check_output(src: &str, expected: &str) {
+    assert_eq!(parse(src).to_string(), expected);
+}
+
+/// Checks that the source code generated from the AST of the given source code is parsed back
+/// to the same AST.
+fn check_round_trip(src: &str) {
+    let ast = parse(src);
+    let generated = ast.to_string();
+    assert_eq!(parse(&generated), ast, "generated source:\n{}", generated);
+}
+
+#[test]
+fn statements() {
+    check_output(
+        "var a = 1, b; let c = a; const d = 'x'; a++; b = a + c;",
+        "var a = 1, b;\nlet c = a;\nconst d = \"x\";\na++;\nb = a + c;\n",
+    );
+    check_output("", "");
+    check_output("{}", "{}\n");
+}
+
+#[test]
+fn functions() {
+    check_output(
+        "function add(a, b = 1, ...rest) { return a + b; } function empty() {}",
+        "function add(a, b = 1, ...rest) {\n    return a + b;\n}\nfunction empty() {}\n",
+    );
+    check_output(
+        "let f = (x) => { return x * 2; };",
+        "let f = (x) => {\n    return x * 2;\n};\n",
+    );
+    check_output(
+        "(function () { return 1; })();",
+        "(function() {\n    return 1;\n}());\n",
+    );
+}
+
+#[test]
+fn nested_blocks() {
+    check_output(
+        "if (a) { while (b) { b--; } } else if (c) c(); else { d(); }",
+        "if (a) {\n    while (b) {\n        b--;\n    }\n} else if (c)\n    c();\nelse {\n    d();\n}\n",
+    );
+    check_output(
+        "for (let i = 0; i < 10; i++) { continue; }",
+        "for (let i = 0; i < 10; i++) {\n    continue;\n}\n",
+    );
+    check_output(
+        "try { a(); } catch (e) { b(e); } finally { c(); }",
+        "try {\n    a();\n} catch (e) {\n    b(e);\n} finally {\n    c();\n}\n",
+    );
+    check_output(
+        "do { a++; } while (a < 10);",
+        "do {\n    a++;\n} while (a < 10);\n",
+    );
+}
+
+#[test]
+fn parentheses() {
+    check_output("(a + b) * c;", "(a + b) * c;\n");
+    check_output("a + b * c;", "a + b * c;\n");
+    check_output("a - (b - c);", "a - (b - c);\n");
+    check_output("(a - b) - c;", "a - b - c;\n");
+    check_output("(-a) ** 2;", "(-a) ** 2;\n");
+    check_output("a ** b ** c;", "a ** b ** c;\n");
+    check_output("(a ** b) ** c;", "(a ** b) ** c;\n");
+    check_output("- (-a);", "- -a;\n");
+    check_output("new (f())();", "new (f())();\n");
+    check_output("(a ? b : c) ? d : e;", "(a ? b : c) ? d : e;\n");
+    check_output("({ a: 1 });", "({\n    a: 1\n});\n");
+    check_output("a = (b, c), d;", "a = (b, c), d;\n");
+    check_output("f((a, b), c);", "f((a, b), c);\n");
+}
+
+#[test]
+fn literals() {
+    check_output(
+        r#"let s = "a\"b\\c\n\td";"#,
+        "let s = \"a\\\"b\\\\c\\n\\td\";\n",
+    );
+    check_output("1.5; 10n; [1, , 2];", "1.5;\n10n;\n[1, undefined, 2];\n");
+    check_output(
+        "let o = { 'b c': 1, 2: 3, get d() { return 4; }, ...e };",
+        "let o = {\n    \"b c\": 1,\n    2: 3,\n    get d() {\n        return 4;\n    },\n    ...e\n};\n",
+    );
+}
+
+#[test]
+fn dangling_else() {
+    check_output(
+        "if (a) { if (b) c(); } else d();",
+        "if (a) {\n    if (b)\n        c();\n} else\n    d();\n",
+    );
+    check_round_trip("if (a) { if (b) c(); } else d();");
+    check_round_trip("if (a) { for (;;) if (b) c(); } else d();");
+}
+
+#[test]
+fn round_trip() {
+    check_round_trip(
+        r#"
+        function fib(n) {
+            if (n < 2) return n;
+            return fib(n - 1) + fib(n - 2);
+        }
+        var obj = { value: fib(10), "key with spaces": [1, "two", true, null], nested: {} };
+        let total = 0;
+        for (let i = 0; i < obj.value; i++) {
+            total += i % 3 === 0 ? i : -i;
+        }
+        while (total > 0) total -= 5;
+        const greet = (name = "world") => { return "hello " + name; };
+        try {
+            throw new Error("oops");
+        } catch (e) {
+            total = typeof e + void 0 + !total;
+        }
+        ({}).toString();
+        a = b = c;
+        delete obj["key with spaces"];
+        " \0";
+        "#,
+    );
+}
@@ -30,6 +30,11 @@ impl Throw {
         &self.expr
     }
 
+    /// Gets a mutable reference to the thrown expression.
+    pub(crate) fn expr_mut(&mut self) -> &mut Node {
+        &mut self.expr
+    }
+
     /// Creates a `Throw` AST node.
     pub fn new<V>(val: V) -> Self
     where
@@ -39,11 +44,17 @@ impl Throw {
             expr: Box::new(val.into()),
         }
     }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("throw ")?;
+        self.expr.display(f, indentation)
+    }
 }
 
 impl fmt::Display for Throw {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "throw {}", self.expr)
+        self.display(f, 0)
     }
 }
 
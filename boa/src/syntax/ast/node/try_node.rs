@@ -1,4 +1,5 @@
 use super::{Block, Identifier, Node};
+use crate::interner::Sym;
 use gc::{Finalize, Trace};
 use std::fmt;
 
@@ -63,9 +64,24 @@ impl Try {
         self.finally.as_ref().map(Finally::block)
     }
 
+    /// Gets a mutable reference to the `try` block.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
+    /// Gets a mutable reference to the `catch` block, if any.
+    pub(crate) fn catch_mut(&mut self) -> Option<&mut Catch> {
+        self.catch.as_mut()
+    }
+
+    /// Gets a mutable reference to the `finally` block, if any.
+    pub(crate) fn finally_mut(&mut self) -> Option<&mut Block> {
+        self.finally.as_mut().map(|finally| &mut finally.block)
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
-        write!(f, "{}try ", "    ".repeat(indentation))?;
+        f.write_str("try ")?;
         self.block.display(f, indentation)?;
 
         if let Some(ref catch) = self.catch {
@@ -114,8 +130,8 @@ impl Catch {
     }
 
     /// Gets the parameter of the catch block.
-    pub fn parameter(&self) -> Option<&str> {
-        self.parameter.as_ref().map(Identifier::as_ref)
+    pub fn parameter(&self) -> Option<Sym> {
+        self.parameter.as_ref().map(Identifier::sym)
     }
 
     /// Retrieves the catch execution block.
@@ -123,11 +139,16 @@ impl Catch {
         &self.block
     }
 
+    /// Retrieves a mutable reference to the catch execution block.
+    pub(crate) fn block_mut(&mut self) -> &mut Block {
+        &mut self.block
+    }
+
     /// Implements the display formatting with indentation.
     pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
         f.write_str(" catch")?;
         if let Some(ref param) = self.parameter {
-            write!(f, "({})", param)?;
+            write!(f, " ({})", param)?;
         }
         f.write_str(" ")?;
         self.block.display(f, indentation)
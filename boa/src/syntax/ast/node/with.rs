@@ -0,0 +1,91 @@
+use super::Node;
+use gc::{Finalize, Trace};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The `with` statement extends the scope chain for a statement, with the properties of an
+/// object.
+///
+/// Syntax: `with (expression) statement`
+///
+/// The identifiers of the statement are first looked up in the properties of the object, except
+/// the ones listed in its `@@unscopables` property. It is a syntax error in strict mode code.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///  - [MDN documentation][mdn]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-WithStatement
+/// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/with
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+pub struct With {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    inner: Box<InnerWith>,
+}
+
+impl With {
+    /// Gets the expression of the object whose properties are added to the scope.
+    pub fn object(&self) -> &Node {
+        &self.inner.object
+    }
+
+    /// Gets the statement run in the extended scope.
+    pub fn body(&self) -> &Node {
+        &self.inner.body
+    }
+
+    /// Gets a mutable reference to the expression of the object.
+    pub(crate) fn object_mut(&mut self) -> &mut Node {
+        &mut self.inner.object
+    }
+
+    /// Gets a mutable reference to the statement run in the extended scope.
+    pub(crate) fn body_mut(&mut self) -> &mut Node {
+        &mut self.inner.body
+    }
+
+    /// Creates a `With` AST node.
+    pub fn new<O, B>(object: O, body: B) -> Self
+    where
+        O: Into<Node>,
+        B: Into<Node>,
+    {
+        Self {
+            inner: Box::new(InnerWith {
+                object: object.into(),
+                body: body.into(),
+            }),
+        }
+    }
+
+    /// Implements the display formatting with indentation.
+    pub(super) fn display(&self, f: &mut fmt::Formatter<'_>, indentation: usize) -> fmt::Result {
+        f.write_str("with (")?;
+        self.object().display(f, indentation)?;
+        f.write_str(")")?;
+        self.body().display_body(f, indentation)
+    }
+}
+
+impl fmt::Display for With {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display(f, 0)
+    }
+}
+
+impl From<With> for Node {
+    fn from(with: With) -> Self {
+        Self::With(with)
+    }
+}
+
+/// Inner structure to avoid multiple indirections in the heap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Trace, Finalize, PartialEq)]
+struct InnerWith {
+    object: Node,
+    body: Node,
+}
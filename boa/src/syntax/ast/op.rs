@@ -593,6 +593,23 @@ pub enum CompOp {
     /// [spec]: https://tc39.es/ecma262/#prod-RelationalExpression
     /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/in
     In,
+
+    /// The `instanceof` operator returns true if the specified object is an instance of the
+    /// right hand side object.
+    ///
+    /// Syntax: `obj instanceof Object`
+    ///
+    /// Returns `true` if the `prototype` property of the right hand side constructor appears anywhere
+    /// in the prototype chain of the object, unless the constructor has a `Symbol.hasInstance`
+    /// method, which then decides.
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-RelationalExpression
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/instanceof
+    InstanceOf,
 }
 
 impl Display for CompOp {
@@ -610,6 +627,7 @@ impl Display for CompOp {
                 Self::LessThan => "<",
                 Self::LessThanOrEqual => "<=",
                 Self::In => "in",
+                Self::InstanceOf => "instanceof",
             }
         )
     }
@@ -705,6 +723,21 @@ pub enum BinOp {
     ///
     /// see: [`AssignOp`](enum.AssignOp.html).
     Assign(AssignOp),
+
+    /// Comma operation.
+    ///
+    /// The comma operator evaluates each of its operands, from left to right, and returns the
+    /// value of the last one.
+    ///
+    /// Syntax: `x, y`
+    ///
+    /// More information:
+    ///  - [ECMAScript reference][spec]
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-Expression
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Operators/Comma_Operator
+    Comma,
 }
 
 impl From<NumOp> for BinOp {
@@ -748,6 +781,7 @@ impl Display for BinOp {
                 Self::Comp(ref op) => op.to_string(),
                 Self::Log(ref op) => op.to_string(),
                 Self::Assign(ref op) => op.to_string(),
+                Self::Comma => ",".to_owned(),
             }
         )
     }
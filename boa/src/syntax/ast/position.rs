@@ -1,5 +1,6 @@
 //! This module implements the `Pos` structure, which represents a position in the source code.
 
+use gc::{unsafe_empty_trace, Finalize, Trace};
 use std::{cmp::Ordering, fmt, num::NonZeroU32};
 
 #[cfg(feature = "serde")]
@@ -49,6 +50,12 @@ impl fmt::Display for Position {
     }
 }
 
+impl Finalize for Position {}
+
+unsafe impl Trace for Position {
+    unsafe_empty_trace!();
+}
+
 /// A span in the JavaScript source code.
 ///
 /// Stores a start position and an end position.
@@ -5,12 +5,14 @@
 //!
 //! [spec]: https://tc39.es/ecma262/#sec-tokens
 
-use crate::builtins::BigInt;
+use crate::builtins::{string::JsString, BigInt};
+use crate::interner::Sym;
 use crate::syntax::{
     ast::{Keyword, Punctuator, Span},
     lexer::LexerError,
 };
 use bitflags::bitflags;
+use gc::{unsafe_empty_trace, Finalize, Trace};
 use std::{
     fmt::{self, Debug, Display, Formatter},
     str::FromStr,
@@ -57,6 +59,25 @@ impl Display for Token {
     }
 }
 
+/// Represents the different kinds of comments.
+///
+/// More information:
+///  - [ECMAScript reference][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-comments
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommentKind {
+    /// A single line comment, starting with `//`.
+    Line,
+
+    /// A block comment, delimited by `/*` and `*/`.
+    Block,
+
+    /// A hashbang comment, starting with `#!` at the beginning of a script.
+    Hashbang,
+}
+
 /// Represents the type differenct types of numeric literals.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, PartialEq, Debug)]
@@ -101,6 +122,12 @@ bitflags! {
     }
 }
 
+impl Finalize for RegExpFlags {}
+
+unsafe impl Trace for RegExpFlags {
+    unsafe_empty_trace!();
+}
+
 impl FromStr for RegExpFlags {
     type Err = LexerError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -219,7 +246,7 @@ pub enum TokenKind {
     EOF,
 
     /// An identifier.
-    Identifier(Box<str>),
+    Identifier(Sym),
 
     /// A keyword.
     ///
@@ -238,13 +265,18 @@ pub enum TokenKind {
     Punctuator(Punctuator),
 
     /// A string literal.
-    StringLiteral(Box<str>),
+    StringLiteral(JsString),
 
     /// A regular expression, consisting of body and flags.
     RegularExpressionLiteral(Box<str>, RegExpFlags),
 
     /// Indicates the end of a line (`\n`).
     LineTerminator,
+
+    /// A comment, consisting of its kind and its text without the delimiters.
+    ///
+    /// Comments are only emitted when the lexer is preserving them.
+    Comment(CommentKind, Box<str>),
 }
 
 impl From<bool> for TokenKind {
@@ -279,7 +311,7 @@ impl TokenKind {
     /// Creates an `Identifier` token type.
     pub fn identifier<I>(ident: I) -> Self
     where
-        I: Into<Box<str>>,
+        I: Into<Sym>,
     {
         Self::Identifier(ident.into())
     }
@@ -305,7 +337,7 @@ impl TokenKind {
     /// Creates a `StringLiteral` token type.
     pub fn string_literal<S>(lit: S) -> Self
     where
-        S: Into<Box<str>>,
+        S: Into<JsString>,
     {
         Self::StringLiteral(lit.into())
     }
@@ -322,6 +354,31 @@ impl TokenKind {
     pub fn line_terminator() -> Self {
         Self::LineTerminator
     }
+
+    /// Creates a `Comment` token kind.
+    pub fn comment<T>(kind: CommentKind, text: T) -> Self
+    where
+        T: Into<Box<str>>,
+    {
+        Self::Comment(kind, text.into())
+    }
+
+    /// Gets the name of the kind, without its data, like `Punctuator` for a `+`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BooleanLiteral(_) => "BooleanLiteral",
+            Self::EOF => "EOF",
+            Self::Identifier(_) => "Identifier",
+            Self::Keyword(_) => "Keyword",
+            Self::NullLiteral => "NullLiteral",
+            Self::NumericLiteral(_) => "NumericLiteral",
+            Self::Punctuator(_) => "Punctuator",
+            Self::StringLiteral(_) => "StringLiteral",
+            Self::RegularExpressionLiteral(_, _) => "RegularExpressionLiteral",
+            Self::LineTerminator => "LineTerminator",
+            Self::Comment(_, _) => "Comment",
+        }
+    }
 }
 
 impl Display for TokenKind {
@@ -339,6 +396,9 @@ impl Display for TokenKind {
             Self::StringLiteral(ref lit) => write!(f, "{}", lit),
             Self::RegularExpressionLiteral(ref body, ref flags) => write!(f, "/{}/{}", body, flags),
             Self::LineTerminator => write!(f, "line terminator"),
+            Self::Comment(CommentKind::Line, ref text) => write!(f, "//{}", text),
+            Self::Comment(CommentKind::Block, ref text) => write!(f, "/*{}*/", text),
+            Self::Comment(CommentKind::Hashbang, ref text) => write!(f, "#!{}", text),
         }
     }
 }
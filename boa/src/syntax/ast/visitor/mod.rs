@@ -0,0 +1,1294 @@
+//! Traversal of the AST.
+//!
+//! The [`Visitor`](trait.Visitor.html) and [`VisitorMut`](trait.VisitorMut.html) traits have a
+//! method for each type of node, which by default walks its children. Analyses and
+//! transformations of the AST only need to override the methods for the nodes they care about.
+
+use super::{
+    node::{
+        ArrayDecl, ArrowFunctionDecl, Assign, BinOp, Binding, Block, Break, Call, Case, Catch,
+        ConditionalOp, ConstDecl, ConstDeclList, Continue, DoWhileLoop, ForLoop, FormalParameter,
+        FunctionDecl, FunctionExpr, GetConstField, GetField, Identifier, If, LetDecl, LetDeclList,
+        New, Object, PropertyDefinition, RegExpLiteral, Return, Spread, StatementList, Switch,
+        Throw, Try, UnaryOp, VarDecl, VarDeclList, WhileLoop, With,
+    },
+    Const, Node,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// A visitor of the AST.
+///
+/// Every method visits a node of the given type. The default implementations walk the children
+/// of the node, so an implementor only needs to override the methods for the nodes it's
+/// interested in, calling the matching `walk_*` function to keep visiting the children.
+pub trait Visitor<'ast>: Sized {
+    /// Visits a node.
+    fn visit_node(&mut self, node: &'ast Node) {
+        walk_node(self, node)
+    }
+
+    /// Visits a `StatementList` node.
+    fn visit_statement_list(&mut self, statement_list: &'ast StatementList) {
+        walk_statement_list(self, statement_list)
+    }
+
+    /// Visits an `ArrayDecl` node.
+    fn visit_array_decl(&mut self, array_decl: &'ast ArrayDecl) {
+        walk_array_decl(self, array_decl)
+    }
+
+    /// Visits an `ArrowFunctionDecl` node.
+    fn visit_arrow_function_decl(&mut self, arrow_function_decl: &'ast ArrowFunctionDecl) {
+        walk_arrow_function_decl(self, arrow_function_decl)
+    }
+
+    /// Visits an `Assign` node.
+    fn visit_assign(&mut self, assign: &'ast Assign) {
+        walk_assign(self, assign)
+    }
+
+    /// Visits a `BinOp` node.
+    fn visit_bin_op(&mut self, bin_op: &'ast BinOp) {
+        walk_bin_op(self, bin_op)
+    }
+
+    /// Visits the `Binding` of a declaration.
+    fn visit_binding(&mut self, binding: &'ast Binding) {
+        walk_binding(self, binding)
+    }
+
+    /// Visits a `Block` node.
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block)
+    }
+
+    /// Visits a `Break` node.
+    fn visit_break(&mut self, _: &'ast Break) {}
+
+    /// Visits a `Call` node.
+    fn visit_call(&mut self, call: &'ast Call) {
+        walk_call(self, call)
+    }
+
+    /// Visits a `ConditionalOp` node.
+    fn visit_conditional_op(&mut self, conditional_op: &'ast ConditionalOp) {
+        walk_conditional_op(self, conditional_op)
+    }
+
+    /// Visits a `Const` node.
+    fn visit_const(&mut self, _: &'ast Const) {}
+
+    /// Visits a `ConstDeclList` node.
+    fn visit_const_decl_list(&mut self, const_decl_list: &'ast ConstDeclList) {
+        walk_const_decl_list(self, const_decl_list)
+    }
+
+    /// Visits a `ConstDecl` node.
+    fn visit_const_decl(&mut self, const_decl: &'ast ConstDecl) {
+        walk_const_decl(self, const_decl)
+    }
+
+    /// Visits a `Continue` node.
+    fn visit_continue(&mut self, _: &'ast Continue) {}
+
+    /// Visits a `DoWhileLoop` node.
+    fn visit_do_while_loop(&mut self, do_while_loop: &'ast DoWhileLoop) {
+        walk_do_while_loop(self, do_while_loop)
+    }
+
+    /// Visits a `FunctionDecl` node.
+    fn visit_function_decl(&mut self, function_decl: &'ast FunctionDecl) {
+        walk_function_decl(self, function_decl)
+    }
+
+    /// Visits a `FunctionExpr` node.
+    fn visit_function_expr(&mut self, function_expr: &'ast FunctionExpr) {
+        walk_function_expr(self, function_expr)
+    }
+
+    /// Visits a `FormalParameter` node.
+    fn visit_formal_parameter(&mut self, formal_parameter: &'ast FormalParameter) {
+        walk_formal_parameter(self, formal_parameter)
+    }
+
+    /// Visits a `GetConstField` node.
+    fn visit_get_const_field(&mut self, get_const_field: &'ast GetConstField) {
+        walk_get_const_field(self, get_const_field)
+    }
+
+    /// Visits a `GetField` node.
+    fn visit_get_field(&mut self, get_field: &'ast GetField) {
+        walk_get_field(self, get_field)
+    }
+
+    /// Visits a `ForLoop` node.
+    fn visit_for_loop(&mut self, for_loop: &'ast ForLoop) {
+        walk_for_loop(self, for_loop)
+    }
+
+    /// Visits an `If` node.
+    fn visit_if(&mut self, if_stmt: &'ast If) {
+        walk_if(self, if_stmt)
+    }
+
+    /// Visits a `LetDeclList` node.
+    fn visit_let_decl_list(&mut self, let_decl_list: &'ast LetDeclList) {
+        walk_let_decl_list(self, let_decl_list)
+    }
+
+    /// Visits a `LetDecl` node.
+    fn visit_let_decl(&mut self, let_decl: &'ast LetDecl) {
+        walk_let_decl(self, let_decl)
+    }
+
+    /// Visits an `Identifier` node.
+    fn visit_identifier(&mut self, _: &'ast Identifier) {}
+
+    /// Visits a `New` node.
+    fn visit_new(&mut self, new: &'ast New) {
+        walk_new(self, new)
+    }
+
+    /// Visits an `Object` node.
+    fn visit_object(&mut self, object: &'ast Object) {
+        walk_object(self, object)
+    }
+
+    /// Visits a `PropertyDefinition` node.
+    fn visit_property_definition(&mut self, property_definition: &'ast PropertyDefinition) {
+        walk_property_definition(self, property_definition)
+    }
+
+    /// Visits a `RegExpLiteral` node.
+    fn visit_regexp_literal(&mut self, _: &'ast RegExpLiteral) {}
+
+    /// Visits a `Return` node.
+    fn visit_return(&mut self, ret: &'ast Return) {
+        walk_return(self, ret)
+    }
+
+    /// Visits a `Switch` node.
+    fn visit_switch(&mut self, switch: &'ast Switch) {
+        walk_switch(self, switch)
+    }
+
+    /// Visits a `Case` node.
+    fn visit_case(&mut self, case: &'ast Case) {
+        walk_case(self, case)
+    }
+
+    /// Visits a `Spread` node.
+    fn visit_spread(&mut self, spread: &'ast Spread) {
+        walk_spread(self, spread)
+    }
+
+    /// Visits a `Throw` node.
+    fn visit_throw(&mut self, throw: &'ast Throw) {
+        walk_throw(self, throw)
+    }
+
+    /// Visits a `Try` node.
+    fn visit_try(&mut self, try_stmt: &'ast Try) {
+        walk_try(self, try_stmt)
+    }
+
+    /// Visits a `Catch` node.
+    fn visit_catch(&mut self, catch: &'ast Catch) {
+        walk_catch(self, catch)
+    }
+
+    /// Visits an `UnaryOp` node.
+    fn visit_unary_op(&mut self, unary_op: &'ast UnaryOp) {
+        walk_unary_op(self, unary_op)
+    }
+
+    /// Visits a `VarDeclList` node.
+    fn visit_var_decl_list(&mut self, var_decl_list: &'ast VarDeclList) {
+        walk_var_decl_list(self, var_decl_list)
+    }
+
+    /// Visits a `VarDecl` node.
+    fn visit_var_decl(&mut self, var_decl: &'ast VarDecl) {
+        walk_var_decl(self, var_decl)
+    }
+
+    /// Visits a `WhileLoop` node.
+    fn visit_while_loop(&mut self, while_loop: &'ast WhileLoop) {
+        walk_while_loop(self, while_loop)
+    }
+
+    /// Visits a `With` node.
+    fn visit_with(&mut self, with: &'ast With) {
+        walk_with(self, with)
+    }
+}
+
+/// Walks the node, visiting it with the method for its type.
+pub fn walk_node<'ast, V>(visitor: &mut V, node: &'ast Node)
+where
+    V: Visitor<'ast>,
+{
+    match node {
+        Node::ArrayDecl(n) => visitor.visit_array_decl(n),
+        Node::ArrowFunctionDecl(n) => visitor.visit_arrow_function_decl(n),
+        Node::Assign(n) => visitor.visit_assign(n),
+        Node::BinOp(n) => visitor.visit_bin_op(n),
+        Node::Block(n) => visitor.visit_block(n),
+        Node::Break(n) => visitor.visit_break(n),
+        Node::Call(n) => visitor.visit_call(n),
+        Node::ConditionalOp(n) => visitor.visit_conditional_op(n),
+        Node::Const(n) => visitor.visit_const(n),
+        Node::ConstDeclList(n) => visitor.visit_const_decl_list(n),
+        Node::Continue(n) => visitor.visit_continue(n),
+        Node::DoWhileLoop(n) => visitor.visit_do_while_loop(n),
+        Node::FunctionDecl(n) => visitor.visit_function_decl(n),
+        Node::FunctionExpr(n) => visitor.visit_function_expr(n),
+        Node::GetConstField(n) => visitor.visit_get_const_field(n),
+        Node::GetField(n) => visitor.visit_get_field(n),
+        Node::ForLoop(n) => visitor.visit_for_loop(n),
+        Node::If(n) => visitor.visit_if(n),
+        Node::LetDeclList(n) => visitor.visit_let_decl_list(n),
+        Node::Identifier(n) => visitor.visit_identifier(n),
+        Node::New(n) => visitor.visit_new(n),
+        Node::Object(n) => visitor.visit_object(n),
+        Node::RegExpLiteral(n) => visitor.visit_regexp_literal(n),
+        Node::Return(n) => visitor.visit_return(n),
+        Node::Switch(n) => visitor.visit_switch(n),
+        Node::Spread(n) => visitor.visit_spread(n),
+        Node::Throw(n) => visitor.visit_throw(n),
+        Node::Try(n) => visitor.visit_try(n),
+        Node::UnaryOp(n) => visitor.visit_unary_op(n),
+        Node::VarDeclList(n) => visitor.visit_var_decl_list(n),
+        Node::WhileLoop(n) => visitor.visit_while_loop(n),
+        Node::With(n) => visitor.visit_with(n),
+        Node::This | Node::NewTarget => {}
+    }
+}
+
+/// Walks the children of a `StatementList` node.
+pub fn walk_statement_list<'ast, V>(visitor: &mut V, statement_list: &'ast StatementList)
+where
+    V: Visitor<'ast>,
+{
+    for node in statement_list.statements() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of an `ArrayDecl` node.
+pub fn walk_array_decl<'ast, V>(visitor: &mut V, array_decl: &'ast ArrayDecl)
+where
+    V: Visitor<'ast>,
+{
+    for node in array_decl.as_ref() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of an `ArrowFunctionDecl` node.
+pub fn walk_arrow_function_decl<'ast, V>(
+    visitor: &mut V,
+    arrow_function_decl: &'ast ArrowFunctionDecl,
+) where
+    V: Visitor<'ast>,
+{
+    for item in arrow_function_decl.params() {
+        visitor.visit_formal_parameter(item);
+    }
+    for node in arrow_function_decl.body() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of an `Assign` node.
+pub fn walk_assign<'ast, V>(visitor: &mut V, assign: &'ast Assign)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(assign.lhs());
+    visitor.visit_node(assign.rhs());
+}
+
+/// Walks the children of a `BinOp` node.
+pub fn walk_bin_op<'ast, V>(visitor: &mut V, bin_op: &'ast BinOp)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(bin_op.lhs());
+    visitor.visit_node(bin_op.rhs());
+}
+
+/// Walks the default values of the elements of a `Binding`, and its nested patterns.
+pub fn walk_binding<'ast, V>(visitor: &mut V, binding: &'ast Binding)
+where
+    V: Visitor<'ast>,
+{
+    if let Binding::Array(pattern) = binding {
+        for element in pattern.elements().iter().flatten() {
+            if let Some(node) = element.init() {
+                visitor.visit_node(node);
+            }
+            visitor.visit_binding(element.target());
+        }
+        if let Some(rest) = pattern.rest() {
+            visitor.visit_binding(rest);
+        }
+    }
+}
+
+/// Walks the children of a `Block` node.
+pub fn walk_block<'ast, V>(visitor: &mut V, block: &'ast Block)
+where
+    V: Visitor<'ast>,
+{
+    for node in block.statements() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `Call` node.
+pub fn walk_call<'ast, V>(visitor: &mut V, call: &'ast Call)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(call.expr());
+    for node in call.args() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `ConditionalOp` node.
+pub fn walk_conditional_op<'ast, V>(visitor: &mut V, conditional_op: &'ast ConditionalOp)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(conditional_op.cond());
+    visitor.visit_node(conditional_op.if_true());
+    visitor.visit_node(conditional_op.if_false());
+}
+
+/// Walks the children of a `ConstDeclList` node.
+pub fn walk_const_decl_list<'ast, V>(visitor: &mut V, const_decl_list: &'ast ConstDeclList)
+where
+    V: Visitor<'ast>,
+{
+    for item in const_decl_list.as_ref() {
+        visitor.visit_const_decl(item);
+    }
+}
+
+/// Walks the children of a `ConstDecl` node.
+pub fn walk_const_decl<'ast, V>(visitor: &mut V, const_decl: &'ast ConstDecl)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(const_decl.init());
+    visitor.visit_binding(const_decl.binding());
+}
+
+/// Walks the children of a `DoWhileLoop` node.
+pub fn walk_do_while_loop<'ast, V>(visitor: &mut V, do_while_loop: &'ast DoWhileLoop)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(do_while_loop.body());
+    visitor.visit_node(do_while_loop.cond());
+}
+
+/// Walks the children of a `FunctionDecl` node.
+pub fn walk_function_decl<'ast, V>(visitor: &mut V, function_decl: &'ast FunctionDecl)
+where
+    V: Visitor<'ast>,
+{
+    for item in function_decl.parameters() {
+        visitor.visit_formal_parameter(item);
+    }
+    for node in function_decl.body() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `FunctionExpr` node.
+pub fn walk_function_expr<'ast, V>(visitor: &mut V, function_expr: &'ast FunctionExpr)
+where
+    V: Visitor<'ast>,
+{
+    for item in function_expr.parameters() {
+        visitor.visit_formal_parameter(item);
+    }
+    for node in function_expr.body() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `FormalParameter` node.
+pub fn walk_formal_parameter<'ast, V>(visitor: &mut V, formal_parameter: &'ast FormalParameter)
+where
+    V: Visitor<'ast>,
+{
+    if let Some(node) = formal_parameter.init() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `GetConstField` node.
+pub fn walk_get_const_field<'ast, V>(visitor: &mut V, get_const_field: &'ast GetConstField)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(get_const_field.obj());
+}
+
+/// Walks the children of a `GetField` node.
+pub fn walk_get_field<'ast, V>(visitor: &mut V, get_field: &'ast GetField)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(get_field.obj());
+    visitor.visit_node(get_field.field());
+}
+
+/// Walks the children of a `ForLoop` node.
+pub fn walk_for_loop<'ast, V>(visitor: &mut V, for_loop: &'ast ForLoop)
+where
+    V: Visitor<'ast>,
+{
+    if let Some(node) = for_loop.init() {
+        visitor.visit_node(node);
+    }
+    if let Some(node) = for_loop.condition() {
+        visitor.visit_node(node);
+    }
+    if let Some(node) = for_loop.final_expr() {
+        visitor.visit_node(node);
+    }
+    visitor.visit_node(for_loop.body());
+}
+
+/// Walks the children of an `If` node.
+pub fn walk_if<'ast, V>(visitor: &mut V, if_stmt: &'ast If)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(if_stmt.cond());
+    visitor.visit_node(if_stmt.body());
+    if let Some(node) = if_stmt.else_node() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `LetDeclList` node.
+pub fn walk_let_decl_list<'ast, V>(visitor: &mut V, let_decl_list: &'ast LetDeclList)
+where
+    V: Visitor<'ast>,
+{
+    for item in let_decl_list.as_ref() {
+        visitor.visit_let_decl(item);
+    }
+}
+
+/// Walks the children of a `LetDecl` node.
+pub fn walk_let_decl<'ast, V>(visitor: &mut V, let_decl: &'ast LetDecl)
+where
+    V: Visitor<'ast>,
+{
+    if let Some(node) = let_decl.init() {
+        visitor.visit_node(node);
+    }
+    visitor.visit_binding(let_decl.binding());
+}
+
+/// Walks the children of a `New` node.
+pub fn walk_new<'ast, V>(visitor: &mut V, new: &'ast New)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(new.expr());
+    for node in new.args() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of an `Object` node.
+pub fn walk_object<'ast, V>(visitor: &mut V, object: &'ast Object)
+where
+    V: Visitor<'ast>,
+{
+    for item in object.properties() {
+        visitor.visit_property_definition(item);
+    }
+}
+
+/// Walks the children of a `PropertyDefinition` node.
+pub fn walk_property_definition<'ast, V>(
+    visitor: &mut V,
+    property_definition: &'ast PropertyDefinition,
+) where
+    V: Visitor<'ast>,
+{
+    match property_definition {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(_, node) | PropertyDefinition::SpreadObject(node) => {
+            visitor.visit_node(node)
+        }
+        PropertyDefinition::MethodDefinition(_, _, function) => {
+            visitor.visit_function_expr(function)
+        }
+    }
+}
+
+/// Walks the children of a `Return` node.
+pub fn walk_return<'ast, V>(visitor: &mut V, ret: &'ast Return)
+where
+    V: Visitor<'ast>,
+{
+    if let Some(node) = ret.expr() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `Switch` node.
+pub fn walk_switch<'ast, V>(visitor: &mut V, switch: &'ast Switch)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(switch.val());
+    for item in switch.cases() {
+        visitor.visit_case(item);
+    }
+    if let Some(node) = switch.default() {
+        visitor.visit_node(node);
+    }
+}
+
+/// Walks the children of a `Case` node.
+pub fn walk_case<'ast, V>(visitor: &mut V, case: &'ast Case)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(case.condition());
+    visitor.visit_statement_list(case.body());
+}
+
+/// Walks the children of a `Spread` node.
+pub fn walk_spread<'ast, V>(visitor: &mut V, spread: &'ast Spread)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(spread.val());
+}
+
+/// Walks the children of a `Throw` node.
+pub fn walk_throw<'ast, V>(visitor: &mut V, throw: &'ast Throw)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(throw.expr());
+}
+
+/// Walks the children of a `Try` node.
+pub fn walk_try<'ast, V>(visitor: &mut V, try_stmt: &'ast Try)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_block(try_stmt.block());
+    if let Some(item) = try_stmt.catch() {
+        visitor.visit_catch(item);
+    }
+    if let Some(item) = try_stmt.finally() {
+        visitor.visit_block(item);
+    }
+}
+
+/// Walks the children of a `Catch` node.
+pub fn walk_catch<'ast, V>(visitor: &mut V, catch: &'ast Catch)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_block(catch.block());
+}
+
+/// Walks the children of an `UnaryOp` node.
+pub fn walk_unary_op<'ast, V>(visitor: &mut V, unary_op: &'ast UnaryOp)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(unary_op.target());
+}
+
+/// Walks the children of a `VarDeclList` node.
+pub fn walk_var_decl_list<'ast, V>(visitor: &mut V, var_decl_list: &'ast VarDeclList)
+where
+    V: Visitor<'ast>,
+{
+    for item in var_decl_list.as_ref() {
+        visitor.visit_var_decl(item);
+    }
+}
+
+/// Walks the children of a `VarDecl` node.
+pub fn walk_var_decl<'ast, V>(visitor: &mut V, var_decl: &'ast VarDecl)
+where
+    V: Visitor<'ast>,
+{
+    if let Some(node) = var_decl.init() {
+        visitor.visit_node(node);
+    }
+    visitor.visit_binding(var_decl.binding());
+}
+
+/// Walks the children of a `WhileLoop` node.
+pub fn walk_while_loop<'ast, V>(visitor: &mut V, while_loop: &'ast WhileLoop)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(while_loop.cond());
+    visitor.visit_node(while_loop.expr());
+}
+
+/// Walks the children of a `With` node.
+pub fn walk_with<'ast, V>(visitor: &mut V, with: &'ast With)
+where
+    V: Visitor<'ast>,
+{
+    visitor.visit_node(with.object());
+    visitor.visit_node(with.body());
+}
+
+/// A mutable visitor of the AST.
+///
+/// Works like [`Visitor`](trait.Visitor.html), but each node is visited through a mutable
+/// reference, so it can be modified or replaced as a whole.
+pub trait VisitorMut: Sized {
+    /// Visits a node.
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node)
+    }
+
+    /// Visits a `StatementList` node.
+    fn visit_statement_list_mut(&mut self, statement_list: &mut StatementList) {
+        walk_statement_list_mut(self, statement_list)
+    }
+
+    /// Visits an `ArrayDecl` node.
+    fn visit_array_decl_mut(&mut self, array_decl: &mut ArrayDecl) {
+        walk_array_decl_mut(self, array_decl)
+    }
+
+    /// Visits an `ArrowFunctionDecl` node.
+    fn visit_arrow_function_decl_mut(&mut self, arrow_function_decl: &mut ArrowFunctionDecl) {
+        walk_arrow_function_decl_mut(self, arrow_function_decl)
+    }
+
+    /// Visits an `Assign` node.
+    fn visit_assign_mut(&mut self, assign: &mut Assign) {
+        walk_assign_mut(self, assign)
+    }
+
+    /// Visits a `BinOp` node.
+    fn visit_bin_op_mut(&mut self, bin_op: &mut BinOp) {
+        walk_bin_op_mut(self, bin_op)
+    }
+
+    /// Visits the `Binding` of a declaration.
+    fn visit_binding_mut(&mut self, binding: &mut Binding) {
+        walk_binding_mut(self, binding)
+    }
+
+    /// Visits a `Block` node.
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    /// Visits a `Break` node.
+    fn visit_break_mut(&mut self, _: &mut Break) {}
+
+    /// Visits a `Call` node.
+    fn visit_call_mut(&mut self, call: &mut Call) {
+        walk_call_mut(self, call)
+    }
+
+    /// Visits a `ConditionalOp` node.
+    fn visit_conditional_op_mut(&mut self, conditional_op: &mut ConditionalOp) {
+        walk_conditional_op_mut(self, conditional_op)
+    }
+
+    /// Visits a `Const` node.
+    fn visit_const_mut(&mut self, _: &mut Const) {}
+
+    /// Visits a `ConstDeclList` node.
+    fn visit_const_decl_list_mut(&mut self, const_decl_list: &mut ConstDeclList) {
+        walk_const_decl_list_mut(self, const_decl_list)
+    }
+
+    /// Visits a `ConstDecl` node.
+    fn visit_const_decl_mut(&mut self, const_decl: &mut ConstDecl) {
+        walk_const_decl_mut(self, const_decl)
+    }
+
+    /// Visits a `Continue` node.
+    fn visit_continue_mut(&mut self, _: &mut Continue) {}
+
+    /// Visits a `DoWhileLoop` node.
+    fn visit_do_while_loop_mut(&mut self, do_while_loop: &mut DoWhileLoop) {
+        walk_do_while_loop_mut(self, do_while_loop)
+    }
+
+    /// Visits a `FunctionDecl` node.
+    fn visit_function_decl_mut(&mut self, function_decl: &mut FunctionDecl) {
+        walk_function_decl_mut(self, function_decl)
+    }
+
+    /// Visits a `FunctionExpr` node.
+    fn visit_function_expr_mut(&mut self, function_expr: &mut FunctionExpr) {
+        walk_function_expr_mut(self, function_expr)
+    }
+
+    /// Visits a `FormalParameter` node.
+    fn visit_formal_parameter_mut(&mut self, formal_parameter: &mut FormalParameter) {
+        walk_formal_parameter_mut(self, formal_parameter)
+    }
+
+    /// Visits a `GetConstField` node.
+    fn visit_get_const_field_mut(&mut self, get_const_field: &mut GetConstField) {
+        walk_get_const_field_mut(self, get_const_field)
+    }
+
+    /// Visits a `GetField` node.
+    fn visit_get_field_mut(&mut self, get_field: &mut GetField) {
+        walk_get_field_mut(self, get_field)
+    }
+
+    /// Visits a `ForLoop` node.
+    fn visit_for_loop_mut(&mut self, for_loop: &mut ForLoop) {
+        walk_for_loop_mut(self, for_loop)
+    }
+
+    /// Visits an `If` node.
+    fn visit_if_mut(&mut self, if_stmt: &mut If) {
+        walk_if_mut(self, if_stmt)
+    }
+
+    /// Visits a `LetDeclList` node.
+    fn visit_let_decl_list_mut(&mut self, let_decl_list: &mut LetDeclList) {
+        walk_let_decl_list_mut(self, let_decl_list)
+    }
+
+    /// Visits a `LetDecl` node.
+    fn visit_let_decl_mut(&mut self, let_decl: &mut LetDecl) {
+        walk_let_decl_mut(self, let_decl)
+    }
+
+    /// Visits an `Identifier` node.
+    fn visit_identifier_mut(&mut self, _: &mut Identifier) {}
+
+    /// Visits a `New` node.
+    fn visit_new_mut(&mut self, new: &mut New) {
+        walk_new_mut(self, new)
+    }
+
+    /// Visits an `Object` node.
+    fn visit_object_mut(&mut self, object: &mut Object) {
+        walk_object_mut(self, object)
+    }
+
+    /// Visits a `PropertyDefinition` node.
+    fn visit_property_definition_mut(&mut self, property_definition: &mut PropertyDefinition) {
+        walk_property_definition_mut(self, property_definition)
+    }
+
+    /// Visits a `RegExpLiteral` node.
+    fn visit_regexp_literal_mut(&mut self, _: &mut RegExpLiteral) {}
+
+    /// Visits a `Return` node.
+    fn visit_return_mut(&mut self, ret: &mut Return) {
+        walk_return_mut(self, ret)
+    }
+
+    /// Visits a `Switch` node.
+    fn visit_switch_mut(&mut self, switch: &mut Switch) {
+        walk_switch_mut(self, switch)
+    }
+
+    /// Visits a `Case` node.
+    fn visit_case_mut(&mut self, case: &mut Case) {
+        walk_case_mut(self, case)
+    }
+
+    /// Visits a `Spread` node.
+    fn visit_spread_mut(&mut self, spread: &mut Spread) {
+        walk_spread_mut(self, spread)
+    }
+
+    /// Visits a `Throw` node.
+    fn visit_throw_mut(&mut self, throw: &mut Throw) {
+        walk_throw_mut(self, throw)
+    }
+
+    /// Visits a `Try` node.
+    fn visit_try_mut(&mut self, try_stmt: &mut Try) {
+        walk_try_mut(self, try_stmt)
+    }
+
+    /// Visits a `Catch` node.
+    fn visit_catch_mut(&mut self, catch: &mut Catch) {
+        walk_catch_mut(self, catch)
+    }
+
+    /// Visits an `UnaryOp` node.
+    fn visit_unary_op_mut(&mut self, unary_op: &mut UnaryOp) {
+        walk_unary_op_mut(self, unary_op)
+    }
+
+    /// Visits a `VarDeclList` node.
+    fn visit_var_decl_list_mut(&mut self, var_decl_list: &mut VarDeclList) {
+        walk_var_decl_list_mut(self, var_decl_list)
+    }
+
+    /// Visits a `VarDecl` node.
+    fn visit_var_decl_mut(&mut self, var_decl: &mut VarDecl) {
+        walk_var_decl_mut(self, var_decl)
+    }
+
+    /// Visits a `WhileLoop` node.
+    fn visit_while_loop_mut(&mut self, while_loop: &mut WhileLoop) {
+        walk_while_loop_mut(self, while_loop)
+    }
+
+    /// Visits a `With` node.
+    fn visit_with_mut(&mut self, with: &mut With) {
+        walk_with_mut(self, with)
+    }
+}
+
+/// Walks the node, visiting it with the method for its type.
+pub fn walk_node_mut<V>(visitor: &mut V, node: &mut Node)
+where
+    V: VisitorMut,
+{
+    match node {
+        Node::ArrayDecl(n) => visitor.visit_array_decl_mut(n),
+        Node::ArrowFunctionDecl(n) => visitor.visit_arrow_function_decl_mut(n),
+        Node::Assign(n) => visitor.visit_assign_mut(n),
+        Node::BinOp(n) => visitor.visit_bin_op_mut(n),
+        Node::Block(n) => visitor.visit_block_mut(n),
+        Node::Break(n) => visitor.visit_break_mut(n),
+        Node::Call(n) => visitor.visit_call_mut(n),
+        Node::ConditionalOp(n) => visitor.visit_conditional_op_mut(n),
+        Node::Const(n) => visitor.visit_const_mut(n),
+        Node::ConstDeclList(n) => visitor.visit_const_decl_list_mut(n),
+        Node::Continue(n) => visitor.visit_continue_mut(n),
+        Node::DoWhileLoop(n) => visitor.visit_do_while_loop_mut(n),
+        Node::FunctionDecl(n) => visitor.visit_function_decl_mut(n),
+        Node::FunctionExpr(n) => visitor.visit_function_expr_mut(n),
+        Node::GetConstField(n) => visitor.visit_get_const_field_mut(n),
+        Node::GetField(n) => visitor.visit_get_field_mut(n),
+        Node::ForLoop(n) => visitor.visit_for_loop_mut(n),
+        Node::If(n) => visitor.visit_if_mut(n),
+        Node::LetDeclList(n) => visitor.visit_let_decl_list_mut(n),
+        Node::Identifier(n) => visitor.visit_identifier_mut(n),
+        Node::New(n) => visitor.visit_new_mut(n),
+        Node::Object(n) => visitor.visit_object_mut(n),
+        Node::RegExpLiteral(n) => visitor.visit_regexp_literal_mut(n),
+        Node::Return(n) => visitor.visit_return_mut(n),
+        Node::Switch(n) => visitor.visit_switch_mut(n),
+        Node::Spread(n) => visitor.visit_spread_mut(n),
+        Node::Throw(n) => visitor.visit_throw_mut(n),
+        Node::Try(n) => visitor.visit_try_mut(n),
+        Node::UnaryOp(n) => visitor.visit_unary_op_mut(n),
+        Node::VarDeclList(n) => visitor.visit_var_decl_list_mut(n),
+        Node::WhileLoop(n) => visitor.visit_while_loop_mut(n),
+        Node::With(n) => visitor.visit_with_mut(n),
+        Node::This | Node::NewTarget => {}
+    }
+}
+
+/// Walks the children of a `StatementList` node.
+pub fn walk_statement_list_mut<V>(visitor: &mut V, statement_list: &mut StatementList)
+where
+    V: VisitorMut,
+{
+    for node in statement_list.statements_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of an `ArrayDecl` node.
+pub fn walk_array_decl_mut<V>(visitor: &mut V, array_decl: &mut ArrayDecl)
+where
+    V: VisitorMut,
+{
+    for node in array_decl.as_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of an `ArrowFunctionDecl` node.
+pub fn walk_arrow_function_decl_mut<V>(visitor: &mut V, arrow_function_decl: &mut ArrowFunctionDecl)
+where
+    V: VisitorMut,
+{
+    for item in arrow_function_decl.params_mut() {
+        visitor.visit_formal_parameter_mut(item);
+    }
+    for node in arrow_function_decl.body_mut().statements_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of an `Assign` node.
+pub fn walk_assign_mut<V>(visitor: &mut V, assign: &mut Assign)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(assign.lhs_mut());
+    visitor.visit_node_mut(assign.rhs_mut());
+}
+
+/// Walks the children of a `BinOp` node.
+pub fn walk_bin_op_mut<V>(visitor: &mut V, bin_op: &mut BinOp)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(bin_op.lhs_mut());
+    visitor.visit_node_mut(bin_op.rhs_mut());
+}
+
+/// Walks the default values of the elements of a `Binding`, and its nested patterns.
+pub fn walk_binding_mut<V>(visitor: &mut V, binding: &mut Binding)
+where
+    V: VisitorMut,
+{
+    if let Binding::Array(pattern) = binding {
+        for element in pattern.elements_mut().iter_mut().flatten() {
+            if let Some(node) = element.init_mut() {
+                visitor.visit_node_mut(node);
+            }
+            visitor.visit_binding_mut(element.target_mut());
+        }
+        if let Some(rest) = pattern.rest_mut() {
+            visitor.visit_binding_mut(rest);
+        }
+    }
+}
+
+/// Walks the children of a `Block` node.
+pub fn walk_block_mut<V>(visitor: &mut V, block: &mut Block)
+where
+    V: VisitorMut,
+{
+    for node in block.statements_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `Call` node.
+pub fn walk_call_mut<V>(visitor: &mut V, call: &mut Call)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(call.expr_mut());
+    for node in call.args_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `ConditionalOp` node.
+pub fn walk_conditional_op_mut<V>(visitor: &mut V, conditional_op: &mut ConditionalOp)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(conditional_op.cond_mut());
+    visitor.visit_node_mut(conditional_op.if_true_mut());
+    visitor.visit_node_mut(conditional_op.if_false_mut());
+}
+
+/// Walks the children of a `ConstDeclList` node.
+pub fn walk_const_decl_list_mut<V>(visitor: &mut V, const_decl_list: &mut ConstDeclList)
+where
+    V: VisitorMut,
+{
+    for item in const_decl_list.as_mut() {
+        visitor.visit_const_decl_mut(item);
+    }
+}
+
+/// Walks the children of a `ConstDecl` node.
+pub fn walk_const_decl_mut<V>(visitor: &mut V, const_decl: &mut ConstDecl)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(const_decl.init_mut());
+    visitor.visit_binding_mut(const_decl.binding_mut());
+}
+
+/// Walks the children of a `DoWhileLoop` node.
+pub fn walk_do_while_loop_mut<V>(visitor: &mut V, do_while_loop: &mut DoWhileLoop)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(do_while_loop.body_mut());
+    visitor.visit_node_mut(do_while_loop.cond_mut());
+}
+
+/// Walks the children of a `FunctionDecl` node.
+pub fn walk_function_decl_mut<V>(visitor: &mut V, function_decl: &mut FunctionDecl)
+where
+    V: VisitorMut,
+{
+    for item in function_decl.parameters_mut() {
+        visitor.visit_formal_parameter_mut(item);
+    }
+    for node in function_decl.body_mut().statements_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `FunctionExpr` node.
+pub fn walk_function_expr_mut<V>(visitor: &mut V, function_expr: &mut FunctionExpr)
+where
+    V: VisitorMut,
+{
+    for item in function_expr.parameters_mut() {
+        visitor.visit_formal_parameter_mut(item);
+    }
+    for node in function_expr.body_mut().statements_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `FormalParameter` node.
+pub fn walk_formal_parameter_mut<V>(visitor: &mut V, formal_parameter: &mut FormalParameter)
+where
+    V: VisitorMut,
+{
+    if let Some(node) = formal_parameter.init_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `GetConstField` node.
+pub fn walk_get_const_field_mut<V>(visitor: &mut V, get_const_field: &mut GetConstField)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(get_const_field.obj_mut());
+}
+
+/// Walks the children of a `GetField` node.
+pub fn walk_get_field_mut<V>(visitor: &mut V, get_field: &mut GetField)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(get_field.obj_mut());
+    visitor.visit_node_mut(get_field.field_mut());
+}
+
+/// Walks the children of a `ForLoop` node.
+pub fn walk_for_loop_mut<V>(visitor: &mut V, for_loop: &mut ForLoop)
+where
+    V: VisitorMut,
+{
+    if let Some(node) = for_loop.init_mut() {
+        visitor.visit_node_mut(node);
+    }
+    if let Some(node) = for_loop.condition_mut() {
+        visitor.visit_node_mut(node);
+    }
+    if let Some(node) = for_loop.final_expr_mut() {
+        visitor.visit_node_mut(node);
+    }
+    visitor.visit_node_mut(for_loop.body_mut());
+}
+
+/// Walks the children of an `If` node.
+pub fn walk_if_mut<V>(visitor: &mut V, if_stmt: &mut If)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(if_stmt.cond_mut());
+    visitor.visit_node_mut(if_stmt.body_mut());
+    if let Some(node) = if_stmt.else_node_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `LetDeclList` node.
+pub fn walk_let_decl_list_mut<V>(visitor: &mut V, let_decl_list: &mut LetDeclList)
+where
+    V: VisitorMut,
+{
+    for item in let_decl_list.as_mut() {
+        visitor.visit_let_decl_mut(item);
+    }
+}
+
+/// Walks the children of a `LetDecl` node.
+pub fn walk_let_decl_mut<V>(visitor: &mut V, let_decl: &mut LetDecl)
+where
+    V: VisitorMut,
+{
+    if let Some(node) = let_decl.init_mut() {
+        visitor.visit_node_mut(node);
+    }
+    visitor.visit_binding_mut(let_decl.binding_mut());
+}
+
+/// Walks the children of a `New` node.
+pub fn walk_new_mut<V>(visitor: &mut V, new: &mut New)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(new.expr_mut());
+    for node in new.args_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of an `Object` node.
+pub fn walk_object_mut<V>(visitor: &mut V, object: &mut Object)
+where
+    V: VisitorMut,
+{
+    for item in object.properties_mut() {
+        visitor.visit_property_definition_mut(item);
+    }
+}
+
+/// Walks the children of a `PropertyDefinition` node.
+pub fn walk_property_definition_mut<V>(
+    visitor: &mut V,
+    property_definition: &mut PropertyDefinition,
+) where
+    V: VisitorMut,
+{
+    match property_definition {
+        PropertyDefinition::IdentifierReference(_) => {}
+        PropertyDefinition::Property(_, node) | PropertyDefinition::SpreadObject(node) => {
+            visitor.visit_node_mut(node)
+        }
+        PropertyDefinition::MethodDefinition(_, _, function) => {
+            visitor.visit_function_expr_mut(function)
+        }
+    }
+}
+
+/// Walks the children of a `Return` node.
+pub fn walk_return_mut<V>(visitor: &mut V, ret: &mut Return)
+where
+    V: VisitorMut,
+{
+    if let Some(node) = ret.expr_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `Switch` node.
+pub fn walk_switch_mut<V>(visitor: &mut V, switch: &mut Switch)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(switch.val_mut());
+    for item in switch.cases_mut() {
+        visitor.visit_case_mut(item);
+    }
+    if let Some(node) = switch.default_mut() {
+        visitor.visit_node_mut(node);
+    }
+}
+
+/// Walks the children of a `Case` node.
+pub fn walk_case_mut<V>(visitor: &mut V, case: &mut Case)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(case.condition_mut());
+    visitor.visit_statement_list_mut(case.body_mut());
+}
+
+/// Walks the children of a `Spread` node.
+pub fn walk_spread_mut<V>(visitor: &mut V, spread: &mut Spread)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(spread.val_mut());
+}
+
+/// Walks the children of a `Throw` node.
+pub fn walk_throw_mut<V>(visitor: &mut V, throw: &mut Throw)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(throw.expr_mut());
+}
+
+/// Walks the children of a `Try` node.
+pub fn walk_try_mut<V>(visitor: &mut V, try_stmt: &mut Try)
+where
+    V: VisitorMut,
+{
+    visitor.visit_block_mut(try_stmt.block_mut());
+    if let Some(item) = try_stmt.catch_mut() {
+        visitor.visit_catch_mut(item);
+    }
+    if let Some(item) = try_stmt.finally_mut() {
+        visitor.visit_block_mut(item);
+    }
+}
+
+/// Walks the children of a `Catch` node.
+pub fn walk_catch_mut<V>(visitor: &mut V, catch: &mut Catch)
+where
+    V: VisitorMut,
+{
+    visitor.visit_block_mut(catch.block_mut());
+}
+
+/// Walks the children of an `UnaryOp` node.
+pub fn walk_unary_op_mut<V>(visitor: &mut V, unary_op: &mut UnaryOp)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(unary_op.target_mut());
+}
+
+/// Walks the children of a `VarDeclList` node.
+pub fn walk_var_decl_list_mut<V>(visitor: &mut V, var_decl_list: &mut VarDeclList)
+where
+    V: VisitorMut,
+{
+    for item in var_decl_list.as_mut() {
+        visitor.visit_var_decl_mut(item);
+    }
+}
+
+/// Walks the children of a `VarDecl` node.
+pub fn walk_var_decl_mut<V>(visitor: &mut V, var_decl: &mut VarDecl)
+where
+    V: VisitorMut,
+{
+    if let Some(node) = var_decl.init_mut() {
+        visitor.visit_node_mut(node);
+    }
+    visitor.visit_binding_mut(var_decl.binding_mut());
+}
+
+/// Walks the children of a `WhileLoop` node.
+pub fn walk_while_loop_mut<V>(visitor: &mut V, while_loop: &mut WhileLoop)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(while_loop.cond_mut());
+    visitor.visit_node_mut(while_loop.expr_mut());
+}
+
+/// Walks the children of a `With` node.
+pub fn walk_with_mut<V>(visitor: &mut V, with: &mut With)
+where
+    V: VisitorMut,
+{
+    visitor.visit_node_mut(with.object_mut());
+    visitor.visit_node_mut(with.body_mut());
+}
@@ -0,0 +1,106 @@
+//! Tests for the AST visitors.
+
+use super::*;
+use crate::syntax::{lexer::Lexer, parser::Parser};
+
+/// Parses the given JavaScript source code.
+fn parse(src: &str) -> StatementList {
+    Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .expect("failed to parse")
+}
+
+/// Collects the names of all the identifiers found in the AST.
+#[derive(Debug, Default)]
+struct IdentifierCollector<'ast> {
+    names: Vec<&'ast str>,
+}
+
+impl<'ast> Visitor<'ast> for IdentifierCollector<'ast> {
+    fn visit_identifier(&mut self, identifier: &'ast Identifier) {
+        self.names.push(identifier.as_ref());
+    }
+}
+
+#[test]
+fn visit_all_identifiers() {
+    let ast = parse(
+        r#"
+        function f(x, y = a) {
+            if (x) { return [y, ...b]; } else { throw c; }
+        }
+        let obj = { e: g(h), method() { return i.j; } };
+        for (let k = 0; k < l; k++) {
+            try { m[n]; } catch (err) { o } finally { p ? q : r }
+        }
+        "#,
+    );
+
+    let mut collector = IdentifierCollector::default();
+    collector.visit_statement_list(&ast);
+
+    let mut names = collector.names;
+    names.sort();
+    assert_eq!(
+        names,
+        vec!["a", "b", "c", "g", "h", "i", "k", "k", "l", "m", "n", "o", "p", "q", "r", "x", "y"]
+    );
+}
+
+/// Stops walking into nested functions.
+#[derive(Debug, Default)]
+struct TopLevelCalls {
+    calls: usize,
+}
+
+impl<'ast> Visitor<'ast> for TopLevelCalls {
+    fn visit_call(&mut self, call: &'ast Call) {
+        self.calls += 1;
+        walk_call(self, call);
+    }
+
+    fn visit_function_decl(&mut self, _: &'ast FunctionDecl) {}
+}
+
+#[test]
+fn override_walk() {
+    let ast = parse("a(b()); function f() { c(); d(); }");
+
+    let mut visitor = TopLevelCalls::default();
+    visitor.visit_statement_list(&ast);
+    assert_eq!(visitor.calls, 2);
+}
+
+/// Replaces every `missing` identifier with `void 0`, and renames the rest.
+#[derive(Debug)]
+struct Renamer;
+
+impl VisitorMut for Renamer {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        if let Node::Identifier(ident) = node {
+            if ident.as_ref() == "missing" {
+                *node = Node::from(UnaryOp::new(
+                    crate::syntax::ast::op::UnaryOp::Void,
+                    Const::from(0),
+                ));
+                return;
+            }
+        }
+        walk_node_mut(self, node);
+    }
+
+    fn visit_identifier_mut(&mut self, identifier: &mut Identifier) {
+        *identifier = Identifier::from(format!("_{}", identifier.as_ref()));
+    }
+}
+
+#[test]
+fn visit_mut_replaces_nodes() {
+    let mut ast = parse("let a = b + missing; while (c) { d(missing, e) }");
+    Renamer.visit_statement_list_mut(&mut ast);
+
+    assert_eq!(
+        ast,
+        parse("let a = _b + void 0; while (_c) { _d(void 0, _e) }")
+    );
+}
@@ -0,0 +1,131 @@
+//! Character cursor implementation for the lexer.
+
+use super::LexerError;
+use std::{
+    collections::VecDeque,
+    io::{self, Bytes, Read},
+};
+
+/// Character cursor.
+///
+/// This internal structure decodes UTF-8 characters from the input lazily, keeping only the
+/// characters that have been peeked but not consumed in memory.
+#[derive(Debug)]
+pub(super) struct Cursor<R> {
+    /// The bytes being input.
+    bytes: Bytes<R>,
+    /// Characters that have been decoded, but not consumed yet.
+    peeked: VecDeque<char>,
+    /// The first I/O or decoding error found in the input, if any.
+    error: Option<LexerError>,
+    /// Whether reading the input has failed.
+    failed: bool,
+}
+
+impl<R> Cursor<R>
+where
+    R: Read,
+{
+    /// Creates a new cursor.
+    pub(super) fn new(input: R) -> Self {
+        Self {
+            bytes: input.bytes(),
+            peeked: VecDeque::new(),
+            error: None,
+            failed: false,
+        }
+    }
+
+    /// Moves the cursor to the next character and returns it.
+    pub(super) fn next(&mut self) -> Option<char> {
+        if let Some(ch) = self.peeked.pop_front() {
+            Some(ch)
+        } else {
+            self.decode()
+        }
+    }
+
+    /// Peeks the next character without moving the cursor.
+    pub(super) fn peek(&mut self) -> Option<char> {
+        self.peek_nth(0)
+    }
+
+    /// Peeks the character `skip` positions after the next one, without moving the cursor.
+    pub(super) fn peek_nth(&mut self, skip: usize) -> Option<char> {
+        while self.peeked.len() <= skip {
+            let ch = self.decode()?;
+            self.peeked.push_back(ch);
+        }
+
+        self.peeked.get(skip).copied()
+    }
+
+    /// Takes the error found while reading the input, if any.
+    ///
+    /// Once an error has been found, the cursor behaves as if the input had ended.
+    pub(super) fn take_error(&mut self) -> Option<LexerError> {
+        self.error.take()
+    }
+
+    /// Reads a single byte from the input, recording any error.
+    fn read_byte(&mut self) -> Option<u8> {
+        match self.bytes.next()? {
+            Ok(byte) => Some(byte),
+            Err(e) => {
+                self.fail(e);
+                None
+            }
+        }
+    }
+
+    /// Decodes the next UTF-8 character from the input.
+    fn decode(&mut self) -> Option<char> {
+        if self.failed {
+            return None;
+        }
+
+        let first = self.read_byte()?;
+        let len = match first {
+            0x00..=0x7F => return Some(char::from(first)),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => {
+                self.fail(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                ));
+                return None;
+            }
+        };
+
+        let mut buf = [first, 0, 0, 0];
+        for byte in buf.iter_mut().take(len).skip(1) {
+            if let Some(next) = self.read_byte() {
+                *byte = next;
+            } else {
+                self.fail(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "stream ended in the middle of a UTF-8 character",
+                ));
+                return None;
+            }
+        }
+
+        match std::str::from_utf8(&buf[..len]) {
+            Ok(s) => s.chars().next(),
+            Err(e) => {
+                self.fail(io::Error::new(io::ErrorKind::InvalidData, e));
+                None
+            }
+        }
+    }
+
+    /// Records an input error, if none has been recorded yet.
+    fn fail(&mut self, err: io::Error) {
+        if !self.failed {
+            self.failed = true;
+            self.error = Some(LexerError::new(format!("could not read input: {}", err)));
+        }
+    }
+}
@@ -2,24 +2,25 @@
 //!
 //! The Lexer splits its input source code into a sequence of input elements called tokens, represented by the [Token](../ast/token/struct.Token.html) structure.
 //! It also removes whitespace and comments and attaches them to the next token.
+//!
+//! The input is read lazily from any [`Read`][std::io::Read] implementor, and tokens are produced
+//! on demand through the [`Iterator`] implementation of the [`Lexer`].
 
+mod cursor;
 #[cfg(test)]
 mod tests;
 
-use crate::builtins::BigInt;
+use crate::builtins::{string::JsString, BigInt};
 use crate::{
     syntax::ast::{
-        token::{NumericLiteral, Token, TokenKind},
-        Position, Punctuator, Span,
+        token::{CommentKind, NumericLiteral, Token, TokenKind},
+        Keyword, Position, Punctuator, Span,
     },
     BoaProfiler,
 };
-use std::{
-    char::{decode_utf16, from_u32},
-    error, fmt,
-    iter::Peekable,
-    str::{Chars, FromStr},
-};
+use cursor::Cursor;
+use std::{char::from_u32, collections::VecDeque, error, fmt, io::Read, str::FromStr};
+use unicode_xid::UnicodeXID;
 
 /// `vop` tests the next token to see if we're on an assign operation of just a plain binary operation.
 ///
@@ -29,7 +30,7 @@ macro_rules! vop {
         let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
         match preview {
             '=' => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $assign_op
             }
@@ -40,12 +41,12 @@ macro_rules! vop {
         let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
         match preview {
             '=' => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $assign_op
             },
             $($case => {
-                $this.next();
+                $this.next_char();
                 $this.next_column();
                 $block
             })+,
@@ -56,7 +57,7 @@ macro_rules! vop {
         let preview = $this.preview_next().ok_or_else(|| LexerError::new("could not preview next value"))?;
         match preview {
             $($case => {
-                $this.next()?;
+                $this.next_char()?;
                 $this.next_column();
                 $block
             })+,
@@ -117,31 +118,98 @@ impl error::Error for LexerError {
     }
 }
 
+/// The state of the directive prologue of a script, while it's being lexed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Prologue {
+    /// Waiting for the string literal of a directive.
+    Directive,
+    /// A string literal has been lexed, and is a directive if a semicolon or a line terminator
+    /// follows. Holds whether it's a `"use strict"` directive.
+    EndOfDirective(bool),
+    /// The prologue is over.
+    Done,
+}
+
 /// A lexical analyzer for JavaScript source code.
+///
+/// The lexer is an [`Iterator`] of tokens, reading its input only as far as needed to produce
+/// the next one.
 #[derive(Debug)]
-pub struct Lexer<'a> {
-    /// The list of tokens generated so far.
+pub struct Lexer<R> {
+    /// The list of tokens generated by [`lex()`](#method.lex).
     ///
     /// This field is public so you can use them once lexing has finished.
     pub tokens: Vec<Token>,
     /// The current position in the source code.
     position: Position,
-    /// The full Peekable buffer, an array of [Char]s
-    buffer: Peekable<Chars<'a>>,
+    /// The input, decoded character by character.
+    buffer: Cursor<R>,
+    /// Tokens that have been lexed, but not yielded yet.
+    pending: VecDeque<Token>,
+    /// Whether the input has been exhausted, or an error has been found.
+    finished: bool,
+    /// Whether a `/` starts a regular expression literal instead of a division, given the
+    /// previous token.
+    regex_allowed: bool,
+    /// Whether the source code is strict mode code.
+    strict_mode: bool,
+    /// How far the directive prologue of the script has been lexed.
+    prologue: Prologue,
+    /// Whether comments are emitted as tokens instead of being discarded.
+    preserve_comments: bool,
 }
 
-impl<'a> Lexer<'a> {
-    /// Returns a Lexer with a buffer inside
+impl<R> Lexer<R>
+where
+    R: Read,
+{
+    /// Returns a Lexer reading its source code from the given input.
     ///
-    /// The buffer needs to have a lifetime as long as the Lexer instance itself
-    pub fn new(buffer: &'a str) -> Lexer<'a> {
-        Lexer {
+    /// The input is read on demand, so it's recommended to wrap unbuffered readers (like files)
+    /// in a [`BufReader`](std::io::BufReader).
+    pub fn new(reader: R) -> Self {
+        Self {
             tokens: Vec::new(),
             position: Position::new(1, 1),
-            buffer: buffer.chars().peekable(),
+            buffer: Cursor::new(reader),
+            pending: VecDeque::new(),
+            finished: false,
+            regex_allowed: true,
+            strict_mode: false,
+            prologue: Prologue::Directive,
+            preserve_comments: false,
         }
     }
 
+    /// Checks if comments are emitted as tokens.
+    pub fn preserve_comments(&self) -> bool {
+        self.preserve_comments
+    }
+
+    /// Sets whether comments are emitted as [`Comment`](TokenKind::Comment) tokens, with their
+    /// spans, instead of being discarded.
+    ///
+    /// This is useful for tooling that needs to reproduce the source code, like formatters.
+    /// The parser ignores comment tokens.
+    pub fn set_preserve_comments(&mut self, preserve_comments: bool) {
+        self.preserve_comments = preserve_comments;
+    }
+
+    /// Checks if the lexer is lexing strict mode code.
+    pub fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    /// Sets whether the source code is strict mode code.
+    ///
+    /// Some literals, like legacy octal numbers, are syntax errors in strict mode code. A
+    /// `"use strict"` directive in the prologue of the script turns strict mode on by itself, but
+    /// the directives of function bodies are only found by the parser, after their tokens have
+    /// been lexed.
+    pub fn set_strict_mode(&mut self, strict_mode: bool) {
+        self.strict_mode = strict_mode;
+    }
+
     /// Push a token onto the token queue.
     fn push_token(&mut self, tk: TokenKind, start: Position) {
         let end = if let TokenKind::LineTerminator = tk {
@@ -152,7 +220,47 @@ impl<'a> Lexer<'a> {
                 self.position.column_number() - 1,
             )
         };
-        self.tokens.push(Token::new(tk, Span::new(start, end)))
+        match tk {
+            TokenKind::LineTerminator | TokenKind::Comment(_, _) => {}
+            _ => self.regex_allowed = regex_allowed_after(&tk),
+        }
+        self.update_prologue(&tk, start, end);
+        self.pending
+            .push_back(Token::new(tk, Span::new(start, end)))
+    }
+
+    /// Follows the directive prologue of the script, turning strict mode on once a
+    /// `"use strict"` directive has been lexed.
+    ///
+    /// A directive ends with a semicolon or a line terminator, so that the literals after it are
+    /// already lexed in strict mode.
+    ///
+    /// More information:
+    ///  - [ECMAScript specification][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-directive-prologues-and-the-use-strict-directive
+    fn update_prologue(&mut self, tk: &TokenKind, start: Position, end: Position) {
+        self.prologue = match (self.prologue, tk) {
+            (Prologue::Done, _) | (_, TokenKind::Comment(_, _)) => return,
+            (Prologue::Directive, TokenKind::LineTerminator) => Prologue::Directive,
+            (Prologue::Directive, TokenKind::StringLiteral(ref string)) => {
+                // The directive must be exactly `"use strict"` or `'use strict'`, without escapes.
+                let exact = start.line_number() == end.line_number()
+                    && end.column_number() - start.column_number() == 11;
+                Prologue::EndOfDirective(exact && *string == "use strict")
+            }
+            (Prologue::EndOfDirective(use_strict), TokenKind::LineTerminator)
+            | (
+                Prologue::EndOfDirective(use_strict),
+                TokenKind::Punctuator(Punctuator::Semicolon),
+            ) => {
+                if use_strict {
+                    self.strict_mode = true;
+                }
+                Prologue::Directive
+            }
+            _ => Prologue::Done,
+        };
     }
 
     /// Push a punctuation token
@@ -189,38 +297,24 @@ impl<'a> Lexer<'a> {
         self.position = pos;
     }
 
-    /// Changes the current position by advancing the given number of lines.
-    fn move_lines(&mut self, lines: u32) {
-        let pos = Position::new(self.position.line_number() + lines, 1);
-        self.position = pos;
-    }
-
-    /// next fetches the next token and return it, or a LexerError if there are no more.
-    fn next(&mut self) -> char {
+    /// Fetches the next character and returns it.
+    ///
+    /// It panics if there are no more characters, so `preview_next()` should be used before.
+    fn next_char(&mut self) -> char {
         self.buffer.next().expect(
             "No more more characters to consume from input stream, \
-             use preview_next() first to check before calling next()",
+             use preview_next() first to check before calling next_char()",
         )
     }
 
     /// Preview the next character but don't actually increment
     fn preview_next(&mut self) -> Option<char> {
-        self.buffer.peek().copied()
+        self.buffer.peek()
     }
 
     /// Preview a char x indexes further in buf, without incrementing
     fn preview_multiple_next(&mut self, nb_next: usize) -> Option<char> {
-        let mut next_peek = None;
-
-        for (i, x) in self.buffer.clone().enumerate() {
-            if i >= nb_next {
-                break;
-            }
-
-            next_peek = Some(x);
-        }
-
-        next_peek
+        self.buffer.peek_nth(nb_next.saturating_sub(1))
     }
 
     /// Utility Function, while ``f(char)`` is true, read chars and move curser.
@@ -230,10 +324,10 @@ impl<'a> Lexer<'a> {
         F: FnMut(char) -> bool,
     {
         let mut s = String::new();
-        while self.buffer.peek().is_some()
+        while self.preview_next().is_some()
             && f(self.preview_next().expect("Could not preview next value"))
         {
-            s.push(self.next());
+            s.push(self.next_char());
         }
 
         Ok(s)
@@ -257,9 +351,7 @@ impl<'a> Lexer<'a> {
     /// [spec]: https://tc39.es/ecma262/#sec-literals-numeric-literals
     fn check_after_numeric_literal(&mut self) -> Result<(), LexerError> {
         match self.preview_next() {
-            Some(ch)
-                if ch.is_ascii_alphabetic() || ch == '$' || ch == '_' || ch.is_ascii_digit() =>
-            {
+            Some(ch) if is_identifier_start(ch) || ch == '\\' || ch.is_ascii_digit() => {
                 Err(LexerError::new("NumericLiteral token must not be followed by IdentifierStart nor DecimalDigit characters"))
             }
             Some(_) => Ok(()),
@@ -267,89 +359,99 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Reads the digits of a numeric literal in the given base into `buf`, skipping the numeric
+    /// separators (`_`) found between them.
+    ///
+    /// `after_digit` tells if the character right before the current position is a digit of the
+    /// same literal, since a separator can only appear between two digits.
+    fn take_digits(
+        &mut self,
+        base: u32,
+        buf: &mut String,
+        mut after_digit: bool,
+    ) -> Result<(), LexerError> {
+        while let Some(ch) = self.preview_next() {
+            if ch == '_' {
+                let before_digit = self
+                    .preview_multiple_next(2)
+                    .map_or(false, |next| next.is_digit(base));
+                if !after_digit || !before_digit {
+                    return Err(LexerError::new(format!(
+                        "numeric separators are only allowed between digits, at line {}, column {}",
+                        self.position.line_number(),
+                        self.position.column_number() + 1
+                    )));
+                }
+                after_digit = false;
+            } else if ch.is_digit(base) {
+                buf.push(ch);
+                after_digit = true;
+            } else {
+                break;
+            }
+            self.next_char();
+            self.next_column();
+        }
+
+        Ok(())
+    }
+
     /// Lexes a numerical literal.
     ///
+    /// Numeric separators are removed from the digits before converting them, and integers that
+    /// don't fit in an `i32` are converted to the nearest `f64`.
+    ///
     /// More information:
     ///  - [ECMAScript Specification][spec]
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-literals-numeric-literals
-    fn reed_numerical_literal(&mut self, ch: char) -> Result<(), LexerError> {
+    fn reed_numerical_literal(&mut self, ch: char, start_pos: Position) -> Result<(), LexerError> {
         /// This is a helper structure
         ///
         /// This structure helps with identifying what numerical type it is and what base is it.
         #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         enum NumericKind {
             Rational,
-            Integer(u8),
-            BigInt(u8),
+            Integer(u32),
+            BigInt(u32),
         }
 
-        impl NumericKind {
-            /// Get the base of the number kind.
-            fn base(self) -> u32 {
-                match self {
-                    Self::Rational => 10,
-                    Self::Integer(base) => base as u32,
-                    Self::BigInt(base) => base as u32,
-                }
-            }
-
-            /// Converts `self` to BigInt kind.
-            fn to_bigint(self) -> Self {
-                match self {
-                    Self::Rational => unreachable!("can not convert rational number to BigInt"),
-                    Self::Integer(base) => Self::BigInt(base),
-                    Self::BigInt(base) => Self::BigInt(base),
-                }
-            }
-        }
-
-        // TODO: Setup strict mode.
-        let strict_mode = false;
-
-        let mut buf = ch.to_string();
+        let mut buf = String::new();
         let mut kind = NumericKind::Integer(10);
-        let start_pos = self.position;
+
         if ch == '0' {
-            match self.preview_next() {
-                None => {
-                    self.next_column();
-                    self.push_token(
-                        TokenKind::NumericLiteral(NumericLiteral::Integer(0)),
-                        start_pos,
-                    );
-                    return Ok(());
-                }
-                Some('x') | Some('X') => {
-                    self.next();
-                    self.next_column();
-                    kind = NumericKind::Integer(16);
-                }
-                Some('o') | Some('O') => {
-                    self.next();
-                    self.next_column();
-                    kind = NumericKind::Integer(8);
-                }
-                Some('b') | Some('B') => {
-                    self.next();
-                    self.next_column();
-                    kind = NumericKind::Integer(2);
+            let base = match self.preview_next() {
+                Some('x') | Some('X') => Some(16),
+                Some('o') | Some('O') => Some(8),
+                Some('b') | Some('B') => Some(2),
+                _ => None,
+            };
+
+            if let Some(base) = base {
+                self.next_char();
+                self.next_column();
+                self.take_digits(base, &mut buf, false)?;
+                if buf.is_empty() {
+                    return Err(LexerError::new(format!(
+                        "expected digits after the numeric literal prefix at line {}, column {}",
+                        start_pos.line_number(),
+                        start_pos.column_number()
+                    )));
                 }
-                Some(ch) if ch.is_ascii_digit() => {
-                    let mut is_implicit_octal = true;
-                    while let Some(ch) = self.preview_next() {
-                        if !ch.is_ascii_digit() {
-                            break;
-                        } else if !ch.is_digit(8) {
-                            is_implicit_octal = false;
-                        }
-                        buf.push(self.next());
-                    }
-                    if !strict_mode {
-                        if is_implicit_octal {
-                            kind = NumericKind::Integer(8);
-                        }
-                    } else {
+                kind = if self.next_is('n') {
+                    NumericKind::BigInt(base)
+                } else {
+                    NumericKind::Integer(base)
+                };
+            } else {
+                buf.push('0');
+                let digits = self.take_char_while(|ch| ch.is_ascii_digit())?;
+                if !digits.is_empty() {
+                    self.move_columns(digits.len() as u32);
+                    buf.push_str(&digits);
+
+                    let is_implicit_octal = digits.chars().all(|ch| ch.is_digit(8));
+                    if self.strict_mode {
                         return Err(if is_implicit_octal {
                             LexerError::new(
                                 "Implicit octal literals are not allowed in strict mode.",
@@ -360,79 +462,46 @@ impl<'a> Lexer<'a> {
                             )
                         });
                     }
+                    if is_implicit_octal {
+                        kind = NumericKind::Integer(8);
+                    } else if self.preview_next() == Some('n') {
+                        return Err(LexerError::new("BigInt literals can't have leading zeros."));
+                    }
                 }
-                Some(_) => {}
-            }
-        }
-
-        while let Some(ch) = self.preview_next() {
-            if !ch.is_digit(kind.base()) {
-                break;
             }
-            buf.push(self.next());
+        } else {
+            buf.push(ch);
+            self.take_digits(10, &mut buf, true)?;
         }
 
-        if self.next_is('n') {
-            kind = kind.to_bigint();
-        }
+        if kind == NumericKind::Integer(10) {
+            if self.next_is('n') {
+                kind = NumericKind::BigInt(10);
+            } else {
+                if self.next_is('.') {
+                    kind = NumericKind::Rational;
+                    buf.push('.');
+                    self.take_digits(10, &mut buf, false)?;
+                }
 
-        if let NumericKind::Integer(10) = kind {
-            'digitloop: while let Some(ch) = self.preview_next() {
-                match ch {
-                    '.' => loop {
-                        kind = NumericKind::Rational;
-                        buf.push(self.next());
-
-                        let c = match self.preview_next() {
-                            Some(ch) => ch,
-                            None => break,
-                        };
-
-                        match c {
-                            'e' | 'E' => {
-                                match self
-                                    .preview_multiple_next(2)
-                                    .unwrap_or_default()
-                                    .to_digit(10)
-                                {
-                                    Some(0..=9) | None => {
-                                        buf.push(self.next());
-                                    }
-                                    _ => {
-                                        break 'digitloop;
-                                    }
-                                }
-                            }
-                            _ => {
-                                if !c.is_digit(10) {
-                                    break 'digitloop;
-                                }
-                            }
-                        }
-                    },
-                    'e' | 'E' => {
-                        kind = NumericKind::Rational;
-                        match self
-                            .preview_multiple_next(2)
-                            .unwrap_or_default()
-                            .to_digit(10)
-                        {
-                            Some(0..=9) | None => {
-                                buf.push(self.next());
-                            }
-                            _ => {
-                                break;
-                            }
-                        }
-                        buf.push(self.next());
-                    }
-                    '+' | '-' => {
-                        break;
+                if let Some('e') | Some('E') = self.preview_next() {
+                    kind = NumericKind::Rational;
+                    buf.push(self.next_char());
+                    self.next_column();
+                    if let Some('+') | Some('-') = self.preview_next() {
+                        buf.push(self.next_char());
+                        self.next_column();
                     }
-                    _ if ch.is_digit(10) => {
-                        buf.push(self.next());
+
+                    let len = buf.len();
+                    self.take_digits(10, &mut buf, false)?;
+                    if buf.len() == len {
+                        return Err(LexerError::new(format!(
+                            "expected exponent digits at line {}, column {}",
+                            self.position.line_number(),
+                            self.position.column_number() + 1
+                        )));
                     }
-                    _ => break,
                 }
             }
         }
@@ -440,409 +509,713 @@ impl<'a> Lexer<'a> {
         self.check_after_numeric_literal()?;
 
         let num = match kind {
-                NumericKind::BigInt(base) => {
-                    NumericLiteral::BigInt(
-                        BigInt::from_string_radix(&buf, base as u32).expect("Could not conver to BigInt")
-                        )
-                }
-                NumericKind::Rational /* base: 10 */ => {
+            NumericKind::BigInt(base) => NumericLiteral::BigInt(
+                BigInt::from_string_radix(&buf, base).expect("Could not conver to BigInt"),
+            ),
+            NumericKind::Rational /* base: 10 */ => NumericLiteral::Rational(
+                f64::from_str(&buf).map_err(|_| LexerError::new("Could not convert value to f64"))?,
+            ),
+            NumericKind::Integer(base) => {
+                if let Ok(num) = i32::from_str_radix(&buf, base) {
+                    NumericLiteral::Integer(num)
+                } else if base == 10 {
                     NumericLiteral::Rational(
                         f64::from_str(&buf)
                             .map_err(|_| LexerError::new("Could not convert value to f64"))?,
                     )
+                } else {
+                    NumericLiteral::Rational(power_of_two_radix_to_f64(&buf, base))
                 }
-                NumericKind::Integer(base) => {
-                    if let Ok(num) = i32::from_str_radix(&buf, base as u32) {
-                        NumericLiteral::Integer(
-                            num
-                        )
-                    } else {
-                        let b = f64::from(base);
-                        let mut result = 0.0_f64;
-                        for c in buf.chars() {
-                            let digit = f64::from(c.to_digit(base as u32).unwrap());
-                            result = result * b + digit;
-                        }
+            }
+        };
 
-                        NumericLiteral::Rational(result)
-                    }
+        self.push_token(TokenKind::NumericLiteral(num), start_pos);
+
+        Ok(())
+    }
 
+    /// Lexes an identifier name, which can be an identifier, a keyword or a literal.
+    ///
+    /// Identifiers can contain any Unicode `ID_Start` and `ID_Continue` characters, as well as
+    /// Unicode escape sequences. Reserved words are checked once the escapes are decoded, since
+    /// they must not contain any escape sequence.
+    ///
+    /// More information:
+    ///  - [ECMAScript Specification][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-names-and-keywords
+    fn read_identifier_name(&mut self, ch: char, start_pos: Position) -> Result<(), LexerError> {
+        let mut escaped = false;
+        let first = if ch == '\\' {
+            escaped = true;
+            self.read_identifier_escape(start_pos)?
+        } else {
+            ch
+        };
+        if !is_identifier_start(first) {
+            return Err(LexerError::new(format!(
+                "invalid identifier start '{}' at line {}, column {}",
+                first,
+                start_pos.line_number(),
+                start_pos.column_number()
+            )));
+        }
+
+        let mut buf = first.to_string();
+        while let Some(ch) = self.preview_next() {
+            let escape_pos = self.position;
+            let part = if ch == '\\' {
+                self.next_char();
+                self.next_column();
+                escaped = true;
+                self.read_identifier_escape(escape_pos)?
+            } else if is_identifier_part(ch) {
+                self.next_char();
+                self.next_column();
+                ch
+            } else {
+                break;
+            };
+
+            if !is_identifier_part(part) {
+                return Err(LexerError::new(format!(
+                    "invalid identifier part '{}' at line {}, column {}",
+                    part,
+                    escape_pos.line_number(),
+                    escape_pos.column_number()
+                )));
+            }
+            buf.push(part);
+        }
+
+        let tk = match buf.as_str() {
+            "true" => TokenKind::BooleanLiteral(true),
+            "false" => TokenKind::BooleanLiteral(false),
+            "null" => TokenKind::NullLiteral,
+            "NaN" => TokenKind::NumericLiteral(NumericLiteral::Rational(f64::NAN)),
+            slice => {
+                if let Ok(keyword) = FromStr::from_str(slice) {
+                    TokenKind::Keyword(keyword)
+                } else {
+                    TokenKind::identifier(slice)
+                }
+            }
+        };
+
+        if escaped {
+            if let TokenKind::Keyword(_) | TokenKind::BooleanLiteral(_) | TokenKind::NullLiteral =
+                tk
+            {
+                return Err(LexerError::new(format!(
+                    "reserved word `{}` must not contain escaped characters at line {}, column {}",
+                    buf,
+                    start_pos.line_number(),
+                    start_pos.column_number()
+                )));
+            }
+        }
+
+        self.push_token(tk, start_pos);
+        Ok(())
+    }
+
+    /// Reads a Unicode escape sequence in an identifier name, right after the backslash.
+    ///
+    /// Both the `\uXXXX` and the `\u{X...X}` forms are accepted.
+    ///
+    /// More information:
+    ///  - [ECMAScript Specification][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#prod-IdentifierStart
+    fn read_identifier_escape(&mut self, start_pos: Position) -> Result<char, LexerError> {
+        let invalid_escape = || {
+            LexerError::new(format!(
+                "invalid Unicode escape sequence in identifier at line {}, column {}",
+                start_pos.line_number(),
+                start_pos.column_number()
+            ))
+        };
+
+        if !self.next_is('u') {
+            return Err(invalid_escape());
+        }
+
+        let digits = if self.next_is('{') {
+            let digits = self.take_char_while(|ch| ch.is_ascii_hexdigit())?;
+            self.move_columns(digits.len() as u32);
+            if digits.is_empty() || !self.next_is('}') {
+                return Err(invalid_escape());
+            }
+            digits
+        } else {
+            let mut digits = String::with_capacity(4);
+            for _ in 0..4 {
+                match self.preview_next() {
+                    Some(ch) if ch.is_ascii_hexdigit() => digits.push(self.next_char()),
+                    _ => return Err(invalid_escape()),
+                }
+            }
+            self.move_columns(4);
+            digits
+        };
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(from_u32)
+            .ok_or_else(invalid_escape)
+    }
+
+    /// Lexes a regular expression literal, right after the opening slash.
+    ///
+    /// More information:
+    ///  - [ECMAScript Specification][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-literals-regular-expression-literals
+    fn read_regex_literal(&mut self, start_pos: Position) -> Result<(), LexerError> {
+        let mut body = String::new();
+        // a `/` inside a class (`[...]`) does not end the literal
+        let mut in_class = false;
+        loop {
+            let ch = match self.preview_next() {
+                // newline/eof not allowed in regex literal
+                None | Some('\n') | Some('\r') | Some('\u{2028}') | Some('\u{2029}') => {
+                    return Err(LexerError::new(format!(
+                        "unterminated regular expression literal at line {}, column {}",
+                        start_pos.line_number(),
+                        start_pos.column_number()
+                    )));
                 }
+                Some(ch) => ch,
             };
+            self.next_char();
+            self.next_column();
 
-        self.move_columns(buf.len() as u32);
-        self.push_token(TokenKind::NumericLiteral(num), start_pos);
+            match ch {
+                '/' if !in_class => break,
+                '[' => in_class = true,
+                ']' => in_class = false,
+                '\\' => {
+                    body.push(ch);
+                    match self.preview_next() {
+                        // newline/eof not allowed in regex literal
+                        None | Some('\n') | Some('\r') | Some('\u{2028}') | Some('\u{2029}') => {
+                            continue;
+                        }
+                        Some(_) => {
+                            body.push(self.next_char());
+                            self.next_column();
+                            continue;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            body.push(ch);
+        }
+
+        // body was parsed, now look for flags
+        let flags = self.take_char_while(is_identifier_part)?;
+        self.move_columns(flags.chars().count() as u32);
+        self.push_token(
+            TokenKind::regular_expression_literal(body, flags.parse()?),
+            start_pos,
+        );
 
         Ok(())
     }
 
     /// Runs the lexer until completion, returning a [LexerError] if there's a syntax issue, or an empty unit result
     ///
+    /// The generated tokens are stored in the `tokens` field.
+    ///
     /// # Example
     ///
     /// ```
     /// # use boa::syntax::lexer::{LexerError, Lexer};
     /// fn main() -> Result<(), LexerError> {
     ///     let buffer = String::from("Hello World");
-    ///     let mut lexer = Lexer::new(&buffer);
+    ///     let mut lexer = Lexer::new(buffer.as_bytes());
     ///     lexer.lex()
     /// }
     /// ```
     pub fn lex(&mut self) -> Result<(), LexerError> {
         let _timer = BoaProfiler::global().start_event("lex", "lexing");
-        loop {
-            // Check if we've reached the end
-            if self.preview_next().is_none() {
-                return Ok(());
-            }
-            let start_pos = self.position;
-            self.next_column();
-            let ch = self.next();
-            match ch {
-                '"' | '\'' => {
-                    let mut buf = String::new();
-                    loop {
-                        if self.preview_next().is_none() {
-                            return Err(LexerError::new("Unterminated String"));
+        while let Some(token) = Iterator::next(self) {
+            let token = token?;
+            self.tokens.push(token);
+        }
+
+        Ok(())
+    }
+
+    /// Lexes the next input element, which might not produce any token (whitespace, comments).
+    ///
+    /// Returns `false` if the end of the input has been reached.
+    fn lex_next(&mut self) -> Result<bool, LexerError> {
+        let lexed = self.lex_input_element();
+        if let Some(err) = self.buffer.take_error() {
+            return Err(err);
+        }
+        lexed
+    }
+
+    /// Lexes the next input element, without checking for input errors.
+    fn lex_input_element(&mut self) -> Result<bool, LexerError> {
+        // Check if we've reached the end
+        if self.preview_next().is_none() {
+            return Ok(false);
+        }
+        let start_pos = self.position;
+        self.next_column();
+        let ch = self.next_char();
+        match ch {
+            '"' | '\'' => {
+                let mut buf: Vec<u16> = Vec::new();
+                // The number of source characters that are not counted by the escape sequences.
+                let mut str_length: u32 = 0;
+                loop {
+                    if self.preview_next().is_none() {
+                        return Err(LexerError::new("Unterminated String"));
+                    }
+                    match self.next_char() {
+                        '\'' if ch == '\'' => {
+                            break;
                         }
-                        match self.next() {
-                            '\'' if ch == '\'' => {
-                                break;
-                            }
-                            '"' if ch == '"' => {
-                                break;
+                        '"' if ch == '"' => {
+                            break;
+                        }
+                        '\\' => {
+                            if self.preview_next().is_none() {
+                                return Err(LexerError::new("Unterminated String"));
                             }
-                            '\\' => {
-                                if self.preview_next().is_none() {
-                                    return Err(LexerError::new("Unterminated String"));
-                                }
-                                let escape_pos = self.position;
-                                let escape = self.next();
-                                if escape != '\n' {
-                                    let escaped_ch = match escape {
-                                        'n' => '\n',
-                                        'r' => '\r',
-                                        't' => '\t',
-                                        'b' => '\x08',
-                                        'f' => '\x0c',
-                                        '0' => '\0',
-                                        'x' => {
-                                            let mut nums = String::with_capacity(2);
-                                            for _ in 0_u8..2 {
-                                                if self.preview_next().is_none() {
-                                                    return Err(LexerError::new("Unterminated String"));
-                                                }
-                                                nums.push(self.next());
+                            let escape_pos = self.position;
+                            let escape = self.next_char();
+                            str_length += 2;
+                            if escape != '\n' {
+                                let escaped_ch = match escape {
+                                    'n' => '\n',
+                                    'r' => '\r',
+                                    't' => '\t',
+                                    'b' => '\x08',
+                                    'f' => '\x0c',
+                                    '0' => '\0',
+                                    'x' => {
+                                        let mut nums = String::with_capacity(2);
+                                        for _ in 0_u8..2 {
+                                            if self.preview_next().is_none() {
+                                                return Err(LexerError::new("Unterminated String"));
                                             }
-                                            self.move_columns(2);
-                                            let as_num = match u64::from_str_radix(&nums, 16) {
+                                            nums.push(self.next_char());
+                                        }
+                                        self.move_columns(2);
+                                        let as_num = match u64::from_str_radix(&nums, 16) {
+                                            Ok(v) => v,
+                                            Err(_) => 0,
+                                        };
+                                        match from_u32(as_num as u32) {
+                                            Some(v) => v,
+                                            None => panic!(
+                                                "{}: {} is not a valid unicode scalar value",
+                                                self.position, as_num
+                                            ),
+                                        }
+                                    }
+                                    'u' => {
+                                        // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
+                                        // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
+                                        // We will need to loop to make sure we catch all UTF-16 codepoints
+                                        // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
+
+                                        // Support \u{X..X} (Unicode Codepoint)
+                                        if self.next_is('{') {
+                                            let s = self
+                                                .take_char_while(char::is_alphanumeric)
+                                                .expect("Could not read chars");
+
+                                            // We know this is a single unicode codepoint, convert to u32
+                                            let as_num = match u32::from_str_radix(&s, 16) {
                                                 Ok(v) => v,
                                                 Err(_) => 0,
                                             };
-                                            match from_u32(as_num as u32) {
-                                                Some(v) => v,
-                                                None => panic!(
-                                                    "{}: {} is not a valid unicode scalar value",
-                                                    self.position, as_num
-                                                ),
+                                            if as_num > 0x10_FFFF {
+                                                return Err(LexerError::new("Invalid Unicode escape sequence"));
                                             }
-                                        }
-                                        'u' => {
-                                            // There are 2 types of codepoints. Surragate codepoints and unicode codepoints.
-                                            // UTF-16 could be surrogate codepoints, "\uXXXX\uXXXX" which make up a single unicode codepoint.
-                                            // We will need to loop to make sure we catch all UTF-16 codepoints
-                                            // Example Test: https://github.com/tc39/test262/blob/ee3715ee56744ccc8aeb22a921f442e98090b3c1/implementation-contributed/v8/mjsunit/es6/unicode-escapes.js#L39-L44
-
-                                            // Support \u{X..X} (Unicode Codepoint)
-                                            if self.next_is('{') {
+
+                                            if self.preview_next().is_none() {
+                                                return Err(LexerError::new("Unterminated String"));
+                                            }
+                                            self.next_char(); // '}'
+                                            self.move_columns(s.len() as u32 + 1);
+
+                                            // Surrogates are kept as lone code units.
+                                            if let Some(c) = from_u32(as_num) {
+                                                c
+                                            } else {
+                                                buf.push(as_num as u16);
+                                                continue;
+                                            }
+                                        } else {
+                                            let mut codepoints: Vec<u16> = vec![];
+                                            loop {
+                                                // Collect each character after \u e.g \uD83D will give "D83D"
                                                 let s = self
                                                     .take_char_while(char::is_alphanumeric)
                                                     .expect("Could not read chars");
 
-                                                // We know this is a single unicode codepoint, convert to u32
-                                                let as_num = match u32::from_str_radix(&s, 16) {
+                                                // Convert to u16
+                                                let as_num = match u16::from_str_radix(&s, 16) {
                                                     Ok(v) => v,
                                                     Err(_) => 0,
                                                 };
-                                                let c = from_u32(as_num).ok_or_else(|| LexerError::new("Invalid Unicode escape sequence"))?;
 
-                                                if self.preview_next().is_none() {
-                                                    return Err(LexerError::new("Unterminated String"));
-                                                }
-                                                self.next(); // '}'
+                                                codepoints.push(as_num);
                                                 self.move_columns(s.len() as u32);
-                                                c
-                                            } else {
-                                                let mut codepoints: Vec<u16> = vec![];
-                                                loop {
-                                                    // Collect each character after \u e.g \uD83D will give "D83D"
-                                                    let s = self
-                                                        .take_char_while(char::is_alphanumeric)
-                                                        .expect("Could not read chars");
-
-                                                    // Convert to u16
-                                                    let as_num = match u16::from_str_radix(&s, 16) {
-                                                        Ok(v) => v,
-                                                        Err(_) => 0,
-                                                    };
-
-                                                    codepoints.push(as_num);
-                                                    self.move_columns(s.len() as u32);
-
-                                                    // Check for another UTF-16 codepoint
-                                                    if self.next_is('\\') && self.next_is('u') {
-                                                        continue;
-                                                    }
-                                                    break;
-                                                }
 
-                                                // codepoints length should either be 1 (unicode codepoint) or 2 (surrogate codepoint).
-                                                // Rust's decode_utf16 will deal with it regardless
-                                                decode_utf16(codepoints.iter().cloned())
-                                                    .next()
-                                                    .expect("Could not get next codepoint")
-                                                    .expect("Could not get next codepoint")
+                                                // Check for another UTF-16 codepoint
+                                                if self.next_is('\\') && self.next_is('u') {
+                                                    continue;
+                                                }
+                                                break;
                                             }
+
+                                            // The code units are kept as they are, so surrogate pairs make up a
+                                            // single code point and lone surrogates are allowed.
+                                            buf.extend(codepoints);
+                                            continue;
                                         }
-                                        '\'' | '"' | '\\' => escape,
-                                        ch => {
-                                            let details = format!("invalid escape sequence `{}` at line {}, column {}", escape_pos.line_number(), escape_pos.column_number(), ch);
-                                            return Err(LexerError { details });
-                                        }
-                                    };
-                                    buf.push(escaped_ch);
-                                }
+                                    }
+                                    '\'' | '"' | '\\' => escape,
+                                    ch => {
+                                        let details = format!("invalid escape sequence `{}` at line {}, column {}", escape_pos.line_number(), escape_pos.column_number(), ch);
+                                        return Err(LexerError { details });
+                                    }
+                                };
+                                buf.extend(escaped_ch.encode_utf16(&mut [0; 2]).iter());
                             }
-                            next_ch => buf.push(next_ch),
                         }
-                    }
-                    let str_length = buf.len() as u32;
-                    // Why +1? Quotation marks are not included,
-                    // So technically it would be +2, (for both " ") but we want to be 1 less
-                    // to compensate for the incrementing at the top
-                    self.move_columns( str_length.wrapping_add(1));
-                    self.push_token(TokenKind::string_literal(buf), start_pos);
-                }
-                _ if ch.is_digit(10) => self.reed_numerical_literal(ch)?,
-                _ if ch.is_alphabetic() || ch == '$' || ch == '_' => {
-                    let mut buf = ch.to_string();
-                    while let Some(ch) = self.preview_next() {
-                        if ch.is_alphabetic() || ch.is_digit(10) || ch == '_' {
-                            buf.push(self.next());
-                        } else {
-                            break;
+                        next_ch => {
+                            str_length += 1;
+                            buf.extend(next_ch.encode_utf16(&mut [0; 2]).iter())
                         }
                     }
-                    let tk = match buf.as_str() {
-                        "true" => TokenKind::BooleanLiteral(true),
-                        "false" => TokenKind::BooleanLiteral(false),
-                        "null" => TokenKind::NullLiteral,
-                        "NaN" => TokenKind::NumericLiteral(NumericLiteral::Rational(f64::NAN)),
-                        slice => {
-                            if let Ok(keyword) = FromStr::from_str(slice) {
-                                TokenKind::Keyword(keyword)
-                            } else {
-                                TokenKind::identifier(slice)
-                            }
-                        }
-                    };
-
-                    // Move position forward the length of the token
-                    self.move_columns( (buf.len().wrapping_sub(1)) as u32);
-
-                    self.push_token(tk, start_pos);
                 }
-                ';' => self.push_punc(Punctuator::Semicolon, start_pos),
-                ':' => self.push_punc(Punctuator::Colon, start_pos),
-                '.' => {
-                    // . or ...
+                // Why +1? Quotation marks are not included,
+                // So technically it would be +2, (for both " ") but we want to be 1 less
+                // to compensate for the incrementing at the top
+                self.move_columns( str_length.wrapping_add(1));
+                self.push_token(TokenKind::string_literal(JsString::from(buf)), start_pos);
+            }
+            _ if ch.is_digit(10) => self.reed_numerical_literal(ch, start_pos)?,
+            _ if is_identifier_start(ch) || ch == '\\' => self.read_identifier_name(ch, start_pos)?,
+            ';' => self.push_punc(Punctuator::Semicolon, start_pos),
+            ':' => self.push_punc(Punctuator::Colon, start_pos),
+            '.' => {
+                // . or ...
+                if self.next_is('.') {
                     if self.next_is('.') {
-                        if self.next_is('.') {
-                            self.push_punc(Punctuator::Spread, start_pos);
-                        } else {
-                            return Err(LexerError::new("Expecting Token ."));
-                        }
+                        self.push_punc(Punctuator::Spread, start_pos);
                     } else {
-                        self.push_punc(Punctuator::Dot, start_pos);
-                    };
+                        return Err(LexerError::new("Expecting Token ."));
+                    }
+                } else {
+                    self.push_punc(Punctuator::Dot, start_pos);
+                };
+            }
+            '(' => self.push_punc(Punctuator::OpenParen, start_pos),
+            ')' => self.push_punc(Punctuator::CloseParen, start_pos),
+            ',' => self.push_punc(Punctuator::Comma, start_pos),
+            '{' => self.push_punc(Punctuator::OpenBlock, start_pos),
+            '}' => self.push_punc(Punctuator::CloseBlock, start_pos),
+            '[' => self.push_punc(Punctuator::OpenBracket, start_pos),
+            ']' => self.push_punc(Punctuator::CloseBracket, start_pos),
+            '?' => self.push_punc(Punctuator::Question, start_pos),
+            // A hashbang comment, only allowed at the very start of the source.
+            '#' if start_pos == Position::new(1, 1) && self.preview_next() == Some('!') => {
+                self.next_char();
+                self.next_column();
+                let text = self.take_char_while(|ch| !is_line_terminator(ch))?;
+                self.move_columns(text.chars().count() as u32);
+                if self.preserve_comments {
+                    self.push_token(TokenKind::comment(CommentKind::Hashbang, text), start_pos);
                 }
-                '(' => self.push_punc(Punctuator::OpenParen, start_pos),
-                ')' => self.push_punc(Punctuator::CloseParen, start_pos),
-                ',' => self.push_punc(Punctuator::Comma, start_pos),
-                '{' => self.push_punc(Punctuator::OpenBlock, start_pos),
-                '}' => self.push_punc(Punctuator::CloseBlock, start_pos),
-                '[' => self.push_punc(Punctuator::OpenBracket, start_pos),
-                ']' => self.push_punc(Punctuator::CloseBracket, start_pos),
-                '?' => self.push_punc(Punctuator::Question, start_pos),
-                // Comments
-                '/' => {
-                    if let Some(ch) = self.preview_next() {
-                        match ch {
-                            // line comment
-                            '/' => {
-                                while self.preview_next().is_some() {
-                                    if self.next() == '\n' {
-                                        break;
-                                    }
-                                }
-                                self.next_line()
+            }
+            // Comments
+            '/' => {
+                if let Some(ch) = self.preview_next() {
+                    match ch {
+                        // line comment
+                        '/' => {
+                            self.next_char();
+                            self.next_column();
+                            let text = self.take_char_while(|ch| !is_line_terminator(ch))?;
+                            self.move_columns(text.chars().count() as u32);
+                            if self.preserve_comments {
+                                self.push_token(
+                                    TokenKind::comment(CommentKind::Line, text),
+                                    start_pos,
+                                );
                             }
-                            // block comment
-                            '*' => {
-                                let mut lines = 0;
-                                loop {
-                                    if self.preview_next().is_none() {
-                                        return Err(LexerError::new("unterminated multiline comment"));
-                                    }
-                                    match self.next() {
-                                        '*' => {
-                                            if self.next_is('/') {
-                                                break;
-                                            }
-                                        }
-                                        next_ch => {
-                                            if next_ch == '\n' {
-                                                lines += 1;
-                                            }
-                                        },
-                                    }
+                        }
+                        // block comment
+                        '*' => {
+                            self.next_char();
+                            self.next_column();
+                            let mut text = String::new();
+                            let mut has_line_terminator = false;
+                            loop {
+                                if self.preview_next().is_none() {
+                                    return Err(LexerError::new("unterminated multiline comment"));
                                 }
-                                self.move_lines(lines);
-                            }
-                            // division, assigndiv or regex literal
-                            _ => {
-                                // if we fail to parse a regex literal, store a copy of the current
-                                // buffer to restore later on
-                                let original_buffer = self.buffer.clone();
-                                let original_pos = self.position;
-                                // first, try to parse a regex literal
-                                let mut body = String::new();
-                                let mut regex = false;
-                                loop {
+                                let ch = self.next_char();
+                                if ch == '*' && self.next_is('/') {
                                     self.next_column();
-                                    match self.buffer.next() {
-                                        // end of body
-                                        Some('/') => {
-                                            regex = true;
-                                            break;
-                                        }
-                                        // newline/eof not allowed in regex literal
-                                        n @ Some('\n') | n @ Some('\r') | n @ Some('\u{2028}')
-                                        | n @ Some('\u{2029}') => {
-                                            self.carriage_return();
-                                            if n != Some('\r') {
-                                                self.next_line();
-                                            }
-                                            break
-                                        },
-                                        None => {
-                                            self.position = Position::new(self.position.line_number(), self.position.column_number()-1);
-                                            break
-                                        }
-                                        // escape sequence
-                                        Some('\\') => {
-                                            body.push('\\');
-                                            if self.preview_next().is_none() {
-                                                break;
-                                            }
-                                            match self.next() {
-                                                // newline not allowed in regex literal
-                                                '\n' | '\r' | '\u{2028}' | '\u{2029}' => break,
-                                                ch => body.push(ch),
-                                            }
-                                        }
-                                        Some(ch) => body.push(ch),
-                                    }
+                                    break;
                                 }
-                                if regex {
-                                    // body was parsed, now look for flags
-                                    let flags = self.take_char_while(char::is_alphabetic)?;
-                                    self.move_columns(body.len() as u32 + 1 + flags.len() as u32);
-                                    self.push_token(TokenKind::regular_expression_literal(
-                                        body, flags.parse()?,
-                                    ), start_pos);
-                                } else {
-                                    // failed to parse regex, restore original buffer position and
-                                    // parse either div or assigndiv
-                                    self.buffer = original_buffer;
-                                    self.position = original_pos;
-                                    if self.next_is('=') {
-                                        self.push_token(TokenKind::Punctuator(
-                                            Punctuator::AssignDiv,
-                                        ), start_pos);
-                                    } else {
-                                        self.push_token(TokenKind::Punctuator(Punctuator::Div), start_pos);
+                                match ch {
+                                    '\r' => self.carriage_return(),
+                                    _ if is_line_terminator(ch) => {
+                                        has_line_terminator = true;
+                                        self.next_line();
                                     }
+                                    _ => self.next_column(),
                                 }
+                                text.push(ch);
+                            }
+                            if self.preserve_comments {
+                                self.push_token(
+                                    TokenKind::comment(CommentKind::Block, text),
+                                    start_pos,
+                                );
+                            }
+                            // A multi-line comment containing a line terminator counts as a
+                            // line terminator for automatic semicolon insertion.
+                            if has_line_terminator {
+                                self.push_token(TokenKind::LineTerminator, start_pos);
+                            }
+                        }
+                        // regex literal, if the previous token allows it
+                        _ if self.regex_allowed => self.read_regex_literal(start_pos)?,
+                        // division or assigndiv
+                        _ => {
+                            if self.next_is('=') {
+                                self.push_punc(Punctuator::AssignDiv, start_pos);
+                            } else {
+                                self.push_punc(Punctuator::Div, start_pos);
                             }
                         }
-                    } else {
-                        return Err(LexerError::new("Expecting Token /,*,= or regex"));
-                    }
-                }
-                '*' => op!(self, start_pos, Punctuator::AssignMul, Punctuator::Mul, {
-                    '*' => vop!(self, Punctuator::AssignPow, Punctuator::Exp)
-                }),
-                '+' => op!(self, start_pos, Punctuator::AssignAdd, Punctuator::Add, {
-                    '+' => Punctuator::Inc
-                }),
-                '-' => op!(self, start_pos, Punctuator::AssignSub, Punctuator::Sub, {
-                    '-' => {
-                        Punctuator::Dec
                     }
-                }),
-                '%' => op!(self, start_pos, Punctuator::AssignMod, Punctuator::Mod),
-                '|' => op!(self, start_pos, Punctuator::AssignOr, Punctuator::Or, {
-                    '|' => Punctuator::BoolOr
-                }),
-                '&' => op!(self, start_pos, Punctuator::AssignAnd, Punctuator::And, {
-                    '&' => Punctuator::BoolAnd
-                }),
-                '^' => op!(self, start_pos, Punctuator::AssignXor, Punctuator::Xor),
-                '=' => op!(self, start_pos, if self.next_is('=') {
-                    Punctuator::StrictEq
                 } else {
-                    Punctuator::Eq
-                }, Punctuator::Assign, {
-                    '>' => {
-                        Punctuator::Arrow
-                    }
-                }),
-                '<' => op!(self, start_pos, Punctuator::LessThanOrEq, Punctuator::LessThan, {
-                    '<' => vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
-                }),
-                '>' => op!(self, start_pos, Punctuator::GreaterThanOrEq, Punctuator::GreaterThan, {
-                    '>' => vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
-                        '>' => vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
-                    })
-                }),
-                '!' => op!(
-                    self,
-                    start_pos,
-                    vop!(self, Punctuator::StrictNotEq, Punctuator::NotEq),
-                    Punctuator::Not
-                ),
-                '~' => self.push_punc(Punctuator::Neg, start_pos),
-                '\n' | '\u{2028}' | '\u{2029}' => {
-                    self.next_line();
-                    self.push_token(TokenKind::LineTerminator, start_pos);
+                    return Err(LexerError::new("Expecting Token /,*,= or regex"));
+                }
+            }
+            '*' => op!(self, start_pos, Punctuator::AssignMul, Punctuator::Mul, {
+                '*' => vop!(self, Punctuator::AssignPow, Punctuator::Exp)
+            }),
+            '+' => op!(self, start_pos, Punctuator::AssignAdd, Punctuator::Add, {
+                '+' => Punctuator::Inc
+            }),
+            '-' => op!(self, start_pos, Punctuator::AssignSub, Punctuator::Sub, {
+                '-' => {
+                    Punctuator::Dec
+                }
+            }),
+            '%' => op!(self, start_pos, Punctuator::AssignMod, Punctuator::Mod),
+            '|' => op!(self, start_pos, Punctuator::AssignOr, Punctuator::Or, {
+                '|' => Punctuator::BoolOr
+            }),
+            '&' => op!(self, start_pos, Punctuator::AssignAnd, Punctuator::And, {
+                '&' => Punctuator::BoolAnd
+            }),
+            '^' => op!(self, start_pos, Punctuator::AssignXor, Punctuator::Xor),
+            '=' => op!(self, start_pos, if self.next_is('=') {
+                Punctuator::StrictEq
+            } else {
+                Punctuator::Eq
+            }, Punctuator::Assign, {
+                '>' => {
+                    Punctuator::Arrow
                 }
-                '\r' => {
-                    self.carriage_return();
+            }),
+            '<' => op!(self, start_pos, Punctuator::LessThanOrEq, Punctuator::LessThan, {
+                '<' => vop!(self, Punctuator::AssignLeftSh, Punctuator::LeftSh)
+            }),
+            '>' => op!(self, start_pos, Punctuator::GreaterThanOrEq, Punctuator::GreaterThan, {
+                '>' => vop!(self, Punctuator::AssignRightSh, Punctuator::RightSh, {
+                    '>' => vop!(self, Punctuator::AssignURightSh, Punctuator::URightSh)
+                })
+            }),
+            '!' => op!(
+                self,
+                start_pos,
+                vop!(self, Punctuator::StrictNotEq, Punctuator::NotEq),
+                Punctuator::Not
+            ),
+            '~' => self.push_punc(Punctuator::Neg, start_pos),
+            '\n' | '\u{2028}' | '\u{2029}' => {
+                self.next_line();
+                self.push_token(TokenKind::LineTerminator, start_pos);
+            }
+            '\r' => {
+                self.carriage_return();
+            }
+            // The rust char::is_whitespace function and the ecma standard use different sets
+            // of characters as whitespaces:
+            //  * Rust uses \p{White_Space},
+            //  * ecma standard uses \{Space_Separator} + \u{0009}, \u{000B}, \u{000C}, \u{FEFF}
+            //
+            // Explicit whitespace: see https://tc39.es/ecma262/#table-32
+            '\u{0020}' | '\u{0009}' | '\u{000B}' | '\u{000C}' | '\u{00A0}' | '\u{FEFF}' |
+            // Unicode Space_Seperator category (minus \u{0020} and \u{00A0} which are allready stated above)
+            '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => (),
+            _ => {
+                let details = format!("Unexpected '{}' at line {}, column {}", start_pos.line_number(), start_pos.column_number(), ch);
+                return Err(LexerError { details });
+            },
+        }
+
+        Ok(true)
+    }
+}
+
+/// Checks if a `/` after a token of the given kind starts a regular expression literal, or if
+/// it's a division punctuator instead.
+///
+/// A regular expression can't appear right after an expression that could be the left operand
+/// of a division, like identifiers, literals, or closing parentheses and brackets. A closing
+/// brace is considered to end a block, so a regular expression can follow it.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-lexical-grammar
+fn regex_allowed_after(kind: &TokenKind) -> bool {
+    match kind {
+        TokenKind::BooleanLiteral(_)
+        | TokenKind::Identifier(_)
+        | TokenKind::NullLiteral
+        | TokenKind::NumericLiteral(_)
+        | TokenKind::StringLiteral(_)
+        | TokenKind::RegularExpressionLiteral(_, _)
+        | TokenKind::Keyword(Keyword::This)
+        | TokenKind::Keyword(Keyword::Super)
+        | TokenKind::Punctuator(Punctuator::CloseParen)
+        | TokenKind::Punctuator(Punctuator::CloseBracket)
+        | TokenKind::Punctuator(Punctuator::Inc)
+        | TokenKind::Punctuator(Punctuator::Dec) => false,
+        _ => true,
+    }
+}
+
+/// Converts the digits of an integer literal in a base that is a power of two to the nearest
+/// `f64`, rounding ties to even.
+///
+/// The digits are read bit by bit, so that the result is rounded only once.
+fn power_of_two_radix_to_f64(digits: &str, base: u32) -> f64 {
+    /// Bits kept in the significand: the 53 bits of an `f64` and a rounding bit.
+    const KEPT_BITS: u32 = 54;
+
+    let bits_per_digit = base.trailing_zeros();
+    let mut significand: u64 = 0;
+    let mut significant_bits = 0;
+    let mut exponent = 0;
+    let mut sticky = false;
+
+    for digit in digits.chars().filter_map(|ch| ch.to_digit(base)) {
+        for i in (0..bits_per_digit).rev() {
+            let bit = (digit >> i) & 1;
+            if significant_bits < KEPT_BITS {
+                significand = (significand << 1) | u64::from(bit);
+                if significand != 0 {
+                    significant_bits += 1;
+                }
+            } else {
+                exponent += 1;
+                sticky |= bit == 1;
+            }
+        }
+    }
+
+    if significant_bits == KEPT_BITS {
+        let round = significand & 1 == 1;
+        significand >>= 1;
+        exponent += 1;
+        if round && (sticky || significand & 1 == 1) {
+            significand += 1;
+        }
+    }
+
+    significand as f64 * 2_f64.powi(exponent)
+}
+
+/// Checks if a character is a line terminator.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#sec-line-terminators
+fn is_line_terminator(ch: char) -> bool {
+    ch == '\n' || ch == '\r' || ch == '\u{2028}' || ch == '\u{2029}'
+}
+
+/// Checks if a character can start an identifier, not taking escape sequences into account.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-IdentifierStart
+fn is_identifier_start(ch: char) -> bool {
+    ch == '$' || ch == '_' || UnicodeXID::is_xid_start(ch)
+}
+
+/// Checks if a character can be part of an identifier, not taking escape sequences into account.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-IdentifierPart
+fn is_identifier_part(ch: char) -> bool {
+    /// Zero width non-joiner.
+    const ZWNJ: char = '\u{200C}';
+    /// Zero width joiner.
+    const ZWJ: char = '\u{200D}';
+
+    ch == '$' || ch == ZWNJ || ch == ZWJ || UnicodeXID::is_xid_continue(ch)
+}
+
+/// Checks if a string is an identifier name, like the names of the properties that can be written
+/// without quotes.
+///
+/// More information:
+///  - [ECMAScript Specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-IdentifierName
+pub(crate) fn is_identifier_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().map_or(false, is_identifier_start) && chars.all(is_identifier_part)
+}
+
+impl<R> Iterator for Lexer<R>
+where
+    R: Read,
+{
+    type Item = Result<Token, LexerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                return Some(Ok(token));
+            }
+            if self.finished {
+                return None;
+            }
+
+            match self.lex_next() {
+                Ok(true) => {}
+                Ok(false) => self.finished = true,
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(e));
                 }
-                // The rust char::is_whitespace function and the ecma standard use different sets
-                // of characters as whitespaces:
-                //  * Rust uses \p{White_Space},
-                //  * ecma standard uses \{Space_Separator} + \u{0009}, \u{000B}, \u{000C}, \u{FEFF}
-                //
-                // Explicit whitespace: see https://tc39.es/ecma262/#table-32
-                '\u{0020}' | '\u{0009}' | '\u{000B}' | '\u{000C}' | '\u{00A0}' | '\u{FEFF}' |
-                // Unicode Space_Seperator category (minus \u{0020} and \u{00A0} which are allready stated above)
-                '\u{1680}' | '\u{2000}'..='\u{200A}' | '\u{202F}' | '\u{205F}' | '\u{3000}' => (),
-                _ => {
-                    let details = format!("Unexpected '{}' at line {}, column {}", start_pos.line_number(), start_pos.column_number(), ch);
-                    return Err(LexerError { details });
-                },
             }
         }
     }
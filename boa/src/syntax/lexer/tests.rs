@@ -11,17 +11,28 @@ fn span(start: (u32, u32), end: (u32, u32)) -> Span {
 #[test]
 fn check_single_line_comment() {
     let s1 = "var \n//This is a comment\ntrue";
-    let mut lexer = Lexer::new(s1);
+    let mut lexer = Lexer::new(s1.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Var));
     assert_eq!(lexer.tokens[1].kind, TokenKind::LineTerminator);
-    assert_eq!(lexer.tokens[2].kind, TokenKind::BooleanLiteral(true));
+    assert_eq!(lexer.tokens[2].kind, TokenKind::LineTerminator);
+    assert_eq!(lexer.tokens[3].kind, TokenKind::BooleanLiteral(true));
 }
 
 #[test]
 fn check_multi_line_comment() {
     let s = "var /* await \n break \n*/ x";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Var));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::LineTerminator);
+    assert_eq!(lexer.tokens[2].kind, TokenKind::identifier("x"));
+}
+
+#[test]
+fn check_single_line_multi_line_comment() {
+    let s = "var /* comment */ x";
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Var));
     assert_eq!(lexer.tokens[1].kind, TokenKind::identifier("x"));
@@ -30,7 +41,7 @@ fn check_multi_line_comment() {
 #[test]
 fn check_string() {
     let s = "'aaa' \"bbb\"";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::string_literal("aaa"));
 
@@ -43,7 +54,7 @@ fn check_punctuators() {
     let s = "{ ( ) [ ] . ... ; , < > <= >= == != === !== \
              + - * % -- << >> >>> & | ^ ! ~ && || ? : \
              = += -= *= &= **= ++ ** <<= >>= >>>= &= |= ^= =>";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(
         lexer.tokens[0].kind,
@@ -241,7 +252,7 @@ fn check_keywords() {
              do else export extends finally for function if import in instanceof \
              new return super switch this throw try typeof var void while with yield";
 
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Await));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Keyword(Keyword::Break));
@@ -285,7 +296,7 @@ fn check_keywords() {
 #[test]
 fn check_variable_definition_tokens() {
     let s = "let a = 'hello';";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::Keyword(Keyword::Let));
     assert_eq!(lexer.tokens[1].kind, TokenKind::identifier("a"));
@@ -296,11 +307,31 @@ fn check_variable_definition_tokens() {
     assert_eq!(lexer.tokens[3].kind, TokenKind::string_literal("hello"));
 }
 
+#[test]
+fn token_kind_names() {
+    let s = "let a = 'hello'; // done";
+    let mut lexer = Lexer::new(s.as_bytes());
+    lexer.set_preserve_comments(true);
+    lexer.lex().expect("failed to lex");
+    let names: Vec<_> = lexer.tokens.iter().map(|t| t.kind.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "Keyword",
+            "Identifier",
+            "Punctuator",
+            "StringLiteral",
+            "Punctuator",
+            "Comment"
+        ]
+    );
+}
+
 #[test]
 fn check_positions() {
     let s = r#"console.log("hello world\u{2764}"); // Test"#;
     // --------123456789
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     // The first column is 1 (not zero indexed)
     assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 7)));
@@ -328,7 +359,7 @@ fn check_positions() {
 #[ignore]
 fn two_divisions_in_expression() {
     let s = "    return a !== 0 || 1 / a === 1 / b;";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     // dbg!(&lexer.tokens);
 
@@ -339,7 +370,7 @@ fn two_divisions_in_expression() {
 fn check_line_numbers() {
     let s = "x\ny\n";
 
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
 
     assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 1)));
@@ -353,7 +384,7 @@ fn check_line_numbers() {
 fn check_decrement_advances_lexer_2_places() {
     // Here we want an example of decrementing an integer
     let s = "let a = b--;";
-    let mut lexer = Lexer::new(s);
+    let mut lexer = Lexer::new(s.as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[4].kind, TokenKind::Punctuator(Punctuator::Dec));
     // Decrementing means adding 2 characters '--', the lexer should consume it as a single token
@@ -366,7 +397,7 @@ fn check_decrement_advances_lexer_2_places() {
 
 #[test]
 fn check_nan() {
-    let mut lexer = Lexer::new("let a = NaN;");
+    let mut lexer = Lexer::new("let a = NaN;".as_bytes());
     lexer.lex().expect("failed to lex");
 
     match lexer.tokens[3].kind {
@@ -380,7 +411,8 @@ fn check_nan() {
 #[test]
 fn numbers() {
     let mut lexer = Lexer::new(
-        "1 2 0x34 056 7.89 42. 5e3 5e+3 5e-3 0b10 0O123 0999 1.0e1 1.0e-1 1.0E1 1E1 0.0 0.12",
+        "1 2 0x34 056 7.89 42. 5e3 5e+3 5e-3 0b10 0O123 0999 1.0e1 1.0e-1 1.0E1 1E1 0.0 0.12"
+            .as_bytes(),
     );
 
     lexer.lex().expect("failed to lex");
@@ -406,7 +438,7 @@ fn numbers() {
 
 #[test]
 fn implicit_octal_edge_case() {
-    let mut lexer = Lexer::new("044.5 094.5");
+    let mut lexer = Lexer::new("044.5 094.5".as_bytes());
 
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(36));
@@ -418,7 +450,7 @@ fn implicit_octal_edge_case() {
 
 #[test]
 fn hexadecimal_edge_case() {
-    let mut lexer = Lexer::new("0xffff.ff 0xffffff");
+    let mut lexer = Lexer::new("0xffff.ff 0xffffff".as_bytes());
 
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(0xffff));
@@ -433,13 +465,13 @@ fn hexadecimal_edge_case() {
 
 #[test]
 fn single_number_without_semicolon() {
-    let mut lexer = Lexer::new("1");
+    let mut lexer = Lexer::new("1".as_bytes());
     lexer.lex().expect("failed to lex");
 }
 
 #[test]
 fn number_followed_by_dot() {
-    let mut lexer = Lexer::new("1..");
+    let mut lexer = Lexer::new("1..".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1.0));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Dot));
@@ -447,7 +479,7 @@ fn number_followed_by_dot() {
 
 #[test]
 fn regex_literal() {
-    let mut lexer = Lexer::new("/(?:)/");
+    let mut lexer = Lexer::new("/(?:)/".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(
         lexer.tokens[0].kind,
@@ -457,7 +489,7 @@ fn regex_literal() {
 
 #[test]
 fn regex_literal_flags() {
-    let mut lexer = Lexer::new(r"/\/[^\/]*\/*/gmi");
+    let mut lexer = Lexer::new(r"/\/[^\/]*\/*/gmi".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(
         lexer.tokens[0].kind,
@@ -467,7 +499,7 @@ fn regex_literal_flags() {
 
 #[test]
 fn addition_no_spaces() {
-    let mut lexer = Lexer::new("1+1");
+    let mut lexer = Lexer::new("1+1".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -476,7 +508,7 @@ fn addition_no_spaces() {
 
 #[test]
 fn addition_no_spaces_left_side() {
-    let mut lexer = Lexer::new("1+ 1");
+    let mut lexer = Lexer::new("1+ 1".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -485,7 +517,7 @@ fn addition_no_spaces_left_side() {
 
 #[test]
 fn addition_no_spaces_right_side() {
-    let mut lexer = Lexer::new("1 +1");
+    let mut lexer = Lexer::new("1 +1".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -494,7 +526,7 @@ fn addition_no_spaces_right_side() {
 
 #[test]
 fn addition_no_spaces_e_number_left_side() {
-    let mut lexer = Lexer::new("1e2+ 1");
+    let mut lexer = Lexer::new("1e2+ 1".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(100.0));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -503,7 +535,7 @@ fn addition_no_spaces_e_number_left_side() {
 
 #[test]
 fn addition_no_spaces_e_number_right_side() {
-    let mut lexer = Lexer::new("1 +1e3");
+    let mut lexer = Lexer::new("1 +1e3".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -512,7 +544,7 @@ fn addition_no_spaces_e_number_right_side() {
 
 #[test]
 fn addition_no_spaces_e_number() {
-    let mut lexer = Lexer::new("1e3+1e11");
+    let mut lexer = Lexer::new("1e3+1e11".as_bytes());
     lexer.lex().expect("failed to lex");
     assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1000.0));
     assert_eq!(lexer.tokens[1].kind, TokenKind::Punctuator(Punctuator::Add));
@@ -521,3 +553,336 @@ fn addition_no_spaces_e_number() {
         TokenKind::numeric_literal(100_000_000_000.0)
     );
 }
+
+#[test]
+fn lexer_is_lazy_iterator() {
+    let mut lexer = Lexer::new("let a = 5; @".as_bytes());
+
+    assert_eq!(
+        lexer.next().map(|tk| tk.expect("failed to lex").kind),
+        Some(TokenKind::Keyword(Keyword::Let))
+    );
+    assert_eq!(
+        lexer.next().map(|tk| tk.expect("failed to lex").kind),
+        Some(TokenKind::identifier("a"))
+    );
+    assert_eq!(lexer.by_ref().take(3).filter(Result::is_ok).count(), 3);
+    assert!(lexer.next().expect("expected an error").is_err());
+    assert!(lexer.next().is_none());
+}
+
+#[test]
+fn multi_byte_input() {
+    let mut lexer = Lexer::new("'ñ→😀' x".as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(lexer.tokens[0].kind, TokenKind::string_literal("ñ→😀"));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::identifier("x"));
+}
+
+#[test]
+fn invalid_utf8_input() {
+    let mut lexer = Lexer::new(&b"let a = '\xFF';"[..]);
+
+    assert!(lexer.lex().is_err());
+}
+
+#[test]
+fn unicode_identifiers() {
+    let mut lexer = Lexer::new("ñandú π $x _y a\u{200D}b 變數".as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(lexer.tokens[0].kind, TokenKind::identifier("ñandú"));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::identifier("π"));
+    assert_eq!(lexer.tokens[2].kind, TokenKind::identifier("$x"));
+    assert_eq!(lexer.tokens[3].kind, TokenKind::identifier("_y"));
+    assert_eq!(lexer.tokens[4].kind, TokenKind::identifier("a\u{200D}b"));
+    assert_eq!(lexer.tokens[5].kind, TokenKind::identifier("變數"));
+
+    assert_eq!(lexer.tokens[1].span(), span((1, 7), (1, 7)));
+    assert_eq!(lexer.tokens[5].span(), span((1, 19), (1, 20)));
+}
+
+#[test]
+fn escaped_identifiers() {
+    let mut lexer = Lexer::new(r"\u0061b a\u{62}c \u{1d4b3}".as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(lexer.tokens[0].kind, TokenKind::identifier("ab"));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::identifier("abc"));
+    assert_eq!(lexer.tokens[2].kind, TokenKind::identifier("\u{1d4b3}"));
+
+    assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 7)));
+    assert_eq!(lexer.tokens[1].span(), span((1, 9), (1, 16)));
+}
+
+#[test]
+fn invalid_escaped_identifiers() {
+    assert!(Lexer::new(r"v\u0061r".as_bytes()).lex().is_err());
+    assert!(Lexer::new(r"\u0031a".as_bytes()).lex().is_err());
+    assert!(Lexer::new(r"a\u002D".as_bytes()).lex().is_err());
+    assert!(Lexer::new(r"a\x41".as_bytes()).lex().is_err());
+    assert!(Lexer::new(r"a\u{}".as_bytes()).lex().is_err());
+    assert!(Lexer::new(r"a\u00".as_bytes()).lex().is_err());
+}
+
+#[test]
+fn division_after_operands() {
+    let mut lexer = Lexer::new("a / b / c; (1) / 2 /= 3; x[0] /g".as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    let divisions = lexer
+        .tokens
+        .iter()
+        .filter(|tk| {
+            tk.kind == TokenKind::Punctuator(Punctuator::Div)
+                || tk.kind == TokenKind::Punctuator(Punctuator::AssignDiv)
+        })
+        .count();
+    assert_eq!(divisions, 5);
+}
+
+#[test]
+fn regex_literal_after_operators() {
+    let mut lexer = Lexer::new("x = /a/g; f(/b/); return /[/]/".as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(
+        lexer.tokens[2].kind,
+        TokenKind::regular_expression_literal("a", "g".parse().unwrap())
+    );
+    assert_eq!(lexer.tokens[2].span(), span((1, 5), (1, 8)));
+    assert_eq!(
+        lexer.tokens[6].kind,
+        TokenKind::regular_expression_literal("b", "".parse().unwrap())
+    );
+    assert_eq!(
+        lexer.tokens[10].kind,
+        TokenKind::regular_expression_literal("[/]", "".parse().unwrap())
+    );
+}
+
+#[test]
+fn unterminated_regex_literal() {
+    assert!(Lexer::new("x = /abc\n/".as_bytes()).lex().is_err());
+    assert!(Lexer::new("x = /abc".as_bytes()).lex().is_err());
+}
+
+#[test]
+fn numeric_separators() {
+    let mut lexer = Lexer::new("1_000_000 0xF_F 0b1010_1010 0o7_7 1_0.0_1e1_0 1_0n".as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].kind, TokenKind::numeric_literal(1_000_000));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::numeric_literal(0xff));
+    assert_eq!(
+        lexer.tokens[2].kind,
+        TokenKind::numeric_literal(0b1010_1010)
+    );
+    assert_eq!(lexer.tokens[3].kind, TokenKind::numeric_literal(0o77));
+    assert_eq!(lexer.tokens[4].kind, TokenKind::numeric_literal(10.01e10));
+    assert_eq!(
+        lexer.tokens[5].kind,
+        TokenKind::NumericLiteral(NumericLiteral::BigInt(BigInt::from(10)))
+    );
+}
+
+#[test]
+fn invalid_numeric_separators() {
+    for src in &[
+        "1_", "1__0", "0_1", "0x_1", "1._5", "1_.5", "1e_5", "1_e5", "1_n", "01_2",
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        assert!(lexer.lex().is_err(), "{} should not be lexed", src);
+    }
+}
+
+#[test]
+fn numeric_literal_spans() {
+    let mut lexer = Lexer::new("0x1F 1_000 1.5e3".as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 4)));
+    assert_eq!(lexer.tokens[1].span(), span((1, 6), (1, 10)));
+    assert_eq!(lexer.tokens[2].span(), span((1, 12), (1, 16)));
+}
+
+#[test]
+fn legacy_octal_strict_mode() {
+    for src in &["010", "08", "09.5"] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        lexer.set_strict_mode(true);
+        assert!(lexer.lex().is_err(), "{} should not be lexed", src);
+    }
+
+    let mut lexer = Lexer::new("0 0.5 0n 0x10".as_bytes());
+    lexer.set_strict_mode(true);
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[3].kind, TokenKind::numeric_literal(16));
+}
+
+#[test]
+fn use_strict_directive() {
+    for src in &[
+        "\"use strict\"; 010",
+        "'use strict'\n010",
+        "// comment\n'other'; \"use strict\";\n010",
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        assert!(lexer.lex().is_err(), "{} should not be lexed", src);
+        assert!(lexer.strict_mode());
+    }
+
+    // Neither of these are `"use strict"` directives.
+    for src in &[
+        "\"use strict\".length; 010",
+        "a; \"use strict\"; 010",
+        "\"use\\x20strict\"; 010",
+    ] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        lexer.lex().expect("failed to lex");
+        assert!(!lexer.strict_mode(), "{} should not be strict", src);
+    }
+}
+
+#[test]
+fn incomplete_numeric_literals() {
+    for src in &["0x", "0b2", "1e", "1e+", "09n"] {
+        let mut lexer = Lexer::new(src.as_bytes());
+        assert!(lexer.lex().is_err(), "{} should not be lexed", src);
+    }
+}
+
+#[test]
+fn large_integer_literals() {
+    let mut lexer = Lexer::new(
+        "0x1000000000000081 0b1000000000000000000000000000000000000000000000000000010000001 \
+         0x20000000000003 18446744073709551617 0x1fffffffffffff800"
+            .as_bytes(),
+    );
+    lexer.lex().expect("failed to lex");
+    // 2^60 + 129 rounds up to 2^60 + 256.
+    assert_eq!(
+        lexer.tokens[0].kind,
+        TokenKind::numeric_literal(1_152_921_504_606_847_232.0)
+    );
+    assert_eq!(
+        lexer.tokens[1].kind,
+        TokenKind::numeric_literal(1_152_921_504_606_847_232.0)
+    );
+    // 2^53 + 3 is a tie, so it's rounded to the even 2^53 + 4.
+    assert_eq!(
+        lexer.tokens[2].kind,
+        TokenKind::numeric_literal(9_007_199_254_740_996.0)
+    );
+    assert_eq!(
+        lexer.tokens[3].kind,
+        TokenKind::numeric_literal(18_446_744_073_709_551_616.0)
+    );
+    assert_eq!(
+        lexer.tokens[4].kind,
+        TokenKind::numeric_literal(36_893_488_147_419_103_232.0)
+    );
+}
+
+#[test]
+fn line_comment_ends_before_line_terminator() {
+    let mut lexer = Lexer::new("a // comment\nb".as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].kind, TokenKind::identifier("a"));
+    assert_eq!(lexer.tokens[1].kind, TokenKind::LineTerminator);
+    assert_eq!(lexer.tokens[2].kind, TokenKind::identifier("b"));
+}
+
+#[test]
+fn block_comment_is_not_closed_by_its_opening() {
+    let mut lexer = Lexer::new("/*/ a */ b".as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens.len(), 1);
+    assert_eq!(lexer.tokens[0].kind, TokenKind::identifier("b"));
+}
+
+#[test]
+fn preserved_comments() {
+    let s = "a // line\n/* block */ b /* multi\nline */ c";
+    let mut lexer = Lexer::new(s.as_bytes());
+    lexer.set_preserve_comments(true);
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(lexer.tokens[0].kind, TokenKind::identifier("a"));
+    assert_eq!(
+        lexer.tokens[1].kind,
+        TokenKind::comment(CommentKind::Line, " line")
+    );
+    assert_eq!(lexer.tokens[1].span(), span((1, 3), (1, 9)));
+    assert_eq!(lexer.tokens[2].kind, TokenKind::LineTerminator);
+    assert_eq!(
+        lexer.tokens[3].kind,
+        TokenKind::comment(CommentKind::Block, " block ")
+    );
+    assert_eq!(lexer.tokens[3].span(), span((2, 1), (2, 11)));
+    assert_eq!(lexer.tokens[4].kind, TokenKind::identifier("b"));
+    assert_eq!(lexer.tokens[4].span(), span((2, 13), (2, 13)));
+    assert_eq!(
+        lexer.tokens[5].kind,
+        TokenKind::comment(CommentKind::Block, " multi\nline ")
+    );
+    assert_eq!(lexer.tokens[5].span(), span((2, 15), (3, 7)));
+    assert_eq!(lexer.tokens[6].kind, TokenKind::LineTerminator);
+    assert_eq!(lexer.tokens[7].kind, TokenKind::identifier("c"));
+    assert_eq!(lexer.tokens[7].span(), span((3, 9), (3, 9)));
+
+    let dumped: Vec<_> = lexer.tokens.iter().map(|tk| tk.kind.to_string()).collect();
+    assert_eq!(dumped[1], "// line");
+    assert_eq!(dumped[5], "/* multi\nline */");
+}
+
+#[test]
+fn preserved_comments_do_not_affect_regex() {
+    let mut lexer = Lexer::new("a /* comment */ / b".as_bytes());
+    lexer.set_preserve_comments(true);
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[2].kind, TokenKind::Punctuator(Punctuator::Div));
+}
+
+#[test]
+fn hashbang() {
+    let mut lexer = Lexer::new("#!/usr/bin/env boa\nlet a".as_bytes());
+    lexer.lex().expect("failed to lex");
+    assert_eq!(lexer.tokens[0].kind, TokenKind::LineTerminator);
+    assert_eq!(lexer.tokens[1].kind, TokenKind::Keyword(Keyword::Let));
+    assert_eq!(lexer.tokens[1].span(), span((2, 1), (2, 3)));
+
+    let mut lexer = Lexer::new("#!/usr/bin/env boa\nlet a".as_bytes());
+    lexer.set_preserve_comments(true);
+    lexer.lex().expect("failed to lex");
+    assert_eq!(
+        lexer.tokens[0].kind,
+        TokenKind::comment(CommentKind::Hashbang, "/usr/bin/env boa")
+    );
+    assert_eq!(lexer.tokens[0].span(), span((1, 1), (1, 18)));
+    assert_eq!(lexer.tokens[0].kind.to_string(), "#!/usr/bin/env boa");
+}
+
+#[test]
+fn hashbang_only_at_start() {
+    let mut lexer = Lexer::new(" #!/usr/bin/env boa".as_bytes());
+    assert!(lexer.lex().is_err());
+
+    let mut lexer = Lexer::new("a\n#!/usr/bin/env boa".as_bytes());
+    assert!(lexer.lex().is_err());
+}
+
+#[test]
+fn lone_surrogate_escapes() {
+    let mut lexer = Lexer::new(r#"'\uD834' '\u{DF06}' '𝌆'"#.as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert_eq!(
+        lexer.tokens[0].kind,
+        TokenKind::string_literal(JsString::from_utf16(&[0xD834]))
+    );
+    assert_eq!(
+        lexer.tokens[1].kind,
+        TokenKind::string_literal(JsString::from_utf16(&[0xDF06]))
+    );
+    assert_eq!(lexer.tokens[2].kind, TokenKind::string_literal("\u{1D306}"));
+}
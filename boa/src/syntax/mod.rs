@@ -1,5 +1,74 @@
 //! Syntactical analysis, such as AST, Parsing and Lexing
 
+pub mod analyzer;
 pub mod ast;
 pub mod lexer;
+pub mod optimizer;
 pub mod parser;
+pub mod source_map;
+
+use self::{
+    analyzer::{analyze, AnalyzerError},
+    ast::{node::StatementList, Position},
+    lexer::Lexer,
+    optimizer::{optimize, OptimizerOptions},
+    parser::{error::ParseError, Parser},
+};
+use std::{error, fmt, io::Read};
+
+/// An error found while preparing a script to be run.
+#[derive(Debug, Clone)]
+pub enum ScriptError {
+    /// The script couldn't be lexed or parsed.
+    Parse(ParseError),
+    /// The script parsed, but the analysis found an early error, like a redeclared binding.
+    Early(AnalyzerError),
+}
+
+impl ScriptError {
+    /// Gets the position in the source code where the error was found, if it is known.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Parse(e) => e.position(),
+            Self::Early(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => e.fmt(f),
+            Self::Early(e) => e.fmt(f),
+        }
+    }
+}
+
+impl error::Error for ScriptError {}
+
+impl From<ParseError> for ScriptError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<AnalyzerError> for ScriptError {
+    fn from(e: AnalyzerError) -> Self {
+        Self::Early(e)
+    }
+}
+
+/// Prepares a script to be run: it is lexed and parsed, the scope analysis resolves its
+/// identifiers and reports its early errors, then it is optimized with the given options.
+///
+/// This is the pipeline shared by all the front ends, like the CLI, the test runner and the C
+/// API, so that they accept the same scripts.
+pub fn parse<R>(source: R, options: OptimizerOptions) -> Result<StatementList, ScriptError>
+where
+    R: Read,
+{
+    let mut script = Parser::from_lexer(Lexer::new(source)).parse_all()?;
+    analyze(&mut script)?;
+    optimize(&mut script, options);
+    Ok(script)
+}
@@ -0,0 +1,189 @@
+//! Optimizations on the AST.
+//!
+//! The optimizer rewrites a parsed script before it's executed. It can fold the operations whose
+//! operands are constant, and remove the code that can never be run. The operations are folded
+//! with the same `Value` operators the interpreter uses, so the result is the same as if the
+//! operation had been run.
+
+use crate::{
+    builtins::value::{Value, ValueData},
+    syntax::ast::{
+        node::{ArrowFunctionDecl, Block, FunctionDecl, FunctionExpr, Node, StatementList},
+        op::{self, NumOp},
+        visitor::{self, VisitorMut},
+        Const,
+    },
+};
+use bitflags::bitflags;
+use std::mem;
+
+#[cfg(test)]
+mod tests;
+
+bitflags! {
+    /// The optimizations to run on a script before executing it.
+    ///
+    /// No optimization is run by default.
+    #[derive(Default)]
+    pub struct OptimizerOptions: u8 {
+        /// Replaces the arithmetic operations and string concatenations of constants with
+        /// their result.
+        const CONSTANT_FOLDING = 0b0000_0001;
+        /// Replaces `if` statements on a constant condition with the branch that will run, and
+        /// removes the statements after a `return` or `throw` statement.
+        const DEAD_CODE_ELIMINATION = 0b0000_0010;
+    }
+}
+
+/// Runs the given optimizations on a script.
+pub fn optimize(script: &mut StatementList, options: OptimizerOptions) {
+    if !options.is_empty() {
+        Optimizer { options }.visit_statement_list_mut(script);
+    }
+}
+
+/// The optimizer, which rewrites the nodes after optimizing their children.
+#[derive(Debug, Clone, Copy)]
+struct Optimizer {
+    options: OptimizerOptions,
+}
+
+impl Optimizer {
+    /// Removes the statements that come after a `return` or `throw` statement in a list.
+    fn eliminate_unreachable(&self, list: &mut StatementList) {
+        if !self
+            .options
+            .contains(OptimizerOptions::DEAD_CODE_ELIMINATION)
+        {
+            return;
+        }
+
+        if let Some(pos) = list
+            .statements()
+            .iter()
+            .position(|node| matches!(node, Node::Return(_) | Node::Throw(_)))
+        {
+            list.truncate(pos + 1);
+        }
+    }
+}
+
+impl VisitorMut for Optimizer {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        visitor::walk_node_mut(self, node);
+
+        if self.options.contains(OptimizerOptions::CONSTANT_FOLDING) {
+            if let Some(folded) = fold(node) {
+                *node = Node::Const(folded);
+            }
+        }
+
+        if self
+            .options
+            .contains(OptimizerOptions::DEAD_CODE_ELIMINATION)
+        {
+            if let Node::If(ref mut if_stmt) = node {
+                if let Node::Const(ref cond) = if_stmt.cond() {
+                    let branch = if const_to_value(cond).is_true() {
+                        Some(if_stmt.body_mut())
+                    } else {
+                        if_stmt.else_node_mut()
+                    };
+
+                    // An `if` statement that doesn't run any branch evaluates to `undefined`.
+                    let branch = branch.map_or(Node::Const(Const::Undefined), |branch| {
+                        mem::replace(branch, Node::Const(Const::Undefined))
+                    });
+                    *node = branch;
+                }
+            }
+        }
+    }
+
+    fn visit_statement_list_mut(&mut self, list: &mut StatementList) {
+        visitor::walk_statement_list_mut(self, list);
+        self.eliminate_unreachable(list);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        visitor::walk_block_mut(self, block);
+        self.eliminate_unreachable(block.statement_list_mut());
+    }
+
+    fn visit_function_decl_mut(&mut self, function: &mut FunctionDecl) {
+        visitor::walk_function_decl_mut(self, function);
+        self.eliminate_unreachable(function.body_mut());
+    }
+
+    fn visit_function_expr_mut(&mut self, function: &mut FunctionExpr) {
+        visitor::walk_function_expr_mut(self, function);
+        self.eliminate_unreachable(function.body_mut());
+    }
+
+    fn visit_arrow_function_decl_mut(&mut self, function: &mut ArrowFunctionDecl) {
+        visitor::walk_arrow_function_decl_mut(self, function);
+        self.eliminate_unreachable(function.body_mut());
+    }
+}
+
+/// Computes the result of an operation on constants, if it can be known before running it.
+fn fold(node: &Node) -> Option<Const> {
+    let value = match node {
+        Node::BinOp(bin_op) => {
+            let (a, b) = (foldable(bin_op.lhs())?, foldable(bin_op.rhs())?);
+            match bin_op.op() {
+                op::BinOp::Num(NumOp::Add) => a + b,
+                op::BinOp::Num(NumOp::Sub) => a - b,
+                op::BinOp::Num(NumOp::Mul) => a * b,
+                op::BinOp::Num(NumOp::Exp) => a.as_num_to_power(b),
+                op::BinOp::Num(NumOp::Div) => a / b,
+                op::BinOp::Num(NumOp::Mod) => a % b,
+                _ => return None,
+            }
+        }
+        Node::UnaryOp(unary_op) => {
+            let a = foldable(unary_op.target())?;
+            match unary_op.op() {
+                op::UnaryOp::Minus => -a,
+                op::UnaryOp::Plus => Value::from(a.to_number()),
+                op::UnaryOp::Not => !a,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    Some(match value.data() {
+        ValueData::Null => Const::Null,
+        ValueData::Undefined => Const::Undefined,
+        ValueData::Boolean(b) => Const::Bool(*b),
+        ValueData::String(ref s) => Const::from(s.clone()),
+        ValueData::Rational(num) => Const::Num(*num),
+        ValueData::Integer(num) => Const::Int(*num),
+        _ => return None,
+    })
+}
+
+/// Gets the value of a constant operand that can be folded.
+///
+/// `BigInt` operations are never folded, since some of them fail when run.
+fn foldable(node: &Node) -> Option<Value> {
+    match node {
+        Node::Const(Const::BigInt(_)) => None,
+        Node::Const(c) => Some(const_to_value(c)),
+        _ => None,
+    }
+}
+
+/// Creates the value of a constant, as the interpreter does.
+fn const_to_value(c: &Const) -> Value {
+    match *c {
+        Const::Null => Value::null(),
+        Const::Undefined => Value::undefined(),
+        Const::Num(num) => Value::rational(num),
+        Const::Int(num) => Value::integer(num),
+        Const::BigInt(ref num) => Value::from(num.clone()),
+        Const::String(ref s) => Value::string(s.clone()),
+        Const::Bool(b) => Value::boolean(b),
+    }
+}
@@ -0,0 +1,106 @@
+//! Tests for the AST optimizer.
+
+use super::*;
+use crate::{
+    exec::Interpreter,
+    forward,
+    realm::Realm,
+    syntax::{lexer::Lexer, parser::Parser},
+};
+
+/// Parses and optimizes the given JavaScript source code.
+fn optimized(src: &str, options: OptimizerOptions) -> StatementList {
+    let mut script = Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .expect("failed to parse");
+    optimize(&mut script, options);
+    script
+}
+
+/// Parses the given JavaScript source code.
+fn parse(src: &str) -> StatementList {
+    optimized(src, OptimizerOptions::empty())
+}
+
+#[test]
+fn constant_folding() {
+    let script = optimized(
+        "1 + 2 * 3; 'a' + 1 + 'b'; -(4 - 6); !0; 2 ** 10 / 4 % 5; 10n + 1n",
+        OptimizerOptions::CONSTANT_FOLDING,
+    );
+
+    assert_eq!(
+        script.statements()[..5],
+        [
+            Node::Const(Const::Num(7.0)),
+            Node::Const(Const::from("a1b")),
+            Node::Const(Const::Num(2.0)),
+            Node::Const(Const::Bool(true)),
+            Node::Const(Const::Num(1.0)),
+        ]
+    );
+    assert!(matches!(script.statements()[5], Node::BinOp(_)));
+}
+
+#[test]
+fn no_folding_of_variables() {
+    let src = "let a = 1; a + 2; 3 + a; -a;";
+    assert_eq!(
+        optimized(src, OptimizerOptions::CONSTANT_FOLDING),
+        parse(src)
+    );
+}
+
+#[test]
+fn constant_branches() {
+    let options = OptimizerOptions::all();
+
+    assert_eq!(
+        optimized("if (1 - 1) { a(); } else { b(); }", options),
+        parse("{ b(); }")
+    );
+    assert_eq!(
+        optimized("if ('yes') a(); else b();", options),
+        parse("a();")
+    );
+    assert_eq!(optimized("if (false) a();", options), parse("undefined;"));
+}
+
+#[test]
+fn unreachable_statements() {
+    let options = OptimizerOptions::DEAD_CODE_ELIMINATION;
+
+    assert_eq!(
+        optimized("function f() { a(); return 1; b(); c(); }", options),
+        parse("function f() { a(); return 1; }")
+    );
+    assert_eq!(
+        optimized("{ throw a; b(); }", options),
+        parse("{ throw a; }")
+    );
+    assert_eq!(
+        optimized("let f = () => { return; a(); };", options),
+        parse("let f = () => { return; };")
+    );
+}
+
+#[test]
+fn same_results() {
+    let scenarios = [
+        "1 + 2 * 3",
+        "'a' + 1 + 'b'",
+        "-(4 - 6) + !0",
+        "if (0) { 1 } else { 2 }",
+        "function f() { return 1; return 2; } f()",
+        "let a = 5; if (a) { a + 1 * 2 }",
+    ];
+
+    for scenario in scenarios.iter() {
+        let mut engine = Interpreter::new(Realm::create());
+        let expected = forward(&mut engine, scenario);
+
+        let mut engine = Interpreter::new(Realm::create());
+        engine.set_optimizer_options(OptimizerOptions::all());
+        assert_eq!(forward(&mut engine, scenario), expected);
+    }
+}
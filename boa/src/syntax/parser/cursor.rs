@@ -1,29 +1,170 @@
 //! Cursor implementation for the parser.
 
 use super::ParseError;
-use crate::syntax::ast::{
-    token::{Token, TokenKind},
-    Punctuator,
+use crate::syntax::{
+    ast::{
+        token::{Token, TokenKind},
+        Punctuator,
+    },
+    lexer::LexerError,
 };
+use std::fmt;
+
+/// The maximum depth of the nested expressions and statements.
+///
+/// Deeper source code is rejected with an error, instead of growing the stack without bounds.
+pub(super) const MAX_NESTING_DEPTH: usize = 1024;
+
+/// The stack space left below which a nested construct is parsed on a new stack segment.
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+/// The size of the stack segments allocated for deeply nested source code.
+const STACK_SEGMENT_SIZE: usize = 4 * 1024 * 1024;
 
 /// Token cursor.
 ///
 /// This internal structure gives basic testable operations to the parser.
-#[derive(Debug, Clone, Default)]
+///
+/// Tokens are pulled from the token source only when they are needed. The tokens read so far
+/// are kept, so that the cursor can go back to a previous position.
 pub(super) struct Cursor<'a> {
-    /// The tokens being input.
-    tokens: &'a [Token],
+    /// The tokens read so far from the source.
+    tokens: Vec<Token>,
+    /// The source of the tokens not read yet.
+    source: Box<dyn Iterator<Item = Result<Token, LexerError>> + 'a>,
+    /// The first error found in the token source, if any.
+    error: Option<LexerError>,
     /// The current position within the tokens.
     pos: usize,
+    /// The number of nested expressions and statements being parsed.
+    depth: usize,
+    /// Whether the code being parsed is strict mode code.
+    strict: bool,
+}
+
+impl fmt::Debug for Cursor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("tokens", &self.tokens)
+            .field("error", &self.error)
+            .field("pos", &self.pos)
+            .field("depth", &self.depth)
+            .field("strict", &self.strict)
+            .finish()
+    }
 }
 
 impl<'a> Cursor<'a> {
     /// Creates a new cursor.
-    pub(super) fn new(tokens: &'a [Token]) -> Self {
+    pub(super) fn new<I>(source: I) -> Self
+    where
+        I: IntoIterator<Item = Result<Token, LexerError>>,
+        I::IntoIter: 'a,
+    {
         Self {
-            tokens,
-            ..Self::default()
+            tokens: Vec::new(),
+            // Comments are not part of the syntactic grammar.
+            source: Box::new(source.into_iter().filter(|token| match token {
+                Ok(Token {
+                    kind: TokenKind::Comment(_, _),
+                    ..
+                }) => false,
+                _ => true,
+            })),
+            error: None,
+            pos: 0,
+            depth: 0,
+            strict: false,
+        }
+    }
+
+    /// Takes the first error found in the token source, if any.
+    ///
+    /// Once an error has been found, the cursor behaves as if the token stream had ended.
+    pub(super) fn take_error(&mut self) -> Option<LexerError> {
+        self.error.take()
+    }
+
+    /// Gets the token at the given index, reading it from the source if needed.
+    fn get(&mut self, index: usize) -> Option<&Token> {
+        while self.tokens.len() <= index && self.error.is_none() {
+            match self.source.next() {
+                Some(Ok(tk)) => self.tokens.push(tk),
+                Some(Err(e)) => self.error = Some(e),
+                None => break,
+            }
         }
+
+        self.tokens.get(index)
+    }
+
+    /// Parses a nested expression or statement, one level deeper than the current one.
+    ///
+    /// An error is returned if the source is nested more than `MAX_NESTING_DEPTH` levels deep.
+    /// The stack is extended on the heap when it runs low, so that untrusted source code can't
+    /// overflow it.
+    pub(super) fn nested<T, F>(&mut self, parse: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, ParseError>,
+    {
+        if self.depth >= MAX_NESTING_DEPTH {
+            let token = self.peek(0).ok_or(ParseError::AbruptEnd)?;
+            return Err(ParseError::general(
+                "the source is nested too deeply",
+                token.span().start(),
+            ));
+        }
+
+        self.depth += 1;
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_SEGMENT_SIZE, || parse(self));
+        self.depth -= 1;
+        result
+    }
+
+    /// Checks if the code being parsed is strict mode code.
+    pub(super) fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Sets whether the code being parsed is strict mode code.
+    pub(super) fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Checks if the statements starting at the cursor begin with a directive prologue which
+    /// contains a `'use strict'` directive, without moving the cursor.
+    ///
+    /// More information:
+    ///  - [ECMAScript specification][spec]
+    ///
+    /// [spec]: https://tc39.es/ecma262/#sec-directive-prologues-and-the-use-strict-directive
+    pub(super) fn has_use_strict_directive(&mut self) -> bool {
+        let mut skip = 0;
+        while let Some(token) = self.peek(skip) {
+            let directive = match token.kind {
+                TokenKind::StringLiteral(ref directive) => directive,
+                _ => return false,
+            };
+
+            // A directive is a whole expression statement.
+            skip += 1;
+            match self.peek(skip) {
+                Some(next) if next.kind == TokenKind::Punctuator(Punctuator::Semicolon) => {
+                    skip += 1;
+                }
+                Some(next)
+                    if next.kind == TokenKind::Punctuator(Punctuator::CloseBlock)
+                        || next.span().start().line_number() > token.span().end().line_number() => {
+                }
+                Some(_) => return false,
+                None => {}
+            }
+
+            if *directive == "use strict" {
+                return true;
+            }
+        }
+        false
     }
 
     /// Retrieves the current position of the cursor in the token stream.
@@ -39,9 +180,9 @@ impl<'a> Cursor<'a> {
     }
 
     /// Moves the cursor to the next token and returns the token.
-    pub(super) fn next(&mut self) -> Option<&'a Token> {
+    pub(super) fn next(&mut self) -> Option<Token> {
         loop {
-            let token = self.tokens.get(self.pos);
+            let token = self.get(self.pos).cloned();
             if let Some(tk) = token {
                 self.pos += 1;
 
@@ -55,17 +196,18 @@ impl<'a> Cursor<'a> {
     }
 
     /// Peeks the next token without moving the cursor.
-    pub(super) fn peek(&self, skip: usize) -> Option<&'a Token> {
+    pub(super) fn peek(&mut self, skip: usize) -> Option<Token> {
         let mut count = 0;
         let mut skipped = 0;
         loop {
-            let token = self.tokens.get(self.pos + count);
+            let pos = self.pos + count;
+            let token = self.get(pos);
             count += 1;
 
             if let Some(tk) = token {
                 if tk.kind != TokenKind::LineTerminator {
                     if skipped == skip {
-                        break Some(tk);
+                        break Some(tk.clone());
                     }
 
                     skipped += 1;
@@ -84,36 +226,17 @@ impl<'a> Cursor<'a> {
         );
 
         self.pos -= 1;
-        while self
-            .tokens
-            .get(self.pos - 1)
-            .expect("token disappeared")
-            .kind
-            == TokenKind::LineTerminator
-            && self.pos > 0
-        {
+        while self.pos > 0 && self.tokens[self.pos - 1].kind == TokenKind::LineTerminator {
             self.pos -= 1;
         }
     }
 
     /// Peeks the previous token without moving the cursor.
-    pub(super) fn peek_prev(&self) -> Option<&'a Token> {
-        if self.pos == 0 {
-            None
-        } else {
-            let mut back = 1;
-            let mut tok = self.tokens.get(self.pos - back).expect("token disappeared");
-            while self.pos >= back && tok.kind == TokenKind::LineTerminator {
-                back += 1;
-                tok = self.tokens.get(self.pos - back).expect("token disappeared");
-            }
-
-            if back == self.pos {
-                None
-            } else {
-                Some(tok)
-            }
-        }
+    pub(super) fn peek_prev(&self) -> Option<&Token> {
+        self.tokens[..self.pos]
+            .iter()
+            .rev()
+            .find(|tk| tk.kind != TokenKind::LineTerminator)
     }
 
     /// Returns an error if the next token is not of kind `kind`.
@@ -129,11 +252,7 @@ impl<'a> Cursor<'a> {
         if next_token.kind == kind {
             Ok(())
         } else {
-            Err(ParseError::expected(
-                vec![kind],
-                next_token.clone(),
-                context,
-            ))
+            Err(ParseError::expected(vec![kind], next_token, context))
         }
     }
 
@@ -142,8 +261,9 @@ impl<'a> Cursor<'a> {
     /// It will automatically insert a semicolon if needed, as specified in the [spec][spec].
     ///
     /// [spec]: https://tc39.es/ecma262/#sec-automatic-semicolon-insertion
-    pub(super) fn peek_semicolon(&self, do_while: bool) -> (bool, Option<&Token>) {
-        match self.tokens.get(self.pos) {
+    pub(super) fn peek_semicolon(&mut self, do_while: bool) -> (bool, Option<Token>) {
+        let pos = self.pos;
+        match self.get(pos).cloned() {
             Some(tk) => match tk.kind {
                 TokenKind::Punctuator(Punctuator::Semicolon) => (true, Some(tk)),
                 TokenKind::LineTerminator | TokenKind::Punctuator(Punctuator::CloseBlock) => {
@@ -193,13 +313,22 @@ impl<'a> Cursor<'a> {
             (true, None) => Ok(()),
             (false, Some(tk)) => Err(ParseError::expected(
                 vec![TokenKind::Punctuator(Punctuator::Semicolon)],
-                tk.clone(),
+                tk,
                 context,
             )),
             (false, None) => unreachable!(),
         }
     }
 
+    /// Checks if the next token is a line terminator, without skipping it.
+    pub(super) fn peek_is_line_terminator(&mut self) -> bool {
+        let pos = self.pos;
+        match self.get(pos) {
+            Some(tk) => tk.kind == TokenKind::LineTerminator,
+            None => false,
+        }
+    }
+
     /// It will make sure that the next token is not a line terminator.
     ///
     /// It expects that the token stream does not end here.
@@ -207,7 +336,8 @@ impl<'a> Cursor<'a> {
         let mut count = 0;
         let mut skipped = 0;
         loop {
-            let token = self.tokens.get(self.pos + count);
+            let pos = self.pos + count;
+            let token = self.get(pos);
             count += 1;
             if let Some(tk) = token {
                 if skipped == skip && tk.kind == TokenKind::LineTerminator {
@@ -227,7 +357,7 @@ impl<'a> Cursor<'a> {
     ///
     /// When the next token is a `kind` token, get the token, otherwise return `None`. This
     /// function skips line terminators.
-    pub(super) fn next_if<K>(&mut self, kind: K) -> Option<&'a Token>
+    pub(super) fn next_if<K>(&mut self, kind: K) -> Option<Token>
     where
         K: Into<TokenKind>,
     {
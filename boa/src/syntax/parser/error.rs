@@ -1,8 +1,11 @@
 //! Error and result implementation for the parser.
-use crate::syntax::ast::{
-    position::Position,
-    token::{Token, TokenKind},
-    Node,
+use crate::syntax::{
+    ast::{
+        position::Position,
+        token::{Token, TokenKind},
+        Node,
+    },
+    lexer::LexerError,
 };
 use std::fmt;
 
@@ -40,6 +43,8 @@ pub enum ParseError {
         message: &'static str,
         position: Position,
     },
+    /// When the token stream could not be lexed
+    Lex { err: LexerError },
 }
 
 impl ParseError {
@@ -79,6 +84,17 @@ impl ParseError {
     pub(super) fn general(message: &'static str, position: Position) -> Self {
         Self::General { message, position }
     }
+
+    /// Gets the position in the source code where the error was found, if it is known.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Self::Expected { found, .. } | Self::Unexpected { found, .. } => {
+                Some(found.span().start())
+            }
+            Self::General { position, .. } => Some(*position),
+            Self::AbruptEnd | Self::Lex { .. } => None,
+        }
+    }
 }
 
 impl fmt::Display for ParseError {
@@ -143,6 +159,7 @@ impl fmt::Display for ParseError {
                 position.line_number(),
                 position.column_number()
             ),
+            Self::Lex { err } => write!(f, "{}", err),
         }
     }
 }
@@ -16,7 +16,7 @@ use crate::{
     syntax::{
         ast::{
             node::{Assign, BinOp, Node},
-            Keyword, Punctuator, TokenKind,
+            op, Keyword, Punctuator, TokenKind,
         },
         parser::{AllowAwait, AllowIn, AllowYield, Cursor, ParseError, ParseResult, TokenParser},
     },
@@ -73,60 +73,67 @@ impl TokenParser for AssignmentExpression {
     type Output = Node;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
-        let _timer = BoaProfiler::global().start_event("AssignmentExpression", "Parsing");
-        // Arrow function
-        let next_token = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
-        match next_token.kind {
-            // a=>{}
-            TokenKind::Identifier(_)
-            | TokenKind::Keyword(Keyword::Yield)
-            | TokenKind::Keyword(Keyword::Await)
-                if cursor.peek_expect_no_lineterminator(1).is_ok() =>
-            {
-                if let Some(tok) = cursor.peek(1) {
-                    if tok.kind == TokenKind::Punctuator(Punctuator::Arrow) {
-                        return ArrowFunction::new(
-                            self.allow_in,
-                            self.allow_yield,
-                            self.allow_await,
-                        )
-                        .parse(cursor)
-                        .map(Node::ArrowFunctionDecl);
+        cursor.nested(|cursor| {
+            let _timer = BoaProfiler::global().start_event("AssignmentExpression", "Parsing");
+            // Arrow function
+            let next_token = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+            match next_token.kind {
+                // a=>{}
+                TokenKind::Identifier(_)
+                | TokenKind::Keyword(Keyword::Yield)
+                | TokenKind::Keyword(Keyword::Await)
+                    if cursor.peek_expect_no_lineterminator(1).is_ok() =>
+                {
+                    if let Some(tok) = cursor.peek(1) {
+                        if tok.kind == TokenKind::Punctuator(Punctuator::Arrow) {
+                            return ArrowFunction::new(
+                                self.allow_in,
+                                self.allow_yield,
+                                self.allow_await,
+                            )
+                            .parse(cursor)
+                            .map(Node::ArrowFunctionDecl);
+                        }
                     }
                 }
-            }
-            // (a,b)=>{}
-            TokenKind::Punctuator(Punctuator::OpenParen) => {
-                if let Some(node) =
-                    ArrowFunction::new(self.allow_in, self.allow_yield, self.allow_await)
-                        .try_parse(cursor)
-                        .map(Node::ArrowFunctionDecl)
-                {
-                    return Ok(node);
+                // (a,b)=>{}
+                TokenKind::Punctuator(Punctuator::OpenParen) => {
+                    if let Some(node) =
+                        ArrowFunction::new(self.allow_in, self.allow_yield, self.allow_await)
+                            .try_parse(cursor)
+                            .map(Node::ArrowFunctionDecl)
+                    {
+                        return Ok(node);
+                    }
                 }
+                _ => {}
             }
-            _ => {}
-        }
 
-        let mut lhs = ConditionalExpression::new(self.allow_in, self.allow_yield, self.allow_await)
-            .parse(cursor)?;
+            let mut lhs =
+                ConditionalExpression::new(self.allow_in, self.allow_yield, self.allow_await)
+                    .parse(cursor)?;
 
-        if let Some(tok) = cursor.next() {
-            match tok.kind {
-                TokenKind::Punctuator(Punctuator::Assign) => {
-                    lhs = Assign::new(lhs, self.parse(cursor)?).into();
-                }
-                TokenKind::Punctuator(p) if p.as_binop().is_some() => {
-                    let expr = self.parse(cursor)?;
-                    let binop = p.as_binop().expect("binop disappeared");
-                    lhs = BinOp::new(binop, lhs, expr).into();
-                }
-                _ => {
-                    cursor.back();
+            if let Some(tok) = cursor.next() {
+                match tok.kind {
+                    TokenKind::Punctuator(Punctuator::Assign) => {
+                        lhs = Assign::new(lhs, self.parse(cursor)?).into();
+                    }
+                    // Only the assignment operators, the others are left to the expressions
+                    // which contain this one, like the comma operator.
+                    TokenKind::Punctuator(p)
+                        if matches!(p.as_binop(), Some(op::BinOp::Assign(_))) =>
+                    {
+                        let expr = self.parse(cursor)?;
+                        let binop = p.as_binop().expect("binop disappeared");
+                        lhs = BinOp::new(binop, lhs, expr).into();
+                    }
+                    _ => {
+                        cursor.back();
+                    }
                 }
             }
-        }
 
-        Ok(lhs)
+            Ok(lhs)
+        })
     }
 }
@@ -58,11 +58,25 @@ impl TokenParser for MemberExpression {
             == TokenKind::Keyword(Keyword::New)
         {
             let _ = cursor.next().expect("keyword disappeared");
-            let lhs = self.parse(cursor)?;
-            let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
-            let call_node = Call::new(lhs, args);
+            if cursor.next_if(Punctuator::Dot).is_some() {
+                let tok = cursor.next().ok_or(ParseError::AbruptEnd)?;
+                match tok.kind {
+                    TokenKind::Identifier(ref name) if name.as_str() == "target" => Node::NewTarget,
+                    _ => {
+                        return Err(ParseError::expected(
+                            vec![TokenKind::identifier("target")],
+                            tok.clone(),
+                            "new.target expression",
+                        ))
+                    }
+                }
+            } else {
+                let lhs = self.parse(cursor)?;
+                let args = Arguments::new(self.allow_yield, self.allow_await).parse(cursor)?;
+                let call_node = Call::new(lhs, args);
 
-            Node::from(New::from(call_node))
+                Node::from(New::from(call_node))
+            }
         } else {
             PrimaryExpression::new(self.allow_yield, self.allow_await).parse(cursor)?
         };
@@ -72,7 +86,7 @@ impl TokenParser for MemberExpression {
                     let _ = cursor.next().ok_or(ParseError::AbruptEnd)?; // We move the cursor forward.
                     match &cursor.next().ok_or(ParseError::AbruptEnd)?.kind {
                         TokenKind::Identifier(name) => {
-                            lhs = GetConstField::new(lhs, name.clone()).into()
+                            lhs = GetConstField::new(lhs, name.as_str()).into()
                         }
                         TokenKind::Keyword(kw) => {
                             lhs = GetConstField::new(lhs, kw.to_string()).into()
@@ -17,7 +17,7 @@ mod update;
 
 use self::assignment::ExponentiationExpression;
 pub(super) use self::{assignment::AssignmentExpression, primary::Initializer};
-use super::{AllowAwait, AllowIn, AllowYield, Cursor, ParseResult, TokenParser};
+use super::{AllowAwait, AllowIn, AllowYield, Cursor, ParseError, ParseResult, TokenParser};
 use crate::{
     profiler::BoaProfiler,
     syntax::ast::{
@@ -59,17 +59,23 @@ macro_rules! expression { ($name:ident, $lower:ident, [$( $op:path ),*], [$( $lo
             while let Some(tok) = cursor.peek(0) {
                 match tok.kind {
                     TokenKind::Punctuator(op) if $( op == $op )||* => {
-                        let _ = cursor.next().expect("token disappeared");
+                        let tok = cursor.next().expect("token disappeared");
+                        let op = op
+                            .as_binop()
+                            .ok_or_else(|| ParseError::unexpected(tok, "unsupported operator"))?;
                         lhs = BinOp::new(
-                            op.as_binop().expect("Could not get binary operation."),
+                            op,
                             lhs,
                             $lower::new($( self.$low_param ),*).parse(cursor)?
                         ).into();
                     }
                     TokenKind::Keyword(op) if $( op == $op )||* => {
-                        let _ = cursor.next().expect("token disappeared");
+                        let tok = cursor.next().expect("token disappeared");
+                        let op = op
+                            .as_binop()
+                            .ok_or_else(|| ParseError::unexpected(tok, "unsupported operator"))?;
                         lhs = BinOp::new(
-                            op.as_binop().expect("Could not get binary operation."),
+                            op,
                             lhs,
                             $lower::new($( self.$low_param ),*).parse(cursor)?
                         ).into();
@@ -392,7 +398,8 @@ expression!(
         Punctuator::GreaterThan,
         Punctuator::LessThanOrEq,
         Punctuator::GreaterThanOrEq,
-        Keyword::In
+        Keyword::In,
+        Keyword::InstanceOf
     ],
     [allow_yield, allow_await]
 );
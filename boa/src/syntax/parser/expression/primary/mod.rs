@@ -20,7 +20,7 @@ use self::{
 use super::Expression;
 use crate::syntax::{
     ast::{
-        node::{Call, Identifier, New, Node},
+        node::{Identifier, Node, RegExpLiteral},
         token::NumericLiteral,
         Const, Keyword, Punctuator, TokenKind,
     },
@@ -86,12 +86,12 @@ impl TokenParser for PrimaryExpression {
             }
             TokenKind::BooleanLiteral(boolean) => Ok(Const::from(*boolean).into()),
             // TODO: ADD TokenKind::UndefinedLiteral
-            TokenKind::Identifier(ref i) if i.as_ref() == "undefined" => {
+            TokenKind::Identifier(ref i) if i.as_str() == "undefined" => {
                 Ok(Const::Undefined.into())
             }
             TokenKind::NullLiteral => Ok(Const::Null.into()),
-            TokenKind::Identifier(ident) => Ok(Identifier::from(ident.as_ref()).into()), // TODO: IdentifierReference
-            TokenKind::StringLiteral(s) => Ok(Const::from(s.as_ref()).into()),
+            TokenKind::Identifier(ident) => Ok(Identifier::from(*ident).into()), // TODO: IdentifierReference
+            TokenKind::StringLiteral(s) => Ok(Const::from(s.clone()).into()),
             TokenKind::NumericLiteral(NumericLiteral::Integer(num)) => Ok(Const::from(*num).into()),
             TokenKind::NumericLiteral(NumericLiteral::Rational(num)) => {
                 Ok(Const::from(*num).into())
@@ -100,13 +100,7 @@ impl TokenParser for PrimaryExpression {
                 Ok(Const::from(num.clone()).into())
             }
             TokenKind::RegularExpressionLiteral(body, flags) => {
-                Ok(Node::from(New::from(Call::new(
-                    Identifier::from("RegExp"),
-                    vec![
-                        Const::from(body.as_ref()).into(),
-                        Const::from(flags.to_string()).into(),
-                    ],
-                ))))
+                Ok(RegExpLiteral::new(body.as_ref(), *flags).into())
             }
             _ => Err(ParseError::unexpected(tok.clone(), "primary expression")),
         }
@@ -14,7 +14,7 @@ use crate::{
     syntax::{
         ast::{
             node::{self, FunctionExpr, MethodDefinitionKind, Node, Object},
-            token::{Token, TokenKind},
+            token::TokenKind,
             Punctuator,
         },
         parser::{
@@ -128,7 +128,7 @@ impl TokenParser for PropertyDefinition {
 
         let prop_name = cursor
             .next()
-            .map(Token::to_string)
+            .map(|tok| tok.to_string())
             .ok_or(ParseError::AbruptEnd)?;
         if cursor.next_if(Punctuator::Colon).is_some() {
             let val = AssignmentExpression::new(true, self.allow_yield, self.allow_await)
@@ -190,13 +190,13 @@ impl TokenParser for MethodDefinition {
             idn @ "get" | idn @ "set" => {
                 let prop_name = cursor
                     .next()
-                    .map(Token::to_string)
+                    .map(|tok| tok.to_string())
                     .ok_or(ParseError::AbruptEnd)?;
                 cursor.expect(
                     TokenKind::Punctuator(Punctuator::OpenParen),
                     "property method definition",
                 )?;
-                let first_param = cursor.peek(0).expect("current token disappeared").clone();
+                let first_param = cursor.peek(0).expect("current token disappeared");
                 let params = FormalParameters::new(false, false).parse(cursor)?;
                 cursor.expect(Punctuator::CloseParen, "method definition")?;
                 if idn == "get" {
@@ -1,4 +1,7 @@
-use crate::syntax::{ast::Const, parser::tests::check_parser};
+use crate::syntax::{
+    ast::{node::RegExpLiteral, Const},
+    parser::tests::check_parser,
+};
 
 #[test]
 fn check_string() {
@@ -8,3 +11,11 @@ fn check_string() {
     // Check non-empty string
     check_parser("\"hello\"", vec![Const::from("hello").into()]);
 }
+
+#[test]
+fn check_regexp_literal() {
+    check_parser(
+        "/[a-z]+/gi",
+        vec![RegExpLiteral::new("[a-z]+", "gi".parse().unwrap()).into()],
+    );
+}
@@ -1,10 +1,10 @@
 use crate::syntax::{
-    ast::op::{AssignOp, BitOp, CompOp, NumOp},
+    ast::op::{self, AssignOp, BitOp, CompOp, NumOp},
     ast::{
-        node::{BinOp, Identifier},
+        node::{field::GetConstField, Assign, BinOp, Identifier, Node},
         Const,
     },
-    parser::tests::check_parser,
+    parser::tests::{check_invalid, check_parser},
 };
 
 /// Checks numeric operations
@@ -231,4 +231,60 @@ fn check_relational_operations() {
         "p in o",
         vec![BinOp::new(CompOp::In, Identifier::from("p"), Identifier::from("o")).into()],
     );
+    check_parser(
+        "a instanceof b",
+        vec![BinOp::new(
+            CompOp::InstanceOf,
+            Identifier::from("a"),
+            Identifier::from("b"),
+        )
+        .into()],
+    );
+}
+
+/// Checks the comma operator, which is left-associative and binds less tightly than assignments.
+#[test]
+fn check_comma_operator() {
+    check_parser(
+        "a, b, c",
+        vec![BinOp::new(
+            op::BinOp::Comma,
+            BinOp::new(
+                op::BinOp::Comma,
+                Identifier::from("a"),
+                Identifier::from("b"),
+            ),
+            Identifier::from("c"),
+        )
+        .into()],
+    );
+    check_parser(
+        "a = 1, b += 2",
+        vec![BinOp::new(
+            op::BinOp::Comma,
+            Assign::new(Identifier::from("a"), Const::from(1)),
+            BinOp::new(AssignOp::Add, Identifier::from("b"), Const::from(2)),
+        )
+        .into()],
+    );
+}
+
+/// Checks that the increment and decrement operators only accept variables and properties.
+#[test]
+fn check_invalid_update_targets() {
+    check_invalid("1++");
+    check_invalid("++f()");
+    check_invalid("--(a + b)");
+    check_invalid("this--");
+}
+
+/// Checks the `new.target` meta property.
+#[test]
+fn check_new_target() {
+    check_parser("new.target", vec![Node::NewTarget]);
+    check_parser(
+        "new.target.name",
+        vec![GetConstField::new(Node::NewTarget, "name").into()],
+    );
+    check_invalid("new.foo");
 }
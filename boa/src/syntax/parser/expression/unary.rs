@@ -51,33 +51,35 @@ impl TokenParser for UnaryExpression {
     type Output = Node;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
-        let tok = cursor.next().ok_or(ParseError::AbruptEnd)?;
-        match tok.kind {
-            TokenKind::Keyword(Keyword::Delete) => {
-                Ok(node::UnaryOp::new(UnaryOp::Delete, self.parse(cursor)?).into())
+        cursor.nested(|cursor| {
+            let tok = cursor.next().ok_or(ParseError::AbruptEnd)?;
+            match tok.kind {
+                TokenKind::Keyword(Keyword::Delete) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Delete, self.parse(cursor)?).into())
+                }
+                TokenKind::Keyword(Keyword::Void) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Void, self.parse(cursor)?).into())
+                }
+                TokenKind::Keyword(Keyword::TypeOf) => {
+                    Ok(node::UnaryOp::new(UnaryOp::TypeOf, self.parse(cursor)?).into())
+                }
+                TokenKind::Punctuator(Punctuator::Add) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Plus, self.parse(cursor)?).into())
+                }
+                TokenKind::Punctuator(Punctuator::Sub) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Minus, self.parse(cursor)?).into())
+                }
+                TokenKind::Punctuator(Punctuator::Neg) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Tilde, self.parse(cursor)?).into())
+                }
+                TokenKind::Punctuator(Punctuator::Not) => {
+                    Ok(node::UnaryOp::new(UnaryOp::Not, self.parse(cursor)?).into())
+                }
+                _ => {
+                    cursor.back();
+                    UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor)
+                }
             }
-            TokenKind::Keyword(Keyword::Void) => {
-                Ok(node::UnaryOp::new(UnaryOp::Void, self.parse(cursor)?).into())
-            }
-            TokenKind::Keyword(Keyword::TypeOf) => {
-                Ok(node::UnaryOp::new(UnaryOp::TypeOf, self.parse(cursor)?).into())
-            }
-            TokenKind::Punctuator(Punctuator::Add) => {
-                Ok(node::UnaryOp::new(UnaryOp::Plus, self.parse(cursor)?).into())
-            }
-            TokenKind::Punctuator(Punctuator::Sub) => {
-                Ok(node::UnaryOp::new(UnaryOp::Minus, self.parse(cursor)?).into())
-            }
-            TokenKind::Punctuator(Punctuator::Neg) => {
-                Ok(node::UnaryOp::new(UnaryOp::Tilde, self.parse(cursor)?).into())
-            }
-            TokenKind::Punctuator(Punctuator::Not) => {
-                Ok(node::UnaryOp::new(UnaryOp::Not, self.parse(cursor)?).into())
-            }
-            _ => {
-                cursor.back();
-                UpdateExpression::new(self.allow_yield, self.allow_await).parse(cursor)
-            }
-        }
+        })
     }
 }
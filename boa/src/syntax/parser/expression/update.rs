@@ -7,7 +7,7 @@
 
 use super::left_hand_side::LeftHandSideExpression;
 use crate::syntax::{
-    ast::{node, op::UnaryOp, Node, Punctuator, TokenKind},
+    ast::{node, op::UnaryOp, Node, Position, Punctuator, TokenKind},
     parser::{AllowAwait, AllowYield, Cursor, ParseError, ParseResult, TokenParser},
 };
 
@@ -42,38 +42,39 @@ impl TokenParser for UpdateExpression {
 
     fn parse(self, cursor: &mut Cursor<'_>) -> ParseResult {
         let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+        let position = tok.span().start();
         match tok.kind {
             TokenKind::Punctuator(Punctuator::Inc) => {
                 cursor.next().expect("token disappeared");
-                return Ok(node::UnaryOp::new(
-                    UnaryOp::IncrementPre,
-                    LeftHandSideExpression::new(self.allow_yield, self.allow_await)
-                        .parse(cursor)?,
-                )
-                .into());
+                let target = LeftHandSideExpression::new(self.allow_yield, self.allow_await)
+                    .parse(cursor)?;
+                return update(UnaryOp::IncrementPre, target, position);
             }
             TokenKind::Punctuator(Punctuator::Dec) => {
                 cursor.next().expect("token disappeared");
-                return Ok(node::UnaryOp::new(
-                    UnaryOp::DecrementPre,
-                    LeftHandSideExpression::new(self.allow_yield, self.allow_await)
-                        .parse(cursor)?,
-                )
-                .into());
+                let target = LeftHandSideExpression::new(self.allow_yield, self.allow_await)
+                    .parse(cursor)?;
+                return update(UnaryOp::DecrementPre, target, position);
             }
             _ => {}
         }
 
         let lhs = LeftHandSideExpression::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        // Postfix operators can't be preceded by a line terminator, a semicolon is inserted
+        // before them instead.
+        if cursor.peek_is_line_terminator() {
+            return Ok(lhs);
+        }
         if let Some(tok) = cursor.peek(0) {
+            let position = tok.span().start();
             match tok.kind {
                 TokenKind::Punctuator(Punctuator::Inc) => {
                     cursor.next().expect("token disappeared");
-                    return Ok(node::UnaryOp::new(UnaryOp::IncrementPost, lhs).into());
+                    return update(UnaryOp::IncrementPost, lhs, position);
                 }
                 TokenKind::Punctuator(Punctuator::Dec) => {
                     cursor.next().expect("token disappeared");
-                    return Ok(node::UnaryOp::new(UnaryOp::DecrementPost, lhs).into());
+                    return update(UnaryOp::DecrementPost, lhs, position);
                 }
                 _ => {}
             }
@@ -82,3 +83,16 @@ impl TokenParser for UpdateExpression {
         Ok(lhs)
     }
 }
+
+/// Creates an update expression, whose target must be a variable or a property.
+fn update(op: UnaryOp, target: Node, position: Position) -> ParseResult {
+    match target {
+        Node::Identifier(_) | Node::GetConstField(_) | Node::GetField(_) => {
+            Ok(node::UnaryOp::new(op, target).into())
+        }
+        _ => Err(ParseError::general(
+            "invalid target of an increment or decrement operator",
+            position,
+        )),
+    }
+}
@@ -233,6 +233,14 @@ impl TokenParser for FunctionStatementList {
             }
         }
 
-        StatementList::new(self.allow_yield, self.allow_await, true, true).parse(cursor)
+        // A function is strict mode code if its body starts with a `'use strict'` directive, or
+        // if it's nested in strict mode code.
+        let strict = cursor.strict();
+        if cursor.has_use_strict_directive() {
+            cursor.set_strict(true);
+        }
+        let body = StatementList::new(self.allow_yield, self.allow_await, true, true).parse(cursor);
+        cursor.set_strict(strict);
+        body
     }
 }
@@ -9,8 +9,12 @@ mod statement;
 mod tests;
 
 use self::error::{ParseError, ParseResult};
-use crate::syntax::ast::{node::StatementList, Token};
+use crate::syntax::{
+    ast::{node::StatementList, Token},
+    lexer::{Lexer, LexerError},
+};
 use cursor::Cursor;
+use std::io::Read;
 
 /// Trait implemented by parsers.
 ///
@@ -97,14 +101,40 @@ pub struct Parser<'a> {
 impl<'a> Parser<'a> {
     /// Create a new parser, using `tokens` as input
     pub fn new(tokens: &'a [Token]) -> Self {
+        Self::from_tokens(tokens.iter().cloned().map(Ok))
+    }
+
+    /// Create a new parser, lexing the tokens lazily from the given lexer.
+    ///
+    /// Tokens are lexed only when the parser needs them, so the source does not need to be
+    /// tokenized up front.
+    pub fn from_lexer<R>(lexer: Lexer<R>) -> Self
+    where
+        R: Read + 'a,
+    {
+        Self::from_tokens(lexer)
+    }
+
+    /// Create a new parser, using the given token stream as input.
+    pub fn from_tokens<I>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = Result<Token, LexerError>>,
+        I::IntoIter: 'a,
+    {
         Self {
             cursor: Cursor::new(tokens),
         }
     }
 
-    /// Parse all expressions in the token array
+    /// Parse all expressions in the token stream
+    ///
+    /// If the token stream fails, the error it produced is returned.
     pub fn parse_all(&mut self) -> Result<StatementList, ParseError> {
-        Script.parse(&mut self.cursor)
+        let result = Script.parse(&mut self.cursor);
+        if let Some(err) = self.cursor.take_error() {
+            return Err(ParseError::Lex { err });
+        }
+        result
     }
 }
 
@@ -142,6 +172,9 @@ impl TokenParser for ScriptBody {
     type Output = StatementList;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        if cursor.has_use_strict_directive() {
+            cursor.set_strict(true);
+        }
         self::statement::StatementList::new(false, false, false, false).parse(cursor)
     }
 }
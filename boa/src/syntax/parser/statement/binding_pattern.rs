@@ -0,0 +1,128 @@
+//! Binding pattern parsing.
+//!
+//! More information:
+//!  - [ECMAScript specification][spec]
+//!
+//! [spec]: https://tc39.es/ecma262/#sec-destructuring-binding-patterns
+
+use super::BindingIdentifier;
+use crate::{
+    syntax::{
+        ast::{
+            node::{ArrayPattern, Binding, PatternElement},
+            Punctuator, TokenKind,
+        },
+        parser::{
+            expression::Initializer, AllowAwait, AllowYield, Cursor, ParseError, TokenParser,
+        },
+    },
+    BoaProfiler,
+};
+
+/// Parses the target of a binding, which is either a binding identifier or a binding pattern.
+///
+/// Object binding patterns are not supported yet.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-BindingElement
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BindingTarget {
+    allow_yield: AllowYield,
+    allow_await: AllowAwait,
+}
+
+impl BindingTarget {
+    /// Creates a new `BindingTarget` parser.
+    pub(super) fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+    where
+        Y: Into<AllowYield>,
+        A: Into<AllowAwait>,
+    {
+        Self {
+            allow_yield: allow_yield.into(),
+            allow_await: allow_await.into(),
+        }
+    }
+}
+
+impl TokenParser for BindingTarget {
+    type Output = Binding;
+
+    fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+        if tok.kind == TokenKind::Punctuator(Punctuator::OpenBracket) {
+            let pattern =
+                ArrayBindingPattern::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            Ok(pattern.into())
+        } else {
+            let ident = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            Ok(ident.into())
+        }
+    }
+}
+
+/// Parses an array binding pattern.
+///
+/// More information:
+///  - [ECMAScript specification][spec]
+///
+/// [spec]: https://tc39.es/ecma262/#prod-ArrayBindingPattern
+#[derive(Debug, Clone, Copy)]
+struct ArrayBindingPattern {
+    allow_yield: AllowYield,
+    allow_await: AllowAwait,
+}
+
+impl ArrayBindingPattern {
+    /// Creates a new `ArrayBindingPattern` parser.
+    fn new<Y, A>(allow_yield: Y, allow_await: A) -> Self
+    where
+        Y: Into<AllowYield>,
+        A: Into<AllowAwait>,
+    {
+        Self {
+            allow_yield: allow_yield.into(),
+            allow_await: allow_await.into(),
+        }
+    }
+}
+
+impl TokenParser for ArrayBindingPattern {
+    type Output = ArrayPattern;
+
+    fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
+        let _timer = BoaProfiler::global().start_event("ArrayBindingPattern", "Parsing");
+        cursor.expect(Punctuator::OpenBracket, "array binding pattern")?;
+
+        let mut elements = Vec::new();
+        loop {
+            if cursor.next_if(Punctuator::CloseBracket).is_some() {
+                break;
+            }
+            if cursor.next_if(Punctuator::Comma).is_some() {
+                elements.push(None);
+                continue;
+            }
+
+            // The rest element must be the last one, without a trailing comma.
+            if cursor.next_if(Punctuator::Spread).is_some() {
+                let rest = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+                cursor.expect(Punctuator::CloseBracket, "array binding pattern")?;
+                return Ok(ArrayPattern::new(elements, Some(rest)));
+            }
+
+            let target = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+            let init = Initializer::new(true, self.allow_yield, self.allow_await).try_parse(cursor);
+            elements.push(Some(PatternElement::new(target, init)));
+
+            if cursor.next_if(Punctuator::CloseBracket).is_some() {
+                break;
+            }
+            cursor.expect(Punctuator::Comma, "array binding pattern")?;
+        }
+
+        Ok(ArrayPattern::new(elements, None))
+    }
+}
@@ -12,6 +12,7 @@ mod tests;
 
 use super::LabelIdentifier;
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{node::Break, Keyword, Punctuator, TokenKind},
         parser::{AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
@@ -70,6 +71,6 @@ impl TokenParser for BreakStatement {
             Some(label)
         };
 
-        Ok(Break::new::<_, Box<str>>(label))
+        Ok(Break::new::<_, Sym>(label))
     }
 }
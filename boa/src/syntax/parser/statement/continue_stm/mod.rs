@@ -12,6 +12,7 @@ mod tests;
 
 use super::LabelIdentifier;
 use crate::{
+    interner::Sym,
     syntax::{
         ast::{node::Continue, Keyword, Punctuator, TokenKind},
         parser::{AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
@@ -70,6 +71,6 @@ impl TokenParser for ContinueStatement {
             Some(label)
         };
 
-        Ok(Continue::new::<_, Box<str>>(label))
+        Ok(Continue::new::<_, Sym>(label))
     }
 }
@@ -10,11 +10,11 @@
 use crate::{
     syntax::{
         ast::{
-            node::{ConstDecl, ConstDeclList, LetDecl, LetDeclList, Node},
+            node::{Binding, ConstDecl, ConstDeclList, LetDecl, LetDeclList, Node},
             Keyword, Punctuator, TokenKind,
         },
         parser::{
-            expression::Initializer, statement::BindingIdentifier, AllowAwait, AllowIn, AllowYield,
+            expression::Initializer, statement::BindingTarget, AllowAwait, AllowIn, AllowYield,
             Cursor, ParseError, ParseResult, TokenParser,
         },
     },
@@ -125,7 +125,7 @@ impl TokenParser for BindingList {
                 } else {
                     return Err(ParseError::expected(
                         vec![TokenKind::Punctuator(Punctuator::Assign)],
-                        cursor.next().ok_or(ParseError::AbruptEnd)?.clone(),
+                        cursor.next().ok_or(ParseError::AbruptEnd)?,
                         "const declaration",
                     ));
                 }
@@ -133,18 +133,20 @@ impl TokenParser for BindingList {
                 let_decls.push(LetDecl::new(ident, init));
             }
 
+            // A comma continues the list, even if it's preceded by a line terminator.
+            if cursor.next_if(Punctuator::Comma).is_some() {
+                continue;
+            }
+
             match cursor.peek_semicolon(false) {
                 (true, _) => break,
-                (false, Some(tk)) if tk.kind == TokenKind::Punctuator(Punctuator::Comma) => {
-                    let _ = cursor.next();
-                }
                 _ => {
                     return Err(ParseError::expected(
                         vec![
                             TokenKind::Punctuator(Punctuator::Semicolon),
                             TokenKind::LineTerminator,
                         ],
-                        cursor.next().ok_or(ParseError::AbruptEnd)?.clone(),
+                        cursor.next().ok_or(ParseError::AbruptEnd)?,
                         "lexical declaration",
                     ))
                 }
@@ -188,13 +190,20 @@ impl LexicalBinding {
 }
 
 impl TokenParser for LexicalBinding {
-    type Output = (Box<str>, Option<Node>);
+    type Output = (Binding, Option<Node>);
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
-        let ident = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
-        let initializer =
-            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor);
+        let binding = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let initializer = if let Binding::Array(_) = binding {
+            // A binding pattern needs a value to destructure.
+            Some(
+                Initializer::new(self.allow_in, self.allow_yield, self.allow_await)
+                    .parse(cursor)?,
+            )
+        } else {
+            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor)
+        };
 
-        Ok((ident, initializer))
+        Ok((binding, initializer))
     }
 }
@@ -1,8 +1,8 @@
 use crate::syntax::{
     ast::{
         node::{
-            ConstDecl, ConstDeclList, FunctionDecl, LetDecl, LetDeclList, Node, VarDecl,
-            VarDeclList,
+            ArrayDecl, ArrayPattern, ConstDecl, ConstDeclList, FunctionDecl, Identifier, LetDecl,
+            LetDeclList, Node, PatternElement, VarDecl, VarDeclList,
         },
         Const,
     },
@@ -170,6 +170,58 @@ fn multiple_const_declaration() {
     );
 }
 
+/// Checks array binding pattern parsing in declarations.
+#[test]
+fn array_binding_pattern() {
+    check_parser(
+        "let [a, , b = 1, [c], ...d] = e;",
+        vec![LetDeclList::from(vec![LetDecl::new(
+            ArrayPattern::new(
+                vec![
+                    Some(PatternElement::new("a", None)),
+                    None,
+                    Some(PatternElement::new("b", Some(Const::from(1).into()))),
+                    Some(PatternElement::new(
+                        ArrayPattern::new(vec![Some(PatternElement::new("c", None))], None),
+                        None,
+                    )),
+                ],
+                Some("d".into()),
+            ),
+            Some(Identifier::from("e").into()),
+        )])
+        .into()],
+    );
+
+    check_parser(
+        "var [, a,] = [];",
+        vec![VarDeclList::from(vec![VarDecl::new(
+            ArrayPattern::new(vec![None, Some(PatternElement::new("a", None))], None),
+            Some(ArrayDecl::from(vec![]).into()),
+        )])
+        .into()],
+    );
+
+    check_parser(
+        "const [] = a;",
+        vec![ConstDeclList::from(vec![ConstDecl::new(
+            ArrayPattern::new(vec![], None),
+            Identifier::from("a"),
+        )])
+        .into()],
+    );
+}
+
+/// Checks that array binding patterns need an initializer, and that the rest element is last.
+#[test]
+fn invalid_array_binding_pattern() {
+    check_invalid("let [a];");
+    check_invalid("var [a], b = 1;");
+    check_invalid("let [...a, b] = c;");
+    check_invalid("let [...a,] = c;");
+    check_invalid("let [a b] = c;");
+}
+
 /// Function declaration parsing.
 #[test]
 fn function_declaration() {
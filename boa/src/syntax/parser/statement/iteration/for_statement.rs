@@ -67,11 +67,14 @@ impl TokenParser for ForStatement {
         cursor.expect(Punctuator::OpenParen, "for statement")?;
 
         let init = match cursor.peek(0).ok_or(ParseError::AbruptEnd)?.kind {
-            TokenKind::Keyword(Keyword::Var) => Some(
-                VariableDeclarationList::new(false, self.allow_yield, self.allow_await)
-                    .parse(cursor)
-                    .map(Node::from)?,
-            ),
+            TokenKind::Keyword(Keyword::Var) => {
+                let _ = cursor.next().expect("keyword disappeared");
+                Some(
+                    VariableDeclarationList::new(false, self.allow_yield, self.allow_await)
+                        .parse(cursor)
+                        .map(Node::from)?,
+                )
+            }
             TokenKind::Keyword(Keyword::Let) | TokenKind::Keyword(Keyword::Const) => {
                 Some(Declaration::new(self.allow_yield, self.allow_await).parse(cursor)?)
             }
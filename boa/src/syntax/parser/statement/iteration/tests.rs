@@ -1,8 +1,8 @@
 use crate::syntax::{
     ast::{
         node::{
-            field::GetConstField, BinOp, Block, Call, DoWhileLoop, Identifier, UnaryOp, VarDecl,
-            VarDeclList,
+            field::GetConstField, BinOp, Block, Call, DoWhileLoop, ForLoop, Identifier, Node,
+            UnaryOp, VarDecl, VarDeclList,
         },
         op::{self, AssignOp, CompOp},
         Const,
@@ -59,3 +59,29 @@ fn check_do_while_semicolon_insertion() {
         ],
     );
 }
+
+/// Checks a for loop with a `var` declaration and comma operators in its header.
+#[test]
+fn check_for_var_with_comma() {
+    check_parser(
+        "for (var i = 0, j = 10; i < j; i++, j--) {}",
+        vec![ForLoop::new(
+            Node::from(VarDeclList::from(vec![
+                VarDecl::new("i", Some(Const::from(0).into())),
+                VarDecl::new("j", Some(Const::from(10).into())),
+            ])),
+            Node::from(BinOp::new(
+                CompOp::LessThan,
+                Identifier::from("i"),
+                Identifier::from("j"),
+            )),
+            Node::from(BinOp::new(
+                op::BinOp::Comma,
+                UnaryOp::new(op::UnaryOp::IncrementPost, Identifier::from("i")),
+                UnaryOp::new(op::UnaryOp::DecrementPost, Identifier::from("j")),
+            )),
+            Block::from(vec![]),
+        )
+        .into()],
+    );
+}
@@ -7,6 +7,7 @@
 //! [mdn]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements
 //! [spec]: https://tc39.es/ecma262/#sec-ecmascript-language-statements-and-declarations
 
+mod binding_pattern;
 mod block;
 mod break_stm;
 mod continue_stm;
@@ -18,8 +19,10 @@ mod switch;
 mod throw;
 mod try_stm;
 mod variable;
+mod with_stm;
 
 use self::{
+    binding_pattern::BindingTarget,
     block::BlockStatement,
     break_stm::BreakStatement,
     continue_stm::ContinueStatement,
@@ -31,12 +34,14 @@ use self::{
     throw::ThrowStatement,
     try_stm::TryStatement,
     variable::VariableStatement,
+    with_stm::WithStatement,
 };
 use super::{
     expression::Expression, AllowAwait, AllowReturn, AllowYield, Cursor, ParseError, ParseResult,
     TokenParser,
 };
 use crate::{
+    interner::Sym,
     syntax::ast::{node, Keyword, Node, Punctuator, TokenKind},
     BoaProfiler,
 };
@@ -93,81 +98,88 @@ impl TokenParser for Statement {
     type Output = Node;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
-        let _timer = BoaProfiler::global().start_event("Statement", "Parsing");
-        // TODO: add BreakableStatement and divide Whiles, fors and so on to another place.
-        let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
-
-        match tok.kind {
-            TokenKind::Keyword(Keyword::If) => {
-                IfStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Var) => {
-                VariableStatement::new(self.allow_yield, self.allow_await)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::While) => {
-                WhileStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Do) => {
-                DoWhileStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::For) => {
-                ForStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Return) => {
-                if self.allow_return.0 {
-                    ReturnStatement::new(self.allow_yield, self.allow_await)
+        cursor.nested(|cursor| {
+            let _timer = BoaProfiler::global().start_event("Statement", "Parsing");
+            // TODO: add BreakableStatement and divide Whiles, fors and so on to another place.
+            let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+
+            match tok.kind {
+                TokenKind::Keyword(Keyword::If) => {
+                    IfStatement::new(self.allow_yield, self.allow_await, self.allow_return)
                         .parse(cursor)
                         .map(Node::from)
-                } else {
-                    Err(ParseError::unexpected(tok.clone(), "statement"))
                 }
+                TokenKind::Keyword(Keyword::Var) => {
+                    VariableStatement::new(self.allow_yield, self.allow_await)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::While) => {
+                    WhileStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Do) => {
+                    DoWhileStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::For) => {
+                    ForStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Return) => {
+                    if self.allow_return.0 {
+                        ReturnStatement::new(self.allow_yield, self.allow_await)
+                            .parse(cursor)
+                            .map(Node::from)
+                    } else {
+                        Err(ParseError::unexpected(tok.clone(), "statement"))
+                    }
+                }
+                TokenKind::Keyword(Keyword::Break) => {
+                    BreakStatement::new(self.allow_yield, self.allow_await)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Continue) => {
+                    ContinueStatement::new(self.allow_yield, self.allow_await)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Try) => {
+                    TryStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Throw) => {
+                    ThrowStatement::new(self.allow_yield, self.allow_await)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::With) => {
+                    WithStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Keyword(Keyword::Switch) => {
+                    SwitchStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                TokenKind::Punctuator(Punctuator::OpenBlock) => {
+                    BlockStatement::new(self.allow_yield, self.allow_await, self.allow_return)
+                        .parse(cursor)
+                        .map(Node::from)
+                }
+                // TODO: https://tc39.es/ecma262/#prod-LabelledStatement
+                // TokenKind::Punctuator(Punctuator::Semicolon) => {
+                //     return Ok(Node::new(NodeBase::Nope, tok.pos))
+                // }
+                _ => ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor),
             }
-            TokenKind::Keyword(Keyword::Break) => {
-                BreakStatement::new(self.allow_yield, self.allow_await)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Continue) => {
-                ContinueStatement::new(self.allow_yield, self.allow_await)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Try) => {
-                TryStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Throw) => {
-                ThrowStatement::new(self.allow_yield, self.allow_await)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Keyword(Keyword::Switch) => {
-                SwitchStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            TokenKind::Punctuator(Punctuator::OpenBlock) => {
-                BlockStatement::new(self.allow_yield, self.allow_await, self.allow_return)
-                    .parse(cursor)
-                    .map(Node::from)
-            }
-            // TODO: https://tc39.es/ecma262/#prod-LabelledStatement
-            // TokenKind::Punctuator(Punctuator::Semicolon) => {
-            //     return Ok(Node::new(NodeBase::Nope, tok.pos))
-            // }
-            _ => ExpressionStatement::new(self.allow_yield, self.allow_await).parse(cursor),
-        }
+        })
     }
 }
 
@@ -217,7 +229,7 @@ impl TokenParser for StatementList {
         let mut items = Vec::new();
 
         loop {
-            match cursor.peek(0) {
+            let position = match cursor.peek(0) {
                 Some(token) if token.kind == TokenKind::Punctuator(Punctuator::CloseBlock) => {
                     if self.break_when_closingbrase {
                         break;
@@ -232,21 +244,22 @@ impl TokenParser for StatementList {
                         break;
                     }
                 }
-                _ => {}
-            }
+                Some(token) => token.span().start(),
+            };
 
             let item =
                 StatementListItem::new(self.allow_yield, self.allow_await, self.allow_return)
                     .parse(cursor)?;
-            items.push(item);
+            items.push((item, position));
 
             // move the cursor forward for any consecutive semicolon.
             while cursor.next_if(Punctuator::Semicolon).is_some() {}
         }
 
-        items.sort_by(Node::hoistable_order);
+        items.sort_by(|(a, _), (b, _)| Node::hoistable_order(a, b));
 
-        Ok(items.into())
+        let (items, positions) = items.into_iter().unzip();
+        Ok(node::StatementList::with_positions(items, positions))
     }
 }
 
@@ -287,19 +300,20 @@ impl TokenParser for StatementListItem {
     type Output = Node;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
-        let _timer = BoaProfiler::global().start_event("StatementListItem", "Parsing");
-        let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
-
-        match tok.kind {
-            TokenKind::Keyword(Keyword::Function)
-            | TokenKind::Keyword(Keyword::Const)
-            | TokenKind::Keyword(Keyword::Let) => {
-                Declaration::new(self.allow_yield, self.allow_await).parse(cursor)
-            }
-            _ => {
-                Statement::new(self.allow_yield, self.allow_await, self.allow_return).parse(cursor)
+        cursor.nested(|cursor| {
+            let _timer = BoaProfiler::global().start_event("StatementListItem", "Parsing");
+            let tok = cursor.peek(0).ok_or(ParseError::AbruptEnd)?;
+
+            match tok.kind {
+                TokenKind::Keyword(Keyword::Function)
+                | TokenKind::Keyword(Keyword::Const)
+                | TokenKind::Keyword(Keyword::Let) => {
+                    Declaration::new(self.allow_yield, self.allow_await).parse(cursor)
+                }
+                _ => Statement::new(self.allow_yield, self.allow_await, self.allow_return)
+                    .parse(cursor),
             }
-        }
+        })
     }
 }
 
@@ -380,7 +394,7 @@ impl BindingIdentifier {
 }
 
 impl TokenParser for BindingIdentifier {
-    type Output = Box<str>;
+    type Output = Sym;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
         let _timer = BoaProfiler::global().start_event("BindingIdentifier", "Parsing");
@@ -389,9 +403,13 @@ impl TokenParser for BindingIdentifier {
         let next_token = cursor.next().ok_or(ParseError::AbruptEnd)?;
 
         match next_token.kind {
-            TokenKind::Identifier(ref s) => Ok(s.clone()),
-            TokenKind::Keyword(k @ Keyword::Yield) if !self.allow_yield.0 => Ok(k.as_str().into()),
-            TokenKind::Keyword(k @ Keyword::Await) if !self.allow_await.0 => Ok(k.as_str().into()),
+            TokenKind::Identifier(sym) => Ok(sym),
+            TokenKind::Keyword(k @ Keyword::Yield) if !self.allow_yield.0 => {
+                Ok(Sym::new(k.as_str()))
+            }
+            TokenKind::Keyword(k @ Keyword::Await) if !self.allow_await.0 => {
+                Ok(Sym::new(k.as_str()))
+            }
             _ => Err(ParseError::expected(
                 vec![TokenKind::identifier("identifier")],
                 next_token.clone(),
@@ -46,10 +46,7 @@ impl TokenParser for ReturnStatement {
 
         if let (true, tok) = cursor.peek_semicolon(false) {
             match tok {
-                Some(tok)
-                    if tok.kind == TokenKind::Punctuator(Punctuator::Semicolon)
-                        || tok.kind == TokenKind::LineTerminator =>
-                {
+                Some(tok) if tok.kind == TokenKind::Punctuator(Punctuator::Semicolon) => {
                     let _ = cursor.next();
                 }
                 _ => {}
@@ -3,7 +3,7 @@ mod tests;
 
 use crate::{
     syntax::{
-        ast::{node::Throw, Keyword, Punctuator, TokenKind},
+        ast::{node::Throw, Keyword},
         parser::{expression::Expression, AllowAwait, AllowYield, Cursor, ParseError, TokenParser},
     },
     BoaProfiler,
@@ -47,11 +47,7 @@ impl TokenParser for ThrowStatement {
         cursor.peek_expect_no_lineterminator(0)?;
 
         let expr = Expression::new(true, self.allow_yield, self.allow_await).parse(cursor)?;
-        if let Some(tok) = cursor.peek(0) {
-            if tok.kind == TokenKind::Punctuator(Punctuator::Semicolon) {
-                let _ = cursor.next();
-            }
-        }
+        cursor.expect_semicolon(false, "throw statement")?;
 
         Ok(Throw::new(expr))
     }
@@ -2,11 +2,11 @@
 use crate::{
     syntax::{
         ast::{
-            node::{VarDecl, VarDeclList},
+            node::{Binding, VarDecl, VarDeclList},
             Keyword, Punctuator, TokenKind,
         },
         parser::{
-            expression::Initializer, statement::BindingIdentifier, AllowAwait, AllowIn, AllowYield,
+            expression::Initializer, statement::BindingTarget, AllowAwait, AllowIn, AllowYield,
             Cursor, ParseError, TokenParser,
         },
     },
@@ -106,18 +106,20 @@ impl TokenParser for VariableDeclarationList {
                     .parse(cursor)?,
             );
 
+            // A comma continues the list, even if it's preceded by a line terminator.
+            if cursor.next_if(Punctuator::Comma).is_some() {
+                continue;
+            }
+
             match cursor.peek_semicolon(false) {
                 (true, _) => break,
-                (false, Some(tk)) if tk.kind == TokenKind::Punctuator(Punctuator::Comma) => {
-                    let _ = cursor.next();
-                }
                 _ => {
                     return Err(ParseError::expected(
                         vec![
                             TokenKind::Punctuator(Punctuator::Semicolon),
                             TokenKind::LineTerminator,
                         ],
-                        cursor.next().ok_or(ParseError::AbruptEnd)?.clone(),
+                        cursor.next().ok_or(ParseError::AbruptEnd)?,
                         "lexical declaration",
                     ))
                 }
@@ -161,13 +163,17 @@ impl TokenParser for VariableDeclaration {
     type Output = VarDecl;
 
     fn parse(self, cursor: &mut Cursor<'_>) -> Result<Self::Output, ParseError> {
-        // TODO: BindingPattern
-
-        let name = BindingIdentifier::new(self.allow_yield, self.allow_await).parse(cursor)?;
-
-        let ident =
-            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor);
+        let binding = BindingTarget::new(self.allow_yield, self.allow_await).parse(cursor)?;
+        let init = if let Binding::Array(_) = binding {
+            // A binding pattern needs a value to destructure.
+            Some(
+                Initializer::new(self.allow_in, self.allow_yield, self.allow_await)
+                    .parse(cursor)?,
+            )
+        } else {
+            Initializer::new(self.allow_in, self.allow_yield, self.allow_await).try_parse(cursor)
+        };
 
-        Ok(VarDecl::new(name, ident))
+        Ok(VarDecl::new(binding, init))
     }
 }
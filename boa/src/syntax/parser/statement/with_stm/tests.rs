@@ -0,0 +1,35 @@
+use crate::syntax::{
+    ast::node::{Block, Call, Identifier, Node, With},
+    parser::tests::{check_invalid, check_parser},
+};
+
+#[test]
+fn check_with_statement() {
+    check_parser(
+        "with (obj) { f(); }",
+        vec![With::new(
+            Identifier::from("obj"),
+            Block::from(vec![Call::new(Identifier::from("f"), vec![]).into()]),
+        )
+        .into()],
+    );
+}
+
+#[test]
+fn check_with_statement_without_block() {
+    check_parser(
+        "with (obj) f();",
+        vec![With::new(
+            Identifier::from("obj"),
+            Node::from(Call::new(Identifier::from("f"), vec![])),
+        )
+        .into()],
+    );
+}
+
+#[test]
+fn check_with_statement_in_strict_mode() {
+    check_invalid("'use strict'; with (obj) { f(); }");
+    check_invalid("function g() { 'use strict'; with (obj) f(); }");
+    check_invalid("'use strict'\nfunction g() { with (obj) f(); }");
+}
@@ -1,6 +1,6 @@
 //! Tests for the parser.
 
-use super::Parser;
+use super::{error::ParseError, Parser};
 use crate::syntax::{
     ast::{
         node::{
@@ -8,7 +8,7 @@ use crate::syntax::{
             StatementList, UnaryOp, VarDecl, VarDeclList,
         },
         op::{self, NumOp},
-        Const,
+        Const, Position,
     },
     lexer::Lexer,
 };
@@ -20,26 +20,63 @@ pub(super) fn check_parser<L>(js: &str, expr: L)
 where
     L: Into<Box<[Node]>>,
 {
-    let mut lexer = Lexer::new(js);
+    let mut lexer = Lexer::new(js.as_bytes());
     lexer.lex().expect("failed to lex");
+    let expr = StatementList::from(expr);
 
     assert_eq!(
         Parser::new(&lexer.tokens)
             .parse_all()
             .expect("failed to parse"),
-        StatementList::from(expr)
+        expr
+    );
+    assert_eq!(
+        Parser::from_lexer(Lexer::new(js.as_bytes()))
+            .parse_all()
+            .expect("failed to parse lazily lexed tokens"),
+        expr
     );
 }
 
 /// Checks that the given javascript string creates a parse error.
 // TODO: #[track_caller]: https://github.com/rust-lang/rust/issues/47809
 pub(super) fn check_invalid(js: &str) {
-    let mut lexer = Lexer::new(js);
+    let mut lexer = Lexer::new(js.as_bytes());
     lexer.lex().expect("failed to lex");
 
     assert!(Parser::new(&lexer.tokens).parse_all().is_err());
 }
 
+/// Checks that lexing errors are reported by the parser when tokens are lexed lazily.
+#[test]
+fn lazy_lexer_error() {
+    let err = Parser::from_lexer(Lexer::new("let a = 'unterminated".as_bytes()))
+        .parse_all()
+        .expect_err("unterminated strings should not parse");
+
+    assert!(matches!(err, ParseError::Lex { .. }));
+}
+
+/// A `"use strict"` directive makes legacy octal literals syntax errors.
+#[test]
+fn use_strict_legacy_octal() {
+    let err = Parser::from_lexer(Lexer::new(r#""use strict"; 010"#.as_bytes()))
+        .parse_all()
+        .expect_err("legacy octal literals should not parse in strict mode");
+
+    assert!(matches!(err, ParseError::Lex { .. }));
+}
+
+/// The errors give the position of the token where they were found.
+#[test]
+fn parse_error_position() {
+    let err = Parser::from_lexer(Lexer::new("let a = 1;\nif (a) }".as_bytes()))
+        .parse_all()
+        .expect_err("a missing statement should not parse");
+
+    assert_eq!(err.position(), Some(Position::new(2, 8)));
+}
+
 /// Should be parsed as `new Class().method()` instead of `new (Class().method())`
 #[test]
 fn check_construct_call_precedence() {
@@ -104,3 +141,100 @@ fn hoisting() {
         ],
     );
 }
+
+/// Semicolons are inserted at line terminators when the next token can't continue the statement.
+#[test]
+fn asi_line_terminators() {
+    check_parser(
+        "a = 1\nb = 2",
+        vec![
+            Assign::new(Identifier::from("a"), Const::from(1)).into(),
+            Assign::new(Identifier::from("b"), Const::from(2)).into(),
+        ],
+    );
+
+    check_parser(
+        "var a = 1\n, b = 2",
+        vec![VarDeclList::from(vec![
+            VarDecl::new("a", Node::from(Const::from(1))),
+            VarDecl::new("b", Node::from(Const::from(2))),
+        ])
+        .into()],
+    );
+
+    check_invalid("a b");
+    check_invalid("throw a b");
+}
+
+/// A postfix operator can't be preceded by a line terminator.
+#[test]
+fn asi_postfix_operators() {
+    let expected = vec![
+        Identifier::from("a").into(),
+        UnaryOp::new(op::UnaryOp::IncrementPre, Identifier::from("b")).into(),
+    ];
+    check_parser("a\n++b", expected.clone());
+    check_parser("a /*\n*/ ++b", expected);
+
+    check_parser(
+        "a\n--\nb",
+        vec![
+            Identifier::from("a").into(),
+            UnaryOp::new(op::UnaryOp::DecrementPre, Identifier::from("b")).into(),
+        ],
+    );
+}
+
+/// A `return` followed by a line terminator doesn't return the expression on the next line.
+#[test]
+fn asi_restricted_return() {
+    check_parser(
+        "function f() { return\na + 1 }",
+        vec![FunctionDecl::new(
+            Box::from("f"),
+            vec![],
+            vec![
+                Return::new::<Node, Option<_>>(None).into(),
+                BinOp::new(NumOp::Add, Identifier::from("a"), Const::from(1)).into(),
+            ],
+        )
+        .into()],
+    );
+}
+
+/// Comment tokens are ignored by the parser, but still count as line terminators.
+#[test]
+fn preserved_comments_are_ignored() {
+    let mut lexer = Lexer::new("a /* one\ntwo */ ++b // three".as_bytes());
+    lexer.set_preserve_comments(true);
+
+    assert_eq!(
+        Parser::from_lexer(lexer)
+            .parse_all()
+            .expect("failed to parse"),
+        StatementList::from(vec![
+            Identifier::from("a").into(),
+            UnaryOp::new(op::UnaryOp::IncrementPre, Identifier::from("b")).into(),
+        ])
+    );
+}
+
+/// Deeply nested sources are reported as errors, instead of overflowing the stack.
+#[test]
+fn deep_nesting_is_an_error() {
+    let source = format!("{}1{}", "(".repeat(5000), ")".repeat(5000));
+    check_invalid(&source);
+
+    let source = format!("{}{}", "{".repeat(5000), "}".repeat(5000));
+    check_invalid(&source);
+}
+
+/// Nesting below the limit still parses.
+#[test]
+fn moderate_nesting() {
+    let source = format!("{}1{}", "[".repeat(200), "]".repeat(200));
+    let mut lexer = Lexer::new(source.as_bytes());
+    lexer.lex().expect("failed to lex");
+
+    assert!(Parser::new(&lexer.tokens).parse_all().is_ok());
+}
@@ -0,0 +1,340 @@
+//! Source maps, which link the positions of a transpiled script to the ones of its original
+//! source.
+//!
+//! The maps follow the [Source Map Revision 3][spec] format. Only their mappings are used, to
+//! translate the positions found by the engine, like the ones of the syntax errors or of the
+//! statements seen by a debugger. The `sourcesContent` of a map is ignored, and the index maps,
+//! made of `sections`, aren't supported.
+//!
+//! The columns of a map count UTF-16 code units, while the ones of the engine count characters,
+//! so they only match on the lines without characters outside of the Basic Multilingual Plane.
+//!
+//! [spec]: https://sourcemaps.info/spec.html
+
+use super::ast::Position;
+use serde_json::Value;
+use std::{error, fmt};
+
+#[cfg(test)]
+mod tests;
+
+/// The prefixes of the comment giving the URL of the source map of a script. The one with `@` is
+/// deprecated, but still written by some tools.
+const URL_PREFIXES: [&str; 2] = ["//# sourceMappingURL=", "//@ sourceMappingURL="];
+
+/// The alphabet of the base64 encoding, used both by the mappings and by the data URLs.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// An error found while reading a source map.
+#[derive(Debug, Clone)]
+pub struct SourceMapError {
+    details: String,
+}
+
+impl SourceMapError {
+    /// Creates a new source map error with the given details.
+    fn new<M>(details: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Self {
+            details: details.into(),
+        }
+    }
+}
+
+impl fmt::Display for SourceMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.details)
+    }
+}
+
+impl error::Error for SourceMapError {}
+
+/// A position in an original source, found with a source map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OriginalPosition<'a> {
+    /// The original source, as named by the map, after its `sourceRoot`.
+    pub source: &'a str,
+    /// The position in the original source.
+    pub position: Position,
+    /// The original name of the identifier at the position, if the map has it.
+    pub name: Option<&'a str>,
+}
+
+impl fmt::Display for OriginalPosition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.source, self.position)
+    }
+}
+
+/// A segment of the mappings, which maps a generated column to a position in an original
+/// source.
+#[derive(Debug, Clone, Copy)]
+struct Mapping {
+    /// The column in the generated line, starting from 0.
+    column: u32,
+    /// The index of the original source, and the line and column in it, starting from 0.
+    original: Option<(usize, u32, u32)>,
+    /// The index of the original name.
+    name: Option<usize>,
+}
+
+/// A source map, parsed from its JSON form.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    /// The original sources, with the `sourceRoot` of the map before them.
+    sources: Vec<String>,
+    /// The original names of the identifiers.
+    names: Vec<String>,
+    /// The mappings of each generated line, sorted by their column.
+    lines: Vec<Vec<Mapping>>,
+}
+
+impl SourceMap {
+    /// Parses a source map from its JSON form.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON isn't a valid source map of version 3.
+    pub fn parse(json: &str) -> Result<Self, SourceMapError> {
+        let map: Value = serde_json::from_str(json)
+            .map_err(|e| SourceMapError::new(format!("invalid JSON: {}", e)))?;
+        if map.get("sections").is_some() {
+            return Err(SourceMapError::new("index maps are not supported"));
+        }
+        if map["version"] != 3 {
+            return Err(SourceMapError::new("only version 3 is supported"));
+        }
+
+        let root = map["sourceRoot"].as_str().unwrap_or_default();
+        let sources = strings(&map["sources"], "sources")?
+            .into_iter()
+            .map(|source| {
+                if root.is_empty() || source.starts_with('/') {
+                    source
+                } else if root.ends_with('/') {
+                    format!("{}{}", root, source)
+                } else {
+                    format!("{}/{}", root, source)
+                }
+            })
+            .collect();
+        let names = strings(&map["names"], "names")?;
+        let mappings = map["mappings"]
+            .as_str()
+            .ok_or_else(|| SourceMapError::new("the mappings are missing"))?;
+
+        let mut source_map = Self {
+            sources,
+            names,
+            lines: Vec::new(),
+        };
+        source_map.decode_mappings(mappings)?;
+        Ok(source_map)
+    }
+
+    /// Parses a source map inlined in a `data:` URL, encoded in base64.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL isn't a base64 data URL of a valid source map.
+    pub fn from_data_url(url: &str) -> Result<Self, SourceMapError> {
+        let (header, data) = url
+            .strip_prefix("data:")
+            .and_then(|url| {
+                let comma = url.find(',')?;
+                Some((&url[..comma], &url[comma + 1..]))
+            })
+            .ok_or_else(|| SourceMapError::new("invalid data URL"))?;
+        if !header.ends_with(";base64") {
+            return Err(SourceMapError::new(
+                "only the data URLs encoded in base64 are supported",
+            ));
+        }
+        let json = String::from_utf8(decode_base64(data)?)
+            .map_err(|_| SourceMapError::new("the source map is not valid UTF-8"))?;
+        Self::parse(&json)
+    }
+
+    /// Gets the original sources of the map.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Finds the original position of a position of the generated script.
+    ///
+    /// The position is mapped by the closest mapping before it on its line, so a position in
+    /// the middle of a token maps to the start of the original token. Returns `None` if there
+    /// is no such mapping, or if it doesn't have an original position.
+    pub fn lookup(&self, position: Position) -> Option<OriginalPosition<'_>> {
+        let line = self.lines.get(position.line_number() as usize - 1)?;
+        let column = position.column_number() - 1;
+        let index = match line.binary_search_by_key(&column, |mapping| mapping.column) {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+
+        let mapping = line[index];
+        let (source, line, column) = mapping.original?;
+        Some(OriginalPosition {
+            source: &self.sources[source],
+            position: Position::new(line + 1, column + 1),
+            name: mapping.name.map(|name| self.names[name].as_str()),
+        })
+    }
+
+    /// Decodes the mappings, whose fields are relative to the ones of the previous segment,
+    /// except for the generated column, which is reset on each line.
+    fn decode_mappings(&mut self, mappings: &str) -> Result<(), SourceMapError> {
+        let (mut source, mut line, mut column, mut name) = (0, 0, 0, 0);
+        for generated_line in mappings.split(';') {
+            let mut generated_column = 0;
+            let mut segments = Vec::new();
+            for segment in generated_line.split(',').filter(|s| !s.is_empty()) {
+                let fields = decode_vlq(segment)?;
+                if !matches!(fields.len(), 1 | 4 | 5) {
+                    return Err(SourceMapError::new(format!(
+                        "invalid mapping segment '{}'",
+                        segment
+                    )));
+                }
+
+                generated_column = offset(generated_column, fields[0])?;
+                let mut mapping = Mapping {
+                    column: generated_column as u32,
+                    original: None,
+                    name: None,
+                };
+                if fields.len() > 1 {
+                    source = offset(source, fields[1])?;
+                    line = offset(line, fields[2])?;
+                    column = offset(column, fields[3])?;
+                    if source >= self.sources.len() as i64 {
+                        return Err(SourceMapError::new(format!(
+                            "the source {} doesn't exist",
+                            source
+                        )));
+                    }
+                    mapping.original = Some((source as usize, line as u32, column as u32));
+                }
+                if fields.len() > 4 {
+                    name = offset(name, fields[4])?;
+                    if name >= self.names.len() as i64 {
+                        return Err(SourceMapError::new(format!(
+                            "the name {} doesn't exist",
+                            name
+                        )));
+                    }
+                    mapping.name = Some(name as usize);
+                }
+                segments.push(mapping);
+            }
+            segments.sort_by_key(|mapping| mapping.column);
+            self.lines.push(segments);
+        }
+        Ok(())
+    }
+}
+
+/// Finds the URL of the source map of a script, given by its last `sourceMappingURL` comment.
+pub fn source_mapping_url(source: &str) -> Option<&str> {
+    source.lines().rev().find_map(|line| {
+        let line = line.trim();
+        URL_PREFIXES
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+    })
+}
+
+/// Gets the strings of an array of the map, where `null` stands for an empty string.
+fn strings(array: &Value, field: &str) -> Result<Vec<String>, SourceMapError> {
+    let invalid = || SourceMapError::new(format!("the {} are not an array of strings", field));
+    match array {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(string) => Ok(string.clone()),
+                Value::Null => Ok(String::new()),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Adds a relative field of a segment to its previous value, which can't become negative.
+fn offset(previous: i64, relative: i64) -> Result<i64, SourceMapError> {
+    let value = previous + relative;
+    if value < 0 || value > i64::from(u32::MAX) {
+        Err(SourceMapError::new("a mapping is out of bounds"))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Gets the value of a base64 digit.
+fn base64_digit(digit: u8) -> Result<u8, SourceMapError> {
+    BASE64_ALPHABET
+        .iter()
+        .position(|&d| d == digit)
+        .map(|value| value as u8)
+        .ok_or_else(|| SourceMapError::new(format!("invalid base64 digit '{}'", digit as char)))
+}
+
+/// Decodes the fields of a segment, which are base64 variable-length quantities.
+///
+/// Each digit holds 5 bits of the value, from the least significant ones, and a sixth bit telling
+/// whether another digit follows. The least significant bit of the value is its sign.
+fn decode_vlq(segment: &str) -> Result<Vec<i64>, SourceMapError> {
+    let mut fields = Vec::new();
+    let (mut value, mut shift) = (0_i64, 0);
+    for digit in segment.bytes() {
+        let digit = i64::from(base64_digit(digit)?);
+        if shift > 32 {
+            return Err(SourceMapError::new("a mapping is out of bounds"));
+        }
+        value += (digit & 0b1_1111) << shift;
+        if digit & 0b10_0000 == 0 {
+            fields.push(if value & 1 == 1 {
+                -(value >> 1)
+            } else {
+                value >> 1
+            });
+            value = 0;
+            shift = 0;
+        } else {
+            shift += 5;
+        }
+    }
+    if shift != 0 {
+        return Err(SourceMapError::new(format!(
+            "truncated mapping segment '{}'",
+            segment
+        )));
+    }
+    Ok(fields)
+}
+
+/// Decodes base64 data, with or without its padding.
+fn decode_base64(data: &str) -> Result<Vec<u8>, SourceMapError> {
+    let data = data.trim_end_matches('=').as_bytes();
+    let mut bytes = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut bits = 0_u32;
+        for (index, &digit) in chunk.iter().enumerate() {
+            bits |= u32::from(base64_digit(digit)?) << (18 - 6 * index);
+        }
+        let decoded = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+        match chunk.len() {
+            1 => return Err(SourceMapError::new("truncated base64 data")),
+            length => bytes.extend_from_slice(&decoded[..length - 1]),
+        }
+    }
+    Ok(bytes)
+}
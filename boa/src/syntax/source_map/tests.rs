@@ -0,0 +1,94 @@
+//! Tests for the source maps.
+
+use super::*;
+
+/// A map of a script whose lines are:
+/// 1. the first line of `a.ts`;
+/// 2. the identifier `greet` and the fifth column of the second line of `a.ts`;
+/// 3. nothing which is mapped;
+/// 4. the fourth line of `a.ts`, from its seventeenth column.
+const MAP: &str = r#"{
+    "version": 3,
+    "file": "a.js",
+    "sources": ["a.ts"],
+    "names": ["greet"],
+    "mappings": "AAAA;AACAA,IAAI;;gBAEJ"
+}"#;
+
+#[test]
+fn lookup() {
+    let map = SourceMap::parse(MAP).expect("failed to parse the map");
+    let original = |line, column| {
+        map.lookup(Position::new(line, column))
+            .map(|original| original.to_string())
+    };
+
+    assert_eq!(original(1, 1).as_deref(), Some("a.ts:1:1"));
+    assert_eq!(original(2, 3).as_deref(), Some("a.ts:2:1"));
+    assert_eq!(original(2, 7).as_deref(), Some("a.ts:2:5"));
+    assert_eq!(original(3, 1), None);
+    assert_eq!(original(4, 16), None);
+    assert_eq!(original(4, 20).as_deref(), Some("a.ts:4:1"));
+    assert_eq!(original(9, 1), None);
+}
+
+#[test]
+fn lookup_names() {
+    let map = SourceMap::parse(MAP).expect("failed to parse the map");
+
+    assert_eq!(map.lookup(Position::new(2, 1)).unwrap().name, Some("greet"));
+    assert_eq!(map.lookup(Position::new(2, 5)).unwrap().name, None);
+}
+
+#[test]
+fn source_root() {
+    let map = SourceMap::parse(
+        r#"{"version": 3, "sourceRoot": "src", "sources": ["a.ts", null], "mappings": ""}"#,
+    )
+    .expect("failed to parse the map");
+
+    assert_eq!(map.sources(), ["src/a.ts", "src/"]);
+}
+
+#[test]
+fn data_url() {
+    let map = SourceMap::from_data_url(
+        "data:application/json;charset=utf-8;base64,\
+         eyJ2ZXJzaW9uIjozLCJzb3VyY2VzIjpbImEudHMiXSwibmFtZXMiOltdLCJtYXBwaW5ncyI6IkFBQUEifQ==",
+    )
+    .expect("failed to parse the map");
+
+    assert_eq!(map.sources(), ["a.ts"]);
+    assert!(map.lookup(Position::new(1, 1)).is_some());
+    assert!(SourceMap::from_data_url("data:application/json,{}").is_err());
+}
+
+#[test]
+fn invalid_maps() {
+    assert!(SourceMap::parse("{").is_err());
+    assert!(SourceMap::parse(r#"{"version": 2, "sources": [], "mappings": ""}"#).is_err());
+    assert!(SourceMap::parse(r#"{"version": 3, "sections": []}"#).is_err());
+    // The source, the name and the column of the segments are out of bounds.
+    assert!(SourceMap::parse(r#"{"version": 3, "sources": [], "mappings": "AAAA"}"#).is_err());
+    assert!(SourceMap::parse(r#"{"version": 3, "sources": ["a"], "mappings": "AAAAA"}"#).is_err());
+    assert!(SourceMap::parse(r#"{"version": 3, "sources": ["a"], "mappings": "D"}"#).is_err());
+    // The segment is truncated, or has the wrong number of fields.
+    assert!(SourceMap::parse(r#"{"version": 3, "sources": ["a"], "mappings": "g"}"#).is_err());
+    assert!(SourceMap::parse(r#"{"version": 3, "sources": ["a"], "mappings": "AA"}"#).is_err());
+}
+
+#[test]
+fn mapping_url() {
+    assert_eq!(
+        source_mapping_url("f();\n//# sourceMappingURL=a.js.map\n"),
+        Some("a.js.map")
+    );
+    assert_eq!(
+        source_mapping_url("//@ sourceMappingURL=old.map\n//# sourceMappingURL=new.map"),
+        Some("new.map")
+    );
+    assert_eq!(
+        source_mapping_url("f(); // sourceMappingURL=a.js.map"),
+        None
+    );
+}
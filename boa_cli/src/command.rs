@@ -0,0 +1,66 @@
+//! The commands of the interactive mode, typed on a line starting with a dot, like in Node.
+
+use std::path::PathBuf;
+
+/// The description of the commands, printed by `.help`.
+pub(crate) const HELP: &str = "\
+.clear    Reset the realm, forgetting the bindings of the session
+.exit     Exit the interactive mode
+.help     Print this help message
+.load     Load a file into the session
+.save     Save the inputs of the session to a file
+
+Press Ctrl-C to cancel the current input, and Ctrl-D to exit.";
+
+/// A command of the interactive mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Command {
+    /// Replaces the realm with a new one.
+    Clear,
+    /// Ends the session.
+    Exit,
+    /// Prints the description of the commands.
+    Help,
+    /// Runs a file in the realm, as if it had been typed.
+    Load(PathBuf),
+    /// Writes the inputs run since the start of the session, or since the last `.clear`, to a
+    /// file.
+    Save(PathBuf),
+}
+
+impl Command {
+    /// Parses a line starting with a dot followed by a letter, or returns `None` for the other
+    /// lines, like `.5`, which are JavaScript.
+    ///
+    /// Returns an error message if the command doesn't exist or lacks its argument.
+    pub(crate) fn parse(line: &str) -> Option<Result<Self, String>> {
+        let line = line.trim();
+        let rest = line.strip_prefix('.')?;
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        let (keyword, argument) = match rest.find(char::is_whitespace) {
+            Some(index) => (&rest[..index], rest[index..].trim()),
+            None => (rest, ""),
+        };
+        let file = || {
+            if argument.is_empty() {
+                Err(format!("the .{} command needs a file", keyword))
+            } else {
+                Ok(PathBuf::from(argument))
+            }
+        };
+        Some(match keyword {
+            "clear" => Ok(Self::Clear),
+            "exit" => Ok(Self::Exit),
+            "help" => Ok(Self::Help),
+            "load" => file().map(Self::Load),
+            "save" => file().map(Self::Save),
+            _ => Err(format!(
+                "unknown command .{}, type .help to list the commands",
+                keyword
+            )),
+        })
+    }
+}
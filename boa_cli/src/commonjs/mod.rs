@@ -0,0 +1,159 @@
+//! CommonJS modules, which let the scripts load other files with `require`, like in Node, so that
+//! small packages run without a bundler.
+//!
+//! The specifiers are resolved relative to the directory of the module calling `require` by a
+//! `Resolver`, which is the `NodeResolver` finding the `.js` and `.json` files like Node.
+//!
+//! The code of a module runs once per realm, in a function whose parameters are `exports`,
+//! `require`, `module`, `__filename` and `__dirname`, and `require` returns its `module.exports`.
+//! A module is cached before it runs, so a cycle of `require` gets the exports done so far.
+//!
+//! The cache is an object in an internal slot of the global object, as the collector doesn't
+//! trace the values captured by the `require` functions.
+
+use crate::{parse_script, resolver::Resolver};
+use boa::{
+    builtins::{
+        function::Function,
+        intrinsics::Intrinsic,
+        value::{ResultValue, Value, ValueData},
+    },
+    exec::Interpreter,
+    host::HostOperation,
+    job::Job,
+    Executable,
+};
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The internal slot of the global object holding the modules loaded in the realm, by the
+/// canonical path of their file.
+const MODULES: &str = "CommonJSModules";
+
+/// Adds `require` to the global object of a realm, which resolves the specifiers with the given
+/// resolver, relative to the directory of the main script, or else to the working directory.
+pub(crate) fn register<R>(global: &Value, main: Option<&PathBuf>, resolver: R)
+where
+    R: Resolver + 'static,
+{
+    let directory = match main.and_then(|main| main.parent()) {
+        Some(directory) if directory != Path::new("") => directory,
+        _ => Path::new("."),
+    };
+    global.set_internal_slot(MODULES, Value::new_object(None));
+    let resolver: Rc<dyn Resolver> = Rc::new(resolver);
+    global.set_field(
+        "require",
+        require_function(&resolver, directory.to_path_buf()),
+    );
+}
+
+/// Creates the `require` function of a module, which resolves the specifiers relative to its
+/// directory.
+fn require_function(resolver: &Rc<dyn Resolver>, directory: PathBuf) -> Value {
+    let resolver = Rc::clone(resolver);
+    let require = move |_: &mut Value, args: &[Value], engine: &mut Interpreter| {
+        let specifier = match args.first() {
+            Some(specifier) if specifier.is_string() => specifier.to_string(),
+            _ => return engine.throw_type_error("the module specifier must be a string"),
+        };
+        match resolver.resolve(&specifier, &directory) {
+            Some(path) => {
+                let name = path.to_string_lossy();
+                if engine.check_host_operation(&HostOperation::LoadModule(&name))? {
+                    load(&resolver, &path, engine)
+                } else {
+                    Ok(Value::undefined())
+                }
+            }
+            None => engine.throw_type_error(format!(
+                "Cannot find module '{}' from '{}'",
+                specifier,
+                directory.display()
+            )),
+        }
+    };
+
+    let require = ValueData::from_func(Function::closure(Vec::new(), Rc::new(require)));
+    require.set_field("length", 1);
+    require
+}
+
+/// Gets the exports of a module, running it if it isn't loaded yet.
+fn load(resolver: &Rc<dyn Resolver>, path: &Path, engine: &mut Interpreter) -> ResultValue {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let key = path.display().to_string();
+    let modules = engine.realm.global_obj.get_internal_slot(MODULES);
+    if modules.has_field(&key) {
+        return Ok(modules.get_field(key).get_field("exports"));
+    }
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(e) => return engine.throw_type_error(format!("{}: {}", path.display(), e)),
+    };
+
+    let global = engine.realm.global_obj.clone();
+    let module = Value::new_object(Some(&global));
+    module.set_field("exports", Value::new_object(Some(&global)));
+    module.set_field("filename", path.display().to_string());
+    module.set_field("loaded", false);
+    modules.set_field(key.clone(), module.clone());
+
+    let result = if path.extension() == Some(OsStr::new("json")) {
+        let parse = engine.realm.intrinsic(Intrinsic::Json).get_field("parse");
+        Job::new(parse, vec![Value::from(source)])
+            .run(engine)
+            .map(|exports| module.set_field("exports", exports))
+    } else {
+        run_module(resolver, &path, &source, &module, engine)
+    };
+    if let Err(e) = result {
+        // The module may be required again, once what made it fail is fixed.
+        modules.remove_property(&key);
+        return Err(e);
+    }
+
+    module.set_field("loaded", true);
+    Ok(module.get_field("exports"))
+}
+
+/// Runs the code of a module in its function.
+fn run_module(
+    resolver: &Rc<dyn Resolver>,
+    path: &Path,
+    source: &str,
+    module: &Value,
+    engine: &mut Interpreter,
+) -> ResultValue {
+    // The code starts on the line of the function, so that only the columns of the errors on its
+    // first line move, and the line of a shebang is commented out.
+    let source = match source.strip_prefix("#!") {
+        Some(source) => format!("//{}", source),
+        None => source.to_owned(),
+    };
+    let wrapped = format!(
+        "(function (exports, require, module, __filename, __dirname) {{ {}\n}})",
+        source
+    );
+    let function = match parse_script(engine, &wrapped, None) {
+        Ok(script) => script.run(engine)?,
+        Err(e) => return Err(Value::from(format!("{} in {}", e, path.display()))),
+    };
+
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    let args = vec![
+        module.get_field("exports"),
+        require_function(resolver, directory.to_path_buf()),
+        module.clone(),
+        Value::from(path.display().to_string()),
+        Value::from(directory.display().to_string()),
+    ];
+    Job::new(function, args).run(engine)
+}
@@ -0,0 +1,120 @@
+use super::register;
+use crate::resolver::{tests::Files, NodeResolver};
+use boa::{exec::Interpreter, forward, realm::Realm};
+use std::fs;
+
+/// Creates an engine with `require`, for a main script in the directory of the files.
+fn engine(files: &Files) -> Interpreter {
+    let realm = Realm::create();
+    let global = realm.global_obj.clone();
+    register(
+        &global,
+        Some(&files.path("main.js")),
+        NodeResolver::default(),
+    );
+    Interpreter::new(realm)
+}
+
+#[test]
+fn cached_modules() {
+    let files = Files::new(
+        "cached_modules",
+        &[
+            ("counter.js", "exports.count = 0;"),
+            (
+                "a.js",
+                "require('./counter').count += 1; exports.name = 'a';",
+            ),
+            ("lib/b.js", "exports.a = require('../a');"),
+        ],
+    );
+    let mut engine = engine(&files);
+
+    assert_eq!(
+        forward(&mut engine, "require('./a') === require('./a')"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "require('./a.js') === require('./a')"),
+        "true"
+    );
+    assert_eq!(
+        forward(&mut engine, "require('./lib/b').a === require('./a')"),
+        "true"
+    );
+    // The code of a module runs once, however many times it's required.
+    assert_eq!(forward(&mut engine, "require('./counter').count"), "1");
+}
+
+#[test]
+fn module_exports() {
+    let files = Files::new(
+        "module_exports",
+        &[
+            (
+                "function.js",
+                "module.exports = function () { return 42; };",
+            ),
+            ("data.json", r#"{ "answer": 42 }"#),
+            (
+                "paths.js",
+                "exports.file = __filename; exports.dir = __dirname;",
+            ),
+        ],
+    );
+    let mut engine = engine(&files);
+
+    assert_eq!(forward(&mut engine, "require('./function')()"), "42");
+    assert_eq!(forward(&mut engine, "require('./data').answer"), "42");
+    assert_eq!(
+        forward(&mut engine, "require('./paths').file.endsWith('paths.js')"),
+        "true"
+    );
+}
+
+#[test]
+fn cyclic_modules() {
+    let files = Files::new(
+        "cyclic_modules",
+        &[
+            (
+                "a.js",
+                "exports.early = true; exports.b = require('./b'); exports.late = true;",
+            ),
+            (
+                "b.js",
+                "var a = require('./a'); exports.partial = a.early && !a.late;",
+            ),
+        ],
+    );
+    let mut engine = engine(&files);
+
+    // A module required during its own loading gives the exports done so far.
+    assert_eq!(forward(&mut engine, "require('./a').b.partial"), "true");
+    assert_eq!(forward(&mut engine, "require('./a').late"), "true");
+}
+
+#[test]
+fn failed_modules() {
+    let files = Files::new("failed_modules", &[("broken.js", "throw 'broken';")]);
+    let mut engine = engine(&files);
+
+    assert_eq!(
+        forward(&mut engine, "try { require('./broken'); } catch (e) { e }"),
+        "broken"
+    );
+    assert_eq!(
+        forward(
+            &mut engine,
+            "try { require('./missing'); } catch (e) { e.message }"
+        ),
+        format!(
+            "Cannot find module './missing' from '{}'",
+            files.root().display()
+        )
+    );
+
+    // A module which failed isn't cached, so it runs again once it's fixed.
+    fs::write(files.path("broken.js"), "exports.fixed = true;").expect("could not fix the file");
+    assert_eq!(forward(&mut engine, "require('./broken').fixed"), "true");
+}
@@ -0,0 +1,91 @@
+//! The coverage mode of the CLI, which counts the statements and the branches of the files which
+//! run, and writes them in the [LCOV][lcov] format read by the coverage tools, like `genhtml`.
+//!
+//! The lines are the ones where the statements start, and the branches are the ones of the `if`
+//! statements. The statements of a file which run after it, like its callbacks called by the next
+//! files, are counted in the next ones.
+//!
+//! [lcov]: https://github.com/linux-test-project/lcov/blob/master/man/geninfo.1
+
+use crate::{parse_script, run_script, source_map::FileSourceMap, Opt};
+use boa::{coverage::Coverage, exec::Interpreter};
+use std::{collections::BTreeMap, fmt::Write, fs, io, path::Path};
+
+/// Runs the files, like `run`, and writes their coverage to the given file, even if one of them
+/// failed.
+///
+/// Returns `false` if a file failed, in which case the next ones aren't run.
+pub(crate) fn run(engine: &mut Interpreter, args: &Opt, output: &Path) -> io::Result<bool> {
+    engine.enable_coverage();
+    let mut lcov = String::new();
+    let mut succeeded = true;
+    for file in &args.files {
+        let source = fs::read_to_string(file)?;
+        let source_map = FileSourceMap::load(file, &source);
+        let script = match parse_script(engine, &source, source_map.as_ref()) {
+            Ok(script) => script,
+            Err(e) => {
+                eprintln!("{}", e);
+                succeeded = false;
+                break;
+            }
+        };
+        succeeded = run_script(engine, &script, args);
+
+        let mut coverage = engine.take_coverage().unwrap_or_default();
+        coverage.add_script(&script);
+        write_record(&mut lcov, file, &coverage);
+        if !succeeded {
+            break;
+        }
+    }
+
+    fs::write(output, lcov)?;
+    Ok(succeeded)
+}
+
+/// Writes the LCOV record of a file.
+fn write_record(lcov: &mut String, file: &Path, coverage: &Coverage) {
+    let _ = writeln!(lcov, "TN:");
+    let _ = writeln!(lcov, "SF:{}", file.display());
+
+    // The branch points of a line are numbered in the order of their statements.
+    let (mut found, mut hit) = (0, 0);
+    let mut blocks = BTreeMap::new();
+    for (point, counts) in coverage.branches() {
+        let line = point.statement.line_number();
+        let block = blocks.entry(line).or_insert(0);
+        for (branch, &count) in counts.iter().enumerate() {
+            let taken = if counts == [0, 0] {
+                "-".to_owned()
+            } else {
+                count.to_string()
+            };
+            let _ = writeln!(lcov, "BRDA:{},{},{},{}", line, block, branch, taken);
+            found += 1;
+            if count > 0 {
+                hit += 1;
+            }
+        }
+        *block += 1;
+    }
+    let _ = writeln!(lcov, "BRF:{}", found);
+    let _ = writeln!(lcov, "BRH:{}", hit);
+
+    // A line ran as many times as its statement which ran the most.
+    let mut lines = BTreeMap::new();
+    for (position, count) in coverage.statements() {
+        let line = lines.entry(position.line_number()).or_insert(0);
+        *line = count.max(*line);
+    }
+    for (line, count) in &lines {
+        let _ = writeln!(lcov, "DA:{},{}", line, count);
+    }
+    let _ = writeln!(lcov, "LF:{}", lines.len());
+    let _ = writeln!(
+        lcov,
+        "LH:{}",
+        lines.values().filter(|&&count| count > 0).count()
+    );
+    let _ = writeln!(lcov, "end_of_record");
+}
@@ -0,0 +1,573 @@
+//! A server of the [Debug Adapter Protocol][dap], for editors like VS Code to debug the scripts
+//! run by boa. It is built with the `dap` feature.
+//!
+//! The editor starts `boa --dap`, and speaks the protocol on its standard input and output. The
+//! script is the `program` of the `launch` request, and runs once the editor is done configuring
+//! the session. What it prints with the console is sent to the editor as `output` events.
+//!
+//! The server is the `Debugger` of the engine: it pauses on the statements of the lines with a
+//! breakpoint, steps over, into and out of the calls, lists the scopes of the paused statement,
+//! and evaluates expressions in them. The engine doesn't record which functions are called, so
+//! the frames of the stack trace are the statements running at each call depth, and only the
+//! innermost one has scopes.
+//!
+//! If the script has a source map, its positions are translated to the original sources, so the
+//! breakpoints are set and the frames are shown in them.
+//!
+//! [dap]: https://microsoft.github.io/debug-adapter-protocol/
+
+use crate::{
+    create_engine,
+    inspect::{inspect, properties},
+    parse_script,
+    source_map::{FileSourceMap, Original},
+    Opt,
+};
+use boa::{
+    builtins::{
+        console::{ConsoleWriter, LogMessage},
+        value::Value,
+    },
+    debugger::Debugger,
+    environment::lexical_environment::EnvironmentType,
+    exec::Interpreter,
+    syntax::ast::Position,
+    Executable,
+};
+use serde_json::{json, Value as Json};
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    path::PathBuf,
+    process,
+    rc::Rc,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// The identifier of the only thread of the script.
+const THREAD_ID: i64 = 1;
+
+/// Debugs the script given by the editor, until the editor disconnects.
+pub(crate) fn serve(args: &Opt) -> io::Result<()> {
+    let client = Rc::new(Client::connect());
+    let mut engine = create_engine(args);
+    engine.set_console_writer(Output {
+        client: client.clone(),
+    });
+
+    let mut session = Session::new(client.clone());
+    loop {
+        let request = client.next_request();
+        session.handle(&mut engine, &request);
+        if request["command"] == "configurationDone" {
+            break;
+        }
+    }
+
+    let result = fs::read_to_string(&session.program)
+        .map_err(|e| format!("{}: {}", session.program.display(), e))
+        .and_then(|source| {
+            let source_map = FileSourceMap::load(&session.program, &source);
+            session.source_map = source_map.clone();
+            engine.set_debugger(Box::new(session));
+            run(&mut engine, &source, source_map.as_ref())
+        });
+    engine.take_debugger();
+    if let Err(ref message) = result {
+        client.event(
+            "output",
+            json!({ "category": "stderr", "output": format!("{}\n", message) }),
+        );
+    }
+    client.event(
+        "exited",
+        json!({ "exitCode": if result.is_ok() { 0 } else { 1 } }),
+    );
+    client.event("terminated", json!({}));
+
+    loop {
+        let request = client.next_request();
+        client.respond(&request, json!({}));
+        if request["command"] == "disconnect" {
+            return Ok(());
+        }
+    }
+}
+
+/// Runs the script and the jobs it queued, and returns the uncaught exception or syntax error.
+fn run(
+    engine: &mut Interpreter,
+    source: &str,
+    source_map: Option<&FileSourceMap>,
+) -> Result<(), String> {
+    let script = parse_script(engine, source, source_map)?;
+    let result = script
+        .run(engine)
+        .and_then(|_| engine.run_jobs().map(|()| Value::undefined()));
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Uncaught {}", inspect(&e, 2, engine)))
+}
+
+/// The connection to the editor.
+#[derive(Debug)]
+struct Client {
+    /// The requests of the editor, read from the standard input by another thread, so that they
+    /// can be checked for between the statements.
+    requests: Receiver<Json>,
+    /// The sequence number of the next message sent to the editor.
+    seq: Cell<i64>,
+}
+
+impl Client {
+    /// Starts reading the requests from the standard input.
+    fn connect() -> Self {
+        let (sender, requests) = mpsc::channel();
+        thread::spawn(move || {
+            let mut input = BufReader::new(io::stdin());
+            while let Ok(Some(request)) = read_message(&mut input) {
+                if sender.send(request).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            requests,
+            seq: Cell::new(1),
+        }
+    }
+
+    /// Waits for the next request, or exits if the editor closed the connection.
+    fn next_request(&self) -> Json {
+        self.requests.recv().unwrap_or_else(|_| process::exit(0))
+    }
+
+    /// Sends a message to the editor, with the next sequence number.
+    fn send(&self, mut message: Json) {
+        message["seq"] = json!(self.seq.get());
+        self.seq.set(self.seq.get() + 1);
+        let message = message.to_string();
+
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        // If the editor is gone, the reading thread finds it out.
+        let _ = write!(
+            stdout,
+            "Content-Length: {}\r\n\r\n{}",
+            message.len(),
+            message
+        );
+        let _ = stdout.flush();
+    }
+
+    /// Responds to a request which succeeded.
+    fn respond(&self, request: &Json, body: Json) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": true,
+            "body": body,
+        }));
+    }
+
+    /// Responds to a request which failed, with the given error message.
+    fn fail(&self, request: &Json, message: &str) {
+        self.send(json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": false,
+            "message": message,
+        }));
+    }
+
+    /// Sends an event.
+    fn event(&self, event: &str, body: Json) {
+        self.send(json!({ "type": "event", "event": event, "body": body }));
+    }
+}
+
+/// Reads a message preceded by its `Content-Length` header, or returns `None` at the end of the
+/// input.
+fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<Json>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let length = length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Sends the console output of the script to the editor, as the standard output is used by the
+/// protocol.
+#[derive(Debug)]
+struct Output {
+    client: Rc<Client>,
+}
+
+impl ConsoleWriter for Output {
+    fn write(&mut self, msg: LogMessage) {
+        let (category, output) = match msg {
+            LogMessage::Error(msg) | LogMessage::Warn(msg) => ("stderr", msg),
+            LogMessage::Log(msg) | LogMessage::Debug(msg) | LogMessage::Info(msg) => {
+                ("stdout", msg)
+            }
+        };
+        self.client.event(
+            "output",
+            json!({ "category": category, "output": format!("{}\n", output) }),
+        );
+    }
+}
+
+/// How the script runs until its next pause, besides the breakpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    /// Until a breakpoint.
+    Continue,
+    /// Until the next statement.
+    In,
+    /// Until the next statement at the given call depth, or in a caller.
+    Over(usize),
+    /// Until the next statement in a caller of the given call depth.
+    Out(usize),
+}
+
+/// Something the editor can expand in the variables view.
+#[derive(Debug)]
+enum Handle {
+    /// The bindings of a scope.
+    Bindings(Vec<(String, Value)>),
+    /// The properties of an object.
+    Object(Value),
+}
+
+/// The state of the debugging session.
+#[derive(Debug)]
+struct Session {
+    client: Rc<Client>,
+    /// The script which is debugged.
+    program: PathBuf,
+    /// The source map of the script, if it is transpiled.
+    source_map: Option<FileSourceMap>,
+    /// The lines with a breakpoint, by file.
+    breakpoints: HashMap<PathBuf, HashSet<u32>>,
+    /// How the script runs until its next pause.
+    step: Step,
+    /// The reason of the next pause, if the editor asked for one.
+    requested: Option<&'static str>,
+    /// The position of the running statement at each call depth, from the script itself.
+    frames: Vec<Position>,
+    /// What the editor can expand while the script is paused, referenced by their index plus
+    /// one.
+    handles: Vec<Handle>,
+}
+
+impl Session {
+    /// Creates a session, which runs the script until a breakpoint.
+    fn new(client: Rc<Client>) -> Self {
+        Self {
+            client,
+            program: PathBuf::new(),
+            source_map: None,
+            breakpoints: HashMap::new(),
+            step: Step::Continue,
+            requested: None,
+            frames: Vec::new(),
+            handles: Vec::new(),
+        }
+    }
+
+    /// Handles a request of the editor, and returns how to run the script if it resumes it.
+    fn handle(&mut self, engine: &mut Interpreter, request: &Json) -> Option<Step> {
+        let arguments = &request["arguments"];
+        match request["command"].as_str().unwrap_or_default() {
+            "initialize" => {
+                self.client.respond(
+                    request,
+                    json!({
+                        "supportsConfigurationDoneRequest": true,
+                        "supportsEvaluateForHovers": true,
+                        "supportsTerminateRequest": true,
+                    }),
+                );
+                self.client.event("initialized", json!({}));
+            }
+            "launch" => match arguments["program"].as_str() {
+                Some(program) => {
+                    self.program = canonical(program);
+                    if arguments["stopOnEntry"].as_bool() == Some(true) {
+                        self.requested = Some("entry");
+                    }
+                    self.client.respond(request, json!({}));
+                }
+                None => self.client.fail(request, "the program to debug is missing"),
+            },
+            "setBreakpoints" => {
+                let path = canonical(arguments["source"]["path"].as_str().unwrap_or_default());
+                let lines: HashSet<u32> = arguments["breakpoints"]
+                    .as_array()
+                    .map(|breakpoints| {
+                        breakpoints
+                            .iter()
+                            .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                            .map(|line| line as u32)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let breakpoints: Vec<_> = lines
+                    .iter()
+                    .map(|line| json!({ "verified": true, "line": line }))
+                    .collect();
+                self.breakpoints.insert(path, lines);
+                self.client
+                    .respond(request, json!({ "breakpoints": breakpoints }));
+            }
+            "setExceptionBreakpoints" | "configurationDone" => {
+                self.client.respond(request, json!({}));
+            }
+            "threads" => self.client.respond(
+                request,
+                json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] }),
+            ),
+            "stackTrace" => self.stack_trace(request),
+            "scopes" => self.scopes(engine, request),
+            "variables" => self.variables(engine, request),
+            "evaluate" => self.evaluate(engine, request),
+            "pause" => {
+                self.requested = Some("pause");
+                self.client.respond(request, json!({}));
+            }
+            "continue" => {
+                self.client
+                    .respond(request, json!({ "allThreadsContinued": true }));
+                return Some(Step::Continue);
+            }
+            "next" => {
+                self.client.respond(request, json!({}));
+                return Some(Step::Over(engine.call_depth()));
+            }
+            "stepIn" => {
+                self.client.respond(request, json!({}));
+                return Some(Step::In);
+            }
+            "stepOut" => {
+                self.client.respond(request, json!({}));
+                return Some(Step::Out(engine.call_depth()));
+            }
+            "disconnect" | "terminate" => {
+                self.client.respond(request, json!({}));
+                process::exit(0);
+            }
+            command => self
+                .client
+                .fail(request, &format!("unsupported request {}", command)),
+        }
+        None
+    }
+
+    /// Gets the reason to pause on a statement, if any.
+    fn pause_reason(&mut self, depth: usize, position: Position) -> Option<&'static str> {
+        if let Some(reason) = self.requested.take() {
+            return Some(reason);
+        }
+        match self.step {
+            Step::In => return Some("step"),
+            Step::Over(step_depth) if depth <= step_depth => return Some("step"),
+            Step::Out(step_depth) if depth < step_depth => return Some("step"),
+            _ => {}
+        }
+        let original = self.original(position);
+        match self.breakpoints.get(&original.path) {
+            Some(lines) if lines.contains(&original.position.line_number()) => Some("breakpoint"),
+            _ => None,
+        }
+    }
+
+    /// Handles the requests of the editor while the script is paused, until it resumes it.
+    fn pause(&mut self, engine: &mut Interpreter, reason: &str) {
+        self.client.event(
+            "stopped",
+            json!({ "reason": reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+        loop {
+            let request = self.client.next_request();
+            if let Some(step) = self.handle(engine, &request) {
+                self.step = step;
+                break;
+            }
+        }
+        self.handles.clear();
+    }
+
+    /// Responds with the running statements, from the innermost call.
+    fn stack_trace(&self, request: &Json) {
+        let frames: Vec<_> = self
+            .frames
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(depth, &position)| {
+                let Original { path, position } = self.original(position);
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned());
+                json!({
+                    "id": depth + 1,
+                    "name": if depth == 0 { "<script>".to_string() } else { format!("<call {}>", depth) },
+                    "source": { "name": name, "path": path },
+                    "line": position.line_number(),
+                    "column": position.column_number(),
+                })
+            })
+            .collect();
+        self.client.respond(
+            request,
+            json!({ "stackFrames": frames, "totalFrames": self.frames.len() }),
+        );
+    }
+
+    /// Responds with the scopes of a frame, which are only known for the innermost one.
+    fn scopes(&mut self, engine: &Interpreter, request: &Json) {
+        let innermost = request["arguments"]["frameId"].as_u64() == Some(self.frames.len() as u64);
+        let scopes = if innermost {
+            engine.scopes()
+        } else {
+            Vec::new()
+        };
+        let scopes: Vec<_> = scopes
+            .into_iter()
+            .map(|scope| {
+                let name = match scope.kind {
+                    EnvironmentType::Declarative => "Block",
+                    EnvironmentType::Function => "Local",
+                    EnvironmentType::Global => "Global",
+                    EnvironmentType::Object => "With",
+                };
+                json!({
+                    "name": name,
+                    "variablesReference": self.reference(Handle::Bindings(scope.bindings)),
+                    "expensive": scope.kind == EnvironmentType::Global,
+                })
+            })
+            .collect();
+        self.client.respond(request, json!({ "scopes": scopes }));
+    }
+
+    /// Responds with the bindings of a scope, or the properties of an object.
+    fn variables(&mut self, engine: &mut Interpreter, request: &Json) {
+        let handle = request["arguments"]["variablesReference"]
+            .as_u64()
+            .and_then(|reference| self.handles.get((reference as usize).checked_sub(1)?));
+        let entries = match handle {
+            Some(Handle::Bindings(bindings)) => bindings.clone(),
+            Some(Handle::Object(object)) => properties(object),
+            None => return self.client.fail(request, "the variables are gone"),
+        };
+        let variables: Vec<_> = entries
+            .into_iter()
+            .map(|(name, value)| {
+                json!({
+                    "name": name,
+                    "value": inspect(&value, 0, engine),
+                    "variablesReference": self.expandable(value),
+                })
+            })
+            .collect();
+        self.client
+            .respond(request, json!({ "variables": variables }));
+    }
+
+    /// Evaluates an expression in the innermost scope.
+    fn evaluate(&mut self, engine: &mut Interpreter, request: &Json) {
+        let expression = request["arguments"]["expression"]
+            .as_str()
+            .unwrap_or_default();
+        match engine.evaluate(expression) {
+            Ok(value) => {
+                let result = inspect(&value, 0, engine);
+                let reference = self.expandable(value);
+                self.client.respond(
+                    request,
+                    json!({ "result": result, "variablesReference": reference }),
+                );
+            }
+            Err(error) => {
+                let message = format!("Uncaught {}", inspect(&error, 0, engine));
+                self.client.fail(request, &message);
+            }
+        }
+    }
+
+    /// Finds the position of the editor for a position of the script, which is in the original
+    /// source if the source map covers it.
+    fn original(&self, position: Position) -> Original {
+        self.source_map
+            .as_ref()
+            .and_then(|source_map| source_map.lookup(position))
+            .unwrap_or_else(|| Original {
+                path: self.program.clone(),
+                position,
+            })
+    }
+
+    /// Gets the reference of a value which the editor can expand, which is 0 if it isn't an
+    /// object.
+    fn expandable(&mut self, value: Value) -> usize {
+        if value.is_object() {
+            self.reference(Handle::Object(value))
+        } else {
+            0
+        }
+    }
+
+    /// Keeps something the editor can expand until the script resumes, and returns its
+    /// reference.
+    fn reference(&mut self, handle: Handle) -> usize {
+        self.handles.push(handle);
+        self.handles.len()
+    }
+}
+
+impl Debugger for Session {
+    fn on_statement(&mut self, engine: &mut Interpreter, position: Position) {
+        let depth = engine.call_depth();
+        self.frames.resize(depth + 1, position);
+        self.frames[depth] = position;
+
+        // The editor can change the breakpoints, or ask for a pause, while the script runs.
+        while let Ok(request) = self.client.requests.try_recv() {
+            if let Some(step) = self.handle(engine, &request) {
+                self.step = step;
+            }
+        }
+
+        if let Some(reason) = self.pause_reason(depth, position) {
+            self.pause(engine, reason);
+        }
+    }
+}
+
+/// Resolves a path given by the editor, so that the paths of the same file are equal.
+fn canonical(path: &str) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path))
+}
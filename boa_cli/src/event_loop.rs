@@ -0,0 +1,305 @@
+//! A minimal event loop, which runs the timers and microtasks of the scripts.
+//!
+//! The event loop is the job queue of the realm. Once a script has finished, the microtasks are
+//! run, then the timers in the order of their deadlines, running the microtasks enqueued by each
+//! timer before the next one.
+//!
+//! The other threads, like the workers, send tasks to the event loop through its remote sender.
+//! They run as they arrive, between the timers, and the event loop waits for them while it is held.
+
+use boa::{
+    builtins::value::{ResultValue, Value},
+    exec::Interpreter,
+    host::HostOperation,
+    job::{Job, JobQueue, SimpleJobQueue},
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    /// The native functions can't capture anything, so they reach the event loop through here.
+    static EVENT_LOOP: Rc<EventLoop> = Rc::new(EventLoop::default());
+}
+
+/// Gets the event loop of the current thread, to be used as the job queue of the realm.
+pub(crate) fn event_loop() -> Rc<EventLoop> {
+    EVENT_LOOP.with(Rc::clone)
+}
+
+/// A timer started with `setTimeout` or `setInterval`.
+#[derive(Debug)]
+struct Timer {
+    /// The job run when the timer fires.
+    job: Job,
+    /// The delay between two runs, if the timer was started with `setInterval`.
+    interval: Option<Duration>,
+}
+
+/// The pending timers, ordered by deadline.
+#[derive(Debug, Default)]
+struct Timers {
+    /// The identifier of the next timer.
+    next_id: i32,
+    /// The timers, ordered by deadline, then by identifier so that timers with the same deadline
+    /// fire in the order they were started.
+    queue: BTreeMap<(Instant, i32), Timer>,
+    /// The deadline of each timer, to find it from its identifier.
+    deadlines: HashMap<i32, Instant>,
+}
+
+impl Timers {
+    /// Schedules a timer, and returns its identifier.
+    fn insert(&mut self, id: Option<i32>, deadline: Instant, timer: Timer) -> i32 {
+        let id = id.unwrap_or_else(|| {
+            self.next_id += 1;
+            self.next_id
+        });
+        self.queue.insert((deadline, id), timer);
+        self.deadlines.insert(id, deadline);
+        id
+    }
+
+    /// Cancels a timer, if it is still pending.
+    fn remove(&mut self, id: i32) {
+        if let Some(deadline) = self.deadlines.remove(&id) {
+            self.queue.remove(&(deadline, id));
+        }
+    }
+
+    /// Gets the earliest deadline.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.queue.keys().next().map(|&(deadline, _)| deadline)
+    }
+
+    /// Takes the timer with the earliest deadline.
+    fn pop(&mut self) -> Option<(i32, Timer)> {
+        let &(deadline, id) = self.queue.keys().next()?;
+        let timer = self.queue.remove(&(deadline, id))?;
+        self.deadlines.remove(&id);
+        Some((id, timer))
+    }
+}
+
+/// A task sent to the event loop by another thread, which runs in its realm.
+pub(crate) type RemoteTask = Box<dyn FnOnce(&mut Interpreter) -> Result<(), Value> + Send>;
+
+/// The channel of the tasks sent by the other threads.
+#[derive(Debug)]
+struct RemoteTasks {
+    #[cfg_attr(not(feature = "worker"), allow(dead_code))]
+    sender: Sender<RemoteTask>,
+    receiver: Receiver<RemoteTask>,
+}
+
+impl Default for RemoteTasks {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+}
+
+/// The event loop of the CLI.
+#[derive(Debug, Default)]
+pub(crate) struct EventLoop {
+    /// The microtasks, run before each timer.
+    microtasks: SimpleJobQueue,
+    /// The pending timers.
+    timers: RefCell<Timers>,
+    /// The tasks sent by the other threads.
+    remote: RemoteTasks,
+    /// The number of holds, which keep the event loop waiting for remote tasks once it has
+    /// nothing else to run.
+    holds: Cell<usize>,
+    /// Whether the event loop was closed, in which case it stops running the jobs.
+    closed: Cell<bool>,
+}
+
+impl EventLoop {
+    /// Waits for the next remote task, and runs it.
+    pub(crate) fn run_remote_task(&self, interpreter: &mut Interpreter) -> Result<(), Value> {
+        let task = self
+            .remote
+            .receiver
+            .recv()
+            .expect("the event loop keeps a sender");
+        task(interpreter)
+    }
+}
+
+#[cfg(feature = "worker")]
+impl EventLoop {
+    /// Gets a sender of tasks to this event loop, for another thread.
+    pub(crate) fn remote_sender(&self) -> Sender<RemoteTask> {
+        self.remote.sender.clone()
+    }
+
+    /// Keeps the event loop waiting for remote tasks, until the hold is released.
+    pub(crate) fn hold(&self) {
+        self.holds.set(self.holds.get() + 1);
+    }
+
+    /// Releases a hold taken with `hold`.
+    pub(crate) fn release(&self) {
+        self.holds.set(self.holds.get().saturating_sub(1));
+    }
+
+    /// Closes the event loop, which stops running the jobs once the running one returns, and
+    /// discards the pending ones.
+    pub(crate) fn close(&self) {
+        self.closed.set(true);
+        self.microtasks.clear();
+        *self.timers.borrow_mut() = Timers::default();
+    }
+
+    /// Whether the event loop was closed.
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.get()
+    }
+}
+
+impl JobQueue for EventLoop {
+    fn enqueue_promise_job(&self, job: Job, interpreter: &mut Interpreter) {
+        self.microtasks.enqueue_promise_job(job, interpreter);
+    }
+
+    fn run_jobs(&self, interpreter: &mut Interpreter) -> Result<(), Value> {
+        loop {
+            self.microtasks.run_jobs(interpreter)?;
+            if self.closed.get() {
+                return Ok(());
+            }
+            if let Ok(task) = self.remote.receiver.try_recv() {
+                task(interpreter)?;
+                continue;
+            }
+
+            // The remote tasks are waited for until the next timer fires.
+            let deadline = self.timers.borrow().next_deadline();
+            match deadline {
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if deadline > now {
+                        if let Ok(task) = self.remote.receiver.recv_timeout(deadline - now) {
+                            task(interpreter)?;
+                            continue;
+                        }
+                    }
+                }
+                None if self.holds.get() > 0 => {
+                    self.run_remote_task(interpreter)?;
+                    continue;
+                }
+                None => return Ok(()),
+            }
+
+            // The borrow must end before the timer runs, as it can start or clear timers.
+            let next = self.timers.borrow_mut().pop();
+            let (id, timer) = match next {
+                Some(next) => next,
+                None => continue,
+            };
+
+            // An interval is rescheduled before it runs, so that it can clear itself.
+            if let Some(interval) = timer.interval {
+                self.timers.borrow_mut().insert(
+                    Some(id),
+                    Instant::now() + interval,
+                    Timer {
+                        job: timer.job.clone(),
+                        interval: timer.interval,
+                    },
+                );
+            }
+            timer.job.run(interpreter)?;
+        }
+    }
+}
+
+/// Starts a timer, with the arguments of `setTimeout` or `setInterval`.
+fn start_timer(args: &[Value], ctx: &mut Interpreter, repeat: bool) -> ResultValue {
+    let callback = args.first().cloned().unwrap_or_else(Value::undefined);
+    if !callback.is_function() {
+        return ctx.throw_type_error("the timer callback must be a function");
+    }
+    let delay = match args.get(1) {
+        Some(delay) => ctx.to_number(delay)?,
+        None => 0.0,
+    };
+    if !ctx.check_host_operation(&HostOperation::CreateTimer { delay, repeat })? {
+        return Ok(Value::undefined());
+    }
+    // Negative and NaN delays run the timer as soon as possible.
+    let delay = Duration::from_millis(if delay >= 1.0 { delay as u64 } else { 0 });
+    let args = args.get(2..).unwrap_or_default().to_vec();
+
+    let timer = Timer {
+        job: Job::new(callback, args),
+        interval: if repeat { Some(delay) } else { None },
+    };
+    let id = EVENT_LOOP.with(|event_loop| {
+        event_loop
+            .timers
+            .borrow_mut()
+            .insert(None, Instant::now() + delay, timer)
+    });
+    Ok(Value::from(id))
+}
+
+/// Cancels the timer with the identifier given as the first argument.
+fn clear_timer(args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if let Some(id) = args.first() {
+        let id = ctx.to_number(id)?;
+        if id.fract() == 0.0 && id >= f64::from(i32::MIN) && id <= f64::from(i32::MAX) {
+            EVENT_LOOP.with(|event_loop| event_loop.timers.borrow_mut().remove(id as i32));
+        }
+    }
+    Ok(Value::undefined())
+}
+
+/// `setTimeout( callback [ , delay [ , ...args ] ] )`
+///
+/// Calls the callback with the given arguments once the delay, in milliseconds, has elapsed.
+/// Returns the identifier of the timer.
+pub(crate) fn set_timeout(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    start_timer(args, ctx, false)
+}
+
+/// `setInterval( callback [ , delay [ , ...args ] ] )`
+///
+/// Calls the callback with the given arguments every time the delay, in milliseconds, has elapsed.
+/// Returns the identifier of the timer.
+pub(crate) fn set_interval(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    start_timer(args, ctx, true)
+}
+
+/// `clearTimeout( id )`
+///
+/// Cancels a timer started with `setTimeout`.
+pub(crate) fn clear_timeout(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    clear_timer(args, ctx)
+}
+
+/// `clearInterval( id )`
+///
+/// Cancels a timer started with `setInterval`.
+pub(crate) fn clear_interval(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    clear_timer(args, ctx)
+}
+
+/// `queueMicrotask( callback )`
+///
+/// Calls the callback once the running script, and the microtasks enqueued before it, have
+/// finished.
+pub(crate) fn queue_microtask(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let callback = args.first().cloned().unwrap_or_else(Value::undefined);
+    if !callback.is_function() {
+        return ctx.throw_type_error("the microtask callback must be a function");
+    }
+    ctx.enqueue_job(Job::new(callback, Vec::new()));
+    Ok(Value::undefined())
+}
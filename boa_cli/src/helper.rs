@@ -0,0 +1,122 @@
+//! The completion of the interactive mode.
+//!
+//! Pressing tab completes the name before the cursor with the global bindings, or, after a `.`,
+//! with the properties of the object it is read from, prototypes included. Only chains of names,
+//! like `a.b.c`, are completed: nothing is run to find the object.
+
+use boa::{
+    builtins::{object::property_names, value::Value},
+    exec::Interpreter,
+};
+use rustyline::{
+    completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator, Context,
+    Helper, Result,
+};
+
+/// The helper of the line editor, which completes the names with the bindings of the engine.
+///
+/// It keeps a copy of the global bindings, which is refreshed after each input, as the editor
+/// can't borrow the engine while it reads a line.
+#[derive(Debug, Default)]
+pub(crate) struct ReplHelper {
+    globals: Vec<(String, Value)>,
+}
+
+impl ReplHelper {
+    /// Copies the global bindings of the engine, to complete the next lines with them.
+    pub(crate) fn refresh(&mut self, engine: &Interpreter) {
+        self.globals = engine.global_bindings();
+    }
+
+    /// Finds the value of a global binding.
+    fn global(&self, name: &str) -> Option<&Value> {
+        self.globals
+            .iter()
+            .find(|(binding, _)| binding == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Gets the candidates for the last name of a chain of names separated by dots.
+    fn candidates(&self, chain: &str) -> Vec<String> {
+        let mut names = chain.split('.');
+        let first = names.next().unwrap_or_default();
+        let mut names: Vec<&str> = names.collect();
+        let prefix = match names.pop() {
+            Some(prefix) => prefix,
+            None => return filter(self.globals.iter().map(|(name, _)| name.as_str()), first),
+        };
+
+        let mut object = match self.global(first) {
+            Some(value) => value.clone(),
+            None => return Vec::new(),
+        };
+        for name in names {
+            if !object.is_object() {
+                return Vec::new();
+            }
+            object = object.get_field(name);
+        }
+        filter(property_names(&object).iter().map(String::as_str), prefix)
+    }
+}
+
+/// Keeps the names starting with the prefix which can be written after a `.`, sorted.
+fn filter<'a, I>(names: I, prefix: &str) -> Vec<String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    let mut names: Vec<String> = names
+        .filter(|name| name.starts_with(prefix) && is_identifier(name))
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Checks if a name is an identifier, leaving out the unicode escapes and letters.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => chars.all(is_identifier_part),
+        _ => false,
+    }
+}
+
+/// Checks if a character can be part of an identifier, leaving out the unicode letters.
+fn is_identifier_part(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _: &Context<'_>) -> Result<(usize, Vec<String>)> {
+        let before = &line[..pos];
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_identifier_part(c) && c != '.')
+            .map_or(0, |(index, c)| index + c.len_utf8());
+        let chain = &before[start..];
+        if chain.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return Ok((pos, Vec::new()));
+        }
+
+        let prefix_start = before
+            .rfind('.')
+            .filter(|&dot| dot >= start)
+            .map_or(start, |dot| dot + 1);
+        Ok((prefix_start, self.candidates(chain)))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
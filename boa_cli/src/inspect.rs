@@ -0,0 +1,469 @@
+//! The formatting of the values printed by the interactive mode, like Node's `util.inspect`.
+//!
+//! Unlike `to_string()`, the structure of the values is shown: the strings are quoted, and the
+//! objects and arrays are expanded down to a given depth, below which they are abbreviated as
+//! `[Object]` or `[Array]`. An object which contains itself is printed as `[Circular]`.
+
+use boa::{
+    builtins::{
+        object::{ObjectKind, INSTANCE_PROTOTYPE},
+        property::Property,
+        value::{same_value, Value, ValueData},
+    },
+    exec::Interpreter,
+};
+use colored::Colorize;
+
+/// The number of columns above which the entries of an object are printed on separate lines.
+const BREAK_LENGTH: usize = 80;
+
+/// The number of elements of an array which are printed, the rest being only counted.
+const MAX_ARRAY_LENGTH: usize = 100;
+
+/// Formats a value, expanding the objects down to `depth` levels of nesting.
+pub(crate) fn inspect(value: &Value, depth: usize, engine: &mut Interpreter) -> String {
+    let mut inspector = Inspector {
+        engine,
+        depth,
+        seen: Vec::new(),
+        indentation: 0,
+    };
+    inspector.format(value, 0)
+}
+
+/// Lists the own enumerable properties of an object with their values, which are the properties
+/// it's printed with, for the debuggers which expand the objects one level at a time.
+///
+/// The accessors aren't called, so the value of their properties is `undefined`.
+#[cfg(feature = "dap")]
+pub(crate) fn properties(value: &Value) -> Vec<(String, Value)> {
+    let object = match value.as_object() {
+        Some(object) => object,
+        None => return Vec::new(),
+    };
+    object
+        .properties
+        .iter()
+        .filter(|(key, property)| property.enumerable == Some(true) && !is_hidden(object.kind, key))
+        .map(|(key, property)| {
+            let value = property.value.clone().unwrap_or_else(Value::undefined);
+            (key.into_owned(), value)
+        })
+        .collect()
+}
+
+/// The state of the formatting of a value.
+struct Inspector<'a> {
+    engine: &'a mut Interpreter,
+    /// The number of levels of nesting which are expanded.
+    depth: usize,
+    /// The objects which are being formatted, from the outermost, to detect the cycles.
+    seen: Vec<Value>,
+    /// The number of spaces before the entries of the current object, when they are on
+    /// separate lines.
+    indentation: usize,
+}
+
+impl Inspector<'_> {
+    /// Formats a value nested in `level` objects.
+    fn format(&mut self, value: &Value, level: usize) -> String {
+        match *value.data() {
+            ValueData::Undefined => "undefined".bright_black().to_string(),
+            ValueData::Null => "null".bold().to_string(),
+            ValueData::Boolean(boolean) => boolean.to_string().yellow().to_string(),
+            ValueData::Integer(_) | ValueData::Rational(_) => {
+                self.number(value).yellow().to_string()
+            }
+            ValueData::BigInt(ref bigint) => format!("{}n", bigint).yellow().to_string(),
+            ValueData::String(ref string) => quote(&string.to_string()).green().to_string(),
+            ValueData::Symbol(_) => value.to_string().green().to_string(),
+            ValueData::Object(_) => self.object(value, level),
+        }
+    }
+
+    /// Formats a number as JavaScript does, keeping the sign of zero.
+    fn number(&mut self, value: &Value) -> String {
+        match *value.data() {
+            ValueData::Rational(number) if number == 0.0 && number.is_sign_negative() => {
+                "-0".to_string()
+            }
+            _ => self
+                .engine
+                .to_string(value)
+                .unwrap_or_else(|_| value.to_string()),
+        }
+    }
+
+    /// Formats an object, with its own enumerable properties.
+    fn object(&mut self, value: &Value, level: usize) -> String {
+        if self.seen.iter().any(|seen| same_value(seen, value)) {
+            return "[Circular]".cyan().to_string();
+        }
+
+        let (kind, properties) = {
+            let object = value.as_object().expect("the value is an object");
+            let properties: Vec<(String, Property)> = object
+                .properties
+                .iter()
+                .filter(|(key, property)| {
+                    property.enumerable == Some(true) && !is_hidden(object.kind, key)
+                })
+                .map(|(key, property)| (key.into_owned(), property.into_owned()))
+                .collect();
+            (object.kind, properties)
+        };
+
+        let prefix = self.prefix(value, kind);
+        let is_array = kind == ObjectKind::Array;
+        if properties.is_empty() {
+            return match (prefix, is_array) {
+                (Some(prefix), false) if kind != ObjectKind::Ordinary => prefix,
+                (Some(prefix), _) => format!("{} {}", prefix, if is_array { "[]" } else { "{}" }),
+                (None, true) => "[]".to_string(),
+                (None, false) => "{}".to_string(),
+            };
+        }
+        if level > self.depth {
+            let name = if is_array {
+                "[Array]".to_string()
+            } else {
+                format!(
+                    "[{}]",
+                    self.constructor_name(value)
+                        .unwrap_or_else(|| "Object".to_string())
+                )
+            };
+            return name.cyan().to_string();
+        }
+
+        self.seen.push(value.clone());
+        self.indentation += 2;
+        let (entries, grouped) = if is_array {
+            self.array_entries(value, properties, level)
+        } else {
+            let entries = properties
+                .into_iter()
+                .map(|(key, property)| self.entry(&key, &property, level))
+                .collect();
+            (entries, false)
+        };
+        self.indentation -= 2;
+        self.seen.pop();
+
+        let braces = if is_array { ("[", "]") } else { ("{", "}") };
+        self.join(prefix, braces, entries, grouped)
+    }
+
+    /// Formats the elements of an array, counting the holes, then its other properties.
+    ///
+    /// Also tells if the elements were grouped in columns, which takes several lines.
+    fn array_entries(
+        &mut self,
+        value: &Value,
+        properties: Vec<(String, Property)>,
+        level: usize,
+    ) -> (Vec<String>, bool) {
+        let length = self
+            .engine
+            .to_uint32(&value.get_field("length"))
+            .unwrap_or(0) as usize;
+        let mut entries = Vec::new();
+        let mut others = Vec::new();
+        let mut next = 0;
+        let mut printed = 0;
+        let mut numeric = true;
+        for (key, property) in properties {
+            let index = match key.parse::<usize>() {
+                Ok(index) if index < length && index.to_string() == key => index,
+                _ => {
+                    others.push((key, property));
+                    continue;
+                }
+            };
+            if printed == MAX_ARRAY_LENGTH {
+                continue;
+            }
+            if index > next {
+                entries.push(empty_items(index - next));
+                printed += 1;
+                numeric = false;
+            }
+            numeric &=
+                matches!(property.value, Some(ref value) if value.is_number() || value.is_bigint());
+            entries.push(self.property(&property, level));
+            printed += 1;
+            next = index + 1;
+        }
+        if printed < MAX_ARRAY_LENGTH && next < length {
+            entries.push(empty_items(length - next));
+            numeric = false;
+        }
+
+        let count = entries.len();
+        let mut entries = self.group(entries, numeric);
+        let grouped = entries.len() != count;
+        if printed == MAX_ARRAY_LENGTH && next < length {
+            let remaining = length - next;
+            entries.push(format!(
+                "... {} more item{}",
+                remaining,
+                if remaining == 1 { "" } else { "s" }
+            ));
+        }
+
+        for (key, property) in others {
+            entries.push(self.entry(&key, &property, level));
+        }
+        (entries, grouped)
+    }
+
+    /// Groups the elements of a long array in columns, as Node does, if they are short enough
+    /// for several of them to fit on a line.
+    ///
+    /// The columns are aligned, to the right if all the elements are numbers.
+    fn group(&self, entries: Vec<String>, numeric: bool) -> Vec<String> {
+        if entries.len() <= 6 {
+            return entries;
+        }
+        let lengths: Vec<usize> = entries.iter().map(|entry| visible_length(entry)).collect();
+        let max_length = lengths.iter().copied().max().unwrap_or(0);
+        let total_length: usize = lengths.iter().map(|length| length + 2).sum();
+        let actual_max = max_length + 2;
+        if actual_max * 3 + self.indentation >= BREAK_LENGTH
+            || (total_length as f64 / actual_max as f64 <= 5.0 && max_length > 6)
+        {
+            return entries;
+        }
+
+        // A character is about 2.5 times as high as it is wide, and the columns are chosen to
+        // draw a square, with more columns for the short elements.
+        let average_bias = (actual_max as f64 - total_length as f64 / entries.len() as f64).sqrt();
+        let biased_max = (actual_max as f64 - 3.0 - average_bias).max(1.0);
+        let columns = ((2.5 * biased_max * entries.len() as f64).sqrt() / biased_max)
+            .round()
+            .min(((BREAK_LENGTH - self.indentation) / actual_max) as f64)
+            .min(12.0) as usize;
+        if columns <= 1 {
+            return entries;
+        }
+
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                lengths
+                    .iter()
+                    .skip(column)
+                    .step_by(columns)
+                    .copied()
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        entries
+            .chunks(columns)
+            .zip(lengths.chunks(columns))
+            .map(|(row, lengths)| {
+                let mut line = String::new();
+                for (column, (entry, &length)) in row.iter().zip(lengths).enumerate() {
+                    let last = column == row.len() - 1;
+                    let padding = " ".repeat(widths[column] - length);
+                    if numeric {
+                        line.push_str(&padding);
+                    }
+                    line.push_str(entry);
+                    if !last {
+                        line.push_str(", ");
+                        if !numeric {
+                            line.push_str(&padding);
+                        }
+                    }
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Formats a property with its key.
+    fn entry(&mut self, key: &str, property: &Property, level: usize) -> String {
+        let key = if is_identifier(key) {
+            key.to_string()
+        } else {
+            quote(key).green().to_string()
+        };
+        format!("{}: {}", key, self.property(property, level))
+    }
+
+    /// Formats the value of a property, without calling its accessors.
+    fn property(&mut self, property: &Property, level: usize) -> String {
+        match (&property.value, &property.get, &property.set) {
+            (Some(value), _, _) => self.format(value, level + 1),
+            (None, Some(_), Some(_)) => "[Getter/Setter]".cyan().to_string(),
+            (None, Some(_), None) => "[Getter]".cyan().to_string(),
+            (None, None, Some(_)) => "[Setter]".cyan().to_string(),
+            (None, None, None) => self.format(&Value::undefined(), level + 1),
+        }
+    }
+
+    /// Gets what is printed before the braces of an object: its type, or the name of its
+    /// constructor if it isn't a plain object.
+    fn prefix(&mut self, value: &Value, kind: ObjectKind) -> Option<String> {
+        match kind {
+            ObjectKind::Function => Some(
+                match string_of(&value.get_field("name")) {
+                    Some(name) if !name.is_empty() => format!("[Function: {}]", name),
+                    _ => "[Function (anonymous)]".to_string(),
+                }
+                .cyan()
+                .to_string(),
+            ),
+            ObjectKind::Error => {
+                let name = self
+                    .constructor_name(value)
+                    .unwrap_or_else(|| "Error".to_string());
+                let message = value.get_field("message");
+                Some(match string_of(&message) {
+                    Some(message) if !message.is_empty() => format!("{}: {}", name, message),
+                    _ => name,
+                })
+            }
+            ObjectKind::String | ObjectKind::Number | ObjectKind::Boolean | ObjectKind::BigInt => {
+                let slot = match kind {
+                    ObjectKind::String => "StringData",
+                    ObjectKind::Number => "NumberData",
+                    ObjectKind::Boolean => "BooleanData",
+                    _ => "BigIntData",
+                };
+                let primitive = self.format(&value.get_internal_slot(slot), 0);
+                Some(format!("[{}: {}]", kind, primitive))
+            }
+            _ => {
+                if value.get_internal_slot(INSTANCE_PROTOTYPE).is_null() {
+                    return Some("[Object: null prototype]".to_string());
+                }
+                self.constructor_name(value).filter(|name| match kind {
+                    ObjectKind::Array => name != "Array",
+                    _ => name != "Object",
+                })
+            }
+        }
+    }
+
+    /// Gets the name of the constructor of an object, if it has one.
+    fn constructor_name(&self, value: &Value) -> Option<String> {
+        let constructor = value.get_field("constructor");
+        if !constructor.is_function() {
+            return None;
+        }
+        string_of(&constructor.get_field("name")).filter(|name| !name.is_empty())
+    }
+
+    /// Joins the entries of an object on a single line if they are short enough, or on separate
+    /// indented lines otherwise.
+    fn join(
+        &self,
+        prefix: Option<String>,
+        braces: (&str, &str),
+        entries: Vec<String>,
+        grouped: bool,
+    ) -> String {
+        let start = match prefix {
+            Some(prefix) => format!("{} {}", prefix, braces.0),
+            None => braces.0.to_string(),
+        };
+        let length = visible_length(&start)
+            + entries
+                .iter()
+                .map(|entry| visible_length(entry) + 2)
+                .sum::<usize>()
+            + self.indentation;
+        if !grouped && length <= BREAK_LENGTH && !entries.iter().any(|entry| entry.contains('\n')) {
+            return format!("{} {} {}", start, entries.join(", "), braces.1);
+        }
+
+        let indentation = format!("\n{}", " ".repeat(self.indentation));
+        format!(
+            "{}{}  {}{}{}",
+            start,
+            indentation,
+            entries.join(&format!(",{}  ", indentation)),
+            indentation,
+            braces.1
+        )
+    }
+}
+
+/// Checks if a property is hidden, because this engine makes it enumerable when the
+/// specification doesn't, like the `length` of the functions.
+fn is_hidden(kind: ObjectKind, key: &str) -> bool {
+    match kind {
+        ObjectKind::Function => key == "length" || key == "name" || key == "prototype",
+        ObjectKind::Error => key == "message",
+        ObjectKind::String => key == "length",
+        _ => false,
+    }
+}
+
+/// Gets the contents of a string value.
+fn string_of(value: &Value) -> Option<String> {
+    match *value.data() {
+        ValueData::String(ref string) => Some(string.to_string()),
+        _ => None,
+    }
+}
+
+/// Formats a run of holes in an array.
+fn empty_items(count: usize) -> String {
+    format!(
+        "<{} empty item{}>",
+        count,
+        if count == 1 { "" } else { "s" }
+    )
+    .bright_black()
+    .to_string()
+}
+
+/// Quotes a string with single quotes, escaping the characters which can't be printed as is.
+fn quote(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('\'');
+    for c in string.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\u{b}' => quoted.push_str("\\v"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Checks if a key can be printed without quotes.
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == '$' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+/// Counts the characters of a string which are shown, leaving out the color escape sequences.
+fn visible_length(string: &str) -> usize {
+    let mut length = 0;
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            length += 1;
+        }
+    }
+    length
+}
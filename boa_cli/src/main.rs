@@ -25,19 +25,58 @@
     clippy::as_conversions
 )]
 
+mod command;
+#[cfg(feature = "commonjs")]
+mod commonjs;
+mod coverage;
+#[cfg(feature = "dap")]
+mod dap;
+mod event_loop;
+mod helper;
+mod inspect;
+mod repl;
+#[cfg_attr(not(feature = "commonjs"), allow(dead_code))]
+mod resolver;
+mod source_map;
+mod timing;
+mod watch;
+#[cfg(feature = "worker")]
+mod worker;
+
 use boa::{
-    builtins::console::log,
+    builtins::{console::log, value::Value},
+    clock::FixedClock,
     exec::Interpreter,
-    forward_val,
+    parse,
     realm::Realm,
-    syntax::ast::{node::StatementList, token::Token},
+    syntax::{
+        ast::{
+            dot::to_dot,
+            estree::to_estree,
+            node::StatementList,
+            token::{Token, TokenKind},
+        },
+        lexer::Lexer,
+        parser::Parser,
+    },
+    Executable,
+};
+use event_loop::{
+    clear_interval, clear_timeout, event_loop, queue_microtask, set_interval, set_timeout,
 };
+use inspect::inspect;
+use source_map::{syntax_error, FileSourceMap};
 use std::{
-    fs::read_to_string,
-    io::{self, Write},
+    env,
+    ffi::OsStr,
+    fs::{self, read_to_string},
+    io::{self, Read, Write},
     path::PathBuf,
+    process,
 };
 use structopt::{clap::arg_enum, StructOpt};
+use timing::{measure, Report, Timings};
+use watch::watch;
 
 #[cfg(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))]
 #[cfg_attr(
@@ -59,6 +98,10 @@ struct Opt {
     #[structopt(name = "FILE", parse(from_os_str))]
     files: Vec<PathBuf>,
 
+    /// JavaScript code to be evaluated, after the files.
+    #[structopt(long, short = "e", value_name = "CODE")]
+    eval: Option<String>,
+
     /// Dump the token stream to stdout with the given format.
     #[structopt(
         long,
@@ -79,6 +122,78 @@ struct Opt {
         case_insensitive = true
     )]
     dump_ast: Option<Option<DumpFormat>>,
+
+    /// Check the syntax of the scripts, and report their errors, without running them.
+    #[structopt(long, conflicts_with_all = &["dump-tokens", "dump-ast"])]
+    parse_only: bool,
+
+    /// The number of levels of nesting of the objects printed by the interactive mode.
+    #[structopt(long, value_name = "LEVELS", default_value = "2")]
+    depth: usize,
+
+    /// The arguments of the scripts, given after a `--`, which they find in `process.argv`.
+    #[structopt(name = "ARGS", last = true)]
+    script_args: Vec<String>,
+
+    /// Run the files again, in a new realm, each time one of them changes.
+    #[structopt(long, short = "w", requires = "FILE")]
+    watch: bool,
+
+    /// Print the time spent lexing, parsing, compiling and executing each script, and the peak
+    /// size of the heap, to stderr.
+    #[structopt(long)]
+    verbose_timing: bool,
+
+    /// Count the statements and the branches of the files which run, and write their coverage to
+    /// the given file, in the LCOV format.
+    #[structopt(
+        long,
+        value_name = "LCOV_FILE",
+        parse(from_os_str),
+        requires = "FILE",
+        conflicts_with_all = &["eval", "watch", "parse-only", "dump-tokens", "dump-ast", "verbose-timing"]
+    )]
+    coverage: Option<PathBuf>,
+
+    /// Record the calls of the functions, and write them to the given file: as trace events for
+    /// the Chrome DevTools if its extension is `.json`, or else as collapsed stacks for the flame
+    /// graph tools.
+    #[structopt(
+        long,
+        value_name = "PROFILE_FILE",
+        parse(from_os_str),
+        conflicts_with_all = &["watch", "parse-only", "dump-tokens", "dump-ast"]
+    )]
+    profile: Option<PathBuf>,
+
+    /// Write a snapshot of the heap to the given file once the files ran: in the format of the
+    /// Chrome DevTools if its extension is `.heapsnapshot`, or else as a summary of its objects by
+    /// class.
+    #[structopt(
+        long,
+        value_name = "SNAPSHOT_FILE",
+        parse(from_os_str),
+        conflicts_with_all = &["watch", "parse-only", "dump-tokens", "dump-ast"]
+    )]
+    heap_snapshot: Option<PathBuf>,
+
+    /// Print statistics about the garbage collected heap to stderr at exit.
+    #[structopt(long)]
+    gc_stats: bool,
+
+    /// Seed the generator of `Math.random`, so that it produces the same numbers on each run.
+    #[structopt(long, value_name = "SEED")]
+    random_seed: Option<u64>,
+
+    /// Stop the clock read by the scripts at the given time, in milliseconds since the Unix epoch.
+    #[structopt(long, value_name = "TIME")]
+    fixed_time: Option<f64>,
+
+    /// Serve the Debug Adapter Protocol on stdin and stdout, for an editor to debug the script
+    /// given in its launch request.
+    #[cfg(feature = "dap")]
+    #[structopt(long, conflicts_with_all = &["FILE", "eval", "watch", "parse-only"])]
+    dap: bool,
 }
 
 impl Opt {
@@ -107,17 +222,36 @@ arg_enum! {
 
         // This is a pretty printed json format.
         JsonPretty,
+
+        // This is JavaScript source code generated from the ast.
+        Js,
+
+        // This is a pretty printed json format following the ESTree specification.
+        Estree,
+
+        // This is a Graphviz graph of the ast, in the DOT language.
+        Dot,
+
+        // This is a yaml format, which is easy to read and to diff.
+        Yaml,
+
+        // This is a binary MessagePack format, for tools reading the dump.
+        Msgpack,
+
+        // This is a table of the tokens, with their position, kind and text.
+        Table,
     }
 }
 
 /// Lexes the given source code into a stream of tokens and return it.
 ///
+/// Comments are kept in the token stream, so that dumping it doesn't lose them.
+///
 /// Returns a error of type String with a message,
 /// if the source has a syntax error.
 fn lex_source(src: &str) -> Result<Vec<Token>, String> {
-    use boa::syntax::lexer::Lexer;
-
-    let mut lexer = Lexer::new(src);
+    let mut lexer = Lexer::new(src.as_bytes());
+    lexer.set_preserve_comments(true);
     lexer.lex().map_err(|e| format!("SyntaxError: {}", e))?;
     Ok(lexer.tokens)
 }
@@ -127,13 +261,56 @@ fn lex_source(src: &str) -> Result<Vec<Token>, String> {
 /// Returns a error of type String with a message,
 /// if the token stream has a parsing error.
 fn parse_tokens(tokens: Vec<Token>) -> Result<StatementList, String> {
-    use boa::syntax::parser::Parser;
-
     Parser::new(&tokens)
         .parse_all()
         .map_err(|e| format!("ParsingError: {}", e))
 }
 
+/// Formats the token stream as a table, with the position, the kind and the text of each token,
+/// which is easier to read than the other formats when debugging the lexer.
+fn token_table(tokens: &[Token]) -> String {
+    let rows: Vec<_> = tokens
+        .iter()
+        .map(|token| {
+            let (start, end) = (token.span().start(), token.span().end());
+            let position = format!(
+                "{}:{}-{}:{}",
+                start.line_number(),
+                start.column_number(),
+                end.line_number(),
+                end.column_number()
+            );
+            let text = match *token.kind() {
+                TokenKind::StringLiteral(ref string) => format!("{:?}", string.to_string()),
+                TokenKind::LineTerminator => "\\n".to_owned(),
+                ref kind => kind.to_string().escape_debug().to_string(),
+            };
+            (position, token.kind().name(), text)
+        })
+        .collect();
+
+    let position_width = rows.iter().map(|row| row.0.len()).fold(8, usize::max);
+    let kind_width = rows.iter().map(|row| row.1.len()).fold(4, usize::max);
+    let mut table = format!(
+        "{:pw$}  {:kw$}  TEXT\n",
+        "POSITION",
+        "KIND",
+        pw = position_width,
+        kw = kind_width
+    );
+    for (position, kind, text) in rows {
+        table.push_str(&format!(
+            "{:pw$}  {:kw$}  {}\n",
+            position,
+            kind,
+            text,
+            pw = position_width,
+            kw = kind_width
+        ));
+    }
+    table
+}
+
 /// Dumps the token stream or ast to stdout depending on the given arguments.
 ///
 /// Returns a error of type String with a error message,
@@ -149,6 +326,17 @@ fn dump(src: &str, args: &Opt) -> Result<(), String> {
                 DumpFormat::JsonPretty => {
                     println!("{}", serde_json::to_string_pretty(&tokens).unwrap())
                 }
+                DumpFormat::Yaml => print!("{}", serde_yaml::to_string(&tokens).unwrap()),
+                DumpFormat::Msgpack => io::stdout()
+                    .write_all(&rmp_serde::to_vec_named(&tokens).unwrap())
+                    .unwrap(),
+                DumpFormat::Table => print!("{}", token_table(&tokens)),
+                DumpFormat::Js | DumpFormat::Estree | DumpFormat::Dot => {
+                    return Err(format!(
+                        "the {} format can only be used to dump the ast",
+                        format
+                    ))
+                }
             },
             // Default token stream dumping format.
             None => println!("{:#?}", tokens),
@@ -163,6 +351,24 @@ fn dump(src: &str, args: &Opt) -> Result<(), String> {
                 DumpFormat::JsonPretty => {
                     println!("{}", serde_json::to_string_pretty(&ast).unwrap())
                 }
+                DumpFormat::Yaml => print!("{}", serde_yaml::to_string(&ast).unwrap()),
+                DumpFormat::Msgpack => io::stdout()
+                    .write_all(&rmp_serde::to_vec_named(&ast).unwrap())
+                    .unwrap(),
+                DumpFormat::Js => print!("{}", ast),
+                DumpFormat::Estree => {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&to_estree(&ast)).unwrap()
+                    )
+                }
+                DumpFormat::Dot => print!("{}", to_dot(&ast)),
+                DumpFormat::Table => {
+                    return Err(format!(
+                        "the {} format can only be used to dump the token stream",
+                        format
+                    ))
+                }
             },
             // Default ast dumping format.
             None => println!("{:#?}", ast),
@@ -172,51 +378,254 @@ fn dump(src: &str, args: &Opt) -> Result<(), String> {
     Ok(())
 }
 
-pub fn main() -> Result<(), std::io::Error> {
-    let args = Opt::from_args();
+/// Runs a script, or dumps it if a dump flag was given.
+///
+/// With `--verbose-timing`, the time spent in each phase is reported under the given name. The
+/// syntax errors are also reported in the original source, if the script has a source map.
+///
+/// Returns `false` if the script doesn't parse, or throws an uncaught exception.
+fn execute(
+    engine: &mut Interpreter,
+    name: &str,
+    source: &str,
+    source_map: Option<&FileSourceMap>,
+    args: &Opt,
+) -> bool {
+    if args.has_dump_flag() {
+        if let Err(e) = dump(source, args) {
+            eprintln!("{}", e);
+            return false;
+        }
+        return true;
+    }
 
-    let realm = Realm::create().register_global_func("print", log);
+    let mut timings = Timings::default();
+    let script = if args.verbose_timing {
+        timings.parse_script(engine, source, source_map)
+    } else {
+        parse_script(engine, source, source_map)
+    };
+    let succeeded = match script {
+        Ok(script) => {
+            let (succeeded, execute) = measure("execute", || run_script(engine, &script, args));
+            timings.execute = execute;
+            succeeded
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            false
+        }
+    };
 
+    if args.verbose_timing {
+        let stats = engine.gc_stats();
+        eprintln!(
+            "{}",
+            Report {
+                name,
+                timings,
+                stats
+            }
+        );
+    }
+    succeeded
+}
+
+/// Runs a parsed script and the jobs it queued.
+///
+/// The completion value of the script isn't printed, only the interactive mode echoes it.
+///
+/// Returns `false` if it throws an uncaught exception.
+fn run_script(engine: &mut Interpreter, script: &StatementList, args: &Opt) -> bool {
+    if let Err(v) = script.run(engine) {
+        eprintln!("Uncaught {}", inspect(&v, args.depth, engine));
+        return false;
+    }
+    if let Err(v) = engine.run_jobs() {
+        eprintln!("Uncaught {}", inspect(&v, args.depth, engine));
+        return false;
+    }
+    true
+}
+
+/// Parses a script, and runs the early error checks on it.
+fn parse_script(
+    engine: &Interpreter,
+    source: &str,
+    source_map: Option<&FileSourceMap>,
+) -> Result<StatementList, String> {
+    parse(source.as_bytes(), engine.optimizer_options()).map_err(|e| syntax_error(&e, source_map))
+}
+
+/// Parses the files, then the code given with `--eval`, or else the piped script, without running
+/// them, and prints their syntax errors prefixed with the name of their source.
+///
+/// Returns `false` if a script doesn't parse. Unlike `run`, the next ones are still checked.
+fn check_syntax(engine: &Interpreter, args: &Opt) -> io::Result<bool> {
+    let mut sources = Vec::new();
+    for file in &args.files {
+        let source = read_to_string(file)?;
+        let source_map = FileSourceMap::load(file, &source);
+        sources.push((file.display().to_string(), source, source_map));
+    }
+    if let Some(ref code) = args.eval {
+        sources.push(("<eval>".to_owned(), code.clone(), None));
+    } else if args.files.is_empty() {
+        let mut buffer = String::new();
+        io::stdin().read_to_string(&mut buffer)?;
+        sources.push(("<stdin>".to_owned(), buffer, None));
+    }
+
+    let mut succeeded = true;
+    for (name, source, source_map) in sources {
+        if let Err(e) = parse_script(engine, &source, source_map.as_ref()) {
+            eprintln!("{}: {}", name, e);
+            succeeded = false;
+        }
+    }
+    Ok(succeeded)
+}
+
+/// Creates a new realm, which has the globals of the CLI, like the timers, and runs its jobs in
+/// the event loop of the current thread.
+fn create_realm() -> Realm {
+    Realm::create()
+        .with_job_queue(event_loop())
+        .register_global_func("print", log)
+        .register_global_func("setTimeout", set_timeout)
+        .register_global_func("setInterval", set_interval)
+        .register_global_func("clearTimeout", clear_timeout)
+        .register_global_func("clearInterval", clear_interval)
+        .register_global_func("queueMicrotask", queue_microtask)
+}
+
+/// Creates an engine with a new realm, which has the globals of the CLI and the arguments of the
+/// scripts in `process.argv`. With the `commonjs` feature, it also has `require`, and with the
+/// `worker` feature, `Worker`.
+fn create_engine(args: &Opt) -> Interpreter {
+    let realm = create_realm();
+    let global = realm.global_obj.clone();
     let mut engine = Interpreter::new(realm);
+    if let Some(seed) = args.random_seed {
+        engine.set_random_seed(seed);
+    }
+    if let Some(time) = args.fixed_time {
+        engine.set_clock(Box::new(FixedClock(time)));
+    }
+
+    // Like in Node, the arguments follow the path of the engine, and the one of the script.
+    let argv: Vec<Value> = env::args()
+        .take(1)
+        .chain(args.files.first().map(|file| file.display().to_string()))
+        .chain(args.script_args.iter().cloned())
+        .map(Value::from)
+        .collect();
+    let process = Value::new_object(Some(&global));
+    process.set_field("argv", engine.create_array(&argv));
+    global.set_field("process", process);
+
+    #[cfg(feature = "commonjs")]
+    commonjs::register(
+        &global,
+        args.files.first(),
+        resolver::NodeResolver::default(),
+    );
+    #[cfg(feature = "worker")]
+    worker::register(&global, args.depth);
+
+    engine
+}
+
+/// Runs the files, then the code given with `--eval`, or else the piped script or the
+/// interactive mode. With `--parse-only`, they are only checked by `check_syntax`, and with
+/// `--coverage`, they are run by `coverage::run`.
+///
+/// Returns `false` if a script failed, in which case the next ones aren't run.
+fn run(engine: &mut Interpreter, args: &Opt) -> io::Result<bool> {
+    if args.parse_only {
+        return check_syntax(engine, args);
+    }
+    if let Some(ref output) = args.coverage {
+        return coverage::run(engine, args, output);
+    }
 
     for file in &args.files {
         let buffer = read_to_string(file)?;
-
-        if args.has_dump_flag() {
-            match dump(&buffer, &args) {
-                Ok(_) => {}
-                Err(e) => eprintln!("{}", e),
-            }
-        } else {
-            match forward_val(&mut engine, &buffer) {
-                Ok(v) => print!("{}", v.to_string()),
-                Err(v) => eprint!("{}", v.to_string()),
-            }
+        let source_map = FileSourceMap::load(file, &buffer);
+        let name = file.display().to_string();
+        if !execute(engine, &name, &buffer, source_map.as_ref(), args) {
+            return Ok(false);
         }
     }
 
+    if let Some(ref code) = args.eval {
+        return Ok(execute(engine, "<eval>", code, None, args));
+    }
+
     if args.files.is_empty() {
-        loop {
+        if atty::is(atty::Stream::Stdin) {
+            repl::run(engine, args)?;
+        } else {
+            // The script is piped, as in `cat test.js | boa`.
             let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            return Ok(execute(engine, "<stdin>", &buffer, None, args));
+        }
+    }
+    Ok(true)
+}
 
-            io::stdin().read_line(&mut buffer)?;
-
-            if args.has_dump_flag() {
-                match dump(&buffer, &args) {
-                    Ok(_) => {}
-                    Err(e) => eprintln!("{}", e),
-                }
-            } else {
-                match forward_val(&mut engine, buffer.trim_end()) {
-                    Ok(v) => println!("{}", v.to_string()),
-                    Err(v) => eprintln!("{}", v.to_string()),
-                }
-            }
+pub fn main() -> Result<(), io::Error> {
+    let args = Opt::from_args();
+    if !atty::is(atty::Stream::Stdout) || !atty::is(atty::Stream::Stderr) {
+        colored::control::set_override(false);
+    }
 
-            // The flush is needed because where in a REPL and we do not want buffering.
-            std::io::stdout().flush().unwrap();
+    #[cfg(feature = "dap")]
+    {
+        if args.dap {
+            return dap::serve(&args);
         }
     }
 
+    if args.watch {
+        return watch(&args);
+    }
+
+    let mut engine = create_engine(&args);
+    if args.profile.is_some() {
+        engine.start_profiling();
+    }
+    let succeeded = run(&mut engine, &args)?;
+
+    if let Some(ref path) = args.profile {
+        let profile = engine.stop_profiling().unwrap_or_default();
+        let output = if path.extension() == Some(OsStr::new("json")) {
+            profile.to_trace_events()
+        } else {
+            profile.to_collapsed_stacks()
+        };
+        fs::write(path, output)?;
+    }
+
+    if args.gc_stats {
+        eprintln!("{}", engine.gc_stats());
+    }
+
+    // The garbage is collected first, so that the values which aren't reached are the leaked ones.
+    if let Some(ref path) = args.heap_snapshot {
+        engine.gc();
+        let snapshot = engine.heap_snapshot(&[]);
+        let output = if path.extension() == Some(OsStr::new("heapsnapshot")) {
+            snapshot.to_chrome_json()
+        } else {
+            snapshot.to_string()
+        };
+        fs::write(path, output)?;
+    }
+
+    if !succeeded {
+        process::exit(1);
+    }
     Ok(())
 }
@@ -0,0 +1,189 @@
+//! The interactive mode of the CLI, started when no script is given and the input is a terminal.
+//!
+//! Lines are read with a line editor, which keeps the history of the previous sessions. The
+//! input is only run once its brackets are balanced, so functions and objects can be typed over
+//! several lines, and tab completes the names of the bindings and of the properties. The lines
+//! starting with a dot, like `.help`, are commands of the interactive mode.
+
+use crate::{
+    command::{Command, HELP},
+    create_engine, dump,
+    helper::ReplHelper,
+    inspect::inspect,
+    parse_script, Opt,
+};
+use boa::{
+    exec::Interpreter,
+    syntax::{
+        ast::{punctuator::Punctuator, token::TokenKind},
+        lexer::Lexer,
+    },
+    Executable,
+};
+use rustyline::{error::ReadlineError, Editor};
+use std::{
+    fs,
+    io::{self, Write},
+    mem,
+};
+
+/// The file in which the history is kept, in the working directory.
+const HISTORY_FILE: &str = ".boa_history";
+
+/// The prompt of a new input.
+const PROMPT: &str = "> ";
+
+/// The prompt of the lines which continue an input with unbalanced brackets.
+const CONTINUATION_PROMPT: &str = "... ";
+
+/// Reads the input line by line, and runs it in the given engine, until the end of the input.
+///
+/// Ctrl-C cancels the current input, and Ctrl-D ends the session.
+pub(crate) fn run(engine: &mut Interpreter, args: &Opt) -> io::Result<()> {
+    let mut editor = Editor::<ReplHelper>::new();
+    let mut helper = ReplHelper::default();
+    helper.refresh(engine);
+    editor.set_helper(Some(helper));
+    // There is no history on the first run.
+    let _ = editor.load_history(HISTORY_FILE);
+
+    let mut input = String::new();
+    // The inputs run in the current realm, which `.save` writes.
+    let mut session = Vec::new();
+    loop {
+        let prompt = if input.is_empty() {
+            PROMPT
+        } else {
+            CONTINUATION_PROMPT
+        };
+        match editor.readline(prompt) {
+            Ok(line) => {
+                if input.is_empty() {
+                    if let Some(command) = Command::parse(&line) {
+                        editor.add_history_entry(line.trim());
+                        let running = match command {
+                            Ok(command) => run_command(command, engine, &mut session, args),
+                            Err(message) => {
+                                eprintln!("{}", message);
+                                true
+                            }
+                        };
+                        if !running {
+                            break;
+                        }
+                        if let Some(helper) = editor.helper_mut() {
+                            helper.refresh(engine);
+                        }
+                        continue;
+                    }
+                }
+
+                input.push_str(&line);
+                input.push('\n');
+                if is_incomplete(&input) {
+                    continue;
+                }
+
+                let input = mem::take(&mut input);
+                if input.trim().is_empty() {
+                    continue;
+                }
+                editor.add_history_entry(input.trim_end());
+                evaluate(engine, &input, args);
+                session.push(input.trim_end().to_string());
+                if let Some(helper) = editor.helper_mut() {
+                    helper.refresh(engine);
+                }
+            }
+            Err(ReadlineError::Interrupted) => input.clear(),
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(io::Error::new(io::ErrorKind::Other, err)),
+        }
+    }
+
+    if let Err(err) = editor.save_history(HISTORY_FILE) {
+        eprintln!("could not save the history: {}", err);
+    }
+    Ok(())
+}
+
+/// Runs a command of the interactive mode, and returns `false` if it ends the session.
+fn run_command(
+    command: Command,
+    engine: &mut Interpreter,
+    session: &mut Vec<String>,
+    args: &Opt,
+) -> bool {
+    match command {
+        Command::Clear => {
+            *engine = create_engine(args);
+            session.clear();
+            println!("The realm was reset.");
+        }
+        Command::Exit => return false,
+        Command::Help => println!("{}", HELP),
+        Command::Load(path) => match fs::read_to_string(&path) {
+            Ok(source) => {
+                evaluate(engine, &source, args);
+                session.push(source.trim_end().to_string());
+            }
+            Err(err) => eprintln!("could not load {}: {}", path.display(), err),
+        },
+        Command::Save(path) => {
+            let mut contents = session.join("\n");
+            contents.push('\n');
+            match fs::write(&path, contents) {
+                Ok(()) => println!("The session was saved to {}.", path.display()),
+                Err(err) => eprintln!("could not save {}: {}", path.display(), err),
+            }
+        }
+    }
+    true
+}
+
+/// Checks if the input ends inside a block, parentheses or brackets, in which case the next
+/// lines are part of it.
+///
+/// Inputs which can't be lexed are complete, so that their error is reported.
+fn is_incomplete(input: &str) -> bool {
+    let mut depth = 0_i32;
+    for token in Lexer::new(input.as_bytes()) {
+        let token = match token {
+            Ok(token) => token,
+            Err(_) => return false,
+        };
+        match token.kind() {
+            TokenKind::Punctuator(Punctuator::OpenBlock)
+            | TokenKind::Punctuator(Punctuator::OpenParen)
+            | TokenKind::Punctuator(Punctuator::OpenBracket) => depth += 1,
+            TokenKind::Punctuator(Punctuator::CloseBlock)
+            | TokenKind::Punctuator(Punctuator::CloseParen)
+            | TokenKind::Punctuator(Punctuator::CloseBracket) => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+/// Runs an input, or dumps it if a dump flag was given, and prints the result.
+fn evaluate(engine: &mut Interpreter, input: &str, args: &Opt) {
+    if args.has_dump_flag() {
+        if let Err(e) = dump(input, args) {
+            eprintln!("{}", e);
+        }
+    } else {
+        match parse_script(engine, input, None).map(|script| script.run(engine)) {
+            Ok(Ok(v)) => println!("{}", inspect(&v, args.depth, engine)),
+            Ok(Err(v)) => eprintln!("Uncaught {}", inspect(&v, args.depth, engine)),
+            Err(e) => eprintln!("Uncaught {}", e),
+        }
+        if let Err(v) = engine.run_jobs() {
+            eprintln!("Uncaught {}", inspect(&v, args.depth, engine));
+        }
+    }
+
+    // The flush is needed because where in a REPL and we do not want buffering.
+    io::stdout()
+        .flush()
+        .expect("could not flush the standard output");
+}
@@ -0,0 +1,133 @@
+//! The resolution of module specifiers to the files of the modules.
+//!
+//! A `Resolver` finds the file named by a specifier in a module of a given directory, so that
+//! the loaders of modules don't depend on how the files are laid out.
+//!
+//! The `NodeResolver` finds the files like Node does. A specifier which is a path, starting with
+//! `/`, `./` or `../`, is looked for relative to the directory of the module, first as a file,
+//! with the extensions added if there is none, then as a directory, with the entry point given
+//! by its `package.json` or its `index` file. The other specifiers are packages, looked for in
+//! the `node_modules` directories of the directory of the module and of its parents.
+
+use std::{
+    ffi::OsStr,
+    fmt::Debug,
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+pub(crate) mod tests;
+
+/// Finds the files of the modules from their specifiers.
+pub(crate) trait Resolver: Debug {
+    /// Finds the file of the module named by a specifier in a module of the given directory, or
+    /// returns `None` if there is no such module.
+    fn resolve(&self, specifier: &str, directory: &Path) -> Option<PathBuf>;
+}
+
+/// A resolver finding the files like Node does.
+#[derive(Debug, Clone)]
+pub(crate) struct NodeResolver {
+    /// The extensions tried after the path of a module, in order.
+    extensions: Vec<String>,
+    /// The fields of a `package.json` giving the entry point of a package, in order.
+    entry_fields: Vec<String>,
+}
+
+impl NodeResolver {
+    /// Creates a resolver trying the given extensions after the paths of the modules, and the
+    /// given fields of the `package.json` files for the entry points of the packages.
+    pub(crate) fn new<E, F>(extensions: E, entry_fields: F) -> Self
+    where
+        E: IntoIterator,
+        E::Item: Into<String>,
+        F: IntoIterator,
+        F::Item: Into<String>,
+    {
+        Self {
+            extensions: extensions.into_iter().map(Into::into).collect(),
+            entry_fields: entry_fields.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Finds a module which is a file, with or without its extension.
+    fn resolve_file(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+        self.extensions
+            .iter()
+            .map(|extension| {
+                let mut file = path.as_os_str().to_owned();
+                file.push(extension);
+                PathBuf::from(file)
+            })
+            .find(|file| file.is_file())
+    }
+
+    /// Finds a module which is a directory, from the entry point given by its `package.json`, or
+    /// else its `index` file.
+    fn resolve_directory(&self, path: &Path) -> Option<PathBuf> {
+        let package = fs::read_to_string(path.join("package.json"))
+            .ok()
+            .and_then(|package| serde_json::from_str::<serde_json::Value>(&package).ok());
+        if let Some(package) = package {
+            let entry = self
+                .entry_fields
+                .iter()
+                .filter_map(|field| package[field.as_str()].as_str())
+                .map(|entry| path.join(entry))
+                .find_map(|entry| {
+                    self.resolve_file(&entry)
+                        .or_else(|| self.resolve_index(&entry))
+                });
+            if entry.is_some() {
+                return entry;
+            }
+        }
+        self.resolve_index(path)
+    }
+
+    /// Finds the `index` file of a directory.
+    fn resolve_index(&self, path: &Path) -> Option<PathBuf> {
+        self.extensions
+            .iter()
+            .map(|extension| path.join(format!("index{}", extension)))
+            .find(|file| file.is_file())
+    }
+}
+
+impl Default for NodeResolver {
+    /// Creates a resolver for the CommonJS modules, which are `.js` or `.json` files, and whose
+    /// packages give their entry point with the `main` field. The `module` field is ignored, as
+    /// it points to an ECMAScript module.
+    fn default() -> Self {
+        Self::new(vec![".js", ".json"], vec!["main"])
+    }
+}
+
+impl Resolver for NodeResolver {
+    fn resolve(&self, specifier: &str, directory: &Path) -> Option<PathBuf> {
+        let is_path = specifier.starts_with('/')
+            || specifier.starts_with("./")
+            || specifier.starts_with("../")
+            || specifier == "."
+            || specifier == "..";
+        if is_path {
+            let path = directory.join(specifier);
+            return self
+                .resolve_file(&path)
+                .or_else(|| self.resolve_directory(&path));
+        }
+
+        directory
+            .ancestors()
+            .filter(|dir| dir.file_name() != Some(OsStr::new("node_modules")))
+            .find_map(|dir| {
+                let path = dir.join("node_modules").join(specifier);
+                self.resolve_file(&path)
+                    .or_else(|| self.resolve_directory(&path))
+            })
+    }
+}
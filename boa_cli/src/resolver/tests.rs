@@ -0,0 +1,173 @@
+use super::{NodeResolver, Resolver};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// A directory of files for a test, removed when the test ends.
+pub(crate) struct Files(PathBuf);
+
+impl Files {
+    /// Creates the given files, with their contents, in a new directory.
+    pub(crate) fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let root = env::temp_dir().join(format!("boa_resolver_{}_{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (path, contents) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().expect("no parent directory"))
+                .expect("could not create the directory");
+            fs::write(path, contents).expect("could not write the file");
+        }
+        Self(root)
+    }
+
+    /// Gets the directory of the test.
+    pub(crate) fn root(&self) -> &Path {
+        &self.0
+    }
+
+    /// Gets the path of a file or directory of the test.
+    pub(crate) fn path(&self, path: &str) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for Files {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Resolves a specifier with the default resolver.
+fn resolve(specifier: &str, directory: &Path) -> Option<PathBuf> {
+    NodeResolver::default().resolve(specifier, directory)
+}
+
+#[test]
+fn extension_probing() {
+    let files = Files::new(
+        "extension_probing",
+        &[
+            ("a.js", ""),
+            ("b.json", "{}"),
+            ("c.js", ""),
+            ("c.json", "{}"),
+        ],
+    );
+    let root = files.root();
+
+    assert_eq!(resolve("./a", root), Some(files.path("./a.js")));
+    assert_eq!(resolve("./a.js", root), Some(files.path("./a.js")));
+    assert_eq!(resolve("./b", root), Some(files.path("./b.json")));
+    // The extensions are tried in order.
+    assert_eq!(resolve("./c", root), Some(files.path("./c.js")));
+    assert_eq!(resolve("./d", root), None);
+
+    let resolver = NodeResolver::new(vec![".json"], vec!["main"]);
+    assert_eq!(resolver.resolve("./c", root), Some(files.path("./c.json")));
+    assert_eq!(resolver.resolve("./a", root), None);
+}
+
+#[test]
+fn index_files() {
+    let files = Files::new(
+        "index_files",
+        &[
+            ("lib/index.js", ""),
+            ("lib/nested/file.js", ""),
+            ("data/index.json", "{}"),
+        ],
+    );
+    let root = files.root();
+
+    assert_eq!(resolve("./lib", root), Some(files.path("./lib/index.js")));
+    assert_eq!(resolve("./lib/", root), Some(files.path("./lib/index.js")));
+    assert_eq!(
+        resolve("./data", root),
+        Some(files.path("./data/index.json"))
+    );
+    assert_eq!(
+        resolve("..", &files.path("lib/nested")),
+        Some(files.path("lib/nested/../index.js"))
+    );
+}
+
+#[test]
+fn package_main() {
+    let files = Files::new(
+        "package_main",
+        &[
+            ("file/package.json", r#"{ "main": "src/entry" }"#),
+            ("file/src/entry.js", ""),
+            ("file/index.js", ""),
+            ("dir/package.json", r#"{ "main": "./lib" }"#),
+            ("dir/lib/index.js", ""),
+            ("missing/package.json", r#"{ "main": "nowhere.js" }"#),
+            ("missing/index.js", ""),
+            ("esm/package.json", r#"{ "module": "esm.js" }"#),
+            ("esm/esm.js", ""),
+            ("esm/index.js", ""),
+        ],
+    );
+    let root = files.root();
+
+    assert_eq!(
+        resolve("./file", root),
+        Some(files.path("./file/src/entry.js"))
+    );
+    assert_eq!(
+        resolve("./dir", root),
+        Some(files.path("./dir/./lib/index.js"))
+    );
+    // A package whose entry point doesn't exist falls back to its index file.
+    assert_eq!(
+        resolve("./missing", root),
+        Some(files.path("./missing/index.js"))
+    );
+    // The `module` field points to an ECMAScript module, which `require` can't load.
+    assert_eq!(resolve("./esm", root), Some(files.path("./esm/index.js")));
+
+    let resolver = NodeResolver::new(vec![".js"], vec!["module", "main"]);
+    assert_eq!(
+        resolver.resolve("./esm", root),
+        Some(files.path("./esm/esm.js"))
+    );
+}
+
+#[test]
+fn node_modules_lookup() {
+    let files = Files::new(
+        "node_modules_lookup",
+        &[
+            ("node_modules/outer/index.js", ""),
+            ("node_modules/shadowed.js", ""),
+            (
+                "app/node_modules/shadowed/package.json",
+                r#"{ "main": "main.js" }"#,
+            ),
+            ("app/node_modules/shadowed/main.js", ""),
+            ("app/node_modules/outer/node_modules/inner.js", ""),
+            ("app/src/deep/file.js", ""),
+        ],
+    );
+    let deep = files.path("app/src/deep");
+
+    // The packages are looked for in the `node_modules` of each parent directory, nearest first.
+    assert_eq!(
+        resolve("outer", &deep),
+        Some(files.path("node_modules/outer/index.js"))
+    );
+    assert_eq!(
+        resolve("shadowed", &deep),
+        Some(files.path("app/node_modules/shadowed/main.js"))
+    );
+    // A `node_modules` directory isn't searched for another `node_modules` inside it.
+    assert_eq!(
+        resolve("inner", &files.path("app/node_modules/outer/node_modules")),
+        Some(files.path("app/node_modules/outer/node_modules/inner.js"))
+    );
+    // A bare specifier is never relative to the directory of the module.
+    assert_eq!(resolve("file", &deep), None);
+    assert_eq!(resolve("missing", &deep), None);
+}
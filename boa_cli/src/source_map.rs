@@ -0,0 +1,128 @@
+//! The source maps of the files run by the CLI, which translate the positions of their
+//! diagnostics back to the original sources, when the files are transpiled.
+//!
+//! The map of a file is given by its `sourceMappingURL` comment, inlined in a `data:` URL or in
+//! another file, whose path is relative to the one of the script. Without the comment, the map is
+//! looked for next to the file, with the `.map` extension added to its name.
+
+use boa::syntax::{
+    ast::Position,
+    source_map::{source_mapping_url, SourceMap},
+    ScriptError,
+};
+use std::{
+    ffi::OsString,
+    fmt::{self, Display},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// The source map of a file, with the directory its sources are relative to.
+#[derive(Debug, Clone)]
+pub(crate) struct FileSourceMap {
+    map: SourceMap,
+    /// The directory of the map, which is the one of the file when the map is inlined.
+    directory: PathBuf,
+}
+
+impl FileSourceMap {
+    /// Finds and reads the source map of a file, if it has one.
+    ///
+    /// A map which can't be read is ignored with a warning, so that the file still runs.
+    pub(crate) fn load(file: &Path, source: &str) -> Option<Self> {
+        let directory = file.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+        let map = match source_mapping_url(source) {
+            Some(url) if url.starts_with("data:") => SourceMap::from_data_url(url)
+                .map(|map| Self { map, directory })
+                .map_err(|e| e.to_string()),
+            Some(url) => Self::read(&directory.join(url)),
+            None => {
+                let mut name = file.file_name().map_or_else(OsString::new, OsString::from);
+                name.push(".map");
+                let path = file.with_file_name(name);
+                if !path.is_file() {
+                    return None;
+                }
+                Self::read(&path)
+            }
+        };
+
+        match map {
+            Ok(map) => Some(map),
+            Err(e) => {
+                eprintln!(
+                    "warning: ignoring the source map of {}: {}",
+                    file.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Reads a source map from a file.
+    fn read(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let map = SourceMap::parse(&json).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self {
+            map,
+            directory: path.parent().unwrap_or_else(|| Path::new("")).to_path_buf(),
+        })
+    }
+
+    /// Finds the original position of a position of the file.
+    pub(crate) fn lookup(&self, position: Position) -> Option<Original> {
+        let original = self.map.lookup(position)?;
+        Some(Original {
+            path: normalize(&self.directory.join(original.source)),
+            position: original.position,
+        })
+    }
+}
+
+/// A position in an original source.
+#[derive(Debug, Clone)]
+pub(crate) struct Original {
+    /// The path of the original source.
+    pub(crate) path: PathBuf,
+    pub(crate) position: Position,
+}
+
+impl Display for Original {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.path.display(), self.position)
+    }
+}
+
+/// Formats a syntax error, followed by its position in the original source if the source map of
+/// the script covers it.
+pub(crate) fn syntax_error(error: &ScriptError, source_map: Option<&FileSourceMap>) -> String {
+    match error
+        .position()
+        .and_then(|position| source_map?.lookup(position))
+    {
+        Some(original) => format!("SyntaxError: {} ({})", error, original),
+        None => format!("SyntaxError: {}", error),
+    }
+}
+
+/// Removes the `.` and `..` components of a path, without resolving its links, so that the
+/// sources of the maps are printed like the paths of the files.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
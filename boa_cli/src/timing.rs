@@ -0,0 +1,98 @@
+//! The timing report of `--verbose-timing`, which measures each phase of running a script.
+//!
+//! The phases are also recorded as events of the `BoaProfiler`, in the `cli` category, so they
+//! show up around the events of the engine when the `Boa/profiler` feature is enabled.
+
+use crate::source_map::{syntax_error, FileSourceMap};
+use boa::{
+    exec::{GcStats, Interpreter},
+    profiler::BoaProfiler,
+    syntax::{
+        analyzer::analyze, ast::node::StatementList, lexer::Lexer, optimizer::optimize,
+        parser::Parser, ScriptError,
+    },
+};
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// The time spent in each phase of running a script.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Timings {
+    /// Turning the source into tokens.
+    lex: Duration,
+    /// Turning the tokens into a syntax tree.
+    parse: Duration,
+    /// Running the early error checks and the optimizer on the syntax tree.
+    compile: Duration,
+    /// Running the script and the jobs it queued.
+    pub(crate) execute: Duration,
+}
+
+impl Timings {
+    /// Parses a script like `parse_script`, but lexes it up front, so that the lexer and the
+    /// parser are timed separately.
+    pub(crate) fn parse_script(
+        &mut self,
+        engine: &Interpreter,
+        source: &str,
+        source_map: Option<&FileSourceMap>,
+    ) -> Result<StatementList, String> {
+        let (tokens, lex) = measure("lex", || {
+            let mut lexer = Lexer::new(source.as_bytes());
+            lexer.lex().map(|()| lexer.tokens)
+        });
+        self.lex = lex;
+        let tokens = tokens.map_err(|e| format!("SyntaxError: {}", e))?;
+
+        let (script, parse) = measure("parse", || Parser::new(&tokens).parse_all());
+        self.parse = parse;
+        let mut script = script.map_err(|e| syntax_error(&ScriptError::from(e), source_map))?;
+
+        let (analyzed, compile) = measure("compile", || {
+            analyze(&mut script).map(|()| optimize(&mut script, engine.optimizer_options()))
+        });
+        self.compile = compile;
+        analyzed.map_err(|e| syntax_error(&ScriptError::from(e), source_map))?;
+        Ok(script)
+    }
+
+    /// The time spent in all the phases.
+    fn total(&self) -> Duration {
+        self.lex + self.parse + self.compile + self.execute
+    }
+}
+
+/// Runs a phase, and returns its result with the time it took.
+// The guard of the event is `()` without the `Boa/profiler` feature.
+#[allow(clippy::let_unit_value)]
+pub(crate) fn measure<T>(phase: &str, f: impl FnOnce() -> T) -> (T, Duration) {
+    let _timer = BoaProfiler::global().start_event(phase, "cli");
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// The report printed after each script, with its timings and the peak size of the heap.
+#[derive(Debug)]
+pub(crate) struct Report<'a> {
+    /// The name of the script, which is its file, `<eval>` or `<stdin>`.
+    pub(crate) name: &'a str,
+    pub(crate) timings: Timings,
+    pub(crate) stats: GcStats,
+}
+
+impl Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+
+        writeln!(f, "Timings of {}:", self.name)?;
+        writeln!(f, "  lex:       {:.3} ms", ms(self.timings.lex))?;
+        writeln!(f, "  parse:     {:.3} ms", ms(self.timings.parse))?;
+        writeln!(f, "  compile:   {:.3} ms", ms(self.timings.compile))?;
+        writeln!(f, "  execute:   {:.3} ms", ms(self.timings.execute))?;
+        writeln!(f, "  total:     {:.3} ms", ms(self.timings.total()))?;
+        write!(f, "  peak heap: {} bytes", self.stats.peak_heap_size)
+    }
+}
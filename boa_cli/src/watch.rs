@@ -0,0 +1,69 @@
+//! The watch mode of the CLI, which runs the files again each time one of them changes.
+//!
+//! The files are polled, rather than watched with the notifications of the system, which is
+//! enough for the few files given on the command line. Each run has a new realm, so the runs
+//! don't see the bindings of the previous ones.
+
+use crate::{create_engine, run, Opt};
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// The delay between two checks of the files.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Runs the files, then runs them again each time one of them is modified, until the process is
+/// interrupted.
+pub(crate) fn watch(args: &Opt) -> io::Result<()> {
+    let names: Vec<String> = args
+        .files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect();
+    let mut modified = modification_times(&args.files);
+
+    for count in 1.. {
+        println!("--- run {}: {} ---", count, names.join(", "));
+        let mut engine = create_engine(args);
+        let status = match run(&mut engine, args) {
+            Ok(true) => {
+                // The result of the last script isn't followed by a new line.
+                println!();
+                "succeeded"
+            }
+            Ok(false) => "failed",
+            Err(err) => {
+                eprintln!("{}", err);
+                "failed"
+            }
+        };
+        println!("--- run {} {}, waiting for changes ---", count, status);
+        io::stdout().flush()?;
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let times = modification_times(&args.files);
+            if times != modified {
+                modified = times;
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gets the last modification time of each file, or `None` for the files which can't be read.
+fn modification_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| {
+            fs::metadata(file)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
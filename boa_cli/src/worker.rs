@@ -0,0 +1,293 @@
+//! Workers, which run scripts on their own threads, in their own realms, and exchange messages
+//! with the script which started them, like the [Web workers][workers].
+//!
+//! `new Worker(path)` runs the script of a file, whose path is relative to the working directory.
+//! The messages sent with `postMessage` are copied with the structured clone algorithm, and given
+//! to the `onmessage` handler of the other side, in the `data` property of an event. In the
+//! worker, `postMessage`, `onmessage` and `close` are globals, and `self` is the global object.
+//!
+//! A worker runs until its script and its jobs are done, and then for as long as its `onmessage`
+//! is a function. It is stopped sooner by calling `close`, or by the `terminate` method of its
+//! `Worker`, once its running task returns. The thread which started a worker keeps running its
+//! event loop while the worker runs.
+//!
+//! [workers]: https://html.spec.whatwg.org/multipage/workers.html
+
+use crate::{
+    create_realm,
+    event_loop::{event_loop, RemoteTask},
+    inspect::inspect,
+    parse_script,
+};
+use boa::{
+    builtins::{
+        function::{make_builtin_fn, make_constructor_fn},
+        structured_clone::StructuredData,
+        value::{ResultValue, Value},
+    },
+    exec::Interpreter,
+    host::HostOperation,
+    interner::Sym,
+    job::Job,
+    Executable,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Sender},
+    thread,
+};
+
+/// The internal slot holding the identifier of a worker.
+const WORKER_ID: &str = "WorkerId";
+
+/// A worker started by the current thread.
+#[derive(Debug)]
+struct Handle {
+    /// The `Worker` object, whose `onmessage` handles the messages of the worker.
+    object: Value,
+    /// The sender of the tasks to the event loop of the worker.
+    sender: Sender<RemoteTask>,
+}
+
+/// The workers of the current thread.
+#[derive(Debug, Default)]
+struct Workers {
+    /// The identifier of the last worker started.
+    next_id: i32,
+    /// The number of levels of nesting of the uncaught errors printed by the workers.
+    depth: usize,
+    /// The workers which are still running, by identifier.
+    handles: HashMap<i32, Handle>,
+    /// The identifier of the current thread and the sender of the tasks to the thread which
+    /// started it, if it is a worker.
+    parent: Option<(i32, Sender<RemoteTask>)>,
+}
+
+thread_local! {
+    /// The native functions can't capture anything, so they reach the workers through here.
+    static WORKERS: RefCell<Workers> = RefCell::new(Workers::default());
+}
+
+/// Adds `Worker` to the global object of a realm.
+pub(crate) fn register(global: &Value, depth: usize) {
+    WORKERS.with(|workers| workers.borrow_mut().depth = depth);
+
+    let prototype = Value::new_object(Some(global));
+    make_builtin_fn(post_message, "postMessage", &prototype, 1);
+    make_builtin_fn(terminate, "terminate", &prototype, 0);
+    let worker = make_constructor_fn("Worker", 1, make_worker, global, prototype, true);
+    global.set_field("Worker", worker);
+}
+
+/// `new Worker( path )`
+///
+/// Runs the script of the file on a new thread.
+fn make_worker(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let path = match args.first() {
+        Some(path) => PathBuf::from(ctx.to_string(path)?),
+        None => return ctx.throw_type_error("Worker: the path of the script is missing"),
+    };
+    let name = path.to_string_lossy();
+    if !ctx.check_host_operation(&HostOperation::SpawnWorker(&name))? {
+        // The object is left without a worker, so its methods throw.
+        return Ok(this.clone());
+    }
+    let (id, depth) = WORKERS.with(|workers| {
+        let mut workers = workers.borrow_mut();
+        workers.next_id += 1;
+        (workers.next_id, workers.depth)
+    });
+
+    // The worker sends back the sender of its event loop, once it has one.
+    let parent = event_loop().remote_sender();
+    let (ready, sender) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name(format!("worker {}", id))
+        .spawn(move || run(&path, id, depth, parent, ready));
+    let sender = match spawned.ok().and_then(|_| sender.recv().ok()) {
+        Some(sender) => sender,
+        None => return ctx.throw_type_error("Worker: the thread could not be started"),
+    };
+
+    this.set_internal_slot(WORKER_ID, Value::from(id));
+    WORKERS.with(|workers| {
+        let handle = Handle {
+            object: this.clone(),
+            sender,
+        };
+        workers.borrow_mut().handles.insert(id, handle);
+    });
+    event_loop().hold();
+    Ok(this.clone())
+}
+
+/// Gets the sender of the tasks to a worker, which is `None` once it is terminated.
+fn worker_sender(this: &Value, ctx: &mut Interpreter) -> Result<Option<Sender<RemoteTask>>, Value> {
+    let id = this.get_internal_slot(WORKER_ID);
+    if !this.is_object() || !id.is_number() {
+        ctx.throw_type_error("'this' is not a Worker")?;
+        unreachable!();
+    }
+    let id = i32::from(&id);
+    Ok(WORKERS.with(|workers| {
+        workers
+            .borrow()
+            .handles
+            .get(&id)
+            .map(|handle| handle.sender.clone())
+    }))
+}
+
+/// `Worker.prototype.postMessage( message )`
+///
+/// Sends a copy of the message to the worker.
+fn post_message(this: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let sender = worker_sender(this, ctx)?;
+    let data = ctx.structured_serialize(&args.first().cloned().unwrap_or_default())?;
+    if let Some(sender) = sender {
+        // The worker may be done, in which case the message is dropped.
+        let _ = sender.send(Box::new(move |engine| {
+            let handler = global_handler(engine);
+            dispatch_message(engine, handler, &data)
+        }));
+    }
+    Ok(Value::undefined())
+}
+
+/// `Worker.prototype.terminate()`
+///
+/// Stops the worker once its running task returns. The messages it sent, and hasn't delivered yet,
+/// are dropped.
+fn terminate(this: &mut Value, _: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    if let Some(sender) = worker_sender(this, ctx)? {
+        let id = i32::from(&this.get_internal_slot(WORKER_ID));
+        WORKERS.with(|workers| workers.borrow_mut().handles.remove(&id));
+        let _ = sender.send(Box::new(|_| {
+            event_loop().close();
+            Ok(())
+        }));
+    }
+    Ok(Value::undefined())
+}
+
+/// `postMessage( message )`
+///
+/// Sends a copy of the message to the `Worker` of the current worker.
+fn post_message_to_parent(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let data = ctx.structured_serialize(&args.first().cloned().unwrap_or_default())?;
+    let parent = WORKERS.with(|workers| workers.borrow().parent.clone());
+    if let Some((id, sender)) = parent {
+        let _ = sender.send(Box::new(move |engine| {
+            let object = WORKERS.with(|workers| {
+                workers
+                    .borrow()
+                    .handles
+                    .get(&id)
+                    .map(|handle| handle.object.clone())
+            });
+            match object {
+                Some(object) => dispatch_message(engine, object.get_field("onmessage"), &data),
+                None => Ok(()),
+            }
+        }));
+    }
+    Ok(Value::undefined())
+}
+
+/// `close()`
+///
+/// Stops the current worker once the running task returns.
+fn close(_: &mut Value, _: &[Value], _: &mut Interpreter) -> ResultValue {
+    event_loop().close();
+    Ok(Value::undefined())
+}
+
+/// Gets the `onmessage` handler of the current worker, which is a global variable, or a property
+/// of the global object.
+fn global_handler(engine: &Interpreter) -> Value {
+    engine
+        .realm
+        .environment
+        .get_binding_value(Sym::new("onmessage"))
+}
+
+/// Calls an `onmessage` handler, if it is a function, with a message event.
+fn dispatch_message(
+    engine: &mut Interpreter,
+    handler: Value,
+    data: &StructuredData,
+) -> Result<(), Value> {
+    if !handler.is_function() {
+        return Ok(());
+    }
+    let global = engine.realm.global_obj.clone();
+    let event = Value::new_object(Some(&global));
+    event.set_field("data", engine.structured_deserialize(data)?);
+    Job::new(handler, vec![event]).run(engine).map(|_| ())
+}
+
+/// Runs a worker on its thread, and lets the thread which started it know when it is done.
+fn run(
+    path: &Path,
+    id: i32,
+    depth: usize,
+    parent: Sender<RemoteTask>,
+    ready: Sender<Sender<RemoteTask>>,
+) {
+    let realm = create_realm();
+    // The worker exposes its global object as `self`.
+    realm.init_builtins();
+    let global = realm.global_obj.clone();
+    let mut engine = Interpreter::new(realm);
+    register(&global, depth);
+    WORKERS.with(|workers| workers.borrow_mut().parent = Some((id, parent.clone())));
+    make_builtin_fn(post_message_to_parent, "postMessage", &global, 1);
+    make_builtin_fn(close, "close", &global, 0);
+    global.set_field("self", global.clone());
+    #[cfg(feature = "commonjs")]
+    crate::commonjs::register(
+        &global,
+        Some(&path.to_path_buf()),
+        crate::resolver::NodeResolver::default(),
+    );
+    let _ = ready.send(event_loop().remote_sender());
+
+    if let Err(e) = run_script(&mut engine, path) {
+        eprintln!(
+            "Uncaught {} in {}",
+            inspect(&e, depth, &mut engine),
+            path.display()
+        );
+    }
+
+    // The values of the thread must be dropped before the thread ends, with its heap.
+    event_loop().close();
+    WORKERS.with(|workers| workers.borrow_mut().handles.clear());
+    drop(engine);
+    let _ = parent.send(Box::new(move |_| {
+        WORKERS.with(|workers| workers.borrow_mut().handles.remove(&id));
+        event_loop().release();
+        Ok(())
+    }));
+}
+
+/// Runs the script of a worker and its jobs, then its messages while it has a handler for them.
+fn run_script(engine: &mut Interpreter, path: &Path) -> Result<(), Value> {
+    let source =
+        fs::read_to_string(path).map_err(|e| Value::from(format!("{}: {}", path.display(), e)))?;
+    parse_script(engine, &source, None)
+        .map_err(Value::from)?
+        .run(engine)?;
+
+    let event_loop = event_loop();
+    loop {
+        engine.run_jobs()?;
+        if event_loop.is_closed() || !global_handler(engine).is_function() {
+            return Ok(());
+        }
+        event_loop.run_remote_task(engine)?;
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * The C interface of Boa, a JavaScript engine written in Rust.
+ *
+ * A context holds an engine with its own realm. It must only be used from the thread which
+ * created it, as the garbage collected heap is local to each thread.
+ *
+ * Ownership of the values:
+ *  - the values given to the host by the engine own their string or handle, which are released
+ *    with boa_value_free, except for the arguments of the host functions, which are only
+ *    borrowed for the duration of the call;
+ *  - the values given to the engine by the host are always borrowed: the engine copies them.
+ */
+
+#ifndef BOA_H
+#define BOA_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The result of a call to the interface. */
+typedef enum BoaStatus {
+    /* The call succeeded. */
+    BOA_OK,
+    /* The script threw an error, or the engine failed. */
+    BOA_ERROR,
+    /* An argument of the call was invalid, like a null pointer. */
+    BOA_INVALID_ARGUMENT,
+} BoaStatus;
+
+/* The type of a value, which tells which field of its data is set. */
+typedef enum BoaType {
+    /* undefined, without data. */
+    BOA_TYPE_UNDEFINED,
+    /* null, without data. */
+    BOA_TYPE_NULL,
+    /* A boolean, in `boolean`. */
+    BOA_TYPE_BOOLEAN,
+    /* A number, in `number`. */
+    BOA_TYPE_NUMBER,
+    /* A string, in `string`. */
+    BOA_TYPE_STRING,
+    /* A symbol, in `handle`. */
+    BOA_TYPE_SYMBOL,
+    /* A BigInt, in `handle`. */
+    BOA_TYPE_BIGINT,
+    /* An object, functions included, in `handle`. */
+    BOA_TYPE_OBJECT,
+} BoaType;
+
+/* An engine, with its own realm, and the message of the last error. */
+typedef struct BoaContext BoaContext;
+
+/* A value of the engine which the host refers to, like an object. */
+typedef struct BoaHandle BoaHandle;
+
+/*
+ * A string encoded in UTF-8, followed by a NUL character which isn't part of its length.
+ *
+ * JavaScript strings may contain NUL characters themselves, so the length should be used rather
+ * than the terminator when it matters.
+ */
+typedef struct BoaString {
+    char *data;
+    size_t length;
+} BoaString;
+
+/* A JavaScript value, tagged with its type. */
+typedef struct BoaValue {
+    BoaType tag;
+    union {
+        bool boolean;
+        double number;
+        BoaString string;
+        BoaHandle *handle;
+    } data;
+} BoaValue;
+
+/*
+ * A function of the host, called with the arguments of the script and the data given when it
+ * was registered.
+ *
+ * It writes its return value, or the error it throws if it returns BOA_ERROR, in `result`, which
+ * is undefined beforehand.
+ */
+typedef BoaStatus (*BoaFunction)(const BoaValue *args, size_t argc, void *data, BoaValue *result);
+
+/* Creates a new context, which is released with boa_context_free. */
+BoaContext *boa_context_new(void);
+
+/* Releases a context. It may be null. */
+void boa_context_free(BoaContext *context);
+
+/*
+ * Runs a script of `length` bytes of UTF-8 in the realm of a context, then the jobs it enqueued,
+ * and writes its completion value in `result`.
+ *
+ * If the script fails, the error it threw is written in `result`, or undefined if it doesn't
+ * parse, and its message is available with boa_last_error.
+ */
+BoaStatus boa_eval(BoaContext *context, const char *source, size_t length, BoaValue *result);
+
+/*
+ * Gets the message of the error of the last call on a context, or null if it succeeded.
+ *
+ * The message is owned by the context, and valid until the next call on it.
+ */
+const char *boa_last_error(const BoaContext *context);
+
+/*
+ * Adds a function of the host to the global object of a context, with the given `length`
+ * property.
+ *
+ * The data is given to the function on each call, and is never released by the engine.
+ */
+BoaStatus boa_register_function(BoaContext *context, const char *name, BoaFunction function,
+                                uint32_t length, void *data);
+
+/* Releases the string or the handle of a value given by the engine, and sets it to undefined. */
+void boa_value_free(BoaValue *value);
+
+/*
+ * Converts a value to a string, as it would be printed by the console, which is released with
+ * boa_string_free. The string is cut at its first NUL character, if any.
+ */
+char *boa_value_to_string(const BoaValue *value);
+
+/* Releases a string returned by boa_value_to_string. It may be null. */
+void boa_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* BOA_H */
@@ -0,0 +1,297 @@
+//! The C interface of Boa, to embed the engine in programs written in other languages.
+//!
+//! The functions are declared in `include/boa.h`, which has to be kept in sync with this crate.
+//! A context holds an engine with its own realm, and must only be used from the thread which
+//! created it, as the garbage collected heap is local to each thread.
+
+mod value;
+
+#[cfg(test)]
+mod tests;
+
+pub use value::{BoaHandle, BoaString, BoaType, BoaValue, BoaValueData};
+
+use boa::{
+    builtins::value::{display::log_string_from, Value},
+    parse, Executable, Interpreter, Realm,
+};
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
+    ptr, slice,
+};
+use value::to_c_string;
+
+/// The result of a call to the interface.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoaStatus {
+    /// The call succeeded.
+    Ok,
+    /// The script threw an error, or the engine failed.
+    Error,
+    /// An argument of the call was invalid, like a null pointer.
+    InvalidArgument,
+}
+
+/// A function of the host, called with the arguments of the script and the data given when it
+/// was registered.
+///
+/// It writes its return value, or the error it throws if it returns `BoaStatus::Error`, in
+/// `result`, which is `undefined` beforehand.
+pub type BoaFunction = unsafe extern "C" fn(
+    args: *const BoaValue,
+    argc: usize,
+    data: *mut c_void,
+    result: *mut BoaValue,
+) -> BoaStatus;
+
+/// An engine, with its own realm, and the message of the last error.
+#[derive(Debug)]
+pub struct BoaContext {
+    engine: Interpreter,
+    last_error: Option<CString>,
+}
+
+impl BoaContext {
+    /// Records the error of the last call, or clears it.
+    fn set_last_error(&mut self, error: Option<String>) {
+        self.last_error = error.map(to_c_string);
+    }
+}
+
+/// An error of `boa_eval`: its message, and the value which was thrown, if any.
+struct EvalError {
+    message: String,
+    value: Value,
+}
+
+/// Creates a new context, which is released with `boa_context_free`.
+#[no_mangle]
+pub extern "C" fn boa_context_new() -> *mut BoaContext {
+    let context = BoaContext {
+        engine: Interpreter::new(Realm::create()),
+        last_error: None,
+    };
+    Box::into_raw(Box::new(context))
+}
+
+/// Releases a context, and the values of its realm which aren't held by handles.
+///
+/// # Safety
+///
+/// The context must have been created by `boa_context_new`, and not be released already. It may
+/// be null.
+#[no_mangle]
+pub unsafe extern "C" fn boa_context_free(context: *mut BoaContext) {
+    if !context.is_null() {
+        drop(Box::from_raw(context));
+    }
+}
+
+/// Runs a script in the realm of a context, then the jobs it enqueued, and writes its completion
+/// value in `result`.
+///
+/// If the script fails, the error it threw is written in `result`, or `undefined` if it doesn't
+/// parse, and its message is available with `boa_last_error`.
+///
+/// # Safety
+///
+/// The context must be valid, `source` must point to `length` bytes of UTF-8, and `result` to a
+/// value, which is overwritten without being released.
+#[no_mangle]
+pub unsafe extern "C" fn boa_eval(
+    context: *mut BoaContext,
+    source: *const c_char,
+    length: usize,
+    result: *mut BoaValue,
+) -> BoaStatus {
+    let context = match context.as_mut() {
+        Some(context) => context,
+        None => return BoaStatus::InvalidArgument,
+    };
+    let result = match result.as_mut() {
+        Some(result) => result,
+        None => return BoaStatus::InvalidArgument,
+    };
+    *result = BoaValue::undefined();
+    if source.is_null() && length != 0 {
+        context.set_last_error(Some("the source is null".to_string()));
+        return BoaStatus::InvalidArgument;
+    }
+    let source = if length == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(source as *const u8, length)
+    };
+
+    let engine = &mut context.engine;
+    let outcome =
+        panic::catch_unwind(AssertUnwindSafe(|| eval(engine, source))).unwrap_or_else(|_| {
+            Err(EvalError {
+                message: "the engine panicked".to_string(),
+                value: Value::undefined(),
+            })
+        });
+    match outcome {
+        Ok(value) => {
+            context.set_last_error(None);
+            *result = BoaValue::from_value(&value);
+            BoaStatus::Ok
+        }
+        Err(error) => {
+            context.set_last_error(Some(error.message));
+            *result = BoaValue::from_value(&error.value);
+            BoaStatus::Error
+        }
+    }
+}
+
+/// Gets the message of the error of the last call on a context, or null if it succeeded.
+///
+/// The message is owned by the context, and valid until the next call on it.
+///
+/// # Safety
+///
+/// The context must be valid.
+#[no_mangle]
+pub unsafe extern "C" fn boa_last_error(context: *const BoaContext) -> *const c_char {
+    context
+        .as_ref()
+        .and_then(|context| context.last_error.as_ref())
+        .map_or(ptr::null(), |error| error.as_ptr())
+}
+
+/// Adds a function of the host to the global object of a context.
+///
+/// The data is given to the function on each call, and is never released by the engine.
+///
+/// # Safety
+///
+/// The context must be valid, and `name` a NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn boa_register_function(
+    context: *mut BoaContext,
+    name: *const c_char,
+    function: Option<BoaFunction>,
+    length: u32,
+    data: *mut c_void,
+) -> BoaStatus {
+    let context = match context.as_mut() {
+        Some(context) => context,
+        None => return BoaStatus::InvalidArgument,
+    };
+    let function = match function {
+        Some(function) if !name.is_null() => function,
+        _ => {
+            context.set_last_error(Some("the name or the function is null".to_string()));
+            return BoaStatus::InvalidArgument;
+        }
+    };
+    let name = match CStr::from_ptr(name).to_str() {
+        Ok(name) => name,
+        Err(_) => {
+            context.set_last_error(Some("the name is not valid UTF-8".to_string()));
+            return BoaStatus::InvalidArgument;
+        }
+    };
+
+    let length = length.min(i32::MAX as u32) as i32;
+    context
+        .engine
+        .register_global_closure(name, length, move |_, args, _| call(function, args, data));
+    context.set_last_error(None);
+    BoaStatus::Ok
+}
+
+/// Releases the string or the handle of a value given by the engine, and sets it to `undefined`.
+///
+/// # Safety
+///
+/// The value must have been given by the engine, and not be released already. It may be null.
+#[no_mangle]
+pub unsafe extern "C" fn boa_value_free(value: *mut BoaValue) {
+    if let Some(value) = value.as_mut() {
+        value.free();
+    }
+}
+
+/// Converts a value to a string, as it would be printed by the console, which is released with
+/// `boa_string_free`.
+///
+/// The string is cut at its first NUL character, if any.
+///
+/// # Safety
+///
+/// The value must be valid. It may be null, which gives a null string.
+#[no_mangle]
+pub unsafe extern "C" fn boa_value_to_string(value: *const BoaValue) -> *mut c_char {
+    match value.as_ref() {
+        Some(value) => to_c_string(log_string_from(value.to_value().data(), false)).into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Releases a string returned by `boa_value_to_string`.
+///
+/// # Safety
+///
+/// The string must have been returned by `boa_value_to_string`, and not be released already. It
+/// may be null.
+#[no_mangle]
+pub unsafe extern "C" fn boa_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Parses and runs a script, then the jobs it enqueued.
+fn eval(engine: &mut Interpreter, source: &[u8]) -> Result<Value, EvalError> {
+    let script = parse(source, engine.optimizer_options()).map_err(|e| EvalError {
+        message: format!("SyntaxError: {}", e),
+        value: Value::undefined(),
+    })?;
+    let thrown = |value: Value| EvalError {
+        message: describe(&value),
+        value,
+    };
+
+    let result = script.run(engine).map_err(thrown)?;
+    engine.run_jobs().map_err(thrown)?;
+    Ok(result)
+}
+
+/// Describes a thrown value, with the name of its constructor if it is an error.
+fn describe(error: &Value) -> String {
+    if !error.is_object() {
+        return error.to_string();
+    }
+    let constructor = error.get_field("constructor");
+    let name = if constructor.is_function() {
+        constructor.get_field("name")
+    } else {
+        error.get_field("name")
+    };
+    format!("{}: {}", name, error.get_field("message"))
+}
+
+/// Calls a function of the host with the arguments of the script.
+fn call(function: BoaFunction, args: &[Value], data: *mut c_void) -> Result<Value, Value> {
+    let mut args: Vec<BoaValue> = args.iter().map(BoaValue::from_value).collect();
+    let mut result = BoaValue::undefined();
+
+    // The arguments are only borrowed by the host, and the result only by the engine.
+    let (status, value) = unsafe {
+        let status = function(args.as_ptr(), args.len(), data, &mut result);
+        (status, result.to_value())
+    };
+    for arg in &mut args {
+        unsafe { arg.free() };
+    }
+
+    match status {
+        BoaStatus::Ok => Ok(value),
+        _ => Err(value),
+    }
+}
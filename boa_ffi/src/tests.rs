@@ -0,0 +1,170 @@
+use super::*;
+use std::ffi::CStr;
+
+/// Evaluates a script in a context, and returns the status and the result.
+fn eval(context: *mut BoaContext, source: &str) -> (BoaStatus, BoaValue) {
+    let mut result = BoaValue::undefined();
+    let status = unsafe {
+        boa_eval(
+            context,
+            source.as_ptr() as *const c_char,
+            source.len(),
+            &mut result,
+        )
+    };
+    (status, result)
+}
+
+/// Gets the contents of a string value.
+fn string(value: &BoaValue) -> String {
+    assert_eq!(value.tag, BoaType::String);
+    unsafe {
+        let string = value.data.string;
+        let bytes = slice::from_raw_parts(string.data as *const u8, string.length);
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+}
+
+/// Gets the message of the last error.
+fn last_error(context: *mut BoaContext) -> Option<String> {
+    unsafe {
+        let error = boa_last_error(context);
+        if error.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(error).to_string_lossy().into_owned())
+        }
+    }
+}
+
+#[test]
+fn primitives() {
+    let context = boa_context_new();
+
+    let (status, result) = eval(context, "1 + 2");
+    assert_eq!(status, BoaStatus::Ok);
+    assert_eq!(result.tag, BoaType::Number);
+    assert_eq!(unsafe { result.data.number }, 3.0);
+
+    let (_, result) = eval(context, "1 < 2");
+    assert_eq!(result.tag, BoaType::Boolean);
+    assert!(unsafe { result.data.boolean });
+
+    let (_, result) = eval(context, "null");
+    assert_eq!(result.tag, BoaType::Null);
+
+    let (_, mut result) = eval(context, "'a\\0b' + 'c'");
+    assert_eq!(string(&result), "a\0bc");
+    unsafe { boa_value_free(&mut result) };
+    assert_eq!(result.tag, BoaType::Undefined);
+
+    unsafe { boa_context_free(context) };
+}
+
+#[test]
+fn objects() {
+    let context = boa_context_new();
+
+    let (status, mut result) = eval(context, "var point = { x: 1 }; point");
+    assert_eq!(status, BoaStatus::Ok);
+    assert_eq!(result.tag, BoaType::Object);
+
+    let string = unsafe { boa_value_to_string(&result) };
+    assert_eq!(
+        unsafe { CStr::from_ptr(string) }.to_str(),
+        Ok("{\n   x: 1\n}")
+    );
+    unsafe {
+        boa_string_free(string);
+        boa_value_free(&mut result);
+    }
+
+    unsafe { boa_context_free(context) };
+}
+
+#[test]
+fn errors() {
+    let context = boa_context_new();
+
+    let (status, mut result) = eval(context, "throw new TypeError('bad')");
+    assert_eq!(status, BoaStatus::Error);
+    assert_eq!(result.tag, BoaType::Object);
+    assert_eq!(last_error(context).as_deref(), Some("TypeError: bad"));
+    unsafe { boa_value_free(&mut result) };
+
+    let (status, result) = eval(context, "let = ;");
+    assert_eq!(status, BoaStatus::Error);
+    assert_eq!(result.tag, BoaType::Undefined);
+    assert!(last_error(context).unwrap().starts_with("SyntaxError"));
+
+    let (status, _) = eval(context, "1");
+    assert_eq!(status, BoaStatus::Ok);
+    assert_eq!(last_error(context), None);
+
+    unsafe { boa_context_free(context) };
+}
+
+/// Sums its numeric arguments into the counter given as data, and throws on the others.
+unsafe extern "C" fn sum(
+    args: *const BoaValue,
+    argc: usize,
+    data: *mut c_void,
+    result: *mut BoaValue,
+) -> BoaStatus {
+    let total = &mut *(data as *mut f64);
+    for arg in slice::from_raw_parts(args, argc) {
+        if arg.tag != BoaType::Number {
+            let message = "not a number";
+            *result = BoaValue {
+                tag: BoaType::String,
+                data: BoaValueData {
+                    string: BoaString {
+                        data: message.as_ptr() as *mut c_char,
+                        length: message.len(),
+                    },
+                },
+            };
+            return BoaStatus::Error;
+        }
+        *total += arg.data.number;
+    }
+    *result = BoaValue {
+        tag: BoaType::Number,
+        data: BoaValueData { number: *total },
+    };
+    BoaStatus::Ok
+}
+
+#[test]
+fn host_functions() {
+    let context = boa_context_new();
+    let mut total = 0.0_f64;
+
+    let name = CString::new("sum").unwrap();
+    let status = unsafe {
+        boa_register_function(
+            context,
+            name.as_ptr(),
+            Some(sum),
+            0,
+            &mut total as *mut f64 as *mut c_void,
+        )
+    };
+    assert_eq!(status, BoaStatus::Ok);
+
+    let (status, result) = eval(context, "sum(1, 2) + sum(3)");
+    assert_eq!(status, BoaStatus::Ok);
+    assert_eq!(unsafe { result.data.number }, 9.0);
+    assert_eq!(total, 6.0);
+
+    let (status, mut result) = eval(context, "sum('1')");
+    assert_eq!(status, BoaStatus::Error);
+    assert_eq!(string(&result), "not a number");
+    assert_eq!(last_error(context).as_deref(), Some("not a number"));
+    unsafe { boa_value_free(&mut result) };
+
+    let status = unsafe { boa_register_function(context, name.as_ptr(), None, 0, ptr::null_mut()) };
+    assert_eq!(status, BoaStatus::InvalidArgument);
+
+    unsafe { boa_context_free(context) };
+}
@@ -0,0 +1,189 @@
+//! The values exchanged with the host, as a tagged union.
+
+use boa::builtins::value::{Value, ValueData};
+use std::{
+    ffi::CString,
+    os::raw::c_char,
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// The type of a value, which tells which field of its data is set.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoaType {
+    /// `undefined`, without data.
+    Undefined,
+    /// `null`, without data.
+    Null,
+    /// A boolean, in `boolean`.
+    Boolean,
+    /// A number, in `number`.
+    Number,
+    /// A string, in `string`.
+    String,
+    /// A symbol, in `handle`.
+    Symbol,
+    /// A `BigInt`, in `handle`.
+    BigInt,
+    /// An object, functions included, in `handle`.
+    Object,
+}
+
+/// A string encoded in UTF-8, followed by a NUL character which isn't part of its length.
+///
+/// JavaScript strings may contain NUL characters themselves, so the length should be used rather
+/// than the terminator when it matters.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct BoaString {
+    /// The bytes of the string.
+    pub data: *mut c_char,
+    /// The number of bytes of the string, without the terminator.
+    pub length: usize,
+}
+
+/// A value of the engine which the host refers to, like an object.
+///
+/// The host can't look into it, but can pass it back to the engine.
+#[derive(Debug)]
+pub struct BoaHandle(Value);
+
+/// The data of a value, depending on its type.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union BoaValueData {
+    /// The value of a boolean.
+    pub boolean: bool,
+    /// The value of a number.
+    pub number: f64,
+    /// The contents of a string.
+    pub string: BoaString,
+    /// A handle to a symbol, a `BigInt` or an object.
+    pub handle: *mut BoaHandle,
+}
+
+/// A JavaScript value, tagged with its type.
+///
+/// The values given to the host by the engine own their string or handle, which are released with
+/// `boa_value_free`, except for the arguments of the host functions, which are only borrowed for
+/// the duration of the call. The values given to the engine by the host are always borrowed: the
+/// engine copies them.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct BoaValue {
+    /// The type of the value.
+    pub tag: BoaType,
+    /// The data of the value, depending on its type.
+    pub data: BoaValueData,
+}
+
+impl BoaValue {
+    /// The `undefined` value.
+    pub(crate) fn undefined() -> Self {
+        Self {
+            tag: BoaType::Undefined,
+            data: BoaValueData { number: 0.0 },
+        }
+    }
+
+    /// Converts a value of the engine, allocating its string or handle.
+    pub(crate) fn from_value(value: &Value) -> Self {
+        let (tag, data) = match *value.data() {
+            ValueData::Undefined => return Self::undefined(),
+            ValueData::Null => (BoaType::Null, BoaValueData { number: 0.0 }),
+            ValueData::Boolean(boolean) => (BoaType::Boolean, BoaValueData { boolean }),
+            ValueData::Rational(number) => (BoaType::Number, BoaValueData { number }),
+            ValueData::Integer(integer) => (
+                BoaType::Number,
+                BoaValueData {
+                    number: f64::from(integer),
+                },
+            ),
+            ValueData::String(ref string) => (
+                BoaType::String,
+                BoaValueData {
+                    string: new_string(string.to_string()),
+                },
+            ),
+            ValueData::Symbol(_) => (BoaType::Symbol, new_handle(value)),
+            ValueData::BigInt(_) => (BoaType::BigInt, new_handle(value)),
+            ValueData::Object(_) => (BoaType::Object, new_handle(value)),
+        };
+        Self { tag, data }
+    }
+
+    /// Copies the value into a value of the engine.
+    ///
+    /// # Safety
+    ///
+    /// The field of the data matching the type must be set, and its string or handle be valid.
+    pub(crate) unsafe fn to_value(self) -> Value {
+        match self.tag {
+            BoaType::Undefined => Value::undefined(),
+            BoaType::Null => Value::null(),
+            BoaType::Boolean => Value::from(self.data.boolean),
+            BoaType::Number => Value::from(self.data.number),
+            BoaType::String => {
+                let string = self.data.string;
+                if string.data.is_null() {
+                    Value::from(String::new())
+                } else {
+                    let bytes = slice::from_raw_parts(string.data as *const u8, string.length);
+                    Value::from(String::from_utf8_lossy(bytes).into_owned())
+                }
+            }
+            BoaType::Symbol | BoaType::BigInt | BoaType::Object => NonNull::new(self.data.handle)
+                .map_or_else(Value::undefined, |handle| handle.as_ref().0.clone()),
+        }
+    }
+
+    /// Releases the string or the handle of a value allocated by the engine.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been allocated by `from_value`, and not be released already.
+    pub(crate) unsafe fn free(&mut self) {
+        match self.tag {
+            BoaType::String if !self.data.string.data.is_null() => {
+                let string = self.data.string;
+                let bytes =
+                    ptr::slice_from_raw_parts_mut(string.data as *mut u8, string.length + 1);
+                drop(Box::from_raw(bytes));
+            }
+            BoaType::Symbol | BoaType::BigInt | BoaType::Object if !self.data.handle.is_null() => {
+                drop(Box::from_raw(self.data.handle));
+            }
+            _ => {}
+        }
+        *self = Self::undefined();
+    }
+}
+
+/// Allocates a string, with its NUL terminator.
+fn new_string(string: String) -> BoaString {
+    let length = string.len();
+    let mut bytes = string.into_bytes();
+    bytes.push(0);
+    BoaString {
+        data: Box::into_raw(bytes.into_boxed_slice()) as *mut c_char,
+        length,
+    }
+}
+
+/// Allocates a handle to a value.
+fn new_handle(value: &Value) -> BoaValueData {
+    BoaValueData {
+        handle: Box::into_raw(Box::new(BoaHandle(value.clone()))),
+    }
+}
+
+/// Converts a Rust string into a C string, which is cut at its first NUL character, if any.
+pub(crate) fn to_c_string(string: String) -> CString {
+    CString::new(string).unwrap_or_else(|error| {
+        let position = error.nul_position();
+        let mut bytes = error.into_vec();
+        bytes.truncate(position);
+        CString::new(bytes).expect("the string was cut before its first NUL character")
+    })
+}
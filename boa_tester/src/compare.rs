@@ -0,0 +1,81 @@
+//! Comparison of the results with a snapshot of a previous run, to find the regressions.
+
+use crate::results::{Outcome, Statistics, SuiteResult};
+
+/// The differences between the results of a previous run and the current ones.
+#[derive(Debug, Clone)]
+pub(crate) struct Comparison {
+    /// The statistics of the previous run, for the tests of the current one.
+    before: Statistics,
+    /// The statistics of the current run.
+    after: Statistics,
+    /// The tests which passed in the previous run, and don't anymore.
+    pub(crate) regressions: Vec<String>,
+    /// The tests which pass now, and didn't in the previous run.
+    fixes: Vec<String>,
+}
+
+impl Comparison {
+    /// Compares the results of two runs.
+    ///
+    /// Only the tests of the current run are compared, so a part of the suite can be compared with
+    /// a snapshot of the full suite.
+    pub(crate) fn new(before: &SuiteResult, after: &SuiteResult) -> Self {
+        let previous = before.outcomes();
+        let mut stats = Statistics::default();
+        let mut regressions = Vec::new();
+        let mut fixes = Vec::new();
+        for (path, outcome) in after.outcomes() {
+            let passed = outcome == Outcome::Passed;
+            let previous = previous.get(&path);
+            if let Some(&outcome) = previous {
+                stats.count(outcome);
+            }
+            let passed_before = previous == Some(&Outcome::Passed);
+            if passed_before && !passed {
+                regressions.push(path);
+            } else if passed && !passed_before {
+                fixes.push(path);
+            }
+        }
+
+        Self {
+            before: stats,
+            after: after.stats,
+            regressions,
+            fixes,
+        }
+    }
+
+    /// Prints the differences between the two runs.
+    pub(crate) fn print(&self) {
+        println!("Compared with the previous results:");
+        println!(
+            "  passed:      {} -> {} ({:+})",
+            self.before.passed,
+            self.after.passed,
+            self.after.passed as i64 - self.before.passed as i64
+        );
+        println!(
+            "  conformance: {:.2}% -> {:.2}% ({:+.2}%)",
+            self.before.conformance(),
+            self.after.conformance(),
+            self.after.conformance() - self.before.conformance()
+        );
+
+        if !self.fixes.is_empty() {
+            println!();
+            println!("Fixed tests ({}):", self.fixes.len());
+            for path in &self.fixes {
+                println!("  {}", path);
+            }
+        }
+        if !self.regressions.is_empty() {
+            println!();
+            println!("Regressed tests ({}):", self.regressions.len());
+            for path in &self.regressions {
+                println!("  {}", path);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Execution of the tests against the engine.
+
+use crate::{
+    read::{Harness, Test, TestFlag, TestSuite},
+    results::{Outcome, SuiteResult, TestResult},
+};
+use boa::{
+    builtins::{
+        console::log,
+        function::make_builtin_fn,
+        object::{INSTANCE_PROTOTYPE, PROTOTYPE},
+        value::{same_value, ResultValue, Value},
+    },
+    parse, Executable, Interpreter, Realm,
+};
+use std::panic::{self, AssertUnwindSafe};
+
+/// The features the engine doesn't implement; the tests using them are ignored.
+const IGNORED_FEATURES: &[&str] = &[
+    "ArrayBuffer",
+    "Atomics",
+    "DataView",
+    "Proxy",
+    "Reflect",
+    "SharedArrayBuffer",
+    "TypedArray",
+    "async-functions",
+    "async-iteration",
+    "class",
+    "class-fields-private",
+    "class-fields-public",
+    "class-methods-private",
+    "class-static-fields-private",
+    "class-static-fields-public",
+    "class-static-methods-private",
+    "cross-realm",
+    "dynamic-import",
+    "generators",
+    "import.meta",
+    "top-level-await",
+];
+
+/// The flags of the tests the runner can't run.
+const IGNORED_FLAGS: &[TestFlag] = &[TestFlag::Module, TestFlag::Async, TestFlag::CanBlockIsTrue];
+
+impl TestSuite {
+    /// Runs the tests of the suite, and of its subdirectories.
+    pub(crate) fn run(&self, harness: &Harness, verbose: bool) -> SuiteResult {
+        let suites = self
+            .suites
+            .iter()
+            .map(|suite| suite.run(harness, verbose))
+            .collect();
+        let tests = self
+            .tests
+            .iter()
+            .map(|test| test.run(harness, verbose))
+            .collect();
+
+        SuiteResult::new(self.name.clone(), suites, tests)
+    }
+}
+
+impl Test {
+    /// Runs the test, and catches the panics of the engine.
+    pub(crate) fn run(&self, harness: &Harness, verbose: bool) -> TestResult {
+        let outcome = if self.is_ignored() {
+            Outcome::Ignored
+        } else {
+            match panic::catch_unwind(AssertUnwindSafe(|| self.execute(harness))) {
+                Ok(Ok(())) => Outcome::Passed,
+                Ok(Err(error)) => {
+                    if verbose {
+                        println!("{}: {}", self.path.display(), error);
+                        println!("    {}", self.description.trim());
+                    }
+                    Outcome::Failed
+                }
+                Err(_) => {
+                    if verbose {
+                        println!("{}: the engine panicked", self.path.display());
+                    }
+                    Outcome::Panicked
+                }
+            }
+        };
+
+        TestResult {
+            name: self.name.clone(),
+            outcome,
+            features: self.features.clone(),
+        }
+    }
+
+    /// Checks if the test uses flags or features the engine doesn't support.
+    fn is_ignored(&self) -> bool {
+        self.flags.iter().any(|flag| IGNORED_FLAGS.contains(flag))
+            || self
+                .features
+                .iter()
+                .any(|feature| IGNORED_FEATURES.contains(&feature.as_str()))
+    }
+
+    /// Runs the test in a new realm, and checks that it throws the expected error, if any.
+    ///
+    /// The tests flagged `onlyStrict` run in strict mode, and the others only in non-strict mode.
+    fn execute(&self, harness: &Harness) -> Result<(), String> {
+        let mut engine = new_engine();
+        if !self.has_flag(TestFlag::Raw) {
+            run(&mut engine, &harness.assert, "assert.js")?;
+            run(&mut engine, &harness.sta, "sta.js")?;
+            for include in &self.includes {
+                let source = harness
+                    .includes
+                    .get(include)
+                    .ok_or_else(|| format!("the harness file {} is missing", include))?;
+                run(&mut engine, source, include)?;
+            }
+        }
+
+        let source = if self.has_flag(TestFlag::OnlyStrict) {
+            format!("\"use strict\";\n{}", self.content)
+        } else {
+            self.content.clone()
+        };
+        let script = parse(source.as_bytes(), engine.optimizer_options());
+
+        match (script, &self.negative) {
+            (Err(_), Some(negative)) if negative.phase.is_parse() => Ok(()),
+            (Err(error), _) => Err(format!("unexpected syntax error: {}", error)),
+            (Ok(_), Some(negative)) if negative.phase.is_parse() => {
+                Err(format!("expected a {} while parsing", negative.error_type))
+            }
+            (Ok(script), negative) => {
+                let result = script
+                    .run(&mut engine)
+                    .and_then(|_| engine.run_jobs().map(|_| Value::undefined()));
+                match (result, negative) {
+                    (Ok(_), None) => Ok(()),
+                    (Ok(_), Some(negative)) => {
+                        Err(format!("expected a {} to be thrown", negative.error_type))
+                    }
+                    (Err(error), None) => Err(format!("uncaught {}", describe(&error))),
+                    (Err(error), Some(negative)) => {
+                        // The constructor is looked up by its binding, as the declarations of
+                        // the harness aren't properties of the global object.
+                        let constructor =
+                            parse(negative.error_type.as_bytes(), engine.optimizer_options())
+                                .ok()
+                                .and_then(|name| name.run(&mut engine).ok())
+                                .unwrap_or_default();
+                        if is_instance_of(&error, &constructor) {
+                            Ok(())
+                        } else {
+                            Err(format!(
+                                "expected a {}, but got {}",
+                                negative.error_type,
+                                describe(&error)
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Creates a new engine, with the `print` function and the `$262` object of the host.
+///
+/// More information:
+///  - [test262 documentation][doc]
+///
+/// [doc]: https://github.com/tc39/test262/blob/master/INTERPRETING.md#host-defined-functions
+fn new_engine() -> Interpreter {
+    let realm = Realm::create().register_global_func("print", log);
+    // The tests get the global object from `$262.global`.
+    realm.init_builtins();
+    let global = realm.global_obj.clone();
+
+    let host = Value::new_object(Some(&global));
+    host.set_field("global", global.clone());
+    make_builtin_fn(eval_script, "evalScript", &host, 1);
+    global.set_field("$262", host);
+
+    Interpreter::new(realm)
+}
+
+/// `$262.evalScript( source )`
+///
+/// Parses and runs a script in the realm of the test, and returns its completion value.
+fn eval_script(_: &mut Value, args: &[Value], ctx: &mut Interpreter) -> ResultValue {
+    let source = args.first().cloned().unwrap_or_default().to_string();
+    match parse(source.as_bytes(), ctx.optimizer_options()) {
+        Ok(script) => script.run(ctx),
+        Err(e) => {
+            // The engine has no `SyntaxError` constructor yet.
+            let error = Value::new_object(None);
+            error.set_field("name", "SyntaxError");
+            error.set_field("message", e.to_string());
+            Err(error)
+        }
+    }
+}
+
+/// Parses and runs a file of the harness.
+fn run(engine: &mut Interpreter, source: &str, name: &str) -> Result<(), String> {
+    let script = parse(source.as_bytes(), engine.optimizer_options())
+        .map_err(|error| format!("could not parse {}: {}", name, error))?;
+    script
+        .run(engine)
+        .map_err(|error| format!("could not run {}: {}", name, describe(&error)))?;
+    Ok(())
+}
+
+/// Checks if the prototype of a constructor is in the prototype chain of a value.
+fn is_instance_of(value: &Value, constructor: &Value) -> bool {
+    if !value.is_object() || !constructor.is_function() {
+        return false;
+    }
+    let prototype = constructor.get_field(PROTOTYPE);
+    let mut object = value.get_internal_slot(INSTANCE_PROTOTYPE);
+    while object.is_object() {
+        if same_value(&object, &prototype) {
+            return true;
+        }
+        object = object.get_internal_slot(INSTANCE_PROTOTYPE);
+    }
+    false
+}
+
+/// Gets the name of the constructor of an error.
+fn error_name(error: &Value) -> String {
+    let constructor = error.get_field("constructor");
+    if constructor.is_function() {
+        constructor.get_field("name").to_string()
+    } else {
+        error.get_field("name").to_string()
+    }
+}
+
+/// Describes a thrown value, for the failure messages.
+fn describe(error: &Value) -> String {
+    if error.is_object() {
+        format!("{}: {}", error_name(error), error.get_field("message"))
+    } else {
+        error.to_string()
+    }
+}
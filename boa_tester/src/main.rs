@@ -0,0 +1,165 @@
+//! Runner of the ECMAScript conformance test suite, [test262][test262], for Boa.
+//!
+//! The tests are read from a checkout of the test262 repository, run in a new realm each, and a
+//! report of how many of them pass is printed, by directory and by language feature.
+//!
+//! [test262]: https://github.com/tc39/test262
+
+#![deny(
+    unused_qualifications,
+    clippy::all,
+    unused_import_braces,
+    unused_lifetimes,
+    unreachable_pub,
+    trivial_numeric_casts,
+    missing_debug_implementations,
+    missing_copy_implementations,
+    deprecated_in_future,
+    non_ascii_idents,
+    rust_2018_compatibility,
+    rust_2018_idioms,
+    future_incompatible,
+    nonstandard_style
+)]
+#![warn(clippy::perf, clippy::single_match_else, clippy::dbg_macro)]
+#![allow(
+    clippy::missing_inline_in_public_items,
+    clippy::cognitive_complexity,
+    clippy::must_use_candidate,
+    clippy::missing_errors_doc,
+    clippy::as_conversions
+)]
+
+mod compare;
+mod exec;
+mod read;
+mod results;
+
+use compare::Comparison;
+use read::{read_harness, read_suite, read_test, TestSuite};
+use results::SuiteResult;
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    panic,
+    path::{Path, PathBuf},
+    process, thread,
+};
+use structopt::StructOpt;
+
+/// The size of the stack of the thread running the tests, as some of them recurse deeply.
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// CLI configuration for the test262 runner.
+#[derive(Debug, StructOpt)]
+#[structopt(name = "boa_tester", about = "Runs the test262 suite against Boa")]
+struct Cli {
+    /// The path of the checkout of the test262 repository.
+    #[structopt(long, parse(from_os_str), default_value = "test262")]
+    test262_path: PathBuf,
+
+    /// The directory or the file of the tests to run, relative to the test262 repository.
+    #[structopt(name = "SUITE", parse(from_os_str), default_value = "test")]
+    suite: PathBuf,
+
+    /// The depth of the directories listed in the report.
+    #[structopt(long, default_value = "2")]
+    depth: usize,
+
+    /// Prints the path and the error of each test that fails.
+    #[structopt(long, short = "v")]
+    verbose: bool,
+
+    /// Saves the results in a JSON snapshot, usually `test262_results.json`.
+    #[structopt(long, short = "o", parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// Compares the results with a JSON snapshot of a previous run, and fails if any test which
+    /// passed in that run doesn't pass anymore.
+    #[structopt(long, short = "c", parse(from_os_str))]
+    compare: Option<PathBuf>,
+}
+
+fn main() {
+    let cli = Cli::from_args();
+
+    // The panics of the engine are reported as failed tests.
+    panic::set_hook(Box::new(|_| {}));
+
+    let runner = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || run(&cli))
+        .expect("could not start the test runner");
+    match runner.join().expect("the test runner panicked") {
+        Ok(true) => {}
+        // The regressions have been printed with the comparison.
+        Ok(false) => process::exit(1),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// Reads the harness and the tests, runs them and prints the report.
+///
+/// Returns `false` if some tests regressed since the results they are compared with.
+fn run(cli: &Cli) -> io::Result<bool> {
+    let harness = read_harness(&cli.test262_path)?;
+
+    // The root of the results is named after its path, so that the paths of the tests in the
+    // snapshots don't depend on the directory which was run.
+    let path = cli.test262_path.join(&cli.suite);
+    let suite = if path.is_dir() {
+        TestSuite {
+            name: relative_path(&cli.suite),
+            ..read_suite(&path)?
+        }
+    } else {
+        TestSuite {
+            name: relative_path(cli.suite.parent().unwrap_or_else(|| Path::new(""))),
+            suites: Vec::new(),
+            tests: vec![read_test(&path)?],
+        }
+    };
+
+    let results = suite.run(&harness, cli.verbose);
+    if cli.verbose {
+        println!();
+    }
+    results.print_report(cli.depth);
+
+    if let Some(ref path) = cli.output {
+        write_results(path, &results)?;
+    }
+
+    if let Some(ref path) = cli.compare {
+        let previous = read_results(path)?;
+        let comparison = Comparison::new(&previous, &results);
+        println!();
+        comparison.print();
+        return Ok(comparison.regressions.is_empty());
+    }
+    Ok(true)
+}
+
+/// Formats a path relative to the test262 repository, with `/` as the separator.
+fn relative_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Saves the results in a JSON snapshot.
+fn write_results(path: &Path, results: &SuiteResult) -> io::Result<()> {
+    let file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(file, results)?;
+    Ok(())
+}
+
+/// Reads the results saved in a JSON snapshot.
+fn read_results(path: &Path) -> io::Result<SuiteResult> {
+    let file = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(file)?)
+}
@@ -0,0 +1,244 @@
+//! Reading of the tests and of the harness of the test262 suite.
+
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The files of the harness, included before the tests.
+#[derive(Debug, Clone)]
+pub(crate) struct Harness {
+    /// `assert.js`, included before every test which isn't raw.
+    pub(crate) assert: String,
+    /// `sta.js`, included before every test which isn't raw.
+    pub(crate) sta: String,
+    /// The other files of the harness, included by the tests which ask for them, by file name.
+    pub(crate) includes: HashMap<String, String>,
+}
+
+/// A directory of tests.
+#[derive(Debug, Clone)]
+pub(crate) struct TestSuite {
+    /// The name of the directory.
+    pub(crate) name: String,
+    /// The subdirectories.
+    pub(crate) suites: Vec<TestSuite>,
+    /// The tests in the directory.
+    pub(crate) tests: Vec<Test>,
+}
+
+/// A test, with the metadata of its frontmatter.
+#[derive(Debug, Clone)]
+pub(crate) struct Test {
+    /// The name of the file.
+    pub(crate) name: String,
+    /// The path of the file.
+    pub(crate) path: PathBuf,
+    /// The description of the test.
+    pub(crate) description: String,
+    /// The harness files to include before the test.
+    pub(crate) includes: Vec<String>,
+    /// The flags, which change how the test is run.
+    pub(crate) flags: Vec<TestFlag>,
+    /// The error the test must throw, if it is a negative test.
+    pub(crate) negative: Option<Negative>,
+    /// The language features used by the test.
+    pub(crate) features: Vec<String>,
+    /// The source code of the test.
+    pub(crate) content: String,
+}
+
+impl Test {
+    /// Checks if the test has the given flag.
+    pub(crate) fn has_flag(&self, flag: TestFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+}
+
+/// The YAML frontmatter of a test, between `/*---` and `---*/`.
+#[derive(Debug, Clone, Deserialize)]
+struct MetaData {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    includes: Vec<String>,
+    #[serde(default)]
+    flags: Vec<TestFlag>,
+    negative: Option<Negative>,
+    #[serde(default)]
+    features: Vec<String>,
+}
+
+/// A flag of a test.
+///
+/// More information:
+///  - [test262 documentation][doc]
+///
+/// [doc]: https://github.com/tc39/test262/blob/master/INTERPRETING.md#flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum TestFlag {
+    /// The test must only be run in strict mode.
+    OnlyStrict,
+    /// The test must only be run in non-strict mode.
+    NoStrict,
+    /// The test is a module.
+    Module,
+    /// The test must be run as is, without the harness.
+    Raw,
+    /// The test is asynchronous, and reports its result through `$DONE`.
+    Async,
+    /// The test was generated from a template.
+    Generated,
+    /// The test must be run in an agent which can't block.
+    #[serde(rename = "CanBlockIsFalse")]
+    CanBlockIsFalse,
+    /// The test must be run in an agent which can block.
+    #[serde(rename = "CanBlockIsTrue")]
+    CanBlockIsTrue,
+    /// The test may pass or fail from one run to another.
+    #[serde(rename = "non-deterministic")]
+    NonDeterministic,
+    /// A flag added to the suite after this runner was written.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The expected error of a negative test.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Negative {
+    /// When the error must be thrown.
+    pub(crate) phase: Phase,
+    /// The name of the constructor of the error.
+    #[serde(rename = "type")]
+    pub(crate) error_type: String,
+}
+
+/// The phase in which the error of a negative test is thrown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Phase {
+    /// While parsing the test.
+    Parse,
+    /// While parsing the test, in older versions of the suite.
+    Early,
+    /// While resolving the imports of a module.
+    Resolution,
+    /// While running the test.
+    Runtime,
+}
+
+impl Phase {
+    /// Checks if the error is thrown before the test runs.
+    pub(crate) fn is_parse(self) -> bool {
+        self == Self::Parse || self == Self::Early
+    }
+}
+
+/// Reads the files of the harness, in the `harness` directory of the test262 repository.
+pub(crate) fn read_harness(test262_path: &Path) -> io::Result<Harness> {
+    let mut includes = HashMap::new();
+    for entry in fs::read_dir(test262_path.join("harness"))? {
+        let path = entry?.path();
+        if is_js_file(&path) {
+            let name = file_name(&path);
+            includes.insert(name, fs::read_to_string(&path)?);
+        }
+    }
+
+    let mut take = |name: &str| {
+        includes.remove(name).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("the harness file {} is missing", name),
+            )
+        })
+    };
+    let assert = take("assert.js")?;
+    let sta = take("sta.js")?;
+
+    Ok(Harness {
+        assert,
+        sta,
+        includes,
+    })
+}
+
+/// Reads a directory of tests, recursively.
+pub(crate) fn read_suite(path: &Path) -> io::Result<TestSuite> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    let mut suites = Vec::new();
+    let mut tests = Vec::new();
+    for entry in entries {
+        if entry.is_dir() {
+            suites.push(read_suite(&entry)?);
+        } else if is_test_file(&entry) {
+            tests.push(read_test(&entry)?);
+        }
+    }
+
+    Ok(TestSuite {
+        name: file_name(path),
+        suites,
+        tests,
+    })
+}
+
+/// Reads a test file, and its frontmatter.
+pub(crate) fn read_test(path: &Path) -> io::Result<Test> {
+    let content = fs::read_to_string(path)?;
+    let metadata = read_metadata(&content).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid frontmatter in {}: {}", path.display(), err),
+        )
+    })?;
+
+    Ok(Test {
+        name: file_name(path),
+        path: path.to_owned(),
+        description: metadata.description,
+        includes: metadata.includes,
+        flags: metadata.flags,
+        negative: metadata.negative,
+        features: metadata.features,
+        content,
+    })
+}
+
+/// Parses the YAML frontmatter of a test.
+fn read_metadata(content: &str) -> Result<MetaData, String> {
+    let start = content
+        .find("/*---")
+        .ok_or_else(|| "the frontmatter is missing".to_string())?;
+    let end = content[start..]
+        .find("---*/")
+        .ok_or_else(|| "the frontmatter is not closed".to_string())?;
+    let yaml = &content[start + "/*---".len()..start + end];
+
+    serde_yaml::from_str(yaml).map_err(|err| err.to_string())
+}
+
+/// Checks if a file is a test, and not a fixture imported by the module tests.
+fn is_test_file(path: &Path) -> bool {
+    is_js_file(path) && !file_name(path).contains("_FIXTURE")
+}
+
+/// Checks if a file has the `.js` extension.
+fn is_js_file(path: &Path) -> bool {
+    path.extension().and_then(OsStr::to_str) == Some("js")
+}
+
+/// Gets the file name of a path, as a string.
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
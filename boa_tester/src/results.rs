@@ -0,0 +1,224 @@
+//! The results of the tests, and the conformance report.
+
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::AddAssign};
+
+/// The outcome of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Outcome {
+    /// The test passed.
+    Passed,
+    /// The test failed.
+    Failed,
+    /// The test was not run, as it uses features the engine doesn't support.
+    Ignored,
+    /// The engine panicked while running the test.
+    Panicked,
+}
+
+/// The result of a test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct TestResult {
+    /// The name of the test file.
+    pub(crate) name: String,
+    /// The outcome of the test.
+    pub(crate) outcome: Outcome,
+    /// The language features used by the test.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) features: Vec<String>,
+}
+
+/// The number of tests with each outcome.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Statistics {
+    /// The number of tests.
+    pub(crate) total: usize,
+    /// The number of tests which passed.
+    pub(crate) passed: usize,
+    /// The number of tests which were not run.
+    pub(crate) ignored: usize,
+    /// The number of tests which made the engine panic, which are also failures.
+    pub(crate) panicked: usize,
+}
+
+impl Statistics {
+    /// Counts a test with the given outcome.
+    pub(crate) fn count(&mut self, outcome: Outcome) {
+        self.total += 1;
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Ignored => self.ignored += 1,
+            Outcome::Panicked => self.panicked += 1,
+            Outcome::Failed => {}
+        }
+    }
+
+    /// The number of tests which failed, including the panics.
+    pub(crate) fn failed(&self) -> usize {
+        self.total - self.passed - self.ignored
+    }
+
+    /// The percentage of the tests which passed, out of the ones which were run.
+    pub(crate) fn conformance(&self) -> f64 {
+        let run = self.total - self.ignored;
+        if run == 0 {
+            0.0
+        } else {
+            self.passed as f64 * 100.0 / run as f64
+        }
+    }
+}
+
+impl AddAssign for Statistics {
+    fn add_assign(&mut self, other: Self) {
+        self.total += other.total;
+        self.passed += other.passed;
+        self.ignored += other.ignored;
+        self.panicked += other.panicked;
+    }
+}
+
+/// The results of a directory of tests.
+///
+/// The name of the root directory is its path relative to the test262 repository.
+///
+/// They are saved as JSON snapshots, to compare the results of two versions of the engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SuiteResult {
+    /// The name of the directory.
+    pub(crate) name: String,
+    /// The statistics of the tests of the directory, and of its subdirectories.
+    pub(crate) stats: Statistics,
+    /// The results of the subdirectories.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) suites: Vec<SuiteResult>,
+    /// The results of the tests in the directory.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tests: Vec<TestResult>,
+}
+
+impl SuiteResult {
+    /// Creates the results of a directory, and sums their statistics.
+    pub(crate) fn new(name: String, suites: Vec<SuiteResult>, tests: Vec<TestResult>) -> Self {
+        let mut stats = Statistics::default();
+        for suite in &suites {
+            stats += suite.stats;
+        }
+        for test in &tests {
+            stats.count(test.outcome);
+        }
+
+        Self {
+            name,
+            stats,
+            suites,
+            tests,
+        }
+    }
+
+    /// Gets the statistics of the tests using each language feature.
+    pub(crate) fn features(&self) -> BTreeMap<String, Statistics> {
+        let mut features = BTreeMap::new();
+        self.collect_features(&mut features);
+        features
+    }
+
+    /// Gets the outcome of each test, by path relative to the test262 repository.
+    pub(crate) fn outcomes(&self) -> BTreeMap<String, Outcome> {
+        let mut outcomes = BTreeMap::new();
+        self.collect_outcomes(&format!("{}/", self.name), &mut outcomes);
+        outcomes
+    }
+
+    fn collect_outcomes(&self, parent: &str, outcomes: &mut BTreeMap<String, Outcome>) {
+        for suite in &self.suites {
+            suite.collect_outcomes(&format!("{}{}/", parent, suite.name), outcomes);
+        }
+        for test in &self.tests {
+            outcomes.insert(format!("{}{}", parent, test.name), test.outcome);
+        }
+    }
+
+    fn collect_features(&self, features: &mut BTreeMap<String, Statistics>) {
+        for suite in &self.suites {
+            suite.collect_features(features);
+        }
+        for test in &self.tests {
+            for feature in &test.features {
+                features
+                    .entry(feature.clone())
+                    .or_default()
+                    .count(test.outcome);
+            }
+        }
+    }
+
+    /// Prints the report of the results: the totals, then the statistics of the directories up
+    /// to the given depth, and of the features.
+    pub(crate) fn print_report(&self, depth: usize) {
+        println!("Results of {}:", self.name);
+        println!("  total:    {}", self.stats.total);
+        println!("  passed:   {}", self.stats.passed);
+        println!("  ignored:  {}", self.stats.ignored);
+        println!(
+            "  failed:   {} ({} panics)",
+            self.stats.failed(),
+            self.stats.panicked
+        );
+        println!("  conformance: {:.2}%", self.stats.conformance());
+
+        println!();
+        println!("By directory:");
+        print_header();
+        for suite in &self.suites {
+            suite.print_directories("", depth);
+        }
+
+        let features = self.features();
+        if !features.is_empty() {
+            println!();
+            println!("By feature:");
+            print_header();
+            for (feature, stats) in &features {
+                print_row(feature, stats);
+            }
+        }
+    }
+
+    fn print_directories(&self, parent: &str, depth: usize) {
+        if depth == 0 {
+            return;
+        }
+        let path = if parent.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}/{}", parent, self.name)
+        };
+        print_row(&path, &self.stats);
+        for suite in &self.suites {
+            suite.print_directories(&path, depth - 1);
+        }
+    }
+}
+
+/// Prints the header of the tables of the report.
+fn print_header() {
+    println!(
+        "  {:<48} {:>7} {:>7} {:>7} {:>7} {:>8}",
+        "", "total", "passed", "ignored", "failed", "conform."
+    );
+}
+
+/// Prints a row of the tables of the report.
+fn print_row(name: &str, stats: &Statistics) {
+    println!(
+        "  {:<48} {:>7} {:>7} {:>7} {:>7} {:>7.2}%",
+        name,
+        stats.total,
+        stats.passed,
+        stats.ignored,
+        stats.failed(),
+        stats.conformance()
+    );
+}
@@ -1,24 +1,67 @@
-use boa::{Executable, Interpreter, Lexer, Parser, Realm};
+//! The WebAssembly interface of Boa, which runs the engine in the browser.
+//!
+//! Build it with `wasm-pack build boa_wasm`; the functions below are then exported to
+//! JavaScript, and their errors are thrown as strings.
+
+use boa::{
+    parse,
+    syntax::{
+        ast::{estree::to_estree, token::Token},
+        parser::error::ParseError,
+    },
+    Executable, Interpreter, Lexer, Parser, Realm, ScriptError,
+};
 use wasm_bindgen::prelude::*;
 
+/// Runs a script in a new realm, and returns its completion value as a string.
 #[wasm_bindgen]
 pub fn evaluate(src: &str) -> Result<String, JsValue> {
-    let mut lexer = Lexer::new(src);
-    lexer
-        .lex()
-        .map_err(|e| JsValue::from(format!("Syntax Error: {}", e)))?;
-
-    let tokens = lexer.tokens;
-    let expr = Parser::new(&tokens)
-        .parse_all()
-        .map_err(|e| JsValue::from(format!("Parsing Error: {}", e)))?;
-
     // Setup executor
     let realm = Realm::create();
     let mut engine = Interpreter::new(realm);
 
+    let expr = parse(src.as_bytes(), engine.optimizer_options()).map_err(|e| match e {
+        ScriptError::Parse(e) => parse_error(e),
+        ScriptError::Early(e) => JsValue::from(format!("Syntax Error: {}", e)),
+    })?;
+
     // Setup executor
     expr.run(&mut engine)
         .map_err(|e| JsValue::from(format!("Error: {}", e)))
         .map(|v| v.to_string())
 }
+
+/// Lexes a script, and returns its tokens as JSON, comments included.
+#[wasm_bindgen]
+pub fn dump_tokens(src: &str) -> Result<String, JsValue> {
+    let tokens = lex(src)?;
+    serde_json::to_string(&tokens).map_err(|e| JsValue::from(e.to_string()))
+}
+
+/// Parses a script, and returns its AST as JSON, in the ESTree format used by most JavaScript
+/// tools.
+#[wasm_bindgen]
+pub fn dump_ast(src: &str) -> Result<String, JsValue> {
+    let ast = Parser::from_lexer(Lexer::new(src.as_bytes()))
+        .parse_all()
+        .map_err(parse_error)?;
+    serde_json::to_string(&to_estree(&ast)).map_err(|e| JsValue::from(e.to_string()))
+}
+
+/// Lexes a script, keeping its comments.
+fn lex(src: &str) -> Result<Vec<Token>, JsValue> {
+    let mut lexer = Lexer::new(src.as_bytes());
+    lexer.set_preserve_comments(true);
+    lexer
+        .lex()
+        .map_err(|e| JsValue::from(format!("Syntax Error: {}", e)))?;
+    Ok(lexer.tokens)
+}
+
+/// Formats a parsing error.
+fn parse_error(e: ParseError) -> JsValue {
+    match e {
+        ParseError::Lex { err } => JsValue::from(format!("Syntax Error: {}", err)),
+        e => JsValue::from(format!("Parsing Error: {}", e)),
+    }
+}
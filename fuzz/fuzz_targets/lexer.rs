@@ -0,0 +1,10 @@
+//! Lexes arbitrary input: the lexer must return an error instead of panicking.
+
+#![no_main]
+
+use boa::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Lexer::new(data).lex();
+});
@@ -0,0 +1,11 @@
+//! Parses arbitrary input: the parser must return an error instead of panicking or overflowing
+//! the stack.
+
+#![no_main]
+
+use boa::{Lexer, Parser};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = Parser::from_lexer(Lexer::new(data)).parse_all();
+});